@@ -11,13 +11,16 @@ use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartitionWitness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::util::ceil_div_usize;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
 use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+use plonky2_u32::serialization::{ReadU32, WriteU32};
 use plonky2_u32::witness::GeneratedValuesU32;
 
 use crate::gadgets::biguint::{
     BigUintTarget, CircuitBuilderBiguint, GeneratedValuesBigUint, WitnessBigUint,
 };
+use crate::serialization::{ReadBigUint, ReadNonNative, WriteBigUint, WriteNonNative};
 
 #[derive(Clone, Debug)]
 pub struct NonNativeTarget<FF: Field> {
@@ -446,7 +449,8 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderNonNative<F, D>
 }
 
 #[derive(Debug)]
-struct NonNativeAdditionGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
+pub struct NonNativeAdditionGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField>
+{
     a: NonNativeTarget<FF>,
     b: NonNativeTarget<FF>,
     sum: NonNativeTarget<FF>,
@@ -484,11 +488,35 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         out_buffer.set_biguint_target(&self.sum.value, &sum_reduced);
         out_buffer.set_bool_target(self.overflow, overflow);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.sum)?;
+        dst.write_target_bool(self.overflow)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let sum = src.read_target_nonnative()?;
+        let overflow = src.read_target_bool()?;
+        Ok(Self {
+            a,
+            b,
+            sum,
+            overflow,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[derive(Debug)]
-struct NonNativeMultipleAddsGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField>
-{
+pub struct NonNativeMultipleAddsGenerator<
+    F: RichField + Extendable<D>,
+    const D: usize,
+    FF: PrimeField,
+> {
     summands: Vec<NonNativeTarget<FF>>,
     sum: NonNativeTarget<FF>,
     overflow: U32Target,
@@ -529,10 +557,34 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         out_buffer.set_biguint_target(&self.sum.value, &sum_reduced);
         out_buffer.set_u32_target(self.overflow, overflow);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.summands.len())?;
+        for x in &self.summands {
+            dst.write_target_nonnative(x)?;
+        }
+        dst.write_target_nonnative(&self.sum)?;
+        dst.write_target_u32(self.overflow)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let len = src.read_usize()?;
+        let summands = (0..len)
+            .map(|_| src.read_target_nonnative())
+            .collect::<IoResult<Vec<_>>>()?;
+        let sum = src.read_target_nonnative()?;
+        let overflow = src.read_target_u32()?;
+        Ok(Self {
+            summands,
+            sum,
+            overflow,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[derive(Debug)]
-struct NonNativeSubtractionGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
+pub struct NonNativeSubtractionGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
     a: NonNativeTarget<FF>,
     b: NonNativeTarget<FF>,
     diff: NonNativeTarget<FF>,
@@ -570,10 +622,32 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         out_buffer.set_biguint_target(&self.diff.value, &diff_biguint);
         out_buffer.set_bool_target(self.overflow, overflow);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.diff)?;
+        dst.write_target_bool(self.overflow)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let diff = src.read_target_nonnative()?;
+        let overflow = src.read_target_bool()?;
+        Ok(Self {
+            a,
+            b,
+            diff,
+            overflow,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[derive(Debug)]
-struct NonNativeMultiplicationGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
+pub struct NonNativeMultiplicationGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field>
+{
     a: NonNativeTarget<FF>,
     b: NonNativeTarget<FF>,
     prod: NonNativeTarget<FF>,
@@ -609,10 +683,31 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         out_buffer.set_biguint_target(&self.prod.value, &prod_reduced);
         out_buffer.set_biguint_target(&self.overflow, &overflow_biguint);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.prod)?;
+        dst.write_target_biguint(&self.overflow)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let prod = src.read_target_nonnative()?;
+        let overflow = src.read_target_biguint()?;
+        Ok(Self {
+            a,
+            b,
+            prod,
+            overflow,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[derive(Debug)]
-struct NonNativeInverseGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
+pub struct NonNativeInverseGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
     x: NonNativeTarget<FF>,
     inv: BigUintTarget,
     div: BigUintTarget,
@@ -639,6 +734,24 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         out_buffer.set_biguint_target(&self.div, &div);
         out_buffer.set_biguint_target(&self.inv, &inv_biguint);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.x)?;
+        dst.write_target_biguint(&self.inv)?;
+        dst.write_target_biguint(&self.div)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let x = src.read_target_nonnative()?;
+        let inv = src.read_target_biguint()?;
+        let div = src.read_target_biguint()?;
+        Ok(Self {
+            x,
+            inv,
+            div,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]
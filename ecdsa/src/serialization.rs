@@ -0,0 +1,210 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::secp256k1_base::Secp256K1Base;
+use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2::field::types::Field;
+use plonky2::gadgets::arithmetic::EqualityGenerator;
+use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+use plonky2::gadgets::range_check::LowHighGenerator;
+use plonky2::gadgets::split_base::BaseSumGenerator;
+use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+use plonky2::gates::arithmetic_base::{ArithmeticBaseGenerator, ArithmeticGate};
+use plonky2::gates::arithmetic_extension::{ArithmeticExtensionGate, ArithmeticExtensionGenerator};
+use plonky2::gates::base_sum::{BaseSplitGenerator, BaseSumGate};
+use plonky2::gates::constant::ConstantGate;
+use plonky2::gates::exponentiation::{ExponentiationGate, ExponentiationGenerator};
+use plonky2::gates::high_degree_interpolation::{
+    HighDegreeInterpolationGate, HighDegreeInterpolationGenerator,
+};
+use plonky2::gates::low_degree_interpolation::{
+    LowDegreeInterpolationGate, LowDegreeInterpolationGenerator,
+};
+use plonky2::gates::multiplication_extension::{MulExtensionGate, MulExtensionGenerator};
+use plonky2::gates::noop::NoopGate;
+use plonky2::gates::poseidon::{PoseidonGate, PoseidonGenerator};
+use plonky2::gates::poseidon_mds::{PoseidonMdsGate, PoseidonMdsGenerator};
+use plonky2::gates::public_input::PublicInputGate;
+use plonky2::gates::random_access::{RandomAccessGate, RandomAccessGenerator};
+use plonky2::gates::reducing::{ReducingGate, ReducingGenerator};
+use plonky2::gates::reducing_extension::{ReducingExtensionGate, ReducingExtensionGenerator};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::generator::{
+    ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+    SimpleGeneratorAdapter,
+};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+use plonky2::util::serialization::{
+    Buffer, GateSerializer, IoResult, Read, WitnessGeneratorSerializer, Write,
+};
+use plonky2::{impl_gate_serializer, impl_generator_serializer};
+use plonky2_u32::gadgets::arithmetic_u32::SplitToU32Generator;
+use plonky2_u32::gates::add_many_u32::{U32AddManyGate, U32AddManyGenerator};
+use plonky2_u32::gates::arithmetic_u32::{U32ArithmeticGate, U32ArithmeticGenerator};
+use plonky2_u32::gates::comparison::{ComparisonGate, ComparisonGenerator};
+use plonky2_u32::gates::range_check_u32::{U32RangeCheckGate, U32RangeCheckGenerator};
+use plonky2_u32::gates::subtraction_u32::{U32SubtractionGate, U32SubtractionGenerator};
+use plonky2_u32::serialization::{ReadU32, WriteU32};
+
+use crate::gadgets::biguint::{BigUintDivRemGenerator, BigUintTarget};
+use crate::gadgets::glv::GLVDecompositionGenerator;
+use crate::gadgets::nonnative::{
+    NonNativeAdditionGenerator, NonNativeInverseGenerator, NonNativeMultipleAddsGenerator,
+    NonNativeMultiplicationGenerator, NonNativeSubtractionGenerator, NonNativeTarget,
+};
+
+pub trait WriteBigUint {
+    fn write_target_biguint(&mut self, x: &BigUintTarget) -> IoResult<()>;
+}
+
+impl WriteBigUint for Vec<u8> {
+    #[inline]
+    fn write_target_biguint(&mut self, x: &BigUintTarget) -> IoResult<()> {
+        self.write_usize(x.num_limbs())?;
+        for &limb in &x.limbs {
+            self.write_target_u32(limb)?;
+        }
+        Ok(())
+    }
+}
+
+pub trait ReadBigUint {
+    fn read_target_biguint(&mut self) -> IoResult<BigUintTarget>;
+}
+
+impl ReadBigUint for Buffer {
+    #[inline]
+    fn read_target_biguint(&mut self) -> IoResult<BigUintTarget> {
+        let length = self.read_usize()?;
+        let limbs = (0..length)
+            .map(|_| self.read_target_u32())
+            .collect::<IoResult<Vec<_>>>()?;
+        Ok(BigUintTarget { limbs })
+    }
+}
+
+pub trait WriteNonNative {
+    fn write_target_nonnative<FF: Field>(&mut self, x: &NonNativeTarget<FF>) -> IoResult<()>;
+}
+
+impl WriteNonNative for Vec<u8> {
+    #[inline]
+    fn write_target_nonnative<FF: Field>(&mut self, x: &NonNativeTarget<FF>) -> IoResult<()> {
+        self.write_target_biguint(&x.value)
+    }
+}
+
+pub trait ReadNonNative {
+    fn read_target_nonnative<FF: Field>(&mut self) -> IoResult<NonNativeTarget<FF>>;
+}
+
+impl ReadNonNative for Buffer {
+    #[inline]
+    fn read_target_nonnative<FF: Field>(&mut self) -> IoResult<NonNativeTarget<FF>> {
+        let value = self.read_target_biguint()?;
+        Ok(NonNativeTarget {
+            value,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// A [`GateSerializer`] supporting the gates used by the ECDSA circuits, which are those of
+/// `plonky2` and `plonky2_u32`.
+pub struct EcdsaGateSerializer;
+
+impl<F: RichField + Extendable<D>, const D: usize> GateSerializer<F, D> for EcdsaGateSerializer {
+    impl_gate_serializer! {
+        ArithmeticGate,
+        ArithmeticExtensionGate<D>,
+        BaseSumGate<2>,
+        BaseSumGate<4>,
+        ComparisonGate<F, D>,
+        ConstantGate,
+        ExponentiationGate<F, D>,
+        HighDegreeInterpolationGate<F, D>,
+        LowDegreeInterpolationGate<F, D>,
+        MulExtensionGate<D>,
+        NoopGate,
+        PoseidonMdsGate<F, D>,
+        PoseidonGate<F, D>,
+        PublicInputGate,
+        RandomAccessGate<F, D>,
+        ReducingExtensionGate<D>,
+        ReducingGate<D>,
+        U32AddManyGate<F, D>,
+        U32ArithmeticGate<F, D>,
+        U32RangeCheckGate<F, D>,
+        U32SubtractionGate<F, D>
+    }
+}
+
+/// A [`WitnessGeneratorSerializer`] supporting the generators of `plonky2`, `plonky2_u32` and of
+/// this crate. Non-native generators are supported over the secp256k1 base and scalar fields.
+#[derive(Debug)]
+pub struct EcdsaGeneratorSerializer<C: GenericConfig<D>, const D: usize> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C: GenericConfig<D>, const D: usize> Default for EcdsaGeneratorSerializer<C, D> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, C, const D: usize> WitnessGeneratorSerializer<F, D> for EcdsaGeneratorSerializer<C, D>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F> + 'static,
+    C::Hasher: AlgebraicHasher<F>,
+{
+    impl_generator_serializer! {
+        SimpleGeneratorAdapter<F, ArithmeticBaseGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, ArithmeticExtensionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, BaseSplitGenerator<2>>,
+        SimpleGeneratorAdapter<F, BaseSplitGenerator<4>>,
+        SimpleGeneratorAdapter<F, BaseSumGenerator<2>>,
+        SimpleGeneratorAdapter<F, BigUintDivRemGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, ComparisonGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, ConstantGenerator<F>>,
+        SimpleGeneratorAdapter<F, CopyGenerator>,
+        SimpleGeneratorAdapter<F, DummyProofGenerator<F, C, D>>,
+        SimpleGeneratorAdapter<F, EqualityGenerator>,
+        SimpleGeneratorAdapter<F, ExponentiationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, GLVDecompositionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, HighDegreeInterpolationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, LowDegreeInterpolationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, LowHighGenerator>,
+        SimpleGeneratorAdapter<F, MulExtensionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, NonNativeAdditionGenerator<F, D, Secp256K1Base>>,
+        SimpleGeneratorAdapter<F, NonNativeAdditionGenerator<F, D, Secp256K1Scalar>>,
+        SimpleGeneratorAdapter<F, NonNativeInverseGenerator<F, D, Secp256K1Base>>,
+        SimpleGeneratorAdapter<F, NonNativeInverseGenerator<F, D, Secp256K1Scalar>>,
+        SimpleGeneratorAdapter<F, NonNativeMultipleAddsGenerator<F, D, Secp256K1Base>>,
+        SimpleGeneratorAdapter<F, NonNativeMultipleAddsGenerator<F, D, Secp256K1Scalar>>,
+        SimpleGeneratorAdapter<F, NonNativeMultiplicationGenerator<F, D, Secp256K1Base>>,
+        SimpleGeneratorAdapter<F, NonNativeMultiplicationGenerator<F, D, Secp256K1Scalar>>,
+        SimpleGeneratorAdapter<F, NonNativeSubtractionGenerator<F, D, Secp256K1Base>>,
+        SimpleGeneratorAdapter<F, NonNativeSubtractionGenerator<F, D, Secp256K1Scalar>>,
+        SimpleGeneratorAdapter<F, NonzeroTestGenerator>,
+        SimpleGeneratorAdapter<F, PoseidonGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, PoseidonMdsGenerator<D>>,
+        SimpleGeneratorAdapter<F, QuotientGeneratorExtension<D>>,
+        SimpleGeneratorAdapter<F, RandomAccessGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, RandomValueGenerator>,
+        SimpleGeneratorAdapter<F, ReducingGenerator<D>>,
+        SimpleGeneratorAdapter<F, ReducingExtensionGenerator<D>>,
+        SimpleGeneratorAdapter<F, SplitGenerator>,
+        SimpleGeneratorAdapter<F, SplitToU32Generator<F, D>>,
+        SimpleGeneratorAdapter<F, U32AddManyGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32ArithmeticGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32RangeCheckGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32SubtractionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, WireSplitGenerator>
+    }
+}
@@ -1,22 +1,170 @@
 use std::marker::PhantomData;
-use std::ops::Add;
+use std::ops::{Add, Range};
 
 use itertools::Itertools;
 use plonky2::field::extension::{Extendable, FieldExtension};
 use plonky2::field::packed::PackedField;
+use plonky2::field::polynomial::PolynomialValues;
+use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
+use plonky2::timed;
+use plonky2::util::timing::TimingTree;
+use plonky2::util::transpose;
 
-use crate::arithmetic::{add, columns, compare, modular, mul, sub};
+use crate::arithmetic::columns::*;
+use crate::arithmetic::{add, columns, compare, modular, mul, sub, Operation};
 use crate::constraint_consumer::{ConstraintConsumer, RecursiveConstraintConsumer};
+use crate::cross_table_lookup::Column;
+use crate::lookup::{eval_lookups, eval_lookups_circuit, permuted_cols};
+use crate::memory::VALUE_LIMBS;
+use crate::permutation::PermutationPair;
 use crate::stark::Stark;
 use crate::vars::{StarkEvaluationTargets, StarkEvaluationVars};
 
-#[derive(Copy, Clone)]
+/// Every shared column is range-checked to `[0, RANGE_MAX)`, against the values of `RANGE_COUNTER`,
+/// so the table has at least `RANGE_MAX` rows.
+const RANGE_MAX: usize = 1 << LIMB_BITS;
+
+/// The groups of operations which store their inputs and output in the same columns. Each group
+/// is connected to the CPU table by its own cross-table lookup.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CtlLayout {
+    /// ADD, MUL and SUB. SHL is also checked as a MUL.
+    Basic,
+    /// LT and GT.
+    Compare,
+    /// ADDMOD, SUBMOD, MULMOD and MOD.
+    Modular,
+    /// DIV. SHR is also checked as a DIV.
+    Div,
+}
+
+impl CtlLayout {
+    pub(crate) const ALL: [Self; 4] = [Self::Basic, Self::Compare, Self::Modular, Self::Div];
+
+    /// The operation columns of the operations in this group.
+    pub(crate) fn operations(self) -> &'static [usize] {
+        match self {
+            CtlLayout::Basic => &[IS_ADD, IS_MUL, IS_SUB],
+            CtlLayout::Compare => &[IS_LT, IS_GT],
+            CtlLayout::Modular => &[IS_ADDMOD, IS_SUBMOD, IS_MULMOD, IS_MOD],
+            CtlLayout::Div => &[IS_DIV],
+        }
+    }
+
+    /// The registers holding the inputs and output of the operations in this group.
+    fn registers(self) -> &'static [Range<usize>] {
+        match self {
+            CtlLayout::Basic => &[ADD_INPUT_0, ADD_INPUT_1, ADD_OUTPUT],
+            // The output of a comparison is a single column, handled separately.
+            CtlLayout::Compare => &[CMP_INPUT_0, CMP_INPUT_1],
+            CtlLayout::Modular => &[
+                MODULAR_INPUT_0,
+                MODULAR_INPUT_1,
+                MODULAR_MODULUS,
+                MODULAR_OUTPUT,
+            ],
+            CtlLayout::Div => &[DIV_NUMERATOR, DIV_DENOMINATOR, DIV_OUTPUT],
+        }
+    }
+}
+
+/// The columns of the given group of operations, i.e. the operation flags followed by the inputs
+/// and the output. Registers are repacked from 16-bit limbs into the 32-bit limbs used by the CPU.
+pub fn ctl_data<F: Field>(layout: CtlLayout) -> Vec<Column<F>> {
+    let limb_base = F::from_canonical_u64(1 << LIMB_BITS);
+
+    let mut res = Column::singles(layout.operations()).collect_vec();
+    for register in layout.registers() {
+        debug_assert_eq!(register.len(), N_LIMBS);
+        res.extend(
+            register
+                .clone()
+                .step_by(2)
+                .map(|c| Column::linear_combination([(c, F::ONE), (c + 1, limb_base)])),
+        );
+    }
+    if layout == CtlLayout::Compare {
+        res.push(Column::single(CMP_OUTPUT));
+        res.extend((1..VALUE_LIMBS).map(|_| Column::zero()));
+    }
+    res
+}
+
+pub fn ctl_filter<F: Field>(layout: CtlLayout) -> Column<F> {
+    Column::sum(layout.operations())
+}
+
+#[derive(Copy, Clone, Default)]
 pub struct ArithmeticStark<F, const D: usize> {
     pub f: PhantomData<F>,
 }
 
 impl<F: RichField, const D: usize> ArithmeticStark<F, D> {
+    pub(crate) fn generate_trace(
+        &self,
+        operations: Vec<Operation>,
+        min_rows: usize,
+        timing: &mut TimingTree,
+    ) -> Vec<PolynomialValues<F>> {
+        let trace_rows = timed!(
+            timing,
+            "generate trace rows",
+            self.generate_trace_rows(operations, min_rows.max(RANGE_MAX))
+        );
+        let trace_row_vecs: Vec<_> = trace_rows.into_iter().map(|row| row.to_vec()).collect();
+
+        // Transpose to column-major form, which works better to generate the range checks.
+        let mut trace_col_vecs = transpose(&trace_row_vecs);
+        timed!(
+            timing,
+            "generate range checks",
+            Self::generate_range_checks(&mut trace_col_vecs)
+        );
+
+        trace_col_vecs
+            .into_iter()
+            .map(|column| PolynomialValues::new(column))
+            .collect()
+    }
+
+    fn generate_trace_rows(
+        &self,
+        operations: Vec<Operation>,
+        min_rows: usize,
+    ) -> Vec<[F; NUM_ARITH_COLUMNS]> {
+        let mut rows = Vec::with_capacity(2 * operations.len());
+        for op in operations {
+            let (row, next_row) = op.to_rows();
+            rows.push(row);
+            rows.extend(next_row);
+        }
+
+        // Pad to a power of two. Padding rows have no operation flag set, so they are only
+        // constrained by the range checks, and not part of any lookup.
+        let padded_len = rows.len().max(min_rows).next_power_of_two();
+        rows.resize(padded_len, [F::ZERO; NUM_ARITH_COLUMNS]);
+
+        rows
+    }
+
+    /// Generates the `RANGE_COUNTER` column, and the permuted columns in `RC_COLS` showing that
+    /// every shared column only holds 16-bit values.
+    fn generate_range_checks(cols: &mut [Vec<F>]) {
+        debug_assert_eq!(cols.len(), NUM_ARITH_COLUMNS);
+        let n_rows = cols[0].len();
+        debug_assert!(n_rows >= RANGE_MAX);
+
+        cols[RANGE_COUNTER] = (0..n_rows)
+            .map(|i| F::from_canonical_usize(i.min(RANGE_MAX - 1)))
+            .collect();
+        for (c, rc_c) in SHARED_COLS.zip(RC_COLS.step_by(2)) {
+            let (col_perm, table_perm) = permuted_cols(&cols[c], &cols[RANGE_COUNTER]);
+            cols[rc_c] = col_perm;
+            cols[rc_c + 1] = table_perm;
+        }
+    }
+
     pub fn generate(
         &self,
         local_values: &mut [F; columns::NUM_ARITH_COLUMNS],
@@ -79,6 +227,31 @@ impl<F: RichField + Extendable<D>, const D: usize> Stark<F, D> for ArithmeticSta
     {
         let lv = vars.local_values;
         let nv = vars.next_values;
+
+        // Check that the operation flags are boolean, and that at most one of them is set, since
+        // their sum is the multiplicity of the row in the CTLs.
+        let mut flag_sum = P::ZEROS;
+        for &flag in &ALL_OPERATIONS {
+            let flag = lv[flag];
+            yield_constr.constraint(flag * (flag - P::ONES));
+            flag_sum += flag;
+        }
+        yield_constr.constraint(flag_sum * (flag_sum - P::ONES));
+
+        // Check that the range counter starts at 0, increments by 0 or 1, and ends at
+        // RANGE_MAX - 1, so that it takes every value in [0, RANGE_MAX).
+        let rc = lv[RANGE_COUNTER];
+        let incr = nv[RANGE_COUNTER] - rc;
+        yield_constr.constraint_first_row(rc);
+        yield_constr.constraint_transition(incr * (incr - P::ONES));
+        let range_max = P::Scalar::from_canonical_usize(RANGE_MAX - 1);
+        yield_constr.constraint_last_row(rc - range_max);
+
+        // Range-check every shared column against the range counter.
+        for col in RC_COLS.step_by(2) {
+            eval_lookups(vars, yield_constr, col, col + 1);
+        }
+
         add::eval_packed_generic(lv, yield_constr);
         sub::eval_packed_generic(lv, yield_constr);
         mul::eval_packed_generic(lv, yield_constr);
@@ -94,6 +267,31 @@ impl<F: RichField + Extendable<D>, const D: usize> Stark<F, D> for ArithmeticSta
     ) {
         let lv = vars.local_values;
         let nv = vars.next_values;
+
+        let mut flag_sum = builder.zero_extension();
+        for &flag in &ALL_OPERATIONS {
+            let flag = lv[flag];
+            let constr = builder.mul_sub_extension(flag, flag, flag);
+            yield_constr.constraint(builder, constr);
+            flag_sum = builder.add_extension(flag_sum, flag);
+        }
+        let constr = builder.mul_sub_extension(flag_sum, flag_sum, flag_sum);
+        yield_constr.constraint(builder, constr);
+
+        let rc = lv[RANGE_COUNTER];
+        let incr = builder.sub_extension(nv[RANGE_COUNTER], rc);
+        yield_constr.constraint_first_row(builder, rc);
+        let constr = builder.mul_sub_extension(incr, incr, incr);
+        yield_constr.constraint_transition(builder, constr);
+        let range_max =
+            builder.constant_extension(F::Extension::from_canonical_usize(RANGE_MAX - 1));
+        let constr = builder.sub_extension(rc, range_max);
+        yield_constr.constraint_last_row(builder, constr);
+
+        for col in RC_COLS.step_by(2) {
+            eval_lookups_circuit(builder, vars, yield_constr, col, col + 1);
+        }
+
         add::eval_ext_circuit(builder, lv, yield_constr);
         sub::eval_ext_circuit(builder, lv, yield_constr);
         mul::eval_ext_circuit(builder, lv, yield_constr);
@@ -104,4 +302,164 @@ impl<F: RichField + Extendable<D>, const D: usize> Stark<F, D> for ArithmeticSta
     fn constraint_degree(&self) -> usize {
         3
     }
+
+    fn permutation_pairs(&self) -> Vec<PermutationPair> {
+        let mut pairs = Vec::with_capacity(2 * NUM_SHARED_COLS);
+        for (c, c_perm) in SHARED_COLS.zip_eq(RC_COLS.step_by(2)) {
+            pairs.push(PermutationPair::singletons(c, c_perm));
+            pairs.push(PermutationPair::singletons(c_perm + 1, RANGE_COUNTER));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use ethereum_types::U256;
+    use plonky2::field::types::{Field, PrimeField64};
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2::util::timing::TimingTree;
+
+    use crate::arithmetic::arithmetic_stark::{ArithmeticStark, RANGE_MAX};
+    use crate::arithmetic::columns::*;
+    use crate::arithmetic::{BinaryOperator, Operation, TernaryOperator};
+    use crate::constraint_consumer::ConstraintConsumer;
+    use crate::stark::Stark;
+    use crate::stark_testing::{test_stark_circuit_constraints, test_stark_low_degree};
+    use crate::vars::StarkEvaluationVars;
+
+    #[test]
+    fn test_stark_degree() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type S = ArithmeticStark<F, D>;
+
+        let stark = S {
+            f: Default::default(),
+        };
+        test_stark_low_degree(stark)
+    }
+
+    #[test]
+    fn test_stark_circuit() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type S = ArithmeticStark<F, D>;
+
+        let stark = S {
+            f: Default::default(),
+        };
+        test_stark_circuit_constraints::<F, C, S, D>(stark)
+    }
+
+    #[test]
+    fn test_generate_trace_rows() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type S = ArithmeticStark<F, D>;
+
+        let stark = S {
+            f: Default::default(),
+        };
+
+        let operations = vec![
+            Operation::binary(BinaryOperator::Add, 7.into(), U256::MAX),
+            Operation::ternary(TernaryOperator::MulMod, 12.into(), 5.into(), 7.into()),
+            Operation::binary(BinaryOperator::Shl, 3.into(), 5.into()),
+            Operation::binary(BinaryOperator::Shr, 300.into(), U256::MAX),
+        ];
+        let rows = stark.generate_trace_rows(operations, 4);
+
+        // MULMOD takes two rows, and the trace is padded to a power of two.
+        assert_eq!(rows.len(), 8);
+
+        assert_eq!(rows[0][IS_ADD], F::ONE);
+        assert_eq!(rows[0][ADD_OUTPUT.start], F::from_canonical_u64(6));
+        assert!(rows[0][ADD_OUTPUT.start + 1..ADD_OUTPUT.end]
+            .iter()
+            .all(|&c| c == F::ZERO));
+
+        assert_eq!(rows[1][IS_MULMOD], F::ONE);
+        assert_eq!(rows[1][MODULAR_OUTPUT.start], F::from_canonical_u64(4));
+        assert!(ALL_OPERATIONS.iter().all(|&c| rows[2][c] == F::ZERO));
+
+        // SHL is checked as 5 * 2^3.
+        assert_eq!(rows[3][IS_MUL], F::ONE);
+        assert_eq!(rows[3][MUL_INPUT_1.start], F::from_canonical_u64(8));
+        assert_eq!(rows[3][MUL_OUTPUT.start], F::from_canonical_u64(40));
+
+        // Shifting by more than 255 divides by zero, which gives zero.
+        assert_eq!(rows[4][IS_DIV], F::ONE);
+        assert!(rows[4][DIV_DENOMINATOR].iter().all(|&c| c == F::ZERO));
+        assert!(rows[4][DIV_OUTPUT].iter().all(|&c| c == F::ZERO));
+
+        assert!(rows[6..]
+            .iter()
+            .all(|row| row.iter().all(|&c| c == F::ZERO)));
+    }
+
+    #[test]
+    fn test_generate_trace_satisfies_constraints() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type S = ArithmeticStark<F, D>;
+
+        let stark = S {
+            f: Default::default(),
+        };
+
+        // Operands chosen to make the auxiliary values large, or the quotients negative.
+        let operations = vec![
+            Operation::binary(BinaryOperator::Add, U256::MAX, U256::MAX),
+            Operation::binary(BinaryOperator::Sub, 3.into(), U256::MAX),
+            Operation::binary(BinaryOperator::Mul, U256::MAX, U256::MAX),
+            Operation::binary(BinaryOperator::Lt, U256::MAX, 5.into()),
+            Operation::binary(BinaryOperator::Gt, U256::MAX, 5.into()),
+            Operation::binary(BinaryOperator::Div, U256::MAX, 7.into()),
+            Operation::binary(BinaryOperator::Div, U256::MAX, 0.into()),
+            Operation::binary(BinaryOperator::Mod, U256::MAX, 1.into()),
+            Operation::ternary(TernaryOperator::MulMod, U256::MAX, U256::MAX, U256::MAX - 1),
+            Operation::ternary(TernaryOperator::MulMod, U256::MAX, U256::MAX, 1.into()),
+            Operation::ternary(TernaryOperator::AddMod, U256::MAX, U256::MAX, 0.into()),
+            Operation::binary(BinaryOperator::SubFp254, 3.into(), 5.into()),
+            Operation::binary(BinaryOperator::SubFp254, 3.into(), U256::MAX),
+            Operation::binary(BinaryOperator::MulFp254, U256::MAX, U256::MAX),
+        ];
+        let trace = stark.generate_trace(operations, 4, &mut TimingTree::default());
+        let n = trace[0].len();
+        assert_eq!(n, RANGE_MAX);
+
+        for c in SHARED_COLS {
+            assert!(trace[c]
+                .values
+                .iter()
+                .all(|x| x.to_canonical_u64() < RANGE_MAX as u64));
+        }
+
+        let rows = (0..n)
+            .map(|i| {
+                let row: [F; NUM_ARITH_COLUMNS] = core::array::from_fn(|c| trace[c].values[i]);
+                row
+            })
+            .collect::<Vec<_>>();
+        for i in 0..n {
+            let vars = StarkEvaluationVars {
+                local_values: &rows[i],
+                next_values: &rows[(i + 1) % n],
+            };
+            let mut constraint_consumer = ConstraintConsumer::new(
+                vec![F::from_canonical_u64(2)],
+                if i == n - 1 { F::ZERO } else { F::ONE },
+                if i == 0 { F::ONE } else { F::ZERO },
+                if i == n - 1 { F::ONE } else { F::ZERO },
+            );
+            stark.eval_packed_base(vars, &mut constraint_consumer);
+            assert_eq!(constraint_consumer.accumulators(), vec![F::ZERO], "row {i}");
+        }
+    }
 }
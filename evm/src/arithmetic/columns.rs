@@ -35,7 +35,7 @@ pub const IS_GT: usize = IS_LT + 1;
 pub const IS_SHL: usize = IS_GT + 1;
 pub const IS_SHR: usize = IS_SHL + 1;
 
-const START_SHARED_COLS: usize = IS_SHR + 1;
+pub(crate) const START_SHARED_COLS: usize = IS_SHR + 1;
 
 pub(crate) const ALL_OPERATIONS: [usize; 12] = [
     IS_ADD, IS_MUL, IS_SUB, IS_DIV, IS_MOD, IS_ADDMOD, IS_SUBMOD, IS_MULMOD, IS_LT, IS_GT, IS_SHL,
@@ -46,11 +46,15 @@ pub(crate) const ALL_OPERATIONS: [usize; 12] = [
 /// used by any arithmetic circuit, depending on which one is active
 /// this cycle.
 ///
-/// Modular arithmetic takes 9 * N_LIMBS columns which is split across
-/// two rows, the first with 5 * N_LIMBS columns and the second with
-/// 4 * N_LIMBS columns. (There are hence N_LIMBS "wasted columns" in
-/// the second row.)
-const NUM_SHARED_COLS: usize = 5 * N_LIMBS;
+/// Modular arithmetic takes 11 * N_LIMBS columns which is split
+/// across two rows, the first with 5 * N_LIMBS columns and the second
+/// with 6 * N_LIMBS columns. (There are hence N_LIMBS "wasted
+/// columns" in the first row.)
+///
+/// Every shared column is range-checked to 16 bits, in every row.
+pub(crate) const NUM_SHARED_COLS: usize = 6 * N_LIMBS;
+
+pub(crate) const SHARED_COLS: Range<usize> = START_SHARED_COLS..START_SHARED_COLS + NUM_SHARED_COLS;
 
 const GENERAL_INPUT_0: Range<usize> = START_SHARED_COLS..START_SHARED_COLS + N_LIMBS;
 const GENERAL_INPUT_1: Range<usize> = GENERAL_INPUT_0.end..GENERAL_INPUT_0.end + N_LIMBS;
@@ -67,6 +71,8 @@ const AUX_INPUT_1: Range<usize> = AUX_INPUT_0_HI.end..AUX_INPUT_0_HI.end + 2 * N
 // with the first half after the general input columns and the second
 // half after the auxiliary input columns.
 const AUX_INPUT_2: Range<usize> = AUX_INPUT_1.end..AUX_INPUT_1.end + N_LIMBS;
+// Skips the column after AUX_INPUT_2, which holds MODULAR_DIV_DENOM_IS_ZERO.
+const AUX_INPUT_3: Range<usize> = AUX_INPUT_2.end + 1..AUX_INPUT_2.end + 2 * N_LIMBS;
 
 // ADD takes 3 * N_LIMBS = 48 columns
 pub(crate) const ADD_INPUT_0: Range<usize> = GENERAL_INPUT_0;
@@ -78,11 +84,12 @@ pub(crate) const SUB_INPUT_0: Range<usize> = GENERAL_INPUT_0;
 pub(crate) const SUB_INPUT_1: Range<usize> = GENERAL_INPUT_1;
 pub(crate) const SUB_OUTPUT: Range<usize> = GENERAL_INPUT_2;
 
-// MUL takes 4 * N_LIMBS = 64 columns
+// MUL takes 5 * N_LIMBS = 80 columns
 pub(crate) const MUL_INPUT_0: Range<usize> = GENERAL_INPUT_0;
 pub(crate) const MUL_INPUT_1: Range<usize> = GENERAL_INPUT_1;
 pub(crate) const MUL_OUTPUT: Range<usize> = GENERAL_INPUT_2;
-pub(crate) const MUL_AUX_INPUT: Range<usize> = GENERAL_INPUT_3;
+pub(crate) const MUL_AUX_INPUT_LO: Range<usize> = GENERAL_INPUT_3;
+pub(crate) const MUL_AUX_INPUT_HI: Range<usize> = AUX_INPUT_0_LO;
 
 // LT and GT take 4 * N_LIMBS = 64 columns
 pub(crate) const CMP_INPUT_0: Range<usize> = GENERAL_INPUT_0;
@@ -90,11 +97,11 @@ pub(crate) const CMP_INPUT_1: Range<usize> = GENERAL_INPUT_1;
 pub(crate) const CMP_OUTPUT: usize = GENERAL_INPUT_2.start;
 pub(crate) const CMP_AUX_INPUT: Range<usize> = GENERAL_INPUT_3;
 
-// MULMOD takes 4 * N_LIMBS + 2 * 2*N_LIMBS + N_LIMBS = 144 columns
-// but split over two rows of 80 columns and 64 columns.
+// MULMOD takes 4 * N_LIMBS + 2 * N_LIMBS + 2 * 2*N_LIMBS + N_LIMBS =
+// 176 columns but split over two rows of 80 columns and 96 columns.
 //
 // ADDMOD, SUBMOD, MOD and DIV are currently implemented in terms of
-// the general modular code, so they also take 144 columns (also split
+// the general modular code, so they also take 176 columns (also split
 // over two rows).
 pub(crate) const MODULAR_INPUT_0: Range<usize> = GENERAL_INPUT_0;
 pub(crate) const MODULAR_INPUT_1: Range<usize> = GENERAL_INPUT_1;
@@ -103,15 +110,23 @@ pub(crate) const MODULAR_OUTPUT: Range<usize> = GENERAL_INPUT_3;
 pub(crate) const MODULAR_QUO_INPUT_LO: Range<usize> = AUX_INPUT_0_LO;
 // NB: Last value is not used in AUX, it is used in MOD_IS_ZERO
 pub(crate) const MODULAR_QUO_INPUT_HI: Range<usize> = AUX_INPUT_0_HI;
-pub(crate) const MODULAR_AUX_INPUT: Range<usize> = AUX_INPUT_1.start..AUX_INPUT_1.end - 1;
+pub(crate) const MODULAR_AUX_INPUT_LO: Range<usize> = AUX_INPUT_1.start..AUX_INPUT_1.end - 1;
+pub(crate) const MODULAR_AUX_INPUT_HI: Range<usize> = AUX_INPUT_3;
 pub(crate) const MODULAR_MOD_IS_ZERO: usize = AUX_INPUT_1.end - 1;
 pub(crate) const MODULAR_OUT_AUX_RED: Range<usize> = AUX_INPUT_2;
+// Equal to mod_is_zero * IS_DIV; kept in its own column to bound the
+// degree of the constraints.
+pub(crate) const MODULAR_DIV_DENOM_IS_ZERO: usize = AUX_INPUT_2.end;
 
-#[allow(unused)] // TODO: Will be used when hooking into the CPU
 pub(crate) const DIV_NUMERATOR: Range<usize> = MODULAR_INPUT_0;
-#[allow(unused)] // TODO: Will be used when hooking into the CPU
 pub(crate) const DIV_DENOMINATOR: Range<usize> = MODULAR_MODULUS;
-#[allow(unused)] // TODO: Will be used when hooking into the CPU
 pub(crate) const DIV_OUTPUT: Range<usize> = MODULAR_QUO_INPUT_LO;
 
-pub const NUM_ARITH_COLUMNS: usize = START_SHARED_COLS + NUM_SHARED_COLS;
+/// The values 0 to 2^16 - 1, in order, followed by copies of 2^16 - 1.
+pub(crate) const RANGE_COUNTER: usize = START_SHARED_COLS + NUM_SHARED_COLS;
+/// For each shared column, the permuted values of that column followed
+/// by the permuted values of `RANGE_COUNTER`, which together show that
+/// the shared column only holds 16-bit values.
+pub(crate) const RC_COLS: Range<usize> = RANGE_COUNTER + 1..RANGE_COUNTER + 1 + 2 * NUM_SHARED_COLS;
+
+pub const NUM_ARITH_COLUMNS: usize = RC_COLS.end;
@@ -1,7 +1,9 @@
 use std::str::FromStr;
 
 use ethereum_types::U256;
+use plonky2::hash::hash_types::RichField;
 
+use crate::arithmetic::columns::*;
 use crate::util::{addmod, mulmod, submod};
 
 mod add;
@@ -101,7 +103,6 @@ impl TernaryOperator {
 }
 
 #[derive(Debug)]
-#[allow(unused)] // TODO: Should be used soon.
 pub(crate) enum Operation {
     BinaryOperation {
         operator: BinaryOperator,
@@ -151,8 +152,153 @@ impl Operation {
             Operation::TernaryOperation { result, .. } => *result,
         }
     }
+
+    /// Convert the operation into one or two rows of the arithmetic trace. The second row is only
+    /// present for modular operations, which store some of their auxiliary values there.
+    fn to_rows<F: RichField>(&self) -> ([F; NUM_ARITH_COLUMNS], Option<[F; NUM_ARITH_COLUMNS]>) {
+        match *self {
+            Operation::BinaryOperation {
+                operator,
+                input0,
+                input1,
+                result: _,
+            } => binary_op_to_rows(operator, input0, input1),
+            Operation::TernaryOperation {
+                operator,
+                input0,
+                input1,
+                input2,
+                result: _,
+            } => ternary_op_to_rows(operator, input0, input1, input2),
+        }
+    }
+}
+
+fn binary_op_to_rows<F: RichField>(
+    op: BinaryOperator,
+    input0: U256,
+    input1: U256,
+) -> ([F; NUM_ARITH_COLUMNS], Option<[F; NUM_ARITH_COLUMNS]>) {
+    let mut row = [F::ZERO; NUM_ARITH_COLUMNS];
+
+    match op {
+        BinaryOperator::Add => {
+            row[IS_ADD] = F::ONE;
+            u256_to_array(&mut row[ADD_INPUT_0], input0);
+            u256_to_array(&mut row[ADD_INPUT_1], input1);
+            add::generate(&mut row);
+            (row, None)
+        }
+        BinaryOperator::Sub => {
+            row[IS_SUB] = F::ONE;
+            u256_to_array(&mut row[SUB_INPUT_0], input0);
+            u256_to_array(&mut row[SUB_INPUT_1], input1);
+            sub::generate(&mut row);
+            (row, None)
+        }
+        BinaryOperator::Mul => {
+            row[IS_MUL] = F::ONE;
+            u256_to_array(&mut row[MUL_INPUT_0], input0);
+            u256_to_array(&mut row[MUL_INPUT_1], input1);
+            mul::generate(&mut row);
+            (row, None)
+        }
+        BinaryOperator::Lt | BinaryOperator::Gt => {
+            let filter = if op == BinaryOperator::Lt {
+                IS_LT
+            } else {
+                IS_GT
+            };
+            row[filter] = F::ONE;
+            u256_to_array(&mut row[CMP_INPUT_0], input0);
+            u256_to_array(&mut row[CMP_INPUT_1], input1);
+            compare::generate(&mut row, filter);
+            (row, None)
+        }
+        // Shifts are checked as a MUL (resp. DIV) of the shifted value by 2^shift, which the CPU
+        // reads from the shift table. The table only holds the powers up to 2^255, so larger
+        // shifts read zero.
+        BinaryOperator::Shl => {
+            let two_exp = shift_factor(input0);
+            binary_op_to_rows(BinaryOperator::Mul, input1, two_exp)
+        }
+        BinaryOperator::Shr => {
+            let two_exp = shift_factor(input0);
+            binary_op_to_rows(BinaryOperator::Div, input1, two_exp)
+        }
+        BinaryOperator::Div => {
+            let mut nv = [F::ZERO; NUM_ARITH_COLUMNS];
+            row[IS_DIV] = F::ONE;
+            u256_to_array(&mut row[DIV_NUMERATOR], input0);
+            u256_to_array(&mut row[DIV_DENOMINATOR], input1);
+            modular::generate(&mut row, &mut nv, IS_DIV);
+            (row, Some(nv))
+        }
+        BinaryOperator::Mod => {
+            let mut nv = [F::ZERO; NUM_ARITH_COLUMNS];
+            row[IS_MOD] = F::ONE;
+            u256_to_array(&mut row[MODULAR_INPUT_0], input0);
+            // MOD ignores its second input, so we repeat the modulus there; this lets MOD share
+            // the CTL columns of the other modular operations.
+            u256_to_array(&mut row[MODULAR_INPUT_1], input1);
+            u256_to_array(&mut row[MODULAR_MODULUS], input1);
+            modular::generate(&mut row, &mut nv, IS_MOD);
+            (row, Some(nv))
+        }
+        BinaryOperator::AddFp254 => modular_op_to_rows(IS_ADDMOD, input0, input1, bn_base_order()),
+        BinaryOperator::MulFp254 => modular_op_to_rows(IS_MULMOD, input0, input1, bn_base_order()),
+        BinaryOperator::SubFp254 => modular_op_to_rows(IS_SUBMOD, input0, input1, bn_base_order()),
+    }
+}
+
+fn ternary_op_to_rows<F: RichField>(
+    op: TernaryOperator,
+    input0: U256,
+    input1: U256,
+    input2: U256,
+) -> ([F; NUM_ARITH_COLUMNS], Option<[F; NUM_ARITH_COLUMNS]>) {
+    let filter = match op {
+        TernaryOperator::AddMod => IS_ADDMOD,
+        TernaryOperator::MulMod => IS_MULMOD,
+    };
+    modular_op_to_rows(filter, input0, input1, input2)
+}
+
+fn modular_op_to_rows<F: RichField>(
+    filter: usize,
+    input0: U256,
+    input1: U256,
+    modulus: U256,
+) -> ([F; NUM_ARITH_COLUMNS], Option<[F; NUM_ARITH_COLUMNS]>) {
+    let mut lv = [F::ZERO; NUM_ARITH_COLUMNS];
+    let mut nv = [F::ZERO; NUM_ARITH_COLUMNS];
+    lv[filter] = F::ONE;
+    u256_to_array(&mut lv[MODULAR_INPUT_0], input0);
+    u256_to_array(&mut lv[MODULAR_INPUT_1], input1);
+    u256_to_array(&mut lv[MODULAR_MODULUS], modulus);
+    modular::generate(&mut lv, &mut nv, filter);
+    (lv, Some(nv))
+}
+
+/// The value `2^shift`, or zero if `shift > 255`, as looked up in the shift table by the CPU.
+fn shift_factor(shift: U256) -> U256 {
+    if shift > 255.into() {
+        U256::zero()
+    } else {
+        U256::one() << shift
+    }
+}
+
+/// Write the `N_LIMBS` limbs of `x`, least significant first, into `out`.
+fn u256_to_array<F: RichField>(out: &mut [F], x: U256) {
+    debug_assert_eq!(out.len(), N_LIMBS);
+    for (i, limb) in out.iter_mut().enumerate() {
+        let word = x.0[i * LIMB_BITS / 64];
+        let limb_value = (word >> ((i * LIMB_BITS) % 64)) & ((1 << LIMB_BITS) - 1);
+        *limb = F::from_canonical_u64(limb_value);
+    }
 }
 
-fn bn_base_order() -> U256 {
+pub(crate) fn bn_base_order() -> U256 {
     U256::from_str("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47").unwrap()
 }
@@ -51,6 +51,17 @@
 //! always have deg(m*q) <= 2*N-1. Finally, in order for all the degrees
 //! to match, we have deg(s) <= 2*N-2 = 30.
 //!
+//! The coefficients of s(x) don't fit in 16 bits and can be negative,
+//! so they are stored offset and split into two 16-bit limbs (see
+//! `AUX_COEFF_ABS_MAX`), like every other value in the table they are
+//! then range-checked.
+//!
+//! For SUBMOD, A - B can be negative, which would make the quotient Q
+//! negative too, and its limbs impossible to range-check. We instead
+//! verify that A - B + M * 2^256 = C (mod M), which is equivalent but
+//! always nonnegative; that is, for SUBMOD operation(a(x), b(x)) is
+//! a(x) - b(x) + x^N * m(x).
+//!
 //! -*-
 //!
 //! To verify that the output is reduced, that is, output < modulus,
@@ -89,10 +100,10 @@
 //!
 //! -*-
 //!
-//! NB: The implementation uses 9 * N_LIMBS = 144 columns because of
+//! NB: The implementation uses 11 * N_LIMBS = 176 columns because of
 //! the requirements of the general purpose MULMOD; since ADDMOD,
 //! SUBMOD, MOD and DIV are currently implemented in terms of the
-//! general modular code, they also take 144 columns. Possible
+//! general modular code, they also take 176 columns. Possible
 //! improvements:
 //!
 //! - We could reduce the number of columns to 112 for ADDMOD, SUBMOD,
@@ -122,7 +133,6 @@ use crate::arithmetic::columns::*;
 use crate::arithmetic::compare::{eval_ext_circuit_lt, eval_packed_generic_lt};
 use crate::arithmetic::utils::*;
 use crate::constraint_consumer::{ConstraintConsumer, RecursiveConstraintConsumer};
-use crate::range_check_error;
 
 /// Convert the base-2^16 representation of a number into a BigInt.
 ///
@@ -235,6 +245,12 @@ fn generate_modular_op<F: RichField>(
         mod_is_zero = F::ONE;
     }
 
+    // SUBMOD verifies A - B + M * 2^256 instead of A - B; see the file
+    // documentation.
+    if filter == columns::IS_SUBMOD {
+        pol_add_assign(&mut constr_poly[N_LIMBS..], &modulus_limbs);
+    }
+
     let input = columns_to_bigint(&constr_poly);
 
     // modulus != 0 here, because, if the given modulus was zero, then
@@ -246,7 +262,7 @@ fn generate_modular_op<F: RichField>(
         output += &modulus;
     }
     let output_limbs = bigint_to_columns::<N_LIMBS>(&output);
-    let quot = (&input - &output) / &modulus; // exact division; can be -ve
+    let quot = (&input - &output) / &modulus; // exact division; input is nonnegative, so is quot
     let quot_limbs = bigint_to_columns::<{ 2 * N_LIMBS }>(&quot);
 
     // output < modulus here, so the proof requires (output - modulus) % 2^256:
@@ -272,17 +288,21 @@ fn generate_modular_op<F: RichField>(
 
     // Copy lo and hi halves of quot_limbs into their respective registers
     for (i, &lo) in MODULAR_QUO_INPUT_LO.zip(&quot_limbs[..N_LIMBS]) {
-        lv[i] = F::from_noncanonical_i64(lo);
+        lv[i] = F::from_canonical_i64(lo);
     }
     for (i, &hi) in MODULAR_QUO_INPUT_HI.zip(&quot_limbs[N_LIMBS..]) {
-        nv[i] = F::from_noncanonical_i64(hi);
+        nv[i] = F::from_canonical_i64(hi);
     }
 
-    for (i, &c) in MODULAR_AUX_INPUT.zip(&aux_limbs[..2 * N_LIMBS - 1]) {
-        nv[i] = F::from_noncanonical_i64(c);
-    }
+    write_aux_limbs(
+        nv,
+        MODULAR_AUX_INPUT_LO,
+        MODULAR_AUX_INPUT_HI,
+        &aux_limbs[..2 * N_LIMBS - 1],
+    );
 
     nv[MODULAR_MOD_IS_ZERO] = mod_is_zero;
+    nv[MODULAR_DIV_DENOM_IS_ZERO] = mod_is_zero * lv[IS_DIV];
     nv[MODULAR_OUT_AUX_RED].copy_from_slice(&out_aux_red.map(|c| F::from_canonical_i64(c)));
 }
 
@@ -319,14 +339,6 @@ fn modular_constr_poly<P: PackedField>(
     yield_constr: &mut ConstraintConsumer<P>,
     filter: P,
 ) -> [P; 2 * N_LIMBS] {
-    range_check_error!(MODULAR_INPUT_0, 16);
-    range_check_error!(MODULAR_INPUT_1, 16);
-    range_check_error!(MODULAR_MODULUS, 16);
-    range_check_error!(MODULAR_QUO_INPUT_LO, 16);
-    range_check_error!(MODULAR_QUO_INPUT_HI, 16);
-    range_check_error!(MODULAR_AUX_INPUT, 20, signed);
-    range_check_error!(MODULAR_OUTPUT, 16);
-
     let mut modulus = read_value::<N_LIMBS, _>(lv, MODULAR_MODULUS);
     let mod_is_zero = nv[MODULAR_MOD_IS_ZERO];
 
@@ -342,12 +354,17 @@ fn modular_constr_poly<P: PackedField>(
     // modulus = 0.
     modulus[0] += mod_is_zero;
 
+    // Check that div_denom_is_zero = mod_is_zero * IS_DIV. Using it
+    // below instead of the product keeps the constraints of degree 3.
+    let div_denom_is_zero = nv[MODULAR_DIV_DENOM_IS_ZERO];
+    yield_constr.constraint_transition(filter * (mod_is_zero * lv[IS_DIV] - div_denom_is_zero));
+
     let mut output = read_value::<N_LIMBS, _>(lv, MODULAR_OUTPUT);
 
     // Needed to compensate for adding mod_is_zero to modulus above,
     // since the call eval_packed_generic_lt() below subtracts modulus
     // verify in the case of a DIV.
-    output[0] += mod_is_zero * lv[IS_DIV];
+    output[0] += div_denom_is_zero;
 
     // Verify that the output is reduced, i.e. output < modulus.
     let out_aux_red = &nv[MODULAR_OUT_AUX_RED];
@@ -358,7 +375,7 @@ fn modular_constr_poly<P: PackedField>(
     //   output - modulus == out_aux_red + is_less_than*2^256
     //
     // and we were given output = out_aux_red
-    let is_less_than = P::ONES - mod_is_zero * lv[IS_DIV];
+    let is_less_than = P::ONES - div_denom_is_zero;
     // NB: output and modulus in lv while out_aux_red and is_less_than
     // (via mod_is_zero) depend on nv.
     eval_packed_generic_lt(
@@ -371,7 +388,7 @@ fn modular_constr_poly<P: PackedField>(
         true,
     );
     // restore output[0]
-    output[0] -= mod_is_zero * lv[IS_DIV];
+    output[0] -= div_denom_is_zero;
 
     // prod = q(x) * m(x)
     let quot = {
@@ -392,10 +409,7 @@ fn modular_constr_poly<P: PackedField>(
     pol_add_assign(&mut constr_poly, &output);
 
     // constr_poly = c(x) + q(x) * m(x) + (x - β) * s(x)
-    let mut aux = [P::ZEROS; 2 * N_LIMBS];
-    for (i, j) in MODULAR_AUX_INPUT.enumerate() {
-        aux[i] = nv[j];
-    }
+    let aux = read_aux_limbs::<_, { 2 * N_LIMBS }>(nv, MODULAR_AUX_INPUT_LO, MODULAR_AUX_INPUT_HI);
 
     let base = P::Scalar::from_canonical_u64(1 << LIMB_BITS);
     pol_add_assign(&mut constr_poly, &pol_adjoin_root(aux, base));
@@ -409,8 +423,9 @@ pub(crate) fn eval_packed_generic<P: PackedField>(
     nv: &[P; NUM_ARITH_COLUMNS],
     yield_constr: &mut ConstraintConsumer<P>,
 ) {
-    // NB: The CTL code guarantees that filter is 0 or 1, i.e. that
-    // only one of the operations below is "live".
+    // NB: The operation flags are checked to be boolean with at most
+    // one of them set, so filter is 0 or 1, i.e. only one of the
+    // operations below is "live".
     let filter = lv[columns::IS_ADDMOD]
         + lv[columns::IS_MULMOD]
         + lv[columns::IS_MOD]
@@ -427,16 +442,23 @@ pub(crate) fn eval_packed_generic<P: PackedField>(
     let input0 = read_value(lv, MODULAR_INPUT_0);
     let input1 = read_value(lv, MODULAR_INPUT_1);
 
+    // SUBMOD verifies A - B + M * 2^256 instead of A - B, with M
+    // adjusted like in `modular_constr_poly` if it is zero.
+    let mut modulus = read_value::<N_LIMBS, _>(lv, MODULAR_MODULUS);
+    modulus[0] += nv[MODULAR_MOD_IS_ZERO];
+    let mut sub_input = [P::ZEROS; 2 * N_LIMBS];
+    sub_input[..2 * N_LIMBS - 1].copy_from_slice(&pol_sub(input0, input1));
+    pol_add_assign(&mut sub_input[N_LIMBS..], &modulus);
+
     let add_input = pol_add(input0, input1);
-    let sub_input = pol_sub(input0, input1);
     let mul_input = pol_mul_wide(input0, input1);
-    let mod_input = pol_extend(input0);
+    let mod_input: [_; 2 * N_LIMBS - 1] = pol_extend(input0);
 
     for (input, &filter) in [
-        (&add_input, &lv[columns::IS_ADDMOD]),
-        (&sub_input, &lv[columns::IS_SUBMOD]),
-        (&mul_input, &lv[columns::IS_MULMOD]),
-        (&mod_input, &(lv[columns::IS_MOD] + lv[columns::IS_DIV])),
+        (&add_input[..], &lv[columns::IS_ADDMOD]),
+        (&sub_input[..], &lv[columns::IS_SUBMOD]),
+        (&mul_input[..], &lv[columns::IS_MULMOD]),
+        (&mod_input[..], &(lv[columns::IS_MOD] + lv[columns::IS_DIV])),
     ] {
         // Need constr_poly_copy to be the first argument to
         // pol_sub_assign, since it is the longer of the two
@@ -479,13 +501,17 @@ fn modular_constr_poly_ext_circuit<F: RichField + Extendable<D>, const D: usize>
 
     modulus[0] = builder.add_extension(modulus[0], mod_is_zero);
 
+    let div_denom_is_zero = nv[MODULAR_DIV_DENOM_IS_ZERO];
+    let t = builder.mul_sub_extension(mod_is_zero, lv[IS_DIV], div_denom_is_zero);
+    let t = builder.mul_extension(filter, t);
+    yield_constr.constraint_transition(builder, t);
+
     let mut output = read_value::<N_LIMBS, _>(lv, MODULAR_OUTPUT);
-    output[0] = builder.mul_add_extension(mod_is_zero, lv[IS_DIV], output[0]);
+    output[0] = builder.add_extension(output[0], div_denom_is_zero);
 
     let out_aux_red = &nv[MODULAR_OUT_AUX_RED];
     let one = builder.one_extension();
-    let is_less_than =
-        builder.arithmetic_extension(F::NEG_ONE, F::ONE, mod_is_zero, lv[IS_DIV], one);
+    let is_less_than = builder.sub_extension(one, div_denom_is_zero);
 
     eval_ext_circuit_lt(
         builder,
@@ -497,8 +523,7 @@ fn modular_constr_poly_ext_circuit<F: RichField + Extendable<D>, const D: usize>
         is_less_than,
         true,
     );
-    output[0] =
-        builder.arithmetic_extension(F::NEG_ONE, F::ONE, mod_is_zero, lv[IS_DIV], output[0]);
+    output[0] = builder.sub_extension(output[0], div_denom_is_zero);
     let quot = {
         let zero = builder.zero_extension();
         let mut quot = [zero; 2 * N_LIMBS];
@@ -516,11 +541,12 @@ fn modular_constr_poly_ext_circuit<F: RichField + Extendable<D>, const D: usize>
     let mut constr_poly: [_; 2 * N_LIMBS] = prod[0..2 * N_LIMBS].try_into().unwrap();
     pol_add_assign_ext_circuit(builder, &mut constr_poly, &output);
 
-    let zero = builder.zero_extension();
-    let mut aux = [zero; 2 * N_LIMBS];
-    for (i, j) in MODULAR_AUX_INPUT.enumerate() {
-        aux[i] = nv[j];
-    }
+    let aux = read_aux_limbs_ext_circuit::<_, D, { 2 * N_LIMBS }>(
+        builder,
+        nv,
+        MODULAR_AUX_INPUT_LO,
+        MODULAR_AUX_INPUT_HI,
+    );
 
     let base = builder.constant_extension(F::Extension::from_canonical_u64(1u64 << LIMB_BITS));
     let t = pol_adjoin_root_ext_circuit(builder, aux, base);
@@ -549,17 +575,23 @@ pub(crate) fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
     let input0 = read_value(lv, MODULAR_INPUT_0);
     let input1 = read_value(lv, MODULAR_INPUT_1);
 
+    let mut modulus = read_value::<N_LIMBS, _>(lv, MODULAR_MODULUS);
+    modulus[0] = builder.add_extension(modulus[0], nv[MODULAR_MOD_IS_ZERO]);
+    let zero = builder.zero_extension();
+    let mut sub_input = [zero; 2 * N_LIMBS];
+    sub_input[..2 * N_LIMBS - 1].copy_from_slice(&pol_sub_ext_circuit(builder, input0, input1));
+    pol_add_assign_ext_circuit(builder, &mut sub_input[N_LIMBS..], &modulus);
+
     let add_input = pol_add_ext_circuit(builder, input0, input1);
-    let sub_input = pol_sub_ext_circuit(builder, input0, input1);
-    let mul_input = pol_mul_wide_ext_circuit(builder, input0, input1);
+    let mul_input: [_; 2 * N_LIMBS - 1] = pol_mul_wide_ext_circuit(builder, input0, input1);
     let mod_input = pol_extend_ext_circuit(builder, input0);
 
     let mod_div_filter = builder.add_extension(lv[columns::IS_MOD], lv[columns::IS_DIV]);
     for (input, &filter) in [
-        (&add_input, &lv[columns::IS_ADDMOD]),
-        (&sub_input, &lv[columns::IS_SUBMOD]),
-        (&mul_input, &lv[columns::IS_MULMOD]),
-        (&mod_input, &mod_div_filter),
+        (&add_input[..], &lv[columns::IS_ADDMOD]),
+        (&sub_input[..], &lv[columns::IS_SUBMOD]),
+        (&mul_input[..], &lv[columns::IS_MULMOD]),
+        (&mod_input[..], &mod_div_filter),
     ] {
         let mut constr_poly_copy = constr_poly;
         pol_sub_assign_ext_circuit(builder, &mut constr_poly_copy, input);
@@ -49,7 +49,8 @@
 //! as we're only verifying the lower half of A*B, we only need to
 //! know s(x) up to degree 14 (so that (x - β)*s(x) has degree 15). On
 //! the other hand, the coefficients of s(x) can be as large as
-//! 16*(β-2) or 20 bits.
+//! 16*(β-2) or 20 bits, and negative, so they are stored offset and
+//! split into two 16-bit limbs (see `AUX_COEFF_ABS_MAX`).
 //!
 //! Note that, unlike for the general modular multiplication (see the
 //! file `modular.rs`), we don't need to check that output is reduced,
@@ -64,7 +65,6 @@ use plonky2::iop::ext_target::ExtensionTarget;
 use crate::arithmetic::columns::*;
 use crate::arithmetic::utils::*;
 use crate::constraint_consumer::{ConstraintConsumer, RecursiveConstraintConsumer};
-use crate::range_check_error;
 
 pub fn generate<F: RichField>(lv: &mut [F; NUM_ARITH_COLUMNS]) {
     let input0 = read_value_i64_limbs(lv, MUL_INPUT_0);
@@ -96,23 +96,18 @@ pub fn generate<F: RichField>(lv: &mut [F; NUM_ARITH_COLUMNS]) {
     let mut aux_limbs = pol_remove_root_2exp::<LIMB_BITS, _, N_LIMBS>(unreduced_prod);
     aux_limbs[N_LIMBS - 1] = -cy;
 
-    lv[MUL_AUX_INPUT].copy_from_slice(&aux_limbs.map(|c| F::from_noncanonical_i64(c)));
+    write_aux_limbs(lv, MUL_AUX_INPUT_LO, MUL_AUX_INPUT_HI, &aux_limbs);
 }
 
 pub fn eval_packed_generic<P: PackedField>(
     lv: &[P; NUM_ARITH_COLUMNS],
     yield_constr: &mut ConstraintConsumer<P>,
 ) {
-    range_check_error!(MUL_INPUT_0, 16);
-    range_check_error!(MUL_INPUT_1, 16);
-    range_check_error!(MUL_OUTPUT, 16);
-    range_check_error!(MUL_AUX_INPUT, 20);
-
     let is_mul = lv[IS_MUL];
     let input0_limbs = read_value::<N_LIMBS, _>(lv, MUL_INPUT_0);
     let input1_limbs = read_value::<N_LIMBS, _>(lv, MUL_INPUT_1);
     let output_limbs = read_value::<N_LIMBS, _>(lv, MUL_OUTPUT);
-    let aux_limbs = read_value::<N_LIMBS, _>(lv, MUL_AUX_INPUT);
+    let aux_limbs = read_aux_limbs::<_, N_LIMBS>(lv, MUL_AUX_INPUT_LO, MUL_AUX_INPUT_HI);
 
     // Constraint poly holds the coefficients of the polynomial that
     // must be identically zero for this multiplication to be
@@ -154,7 +149,12 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
     let input0_limbs = read_value::<N_LIMBS, _>(lv, MUL_INPUT_0);
     let input1_limbs = read_value::<N_LIMBS, _>(lv, MUL_INPUT_1);
     let output_limbs = read_value::<N_LIMBS, _>(lv, MUL_OUTPUT);
-    let aux_limbs = read_value::<N_LIMBS, _>(lv, MUL_AUX_INPUT);
+    let aux_limbs = read_aux_limbs_ext_circuit::<_, D, N_LIMBS>(
+        builder,
+        lv,
+        MUL_AUX_INPUT_LO,
+        MUL_AUX_INPUT_HI,
+    );
 
     let mut constr_poly = pol_mul_lo_ext_circuit(builder, input0_limbs, input1_limbs);
     pol_sub_assign_ext_circuit(builder, &mut constr_poly, &output_limbs);
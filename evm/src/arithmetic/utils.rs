@@ -1,61 +1,78 @@
 use std::ops::{Add, AddAssign, Mul, Neg, Range, Shr, Sub, SubAssign};
 
 use plonky2::field::extension::Extendable;
+use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::ext_target::ExtensionTarget;
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 
-use crate::arithmetic::columns::{NUM_ARITH_COLUMNS, N_LIMBS};
+use crate::arithmetic::columns::{LIMB_BITS, NUM_ARITH_COLUMNS, N_LIMBS};
 
-/// Emit an error message regarding unchecked range assumptions.
-/// Assumes the values in `cols` are `[cols[0], cols[0] + 1, ...,
-/// cols[0] + cols.len() - 1]`.
+/// An upper bound on the absolute value of the coefficients of the
+/// auxiliary polynomial s(x) used by MUL and the modular operations.
 ///
-/// TODO: Hamish to delete this when he has implemented and integrated
-/// range checks.
-pub(crate) fn _range_check_error<const RC_BITS: u32>(
-    _file: &str,
-    _line: u32,
-    _cols: Range<usize>,
-    _signedness: &str,
+/// Those coefficients don't fit in a 16-bit limb, and can be
+/// negative, so each coefficient c is stored as the two 16-bit limbs
+/// of c + AUX_COEFF_ABS_MAX, which are range-checked like every other
+/// limb. That bounds |c| by 2^32, which is small enough for the
+/// constraint polynomials not to wrap around the field order.
+pub(crate) const AUX_COEFF_ABS_MAX: i64 = 1 << 22;
+
+/// Write the coefficients `aux` of an auxiliary polynomial into the
+/// columns `lo_cols` and `hi_cols` of `row`, as described in
+/// `AUX_COEFF_ABS_MAX`.
+pub(crate) fn write_aux_limbs<F: RichField>(
+    row: &mut [F; NUM_ARITH_COLUMNS],
+    lo_cols: Range<usize>,
+    hi_cols: Range<usize>,
+    aux: &[i64],
 ) {
-    // error!(
-    //     "{}:{}: arithmetic unit skipped {}-bit {} range-checks on columns {}--{}: not yet implemented",
-    //     line,
-    //     file,
-    //     RC_BITS,
-    //     signedness,
-    //     cols.start,
-    //     cols.end - 1,
-    // );
+    debug_assert!(lo_cols.len() == aux.len() && hi_cols.len() == aux.len());
+    for ((lo, hi), &c) in lo_cols.zip(hi_cols).zip(aux) {
+        let c = c + AUX_COEFF_ABS_MAX;
+        debug_assert!((0..2 * AUX_COEFF_ABS_MAX).contains(&c));
+        row[lo] = F::from_canonical_i64(c & ((1 << LIMB_BITS) - 1));
+        row[hi] = F::from_canonical_i64(c >> LIMB_BITS);
+    }
+}
+
+/// Read the coefficients of an auxiliary polynomial written by
+/// `write_aux_limbs` into an array of length `N`, padding it with
+/// zeros.
+pub(crate) fn read_aux_limbs<P: PackedField, const N: usize>(
+    row: &[P; NUM_ARITH_COLUMNS],
+    lo_cols: Range<usize>,
+    hi_cols: Range<usize>,
+) -> [P; N] {
+    let base = P::Scalar::from_canonical_u64(1 << LIMB_BITS);
+    let offset = P::Scalar::from_canonical_u64(AUX_COEFF_ABS_MAX as u64);
+    let mut aux = [P::ZEROS; N];
+    for ((c, lo), hi) in aux.iter_mut().zip(lo_cols).zip(hi_cols) {
+        *c = row[lo] + row[hi] * base - offset;
+    }
+    aux
 }
 
-#[macro_export]
-macro_rules! range_check_error {
-    ($cols:ident, $rc_bits:expr) => {
-        $crate::arithmetic::utils::_range_check_error::<$rc_bits>(
-            file!(),
-            line!(),
-            $cols,
-            "unsigned",
-        );
-    };
-    ($cols:ident, $rc_bits:expr, signed) => {
-        $crate::arithmetic::utils::_range_check_error::<$rc_bits>(
-            file!(),
-            line!(),
-            $cols,
-            "signed",
-        );
-    };
-    ([$cols:ident], $rc_bits:expr) => {
-        $crate::arithmetic::utils::_range_check_error::<$rc_bits>(
-            file!(),
-            line!(),
-            &[$cols],
-            "unsigned",
-        );
-    };
+pub(crate) fn read_aux_limbs_ext_circuit<
+    F: RichField + Extendable<D>,
+    const D: usize,
+    const N: usize,
+>(
+    builder: &mut CircuitBuilder<F, D>,
+    row: &[ExtensionTarget<D>; NUM_ARITH_COLUMNS],
+    lo_cols: Range<usize>,
+    hi_cols: Range<usize>,
+) -> [ExtensionTarget<D>; N] {
+    let base = F::from_canonical_u64(1 << LIMB_BITS);
+    let offset = F::from_canonical_u64(AUX_COEFF_ABS_MAX as u64);
+    let zero = builder.zero_extension();
+    let mut aux = [zero; N];
+    for ((c, lo), hi) in aux.iter_mut().zip(lo_cols).zip(hi_cols) {
+        let t = builder.mul_const_add_extension(base, row[hi], row[lo]);
+        *c = builder.add_const_extension(t, -offset);
+    }
+    aux
 }
 
 /// Return an array of `N` zeros of type T.
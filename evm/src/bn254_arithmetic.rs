@@ -0,0 +1,390 @@
+//! Arithmetic in the BN254 base field and the tower of extensions used by the pairing:
+//!     Fp2  = Fp[i] / (i^2 + 1)
+//!     Fp6  = Fp2[t] / (t^3 - (9 + i))
+//!     Fp12 = Fp6[z] / (z^2 - t)
+//! An Fp12 element is laid out in kernel memory as 12 consecutive words, in the order given by
+//! `Fp12::to_words`.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use ethereum_types::{U256, U512};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// The order of the BN254 base field.
+pub const BN_BASE: U256 = U256([
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fp {
+    pub val: U256,
+}
+
+impl Fp {
+    pub fn new(val: U256) -> Fp {
+        Fp { val: val % BN_BASE }
+    }
+
+    pub const ZERO: Fp = Fp { val: U256::zero() };
+    pub const UNIT: Fp = Fp { val: U256::one() };
+
+    pub fn pow(self, e: U256) -> Fp {
+        let mut current = self;
+        let mut product = Fp::UNIT;
+        for j in 0..e.bits() {
+            if e.bit(j) {
+                product = product * current;
+            }
+            current = current * current;
+        }
+        product
+    }
+
+    pub fn inv(self) -> Fp {
+        self.pow(BN_BASE - 2)
+    }
+}
+
+impl Distribution<Fp> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fp {
+        Fp::new(U256(rng.gen::<[u64; 4]>()))
+    }
+}
+
+impl Add for Fp {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Doesn't overflow since both values are less than BN_BASE < 2^255.
+        Fp::new(self.val + other.val)
+    }
+}
+
+impl Neg for Fp {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fp::new(BN_BASE - self.val)
+    }
+}
+
+impl Sub for Fp {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Fp {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (_, product) = self.val.full_mul(other.val).div_mod(U512::from(BN_BASE));
+        Fp {
+            val: U256::try_from(product).unwrap(),
+        }
+    }
+}
+
+/// The element `re + im * i` of Fp2.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fp2 {
+    pub re: Fp,
+    pub im: Fp,
+}
+
+impl Fp2 {
+    pub const ZERO: Fp2 = Fp2 {
+        re: Fp::ZERO,
+        im: Fp::ZERO,
+    };
+    pub const UNIT: Fp2 = Fp2 {
+        re: Fp::UNIT,
+        im: Fp::ZERO,
+    };
+
+    pub fn scale(self, x: Fp) -> Fp2 {
+        Fp2 {
+            re: x * self.re,
+            im: x * self.im,
+        }
+    }
+
+    pub fn conj(self) -> Fp2 {
+        Fp2 {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
+    /// Multiplication by the non-residue `9 + i` which defines Fp6.
+    pub fn mul_xi(self) -> Fp2 {
+        let nine = Fp::new(9.into());
+        Fp2 {
+            re: nine * self.re - self.im,
+            im: self.re + nine * self.im,
+        }
+    }
+
+    pub fn inv(self) -> Fp2 {
+        let norm = self.re * self.re + self.im * self.im;
+        self.conj().scale(norm.inv())
+    }
+}
+
+impl Distribution<Fp2> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fp2 {
+        Fp2 {
+            re: rng.gen(),
+            im: rng.gen(),
+        }
+    }
+}
+
+impl Add for Fp2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Fp2 {
+            re: self.re + other.re,
+            im: self.im + other.im,
+        }
+    }
+}
+
+impl Neg for Fp2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fp2 {
+            re: -self.re,
+            im: -self.im,
+        }
+    }
+}
+
+impl Sub for Fp2 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Fp2 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Fp2 {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+}
+
+/// The element `t0 + t1 * t + t2 * t^2` of Fp6.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fp6 {
+    pub t0: Fp2,
+    pub t1: Fp2,
+    pub t2: Fp2,
+}
+
+impl Fp6 {
+    pub const ZERO: Fp6 = Fp6 {
+        t0: Fp2::ZERO,
+        t1: Fp2::ZERO,
+        t2: Fp2::ZERO,
+    };
+    pub const UNIT: Fp6 = Fp6 {
+        t0: Fp2::UNIT,
+        t1: Fp2::ZERO,
+        t2: Fp2::ZERO,
+    };
+
+    /// Multiplication by `t`.
+    pub fn sh(self) -> Fp6 {
+        Fp6 {
+            t0: self.t2.mul_xi(),
+            t1: self.t0,
+            t2: self.t1,
+        }
+    }
+
+    pub fn inv(self) -> Fp6 {
+        let Fp6 { t0, t1, t2 } = self;
+        let a = t0 * t0 - (t1 * t2).mul_xi();
+        let b = (t2 * t2).mul_xi() - t0 * t1;
+        let c = t1 * t1 - t0 * t2;
+        let norm = t0 * a + (t2 * b + t1 * c).mul_xi();
+        let norm_inv = norm.inv();
+        Fp6 {
+            t0: a * norm_inv,
+            t1: b * norm_inv,
+            t2: c * norm_inv,
+        }
+    }
+}
+
+impl Distribution<Fp6> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fp6 {
+        Fp6 {
+            t0: rng.gen(),
+            t1: rng.gen(),
+            t2: rng.gen(),
+        }
+    }
+}
+
+impl Add for Fp6 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Fp6 {
+            t0: self.t0 + other.t0,
+            t1: self.t1 + other.t1,
+            t2: self.t2 + other.t2,
+        }
+    }
+}
+
+impl Neg for Fp6 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fp6 {
+            t0: -self.t0,
+            t1: -self.t1,
+            t2: -self.t2,
+        }
+    }
+}
+
+impl Sub for Fp6 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Fp6 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let Fp6 { t0, t1, t2 } = self;
+        let Fp6 {
+            t0: u0,
+            t1: u1,
+            t2: u2,
+        } = other;
+        Fp6 {
+            t0: t0 * u0 + (t1 * u2 + t2 * u1).mul_xi(),
+            t1: t0 * u1 + t1 * u0 + (t2 * u2).mul_xi(),
+            t2: t0 * u2 + t1 * u1 + t2 * u0,
+        }
+    }
+}
+
+/// The element `z0 + z1 * z` of Fp12.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fp12 {
+    pub z0: Fp6,
+    pub z1: Fp6,
+}
+
+impl Fp12 {
+    pub const UNIT: Fp12 = Fp12 {
+        z0: Fp6::UNIT,
+        z1: Fp6::ZERO,
+    };
+
+    /// The conjugate `z0 - z1 * z`, which is also the Frobenius map `x -> x^(p^6)`.
+    pub fn conj(self) -> Fp12 {
+        Fp12 {
+            z0: self.z0,
+            z1: -self.z1,
+        }
+    }
+
+    pub fn inv(self) -> Fp12 {
+        let Fp12 { z0, z1 } = self;
+        let norm_inv = (z0 * z0 - (z1 * z1).sh()).inv();
+        Fp12 {
+            z0: z0 * norm_inv,
+            z1: -(z1 * norm_inv),
+        }
+    }
+
+    pub fn pow(self, e: U256) -> Fp12 {
+        let mut current = self;
+        let mut product = Fp12::UNIT;
+        for j in 0..e.bits() {
+            if e.bit(j) {
+                product = product * current;
+            }
+            current = current * current;
+        }
+        product
+    }
+
+    /// The Frobenius map `x -> x^(p^n)`, computed by exponentiation.
+    pub fn frob(self, n: usize) -> Fp12 {
+        (0..n).fold(self, |x, _| x.pow(BN_BASE))
+    }
+
+    pub fn from_words(words: &[U256]) -> Fp12 {
+        assert_eq!(words.len(), 12);
+        let fp2 = |j: usize| Fp2 {
+            re: Fp::new(words[j]),
+            im: Fp::new(words[j + 1]),
+        };
+        let fp6 = |j: usize| Fp6 {
+            t0: fp2(j),
+            t1: fp2(j + 2),
+            t2: fp2(j + 4),
+        };
+        Fp12 {
+            z0: fp6(0),
+            z1: fp6(6),
+        }
+    }
+
+    pub fn to_words(self) -> Vec<U256> {
+        [self.z0, self.z1]
+            .into_iter()
+            .flat_map(|x| [x.t0, x.t1, x.t2])
+            .flat_map(|x| [x.re.val, x.im.val])
+            .collect()
+    }
+}
+
+impl Distribution<Fp12> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fp12 {
+        Fp12 {
+            z0: rng.gen(),
+            z1: rng.gen(),
+        }
+    }
+}
+
+impl Mul for Fp12 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let Fp12 { z0, z1 } = self;
+        let Fp12 { z0: w0, z1: w1 } = other;
+        let h0 = z0 * w0;
+        let h1 = z1 * w1;
+        let h01 = (z0 + z1) * (w0 + w1);
+        Fp12 {
+            z0: h0 + h1.sh(),
+            z1: h01 - (h0 + h1),
+        }
+    }
+}
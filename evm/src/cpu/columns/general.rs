@@ -10,6 +10,7 @@ pub(crate) union CpuGeneralColumnsView<T: Copy> {
     logic: CpuLogicView<T>,
     jumps: CpuJumpsView<T>,
     shift: CpuShiftView<T>,
+    exception: CpuExceptionView<T>,
 }
 
 impl<T: Copy> CpuGeneralColumnsView<T> {
@@ -52,6 +53,16 @@ impl<T: Copy> CpuGeneralColumnsView<T> {
     pub(crate) fn shift_mut(&mut self) -> &mut CpuShiftView<T> {
         unsafe { &mut self.shift }
     }
+
+    // SAFETY: Each view is a valid interpretation of the underlying array.
+    pub(crate) fn exception(&self) -> &CpuExceptionView<T> {
+        unsafe { &self.exception }
+    }
+
+    // SAFETY: Each view is a valid interpretation of the underlying array.
+    pub(crate) fn exception_mut(&mut self) -> &mut CpuExceptionView<T> {
+        unsafe { &mut self.exception }
+    }
 }
 
 impl<T: Copy + PartialEq> PartialEq<Self> for CpuGeneralColumnsView<T> {
@@ -110,5 +121,11 @@ pub(crate) struct CpuShiftView<T: Copy> {
     pub(crate) high_limb_sum_inv: T,
 }
 
+#[derive(Copy, Clone)]
+pub(crate) struct CpuExceptionView<T: Copy> {
+    // The exception code, in little-endian bits. It selects the kernel's handler.
+    pub(crate) exc_code_bits: [T; 3],
+}
+
 // `u8` is guaranteed to have a `size_of` of 1.
 pub const NUM_SHARED_COLUMNS: usize = size_of::<CpuGeneralColumnsView<u8>>();
@@ -62,6 +62,18 @@ pub struct CpuColumnsView<T: Copy> {
     /// If CPU cycle: We're in kernel (privileged) mode.
     pub is_kernel_mode: T,
 
+    /// If CPU cycle: Gas used by the current context.
+    pub gas: T,
+
+    /// If CPU cycle: The gas limit of the current context. Only constrained in user mode, where it
+    /// is read from memory by `EXIT_KERNEL`, and then carried over from one instruction to the next.
+    pub gas_limit: T,
+
+    /// If CPU cycle in user mode, or executing `GAS`: The gas left after this instruction, i.e.
+    /// `gas_limit` minus the new gas used, broken up into bits in little-endian order. This shows
+    /// that the instruction doesn't exceed the gas limit.
+    pub gas_left_bits: [T; 32],
+
     /// If CPU cycle: flags for EVM instructions (a few cannot be shared; see the comments in
     /// `OpsColumnsView`).
     pub op: OpsColumnsView<T>,
@@ -54,6 +54,7 @@ pub struct OpsColumnsView<T: Copy> {
     pub mstore_general: T,
 
     pub syscall: T,
+    pub exception: T,
 }
 
 // `u8` is guaranteed to have a `size_of` of 1.
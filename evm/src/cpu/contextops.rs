@@ -1,12 +1,15 @@
 use plonky2::field::extension::Extendable;
 use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
 use plonky2::iop::ext_target::ExtensionTarget;
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 
 use crate::constraint_consumer::{ConstraintConsumer, RecursiveConstraintConsumer};
 use crate::cpu::columns::CpuColumnsView;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
 use crate::cpu::membus::NUM_GP_CHANNELS;
+use crate::memory::segments::Segment;
 
 fn eval_packed_get<P: PackedField>(
     lv: &CpuColumnsView<P>,
@@ -46,6 +49,59 @@ fn eval_packed_set<P: PackedField>(
     let filter = lv.op.set_context;
     let pop_channel = lv.mem_channels[0];
     yield_constr.constraint_transition(filter * (pop_channel.value[0] - nv.context));
+
+    let stack_size_segment = P::Scalar::from_canonical_u64(Segment::ContextMetadata as u64);
+    let stack_size_field = P::Scalar::from_canonical_u64(ContextMetadata::StackSize as u64);
+
+    // The length of the old context's stack, after popping the new context, is saved in its
+    // metadata.
+    let write_channel = lv.mem_channels[1];
+    yield_constr.constraint(filter * (write_channel.used - P::ONES));
+    yield_constr.constraint(filter * write_channel.is_read);
+    yield_constr.constraint(filter * (write_channel.addr_context - lv.context));
+    yield_constr.constraint(filter * (write_channel.addr_segment - stack_size_segment));
+    yield_constr.constraint(filter * (write_channel.addr_virtual - stack_size_field));
+    yield_constr.constraint(filter * (write_channel.value[0] - (lv.stack_len - P::ONES)));
+    for &limb in &write_channel.value[1..] {
+        yield_constr.constraint(filter * limb);
+    }
+
+    // The length of the new context's stack is restored from its metadata.
+    let read_channel = lv.mem_channels[2];
+    yield_constr.constraint(filter * (read_channel.used - P::ONES));
+    yield_constr.constraint(filter * (read_channel.is_read - P::ONES));
+    yield_constr.constraint_transition(filter * (read_channel.addr_context - nv.context));
+    yield_constr.constraint(filter * (read_channel.addr_segment - stack_size_segment));
+    yield_constr.constraint(filter * (read_channel.addr_virtual - stack_size_field));
+    yield_constr.constraint_transition(filter * (read_channel.value[0] - nv.stack_len));
+
+    let gas_used_field = P::Scalar::from_canonical_u64(ContextMetadata::GasUsed as u64);
+    let base = P::Scalar::from_canonical_u64(1 << 32);
+
+    // Likewise, the gas used by the old context is saved in its metadata...
+    let write_channel = lv.mem_channels[3];
+    yield_constr.constraint(filter * (write_channel.used - P::ONES));
+    yield_constr.constraint(filter * write_channel.is_read);
+    yield_constr.constraint(filter * (write_channel.addr_context - lv.context));
+    yield_constr.constraint(filter * (write_channel.addr_segment - stack_size_segment));
+    yield_constr.constraint(filter * (write_channel.addr_virtual - gas_used_field));
+    yield_constr.constraint(
+        filter * (write_channel.value[0] + write_channel.value[1] * base - lv.gas),
+    );
+    for &limb in &write_channel.value[2..] {
+        yield_constr.constraint(filter * limb);
+    }
+
+    // ... and that of the new context is restored.
+    let read_channel = lv.mem_channels[4];
+    yield_constr.constraint(filter * (read_channel.used - P::ONES));
+    yield_constr.constraint(filter * (read_channel.is_read - P::ONES));
+    yield_constr.constraint_transition(filter * (read_channel.addr_context - nv.context));
+    yield_constr.constraint(filter * (read_channel.addr_segment - stack_size_segment));
+    yield_constr.constraint(filter * (read_channel.addr_virtual - gas_used_field));
+    yield_constr.constraint_transition(
+        filter * (read_channel.value[0] + read_channel.value[1] * base - nv.gas),
+    );
 }
 
 fn eval_ext_circuit_set<F: RichField + Extendable<D>, const D: usize>(
@@ -60,6 +116,190 @@ fn eval_ext_circuit_set<F: RichField + Extendable<D>, const D: usize>(
     let diff = builder.sub_extension(pop_channel.value[0], nv.context);
     let constr = builder.mul_extension(filter, diff);
     yield_constr.constraint_transition(builder, constr);
+
+    let stack_size_segment = F::from_canonical_u64(Segment::ContextMetadata as u64);
+    let stack_size_field = F::from_canonical_u64(ContextMetadata::StackSize as u64);
+
+    let write_channel = lv.mem_channels[1];
+    {
+        let constr = builder.mul_sub_extension(filter, write_channel.used, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.mul_extension(filter, write_channel.is_read);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(write_channel.addr_context, lv.context);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -stack_size_segment,
+            filter,
+            write_channel.addr_segment,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -stack_size_field,
+            filter,
+            write_channel.addr_virtual,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(write_channel.value[0], lv.stack_len);
+        let constr = builder.mul_add_extension(filter, diff, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    for &limb in &write_channel.value[1..] {
+        let constr = builder.mul_extension(filter, limb);
+        yield_constr.constraint(builder, constr);
+    }
+
+    let read_channel = lv.mem_channels[2];
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.used, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.is_read, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(read_channel.addr_context, nv.context);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -stack_size_segment,
+            filter,
+            read_channel.addr_segment,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -stack_size_field,
+            filter,
+            read_channel.addr_virtual,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(read_channel.value[0], nv.stack_len);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+
+    let gas_used_field = F::from_canonical_u64(ContextMetadata::GasUsed as u64);
+    let base = F::from_canonical_u64(1 << 32);
+
+    let write_channel = lv.mem_channels[3];
+    {
+        let constr = builder.mul_sub_extension(filter, write_channel.used, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.mul_extension(filter, write_channel.is_read);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(write_channel.addr_context, lv.context);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -stack_size_segment,
+            filter,
+            write_channel.addr_segment,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -gas_used_field,
+            filter,
+            write_channel.addr_virtual,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let gas = builder.mul_const_add_extension(
+            base,
+            write_channel.value[1],
+            write_channel.value[0],
+        );
+        let diff = builder.sub_extension(gas, lv.gas);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    for &limb in &write_channel.value[2..] {
+        let constr = builder.mul_extension(filter, limb);
+        yield_constr.constraint(builder, constr);
+    }
+
+    let read_channel = lv.mem_channels[4];
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.used, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.is_read, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(read_channel.addr_context, nv.context);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -stack_size_segment,
+            filter,
+            read_channel.addr_segment,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -gas_used_field,
+            filter,
+            read_channel.addr_virtual,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let gas = builder.mul_const_add_extension(
+            base,
+            read_channel.value[1],
+            read_channel.value[0],
+        );
+        let diff = builder.sub_extension(gas, nv.gas);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
 }
 
 pub fn eval_packed<P: PackedField>(
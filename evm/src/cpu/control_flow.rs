@@ -88,13 +88,15 @@ pub fn eval_packed_generic<P: PackedField>(
 
     // If a non-CPU cycle row is followed by a CPU cycle row, then:
     //  - the `program_counter` of the CPU cycle row is `main` (the entry point of our kernel),
-    //  - execution is in kernel mode, and
-    //  - the stack is empty.
+    //  - execution is in kernel mode,
+    //  - the stack is empty, and
+    //  - no gas has been used.
     let is_last_noncpu_cycle = (lv.is_cpu_cycle - P::ONES) * nv.is_cpu_cycle;
     let pc_diff = nv.program_counter - get_start_pc::<P::Scalar>();
     yield_constr.constraint_transition(is_last_noncpu_cycle * pc_diff);
     yield_constr.constraint_transition(is_last_noncpu_cycle * (nv.is_kernel_mode - P::ONES));
     yield_constr.constraint_transition(is_last_noncpu_cycle * nv.stack_len);
+    yield_constr.constraint_transition(is_last_noncpu_cycle * nv.gas);
 
     // The last row must be a CPU cycle row.
     yield_constr.constraint_last_row(lv.is_cpu_cycle - P::ONES);
@@ -136,8 +138,9 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
 
     // If a non-CPU cycle row is followed by a CPU cycle row, then:
     //  - the `program_counter` of the CPU cycle row is `main` (the entry point of our kernel),
-    //  - execution is in kernel mode, and
-    //  - the stack is empty.
+    //  - execution is in kernel mode,
+    //  - the stack is empty, and
+    //  - no gas has been used.
     {
         let is_last_noncpu_cycle =
             builder.mul_sub_extension(lv.is_cpu_cycle, nv.is_cpu_cycle, nv.is_cpu_cycle);
@@ -159,6 +162,10 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
         // Start with empty stack
         let kernel_constr = builder.mul_extension(is_last_noncpu_cycle, nv.stack_len);
         yield_constr.constraint_transition(builder, kernel_constr);
+
+        // Start with no gas used
+        let gas_constr = builder.mul_extension(is_last_noncpu_cycle, nv.gas);
+        yield_constr.constraint_transition(builder, gas_constr);
     }
 
     // The last row must be a CPU cycle row.
@@ -8,11 +8,13 @@ use plonky2::field::packed::PackedField;
 use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
 
+use crate::arithmetic::arithmetic_stark::CtlLayout;
 use crate::constraint_consumer::{ConstraintConsumer, RecursiveConstraintConsumer};
 use crate::cpu::columns::{CpuColumnsView, COL_MAP, NUM_CPU_COLUMNS};
+use crate::cpu::membus::NUM_GP_CHANNELS;
 use crate::cpu::{
-    bootstrap_kernel, contextops, control_flow, decode, dup_swap, jumps, membus, memio, modfp254,
-    pc, shift, simple_logic, stack, stack_bounds, syscalls,
+    bootstrap_kernel, contextops, control_flow, decode, dup_swap, gas, jumps, membus, memio,
+    modfp254, pc, shift, simple_logic, stack, stack_bounds, syscalls,
 };
 use crate::cross_table_lookup::Column;
 use crate::memory::segments::Segment;
@@ -56,6 +58,67 @@ pub fn ctl_filter_logic<F: Field>() -> Column<F> {
     Column::sum([COL_MAP.op.and, COL_MAP.op.or, COL_MAP.op.xor])
 }
 
+/// The columns and filters of the CPU lookups into the arithmetic table for the given group of
+/// operations. There is one lookup per way of mapping the GP channels to the arithmetic inputs.
+pub fn ctl_data_arithmetic<F: Field>(layout: CtlLayout) -> Vec<(Vec<Column<F>>, Column<F>)> {
+    let op = COL_MAP.op;
+    match layout {
+        CtlLayout::Basic => vec![
+            ctl_arithmetic_looking(&[&[op.add], &[op.mul], &[op.sub]], &[0, 1]),
+            // SHL multiplies the value in channel 1 by the power of two read in channel 2.
+            ctl_arithmetic_looking(&[&[], &[op.shl], &[]], &[1, 2]),
+        ],
+        CtlLayout::Compare => vec![ctl_arithmetic_looking(&[&[op.lt], &[op.gt]], &[0, 1])],
+        CtlLayout::Modular => vec![
+            // The Fp254 operations find the modulus in channel 2, like ADDMOD and MULMOD.
+            ctl_arithmetic_looking(
+                &[
+                    &[op.addmod, op.addfp254],
+                    &[op.subfp254],
+                    &[op.mulmod, op.mulfp254],
+                    &[],
+                ],
+                &[0, 1, 2],
+            ),
+            // MOD ignores its second input; the arithmetic table repeats the modulus there.
+            ctl_arithmetic_looking(&[&[], &[], &[], &[op.mod_]], &[0, 1, 1]),
+        ],
+        CtlLayout::Div => vec![
+            ctl_arithmetic_looking(&[&[op.div]], &[0, 1]),
+            // SHR divides the value in channel 1 by the power of two read in channel 2.
+            ctl_arithmetic_looking(&[&[op.shr]], &[1, 2]),
+        ],
+    }
+}
+
+/// Builds a lookup into the arithmetic table. `ops` gives, for each operation flag of the looked
+/// group, the CPU operation flags mapping to it, and `input_channels` gives the GP channels holding
+/// the inputs. The output is always in the last GP channel.
+fn ctl_arithmetic_looking<F: Field>(
+    ops: &[&[usize]],
+    input_channels: &[usize],
+) -> (Vec<Column<F>>, Column<F>) {
+    let mut res = ops
+        .iter()
+        .map(|&flags| {
+            if flags.is_empty() {
+                Column::zero()
+            } else {
+                Column::sum(flags)
+            }
+        })
+        .collect_vec();
+    for &channel in input_channels {
+        res.extend(Column::singles(COL_MAP.mem_channels[channel].value));
+    }
+    res.extend(Column::singles(
+        COL_MAP.mem_channels[NUM_GP_CHANNELS - 1].value,
+    ));
+
+    let filter = Column::sum(ops.iter().flat_map(|flags| flags.iter()));
+    (res, filter)
+}
+
 pub const MEM_CODE_CHANNEL_IDX: usize = 0;
 pub const MEM_GP_CHANNELS_IDX_START: usize = MEM_CODE_CHANNEL_IDX + 1;
 
@@ -145,6 +208,7 @@ impl<F: RichField + Extendable<D>, const D: usize> Stark<F, D> for CpuStark<F, D
         control_flow::eval_packed_generic(local_values, next_values, yield_constr);
         decode::eval_packed_generic(local_values, &mut dummy_yield_constr);
         dup_swap::eval_packed(local_values, yield_constr);
+        gas::eval_packed(local_values, next_values, yield_constr);
         jumps::eval_packed(local_values, next_values, yield_constr);
         membus::eval_packed(local_values, yield_constr);
         memio::eval_packed(local_values, yield_constr);
@@ -174,6 +238,7 @@ impl<F: RichField + Extendable<D>, const D: usize> Stark<F, D> for CpuStark<F, D
         control_flow::eval_ext_circuit(builder, local_values, next_values, yield_constr);
         decode::eval_ext_circuit(builder, local_values, &mut dummy_yield_constr);
         dup_swap::eval_ext_circuit(builder, local_values, yield_constr);
+        gas::eval_ext_circuit(builder, local_values, next_values, yield_constr);
         jumps::eval_ext_circuit(builder, local_values, next_values, yield_constr);
         membus::eval_ext_circuit(builder, local_values, yield_constr);
         memio::eval_ext_circuit(builder, local_values, yield_constr);
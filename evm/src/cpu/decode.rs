@@ -20,8 +20,8 @@ use crate::cpu::columns::{CpuColumnsView, COL_MAP};
 /// The exception is the PANIC instruction which is user-only without a corresponding kernel block.
 /// This makes the proof unverifiable when PANIC is executed in kernel mode, which is the intended
 /// behavior.
-/// Note: invalid opcodes are not represented here. _Any_ opcode is permitted to decode to
-/// `is_invalid`. The kernel then verifies that the opcode was _actually_ invalid.
+/// Note: invalid opcodes are not represented here. In user mode, _any_ opcode is permitted to raise
+/// an `exception` instead. The kernel then verifies that the exception _actually_ occurred.
 const OPCODES: [(u8, usize, bool, usize); 38] = [
     // (start index of block, number of top bits to check (log2), kernel-only, flag column)
     (0x01, 0, false, COL_MAP.op.add),
@@ -129,10 +129,13 @@ pub fn generate<F: RichField>(lv: &mut CpuColumnsView<F>) {
     assert!(kernel <= 1);
     let kernel = kernel != 0;
 
+    // An exception overrides whatever the opcode would otherwise decode to.
+    let exception = lv.op.exception.is_one();
+
     for (oc, block_length, kernel_only, col) in OPCODES {
         let available = !kernel_only || kernel;
         let opcode_match = top_bits[8 - block_length] == oc;
-        let flag = available && opcode_match;
+        let flag = !exception && available && opcode_match;
         lv[col] = F::from_bool(flag);
     }
 }
@@ -172,12 +175,17 @@ pub fn eval_packed_generic<P: PackedField>(
         let flag = lv[flag_col];
         yield_constr.constraint(cycle_filter * flag * (flag - P::ONES));
     }
-    // Now check that they sum to 0 or 1.
+    let exception = lv.op.exception;
+    yield_constr.constraint(cycle_filter * exception * (exception - P::ONES));
+    // Now check that they sum to 0 or 1, including the exception flag.
     let flag_sum: P = OPCODES
         .into_iter()
         .map(|(_, _, _, flag_col)| lv[flag_col])
-        .sum::<P>();
+        .sum::<P>()
+        + exception;
     yield_constr.constraint(cycle_filter * flag_sum * (flag_sum - P::ONES));
+    // Exceptions can only be raised in user mode. The kernel must not fault.
+    yield_constr.constraint(cycle_filter * exception * kernel_mode);
 
     // Finally, classify all opcodes, together with the kernel flag, into blocks
     for (oc, block_length, kernel_only, col) in OPCODES {
@@ -240,9 +248,15 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
         let constr = builder.mul_extension(cycle_filter, constr);
         yield_constr.constraint(builder, constr);
     }
-    // Now check that they sum to 0 or 1.
+    let exception = lv.op.exception;
+    {
+        let constr = builder.mul_sub_extension(exception, exception, exception);
+        let constr = builder.mul_extension(cycle_filter, constr);
+        yield_constr.constraint(builder, constr);
+    }
+    // Now check that they sum to 0 or 1, including the exception flag.
     {
-        let mut flag_sum = builder.zero_extension();
+        let mut flag_sum = exception;
         for (_, _, _, flag_col) in OPCODES {
             let flag = lv[flag_col];
             flag_sum = builder.add_extension(flag_sum, flag);
@@ -251,6 +265,12 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
         let constr = builder.mul_extension(cycle_filter, constr);
         yield_constr.constraint(builder, constr);
     }
+    // Exceptions can only be raised in user mode. The kernel must not fault.
+    {
+        let constr = builder.mul_extension(exception, kernel_mode);
+        let constr = builder.mul_extension(cycle_filter, constr);
+        yield_constr.constraint(builder, constr);
+    }
 
     // Finally, classify all opcodes, together with the kernel flag, into blocks
     for (oc, block_length, kernel_only, col) in OPCODES {
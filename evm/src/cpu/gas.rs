@@ -0,0 +1,431 @@
+use itertools::izip;
+use plonky2::field::extension::Extendable;
+use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::constraint_consumer::{ConstraintConsumer, RecursiveConstraintConsumer};
+use crate::cpu::columns::ops::OpsColumnsView;
+use crate::cpu::columns::CpuColumnsView;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::membus::NUM_GP_CHANNELS;
+use crate::memory::segments::Segment;
+
+const KERNEL_ONLY_INSTR: Option<u32> = Some(0);
+const G_JUMPDEST: Option<u32> = Some(1);
+const G_BASE: Option<u32> = Some(2);
+const G_VERYLOW: Option<u32> = Some(3);
+const G_LOW: Option<u32> = Some(5);
+const G_MID: Option<u32> = Some(8);
+const G_HIGH: Option<u32> = Some(10);
+
+/// The gas charged for each operation executed in user mode. Operations which are only allowed
+/// in kernel mode cost nothing, and syscalls are charged by the kernel itself, using
+/// `CONSUME_GAS`. `None` marks the operations whose effect on the gas column is constrained
+/// separately.
+const SIMPLE_OPCODES: OpsColumnsView<Option<u32>> = OpsColumnsView {
+    add: G_VERYLOW,
+    mul: G_LOW,
+    sub: G_VERYLOW,
+    div: G_LOW,
+    mod_: G_LOW,
+    addmod: G_MID,
+    mulmod: G_MID,
+    addfp254: KERNEL_ONLY_INSTR,
+    mulfp254: KERNEL_ONLY_INSTR,
+    subfp254: KERNEL_ONLY_INSTR,
+    lt: G_VERYLOW,
+    gt: G_VERYLOW,
+    eq: G_VERYLOW,
+    iszero: G_VERYLOW,
+    and: G_VERYLOW,
+    or: G_VERYLOW,
+    xor: G_VERYLOW,
+    not: G_VERYLOW,
+    byte: G_VERYLOW,
+    shl: G_VERYLOW,
+    shr: G_VERYLOW,
+    keccak_general: KERNEL_ONLY_INSTR,
+    prover_input: KERNEL_ONLY_INSTR,
+    pop: G_BASE,
+    jump: G_MID,
+    jumpi: G_HIGH,
+    pc: G_BASE,
+    gas: G_BASE,
+    jumpdest: G_JUMPDEST,
+    push: G_VERYLOW,
+    dup: G_VERYLOW,
+    swap: G_VERYLOW,
+    get_context: KERNEL_ONLY_INSTR,
+    set_context: None,
+    consume_gas: None,
+    exit_kernel: Some(0),
+    mload_general: KERNEL_ONLY_INSTR,
+    mstore_general: KERNEL_ONLY_INSTR,
+    syscall: Some(0),
+    exception: Some(0),
+};
+
+/// Returns the gas charged for the given operation if it is executed in user mode, or `None` if
+/// it is not a simple operation.
+pub(crate) fn simple_opcode_gas<F: Field>(op: &OpsColumnsView<F>) -> Option<u64> {
+    izip!(op.into_iter(), SIMPLE_OPCODES.into_iter())
+        .find(|(flag, _)| flag.is_one())
+        .and_then(|(_, cost)| cost)
+        .map(u64::from)
+}
+
+fn eval_packed_accumulate<P: PackedField>(
+    lv: &CpuColumnsView<P>,
+    nv: &CpuColumnsView<P>,
+    yield_constr: &mut ConstraintConsumer<P>,
+) {
+    // Simple opcodes: the gas used increases by their cost, unless we're in kernel mode.
+    for (op, cost) in izip!(lv.op.into_iter(), SIMPLE_OPCODES.into_iter()) {
+        if let Some(cost) = cost {
+            let filter = lv.is_cpu_cycle * op;
+            let cost = P::Scalar::from_canonical_u32(cost);
+            yield_constr.constraint_transition(
+                filter * (nv.gas - lv.gas - (P::ONES - lv.is_kernel_mode) * cost),
+            );
+        }
+    }
+
+    // CONSUME_GAS adds the amount it pops to the gas used.
+    let filter = lv.is_cpu_cycle * lv.op.consume_gas;
+    let amount = lv.mem_channels[0].value;
+    let base = P::Scalar::from_canonical_u64(1 << 32);
+    yield_constr.constraint_transition(filter * (nv.gas - lv.gas - amount[0] - amount[1] * base));
+    for &limb in &amount[2..] {
+        yield_constr.constraint(filter * limb);
+    }
+
+    // The rows which continue a long PUSH have no operation flag, and don't change the gas used.
+    let is_continuation = lv.is_cpu_cycle * (P::ONES - lv.op.into_iter().sum::<P>());
+    yield_constr.constraint_transition(is_continuation * (nv.gas - lv.gas));
+}
+
+fn eval_ext_circuit_accumulate<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    lv: &CpuColumnsView<ExtensionTarget<D>>,
+    nv: &CpuColumnsView<ExtensionTarget<D>>,
+    yield_constr: &mut RecursiveConstraintConsumer<F, D>,
+) {
+    for (op, cost) in izip!(lv.op.into_iter(), SIMPLE_OPCODES.into_iter()) {
+        if let Some(cost) = cost {
+            let filter = builder.mul_extension(lv.is_cpu_cycle, op);
+            let cost = F::from_canonical_u32(cost);
+            // nv.gas - lv.gas - cost + is_kernel_mode * cost
+            let diff = builder.sub_extension(nv.gas, lv.gas);
+            let diff = builder.mul_const_add_extension(cost, lv.is_kernel_mode, diff);
+            let diff = builder.add_const_extension(diff, -cost);
+            let constr = builder.mul_extension(filter, diff);
+            yield_constr.constraint_transition(builder, constr);
+        }
+    }
+
+    let filter = builder.mul_extension(lv.is_cpu_cycle, lv.op.consume_gas);
+    let amount = lv.mem_channels[0].value;
+    let base = F::from_canonical_u64(1 << 32);
+    {
+        let diff = builder.sub_extension(nv.gas, lv.gas);
+        let diff = builder.sub_extension(diff, amount[0]);
+        let diff = builder.mul_const_add_extension(-base, amount[1], diff);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+    for &limb in &amount[2..] {
+        let constr = builder.mul_extension(filter, limb);
+        yield_constr.constraint(builder, constr);
+    }
+
+    {
+        let num_ops = builder.add_many_extension(*lv.op);
+        let is_continuation = builder.arithmetic_extension(
+            -F::ONE,
+            F::ONE,
+            lv.is_cpu_cycle,
+            num_ops,
+            lv.is_cpu_cycle,
+        );
+        let diff = builder.sub_extension(nv.gas, lv.gas);
+        let constr = builder.mul_extension(is_continuation, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+}
+
+fn eval_packed_gas<P: PackedField>(
+    lv: &CpuColumnsView<P>,
+    nv: &CpuColumnsView<P>,
+    yield_constr: &mut ConstraintConsumer<P>,
+) {
+    let filter = lv.is_cpu_cycle * lv.op.gas;
+    let base = P::Scalar::from_canonical_u64(1 << 32);
+
+    // The gas limit of the current context is read from its metadata.
+    let read_channel = lv.mem_channels[0];
+    let gas_limit_segment = P::Scalar::from_canonical_u64(Segment::ContextMetadata as u64);
+    let gas_limit_field = P::Scalar::from_canonical_u64(ContextMetadata::GasLimit as u64);
+    yield_constr.constraint(filter * (read_channel.used - P::ONES));
+    yield_constr.constraint(filter * (read_channel.is_read - P::ONES));
+    yield_constr.constraint(filter * (read_channel.addr_context - lv.context));
+    yield_constr.constraint(filter * (read_channel.addr_segment - gas_limit_segment));
+    yield_constr.constraint(filter * (read_channel.addr_virtual - gas_limit_field));
+    for &limb in &read_channel.value[2..] {
+        yield_constr.constraint(filter * limb);
+    }
+
+    // The gas left, after paying for this instruction, is pushed. It is given by its bits, so it
+    // can't wrap around.
+    let push_value = lv.mem_channels[NUM_GP_CHANNELS - 1].value;
+    let gas_limit = read_channel.value[0] + read_channel.value[1] * base;
+    let gas_left = gas_left_packed(lv);
+    yield_constr.constraint_transition(filter * (gas_left + nv.gas - gas_limit));
+    yield_constr.constraint(filter * (push_value[0] - gas_left));
+    for &limb in &push_value[1..] {
+        yield_constr.constraint(filter * limb);
+    }
+
+    for channel in &lv.mem_channels[1..NUM_GP_CHANNELS - 1] {
+        yield_constr.constraint(filter * channel.used);
+    }
+}
+
+fn eval_ext_circuit_gas<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    lv: &CpuColumnsView<ExtensionTarget<D>>,
+    nv: &CpuColumnsView<ExtensionTarget<D>>,
+    yield_constr: &mut RecursiveConstraintConsumer<F, D>,
+) {
+    let filter = builder.mul_extension(lv.is_cpu_cycle, lv.op.gas);
+    let base = F::from_canonical_u64(1 << 32);
+
+    let read_channel = lv.mem_channels[0];
+    let gas_limit_segment = F::from_canonical_u64(Segment::ContextMetadata as u64);
+    let gas_limit_field = F::from_canonical_u64(ContextMetadata::GasLimit as u64);
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.used, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.is_read, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(read_channel.addr_context, lv.context);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -gas_limit_segment,
+            filter,
+            read_channel.addr_segment,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -gas_limit_field,
+            filter,
+            read_channel.addr_virtual,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    for &limb in &read_channel.value[2..] {
+        let constr = builder.mul_extension(filter, limb);
+        yield_constr.constraint(builder, constr);
+    }
+
+    let push_value = lv.mem_channels[NUM_GP_CHANNELS - 1].value;
+    let gas_left = gas_left_ext_circuit(builder, lv);
+    {
+        let gas_limit =
+            builder.mul_const_add_extension(base, read_channel.value[1], read_channel.value[0]);
+        let diff = builder.add_extension(gas_left, nv.gas);
+        let diff = builder.sub_extension(diff, gas_limit);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(push_value[0], gas_left);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    for &limb in &push_value[1..] {
+        let constr = builder.mul_extension(filter, limb);
+        yield_constr.constraint(builder, constr);
+    }
+
+    for channel in &lv.mem_channels[1..NUM_GP_CHANNELS - 1] {
+        let constr = builder.mul_extension(filter, channel.used);
+        yield_constr.constraint(builder, constr);
+    }
+}
+
+/// The gas left after the current instruction, recombined from its bits.
+fn gas_left_packed<P: PackedField>(lv: &CpuColumnsView<P>) -> P {
+    lv.gas_left_bits
+        .into_iter()
+        .enumerate()
+        .map(|(i, bit)| bit * P::Scalar::from_canonical_u64(1 << i))
+        .sum()
+}
+
+fn gas_left_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    lv: &CpuColumnsView<ExtensionTarget<D>>,
+) -> ExtensionTarget<D> {
+    let mut gas_left = builder.zero_extension();
+    for (i, &bit) in lv.gas_left_bits.iter().enumerate() {
+        gas_left = builder.mul_const_add_extension(F::from_canonical_u64(1 << i), bit, gas_left);
+    }
+    gas_left
+}
+
+fn eval_packed_gas_limit<P: PackedField>(
+    lv: &CpuColumnsView<P>,
+    nv: &CpuColumnsView<P>,
+    yield_constr: &mut ConstraintConsumer<P>,
+) {
+    let base = P::Scalar::from_canonical_u64(1 << 32);
+
+    // `EXIT_KERNEL` reads the gas limit of the current context, which the kernel may have changed.
+    let filter = lv.is_cpu_cycle * lv.op.exit_kernel;
+    let read_channel = lv.mem_channels[1];
+    let gas_limit_segment = P::Scalar::from_canonical_u64(Segment::ContextMetadata as u64);
+    let gas_limit_field = P::Scalar::from_canonical_u64(ContextMetadata::GasLimit as u64);
+    yield_constr.constraint(filter * (read_channel.used - P::ONES));
+    yield_constr.constraint(filter * (read_channel.is_read - P::ONES));
+    yield_constr.constraint(filter * (read_channel.addr_context - lv.context));
+    yield_constr.constraint(filter * (read_channel.addr_segment - gas_limit_segment));
+    yield_constr.constraint(filter * (read_channel.addr_virtual - gas_limit_field));
+    yield_constr.constraint_transition(
+        filter * (read_channel.value[0] + read_channel.value[1] * base - nv.gas_limit),
+    );
+    for channel in &lv.mem_channels[2..] {
+        yield_constr.constraint(filter * channel.used);
+    }
+
+    // User mode instructions can't change the gas limit, so it is carried over to the next row.
+    let user_filter = lv.is_cpu_cycle * (P::ONES - lv.is_kernel_mode);
+    yield_constr.constraint_transition(user_filter * (nv.gas_limit - lv.gas_limit));
+
+    // An instruction executed in user mode mustn't exceed the gas limit, i.e. the gas left after it
+    // must be non-negative, which its bits show.
+    for bit in lv.gas_left_bits {
+        yield_constr.constraint(bit * (bit - P::ONES));
+    }
+    let gas_left = gas_left_packed(lv);
+    yield_constr.constraint_transition(user_filter * (lv.gas_limit - nv.gas - gas_left));
+}
+
+fn eval_ext_circuit_gas_limit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    lv: &CpuColumnsView<ExtensionTarget<D>>,
+    nv: &CpuColumnsView<ExtensionTarget<D>>,
+    yield_constr: &mut RecursiveConstraintConsumer<F, D>,
+) {
+    let base = F::from_canonical_u64(1 << 32);
+
+    let filter = builder.mul_extension(lv.is_cpu_cycle, lv.op.exit_kernel);
+    let read_channel = lv.mem_channels[1];
+    let gas_limit_segment = F::from_canonical_u64(Segment::ContextMetadata as u64);
+    let gas_limit_field = F::from_canonical_u64(ContextMetadata::GasLimit as u64);
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.used, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.mul_sub_extension(filter, read_channel.is_read, filter);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(read_channel.addr_context, lv.context);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -gas_limit_segment,
+            filter,
+            read_channel.addr_segment,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let constr = builder.arithmetic_extension(
+            F::ONE,
+            -gas_limit_field,
+            filter,
+            read_channel.addr_virtual,
+            filter,
+        );
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let gas_limit =
+            builder.mul_const_add_extension(base, read_channel.value[1], read_channel.value[0]);
+        let diff = builder.sub_extension(gas_limit, nv.gas_limit);
+        let constr = builder.mul_extension(filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+    for channel in &lv.mem_channels[2..] {
+        let constr = builder.mul_extension(filter, channel.used);
+        yield_constr.constraint(builder, constr);
+    }
+
+    let user_filter = builder.arithmetic_extension(
+        -F::ONE,
+        F::ONE,
+        lv.is_cpu_cycle,
+        lv.is_kernel_mode,
+        lv.is_cpu_cycle,
+    );
+    {
+        let diff = builder.sub_extension(nv.gas_limit, lv.gas_limit);
+        let constr = builder.mul_extension(user_filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+
+    for bit in lv.gas_left_bits {
+        let constr = builder.mul_sub_extension(bit, bit, bit);
+        yield_constr.constraint(builder, constr);
+    }
+    let gas_left = gas_left_ext_circuit(builder, lv);
+    {
+        let diff = builder.sub_extension(lv.gas_limit, nv.gas);
+        let diff = builder.sub_extension(diff, gas_left);
+        let constr = builder.mul_extension(user_filter, diff);
+        yield_constr.constraint_transition(builder, constr);
+    }
+}
+
+pub fn eval_packed<P: PackedField>(
+    lv: &CpuColumnsView<P>,
+    nv: &CpuColumnsView<P>,
+    yield_constr: &mut ConstraintConsumer<P>,
+) {
+    eval_packed_accumulate(lv, nv, yield_constr);
+    eval_packed_gas(lv, nv, yield_constr);
+    eval_packed_gas_limit(lv, nv, yield_constr);
+}
+
+pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    lv: &CpuColumnsView<ExtensionTarget<D>>,
+    nv: &CpuColumnsView<ExtensionTarget<D>>,
+    yield_constr: &mut RecursiveConstraintConsumer<F, D>,
+) {
+    eval_ext_circuit_accumulate(builder, lv, nv, yield_constr);
+    eval_ext_circuit_gas(builder, lv, nv, yield_constr);
+    eval_ext_circuit_gas_limit(builder, lv, nv, yield_constr);
+}
@@ -11,22 +11,42 @@ pub static KERNEL: Lazy<Kernel> = Lazy::new(combined_kernel);
 
 pub(crate) fn combined_kernel() -> Kernel {
     let files = vec![
+        include_str!("asm/core/access_lists.asm"),
         include_str!("asm/core/bootloader.asm"),
+        include_str!("asm/core/call.asm"),
         include_str!("asm/core/create.asm"),
         include_str!("asm/core/create_addresses.asm"),
+        include_str!("asm/core/exception.asm"),
+        include_str!("asm/core/gas.asm"),
         include_str!("asm/core/intrinsic_gas.asm"),
-        include_str!("asm/core/invalid.asm"),
         include_str!("asm/core/jumpdest_analysis.asm"),
+        include_str!("asm/core/log.asm"),
         include_str!("asm/core/nonce.asm"),
+        include_str!("asm/core/precompiles/blake2_f.asm"),
+        include_str!("asm/core/precompiles/bn_add.asm"),
+        include_str!("asm/core/precompiles/bn_mul.asm"),
+        include_str!("asm/core/precompiles/ecrec.asm"),
+        include_str!("asm/core/precompiles/expmod.asm"),
+        include_str!("asm/core/precompiles/id.asm"),
+        include_str!("asm/core/precompiles/main.asm"),
+        include_str!("asm/core/precompiles/rip160.asm"),
+        include_str!("asm/core/precompiles/sha256.asm"),
+        include_str!("asm/core/precompiles/snarkv.asm"),
         include_str!("asm/core/process_txn.asm"),
+        include_str!("asm/core/receipt.asm"),
+        include_str!("asm/core/selfdestruct_list.asm"),
         include_str!("asm/core/syscall.asm"),
         include_str!("asm/core/syscall_stubs.asm"),
         include_str!("asm/core/terminate.asm"),
+        include_str!("asm/core/touched_addresses.asm"),
         include_str!("asm/core/transfer.asm"),
         include_str!("asm/core/util.asm"),
         include_str!("asm/curve/bn254/curve_add.asm"),
         include_str!("asm/curve/bn254/curve_mul.asm"),
+        include_str!("asm/curve/bn254/final_exponent.asm"),
+        include_str!("asm/curve/bn254/miller_loop.asm"),
         include_str!("asm/curve/bn254/moddiv.asm"),
+        include_str!("asm/curve/bn254/twisted_curve.asm"),
         include_str!("asm/curve/common.asm"),
         include_str!("asm/curve/secp256k1/curve_mul.asm"),
         include_str!("asm/curve/secp256k1/curve_add.asm"),
@@ -35,9 +55,14 @@ pub(crate) fn combined_kernel() -> Kernel {
         include_str!("asm/curve/secp256k1/lift_x.asm"),
         include_str!("asm/curve/secp256k1/moddiv.asm"),
         include_str!("asm/exp.asm"),
+        include_str!("asm/fields/fp2_macros.asm"),
         include_str!("asm/fields/fp6_macros.asm"),
         include_str!("asm/fields/fp6_mul.asm"),
+        include_str!("asm/fields/fp12_frob.asm"),
+        include_str!("asm/fields/fp12_inv.asm"),
+        include_str!("asm/fields/fp12_macros.asm"),
         include_str!("asm/fields/fp12_mul.asm"),
+        include_str!("asm/fields/fp12_pow.asm"),
         include_str!("asm/halt.asm"),
         include_str!("asm/hash/blake2b/addresses.asm"),
         include_str!("asm/hash/blake2b/compression.asm"),
@@ -70,6 +95,7 @@ pub(crate) fn combined_kernel() -> Kernel {
         include_str!("asm/memory/txn_fields.asm"),
         include_str!("asm/mpt/accounts.asm"),
         include_str!("asm/mpt/delete/delete.asm"),
+        include_str!("asm/mpt/delete/delete_trie_specific.asm"),
         include_str!("asm/mpt/hash/hash.asm"),
         include_str!("asm/mpt/hash/hash_trie_specific.asm"),
         include_str!("asm/mpt/hex_prefix.asm"),
@@ -89,6 +115,8 @@ pub(crate) fn combined_kernel() -> Kernel {
         include_str!("asm/rlp/num_bytes.asm"),
         include_str!("asm/rlp/read_to_memory.asm"),
         include_str!("asm/shift.asm"),
+        include_str!("asm/signed.asm"),
+        include_str!("asm/transactions/common_decoding.asm"),
         include_str!("asm/transactions/router.asm"),
         include_str!("asm/transactions/type_0.asm"),
         include_str!("asm/transactions/type_1.asm"),
@@ -2,6 +2,19 @@ retzero:
     %stack (account_ptr, retdest) -> (retdest, 0)
     JUMP
 
+global sys_extcodehash:
+    // stack: kexit_info, address
+    DUP2 %charge_account_access_gas
+    SWAP1 %u256_to_addr
+    // stack: address, kexit_info
+    PUSH sys_extcodehash_finish SWAP1
+    // stack: address, sys_extcodehash_finish, kexit_info
+    %jump(extcodehash)
+sys_extcodehash_finish:
+    // stack: hash, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
 global extcodehash:
     // stack: address, retdest
     %mpt_read_state_trie
@@ -14,18 +27,22 @@ global extcodehash:
     SWAP1 JUMP
 
 
-%macro codesize
-    // stack: (empty)
-    %address
-    %extcodesize
-%endmacro
-
 %macro extcodesize
     %stack (address) -> (address, 0, @SEGMENT_KERNEL_ACCOUNT_CODE, %%after)
     %jump(load_code)
 %%after:
 %endmacro
 
+global sys_extcodesize:
+    // stack: kexit_info, address
+    DUP2 %charge_account_access_gas
+    SWAP1 %u256_to_addr
+    // stack: address, kexit_info
+    %extcodesize
+    // stack: code_size, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
 global extcodesize:
     // stack: address, retdest
     %extcodesize
@@ -33,12 +50,18 @@ global extcodesize:
     SWAP1 JUMP
 
 
-%macro codecopy
-    // stack: dest_offset, offset, size, retdest
-    %address
-    // stack: address, dest_offset, offset, size, retdest
+global sys_extcodecopy:
+    // stack: kexit_info, address, dest_offset, offset, size
+    DUP2 %charge_account_access_gas
+    DUP5 DUP4 %charge_copy_gas
+
+    %stack (kexit_info, address, dest_offset, offset, size)
+        -> (address, dest_offset, offset, size, sys_extcodecopy_finish, kexit_info)
+    %u256_to_addr
     %jump(extcodecopy)
-%endmacro
+sys_extcodecopy_finish:
+    // stack: kexit_info
+    EXIT_KERNEL
 
 // Pre stack: address, dest_offset, offset, size, retdest
 // Post stack: (empty)
@@ -49,6 +72,10 @@ global extcodecopy:
     %jump(load_code)
 
 extcodecopy_contd:
+    // stack: code_length, size, offset, dest_offset, retdest
+    // Everything past the end of the code is copied as zeros, so capping the
+    // offset at code_length doesn't change the result.
+    DUP1 DUP4 %min SWAP3 POP
     // stack: code_length, size, offset, dest_offset, retdest
     SWAP1
     // stack: size, code_length, offset, dest_offset, retdest
@@ -68,7 +95,7 @@ extcodecopy_loop:
     // stack: offset < code_length, offset, code_length, dest_offset, i, size, retdest
     DUP2
     // stack: offset, offset < code_length, offset, code_length, dest_offset, i, size, retdest
-    %mload_current(@SEGMENT_KERNEL_ACCOUNT_CODE)
+    %mload_kernel(@SEGMENT_KERNEL_ACCOUNT_CODE)
     // stack: opcode, offset < code_length, offset, code_length, dest_offset, i, size, retdest
     %stack (opcode, offset_lt_code_length, offset, code_length, dest_offset, i, size, retdest)
         -> (offset_lt_code_length, 0, opcode, offset, code_length, dest_offset, i, size, retdest)
@@ -106,6 +133,9 @@ global load_code:
     JUMP
 load_code_ctd:
     // stack: codehash, ctx, segment, retdest
+    // Accounts which don't exist, or have no code, have nothing to load.
+    DUP1 ISZERO %jumpi(load_code_empty)
+    DUP1 PUSH @EMPTY_STRING_HASH EQ %jumpi(load_code_empty)
     PROVER_INPUT(account_code::length)
     // stack: code_length, codehash, ctx, segment, retdest
     PUSH 0
@@ -137,3 +167,7 @@ load_code_check:
     // stack: shouldbecodehash, codehash, retdest, code_length
     %assert_eq
     JUMP
+
+load_code_empty:
+    %stack (codehash, ctx, segment, retdest) -> (retdest, 0)
+    JUMP
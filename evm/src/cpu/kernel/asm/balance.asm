@@ -1,3 +1,16 @@
+global sys_balance:
+    // stack: kexit_info, address
+    DUP2 %charge_account_access_gas
+    SWAP1 %u256_to_addr
+    // stack: address, kexit_info
+    PUSH sys_balance_finish SWAP1
+    // stack: address, sys_balance_finish, kexit_info
+    %jump(balance)
+sys_balance_finish:
+    // stack: balance, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
 global balance:
     // stack: address, retdest
     %mpt_read_state_trie
@@ -21,3 +34,9 @@ global selfbalance:
     // stack: balance, address, retdest
     JUMP
 
+global sys_selfbalance:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_LOW)
+    PUSH sys_balance_finish
+    // stack: sys_balance_finish, kexit_info
+    %jump(selfbalance)
@@ -0,0 +1,157 @@
+// The accessed addresses and storage keys of the current transaction, per
+// EIP-2929. They are kept as append-only lists in @SEGMENT_ACCESSED_ADDRESSES
+// and @SEGMENT_ACCESSED_STORAGE_KEYS, so a reverted context forgets its accesses
+// by restoring the lengths saved at its creation.
+
+// Empties the accessed addresses and storage keys, at the start of a transaction.
+%macro clear_access_lists
+    PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN)
+    PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN)
+%endmacro
+
+// Adds the address to the accessed addresses, if it isn't there already.
+// Pre stack: addr, retdest
+// Post stack: cold_access, which is 1 if the address wasn't accessed before, and 0 otherwise
+global insert_accessed_addresses:
+    // stack: addr, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN)
+    // stack: len, addr, retdest
+    PUSH 0
+insert_accessed_addresses_loop:
+    // stack: i, len, addr, retdest
+    DUP2 DUP2 EQ %jumpi(insert_address)
+    // stack: i, len, addr, retdest
+    DUP1 %mload_kernel(@SEGMENT_ACCESSED_ADDRESSES)
+    // stack: loaded_addr, i, len, addr, retdest
+    DUP4 EQ %jumpi(insert_accessed_addresses_found)
+    // stack: i, len, addr, retdest
+    %increment
+    %jump(insert_accessed_addresses_loop)
+
+insert_address:
+    // stack: i, len, addr, retdest
+    DUP3 SWAP1 %mstore_kernel(@SEGMENT_ACCESSED_ADDRESSES) // Store the new address at the end of the list.
+    // stack: len, addr, retdest
+    %increment
+    %mstore_global_metadata(@GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN)
+    // stack: addr, retdest
+    POP
+    PUSH 1
+    SWAP1
+    JUMP
+
+insert_accessed_addresses_found:
+    // stack: i, len, addr, retdest
+    %stack (i, len, addr, retdest) -> (retdest, 0)
+    JUMP
+
+%macro insert_accessed_addresses
+    %stack (addr) -> (addr, %%after)
+    %jump(insert_accessed_addresses)
+%%after:
+    // stack: cold_access
+%endmacro
+
+%macro insert_accessed_addresses_no_return
+    %insert_accessed_addresses
+    POP
+%endmacro
+
+// Adds the storage key, i.e. the (address, slot) pair, to the accessed storage
+// keys, if it isn't there already.
+// Pre stack: addr, slot, retdest
+// Post stack: cold_access, which is 1 if the storage key wasn't accessed before, and 0 otherwise
+global insert_accessed_storage_keys:
+    // stack: addr, slot, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN)
+    // stack: len, addr, slot, retdest
+    PUSH 0
+insert_accessed_storage_keys_loop:
+    // stack: i, len, addr, slot, retdest
+    DUP2 DUP2 EQ %jumpi(insert_storage_key)
+    // stack: i, len, addr, slot, retdest
+    DUP1 %mload_kernel(@SEGMENT_ACCESSED_STORAGE_KEYS)
+    // stack: loaded_addr, i, len, addr, slot, retdest
+    DUP4 EQ
+    // stack: loaded_addr == addr, i, len, addr, slot, retdest
+    DUP2 %increment %mload_kernel(@SEGMENT_ACCESSED_STORAGE_KEYS)
+    // stack: loaded_slot, loaded_addr == addr, i, len, addr, slot, retdest
+    DUP6 EQ AND
+    // stack: found, i, len, addr, slot, retdest
+    %jumpi(insert_accessed_storage_keys_found)
+    // stack: i, len, addr, slot, retdest
+    %add_const(2)
+    %jump(insert_accessed_storage_keys_loop)
+
+insert_storage_key:
+    // stack: i, len, addr, slot, retdest
+    // Store the new storage key at the end of the list.
+    DUP3 DUP2 %mstore_kernel(@SEGMENT_ACCESSED_STORAGE_KEYS)
+    DUP4 DUP2 %increment %mstore_kernel(@SEGMENT_ACCESSED_STORAGE_KEYS)
+    // stack: i, len, addr, slot, retdest
+    %add_const(2)
+    %mstore_global_metadata(@GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN)
+    // stack: len, addr, slot, retdest
+    %stack (len, addr, slot, retdest) -> (retdest, 1)
+    JUMP
+
+insert_accessed_storage_keys_found:
+    // stack: i, len, addr, slot, retdest
+    %stack (i, len, addr, slot, retdest) -> (retdest, 0)
+    JUMP
+
+%macro insert_accessed_storage_keys
+    %stack (addr, slot) -> (addr, slot, %%after)
+    %jump(insert_accessed_storage_keys)
+%%after:
+    // stack: cold_access
+%endmacro
+
+%macro insert_accessed_storage_keys_no_return
+    %insert_accessed_storage_keys
+    POP
+%endmacro
+
+// Warms the sender, the recipient (if any) and the precompiled contracts of the
+// current transaction, which are accessed addresses from its start.
+global warm_txn_addresses:
+    // stack: retdest
+    %mload_txn_field(@TXN_FIELD_ORIGIN) %insert_accessed_addresses_no_return
+    %is_contract_creation %jumpi(warm_precompiles)
+    %mload_txn_field(@TXN_FIELD_TO) %insert_accessed_addresses_no_return
+warm_precompiles:
+    PUSH @ECREC
+warm_precompiles_loop:
+    // stack: addr, retdest
+    DUP1 %insert_accessed_addresses_no_return
+    // stack: addr, retdest
+    DUP1 %eq_const(@BLAKE2_F) %jumpi(warm_precompiles_done)
+    %increment
+    %jump(warm_precompiles_loop)
+warm_precompiles_done:
+    // stack: addr, retdest
+    POP
+    JUMP
+
+// The gas cost of accessing an account or storage slot. This is the given cold
+// cost for the first access in the current transaction, and @GAS_WARMACCESS for
+// the subsequent ones.
+%macro access_gas(cold_cost)
+    // stack: cold_access
+    DUP1 ISZERO %mul_const(@GAS_WARMACCESS)
+    // stack: warm_gas, cold_access
+    SWAP1 %mul_const($cold_cost)
+    // stack: cold_gas, warm_gas
+    ADD
+    // stack: gas
+%endmacro
+
+// Charges the gas for accessing the given account, warming it if it was cold.
+%macro charge_account_access_gas
+    // stack: addr
+    %u256_to_addr
+    %insert_accessed_addresses
+    %access_gas(@GAS_COLDACCOUNTACCESS)
+    %charge_gas
+    // stack: (empty)
+%endmacro
@@ -1,22 +1,50 @@
 // Handlers for call-like operations, namely CALL, CALLCODE, STATICCALL and DELEGATECALL.
 
 // Creates a new sub context and executes the code of the given account.
-global call:
-    // stack: gas, address, value, args_offset, args_size, ret_offset, ret_size, retdest
+global sys_call:
+    // stack: kexit_info, gas, address, value, args_offset, args_size, ret_offset, ret_size
+    // Sending ETH is a state modification, so it is disallowed in static mode.
+    DUP4 ISZERO ISZERO
+    %mload_context_metadata(@CTX_METADATA_STATIC)
+    AND %jumpi(fault_exception)
+
+    // Sending ETH to a dead account costs extra, per EIP-161.
+    DUP3 %is_dead
+    DUP5 ISZERO ISZERO
+    AND %mul_const(@GAS_NEWACCOUNT)
+    DUP4 DUP6 %call_value_gas
+    ADD
+    %charge_gas
+    DUP6 DUP6 %expand_memory
+    DUP8 DUP8 %expand_memory
+
+    %stack (kexit_info, gas, address, value, args_offset, args_size, ret_offset, ret_size)
+        -> (value, gas, address, value, args_offset, args_size, ret_offset, ret_size, kexit_info)
+    %call_child_gas
     %address
-    %stack (self, gas, address, value)
+    %stack (self, child_gas, address, value)
            // These are (static, should_transfer_value, value, sender, address, code_addr, gas)
-        -> (0, 1, value, self, address, address, gas)
+        -> (0, 1, value, self, address, address, child_gas)
     %jump(call_common)
 
 // Creates a new sub context as if calling itself, but with the code of the
 // given account. In particular the storage remains the same.
-global call_code:
-    // stack: gas, address, value, args_offset, args_size, ret_offset, ret_size, retdest
+global sys_callcode:
+    // stack: kexit_info, gas, address, value, args_offset, args_size, ret_offset, ret_size
+    // Unlike CALL, CALLCODE is allowed in static mode even with a nonzero
+    // value, since it only sends ETH to the current account.
+    DUP3 DUP5 %call_value_gas
+    %charge_gas
+    DUP6 DUP6 %expand_memory
+    DUP8 DUP8 %expand_memory
+
+    %stack (kexit_info, gas, address, value, args_offset, args_size, ret_offset, ret_size)
+        -> (value, gas, address, value, args_offset, args_size, ret_offset, ret_size, kexit_info)
+    %call_child_gas
     %address
-    %stack (self, gas, address, value)
+    %stack (self, child_gas, address, value)
            // These are (static, should_transfer_value, value, sender, address, code_addr, gas)
-        -> (0, 1, value, self, self, address, gas)
+        -> (0, 1, value, self, self, address, child_gas)
     %jump(call_common)
 
 // Creates a new sub context and executes the code of the given account.
@@ -24,90 +52,240 @@ global call_code:
 // instructions or sending ETH in the sub context. The disallowed instructions
 // are CREATE, CREATE2, LOG0, LOG1, LOG2, LOG3, LOG4, SSTORE, SELFDESTRUCT and
 // CALL if the value sent is not 0.
-global static_all:
-    // stack: gas, address, args_offset, args_size, ret_offset, ret_size, retdest
+global sys_staticcall:
+    // stack: kexit_info, gas, address, args_offset, args_size, ret_offset, ret_size
+    DUP3 PUSH 0 %call_value_gas
+    %charge_gas
+    DUP5 DUP5 %expand_memory
+    DUP7 DUP7 %expand_memory
+
+    %stack (kexit_info, gas, address, args_offset, args_size, ret_offset, ret_size)
+        -> (0, gas, address, args_offset, args_size, ret_offset, ret_size, kexit_info)
+    %call_child_gas
     %address
-    %stack (self, gas, address)
+    %stack (self, child_gas, address)
            // These are (static, should_transfer_value, value, sender, address, code_addr, gas)
-        -> (1, 0, 0, self, address, address, gas)
+        -> (1, 0, 0, self, address, address, child_gas)
     %jump(call_common)
 
 // Creates a new sub context as if calling itself, but with the code of the
 // given account. In particular the storage, the current sender and the current
 // value remain the same.
-global delegate_call:
-    // stack: gas, address, args_offset, args_size, ret_offset, ret_size, retdest
+global sys_delegatecall:
+    // stack: kexit_info, gas, address, args_offset, args_size, ret_offset, ret_size
+    DUP3 PUSH 0 %call_value_gas
+    %charge_gas
+    DUP5 DUP5 %expand_memory
+    DUP7 DUP7 %expand_memory
+
+    %stack (kexit_info, gas, address, args_offset, args_size, ret_offset, ret_size)
+        -> (0, gas, address, args_offset, args_size, ret_offset, ret_size, kexit_info)
+    %call_child_gas
     %address
     %sender
     %callvalue
-    %stack (self, sender, value, gas, address)
+    %stack (value, sender, self, child_gas, address)
            // These are (static, should_transfer_value, value, sender, address, code_addr, gas)
-        -> (0, 0, value, sender, self, address, gas)
+        -> (0, 0, value, sender, self, address, child_gas)
     %jump(call_common)
 
-// Pre stack: static, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
-// Post stack: success, leftover_gas
+// The gas charged by a call instruction, before the gas sent along with it.
+// This is the cost of accessing the callee, which warms it, plus
+// @GAS_CALLVALUE if ETH is sent.
+%macro call_value_gas
+    // stack: value, address
+    ISZERO ISZERO
+    %mul_const(@GAS_CALLVALUE)
+    // stack: value_gas, address
+    SWAP1 %u256_to_addr %insert_accessed_addresses
+    %access_gas(@GAS_COLDACCOUNTACCESS)
+    // stack: access_gas, value_gas
+    ADD
+    // stack: cost
+%endmacro
+
+// Computes the gas made available to the callee, and deducts it from ours. Per
+// EIP-150, the caller can send at most all but one 64th of its remaining gas.
+// If ETH is sent, the callee also receives a stipend of @GAS_CALLSTIPEND, which
+// the caller doesn't pay for.
+%macro call_child_gas
+    // stack: value, gas
+    ISZERO ISZERO
+    %mul_const(@GAS_CALLSTIPEND)
+    SWAP1
+    // stack: gas, stipend
+    %all_but_one_64th_gas
+    // stack: all_but_one_64th, gas, stipend
+    %min
+    DUP1 CONSUME_GAS
+    ADD
+    // stack: child_gas
+%endmacro
+
+// Pre stack: static, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+// Post stack: (empty, since we switch to the new context)
 global call_common:
-    // stack: static, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
+    // stack: static, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+    // The callee is static if the caller is, or if this is a STATICCALL.
+    %mload_context_metadata(@CTX_METADATA_STATIC)
+    OR
+    // The call fails without executing any code if the call stack is full.
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %ge_const(1024)
+    %jumpi(call_insufficient_resources)
+
+    // Remember the state before any ETH is transferred, so that the callee can
+    // revert to it.
+    %mload_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
+    // stack: checkpoint, static, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+    %stack (checkpoint, static, should_transfer_value, value, sender, address)
+        -> (should_transfer_value, sender, address, value,
+            checkpoint, static, value, sender, address)
+    %maybe_transfer_eth
+    // stack: transfer_eth_status, checkpoint, static, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+    // The call also fails if the sender can't afford to send the given value.
+    %jumpi(call_insufficient_balance)
+
     %create_context
-    // Store the static flag in metadata.
-    %stack (new_ctx, static) -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_STATIC, static, new_ctx)
+    // stack: new_ctx, checkpoint, static, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+    // Note: We can't use %mstore_context_metadata because we're writing to
+    // memory owned by the new context, not the current one.
+    %stack (new_ctx, checkpoint)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_STATE_TRIE_CHECKPOINT_PTR, checkpoint, new_ctx)
     MSTORE_GENERAL
-    // stack: new_ctx, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
-
-    // Store the address in metadata.
-    %stack (new_ctx, should_transfer_value, value, sender, address)
-        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_ADDRESS, address,
-            new_ctx, should_transfer_value, value, sender, address)
+    %set_new_ctx_checkpoints
+    %stack (new_ctx, static)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_STATIC, static, new_ctx)
     MSTORE_GENERAL
-    // stack: new_ctx, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
-
-    // Store the caller in metadata.
-    %stack (new_ctx, should_transfer_value, value, sender)
-        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALLER, sender,
-            new_ctx, should_transfer_value, value, sender)
+    %stack (new_ctx, value)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALL_VALUE, value, new_ctx)
     MSTORE_GENERAL
-    // stack: new_ctx, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
-
-    // Store the call value field in metadata.
-    %stack (new_ctx, should_transfer_value, value, sender, address) =
-        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALL_VALUE, value,
-            should_transfer_value, sender, address, value, new_ctx)
+    %stack (new_ctx, sender)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALLER, sender, new_ctx)
+    MSTORE_GENERAL
+    %stack (new_ctx, address)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_ADDRESS, address, address, new_ctx)
     MSTORE_GENERAL
-    // stack: should_transfer_value, sender, address, value, new_ctx, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
+    // The callee is touched, even if no ETH is sent to it, per EIP-161. Since the
+    // new context's checkpoints are already saved, this is undone if it reverts.
+    %insert_touched_addresses
+    // stack: new_ctx, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
 
-    %maybe_transfer_eth
-    // stack: new_ctx, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
+    // Load the code to be executed into the new context. We keep code_addr
+    // around, since it tells us whether to run a precompile instead.
+    %stack (new_ctx, code_addr, gas, args_offset, args_size)
+        -> (code_addr, new_ctx, @SEGMENT_CODE, call_after_load_code,
+            new_ctx, gas, args_offset, args_size, code_addr)
+    %jump(load_code)
+call_after_load_code:
+    // stack: code_size, new_ctx, gas, args_offset, args_size, code_addr, ret_offset, ret_size, kexit_info
+    PUSH call_after_jumpdest_analysis DUP2 DUP4
+    // stack: new_ctx, code_size, call_after_jumpdest_analysis, code_size, new_ctx, gas, args_offset, args_size, code_addr, ret_offset, ret_size, kexit_info
+    %jump(jumpdest_analysis)
+call_after_jumpdest_analysis:
+    // stack: code_size, new_ctx, gas, args_offset, args_size, code_addr, ret_offset, ret_size, kexit_info
+    %stack (code_size, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CODE_SIZE, code_size, new_ctx)
+    MSTORE_GENERAL
+    %stack (new_ctx, gas)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_GAS_LIMIT, gas, new_ctx)
+    MSTORE_GENERAL
+    // stack: new_ctx, args_offset, args_size, code_addr, ret_offset, ret_size, kexit_info
 
-    // Store parent context in metadata.
+    // Copy the call's arguments from our memory to the new context's calldata.
+    %stack (new_ctx, args_offset, args_size)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALLDATA_SIZE, args_size,
+            new_ctx, args_offset, args_size)
+    MSTORE_GENERAL
     GET_CONTEXT
-    PUSH @CTX_METADATA_PARENT_CONTEXT
-    PUSH @SEGMENT_CONTEXT_METADATA
-    DUP4 // new_ctx
+    %stack (ctx, new_ctx, args_offset, args_size)
+        -> (new_ctx, @SEGMENT_CALLDATA, 0,
+            ctx, @SEGMENT_MAIN_MEMORY, args_offset,
+            args_size, call_after_copy_calldata, new_ctx)
+    %jump(memcpy)
+call_after_copy_calldata:
+    // stack: new_ctx, code_addr, ret_offset, ret_size, kexit_info
+    %stack (new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_PARENT_PC, after_call, new_ctx)
     MSTORE_GENERAL
-    // stack: new_ctx, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
+    // stack: new_ctx, code_addr, ret_offset, ret_size, kexit_info
 
-    // Store parent PC = after_call.
-    %stack (new_ctx) -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_PARENT_PC, after_call, new_ctx)
-    MSTORE_GENERAL
-    // stack: new_ctx, code_addr, gas, args_offset, args_size, ret_offset, ret_size, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %increment
+    %mstore_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
 
-    // TODO: Populate CALLDATA
-    // TODO: Save parent gas and set child gas
-    // TODO: Populate code
+    DUP2 %is_precompile
+    %jumpi(call_precompile)
+    // stack: new_ctx, code_addr, ret_offset, ret_size, kexit_info
+    SWAP1 POP
 
-    // TODO: Temporary, remove after above steps are done.
-    %stack (new_ctx, code_addr, gas, args_offset, args_size) -> (new_ctx)
-    // stack: new_ctx, ret_offset, ret_size, retdest
+    // Now, switch to the new context and go to usermode with PC=0. The rest of
+    // our stack will be waiting for us in after_call.
+    SET_CONTEXT
+    // stack: (empty, since we're in the new context)
+    PUSH 0 // kexit_info for PC=0 in user mode
+    EXIT_KERNEL
 
-    // Now, switch to the new context and go to usermode with PC=0.
-    DUP1 // new_ctx
+// Precompiles are run by the kernel, but still in the new context, so that
+// they can read their input from its calldata and return like any other callee.
+call_precompile:
+    // stack: new_ctx, code_addr, ret_offset, ret_size, kexit_info
+    // We pass code_addr through SEGMENT_KERNEL_GENERAL[0], since our stack is
+    // not accessible from the new context.
+    SWAP1
+    %mstore_kernel_general(0)
     SET_CONTEXT
-    PUSH 0 // jump dest
+    // stack: (empty, since we're in the new context)
+    %mload_kernel_general(0)
+    // stack: code_addr
+    %jump(handle_precompiles)
+
+call_insufficient_balance:
+    // stack: checkpoint, static, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+    %stack (checkpoint, static, value, sender, address, code_addr, gas,
+            args_offset, args_size, ret_offset, ret_size, kexit_info)
+        -> (gas, kexit_info)
+    %jump(call_failed)
+
+call_insufficient_resources:
+    // stack: static, should_transfer_value, value, sender, address, code_addr, gas, args_offset, args_size, ret_offset, ret_size, kexit_info
+    %stack (static, should_transfer_value, value, sender, address, code_addr, gas,
+            args_offset, args_size, ret_offset, ret_size, kexit_info)
+        -> (gas, kexit_info)
+    // Fall through.
+
+call_failed:
+    // stack: gas, kexit_info
+    // The gas sent to the callee, including any stipend, is given back.
+    %refund_gas
+    // A call which fails without executing any code leaves no return data.
+    PUSH 0 %mstore_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    PUSH 0 // success
+    SWAP1
     EXIT_KERNEL
 
+// The callee's terminating instruction returns here, in our context, after
+// writing its return data to our RETURNDATA segment.
 after_call:
-    // stack: new_ctx, ret_offset, ret_size, retdest
-    // TODO: Set RETURNDATA.
-    // TODO: Return to caller w/ EXIT_KERNEL.
-    // TODO: Return leftover gas
+    // stack: success, leftover_gas, ret_offset, ret_size, kexit_info
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %decrement
+    %mstore_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+
+    // Copy as much of the return data as fits into the given region of memory.
+    %stack (success, leftover_gas, ret_offset, ret_size)
+        -> (ret_size, ret_offset, success, leftover_gas)
+    %mload_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %min
+    // stack: copy_size, ret_offset, success, leftover_gas, kexit_info
+    GET_CONTEXT
+    %stack (ctx, copy_size, ret_offset)
+        -> (ctx, @SEGMENT_MAIN_MEMORY, ret_offset,
+            ctx, @SEGMENT_RETURNDATA, 0,
+            copy_size, after_call_copy_returndata)
+    %jump(memcpy)
+after_call_copy_returndata:
+    // stack: success, leftover_gas, kexit_info
+    SWAP1 %refund_gas
+    SWAP1
+    EXIT_KERNEL
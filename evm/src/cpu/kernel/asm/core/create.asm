@@ -1,57 +1,159 @@
+// Handlers for contract creation, namely CREATE and CREATE2. Contract-creation
+// transactions go through `create_common` as well.
+
 // Create a new contract account with the traditional address scheme, i.e.
 //     address = KEC(RLP(sender, nonce))[12:]
-// This can be used both for the CREATE instruction and for contract-creation
-// transactions.
 //
-// Pre stack: CODE_ADDR, code_len, retdest
-// Post stack: address
-// Note: CODE_ADDR refers to a (context, segment, offset) tuple.
-global create:
-    // stack: sender, endowment, CODE_ADDR, code_len, retdest
-    DUP1 %get_nonce
-    // stack: nonce, sender, endowment, CODE_ADDR, code_len, retdest
-    // Call get_create_address and have it return to create_inner.
-    %stack (nonce, sender)
-        -> (sender, nonce, create_inner, sender)
+// Pre stack: kexit_info, value, code_offset, code_len
+// Post stack: address, or 0 if the creation failed
+global sys_create:
+    // stack: kexit_info, value, code_offset, code_len
+    %mload_context_metadata(@CTX_METADATA_STATIC) %jumpi(fault_exception)
+    %charge_gas_const(@GAS_CREATE)
+    DUP4 DUP4 %expand_memory
+    %address DUP1 %get_nonce
+    // stack: nonce, sender, kexit_info, value, code_offset, code_len
+    // Call get_create_address and have it return to create_from_memory.
+    %stack (nonce, sender) -> (sender, nonce, create_from_memory)
     %jump(get_create_address)
 
 // CREATE2; see EIP-1014. Address will be
 //     address = KEC(0xff || sender || salt || code_hash)[12:]
 //
-// Pre stack: sender, endowment, salt, CODE_ADDR, code_len, retdest
-// Post stack: address
-// Note: CODE_ADDR refers to a (context, segment, offset) tuple.
-global create2:
-    // stack: sender, endowment, salt, CODE_ADDR, code_len, retdest
-    // Call get_create2_address and have it return to create_inner.
-    %stack (sender, endowment, salt) -> (salt, sender, endowment)
-    // stack: salt, sender, endowment, CODE_ADDR, code_len, retdest
-    DUP7 DUP7 DUP7 DUP7 // CODE_ADDR and code_len
-    // stack: CODE_ADDR, code_len, salt, sender, endowment, CODE_ADDR, code_len, retdest
-    PUSH create_inner
-    // stack: create_inner, CODE_ADDR, code_len, salt, sender, endowment, CODE_ADDR, code_len, retdest
-    SWAP5 // create_inner <-> salt
-    // stack: salt, CODE_ADDR, code_len, create_inner, sender, endowment, CODE_ADDR, code_len, retdest
-    DUP7 // sender
-    // stack: sender, salt, CODE_ADDR, code_len, create_inner, sender, endowment, CODE_ADDR, code_len, retdest
+// Pre stack: kexit_info, value, code_offset, code_len, salt
+// Post stack: address, or 0 if the creation failed
+global sys_create2:
+    // stack: kexit_info, value, code_offset, code_len, salt
+    %mload_context_metadata(@CTX_METADATA_STATIC) %jumpi(fault_exception)
+    // Besides the base cost, CREATE2 pays for hashing the init code.
+    DUP4 %add_const(31) %shr_const(5)
+    %mul_const(@GAS_KECCAK256WORD)
+    %add_const(@GAS_CREATE)
+    %charge_gas
+    DUP4 DUP4 %expand_memory
+    GET_CONTEXT
+    %address
+    // Call get_create2_address and have it return to create_from_memory.
+    %stack (sender, ctx, kexit_info, value, code_offset, code_len, salt)
+        -> (sender, salt, ctx, @SEGMENT_MAIN_MEMORY, code_offset, code_len,
+            create_from_memory, kexit_info, value, code_offset, code_len)
     %jump(get_create2_address)
 
-// Pre stack: address, sender, endowment, CODE_ADDR, code_len, retdest
-// Post stack: address
+// Creates a contract at the given address, with init code taken from our memory.
+// Pre stack: address, kexit_info, value, code_offset, code_len
+// Post stack: (empty, since we switch to the constructor's context)
+create_from_memory:
+    // stack: address, kexit_info, value, code_offset, code_len
+    // The creation fails without running any code if the call stack is full, or
+    // if we can't afford the endowment.
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %ge_const(1024)
+    %current_account_data %add_const(1) %mload_trie_data
+    // stack: balance, depth_exceeded, address, kexit_info, value, code_offset, code_len
+    DUP5 GT
+    OR
+    %jumpi(create_insufficient_resources)
+
+    // Our nonce is incremented even if the constructor fails.
+    %address %increment_nonce
+    // The gas sent to the constructor is deducted from ours, and whatever it
+    // doesn't use is refunded in create_from_memory_finish.
+    %all_but_one_64th_gas
+    DUP1 CONSUME_GAS
+    GET_CONTEXT
+    %address
+    %stack (sender, ctx, gas, address, kexit_info, value, code_offset, code_len)
+        -> (address, sender, value, ctx, @SEGMENT_MAIN_MEMORY, code_offset, code_len, gas,
+            create_from_memory_finish, address, kexit_info)
+    %jump(create_common)
+create_from_memory_finish:
+    // stack: success, leftover_gas, address, kexit_info
+    SWAP1 %refund_gas
+    // stack: success, address, kexit_info
+    MUL
+    // stack: address_if_success, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+create_insufficient_resources:
+    // stack: address, kexit_info, value, code_offset, code_len
+    // A creation which fails without executing any code leaves no return data.
+    PUSH 0 %mstore_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %stack (address, kexit_info, value, code_offset, code_len) -> (kexit_info, 0)
+    EXIT_KERNEL
+
+// Creates a contract account at the given address, transfers the endowment to
+// it, and runs the init code in a new context. If the init code succeeds, the
+// data it returns is stored as the contract's code. Our callers are responsible
+// for checking the call stack depth and the sender's balance, and for
+// incrementing the sender's nonce.
+//
+// Pre stack: address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+// Post stack: success, leftover_gas
 // Note: CODE_ADDR refers to a (context, segment, offset) tuple.
-create_inner:
-    // stack: address, sender, endowment, CODE_ADDR, code_len, retdest
-    %stack (address, sender, endowment)
-        -> (sender, address, endowment, sender, address)
-    // TODO: Need to handle insufficient balance failure.
+global create_common:
+    // stack: address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+    // EIP-2929: The new address is accessed, even if the creation fails.
+    DUP1 %insert_accessed_addresses_no_return
+    // EIP-684: The creation fails if the address is already in use.
+    DUP1 %has_nonce_or_code
+    %jumpi(create_collision)
+
+    // Remember the state before the endowment is transferred, so that the
+    // constructor can revert to it.
+    %mload_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
+    // stack: checkpoint, address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+    %stack (checkpoint, address, sender, endowment)
+        -> (sender, address, endowment, checkpoint, address, sender, endowment)
     %transfer_eth
-    // stack: sender, address, CODE_ADDR, code_len, retdest
+    // stack: transfer_eth_status, checkpoint, address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+    // Our callers have already checked that the sender can afford the endowment.
+    %jumpi(panic)
 
-    %increment_nonce
-    // stack: address, CODE_ADDR, code_len, retdest
+    // transfer_eth created the account if needed. Per EIP-161, a new contract's
+    // nonce starts at 1.
+    DUP2 %mpt_read_state_trie
+    %make_account_copy
+    // stack: new_account_ptr, checkpoint, address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+    PUSH 1 DUP2 %mstore_trie_data
+    DUP3 %addr_to_state_key
+    %stack (key, new_account_ptr) -> (key, new_account_ptr, create_common_after_init_account)
+    %jump(mpt_insert_state_trie)
 
+create_common_after_init_account:
+    // stack: checkpoint, address, sender, endowment, CODE_ADDR, code_len, gas, retdest
     %create_context
+    // stack: new_ctx, checkpoint, address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+    // Note: We can't use %mstore_context_metadata because we're writing to
+    // memory owned by the new context, not the current one.
+    %stack (new_ctx, checkpoint)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_STATE_TRIE_CHECKPOINT_PTR, checkpoint, new_ctx)
+    MSTORE_GENERAL
+    %set_new_ctx_checkpoints
+    %stack (new_ctx, address)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_ADDRESS, address, new_ctx, address)
+    MSTORE_GENERAL
+    %stack (new_ctx, address, sender)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALLER, sender, new_ctx, address)
+    MSTORE_GENERAL
+    %stack (new_ctx, address, endowment)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALL_VALUE, endowment, new_ctx, address)
+    MSTORE_GENERAL
+    // stack: new_ctx, address, CODE_ADDR, code_len, gas, retdest
+    %stack (new_ctx, address, code_addr: 3, code_len, gas)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_GAS_LIMIT, gas,
+            new_ctx, address, code_addr, code_len)
+    MSTORE_GENERAL
+    %stack (new_ctx, address, code_addr: 3, code_len)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CODE_SIZE, code_len,
+            new_ctx, address, code_addr, code_len)
+    MSTORE_GENERAL
+    %stack (new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_PARENT_PC, after_constructor, new_ctx)
+    MSTORE_GENERAL
     // stack: new_ctx, address, CODE_ADDR, code_len, retdest
+
+    // Load the init code into the new context.
     %stack (new_ctx, address, src_ctx, src_segment, src_offset, code_len)
         -> (new_ctx, @SEGMENT_CODE, 0,
             src_ctx, src_segment, src_offset,
@@ -61,24 +163,116 @@ create_inner:
 
 run_constructor:
     // stack: new_ctx, address, retdest
-    // At this point, the initialization code has been loaded.
-    // Save our return address in memory, so we'll be in `after_constructor`
-    // after the new context returns.
-    // Note: We can't use %mstore_context_metadata because we're writing to
-    // memory owned by the new context, not the current one.
-    %stack (new_ctx) -> (new_ctx, @SEGMENT_CONTEXT_METADATA,
-                         @CTX_METADATA_PARENT_PC, after_constructor, new_ctx)
-    MSTORE_GENERAL
+    // Find the JUMPDESTs in the init code.
+    PUSH @CTX_METADATA_CODE_SIZE PUSH @SEGMENT_CONTEXT_METADATA DUP3 MLOAD_GENERAL
+    // stack: code_len, new_ctx, address, retdest
+    PUSH run_constructor_jumpdests_analyzed SWAP1 DUP3
+    // stack: new_ctx, code_len, run_constructor_jumpdests_analyzed, new_ctx, address, retdest
+    %jump(jumpdest_analysis)
+run_constructor_jumpdests_analyzed:
     // stack: new_ctx, address, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %increment
+    %mstore_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
 
-    // Now, switch to the new context and go to usermode with PC=0.
+    // Now, switch to the new context and go to usermode with PC=0. The rest of
+    // our stack, including new_ctx, will be waiting for us in after_constructor.
+    DUP1
     SET_CONTEXT
     // stack: (empty, since we're in the new context)
-    PUSH 0
+    PUSH 0 // kexit_info for PC=0 in user mode
     EXIT_KERNEL
 
+// The constructor's terminating instruction returns here, in our context, after
+// writing its return data to our RETURNDATA segment.
 after_constructor:
-    // stack: address, retdest
-    // TODO: If code was returned, store it in the account.
-    SWAP1
+    // stack: success, leftover_gas, new_ctx, address, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %decrement
+    %mstore_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+
+    // A failed constructor has already reverted its state changes, and its
+    // return data is left for us to inspect.
+    ISZERO %jumpi(after_constructor_failed)
+
+    // The constructor's return data is the new contract's code.
+    %mload_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    // stack: code_len, leftover_gas, new_ctx, address, retdest
+    // The code is rejected if it exceeds the size limit of EIP-170, ...
+    DUP1 %gt_const(@MAX_CODE_SIZE)
+    // ... or if it starts with the 0xEF byte, per EIP-3541, ...
+    PUSH 0 %mload_current(@SEGMENT_RETURNDATA) %eq_const(0xef)
+    DUP3 ISZERO ISZERO AND
+    OR
+    // ... or if the leftover gas can't pay for the code deposit.
+    DUP2 %mul_const(@GAS_CODEDEPOSIT)
+    DUP4 LT
+    OR
+    // stack: code_rejected, code_len, leftover_gas, new_ctx, address, retdest
+    %jumpi(create_code_rejected)
+
+    DUP1 %mul_const(@GAS_CODEDEPOSIT)
+    %stack (deposit_cost, code_len, leftover_gas) -> (leftover_gas, deposit_cost, code_len)
+    SUB
+    // stack: leftover_gas, code_len, new_ctx, address, retdest
+    GET_CONTEXT
+    %stack (ctx, leftover_gas, code_len) -> (ctx, @SEGMENT_RETURNDATA, 0, code_len, leftover_gas)
+    KECCAK_GENERAL
+    // stack: code_hash, leftover_gas, new_ctx, address, retdest
+
+    // Store the code hash in the new account. The prover is expected to supply
+    // the code itself, keyed by this hash, in case it gets loaded later.
+    DUP4 %mpt_read_state_trie
+    %make_account_copy
+    // stack: new_account_ptr, code_hash, leftover_gas, new_ctx, address, retdest
+    SWAP1 DUP2 %add_const(3) %mstore_trie_data
+    // stack: new_account_ptr, leftover_gas, new_ctx, address, retdest
+    DUP4 %addr_to_state_key
+    %stack (key, new_account_ptr) -> (key, new_account_ptr, after_constructor_stored_code)
+    %jump(mpt_insert_state_trie)
+after_constructor_stored_code:
+    // stack: leftover_gas, new_ctx, address, retdest
+    // A successful creation leaves no return data.
+    PUSH 0 %mstore_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %stack (leftover_gas, new_ctx, address, retdest) -> (retdest, 1, leftover_gas)
     JUMP
+
+after_constructor_failed:
+    // stack: leftover_gas, new_ctx, address, retdest
+    %stack (leftover_gas, new_ctx, address, retdest) -> (retdest, 0, leftover_gas)
+    JUMP
+
+create_code_rejected:
+    // stack: code_len, leftover_gas, new_ctx, address, retdest
+    // Undo the constructor's state changes, using the checkpoints saved in its
+    // context, and consume all of its gas.
+    %stack (code_len, leftover_gas, new_ctx) -> (new_ctx)
+    %revert_checkpoint_of_ctx
+    PUSH 0 %mstore_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %stack (address, retdest) -> (retdest, 0, 0)
+    JUMP
+
+create_collision:
+    // stack: address, sender, endowment, CODE_ADDR, code_len, gas, retdest
+    // The creation fails, consuming all of the gas sent along with it.
+    PUSH 0 %mstore_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %stack (address, sender, endowment, code_addr: 3, code_len, gas, retdest) -> (retdest, 0, 0)
+    JUMP
+
+// Returns 1 if the given account has a nonzero nonce or nonempty code, and 0
+// otherwise, including if the account doesn't exist.
+%macro has_nonce_or_code
+    // stack: address
+    %mpt_read_state_trie
+    // stack: account_ptr
+    // If the account doesn't exist, the null pointer doubles as our result.
+    DUP1 ISZERO %jumpi(%%after)
+    DUP1 %mload_trie_data ISZERO ISZERO
+    // stack: has_nonce, account_ptr
+    SWAP1 %add_const(3) %mload_trie_data
+    PUSH @EMPTY_STRING_HASH EQ ISZERO
+    // stack: has_code, has_nonce
+    OR
+%%after:
+    // stack: has_nonce_or_code
+%endmacro
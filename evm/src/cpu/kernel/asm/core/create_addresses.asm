@@ -5,14 +5,26 @@
 // Post stack: address
 global get_create_address:
     // stack: sender, nonce, retdest
-    // TODO: Replace with actual implementation.
-    %pop2
-    PUSH 123
+    PUSH 9 // We start at 9 to leave room to prepend the largest possible RLP list header.
+    %encode_rlp_160
+    // stack: pos, nonce, retdest
+    %encode_rlp_scalar
+    // stack: pos, retdest
+    %prepend_rlp_list_prefix
+    // stack: start_pos, rlp_len, retdest
+    PUSH @SEGMENT_RLP_RAW
+    PUSH 0 // context
+    // stack: RLP_ADDR: 3, rlp_len, retdest
+    KECCAK_GENERAL
+    // stack: hash, retdest
+    %u256_to_addr
+    // stack: address, retdest
     SWAP1
     JUMP
 
 // Computes the address for a contract based on the CREATE2 rule, i.e.
 //     address = KEC(0xff || sender || salt || code_hash)[12:]
+// Clobbers @SEGMENT_KERNEL_GENERAL.
 //
 // Pre stack: sender, salt, CODE_ADDR, code_len, retdest
 // Post stack: address
@@ -20,8 +32,37 @@ global get_create_address:
 // Note: CODE_ADDR is a (context, segment, offset) tuple.
 global get_create2_address:
     // stack: sender, salt, CODE_ADDR, code_len, retdest
-    // TODO: Replace with actual implementation.
-    %pop6
-    PUSH 123
+    %stack (sender, salt, code_addr: 3, code_len) -> (code_addr, code_len, sender, salt)
+    KECCAK_GENERAL
+    // stack: code_hash, sender, salt, retdest
+    PUSH 0xff %mstore_kernel_general(0)
+    %stack (code_hash, sender, salt)
+        -> (0, @SEGMENT_KERNEL_GENERAL, 1, sender, 20, get_create2_address_contd, salt, code_hash)
+    %jump(mstore_unpacking)
+get_create2_address_contd:
+    // stack: offset, salt, code_hash, retdest
+    %stack (offset, salt)
+        -> (0, @SEGMENT_KERNEL_GENERAL, offset, salt, 32, get_create2_address_contd2)
+    %jump(mstore_unpacking)
+get_create2_address_contd2:
+    // stack: offset, code_hash, retdest
+    %stack (offset, code_hash)
+        -> (0, @SEGMENT_KERNEL_GENERAL, offset, code_hash, 32, get_create2_address_finish)
+    %jump(mstore_unpacking)
+get_create2_address_finish:
+    // stack: offset, retdest
+    POP
+    %stack (retdest) -> (0, @SEGMENT_KERNEL_GENERAL, 0, 85, retdest) // context, segment, offset, len
+    KECCAK_GENERAL
+    // stack: hash, retdest
+    %u256_to_addr
+    // stack: address, retdest
     SWAP1
     JUMP
+
+// Truncates a 256-bit word to its low 160 bits, e.g. to turn a hash into an address.
+%macro u256_to_addr
+    // stack: x
+    %and_const(0xffffffffffffffffffffffffffffffffffffffff)
+    // stack: x mod 2^160
+%endmacro
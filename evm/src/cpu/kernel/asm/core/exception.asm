@@ -0,0 +1,184 @@
+// Handlers for exceptions which the CPU raises in user mode, e.g. an invalid opcode or a stack
+// underflow. The CPU traps to the handler selected by the exception code, with
+//     trap_info = pc | is_kernel << 32 | stack_len << 64
+// on top of the user's stack, where `pc` is that of the faulting instruction, and `stack_len` is
+// the length of the user's stack before the trap.
+//
+// The CPU doesn't check that the exception really occurred, so each handler verifies it, and
+// panics otherwise. Then the current context halts exceptionally, via `fault_exception`.
+
+global exception_jumptable:
+    JUMPTABLE exc_out_of_gas
+    JUMPTABLE exc_invalid_opcode
+    JUMPTABLE exc_stack_underflow
+    JUMPTABLE exc_invalid_jump_destination
+    JUMPTABLE exc_invalid_jumpi_destination
+    JUMPTABLE exc_stack_overflow
+    JUMPTABLE panic // exception code 6 is unused
+    JUMPTABLE panic // exception code 7 is unused
+
+global exc_out_of_gas:
+    // stack: trap_info
+    %trap_info_opcode
+    // stack: opcode
+    %mload_kernel_code(gas_cost_for_opcode)
+    // stack: gas_cost
+    // Opcodes which the CPU doesn't execute natively have a cost of 0 in the table, so they can't
+    // pass this check; their gas is charged by the kernel.
+    GAS
+    // stack: gas_left, gas_cost
+    LT
+    // stack: gas_left < gas_cost
+    %jumpi(fault_exception)
+    PANIC
+
+global exc_invalid_opcode:
+    // stack: trap_info
+    %trap_info_opcode
+    // stack: opcode
+    // The CPU mustn't implement the opcode natively in user mode...
+    PUSH @INVALID_OPCODES_USER
+    DUP2
+    SHR
+    %and_const(1)
+    // stack: not_native, opcode
+    ISZERO %jumpi(panic)
+    // ... nor trap to a syscall, i.e. its syscall handler must be `panic`.
+    // stack: opcode
+    %mul_const(3)
+    // stack: shift
+    DUP1 %mload_kernel_code(syscall_jumptable)
+    %shl_const(8)
+    DUP2 %increment %mload_kernel_code(syscall_jumptable)
+    ADD
+    %shl_const(8)
+    SWAP1 %add_const(2) %mload_kernel_code(syscall_jumptable)
+    ADD
+    // stack: syscall_handler
+    PUSH panic
+    EQ
+    %jumpi(fault_exception)
+    PANIC
+
+global exc_stack_underflow:
+    // stack: trap_info
+    DUP1 %trap_info_opcode
+    // stack: opcode, trap_info
+    %mload_kernel_code(min_stack_len_for_opcode)
+    // stack: min_stack_len, trap_info
+    SWAP1 %trap_info_stack_len
+    // stack: stack_len, min_stack_len
+    LT
+    // stack: stack_len < min_stack_len
+    %jumpi(fault_exception)
+    PANIC
+
+global exc_invalid_jump_destination:
+    // stack: trap_info, jump_dest
+    %trap_info_opcode
+    // stack: opcode, jump_dest
+    %eq_const(0x56) // JUMP
+    ISZERO %jumpi(panic)
+    // stack: jump_dest
+    %jump(verify_invalid_jump_destination)
+
+global exc_invalid_jumpi_destination:
+    // stack: trap_info, jump_dest, condition
+    %trap_info_opcode
+    // stack: opcode, jump_dest, condition
+    %eq_const(0x57) // JUMPI
+    ISZERO %jumpi(panic)
+    // stack: jump_dest, condition
+    // The jump must actually be taken.
+    SWAP1 ISZERO %jumpi(panic)
+    // stack: jump_dest
+    %jump(verify_invalid_jump_destination)
+
+// Pre stack: jump_dest
+// Faults if jump_dest is not a valid JUMPDEST in the current context's code, and panics otherwise.
+verify_invalid_jump_destination:
+    // stack: jump_dest
+    DUP1 %ge_const(0x100000000)
+    // stack: jump_dest >= 2^32, jump_dest
+    %jumpi(fault_exception)
+    // stack: jump_dest
+    %mload_current(@SEGMENT_JUMPDEST_BITS)
+    // stack: is_jumpdest
+    ISZERO %jumpi(fault_exception)
+    PANIC
+
+global exc_stack_overflow:
+    // stack: trap_info
+    // The only instructions which the CPU executes natively in user mode, and which grow the stack,
+    // are PC, GAS, PUSH* and DUP*.
+    DUP1 %trap_info_opcode
+    // stack: opcode, trap_info
+    DUP1 %eq_const(0x58) // PC
+    DUP2 %eq_const(0x5a) // GAS
+    OR
+    // stack: is_pc_or_gas, opcode, trap_info
+    SWAP1 %sub_const(0x60) %lt_const(0x30) // PUSH* or DUP*
+    OR
+    // stack: grows_stack, trap_info
+    ISZERO %jumpi(panic)
+    // stack: trap_info
+    %trap_info_stack_len
+    // stack: stack_len
+    %ge_const(1024)
+    %jumpi(fault_exception)
+    PANIC
+
+// Reads the faulting opcode from the user's code.
+%macro trap_info_opcode
+    // stack: trap_info
+    %and_const(0xffffffff)
+    // stack: pc
+    %mload_current_code
+    // stack: opcode
+%endmacro
+
+%macro trap_info_stack_len
+    // stack: trap_info
+    %shr_const(64)
+    // stack: stack_len
+%endmacro
+
+// The gas which the CPU charges for each opcode that it executes natively in user mode, or 0 for
+// the other opcodes.
+gas_cost_for_opcode:
+    BYTES 0, 3, 5, 3, 5, 0, 5, 0, 8, 8, 0, 0, 0, 0, 0, 0 // 0x00-0x0f
+    BYTES 3, 3, 0, 0, 3, 3, 3, 3, 3, 3, 3, 3, 3, 0, 0, 0 // 0x10-0x1f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x20-0x2f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x30-0x3f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x40-0x4f
+    BYTES 2, 0, 0, 0, 0, 0, 8, 10, 2, 0, 2, 1, 0, 0, 0, 0 // 0x50-0x5f
+    BYTES 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3 // 0x60-0x6f
+    BYTES 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3 // 0x70-0x7f
+    BYTES 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3 // 0x80-0x8f
+    BYTES 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3 // 0x90-0x9f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xa0-0xaf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xb0-0xbf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xc0-0xcf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xd0-0xdf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xe0-0xef
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xf0-0xff
+
+// The minimum stack length for each opcode that the CPU executes natively in user mode, below
+// which it underflows, or 0 for the other opcodes.
+min_stack_len_for_opcode:
+    BYTES 0, 2, 2, 2, 2, 0, 2, 0, 3, 3, 0, 0, 0, 0, 0, 0 // 0x00-0x0f
+    BYTES 2, 2, 0, 0, 2, 1, 2, 2, 2, 1, 2, 2, 2, 0, 0, 0 // 0x10-0x1f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x20-0x2f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x30-0x3f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x40-0x4f
+    BYTES 1, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0 // 0x50-0x5f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x60-0x6f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0x70-0x7f
+    BYTES 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16 // 0x80-0x8f
+    BYTES 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17 // 0x90-0x9f
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xa0-0xaf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xb0-0xbf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xc0-0xcf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xd0-0xdf
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xe0-0xef
+    BYTES 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 // 0xf0-0xff
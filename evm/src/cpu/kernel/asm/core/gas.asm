@@ -0,0 +1,101 @@
+// Gas accounting. The CPU keeps track of the gas used by the current context,
+// charging instructions which it executes natively in user mode. Everything
+// else is charged by the kernel with CONSUME_GAS, and GAS tells us how much is
+// left, i.e. @CTX_METADATA_GAS_LIMIT minus the gas used so far.
+
+// Charges the given amount of gas to the current context. If it doesn't have
+// that much gas left, this is an exceptional halt, which consumes all of it.
+%macro charge_gas
+    // stack: cost
+    DUP1 GAS LT
+    // stack: gas_left < cost, cost
+    %jumpi(fault_exception)
+    CONSUME_GAS
+    // stack: (empty)
+%endmacro
+
+%macro charge_gas_const(cost)
+    PUSH $cost
+    %charge_gas
+%endmacro
+
+// Gives back gas to the current context, such as the gas left over by a sub
+// context. Since the gas used can only grow, we raise the gas limit instead.
+%macro refund_gas
+    // stack: amount
+    %mload_context_metadata(@CTX_METADATA_GAS_LIMIT)
+    ADD
+    %mstore_context_metadata(@CTX_METADATA_GAS_LIMIT)
+    // stack: (empty)
+%endmacro
+
+// The total cost of a memory of the given number of words, per the yellow
+// paper: 3 words + words^2 / 512.
+%macro memory_cost
+    // stack: num_words
+    DUP1 %square %shr_const(9)
+    // stack: num_words^2 / 512, num_words
+    SWAP1 %mul_const(@GAS_MEMORY)
+    ADD
+    // stack: cost
+%endmacro
+
+// Expands main memory to cover the given range, charging for any new words and
+// updating @CTX_METADATA_MSIZE. An empty range doesn't expand memory, whatever
+// its offset.
+%macro expand_memory
+    // stack: offset, size
+    %stack (offset, size) -> (offset, size, %%after)
+    %jump(expand_memory)
+%%after:
+%endmacro
+
+global expand_memory:
+    // stack: offset, size, retdest
+    DUP2 ISZERO %jumpi(expand_memory_empty)
+    // A range this far out would cost more gas than any block holds.
+    DUP2 DUP2 OR %shr_const(32) %jumpi(fault_exception)
+    ADD
+    // stack: end, retdest
+    %add_const(31) %shr_const(5)
+    // stack: new_num_words, retdest
+    %msize %shr_const(5)
+    // stack: old_num_words, new_num_words, retdest
+    DUP1 DUP3 GT ISZERO
+    // stack: new_num_words <= old_num_words, old_num_words, new_num_words, retdest
+    %jumpi(expand_memory_done)
+    %memory_cost
+    SWAP1
+    // stack: new_num_words, old_cost, retdest
+    DUP1 %shl_const(5) %mstore_context_metadata(@CTX_METADATA_MSIZE)
+    %memory_cost
+    SUB
+    // stack: new_cost - old_cost, retdest
+    %charge_gas
+    JUMP
+expand_memory_done:
+    // stack: old_num_words, new_num_words, retdest
+    %pop2
+    JUMP
+expand_memory_empty:
+    // stack: offset, size, retdest
+    %pop2
+    JUMP
+
+// Charges for expanding memory to cover the given range, plus @GAS_COPY per
+// word of the range, as the copying instructions do.
+%macro charge_copy_gas
+    // stack: offset, size
+    DUP2 %num_words %mul_const(@GAS_COPY)
+    %stack (copy_cost, offset, size) -> (offset, size, copy_cost)
+    %expand_memory
+    %charge_gas
+    // stack: (empty)
+%endmacro
+
+// The number of words needed to hold the given number of bytes.
+%macro num_words
+    // stack: num_bytes
+    %add_const(31) %shr_const(5)
+    // stack: num_words
+%endmacro
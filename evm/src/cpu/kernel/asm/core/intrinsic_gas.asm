@@ -51,9 +51,14 @@ count_zeros_finish:
     PUSH @GAS_TRANSACTION
     // stack: gas_txn, gas_creation, gas_txndata, retdest
 
-    // TODO: Add num_access_list_addresses * GAS_ACCESSLISTADDRESS
-    // TODO: Add num_access_list_slots * GAS_ACCESSLISTSTORAGE
+    %mload_txn_field(@TXN_FIELD_ACCESS_LIST_ADDRESSES)
+    %mul_const(@GAS_ACCESSLISTADDRESS)
+    %mload_txn_field(@TXN_FIELD_ACCESS_LIST_STORAGE_KEYS)
+    %mul_const(@GAS_ACCESSLISTSTORAGE)
+    // stack: gas_access_list_keys, gas_access_list_addresses, gas_txn, gas_creation, gas_txndata, retdest
 
+    ADD
+    ADD
     ADD
     ADD
     // stack: total_gas, retdest
@@ -0,0 +1,77 @@
+// Handlers for LOG0 through LOG4. Each log is appended to @SEGMENT_LOGS as
+//     address, num_topics, data_len, topics[num_topics], data[data_len]
+// so that the current transaction's receipt can be built from it.
+
+global sys_log0:
+    // stack: kexit_info, offset, size
+    %stack (kexit_info, offset, size) -> (offset, size, 0, kexit_info)
+    %jump(log_n)
+
+global sys_log1:
+    // stack: kexit_info, offset, size, topic0
+    %stack (kexit_info, offset, size, topic0) -> (offset, size, 1, topic0, kexit_info)
+    %jump(log_n)
+
+global sys_log2:
+    // stack: kexit_info, offset, size, topic0, topic1
+    %stack (kexit_info, offset, size, topic0, topic1)
+        -> (offset, size, 2, topic0, topic1, kexit_info)
+    %jump(log_n)
+
+global sys_log3:
+    // stack: kexit_info, offset, size, topic0, topic1, topic2
+    %stack (kexit_info, offset, size, topic0, topic1, topic2)
+        -> (offset, size, 3, topic0, topic1, topic2, kexit_info)
+    %jump(log_n)
+
+global sys_log4:
+    // stack: kexit_info, offset, size, topic0, topic1, topic2, topic3
+    %stack (kexit_info, offset, size, topic0, topic1, topic2, topic3)
+        -> (offset, size, 4, topic0, topic1, topic2, topic3, kexit_info)
+    %jump(log_n)
+
+log_n:
+    // stack: offset, size, num_topics, topics[num_topics], kexit_info
+    %mload_context_metadata(@CTX_METADATA_STATIC) %jumpi(fault_exception)
+    DUP3 %mul_const(@GAS_LOGTOPIC)
+    DUP3 %mul_const(@GAS_LOGDATA)
+    ADD
+    %add_const(@GAS_LOG)
+    %charge_gas
+    DUP2 DUP2 %expand_memory
+
+    // Write the log's header.
+    %mload_global_metadata(@GLOBAL_METADATA_LOGS_LEN)
+    // stack: log_pos, offset, size, num_topics, topics[num_topics], kexit_info
+    %address DUP2 %mstore_kernel(@SEGMENT_LOGS)
+    DUP4 DUP2 %increment %mstore_kernel(@SEGMENT_LOGS)
+    DUP3 DUP2 %add_const(2) %mstore_kernel(@SEGMENT_LOGS)
+
+    // The data goes after the topics, which we'll write afterward.
+    DUP4 DUP2 %add_const(3) ADD
+    // stack: data_pos, log_pos, offset, size, num_topics, topics[num_topics], kexit_info
+    DUP1 DUP5 ADD %mstore_global_metadata(@GLOBAL_METADATA_LOGS_LEN)
+    GET_CONTEXT
+    %stack (ctx, data_pos, log_pos, offset, size, num_topics)
+        -> (0, @SEGMENT_LOGS, data_pos,
+            ctx, @SEGMENT_MAIN_MEMORY, offset,
+            size, log_n_store_topics, log_pos, num_topics)
+    %jump(memcpy)
+
+log_n_store_topics:
+    // stack: log_pos, num_topics, topics[num_topics], kexit_info
+    %add_const(3)
+log_n_store_topics_loop:
+    // stack: topic_pos, remaining_topics, topics[remaining_topics], kexit_info
+    DUP2 ISZERO %jumpi(log_n_finish)
+    %stack (topic_pos, remaining_topics, topic) -> (topic_pos, topic, topic_pos, remaining_topics)
+    %mstore_kernel(@SEGMENT_LOGS)
+    // stack: topic_pos, remaining_topics, topics[remaining_topics], kexit_info
+    %increment
+    SWAP1 %decrement SWAP1
+    %jump(log_n_store_topics_loop)
+
+log_n_finish:
+    // stack: topic_pos, 0, kexit_info
+    %pop2
+    EXIT_KERNEL
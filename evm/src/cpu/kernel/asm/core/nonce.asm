@@ -1,11 +1,17 @@
-// Increment the nonce of the given account.
+// Get the nonce of the given account, or 0 if the account doesn't exist.
 // Pre stack: address, retdest
-// Post stack: (empty)
-
+// Post stack: nonce
 global get_nonce:
     // stack: address, retdest
-    // TODO: Replace with actual implementation.
-    POP
+    %mpt_read_state_trie
+    // stack: account_ptr, retdest
+    DUP1 ISZERO %jumpi(get_nonce_no_account)
+    %mload_trie_data
+    // stack: nonce, retdest
+    SWAP1
+    JUMP
+get_nonce_no_account:
+    %stack (account_ptr, retdest) -> (retdest, 0)
     JUMP
 
 // Convenience macro to call get_nonce and return where we left off.
@@ -15,11 +21,24 @@ global get_nonce:
 %%after:
 %endmacro
 
+// Increment the nonce of the given account. Panics if the account doesn't exist.
+// The account is updated by copy, so that earlier state trie roots remain valid checkpoints.
+// Pre stack: address, retdest
+// Post stack: (empty)
 global increment_nonce:
     // stack: address, retdest
-    // TODO: Replace with actual implementation.
-    POP
-    JUMP
+    DUP1 %mpt_read_state_trie
+    // stack: account_ptr, address, retdest
+    DUP1 ISZERO %jumpi(panic)
+    %make_account_copy
+    // stack: new_account_ptr, address, retdest
+    DUP1 %mload_trie_data
+    %increment
+    DUP2 %mstore_trie_data
+    // stack: new_account_ptr, address, retdest
+    SWAP1 %addr_to_state_key
+    // stack: key, new_account_ptr, retdest
+    %jump(mpt_insert_state_trie)
 
 // Convenience macro to call increment_nonce and return where we left off.
 %macro increment_nonce
@@ -0,0 +1,124 @@
+// The BLAKE2b compression function F, as specified in EIP-152. It reuses the
+// round function of our BLAKE2b implementation, with the number of blocks at
+// SEGMENT_KERNEL_GENERAL[0] set to 0, so that the hash value, internal state and
+// message live at fixed addresses (see addresses.asm).
+
+// Where we copy the input in SEGMENT_KERNEL_GENERAL, past the memory used by the
+// round function.
+%macro blake2_f_input_addr(offset)
+    PUSH $offset
+    %add_const(64)
+%endmacro
+
+global precompile_blake2_f:
+    // stack: address
+    POP
+    // The input must consist of exactly 213 bytes: the number of rounds (4 bytes,
+    // big-endian), h (8 words), m (16 words), t (2 words) and the final block
+    // flag f (1 byte), where words are little-endian u64s.
+    %calldatasize %eq_const(213)
+    ISZERO %jumpi(fault_exception)
+    PUSH 212 %load_calldata_byte
+    %gt_const(1) %jumpi(fault_exception)
+
+    // Each round costs 1 gas.
+    %load_calldata_word(0)
+    %shr_const(224)
+    // stack: rounds
+    DUP1 %charge_precompile_gas
+
+    PUSH 0 %mstore_kernel_general(0)
+    GET_CONTEXT
+    %stack (ctx, rounds)
+        -> (0, @SEGMENT_KERNEL_GENERAL, 64,
+            ctx, @SEGMENT_CALLDATA, 0,
+            213, blake2_f_after_copy, rounds)
+    %jump(memcpy)
+blake2_f_after_copy:
+    // stack: rounds
+    // Store h as the hash value, and as the first half of the internal state.
+    PUSH 0
+    %rep 8
+        // stack: i, rounds
+        DUP1 %mul_const(8) %blake2_f_input_addr(4) ADD
+        %mload_kernel_general_u64_LE
+        // stack: h_i, i, rounds
+        DUP1 DUP3 %blake2b_hash_value_addr ADD %mstore_kernel_general
+        DUP2 %blake2b_internal_state_addr ADD %mstore_kernel_general
+        // stack: i, rounds
+        %increment
+    %endrep
+    POP
+
+    // Store m as the message.
+    PUSH 0
+    %rep 16
+        // stack: i, rounds
+        DUP1 %mul_const(8) %blake2_f_input_addr(68) ADD
+        %mload_kernel_general_u64_LE
+        // stack: m_i, i, rounds
+        DUP2 %blake2b_message_addr ADD %mstore_kernel_general
+        // stack: i, rounds
+        %increment
+    %endrep
+    POP
+
+    // The second half of the internal state is the IV, with t and the inverted
+    // final block flag XOR'd into it.
+    %blake2b_internal_state_addr %add_const(8)
+    // stack: addr, rounds
+    %blake2b_iv_i(0) DUP2 %mstore_kernel_general %increment
+    %blake2b_iv_i(1) DUP2 %mstore_kernel_general %increment
+    %blake2b_iv_i(2) DUP2 %mstore_kernel_general %increment
+    %blake2b_iv_i(3) DUP2 %mstore_kernel_general %increment
+    %blake2_f_input_addr(196) %mload_kernel_general_u64_LE
+    %blake2b_iv_i(4) XOR DUP2 %mstore_kernel_general %increment
+    %blake2_f_input_addr(204) %mload_kernel_general_u64_LE
+    %blake2b_iv_i(5) XOR DUP2 %mstore_kernel_general %increment
+    %blake2_f_input_addr(212) %mload_kernel_general
+    %mul_const(0xFFFFFFFFFFFFFFFF)
+    %blake2b_iv_i(6) XOR DUP2 %mstore_kernel_general %increment
+    %blake2b_iv_i(7) DUP2 %mstore_kernel_general
+    POP
+
+    // stack: rounds
+    %blake2b_internal_state_addr
+    PUSH 0
+blake2_f_round_loop:
+    // stack: round, start, rounds
+    DUP3 DUP2 EQ
+    %jumpi(blake2_f_rounds_end)
+    %blake2b_round
+    %increment
+    %jump(blake2_f_round_loop)
+blake2_f_rounds_end:
+    // stack: round, start, rounds
+    %pop3
+    %blake2b_generate_new_hash_value(7)
+    %blake2b_generate_new_hash_value(6)
+    %blake2b_generate_new_hash_value(5)
+    %blake2b_generate_new_hash_value(4)
+    %blake2b_generate_new_hash_value(3)
+    %blake2b_generate_new_hash_value(2)
+    %blake2b_generate_new_hash_value(1)
+    %blake2b_generate_new_hash_value(0)
+    // stack: h_0', ..., h_7'
+
+    // The output is the new hash value, again as little-endian u64s.
+    %reverse_bytes_u64 SWAP1
+    %reverse_bytes_u64 SWAP2
+    %reverse_bytes_u64 SWAP3
+    %reverse_bytes_u64 SWAP4
+    %reverse_bytes_u64 SWAP5
+    %reverse_bytes_u64 SWAP6
+    %reverse_bytes_u64 SWAP7
+    %reverse_bytes_u64
+    // stack: h_7'', h_0'', ..., h_6''
+    %stack (h_7, h_s: 7) -> (h_s, h_7)
+    // stack: h_0'', ..., h_7''
+    %u64s_to_u256
+    %stack (first, second: 4) -> (second, first)
+    %u64s_to_u256
+    // stack: second, first
+    SWAP1
+    %jump(precompile_return_two_words)
@@ -0,0 +1,18 @@
+global precompile_bn_add:
+    // stack: address
+    POP
+    %charge_precompile_gas_const(@BN_ADD_GAS)
+
+    PUSH bn_add_return
+    %load_calldata_word(96)
+    %load_calldata_word(64)
+    %load_calldata_word(32)
+    %load_calldata_word(0)
+    // stack: x0, y0, x1, y1, bn_add_return
+    %jump(ec_add)
+bn_add_return:
+    // stack: x, y
+    // ec_add returns (u256::MAX, u256::MAX) if either input is not a valid point.
+    DUP1 NOT ISZERO
+    %jumpi(fault_exception)
+    %jump(precompile_return_two_words)
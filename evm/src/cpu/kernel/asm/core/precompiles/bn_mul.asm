@@ -0,0 +1,17 @@
+global precompile_bn_mul:
+    // stack: address
+    POP
+    %charge_precompile_gas_const(@BN_MUL_GAS)
+
+    PUSH bn_mul_return
+    %load_calldata_word(64)
+    %load_calldata_word(32)
+    %load_calldata_word(0)
+    // stack: x, y, s, bn_mul_return
+    %jump(ec_mul)
+bn_mul_return:
+    // stack: x, y
+    // ec_mul returns (u256::MAX, u256::MAX) if the input is not a valid point.
+    DUP1 NOT ISZERO
+    %jumpi(fault_exception)
+    %jump(precompile_return_two_words)
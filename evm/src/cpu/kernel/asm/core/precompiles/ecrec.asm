@@ -0,0 +1,23 @@
+global precompile_ecrec:
+    // stack: address
+    POP
+    %charge_precompile_gas_const(@ECREC_GAS)
+
+    PUSH ecrec_return
+    %load_calldata_word(96)
+    %load_calldata_word(64)
+    %load_calldata_word(32)
+    %load_calldata_word(0)
+    // stack: hash, v, r, s, ecrec_return
+    %jump(ecrecover)
+ecrec_return:
+    // stack: address
+    // An invalid signature is not an error, but the output is then empty.
+    DUP1 NOT ISZERO
+    // stack: address == u256::MAX, address
+    %jumpi(ecrec_invalid)
+    %jump(precompile_return_word)
+ecrec_invalid:
+    // stack: u256::MAX
+    POP
+    %jump(precompile_return_empty)
@@ -0,0 +1,454 @@
+// Modular exponentiation, as specified in EIP-198, with the gas cost of EIP-2565.
+// The input consists of the sizes of the base, exponent and modulus (a word
+// each), followed by the base, exponent and modulus themselves, as big-endian
+// integers of those sizes.
+
+global precompile_expmod:
+    // stack: address
+    POP
+    %load_calldata_word(64)
+    %load_calldata_word(32)
+    %load_calldata_word(0)
+    // stack: b_size, e_size, m_size
+    // Such large inputs could never be paid for, and rejecting them up front
+    // rules out overflows below.
+    DUP3 DUP3 DUP3 OR OR
+    %shr_const(32)
+    %jumpi(fault_exception)
+
+    // Load the first 32 bytes of the exponent, or all of it if it's shorter.
+    DUP1 %add_const(96)
+    %load_calldata_word
+    // stack: e_word, b_size, e_size, m_size
+    PUSH 32 DUP4 %min
+    PUSH 32 SUB
+    %mul_const(8)
+    SHR
+    // stack: e_head, b_size, e_size, m_size
+    %stack (e_head) -> (e_head, expmod_after_bit_length)
+    %jump(bit_length)
+expmod_after_bit_length:
+    // stack: e_head_bits, b_size, e_size, m_size
+    // The iteration count is 8 * (e_size - 32) + bit_length(e_head) - 1, where
+    // each term is floored at 0, and the total at 1.
+    PUSH 1 %max %decrement
+    PUSH 32 DUP4 %max
+    %sub_const(32)
+    %mul_const(8)
+    ADD
+    PUSH 1 %max
+    // stack: iteration_count, b_size, e_size, m_size
+    DUP4 DUP3 %max
+    %add_const(7) %div_const(8)
+    %square
+    // stack: multiplication_complexity, iteration_count, b_size, e_size, m_size
+    MUL
+    %div_const(3)
+    PUSH @EXPMOD_MIN_GAS %max
+    %charge_precompile_gas
+    // stack: b_size, e_size, m_size
+
+    DUP3 ISZERO
+    %jumpi(precompile_return_empty)
+    DUP3 %gt_const(32)
+    %jumpi(expmod_large_modulus)
+
+    // Load the modulus.
+    DUP2 DUP2 ADD %add_const(96)
+    GET_CONTEXT
+    %stack (ctx, m_offset, b_size, e_size, m_size)
+        -> (ctx, @SEGMENT_CALLDATA, m_offset, m_size, expmod_after_load_m,
+            b_size, e_size, m_size)
+    %jump(mload_packing)
+expmod_after_load_m:
+    // stack: m, b_size, e_size, m_size
+    // Reduce the base modulo m, one byte at a time. If m is 0, MULMOD and
+    // ADDMOD yield 0, which is also the result we want.
+    %stack (m, b_size) -> (0, 0, b_size, m)
+expmod_reduce_base_loop:
+    // stack: i, acc, b_size, m, e_size, m_size
+    DUP3 DUP2 EQ
+    %jumpi(expmod_reduce_base_end)
+    // acc = (acc * 256 + base[i]) % m
+    DUP4
+    PUSH 256
+    DUP4
+    MULMOD
+    // stack: (acc * 256) % m, i, acc, b_size, m, e_size, m_size
+    DUP5 SWAP1
+    DUP3 %add_const(96) %load_calldata_byte
+    ADDMOD
+    // stack: acc', i, acc, b_size, m, e_size, m_size
+    SWAP2 POP
+    %increment
+    %jump(expmod_reduce_base_loop)
+expmod_reduce_base_end:
+    // stack: i, base, b_size, m, e_size, m_size
+    %stack (i, base, b_size, m, e_size) -> (b_size, e_size, base, m)
+    %add_const(96)
+    // stack: e_offset, e_size, base, m, m_size
+    SWAP1 DUP2 ADD
+    SWAP1
+    // stack: e_offset, e_end, base, m, m_size
+    DUP4 PUSH 1 MOD
+    // stack: r = 1 % m, i = e_offset, e_end, base, m, m_size
+
+    // Square-and-multiply, going through the bits of the exponent from the
+    // most significant one.
+expmod_exp_loop:
+    // stack: r, i, e_end, base, m, m_size
+    DUP3 DUP3 EQ
+    %jumpi(expmod_exp_end)
+    DUP2 %load_calldata_byte
+    // stack: byte, r, i, e_end, base, m, m_size
+    %rep 8
+        // r = r^2 % m
+        DUP6
+        DUP3 DUP1
+        MULMOD
+        SWAP2 POP
+        // stack: byte, r, i, e_end, base, m, m_size
+        // If the current top bit of the byte is set, r = (r * base) % m.
+        DUP6
+        PUSH 1
+        DUP7
+        DUP4 %shr_const(7) %and_const(1)
+        // stack: bit, base, 1, m, byte, r, i, e_end, base, m, m_size
+        %select
+        DUP4
+        MULMOD
+        SWAP2 POP
+        // stack: byte, r, i, e_end, base, m, m_size
+        %shl_const(1)
+    %endrep
+    POP
+    SWAP1 %increment SWAP1
+    %jump(expmod_exp_loop)
+expmod_exp_end:
+    // stack: r, i, e_end, base, m, m_size
+    %stack (r, i, e_end, base, m, m_size) -> (m_size, m_size, r)
+    %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    // stack: m_size, r
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    %stack (parent_ctx, m_size, r)
+        -> (parent_ctx, @SEGMENT_RETURNDATA, 0, r, m_size, expmod_after_store)
+    %jump(mstore_unpacking)
+expmod_after_store:
+    // stack: offset'
+    POP
+    %jump(precompile_success)
+
+// Moduli wider than a word are handled with integers stored as arrays of n
+// 128-bit limbs, least significant first, in SEGMENT_KERNEL_GENERAL (see the
+// address macros below). Limb sums then fit in a word along with their carry.
+// Products are computed by shifting and adding, one bit at a time, so that all
+// arithmetic reduces to modular addition.
+expmod_large_modulus:
+    // stack: b_size, e_size, m_size
+    DUP3 %add_const(15) %div_const(16)
+    %mstore_kernel_general(0)
+
+    // Load the modulus, from its least significant limb.
+    DUP2 DUP2 ADD %add_const(96)
+    // stack: m_offset, b_size, e_size, m_size
+    DUP4 DUP2 ADD
+    PUSH 0
+expmod_load_m_loop:
+    // stack: k, end, m_offset, b_size, e_size, m_size
+    DUP1 %expmod_n EQ
+    %jumpi(expmod_load_m_end)
+    // Limb k consists of the (up to) 16 bytes ending at end.
+    DUP3 DUP3 %sub_const(16) %max
+    DUP1 DUP4 SUB
+    // stack: len, start, k, end, m_offset, b_size, e_size, m_size
+    SWAP1 GET_CONTEXT
+    %stack (ctx, start, len, k, end)
+        -> (ctx, @SEGMENT_CALLDATA, start, len, expmod_after_load_m_limb, start, k)
+    %jump(mload_packing)
+expmod_after_load_m_limb:
+    // stack: limb, start, k, m_offset, b_size, e_size, m_size
+    DUP3 %expmod_m ADD
+    %mstore_kernel_general
+    // The next limb ends where this one starts.
+    SWAP1 %increment
+    %jump(expmod_load_m_loop)
+expmod_load_m_end:
+    // stack: k, end, m_offset, b_size, e_size, m_size
+    %pop3
+    // Zero ONE, B, R and T, which memory may hold stale values for, then set
+    // ONE to 1.
+    %expmod_n %mul_const(4) %expmod_one
+    %stack (one, count) -> (one, count, expmod_after_zero)
+    %jump(expmod_zero)
+expmod_after_zero:
+    PUSH 1 %expmod_one
+    %mstore_kernel_general
+
+    // If m is 0, the result is 0, which R already holds.
+    PUSH 0 PUSH 0
+expmod_m_or_loop:
+    // stack: k, acc, b_size, e_size, m_size
+    DUP1 %expmod_n EQ
+    %jumpi(expmod_m_or_end)
+    DUP1 %expmod_m ADD %mload_kernel_general
+    DUP3 OR
+    SWAP2 POP
+    %increment
+    %jump(expmod_m_or_loop)
+expmod_m_or_end:
+    // stack: k, acc, b_size, e_size, m_size
+    POP ISZERO
+    %jumpi(expmod_large_zero_modulus)
+
+    // R = 1 % m
+    PUSH expmod_after_init_r %expmod_r %expmod_one %expmod_r
+    %jump(expmod_add_mod)
+expmod_after_init_r:
+    // stack: b_size, e_size, m_size
+    // Reduce the base modulo m, one bit at a time: B = (2 B + bit) % m.
+    DUP1 %mul_const(8)
+    PUSH 0
+expmod_large_base_loop:
+    // stack: i, b_bits, b_size, e_size, m_size
+    DUP2 DUP2 EQ
+    %jumpi(expmod_large_base_end)
+    PUSH expmod_large_base_doubled %expmod_base %expmod_base %expmod_base
+    %jump(expmod_add_mod)
+expmod_large_base_doubled:
+    // stack: i, b_bits, b_size, e_size, m_size
+    PUSH 96 DUP2 %expmod_calldata_bit
+    SWAP1 %increment SWAP1
+    // stack: bit, i + 1, b_bits, b_size, e_size, m_size
+    ISZERO
+    %jumpi(expmod_large_base_loop)
+    PUSH expmod_large_base_loop %expmod_base %expmod_one %expmod_base
+    %jump(expmod_add_mod)
+expmod_large_base_end:
+    // stack: i, b_bits, b_size, e_size, m_size
+    %pop2
+    %add_const(96)
+    // stack: e_offset, e_size, m_size
+    SWAP1 %mul_const(8)
+    PUSH 0
+
+    // Square-and-multiply, as for small moduli.
+expmod_large_exp_loop:
+    // stack: i, e_bits, e_offset, m_size
+    DUP2 DUP2 EQ
+    %jumpi(expmod_large_exp_end)
+    PUSH expmod_large_exp_squared %expmod_r %expmod_r %expmod_r
+    %jump(expmod_mul_mod)
+expmod_large_exp_squared:
+    // stack: i, e_bits, e_offset, m_size
+    DUP3 DUP2 %expmod_calldata_bit
+    SWAP1 %increment SWAP1
+    // stack: bit, i + 1, e_bits, e_offset, m_size
+    ISZERO
+    %jumpi(expmod_large_exp_loop)
+    PUSH expmod_large_exp_loop %expmod_r %expmod_base %expmod_r
+    %jump(expmod_mul_mod)
+expmod_large_exp_end:
+    // stack: i, e_bits, e_offset, m_size
+    %pop3
+    %jump(expmod_large_output)
+
+expmod_large_zero_modulus:
+    // stack: b_size, e_size, m_size
+    %pop2
+expmod_large_output:
+    // stack: m_size
+    DUP1 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    // Write R to the parent's return data, from its least significant limb.
+    PUSH 0
+expmod_large_output_loop:
+    // stack: k, end
+    DUP1 %expmod_n EQ
+    %jumpi(expmod_large_output_end)
+    // Limb k goes in the (up to) 16 bytes ending at end.
+    PUSH 16 DUP3 %min
+    DUP1 DUP4 SUB
+    // stack: start, len, k, end
+    DUP3 %expmod_r ADD %mload_kernel_general
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    %stack (parent_ctx, limb, start, len, k, end)
+        -> (parent_ctx, @SEGMENT_RETURNDATA, start, limb, len,
+            expmod_after_store_limb, start, k)
+    %jump(mstore_unpacking)
+expmod_after_store_limb:
+    // stack: offset', start, k
+    POP SWAP1 %increment
+    %jump(expmod_large_output_loop)
+expmod_large_output_end:
+    // stack: k, end
+    %pop2
+    %jump(precompile_success)
+
+// The number of limbs n, followed by the limbs of m, 1, the reduced base B, the
+// result R, the product accumulator T, and a difference D.
+%macro expmod_n
+    %mload_kernel_general(0)
+%endmacro
+
+%macro expmod_limbs(index)
+    %expmod_n %mul_const($index) %increment
+%endmacro
+
+%macro expmod_m
+    %expmod_limbs(0)
+%endmacro
+
+%macro expmod_one
+    %expmod_limbs(1)
+%endmacro
+
+%macro expmod_base
+    %expmod_limbs(2)
+%endmacro
+
+%macro expmod_r
+    %expmod_limbs(3)
+%endmacro
+
+%macro expmod_t
+    %expmod_limbs(4)
+%endmacro
+
+%macro expmod_d
+    %expmod_limbs(5)
+%endmacro
+
+// Returns the i-th bit, from the most significant one, of the calldata bytes
+// starting at offset.
+%macro expmod_calldata_bit
+    // stack: i, offset
+    SWAP1 DUP2 %shr_const(3) ADD
+    %load_calldata_byte
+    // stack: byte, i
+    SWAP1 %and_const(7) PUSH 7 SUB
+    SHR %and_const(1)
+    // stack: bit
+%endmacro
+
+// Pre stack: ptr, count, retdest
+// Post stack: (empty), with the count limbs from ptr set to 0.
+expmod_zero:
+    DUP2 ISZERO
+    %jumpi(expmod_zero_end)
+    PUSH 0 DUP2
+    %mstore_kernel_general
+    %increment
+    SWAP1 %decrement SWAP1
+    %jump(expmod_zero)
+expmod_zero_end:
+    %pop2
+    JUMP
+
+// Pre stack: x, y, out, retdest, where x and y point to integers less than m.
+// Post stack: (empty), with (x + y) % m stored at out, which may be x or y.
+expmod_add_mod:
+    %stack (x, y, out) -> (0, 0, x, y, out)
+expmod_add_mod_loop:
+    // stack: i, carry, x, y, out, retdest
+    DUP1 %expmod_n EQ
+    %jumpi(expmod_add_mod_end)
+    DUP1 DUP4 ADD %mload_kernel_general
+    DUP2 DUP6 ADD %mload_kernel_general
+    ADD DUP3 ADD
+    // stack: sum, i, carry, x, y, out, retdest
+    DUP1 %shr_const(128)
+    SWAP3 POP
+    %and_const(0xffffffffffffffffffffffffffffffff)
+    DUP2 DUP7 ADD
+    %mstore_kernel_general
+    %increment
+    %jump(expmod_add_mod_loop)
+expmod_add_mod_end:
+    %stack (i, carry, x, y, out) -> (out, carry)
+    // Fall through.
+
+// Pre stack: ptr, carry, retdest, where carry * 2^(128 n) + [ptr] < 2 m.
+// Post stack: (empty), with [ptr] reduced modulo m.
+expmod_reduce_once:
+    // Store [ptr] - m in D, and find whether it borrows.
+    %stack (ptr, carry) -> (0, 0, ptr, carry)
+expmod_reduce_once_loop:
+    // stack: i, borrow, ptr, carry, retdest
+    DUP1 %expmod_n EQ
+    %jumpi(expmod_reduce_once_end)
+    DUP1 DUP4 ADD %mload_kernel_general
+    %add_const(0x100000000000000000000000000000000)
+    DUP2 %expmod_m ADD %mload_kernel_general
+    SWAP1 SUB
+    DUP3 SWAP1 SUB
+    // stack: diff, i, borrow, ptr, carry, retdest
+    DUP1 %shr_const(128) ISZERO
+    SWAP3 POP
+    %and_const(0xffffffffffffffffffffffffffffffff)
+    DUP2 %expmod_d ADD
+    %mstore_kernel_general
+    %increment
+    %jump(expmod_reduce_once_loop)
+expmod_reduce_once_end:
+    // stack: i, borrow, ptr, carry, retdest
+    %stack (i, borrow, ptr, carry) -> (borrow, carry, ptr)
+    ISZERO OR
+    // stack: [ptr] >= m, ptr, retdest
+    %jumpi(expmod_reduce_once_sub)
+    POP
+    JUMP
+expmod_reduce_once_sub:
+    // stack: ptr, retdest
+    %expmod_n %expmod_d
+    %stack (d, n, ptr)
+        -> (0, @SEGMENT_KERNEL_GENERAL, ptr, 0, @SEGMENT_KERNEL_GENERAL, d, n)
+    %jump(memcpy)
+
+// Pre stack: a, b, out, retdest, where a and b point to integers less than m.
+// Post stack: (empty), with (a * b) % m stored at out, which may be a or b.
+expmod_mul_mod:
+    %expmod_n %expmod_t
+    %stack (t, n) -> (t, n, expmod_mul_mod_zeroed)
+    %jump(expmod_zero)
+expmod_mul_mod_zeroed:
+    // Going through the bits of b from the most significant one,
+    // T = (2 T + bit * a) % m.
+    %expmod_n %mul_const(128)
+expmod_mul_mod_loop:
+    // stack: i, a, b, out, retdest, where bit i - 1 of b comes next.
+    DUP1 ISZERO
+    %jumpi(expmod_mul_mod_end)
+    %decrement
+    %expmod_t
+    %stack (t) -> (t, t, t, expmod_mul_mod_doubled)
+    %jump(expmod_add_mod)
+expmod_mul_mod_doubled:
+    // stack: i, a, b, out, retdest
+    DUP1 %shr_const(7) DUP4 ADD %mload_kernel_general
+    DUP2 %and_const(127) SHR %and_const(1)
+    // stack: bit, i, a, b, out, retdest
+    ISZERO
+    %jumpi(expmod_mul_mod_loop)
+    DUP2 %expmod_t
+    %stack (t, a) -> (t, a, t, expmod_mul_mod_loop)
+    %jump(expmod_add_mod)
+expmod_mul_mod_end:
+    // stack: i, a, b, out, retdest
+    %expmod_n %expmod_t
+    %stack (t, n, i, a, b, out)
+        -> (0, @SEGMENT_KERNEL_GENERAL, out, 0, @SEGMENT_KERNEL_GENERAL, t, n)
+    %jump(memcpy)
+
+// Pre stack: x, retdest
+// Post stack: the number of bits of x
+bit_length:
+    PUSH 0
+bit_length_loop:
+    // stack: n, x, retdest
+    DUP2 ISZERO
+    %jumpi(bit_length_end)
+    %increment
+    SWAP1 %shr_const(1) SWAP1
+    %jump(bit_length_loop)
+bit_length_end:
+    %stack (n, x, retdest) -> (retdest, n)
+    JUMP
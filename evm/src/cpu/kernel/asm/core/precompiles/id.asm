@@ -0,0 +1,15 @@
+global precompile_id:
+    // stack: address
+    POP
+    %charge_precompile_gas_per_word(@ID_STATIC_GAS, @ID_DYNAMIC_GAS)
+
+    // Return our calldata as is.
+    %calldatasize
+    DUP1 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    GET_CONTEXT
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    %stack (parent_ctx, ctx, size)
+        -> (parent_ctx, @SEGMENT_RETURNDATA, 0,
+            ctx, @SEGMENT_CALLDATA, 0,
+            size, precompile_success)
+    %jump(memcpy)
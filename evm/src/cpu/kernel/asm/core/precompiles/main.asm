@@ -0,0 +1,117 @@
+// Precompiled contracts, which live at addresses @ECREC through @BLAKE2_F.
+// A call to one of them creates a new context as usual, but instead of running
+// EVM code, call_precompile jumps to the matching handler below. Each handler
+// reads its input from the new context's calldata, charges its gas, writes its
+// output to the parent's return data, and then terminates the context.
+
+%macro is_precompile
+    // stack: addr
+    DUP1 %ge_const(@ECREC)
+    SWAP1 %le_const(@BLAKE2_F)
+    AND
+    // stack: is_precompile
+%endmacro
+
+global handle_precompiles:
+    // stack: address
+    DUP1 %eq_const(@ECREC)    %jumpi(precompile_ecrec)
+    DUP1 %eq_const(@SHA256)   %jumpi(precompile_sha256)
+    DUP1 %eq_const(@RIP160)   %jumpi(precompile_rip160)
+    DUP1 %eq_const(@ID)       %jumpi(precompile_id)
+    DUP1 %eq_const(@EXPMOD)   %jumpi(precompile_expmod)
+    DUP1 %eq_const(@BN_ADD)   %jumpi(precompile_bn_add)
+    DUP1 %eq_const(@BN_MUL)   %jumpi(precompile_bn_mul)
+    DUP1 %eq_const(@SNARKV)   %jumpi(precompile_snarkv)
+    DUP1 %eq_const(@BLAKE2_F) %jumpi(precompile_blake2_f)
+    PANIC // Unreachable, since call_common checked %is_precompile.
+
+// Charges the given amount of gas for running a precompile, or fails if the
+// context was not given enough gas.
+%macro charge_precompile_gas
+    // stack: cost
+    %charge_gas
+    // stack: (empty)
+%endmacro
+
+%macro charge_precompile_gas_const(cost)
+    PUSH $cost
+    %charge_precompile_gas
+%endmacro
+
+// Charges static_gas + dynamic_gas * ceil(calldata_size / 32), the cost of the
+// precompiles whose price depends on the length of their input.
+%macro charge_precompile_gas_per_word(static_gas, dynamic_gas)
+    %calldatasize
+    %add_const(31) %div_const(32)
+    %mul_const($dynamic_gas)
+    %add_const($static_gas)
+    %charge_precompile_gas
+%endmacro
+
+// Loads the word at the given offset of our calldata. As in the EVM, bytes past
+// the end of the calldata read as zero.
+%macro load_calldata_word
+    // stack: offset
+    %stack (offset) -> (offset, 32, %%after)
+    PUSH @SEGMENT_CALLDATA
+    GET_CONTEXT
+    // stack: ctx, @SEGMENT_CALLDATA, offset, 32, %%after
+    %jump(mload_packing)
+%%after:
+    // stack: word
+%endmacro
+
+%macro load_calldata_word(offset)
+    PUSH $offset
+    %load_calldata_word
+%endmacro
+
+// Loads the given byte of our calldata, which reads as zero past its end.
+%macro load_calldata_byte
+    // stack: offset
+    PUSH @SEGMENT_CALLDATA
+    GET_CONTEXT
+    MLOAD_GENERAL
+    // stack: byte
+%endmacro
+
+// Stores the given word at the given offset of the parent's return data.
+%macro mstore_parent_returndata_word
+    // stack: offset, word
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    %stack (parent_ctx, offset, word)
+        -> (parent_ctx, @SEGMENT_RETURNDATA, offset, word, 32, %%after)
+    %jump(mstore_unpacking)
+%%after:
+    // stack: offset'
+    POP
+%endmacro
+
+// Returns the given word as the precompile's output.
+global precompile_return_word:
+    // stack: word
+    PUSH 32 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    PUSH 0 %mstore_parent_returndata_word
+    %jump(precompile_success)
+
+// Returns the given pair of words, such as the coordinates of a curve point, as
+// the precompile's output.
+global precompile_return_two_words:
+    // stack: word0, word1
+    PUSH 64 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    PUSH 0 %mstore_parent_returndata_word
+    PUSH 32 %mstore_parent_returndata_word
+    %jump(precompile_success)
+
+// Returns successfully without any output.
+global precompile_return_empty:
+    PUSH 0 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    // Fall through.
+
+// Terminates the precompile's context successfully. Its output must already be
+// in the parent's return data.
+global precompile_success:
+    %leftover_gas
+    // stack: leftover_gas
+    PUSH 1 // success
+    %jump(terminate_common)
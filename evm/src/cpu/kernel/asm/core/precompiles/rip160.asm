@@ -0,0 +1,10 @@
+global precompile_rip160:
+    // stack: address
+    POP
+    %charge_precompile_gas_per_word(@RIP160_STATIC_GAS, @RIP160_DYNAMIC_GAS)
+
+    // The 20-byte hash is returned left-padded to 32 bytes.
+    %calldatasize
+    GET_CONTEXT
+    %stack (ctx, size) -> (ctx, @SEGMENT_CALLDATA, 0, size, precompile_return_word)
+    %jump(ripemd)
@@ -0,0 +1,19 @@
+global precompile_sha256:
+    // stack: address
+    POP
+    %charge_precompile_gas_per_word(@SHA256_STATIC_GAS, @SHA256_DYNAMIC_GAS)
+
+    // sha2_pad expects the input in SEGMENT_KERNEL_GENERAL, as its length
+    // followed by its bytes.
+    %calldatasize
+    DUP1 %mstore_kernel_general(0)
+    // stack: size
+    GET_CONTEXT
+    %stack (ctx, size)
+        -> (0, @SEGMENT_KERNEL_GENERAL, 1,
+            ctx, @SEGMENT_CALLDATA, 0,
+            size, sha256_after_copy)
+    %jump(memcpy)
+sha256_after_copy:
+    PUSH precompile_return_word
+    %jump(sha2_pad)
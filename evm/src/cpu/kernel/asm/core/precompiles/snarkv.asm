@@ -0,0 +1,101 @@
+// The pairing check precompile of EIP-197. It returns 1 if the product of the
+// pairings of its input pairs is one, and 0 otherwise.
+//
+// Kernel memory layout, besides that of the pairing routines:
+//     238-249: the output of the Miller loop on the current pair
+//     250-261: the product of the Miller loop outputs so far
+global precompile_snarkv:
+    // stack: address
+    POP
+    // The input is a list of k pairs of a G1 point (64 bytes) and a G2 point (128 bytes).
+    %calldatasize
+    DUP1 %mod_const(192)
+    %jumpi(fault_exception)
+    %div_const(192)
+    // stack: k
+    DUP1 %mul_const(@SNARKV_DYNAMIC_GAS)
+    %add_const(@SNARKV_STATIC_GAS)
+    %charge_precompile_gas
+    // stack: k
+
+    // An empty product of pairings is 1.
+    DUP1 ISZERO
+    %jumpi(snarkv_empty)
+    PUSH 250 %store_one_fp12
+    PUSH 0
+snarkv_loop:
+    // stack: i, k
+    DUP2 DUP2 EQ
+    %jumpi(snarkv_end)
+    DUP1 %mul_const(192)
+    // stack: offset, i, k
+    DUP1 %add_const(32) %load_calldata_word
+    DUP2 %load_calldata_word
+    // stack: P: 2, offset, i, k
+    DUP2 DUP2 %ec_check
+    ISZERO
+    %jumpi(fault_exception)
+    // An element x + x_ i of Fp2 is encoded as x_ followed by x.
+    DUP3 %add_const(128) %load_calldata_word
+    DUP4 %add_const(160) %load_calldata_word
+    DUP5 %add_const(64)  %load_calldata_word
+    DUP6 %add_const(96)  %load_calldata_word
+    // stack: Q: 4, P: 2, offset, i, k
+    DUP1 %ge_const(@BN_BASE)
+    DUP3 %ge_const(@BN_BASE) OR
+    DUP4 %ge_const(@BN_BASE) OR
+    DUP5 %ge_const(@BN_BASE) OR
+    %jumpi(fault_exception)
+
+    // Pairs involving the identity of G1 or G2 contribute nothing to the product.
+    DUP1 DUP3 OR
+    DUP4 OR
+    DUP5 OR
+    ISZERO
+    // stack: Q == 0, Q: 4, P: 2, offset, i, k
+    %jumpi(snarkv_skip)
+    DUP4 DUP4 DUP4 DUP4
+    %twisted_check
+    ISZERO
+    %jumpi(fault_exception)
+    %stack (q: 4) -> (q, snarkv_after_subgroup_check, q)
+    %jump(bn254_twisted_in_subgroup)
+snarkv_after_subgroup_check:
+    // stack: in_subgroup, Q: 4, P: 2, offset, i, k
+    ISZERO
+    %jumpi(fault_exception)
+    DUP6 DUP6 %ec_isidentity
+    %jumpi(snarkv_skip)
+    %stack (q: 4, p: 2) -> (p, q, 238, snarkv_after_miller)
+    // stack: P: 2, Q: 4, 238, snarkv_after_miller, offset, i, k
+    %jump(bn254_miller)
+snarkv_after_miller:
+    // stack: offset, i, k
+    %mul_fp12(250, 238, 250)
+    POP
+    %jump(snarkv_next)
+snarkv_skip:
+    // stack: Q: 4, P: 2, offset, i, k
+    %pop7
+snarkv_next:
+    // stack: i, k
+    %increment
+    %jump(snarkv_loop)
+
+snarkv_end:
+    // stack: i, k
+    %pop2
+    PUSH snarkv_check
+    PUSH 250
+    // stack: 250, snarkv_check
+    %jump(bn254_final_exponent)
+snarkv_check:
+    PUSH 250 %is_one_fp12
+    // stack: is_one
+    %jump(precompile_return_word)
+
+snarkv_empty:
+    // stack: k
+    POP
+    PUSH 1
+    %jump(precompile_return_word)
@@ -6,6 +6,19 @@
 // Pre stack: retdest
 // Post stack: (empty)
 global process_normalized_txn:
+    // stack: retdest
+    // Remember the state trie at the start of the transaction, so that SSTORE
+    // can look up the original values of storage slots.
+    %mload_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
+    %mstore_global_metadata(@GLOBAL_METADATA_TXN_START_STATE_TRIE_ROOT)
+    PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_REFUND_COUNTER)
+    PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_LOGS_LEN)
+    PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN)
+    PUSH 0 %mstore_global_metadata(@GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN)
+
+    PUSH process_normalized_txn_warmed
+    %jump(warm_txn_addresses)
+process_normalized_txn_warmed:
     // stack: retdest
     PUSH validate
     %jump(intrinsic_gas)
@@ -13,15 +26,70 @@ global process_normalized_txn:
 global validate:
     // stack: intrinsic_gas, retdest
     // TODO: Check signature? (Or might happen in type_0.asm etc.)
-    // TODO: Assert nonce is correct.
-    // TODO: Assert sender has no code.
-    POP // TODO: Assert gas_limit >= intrinsic_gas.
-    // stack: retdest
+    // stack: intrinsic_gas, retdest
+    DUP1 %mload_txn_field(@TXN_FIELD_GAS_LIMIT) LT
+    %jumpi(invalid_txn)
+    // The gas limit must fit in what's left of the block's.
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_GAS_USED)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_GAS_LIMIT)
+    SUB
+    %mload_txn_field(@TXN_FIELD_GAS_LIMIT)
+    GT %jumpi(invalid_txn)
+    // The nonce must be the sender's current one.
+    %mload_txn_field(@TXN_FIELD_ORIGIN) %get_nonce
+    %mload_txn_field(@TXN_FIELD_NONCE)
+    EQ ISZERO %jumpi(invalid_txn)
+    // The sender must not have code, per EIP-3607.
+    PUSH validate_got_code_hash
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    %jump(extcodehash)
+validate_got_code_hash:
+    // stack: code_hash, intrinsic_gas, retdest
+    // A missing account has a code hash of 0 here.
+    DUP1 ISZERO
+    SWAP1 %eq_const(@EMPTY_STRING_HASH)
+    OR ISZERO %jumpi(invalid_txn)
+    // The sender must be willing to pay the base fee, per EIP-1559.
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BASE_FEE)
+    %mload_txn_field(@TXN_FIELD_MAX_FEE_PER_GAS)
+    LT %jumpi(invalid_txn)
+    // The priority fee can't exceed the max fee, also per EIP-1559.
+    %mload_txn_field(@TXN_FIELD_MAX_FEE_PER_GAS)
+    %mload_txn_field(@TXN_FIELD_MAX_PRIORITY_FEE_PER_GAS)
+    GT %jumpi(invalid_txn)
+    // The sender must be able to afford the value, along with the whole gas
+    // limit at the maximum fee. We check max_fee <= (balance - value) / gas_limit,
+    // since the total cost may not fit in a word.
+    PUSH validate_got_balance
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    %jump(balance)
+validate_got_balance:
+    // stack: balance, intrinsic_gas, retdest
+    %mload_txn_field(@TXN_FIELD_VALUE)
+    DUP2 DUP2 GT
+    %stack (value_too_high, value, balance) -> (value, balance, value_too_high)
+    SWAP1 SUB
+    %mload_txn_field(@TXN_FIELD_GAS_LIMIT)
+    SWAP1 DIV
+    // stack: (balance - value) / gas_limit, value_too_high, intrinsic_gas, retdest
+    %mload_txn_field(@TXN_FIELD_MAX_FEE_PER_GAS) GT
+    OR
+    %jumpi(invalid_txn)
+    // stack: intrinsic_gas, retdest
 
 global charge_gas:
-    // TODO: Deduct gas limit from sender (some gas may be refunded later).
+    // stack: intrinsic_gas, retdest
+    // Deduct the cost of the whole gas limit from the sender. Whatever gas is
+    // left unused gets refunded at the end of the transaction.
+    %effective_gas_price
+    %mload_txn_field(@TXN_FIELD_GAS_LIMIT)
+    MUL
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    %deduct_eth
+    // stack: deduct_eth_status, intrinsic_gas, retdest
+    %jumpi(invalid_txn)
 
-    PUSH 0 // TODO: Push sender.
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
     %increment_nonce
 
 global process_based_on_type:
@@ -30,31 +98,199 @@ global process_based_on_type:
     %jump(process_message_txn)
 
 global process_contract_creation_txn:
-    // stack: retdest
-    // Push the code address & length onto the stack, then call `create`.
+    // stack: intrinsic_gas, retdest
+    // The address is based on the sender's nonce before it was incremented, i.e.
+    // the transaction's nonce.
+    PUSH process_contract_creation_txn_got_address
+    %mload_txn_field(@TXN_FIELD_NONCE)
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    // stack: sender, nonce, process_contract_creation_txn_got_address, intrinsic_gas, retdest
+    %jump(get_create_address)
+
+global process_contract_creation_txn_got_address:
+    // stack: address, intrinsic_gas, retdest
+    // The init code gets whatever gas is left after paying the intrinsic gas.
+    DUP2 %mload_txn_field(@TXN_FIELD_GAS_LIMIT) SUB
+    %mload_txn_field(@TXN_FIELD_VALUE)
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
     %mload_txn_field(@TXN_FIELD_DATA_LEN)
-    // stack: code_len, retdest
-    PUSH 0
-    // stack: code_offset, code_len, retdest
-    PUSH @SEGMENT_TXN_DATA
-    // stack: code_segment, code_offset, code_len, retdest
-    PUSH 0 // context
-    // stack: CODE_ADDR, code_len, retdest
-    %jump(create)
+    // stack: code_len, sender, endowment, gas, address, intrinsic_gas, retdest
+    %stack (code_len, sender, endowment, gas, address)
+        -> (address, sender, endowment, 0, @SEGMENT_TXN_DATA, 0, code_len, gas,
+            process_contract_creation_txn_after_create)
+    %jump(create_common)
+
+global process_contract_creation_txn_after_create:
+    // stack: success, leftover_gas, intrinsic_gas, retdest
+    %stack (success, leftover_gas, intrinsic_gas) -> (success, leftover_gas)
+    %jump(finalize_txn)
 
 global process_message_txn:
-    // stack: retdest
+    // stack: intrinsic_gas, retdest
+    // Remember the state before any ETH is transferred, so that the callee can
+    // revert to it.
+    %mload_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
     %mload_txn_field(@TXN_FIELD_VALUE)
     %mload_txn_field(@TXN_FIELD_TO)
     %mload_txn_field(@TXN_FIELD_ORIGIN)
-    // stack: from, to, amount, retdest
+    // stack: from, to, amount, checkpoint, intrinsic_gas, retdest
     %transfer_eth
-    // stack: transfer_eth_status, retdest
-    %jumpi(process_message_txn_insufficient_balance)
-    // stack: retdest
-    // TODO: If code is non-empty, execute it in a new context.
+    // stack: transfer_eth_status, checkpoint, intrinsic_gas, retdest
+    // Unreachable, since validate checked that the sender can afford the value.
+    %jumpi(panic)
+    // stack: checkpoint, intrinsic_gas, retdest
+
+    // If the recipient is a precompile or has code, run it in a new context.
+    %mload_txn_field(@TXN_FIELD_TO) %is_precompile
+    %mload_txn_field(@TXN_FIELD_TO) %extcodesize
+    OR
+    %jumpi(process_message_txn_execute)
+    // stack: checkpoint, intrinsic_gas, retdest
+    POP
+    %mload_txn_field(@TXN_FIELD_TO) %insert_touched_addresses
+    // Only the intrinsic gas is used.
+    %mload_txn_field(@TXN_FIELD_GAS_LIMIT)
+    SUB
+    // stack: leftover_gas, retdest
+    PUSH 1 // success
+    %jump(finalize_txn)
+
+process_message_txn_execute:
+    // stack: checkpoint, intrinsic_gas, retdest
+    %create_context
+    // stack: new_ctx, checkpoint, intrinsic_gas, retdest
+    // Note: We can't use %mstore_context_metadata because we're writing to
+    // memory owned by the new context, not the current one.
+    %stack (new_ctx, checkpoint)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_STATE_TRIE_CHECKPOINT_PTR, checkpoint, new_ctx)
+    MSTORE_GENERAL
+    %set_new_ctx_checkpoints
+    // The recipient is touched by the callee, so that this is undone if it reverts.
+    %mload_txn_field(@TXN_FIELD_TO) %insert_touched_addresses
+    %mload_txn_field(@TXN_FIELD_VALUE)
+    %stack (value, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALL_VALUE, value, new_ctx)
+    MSTORE_GENERAL
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    %stack (sender, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALLER, sender, new_ctx)
+    MSTORE_GENERAL
+    %mload_txn_field(@TXN_FIELD_TO)
+    %stack (address, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_ADDRESS, address, new_ctx)
+    MSTORE_GENERAL
+    // The callee gets whatever gas is left after paying the intrinsic gas.
+    DUP2 %mload_txn_field(@TXN_FIELD_GAS_LIMIT) SUB
+    %stack (gas, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_GAS_LIMIT, gas, new_ctx)
+    MSTORE_GENERAL
+    %mload_txn_field(@TXN_FIELD_DATA_LEN)
+    %stack (data_len, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CALLDATA_SIZE, data_len, new_ctx)
+    MSTORE_GENERAL
+    %stack (new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_PARENT_PC, process_message_txn_after_call, new_ctx)
+    MSTORE_GENERAL
+    // stack: new_ctx, intrinsic_gas, retdest
+
+    // Copy the transaction's data to the new context's calldata.
+    %mload_txn_field(@TXN_FIELD_DATA_LEN)
+    %stack (data_len, new_ctx)
+        -> (new_ctx, @SEGMENT_CALLDATA, 0,
+            0, @SEGMENT_TXN_DATA, 0,
+            data_len, process_message_txn_copied_calldata, new_ctx)
+    %jump(memcpy)
+process_message_txn_copied_calldata:
+    // stack: new_ctx, intrinsic_gas, retdest
+    %mload_txn_field(@TXN_FIELD_TO)
+    %stack (address, new_ctx)
+        -> (address, new_ctx, @SEGMENT_CODE, process_message_txn_code_loaded, new_ctx)
+    %jump(load_code)
+process_message_txn_code_loaded:
+    // stack: code_size, new_ctx, intrinsic_gas, retdest
+    PUSH process_message_txn_jumpdests_analyzed DUP2 DUP4
+    // stack: new_ctx, code_size, process_message_txn_jumpdests_analyzed, code_size, new_ctx, intrinsic_gas, retdest
+    %jump(jumpdest_analysis)
+process_message_txn_jumpdests_analyzed:
+    // stack: code_size, new_ctx, intrinsic_gas, retdest
+    %stack (code_size, new_ctx)
+        -> (new_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_CODE_SIZE, code_size, new_ctx)
+    MSTORE_GENERAL
+    // stack: new_ctx, intrinsic_gas, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %increment
+    %mstore_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+
+    %mload_txn_field(@TXN_FIELD_TO) %is_precompile
+    %jumpi(process_message_txn_precompile)
+    // Switch to the new context and go to usermode with PC=0. The rest of our
+    // stack will be waiting for us in process_message_txn_after_call.
+    SET_CONTEXT
+    // stack: (empty, since we're in the new context)
+    PUSH 0 // kexit_info for PC=0 in user mode
+    EXIT_KERNEL
+
+process_message_txn_precompile:
+    // stack: new_ctx, intrinsic_gas, retdest
+    SET_CONTEXT
+    // stack: (empty, since we're in the new context)
+    %mload_txn_field(@TXN_FIELD_TO)
+    %jump(handle_precompiles)
+
+global process_message_txn_after_call:
+    // stack: success, leftover_gas, intrinsic_gas, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %decrement
+    %mstore_global_metadata(@GLOBAL_METADATA_CALL_STACK_DEPTH)
+    %stack (success, leftover_gas, intrinsic_gas) -> (success, leftover_gas)
+    %jump(finalize_txn)
+
+// An invalid transaction is skipped, leaving the state as it was. It gets no
+// receipt, and uses no gas of the block.
+global invalid_txn:
+    // stack: intrinsic_gas, retdest
+    POP
     JUMP
 
-global process_message_txn_insufficient_balance:
-    // stack: retdest
-    PANIC // TODO
+// Settles the fees of a transaction once it has run, deletes the accounts which
+// it destroyed or left empty, and records its receipt.
+// Pre stack: success, leftover_gas, retdest
+// Post stack: (empty)
+global finalize_txn:
+    // stack: success, leftover_gas, retdest
+    SWAP1 %mload_txn_field(@TXN_FIELD_GAS_LIMIT) SUB
+    // stack: gas_used_before_refund, success, retdest
+    // The refund is capped at a fifth of the gas used, per EIP-3529.
+    DUP1 %div_const(@MAX_REFUND_QUOTIENT)
+    %mload_global_metadata(@GLOBAL_METADATA_REFUND_COUNTER)
+    %min
+    // stack: refund, gas_used_before_refund, success, retdest
+    SWAP1 SUB
+    // stack: gas_used, success, retdest
+
+    // Refund the sender for the gas it didn't use, at the price it paid.
+    %effective_gas_price
+    DUP2 %mload_txn_field(@TXN_FIELD_GAS_LIMIT) SUB
+    // stack: gas_unused, gas_price, gas_used, success, retdest
+    MUL
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    %add_eth
+    // stack: gas_used, success, retdest
+
+    // Pay the priority fee to the block beneficiary. The base fee is burned.
+    %effective_gas_price
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BASE_FEE)
+    SWAP1 SUB
+    // stack: priority_fee_per_gas, gas_used, success, retdest
+    DUP2 MUL
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BENEFICIARY)
+    // stack: beneficiary, priority_fee, gas_used, success, retdest
+    DUP1 %insert_touched_addresses
+    %add_eth
+    // stack: gas_used, success, retdest
+
+    %delete_all_selfdestructed_addresses
+    %delete_all_touched_addresses
+    // stack: gas_used, success, retdest
+    SWAP1
+    %jump(process_receipt)
@@ -0,0 +1,292 @@
+// Builds the current transaction's receipt, and inserts it into the receipt
+// trie. A receipt is the RLP encoding of
+//     [status, cumulative_gas_used, logs_bloom, logs]
+// where each log, taken from @SEGMENT_LOGS, is encoded as
+//     [address, [topic_0, ..., topic_n], data].
+// The receipt of a typed transaction is further prefixed with its type byte.
+// The receipt trie maps RLP(txn_number) to the encoded receipt.
+//
+// Pre stack: status, gas_used, retdest
+// Post stack: (empty)
+global process_receipt:
+    // stack: status, gas_used, retdest
+    SWAP1
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_GAS_USED)
+    ADD
+    DUP1 %mstore_global_metadata(@GLOBAL_METADATA_BLOCK_GAS_USED)
+    // stack: cumulative_gas_used, status, retdest
+    SWAP1
+    // We write the list payload starting at position 9, leaving room for the
+    // list prefix, which we prepend once we know the payload length.
+    PUSH 9
+    %encode_rlp_scalar
+    // stack: pos, cumulative_gas_used, retdest
+    %encode_rlp_scalar
+    // stack: pos, retdest
+    PUSH 256 SWAP1
+    %encode_rlp_multi_byte_string_prefix
+    // stack: bloom_pos, retdest
+
+    // Clear the bloom filter, since @SEGMENT_RLP_RAW may contain stale data.
+    PUSH 0
+process_receipt_clear_bloom_loop:
+    // stack: i, bloom_pos, retdest
+    DUP1 %eq_const(256) %jumpi(process_receipt_clear_bloom_done)
+    PUSH 0 DUP3 DUP3 ADD
+    // stack: bloom_pos + i, 0, i, bloom_pos, retdest
+    %mstore_rlp
+    %increment
+    %jump(process_receipt_clear_bloom_loop)
+process_receipt_clear_bloom_done:
+    POP
+
+    // In a first pass over the logs, we add them to the bloom filter, and
+    // compute the payload length of the logs list.
+    PUSH 0 // logs_payload_len
+    PUSH 0 // log_pos
+process_receipt_bloom_loop:
+    // stack: log_pos, logs_payload_len, bloom_pos, retdest
+    DUP1 %mload_global_metadata(@GLOBAL_METADATA_LOGS_LEN) EQ
+    %jumpi(process_receipt_bloom_done)
+    DUP1 %mload_kernel(@SEGMENT_LOGS) // address
+    %keccak256_word(20)
+    DUP4 SWAP1 %add_to_bloom
+    // stack: log_pos, logs_payload_len, bloom_pos, retdest
+    DUP1 %increment %mload_kernel(@SEGMENT_LOGS)
+    DUP2 %add_const(3) ADD
+    // stack: topics_end, log_pos, logs_payload_len, bloom_pos, retdest
+    DUP2 %add_const(3)
+process_receipt_bloom_topics_loop:
+    // stack: topic_pos, topics_end, log_pos, logs_payload_len, bloom_pos, retdest
+    DUP2 DUP2 EQ %jumpi(process_receipt_bloom_topics_done)
+    DUP1 %mload_kernel(@SEGMENT_LOGS) // topic
+    %keccak256_word(32)
+    DUP6 SWAP1 %add_to_bloom
+    %increment
+    %jump(process_receipt_bloom_topics_loop)
+process_receipt_bloom_topics_done:
+    %pop2
+    // stack: log_pos, logs_payload_len, bloom_pos, retdest
+    DUP1 %log_payload_len %rlp_list_len
+    DUP3 ADD SWAP2 POP
+    // stack: log_pos, logs_payload_len', bloom_pos, retdest
+    %next_log
+    %jump(process_receipt_bloom_loop)
+
+process_receipt_bloom_done:
+    // stack: log_pos, logs_payload_len, bloom_pos, retdest
+    POP
+    SWAP1 %add_const(256)
+    // stack: pos, logs_payload_len, retdest
+    %encode_rlp_list_prefix
+    // stack: pos, retdest
+
+    // In a second pass, we encode each log.
+    PUSH 0 // log_pos
+process_receipt_encode_logs_loop:
+    // stack: log_pos, pos, retdest
+    DUP1 %mload_global_metadata(@GLOBAL_METADATA_LOGS_LEN) EQ
+    %jumpi(process_receipt_encode_logs_done)
+    DUP1 %log_payload_len
+    DUP3 %encode_rlp_list_prefix
+    SWAP2 POP
+    // stack: log_pos, pos, retdest
+    DUP1 %mload_kernel(@SEGMENT_LOGS) // address
+    DUP3 %encode_rlp_160
+    SWAP2 POP
+    // stack: log_pos, pos, retdest
+    DUP1 %increment %mload_kernel(@SEGMENT_LOGS) // num_topics
+    %mul_const(33) // each topic takes 1 + 32 bytes
+    DUP3 %encode_rlp_list_prefix
+    SWAP2 POP
+    // stack: log_pos, pos, retdest
+    DUP1 %increment %mload_kernel(@SEGMENT_LOGS)
+    DUP2 %add_const(3) ADD
+    // stack: topics_end, log_pos, pos, retdest
+    DUP2 %add_const(3)
+process_receipt_encode_topics_loop:
+    // stack: topic_pos, topics_end, log_pos, pos, retdest
+    DUP2 DUP2 EQ %jumpi(process_receipt_encode_topics_done)
+    DUP1 %mload_kernel(@SEGMENT_LOGS) // topic
+    DUP5 %encode_rlp_256
+    SWAP4 POP
+    %increment
+    %jump(process_receipt_encode_topics_loop)
+process_receipt_encode_topics_done:
+    // The data comes right after the topics.
+    // stack: data_pos, topics_end, log_pos, pos, retdest
+    POP
+    DUP2 %add_const(2) %mload_kernel(@SEGMENT_LOGS) // data_len
+    %stack (data_len, data_pos, log_pos, pos)
+        -> (pos, 0, @SEGMENT_LOGS, data_pos, data_len,
+            process_receipt_encode_logs_after_data, log_pos)
+    %jump(encode_rlp_string)
+process_receipt_encode_logs_after_data:
+    // stack: pos, log_pos, retdest
+    SWAP1 %next_log
+    %jump(process_receipt_encode_logs_loop)
+
+process_receipt_encode_logs_done:
+    // stack: log_pos, end_pos, retdest
+    POP
+    %prepend_rlp_list_prefix
+    // stack: start_pos, rlp_len, retdest
+    // The receipt of a typed transaction is prefixed with its type, per EIP-2718.
+    %mload_txn_field(@TXN_FIELD_TYPE)
+    ISZERO %jumpi(process_receipt_store)
+    %decrement
+    %mload_txn_field(@TXN_FIELD_TYPE) DUP2 %mstore_rlp
+    SWAP1 %increment SWAP1
+
+process_receipt_store:
+    // stack: start_pos, rlp_len, retdest
+    // Copy the receipt to the trie data, prefixed by its length.
+    %get_trie_data_size
+    // stack: value_ptr, start_pos, rlp_len, retdest
+    DUP3 %append_to_trie_data
+    %get_trie_data_size
+    // stack: receipt_ptr, value_ptr, start_pos, rlp_len, retdest
+    DUP1 DUP5 ADD %set_trie_data_size
+    %stack (receipt_ptr, value_ptr, start_pos, rlp_len)
+        -> (0, @SEGMENT_TRIE_DATA, receipt_ptr,
+            0, @SEGMENT_RLP_RAW, start_pos,
+            rlp_len, process_receipt_insert, value_ptr)
+    %jump(memcpy)
+
+process_receipt_insert:
+    // stack: value_ptr, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_TXN_NUMBER)
+    DUP1 %increment %mstore_global_metadata(@GLOBAL_METADATA_TXN_NUMBER)
+    // stack: txn_number, value_ptr, retdest
+    %txn_number_to_receipt_key
+    // stack: num_nibbles, key, value_ptr, retdest
+    %jump(mpt_insert_receipt_trie)
+
+// Computes the receipt trie key of the given transaction, namely RLP(txn_number).
+//
+// Pre stack: txn_number
+// Post stack: num_nibbles, key
+%macro txn_number_to_receipt_key
+    // stack: txn_number
+    DUP1 %gt_const(0x7f) %jumpi(%%medium)
+    DUP1 ISZERO %jumpi(%%zero)
+    // txn_number is its own encoding.
+    PUSH 2
+    %jump(%%finish)
+%%zero:
+    // stack: 0
+    // RLP encodes 0 as the empty string, i.e. 0x80.
+    %stack (txn_number) -> (2, 0x80)
+    %jump(%%finish)
+%%medium:
+    // stack: txn_number
+    DUP1 %num_bytes
+    // stack: len, txn_number
+    DUP1 %add_const(0x80)
+    DUP2 %mul_const(8)
+    SHL
+    // stack: prefix << (8 * len), len, txn_number
+    %stack (prefix_shifted, len, txn_number) -> (len, prefix_shifted, txn_number)
+    %increment %mul_const(2)
+    // stack: num_nibbles, prefix_shifted, txn_number
+    %stack (num_nibbles, prefix_shifted, txn_number) -> (prefix_shifted, txn_number, num_nibbles)
+    OR
+    SWAP1
+%%finish:
+%endmacro
+
+// Adds the given hash to the 2048-bit bloom filter at the given position of
+// @SEGMENT_RLP_RAW. Following the yellow paper, the low 11 bits of each of the
+// first three pairs of bytes of the hash select a bit to set.
+//
+// Pre stack: hash, bloom_pos, retdest
+// Post stack: (empty)
+global add_to_bloom:
+    // stack: hash, bloom_pos, retdest
+    %rep 3
+        DUP1 %shr_const(240) %and_const(0x7ff)
+        // stack: bit_index, hash, bloom_pos, retdest
+        PUSH 1 DUP2 %and_const(7) SHL
+        // stack: bit, bit_index, hash, bloom_pos, retdest
+        SWAP1 %shr_const(3) PUSH 255 SUB
+        // The bloom filter is big-endian, so bit 0 is in its last byte.
+        DUP4 ADD
+        // stack: byte_pos, bit, hash, bloom_pos, retdest
+        DUP1 %mload_kernel(@SEGMENT_RLP_RAW)
+        SWAP1 SWAP2 OR SWAP1
+        // stack: byte_pos, byte | bit, hash, bloom_pos, retdest
+        %mstore_rlp
+        // stack: hash, bloom_pos, retdest
+        %shl_const(16)
+    %endrep
+    %stack (hash, bloom_pos, retdest) -> (retdest)
+    JUMP
+
+// Convenience macro to call add_to_bloom and return where we left off.
+%macro add_to_bloom
+    %stack (hash, bloom_pos) -> (hash, bloom_pos, %%after)
+    %jump(add_to_bloom)
+%%after:
+%endmacro
+
+// Given the position of a log in @SEGMENT_LOGS, returns the position of the next one.
+%macro next_log
+    // stack: log_pos
+    DUP1 %increment %mload_kernel(@SEGMENT_LOGS) // num_topics
+    DUP2 %add_const(2) %mload_kernel(@SEGMENT_LOGS) // data_len
+    ADD ADD %add_const(3)
+    // stack: next_log_pos
+%endmacro
+
+// Given the position of a log in @SEGMENT_LOGS, computes the payload length of
+// its RLP encoding.
+%macro log_payload_len
+    // stack: log_pos
+    DUP1 %increment %mload_kernel(@SEGMENT_LOGS)
+    %mul_const(33) // each topic takes 1 + 32 bytes
+    %rlp_list_len
+    // stack: topics_rlp_len, log_pos
+    SWAP1
+    %stack (log_pos) -> (log_pos, %%after)
+    %jump(log_data_rlp_len)
+%%after:
+    // stack: data_rlp_len, topics_rlp_len
+    ADD
+    %add_const(21) // the address takes 1 + 20 bytes
+    // stack: payload_len
+%endmacro
+
+// Computes the length of the RLP encoding of the given log's data.
+//
+// Pre stack: log_pos, retdest
+// Post stack: data_rlp_len
+log_data_rlp_len:
+    // stack: log_pos, retdest
+    DUP1 %add_const(2) %mload_kernel(@SEGMENT_LOGS)
+    // stack: data_len, log_pos, retdest
+    DUP1 %eq_const(1) %jumpi(log_data_rlp_len_single_byte)
+log_data_rlp_len_string:
+    // stack: data_len, log_pos, retdest
+    SWAP1 POP
+    DUP1 %gt_const(55) %jumpi(log_data_rlp_len_large)
+    // The prefix is a single byte.
+    %increment
+    SWAP1
+    JUMP
+log_data_rlp_len_large:
+    // The prefix is a byte followed by the length itself.
+    // stack: data_len, retdest
+    DUP1 %num_bytes
+    ADD %increment
+    SWAP1
+    JUMP
+log_data_rlp_len_single_byte:
+    // A single byte below 0x80 is its own encoding.
+    // stack: 1, log_pos, retdest
+    DUP2 %increment %mload_kernel(@SEGMENT_LOGS)
+    DUP3 %add_const(3) ADD
+    %mload_kernel(@SEGMENT_LOGS)
+    // stack: data_byte, 1, log_pos, retdest
+    %ge_const(0x80) %jumpi(log_data_rlp_len_string)
+    %stack (data_len, log_pos, retdest) -> (retdest, 1)
+    JUMP
@@ -0,0 +1,51 @@
+// The addresses of the accounts which self-destructed in the current
+// transaction. They are kept as an append-only list in
+// @SEGMENT_SELFDESTRUCT_LIST, so a reverted context forgets its self-destructs
+// by restoring the length saved at its creation. The accounts are only deleted
+// at the end of the transaction, since until then their code keeps running.
+
+// Appends the address to the self-destruct list. An address may appear more
+// than once, which is harmless since deleting an account twice is a no-op.
+%macro insert_selfdestruct_list
+    // stack: addr
+    %mload_global_metadata(@GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN)
+    // stack: len, addr
+    DUP1 %increment
+    %mstore_global_metadata(@GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN)
+    // stack: len, addr
+    %mstore_kernel(@SEGMENT_SELFDESTRUCT_LIST)
+    // stack: (empty)
+%endmacro
+
+// Deletes the self-destructed accounts from the state trie.
+// Pre stack: retdest
+// Post stack: (empty)
+global delete_all_selfdestructed_addresses:
+    // stack: retdest
+    %mload_global_metadata(@GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN)
+    // stack: len, retdest
+    PUSH 0
+delete_all_selfdestructed_addresses_loop:
+    // stack: i, len, retdest
+    DUP2 DUP2 EQ %jumpi(delete_all_selfdestructed_addresses_done)
+    // stack: i, len, retdest
+    PUSH delete_all_selfdestructed_addresses_next
+    DUP2 %mload_kernel(@SEGMENT_SELFDESTRUCT_LIST)
+    // stack: addr, delete_all_selfdestructed_addresses_next, i, len, retdest
+    %addr_to_state_key
+    %jump(mpt_delete_state_trie)
+delete_all_selfdestructed_addresses_next:
+    // stack: i, len, retdest
+    %increment
+    %jump(delete_all_selfdestructed_addresses_loop)
+delete_all_selfdestructed_addresses_done:
+    // stack: i, len, retdest
+    %pop2
+    JUMP
+
+%macro delete_all_selfdestructed_addresses
+    PUSH %%after
+    %jump(delete_all_selfdestructed_addresses)
+%%after:
+    // stack: (empty)
+%endmacro
@@ -1,49 +1,2 @@
 // Labels for unimplemented syscalls to make the kernel assemble.
 // Each label should be removed from this file once it is implemented.
-
-global sys_sdiv:
-global sys_smod:
-global sys_signextend:
-global sys_slt:
-global sys_sgt:
-global sys_sar:
-global sys_address:
-global sys_balance:
-global sys_origin:
-global sys_caller:
-global sys_callvalue:
-global sys_calldataload:
-global sys_calldatasize:
-global sys_calldatacopy:
-global sys_codesize:
-global sys_codecopy:
-global sys_gasprice:
-global sys_extcodesize:
-global sys_extcodecopy:
-global sys_returndatasize:
-global sys_returndatacopy:
-global sys_extcodehash:
-global sys_blockhash:
-global sys_coinbase:
-global sys_timestamp:
-global sys_number:
-global sys_prevrandao:
-global sys_gaslimit:
-global sys_chainid:
-global sys_selfbalance:
-global sys_basefee:
-global sys_sload:
-global sys_sstore:
-global sys_msize:
-global sys_log0:
-global sys_log1:
-global sys_log2:
-global sys_log3:
-global sys_log4:
-global sys_create:
-global sys_call:
-global sys_callcode:
-global sys_delegatecall:
-global sys_create2:
-global sys_staticcall:
-    PANIC
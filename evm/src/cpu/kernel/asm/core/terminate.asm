@@ -2,26 +2,79 @@
 // RETURN, SELFDESTRUCT, REVERT, and exceptions such as stack underflow.
 
 global sys_stop:
-    // TODO: Set parent context's CTX_METADATA_RETURNDATA_SIZE to 0.
-    // TODO: Refund unused gas to parent.
+    // stack: kexit_info
+    // A STOP leaves no return data.
+    PUSH 0 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %leftover_gas
+    // stack: leftover_gas
+    PUSH 1 // success
     %jump(terminate_common)
 
 global sys_return:
-    // TODO: Set parent context's CTX_METADATA_RETURNDATA_SIZE.
-    // TODO: Copy returned memory to parent context's RETURNDATA (but not if we're returning from a constructor?)
-    // TODO: Copy returned memory to parent context's memory (as specified in their call instruction)
-    // TODO: Refund unused gas to parent.
+    // stack: kexit_info, offset, size
+    // When returning from a constructor, after_constructor stores the return data as the new code.
+    %stack (kexit_info, offset, size) -> (offset, size, sys_return_finish)
+    %jump(copy_returndata_to_parent)
+sys_return_finish:
+    %leftover_gas
+    // stack: leftover_gas
+    PUSH 1 // success
     %jump(terminate_common)
 
 global sys_selfdestruct:
-    %consume_gas_const(@GAS_SELFDESTRUCT)
-    // TODO: Destroy account.
-    // TODO: Refund unused gas to parent.
+    // stack: kexit_info, recipient
+    // Destroying an account is a state modification, so it is disallowed in static mode.
+    %mload_context_metadata(@CTX_METADATA_STATIC) %jumpi(fault_exception)
+    SWAP1 %u256_to_addr
+    // stack: recipient, kexit_info
+
+    // A cold recipient costs extra, per EIP-2929, and so does a dead one which
+    // receives a nonzero balance, since it will be created.
+    DUP1 %insert_accessed_addresses
+    %mul_const(@GAS_COLDACCOUNTACCESS)
+    // stack: cold_access_gas, recipient, kexit_info
+    DUP2 %is_dead
+    %current_account_data %increment %mload_trie_data
+    // stack: balance, recipient_is_dead, cold_access_gas, recipient, kexit_info
+    ISZERO ISZERO AND
+    %mul_const(@GAS_NEWACCOUNT)
+    ADD
+    %add_const(@GAS_SELFDESTRUCT)
+    %charge_gas
+    // stack: recipient, kexit_info
+
+    // The account is only deleted at the end of the transaction.
+    %address %insert_selfdestruct_list
+    DUP1 %insert_touched_addresses
+
+    // Send our whole balance to the recipient. We then deduct our balance as it
+    // is after sending, zeroing it, so that if the recipient is ourselves, the ETH
+    // is burned.
+    %current_account_data %increment %mload_trie_data
+    // stack: balance, recipient, kexit_info
+    SWAP1 %add_eth
+    // stack: kexit_info
+    %current_account_data %increment %mload_trie_data
+    %address %deduct_eth
+    // stack: deduct_eth_status, kexit_info
+    %pop2
+
+    // Like a STOP, a SELFDESTRUCT leaves no return data.
+    PUSH 0 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %leftover_gas
+    // stack: leftover_gas
+    PUSH 1 // success
     %jump(terminate_common)
 
 global sys_revert:
-    // TODO: Refund unused gas to parent.
-    // TODO: Revert state changes.
+    // stack: kexit_info, offset, size
+    %stack (kexit_info, offset, size) -> (offset, size, sys_revert_finish)
+    %jump(copy_returndata_to_parent)
+sys_revert_finish:
+    %revert_checkpoint
+    %leftover_gas
+    // stack: leftover_gas
+    PUSH 0 // success
     %jump(terminate_common)
 
 // The execution is in an exceptional halt-ing state if
@@ -32,29 +85,131 @@ global sys_revert:
 // - the new stack size would be larger than 1024, or
 // - state modification is attempted during a static call
 global fault_exception:
-    // TODO: Revert state changes.
+    // Whatever is left on our stack is discarded along with this context.
+    PUSH 0 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    %revert_checkpoint
+    PUSH 0 // leftover_gas; an exceptional halt consumes all gas sent to this context
+    PUSH 0 // success
     %jump(terminate_common)
 
-terminate_common:
-    // stack: success
-    // We want to move the success flag from our (child) context's stack to the
-    // parent context's stack. We will write it to memory, specifically
-    // SEGMENT_KERNEL_GENERAL[0], then load it after the context switch.
-    PUSH 0
-    // stack: 0, success
-    %mstore_kernel(@SEGMENT_KERNEL_GENERAL)
+// Copies the given range of our memory to the parent context's RETURNDATA
+// segment, and records its size in the parent's metadata.
+// Pre stack: offset, size, retdest
+// Post stack: (empty)
+copy_returndata_to_parent:
+    // stack: offset, size, retdest
+    DUP2 DUP2 %expand_memory
+    DUP2 %mstore_parent_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    GET_CONTEXT
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    %stack (parent_ctx, ctx, offset, size, retdest)
+        -> (parent_ctx, @SEGMENT_RETURNDATA, 0,
+            ctx, @SEGMENT_MAIN_MEMORY, offset,
+            size, retdest)
+    %jump(memcpy)
+
+global terminate_common:
+    // stack: success, leftover_gas
+    // We want to move the success flag and leftover gas from our (child)
+    // context's stack to the parent context's stack. We will write them to
+    // memory, specifically SEGMENT_KERNEL_GENERAL[0..2], then load them after
+    // the context switch. We also save our PARENT_PC, since it is part of our
+    // context's metadata and can't be read once we've switched.
+    %mstore_kernel_general(0)
+    // stack: leftover_gas
+    %mstore_kernel_general(1)
     // stack: (empty)
+    %mload_context_metadata(@CTX_METADATA_PARENT_PC)
+    %mstore_kernel_general(2)
 
     // Go back to the parent context.
     %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
     SET_CONTEXT
-    // stack: (empty)
+    // stack: (empty, or rather the parent's stack which we don't touch)
 
-    // Load the success flag that we stored in SEGMENT_KERNEL_GENERAL[0].
-    PUSH 0
-    %mload_kernel(@SEGMENT_KERNEL_GENERAL)
-    // stack: success
+    // Load the values that we stored in SEGMENT_KERNEL_GENERAL.
+    %mload_kernel_general(1)
+    %mload_kernel_general(0)
+    // stack: success, leftover_gas
 
     // JUMP to the parent IP.
-    %mload_context_metadata(@CTX_METADATA_PARENT_PC)
+    %mload_kernel_general(2)
     JUMP
+
+// Store the given value in a field of the parent context's metadata.
+%macro mstore_parent_context_metadata(field)
+    // stack: value
+    PUSH $field
+    PUSH @SEGMENT_CONTEXT_METADATA
+    %mload_context_metadata(@CTX_METADATA_PARENT_CONTEXT)
+    // stack: parent_ctx, segment, field, value
+    MSTORE_GENERAL
+    // stack: (empty)
+%endmacro
+
+// Saves the sizes of the kernel's append-only lists, and the refund counter, in
+// the metadata of the given new context, so that they can be restored if it
+// reverts. The state trie checkpoint is saved separately by the callers, since
+// it's taken before any ETH is sent to the new context.
+%macro set_new_ctx_checkpoints
+    // stack: new_ctx
+    %set_new_ctx_checkpoint(@GLOBAL_METADATA_LOGS_LEN, @CTX_METADATA_LOGS_CHECKPOINT)
+    %set_new_ctx_checkpoint(@GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN, @CTX_METADATA_ACCESSED_ADDRESSES_CHECKPOINT)
+    %set_new_ctx_checkpoint(@GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN, @CTX_METADATA_ACCESSED_STORAGE_KEYS_CHECKPOINT)
+    %set_new_ctx_checkpoint(@GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN, @CTX_METADATA_SELFDESTRUCT_LIST_CHECKPOINT)
+    %set_new_ctx_checkpoint(@GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN, @CTX_METADATA_TOUCHED_ADDRESSES_CHECKPOINT)
+    %set_new_ctx_checkpoint(@GLOBAL_METADATA_REFUND_COUNTER, @CTX_METADATA_REFUND_COUNTER_CHECKPOINT)
+    // stack: new_ctx
+%endmacro
+
+%macro set_new_ctx_checkpoint(global_field, ctx_field)
+    // stack: new_ctx
+    // Note: We can't use %mstore_context_metadata because we're writing to
+    // memory owned by the new context, not the current one.
+    %mload_global_metadata($global_field)
+    PUSH $ctx_field
+    PUSH @SEGMENT_CONTEXT_METADATA
+    DUP4
+    // stack: new_ctx, segment, ctx_field, value, new_ctx
+    MSTORE_GENERAL
+    // stack: new_ctx
+%endmacro
+
+// Undo any state changes made since the current context was created, by
+// restoring the state trie root saved in @CTX_METADATA_STATE_TRIE_CHECKPOINT_PTR,
+// and the other checkpoints saved by %set_new_ctx_checkpoints.
+%macro revert_checkpoint
+    GET_CONTEXT
+    %revert_checkpoint_of_ctx
+%endmacro
+
+// Like %revert_checkpoint, but for the given context, which need not be the
+// current one.
+%macro revert_checkpoint_of_ctx
+    // stack: ctx
+    %revert_ctx_checkpoint(@CTX_METADATA_STATE_TRIE_CHECKPOINT_PTR, @GLOBAL_METADATA_STATE_TRIE_ROOT)
+    %revert_ctx_checkpoint(@CTX_METADATA_LOGS_CHECKPOINT, @GLOBAL_METADATA_LOGS_LEN)
+    %revert_ctx_checkpoint(@CTX_METADATA_ACCESSED_ADDRESSES_CHECKPOINT, @GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN)
+    %revert_ctx_checkpoint(@CTX_METADATA_ACCESSED_STORAGE_KEYS_CHECKPOINT, @GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN)
+    %revert_ctx_checkpoint(@CTX_METADATA_SELFDESTRUCT_LIST_CHECKPOINT, @GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN)
+    %revert_ctx_checkpoint(@CTX_METADATA_TOUCHED_ADDRESSES_CHECKPOINT, @GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN)
+    %revert_ctx_checkpoint(@CTX_METADATA_REFUND_COUNTER_CHECKPOINT, @GLOBAL_METADATA_REFUND_COUNTER)
+    // stack: ctx
+    POP
+%endmacro
+
+%macro revert_ctx_checkpoint(ctx_field, global_field)
+    // stack: ctx
+    PUSH $ctx_field
+    PUSH @SEGMENT_CONTEXT_METADATA
+    DUP3
+    // stack: ctx, segment, ctx_field, ctx
+    MLOAD_GENERAL
+    %mstore_global_metadata($global_field)
+    // stack: ctx
+%endmacro
+
+// The gas which the current context can return to its parent.
+%macro leftover_gas
+    GAS
+%endmacro
@@ -0,0 +1,128 @@
+// The addresses touched by the current transaction, per EIP-161. They are kept
+// as an append-only list in @SEGMENT_TOUCHED_ADDRESSES, without duplicates, so
+// a reverted context forgets what it touched by restoring the length saved at
+// its creation. At the end of the transaction, the touched accounts which are
+// empty get deleted.
+
+// Adds the address to the touched addresses, if it isn't there already.
+// Pre stack: addr, retdest
+// Post stack: (empty)
+global insert_touched_addresses:
+    // stack: addr, retdest
+    %mload_global_metadata(@GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN)
+    // stack: len, addr, retdest
+    PUSH 0
+insert_touched_addresses_loop:
+    // stack: i, len, addr, retdest
+    DUP2 DUP2 EQ %jumpi(insert_touched_address)
+    // stack: i, len, addr, retdest
+    DUP1 %mload_kernel(@SEGMENT_TOUCHED_ADDRESSES)
+    // stack: loaded_addr, i, len, addr, retdest
+    DUP4 EQ %jumpi(insert_touched_addresses_found)
+    // stack: i, len, addr, retdest
+    %increment
+    %jump(insert_touched_addresses_loop)
+
+insert_touched_address:
+    // stack: i, len, addr, retdest
+    DUP3 SWAP1 %mstore_kernel(@SEGMENT_TOUCHED_ADDRESSES) // Store the new address at the end of the list.
+    // stack: len, addr, retdest
+    %increment
+    %mstore_global_metadata(@GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN)
+    // stack: addr, retdest
+    POP
+    JUMP
+
+insert_touched_addresses_found:
+    // stack: i, len, addr, retdest
+    %stack (i, len, addr, retdest) -> (retdest)
+    JUMP
+
+%macro insert_touched_addresses
+    %stack (addr) -> (addr, %%after)
+    %jump(insert_touched_addresses)
+%%after:
+    // stack: (empty)
+%endmacro
+
+// Deletes the touched accounts which are empty from the state trie.
+// Pre stack: retdest
+// Post stack: (empty)
+global delete_all_touched_addresses:
+    // stack: retdest
+    %mload_global_metadata(@GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN)
+    // stack: len, retdest
+    PUSH 0
+delete_all_touched_addresses_loop:
+    // stack: i, len, retdest
+    DUP2 DUP2 EQ %jumpi(delete_all_touched_addresses_done)
+    // stack: i, len, retdest
+    DUP1 %mload_kernel(@SEGMENT_TOUCHED_ADDRESSES)
+    // stack: addr, i, len, retdest
+    DUP1 %mpt_read_state_trie
+    // stack: account_ptr, addr, i, len, retdest
+    // An account which doesn't exist, e.g. since it self-destructed, has nothing to delete.
+    DUP1 ISZERO %jumpi(delete_all_touched_addresses_skip)
+    %is_empty
+    // stack: is_empty, addr, i, len, retdest
+    ISZERO %jumpi(delete_all_touched_addresses_next)
+    // stack: addr, i, len, retdest
+    %addr_to_state_key
+    // stack: key, i, len, retdest
+    %stack (key) -> (key, delete_all_touched_addresses_next_deleted)
+    %jump(mpt_delete_state_trie)
+delete_all_touched_addresses_skip:
+    // stack: account_ptr, addr, i, len, retdest
+    POP
+delete_all_touched_addresses_next:
+    // stack: addr, i, len, retdest
+    POP
+delete_all_touched_addresses_next_deleted:
+    // stack: i, len, retdest
+    %increment
+    %jump(delete_all_touched_addresses_loop)
+delete_all_touched_addresses_done:
+    // stack: i, len, retdest
+    %pop2
+    JUMP
+
+%macro delete_all_touched_addresses
+    PUSH %%after
+    %jump(delete_all_touched_addresses)
+%%after:
+    // stack: (empty)
+%endmacro
+
+// Returns 1 if the account at the given pointer is empty, per EIP-161, i.e. if
+// it has a zero nonce, a zero balance and no code, and 0 otherwise.
+%macro is_empty
+    // stack: account_ptr
+    DUP1 %mload_trie_data
+    // stack: nonce, account_ptr
+    DUP2 %increment %mload_trie_data
+    // stack: balance, nonce, account_ptr
+    OR ISZERO
+    // stack: no_nonce_or_balance, account_ptr
+    SWAP1 %add_const(3) %mload_trie_data
+    PUSH @EMPTY_STRING_HASH EQ
+    // stack: no_code, no_nonce_or_balance
+    AND
+    // stack: is_empty
+%endmacro
+
+// Returns 1 if the given account is dead, per EIP-161, i.e. if it doesn't exist
+// or is empty, and 0 otherwise.
+%macro is_dead
+    // stack: addr
+    %mpt_read_state_trie
+    // stack: account_ptr
+    DUP1 ISZERO %jumpi(%%nonexistent)
+    %is_empty
+    %jump(%%after)
+%%nonexistent:
+    // stack: account_ptr
+    POP
+    PUSH 1
+%%after:
+    // stack: is_dead
+%endmacro
@@ -25,11 +25,12 @@ global transfer_eth_failure:
 %endmacro
 
 // Pre stack: should_transfer, from, to, amount
-// Post stack: (empty)
+// Post stack: status (0 indicates success)
 %macro maybe_transfer_eth
     %jumpi(%%transfer)
-    // We're skipping the transfer, so just pop the arguments and return.
+    // We're skipping the transfer, so just pop the arguments and return success.
     %pop3
+    PUSH 0
     %jump(%%after)
 %%transfer:
     %transfer_eth
@@ -37,29 +38,36 @@ global transfer_eth_failure:
 %endmacro
 
 // Returns 0 on success, or 1 if addr has insufficient balance. Panics if addr isn't found in the trie.
+// The account is updated by copy, so that earlier state trie roots remain valid checkpoints.
 // Pre stack: addr, amount, retdest
 // Post stack: status (0 indicates success)
 global deduct_eth:
     // stack: addr, amount, retdest
-    %mpt_read_state_trie
-    // stack: account_ptr, amount, retdest
-    DUP1 ISZERO %jumpi(panic) // If the account pointer is null, return 0.
-    %add_const(1)
-    // stack: balance_ptr, amount, retdest
-    DUP1 %mload_trie_data
-    // stack: balance, balance_ptr, amount, retdest
-    DUP1 DUP4 GT
-    // stack: amount > balance, balance, balance_ptr, amount, retdest
+    DUP1 %mpt_read_state_trie
+    // stack: account_ptr, addr, amount, retdest
+    DUP1 ISZERO %jumpi(panic) // If the account pointer is null, panic.
+    DUP1 %add_const(1) %mload_trie_data
+    // stack: balance, account_ptr, addr, amount, retdest
+    DUP1 DUP5 GT
+    // stack: amount > balance, balance, account_ptr, addr, amount, retdest
     %jumpi(deduct_eth_insufficient_balance)
-    %stack (balance, balance_ptr, amount, retdest) -> (balance, amount, balance_ptr, retdest, 0)
-    SUB
-    SWAP1
-    // stack: balance_ptr, balance - amount, retdest, 0
-    %mstore_trie_data
-    // stack: retdest, 0
-    JUMP
+    %stack (balance, account_ptr, addr, amount) -> (amount, balance, account_ptr, addr)
+    SWAP1 SUB
+    // stack: new_balance, account_ptr, addr, retdest
+    SWAP1 %make_account_copy
+    // stack: new_account_ptr, new_balance, addr, retdest
+    %stack (new_account_ptr, new_balance) -> (new_account_ptr, new_balance, new_account_ptr)
+    %add_const(1) %mstore_trie_data
+    // stack: new_account_ptr, addr, retdest
+    SWAP1 %addr_to_state_key
+    // stack: key, new_account_ptr, retdest
+    %stack (key, new_account_ptr, retdest) -> (key, new_account_ptr, deduct_eth_success, retdest)
+    %jump(mpt_insert_state_trie)
+deduct_eth_success:
+    // stack: retdest
+    PUSH 0 SWAP1 JUMP
 global deduct_eth_insufficient_balance:
-    %stack (balance, balance_ptr, amount, retdest) -> (retdest, 1)
+    %stack (balance, account_ptr, addr, amount, retdest) -> (retdest, 1)
     JUMP
 
 // Convenience macro to call deduct_eth and return where we left off.
@@ -69,6 +77,7 @@ global deduct_eth_insufficient_balance:
 %%after:
 %endmacro
 
+// The account is updated by copy, so that earlier state trie roots remain valid checkpoints.
 // Pre stack: addr, amount, redest
 // Post stack: (empty)
 global add_eth:
@@ -76,16 +85,20 @@ global add_eth:
     DUP1 %mpt_read_state_trie
     // stack: account_ptr, addr, amount, retdest
     DUP1 ISZERO %jumpi(add_eth_new_account) // If the account pointer is null, we need to create the account.
-    %add_const(1)
-    // stack: balance_ptr, addr, amount, retdest
+    %make_account_copy
+    // stack: new_account_ptr, addr, amount, retdest
+    DUP1 %add_const(1)
+    // stack: balance_ptr, new_account_ptr, addr, amount, retdest
     DUP1 %mload_trie_data
-    // stack: balance, balance_ptr, addr, amount, retdest
-    %stack (balance, balance_ptr, addr, amount) -> (amount, balance, addr, balance_ptr)
+    // stack: balance, balance_ptr, new_account_ptr, addr, amount, retdest
+    %stack (balance, balance_ptr, new_account_ptr, addr, amount) -> (amount, balance, balance_ptr, addr, new_account_ptr)
     ADD
-    // stack: new_balance, addr, balance_ptr, retdest
+    // stack: new_balance, balance_ptr, addr, new_account_ptr, retdest
     SWAP1 %mstore_trie_data
-    // stack: addr, retdest
-    POP JUMP
+    // stack: addr, new_account_ptr, retdest
+    %addr_to_state_key
+    // stack: key, new_account_ptr, retdest
+    %jump(mpt_insert_state_trie)
 global add_eth_new_account:
     // TODO: Skip creation if amount == 0?
     // stack: null_account_ptr, addr, amount, retdest
@@ -4,7 +4,7 @@
     %next_context_id
     GET_CONTEXT
     %stack (ctx, next_ctx)
-       -> (next_ctx, @SEGMENT_NORMALIZED_TXN, @CTX_METADATA_PARENT_CONTEXT,
+       -> (next_ctx, @SEGMENT_CONTEXT_METADATA, @CTX_METADATA_PARENT_CONTEXT,
            ctx, next_ctx)
     MSTORE_GENERAL
     // stack: next_ctx
@@ -21,6 +21,15 @@
     // stack: new_ctx
 %endmacro
 
+// Per EIP-150, a context can pass at most all but one 64th of its remaining gas
+// to a sub context.
+%macro all_but_one_64th_gas
+    // stack: (empty)
+    GAS
+    DUP1 %shr_const(6) SWAP1 SUB
+    // stack: all_but_one_64th
+%endmacro
+
 // Returns whether the current transaction is a contract creation transaction.
 %macro is_contract_creation
     // stack: (empty)
@@ -30,3 +39,22 @@
     // If there is no "to" field, then this is a contract creation.
     // stack: to == 0
 %endmacro
+
+// Adds the given amount to the refund counter of the current transaction.
+%macro add_refund
+    // stack: amount
+    %mload_global_metadata(@GLOBAL_METADATA_REFUND_COUNTER)
+    ADD
+    %mstore_global_metadata(@GLOBAL_METADATA_REFUND_COUNTER)
+    // stack: (empty)
+%endmacro
+
+// Subtracts the given amount from the refund counter of the current transaction.
+%macro sub_refund
+    // stack: amount
+    %mload_global_metadata(@GLOBAL_METADATA_REFUND_COUNTER)
+    // stack: refund_counter, amount
+    SUB
+    %mstore_global_metadata(@GLOBAL_METADATA_REFUND_COUNTER)
+    // stack: (empty)
+%endmacro
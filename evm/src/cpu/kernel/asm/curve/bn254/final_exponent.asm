@@ -0,0 +1,83 @@
+// The final exponentiation F -> F^((p^12 - 1) / r) of the BN254 pairing.
+//
+// The exponent splits into an easy part (p^6 - 1)(p^2 + 1), computed with an
+// inverse and Frobenius maps, and a hard part (p^4 - p^2 + 1) / r. For the
+// latter we use the addition chain of Fuentes-Castaneda, Knapp and
+// Rodriguez-Henriquez, "Faster hashing to G2", which raises to a multiple of the
+// hard part coprime to r, using three exponentiations by the BN parameter x.
+// After the easy part, F lies in the cyclotomic subgroup, where the inverse is
+// the conjugate.
+//
+// Kernel memory layout, besides the scratch space of the Fp12 routines:
+//     300-311: r
+//     312-503: y0, ..., y15, 12 words each
+
+%macro mul_fp12(a, b, out)
+    %stack () -> ($a, $b, $out, %%after)
+    %jump(mul_Fp12)
+%%after:
+%endmacro
+
+// Calls a routine, such as a Frobenius map, which maps the Fp12 element at in
+// to one at out.
+%macro call_fp12(f, in, out)
+    %stack () -> ($in, $out, %%after)
+    %jump($f)
+%%after:
+%endmacro
+
+// Stores F^(-x) at out, where F is the Fp12 element at in, as the conjugate
+// of F^x.
+%macro exp_by_neg_x(in, out)
+    %stack () -> ($in, 0x44e992b44a6909f1, $out, %%after)
+    %jump(pow_fp12)
+%%after:
+    PUSH $out  %conj_fp12
+%endmacro
+
+// Replaces the Fp12 element at ptr, which must lie outside the scratch space,
+// by its final exponentiation.
+global bn254_final_exponent:
+    // stack: ptr, retdest
+    // Easy part: r = F^((p^6 - 1)(p^2 + 1)).
+    %stack (ptr) -> (ptr, 312, bn254_final_exponent_easy, ptr)
+    %jump(inv_fp12)
+bn254_final_exponent_easy:
+    // stack: ptr, retdest
+    DUP1  %load_fp6
+    %store_fp6(300)
+    DUP1  %add_const(6)  %load_fp6
+    %store_fp6(306)
+    PUSH 300  %conj_fp12
+    %mul_fp12(300, 312, 300)
+    %call_fp12(frob_fp12_2, 300, 312)
+    %mul_fp12(300, 312, 300)
+
+    // Hard part.
+    %exp_by_neg_x(300, 312)
+    %mul_fp12(312, 312, 324)
+    %mul_fp12(324, 324, 336)
+    %mul_fp12(336, 324, 348)
+    %exp_by_neg_x(348, 360)
+    %mul_fp12(360, 360, 372)
+    // y6 = conj(y5^-x) = y5^x.
+    %stack () -> (372, 0x44e992b44a6909f1, 384, bn254_final_exponent_y6)
+    %jump(pow_fp12)
+bn254_final_exponent_y6:
+    PUSH 348  %conj_fp12
+    %mul_fp12(384, 360, 396)
+    %mul_fp12(396, 348, 408)
+    %mul_fp12(408, 324, 420)
+    %mul_fp12(408, 360, 432)
+    %mul_fp12(432, 300, 444)
+    %call_fp12(frob_fp12_1, 420, 456)
+    %mul_fp12(456, 444, 468)
+    %call_fp12(frob_fp12_2, 408, 408)
+    %mul_fp12(408, 468, 480)
+    PUSH 300  %conj_fp12
+    %mul_fp12(300, 420, 492)
+    %call_fp12(frob_fp12_3, 492, 492)
+    // stack: ptr, retdest
+    %stack (ptr) -> (492, 480, ptr)
+    // stack: 492, 480, ptr, retdest
+    %jump(mul_Fp12)
@@ -0,0 +1,163 @@
+// The Miller loop of the optimal ate pairing on BN254,
+//     f = f_{6x+2,Q}(P) * l_{[6x+2]Q,pi(Q)}(P) * l_{[6x+2]Q+pi(Q),-pi^2(Q)}(P),
+// where x = 0x44e992b44a6909f1 is the BN parameter, pi is the Frobenius map and
+// l_{T,S} is the line through T and S. The point P is in G1, and Q in G2 is
+// given as a point of the twisted curve. Vertical lines are omitted, since
+// their values lie in a proper subfield of Fp12 and are sent to one by the
+// final exponentiation.
+//
+// Under the twist map (x, y) -> (x z^2, y z^3), the line of slope lambda
+// through T = (xt, yt) on the twisted curve, evaluated at P = (xp, yp), is
+//     yp - (lambda xp) z + (lambda xt - yt) z^3.
+// Since z^2 = t, this is the sparse Fp12 element with f = yp and
+// f' = -lambda xp + (lambda xt - yt) t.
+//
+// Kernel memory layout:
+//     200-201: P
+//     202-205: Q
+//     206-209: pi(Q)
+//     210-213: -pi^2(Q)
+//     214-225: the current line
+//     226-237: the accumulator f
+
+// Multiplies the accumulator by the current line.
+%macro miller_mul_line
+    PUSH %%after
+    PUSH 226
+    PUSH 214
+    PUSH 226
+    // stack: 226, 214, 226, %%after
+    %jump(mul_Fp12)
+%%after:
+%endmacro
+
+// Squares the accumulator.
+%macro miller_square
+    PUSH %%after
+    PUSH 226
+    PUSH 226
+    PUSH 226
+    // stack: 226, 226, 226, %%after
+    %jump(mul_Fp12)
+%%after:
+%endmacro
+
+// Stores the line of slope lambda through T, evaluated at P. Only the two
+// coefficients of f' which depend on the line are written; the others are set
+// by bn254_miller.
+%macro miller_store_line
+    // stack:                     lambda: 2, T: 4
+    DUP6  DUP6
+    DUP6  DUP6
+    DUP6  DUP6
+    // stack: lambda: 2, xt: 2, yt: 2, lambda: 2, T: 4
+    %mul_fp2
+    %sub_fp2
+    PUSH 222  %store_fp2
+    // stack:                     lambda: 2, T: 4
+    DUP2  DUP2
+    PUSH 200  %mload_kernel_general
+    %scale_fp2
+    %neg_fp2
+    PUSH 220  %store_fp2
+    // stack:                     lambda: 2, T: 4
+%endmacro
+
+// Multiplies the accumulator by the line through T and the point of the
+// twisted curve at ptr, and replaces T by their sum.
+%macro miller_add
+    // stack:               ptr, T: 4
+    DUP1  %load_twisted
+    // stack:        S: 4, ptr, T: 4
+    %stack (s: 4, ptr, t: 4) -> (s, t, ptr)
+    %twisted_chord_slope
+    // stack: xs: 2, lambda: 2, T: 4, ptr
+    POP  POP
+    %miller_store_line
+    DUP7  %load_fp2
+    // stack: xs: 2, lambda: 2, T: 4, ptr
+    %twisted_add_with_slope
+    %miller_mul_line
+    // stack:               T + S: 4, ptr
+    %stack (t: 4, ptr) -> (t)
+%endmacro
+
+// Stores f_{P,Q} at out, the output of the Miller loop on the points P of G1
+// and Q of G2, neither of which may be the identity.
+global bn254_miller:
+    // stack: P: 2, Q: 4, out, retdest
+    DUP2  PUSH 214  %mstore_kernel_general
+    PUSH 200  %mstore_kernel_general
+    PUSH 201  %mstore_kernel_general
+    // stack: Q: 4, out, retdest
+    DUP4  DUP4  DUP4  DUP4
+    PUSH 202  %store_twisted
+    // pi(Q) = (conj(x) gamma_x, conj(y) gamma_y), where
+    //     gamma_x = (9 + i)^((p - 1) / 3) and gamma_y = (9 + i)^((p - 1) / 2).
+    DUP2  DUP2
+    %conj_fp2
+    PUSH 0x16c9e55061ebae204ba4cc8bd75a079432ae2a1d0b7c9dce1665d51c640fcba2
+    PUSH 0x2fb347984f7911f74c0bec3cf559b143b78cc310c2c3330c99e39557176f553d
+    %mul_fp2
+    PUSH 206  %store_fp2
+    DUP4  DUP4
+    %conj_fp2
+    PUSH 0x7c03cbcac41049a0704b5a7ec796f2b21807dc98fa25bd282d37f632623b0e3
+    PUSH 0x63cf305489af5dcdc5ec698b6e2f9b9dbaae0eda9c95998dc54014671a0135a
+    %mul_fp2
+    PUSH 208  %store_fp2
+    // -pi^2(Q) = (x (9 + i)^((p^2 - 1) / 3), y), as (9 + i)^((p^2 - 1) / 2) = -1.
+    DUP2  DUP2
+    PUSH 0x30644e72e131a0295e6dd9e7e0acccb0c28f069fbb966e3de4bd44e5607cfd48
+    %scale_fp2
+    PUSH 210  %store_fp2
+    DUP4  DUP4
+    PUSH 212  %store_fp2
+    // Zero out the coefficients of the line which are always zero.
+    PUSH 0  PUSH 215  %mstore_kernel_general
+    PUSH 0  PUSH 216  %mstore_kernel_general
+    PUSH 0  PUSH 217  %mstore_kernel_general
+    PUSH 0  PUSH 218  %mstore_kernel_general
+    PUSH 0  PUSH 219  %mstore_kernel_general
+    PUSH 0  PUSH 224  %mstore_kernel_general
+    PUSH 0  PUSH 225  %mstore_kernel_general
+    PUSH 226  %store_one_fp12
+    // stack: T = Q: 4, out, retdest
+    // Start with T = Q, which accounts for bit 64, the leading bit of 6x + 2.
+    PUSH 63
+    %stack (i, t: 4) -> (t, i)
+miller_loop:
+    // stack: T: 4, i, out, retdest
+    %miller_square
+    %twisted_tangent_slope
+    %miller_store_line
+    DUP4  DUP4
+    %twisted_add_with_slope
+    %miller_mul_line
+    // 6x + 2
+    PUSH 0x19d797039be763ba8
+    DUP6  SHR
+    %and_const(1)
+    ISZERO
+    %jumpi(miller_loop_next)
+    PUSH 202  %miller_add
+miller_loop_next:
+    // stack: T: 4, i, out, retdest
+    DUP5  ISZERO
+    %jumpi(miller_loop_end)
+    SWAP4  %decrement  SWAP4
+    %jump(miller_loop)
+miller_loop_end:
+    // stack: T: 4, 0, out, retdest
+    PUSH 206  %miller_add
+    PUSH 210  %miller_add
+    // stack: T: 4, 0, out, retdest
+    %pop5
+    // stack: out, retdest
+    %load_fp6(226)
+    DUP7  %store_fp6
+    %load_fp6(232)
+    DUP7  %add_const(6)  %store_fp6
+    // stack: out, retdest
+    POP
+    JUMP
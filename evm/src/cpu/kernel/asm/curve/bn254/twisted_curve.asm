@@ -0,0 +1,194 @@
+// Arithmetic on the sextic twist of BN254 over Fp2,
+//     y^2 = x^3 + 3 / (9 + i),
+// which contains the G2 inputs of the pairing. A point (x, y) is kept on the
+// stack as four words (x, x_, y, y_), and in kernel memory as four
+// consecutive words in the same order. We use affine coordinates, so callers
+// must rule out the identity and any additions of points with equal
+// x-coordinates.
+
+%macro load_twisted
+    // stack:         ptr
+    DUP1  %add_const(2)  %load_fp2
+    // stack:   y: 2, ptr
+    %stack (y: 2, ptr) -> (ptr, y)
+    %load_fp2
+    // stack: x: 2, y: 2
+%endmacro
+
+%macro store_twisted
+    // stack: ptr, x: 2, y: 2
+    DUP1  %add_const(2)
+    %stack (ptr2, ptr, x: 2, y: 2) -> (ptr, x, ptr2, y)
+    %store_fp2
+    %store_fp2
+%endmacro
+
+// Puts y^2 == x^3 + 3 / (9 + i) on top of the stack, in place of (x, y).
+%macro twisted_check
+    // stack:        x: 2, y: 2
+    DUP2  DUP2
+    DUP2  DUP2
+    %mul_fp2
+    %mul_fp2
+    // stack:      x^3: 2, y: 2
+    PUSH 0x9713b03af0fed4cd2cafadeed8fdf4a74fa084e52d1852e4a2bd0685c315d2
+    PUSH 0x2b149d40ceb8aaae81be18991be06ac3b5b4c5e559dbefa33267e6dc24a138e5
+    %add_fp2
+    // stack:  x^3 + b: 2, y: 2
+    %stack (a: 2, y: 2) -> (y, y, a)
+    %mul_fp2
+    // stack: y^2: 2, x^3 + b: 2
+    %stack (u, u_, v, v_) -> (u, v, u_, v_)
+    EQ
+    SWAP2
+    EQ
+    AND
+    // stack: y^2 == x^3 + b
+%endmacro
+
+// The slope 3x^2 / 2y of the tangent line at T = (x, y).
+%macro twisted_tangent_slope
+    // stack:                     T: 4
+    DUP4  DUP4
+    // stack:               y: 2, T: 4
+    PUSH 2  %scale_fp2
+    %inv_fp2
+    // stack:          1/2y: 2, T: 4
+    DUP4  DUP4
+    DUP2  DUP2
+    %mul_fp2
+    PUSH 3  %scale_fp2
+    // stack: 3x^2: 2, 1/2y: 2, T: 4
+    %mul_fp2
+    // stack:        lambda: 2, T: 4
+%endmacro
+
+// The slope (y - yq) / (x - xq) of the line through T = (x, y) and
+// Q = (xq, yq), which must have distinct x-coordinates.
+%macro twisted_chord_slope
+    // stack:                                   xq: 2, yq: 2, T: 4
+    DUP4  DUP4
+    DUP10  DUP10
+    // stack:                y: 2, yq: 2, xq: 2, yq: 2, T: 4
+    %sub_fp2
+    // stack:                      y - yq: 2, xq: 2, yq: 2, T: 4
+    DUP4  DUP4
+    DUP10  DUP10
+    // stack:     x: 2, xq: 2, y - yq: 2, xq: 2, yq: 2, T: 4
+    %sub_fp2
+    %inv_fp2
+    %mul_fp2
+    // stack:                      lambda: 2, xq: 2, yq: 2, T: 4
+    %stack (lambda: 2, xq: 2, yq: 2) -> (xq, lambda)
+    // stack:                                xq: 2, lambda: 2, T: 4
+%endmacro
+
+// Given the slope lambda of the line through T = (x, y) and another point of
+// the twisted curve with x-coordinate xo, returns their sum (x3, y3):
+//     x3 = lambda^2 - x - xo
+//     y3 = lambda (x - x3) - y
+// For a tangent line, xo = x and the sum is 2T.
+%macro twisted_add_with_slope
+    // stack:                        xo: 2, lambda: 2, T: 4
+    DUP6  DUP6
+    %add_fp2
+    // stack:                    x + xo: 2, lambda: 2, T: 4
+    DUP4  DUP4
+    DUP2  DUP2
+    %mul_fp2
+    // stack:      lambda^2: 2, x + xo: 2, lambda: 2, T: 4
+    %sub_fp2
+    // stack:                        x3: 2, lambda: 2, T: 4
+    DUP2  DUP2
+    DUP8  DUP8
+    // stack:       x: 2, x3: 2, x3: 2, lambda: 2, T: 4
+    %sub_fp2
+    DUP6  DUP6
+    %mul_fp2
+    // stack:        lambda (x - x3): 2, x3: 2, lambda: 2, T: 4
+    DUP10  DUP10
+    %sub_fp2
+    %neg_fp2
+    // stack:                 y3: 2, x3: 2, lambda: 2, T: 4
+    %stack (y3: 2, x3: 2, lambda: 2, t: 4) -> (x3, y3)
+    // stack:                                  x3: 2, y3: 2
+%endmacro
+
+%macro twisted_double
+    // stack:                T: 4
+    %twisted_tangent_slope
+    // stack:     lambda: 2, T: 4
+    DUP4  DUP4
+    // stack: x: 2, lambda: 2, T: 4
+    %twisted_add_with_slope
+    // stack:               2T: 4
+%endmacro
+
+// Returns 1 if the point Q of the twisted curve, which must not be the
+// identity, lies in the subgroup of order r, the order of G2, and 0 otherwise.
+// We compute (r - 1) Q by double-and-add and compare it with -Q. For Q of
+// order r, every addition is of Q to some kQ with 1 < k < r - 1, so these two
+// points have distinct x-coordinates. Meeting equal x-coordinates thus shows
+// that Q is not in the subgroup. The twisted curve has no point of order 2, so
+// doublings are always well defined.
+// Uses kernel memory 202-205 to hold Q.
+global bn254_twisted_in_subgroup:
+    // stack:          Q: 4, retdest
+    DUP4  DUP4  DUP4  DUP4
+    PUSH 202  %store_twisted
+    // stack:          Q: 4, retdest
+    // Start with T = Q, which accounts for bit 253, the leading bit of r - 1.
+    PUSH 252
+    %stack (i, t: 4) -> (t, i)
+twisted_subgroup_loop:
+    // stack:       T: 4, i, retdest
+    %twisted_double
+    // r - 1
+    PUSH 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000
+    DUP6  SHR
+    %and_const(1)
+    ISZERO
+    %jumpi(twisted_subgroup_next)
+    // stack:       T: 4, i, retdest
+    PUSH 202  %load_fp2
+    // stack: xq: 2, T: 4, i, retdest
+    DUP3  EQ
+    SWAP1  DUP4  EQ
+    AND
+    %jumpi(twisted_subgroup_fail)
+    PUSH 202  %load_twisted
+    // stack: Q: 4, T: 4, i, retdest
+    %twisted_chord_slope
+    %twisted_add_with_slope
+twisted_subgroup_next:
+    // stack:       T: 4, i, retdest
+    DUP5  ISZERO
+    %jumpi(twisted_subgroup_end)
+    SWAP4  %decrement  SWAP4
+    %jump(twisted_subgroup_loop)
+twisted_subgroup_end:
+    // stack:       T: 4, 0, retdest
+    PUSH 202  %load_twisted
+    // stack: Q: 4, T: 4, 0, retdest
+    %stack (xq, xq_, yq, yq_, x, x_, y, y_) -> (yq, y, yq_, y_, xq, x, xq_, x_)
+    ADDFP254
+    SWAP2
+    ADDFP254
+    OR
+    ISZERO
+    // stack: y == -yq, xq, x, xq_, x_, 0, retdest
+    SWAP2
+    EQ
+    AND
+    SWAP2
+    EQ
+    AND
+    // stack: (r - 1) Q == -Q, 0, retdest
+    %stack (in_subgroup, i, retdest) -> (retdest, in_subgroup)
+    JUMP
+twisted_subgroup_fail:
+    // stack:       T: 4, i, retdest
+    %pop5
+    PUSH 0
+    SWAP1
+    JUMP
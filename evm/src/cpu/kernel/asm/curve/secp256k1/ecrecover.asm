@@ -130,19 +130,17 @@ ecrecover_with_first_point:
 // Take a public key (PKx, PKy) and return the associated address KECCAK256(PKx || PKy)[-20:].
 pubkey_to_addr:
     // stack: PKx, PKy, retdest
-    PUSH 0
-    // stack: 0, PKx, PKy, retdest
-    MSTORE // TODO: switch to kernel memory (like `%mstore_kernel(@SEGMENT_KERNEL_GENERAL)`).
-    // stack: PKy, retdest
-    PUSH 0x20
-    // stack: 0x20, PKy, retdest
-    MSTORE
-    // stack: retdest
-    PUSH 0x40
-    // stack: 0x40, retdest
-    PUSH 0
-    // stack: 0, 0x40, retdest
-    KECCAK256
+    // Write PKx || PKy to @SEGMENT_KERNEL_GENERAL[0..64], so that we can hash it.
+    %stack (PKx) -> (0, @SEGMENT_KERNEL_GENERAL, 0, PKx, 32, pubkey_to_addr_after_PKx)
+    %jump(mstore_unpacking)
+pubkey_to_addr_after_PKx:
+    // stack: offset = 32, PKy, retdest
+    %stack (offset, PKy) -> (0, @SEGMENT_KERNEL_GENERAL, offset, PKy, 32, pubkey_to_addr_after_PKy)
+    %jump(mstore_unpacking)
+pubkey_to_addr_after_PKy:
+    // stack: offset = 64, retdest
+    %stack (offset) -> (0, @SEGMENT_KERNEL_GENERAL, 0, offset)
+    KECCAK_GENERAL
     // stack: hash, retdest
     PUSH 0xffffffffffffffffffffffffffffffffffffffff
     // stack: 2^160-1, hash, retdest
@@ -73,4 +73,25 @@ recursion_return:
     jump
 
 global sys_exp:
-    PANIC // TODO: Implement.
+    // stack: kexit_info, x, e
+    // The cost is @GAS_EXP, plus @GAS_EXPBYTE per byte of the exponent.
+    PUSH 0 DUP4
+    // stack: e, byte_len = 0, kexit_info, x, e
+sys_exp_byte_len_loop:
+    DUP1 ISZERO %jumpi(sys_exp_charge)
+    %shr_const(8)
+    SWAP1 %increment SWAP1
+    %jump(sys_exp_byte_len_loop)
+sys_exp_charge:
+    // stack: 0, byte_len, kexit_info, x, e
+    POP
+    %mul_const(@GAS_EXPBYTE)
+    %add_const(@GAS_EXP)
+    %charge_gas
+    // stack: kexit_info, x, e
+    %stack (kexit_info, x, e) -> (x, e, sys_exp_finish, kexit_info)
+    %jump(exp)
+sys_exp_finish:
+    // stack: x^e, kexit_info
+    SWAP1
+    EXIT_KERNEL
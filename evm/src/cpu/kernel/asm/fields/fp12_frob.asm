@@ -0,0 +1,83 @@
+// The Frobenius maps x -> x^(p^n) on Fp12, for n = 1, 2, 3.
+//
+// Writing F as the sum of c_e w^e for e = 0..5, where each c_e is in Fp2 and
+// w = z is the generator of Fp12 over Fp2 with w^6 = 9 + i, we have
+//     F^(p^n) = sum of frob_n(c_e) * gamma_{n,e} * w^e,
+// where frob_n is the Frobenius map on Fp2 (conjugation when n is odd), and
+//     gamma_{n,e} = (9 + i)^(e (p^n - 1) / 6).
+// The coefficients c_0, c_2, c_4, c_1, c_3, c_5 are stored at offsets 0, 2, 4,
+// 6, 8 and 10 respectively, which is the order of the constants below.
+
+// Stores the conjugate of the coefficient at in + k at out + k.
+%macro frob_fp2_conj(k)
+    // stack:       in, out
+    DUP1  %add_const($k)  %load_fp2
+    // stack: x, x_, in, out
+    %conj_fp2
+    // stack: y, y_, in, out
+    DUP4  %add_const($k)  %store_fp2
+    // stack:       in, out
+%endmacro
+
+// Stores the conjugate of the coefficient at in + k, multiplied by c + c_ i,
+// at out + k.
+%macro frob_fp2_conj(k, c, c_)
+    // stack:       in, out
+    DUP1  %add_const($k)  %load_fp2
+    // stack: x, x_, in, out
+    %conj_fp2
+    PUSH $c_  PUSH $c
+    %mul_fp2
+    // stack: y, y_, in, out
+    DUP4  %add_const($k)  %store_fp2
+    // stack:       in, out
+%endmacro
+
+// Stores the coefficient at in + k, multiplied by c in Fp, at out + k.
+%macro frob_fp2_scale(k, c)
+    // stack:       in, out
+    DUP1  %add_const($k)  %load_fp2
+    // stack: x, x_, in, out
+    PUSH $c
+    %scale_fp2
+    // stack: y, y_, in, out
+    DUP4  %add_const($k)  %store_fp2
+    // stack:       in, out
+%endmacro
+
+global frob_fp12_1:
+    // stack: in, out, retdest
+    %frob_fp2_conj(0)
+    %frob_fp2_conj(2,  0x2fb347984f7911f74c0bec3cf559b143b78cc310c2c3330c99e39557176f553d, 0x16c9e55061ebae204ba4cc8bd75a079432ae2a1d0b7c9dce1665d51c640fcba2)
+    %frob_fp2_conj(4,  0x5b54f5e64eea80180f3c0b75a181e84d33365f7be94ec72848a1f55921ea762, 0x2c145edbe7fd8aee9f3a80b03b0b1c923685d2ea1bdec763c13b4711cd2b8126)
+    %frob_fp2_conj(6,  0x1284b71c2865a7dfe8b99fdd76e68b605c521e08292f2176d60b35dadcc9e470, 0x246996f3b4fae7e6a6327cfe12150b8e747992778eeec7e5ca5cf05f80f362ac)
+    %frob_fp2_conj(8,  0x63cf305489af5dcdc5ec698b6e2f9b9dbaae0eda9c95998dc54014671a0135a, 0x7c03cbcac41049a0704b5a7ec796f2b21807dc98fa25bd282d37f632623b0e3)
+    %frob_fp2_conj(10, 0x183c1e74f798649e93a3661a4353ff4425c459b55aa1bd32ea2c810eab7692f, 0x12acf2ca76fd0675a27fb246c7729f7db080cb99678e2ac024c6b8ee6e0c2c4b)
+    // stack: in, out, retdest
+    %pop2
+    JUMP
+
+// For n = 2, the constants lie in Fp, and conjugating twice is the identity.
+global frob_fp12_2:
+    // stack: in, out, retdest
+    %frob_fp2_scale(0,  1)
+    %frob_fp2_scale(2,  0x30644e72e131a0295e6dd9e7e0acccb0c28f069fbb966e3de4bd44e5607cfd48)
+    %frob_fp2_scale(4,  0x59e26bcea0d48bacd4f263f1acdb5c4f5763473177fffffe)
+    %frob_fp2_scale(6,  0x30644e72e131a0295e6dd9e7e0acccb0c28f069fbb966e3de4bd44e5607cfd49)
+    %frob_fp2_scale(8,  0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd46)
+    %frob_fp2_scale(10, 0x59e26bcea0d48bacd4f263f1acdb5c4f5763473177ffffff)
+    // stack: in, out, retdest
+    %pop2
+    JUMP
+
+global frob_fp12_3:
+    // stack: in, out, retdest
+    %frob_fp2_conj(0)
+    %frob_fp2_conj(2,  0x856e078b755ef0abaff1c77959f25ac805ffd3d5d6942d37b746ee87bdcfb6d, 0x4f1de41b3d1766fa9f30e6dec26094f0fdf31bf98ff2631380cab2baaa586de)
+    %frob_fp2_conj(4,  0xbc58c6611c08dab19bee0f7b5b2444ee633094575b06bcb0e1a92bc3ccbf066, 0x23d5e999e1910a12feb0f6ef0cd21d04a44a9e08737f96e55fe3ed9d730c239f)
+    %frob_fp2_conj(6,  0x19dc81cfcc82e4bbefe9608cd0acaa90894cb38dbe55d24ae86f7d391ed4a67f, 0xabf8b60be77d7306cbeee33576139d7f03a5e397d439ec7694aa2bf4c0c101)
+    %frob_fp2_conj(8,  0x2a275b6d9896aa4cdbf17f1dca9e5ea3bbd689a3bea870f45fcc8ad066dce9ed, 0x28a411b634f09b8fb14b900e9507e9327600ecc7d8cf6ebab94d0cb3b2594c64)
+    %frob_fp2_conj(10, 0x13c49044952c0905711699fa3b4d3f692ed68098967c84a5ebde847076261b43, 0x16db366a59b1dd0b9fb1b2282a48633d3e2ddaea200280211f25041384282499)
+    // stack: in, out, retdest
+    %pop2
+    JUMP
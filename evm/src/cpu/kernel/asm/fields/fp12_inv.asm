@@ -0,0 +1,27 @@
+// Non-deterministically provides the inverse of the nonzero Fp12 element at
+// in, stores it at out, and checks that their product is one. The pointers in
+// and out must not overlap. Uses kernel memory 118-129 for the product.
+global inv_fp12:
+    // stack:                      in, out, retdest
+    PUSH 0
+inv_fp12_loop:
+    // stack:                   i, in, out, retdest
+    PROVER_INPUT(ffe::bn254_base::inverse_fp12)
+    // stack:                y, i, in, out, retdest
+    DUP2  DUP5  ADD
+    // stack:       out + i, y, i, in, out, retdest
+    %mstore_kernel_general
+    // stack:                   i, in, out, retdest
+    %increment
+    DUP1  %lt_const(12)
+    %jumpi(inv_fp12_loop)
+    // stack:                  12, in, out, retdest
+    POP
+    %stack (in, out) -> (in, out, 118, inv_fp12_check)
+    // stack: in, out, 118, inv_fp12_check, retdest
+    %jump(mul_Fp12)
+inv_fp12_check:
+    // stack:                              retdest
+    PUSH 118  %is_one_fp12
+    %assert_nonzero
+    JUMP
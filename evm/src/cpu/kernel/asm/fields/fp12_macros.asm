@@ -0,0 +1,56 @@
+// Fp12 elements are stored as 12 consecutive words of kernel memory, as
+// described in fp12_mul.asm: the coefficients f and f' of F = f + f'z.
+
+%macro store_one_fp12
+    // stack:           ptr
+    PUSH 1  DUP2
+    // stack:   ptr, 1, ptr
+    %mstore_kernel_general
+    %rep 11
+        // stack:       ind
+        %increment
+        PUSH 0  DUP2
+        // stack: ind', 0, ind'
+        %mstore_kernel_general
+    %endrep
+    // stack:         ind11
+    POP
+%endmacro
+
+// Pushes 1 if the Fp12 element at the given pointer is one, and 0 otherwise.
+%macro is_one_fp12
+    // stack:            ptr
+    DUP1  %mload_kernel_general
+    %sub_const(1)
+    // stack:       acc, ptr
+    %rep 11
+        SWAP1  %increment
+        // stack:   ind, acc
+        DUP1  %mload_kernel_general
+        // stack: x, ind, acc
+        SWAP1  SWAP2
+        // stack: acc, x, ind
+        OR
+        // stack:   acc, ind
+    %endrep
+    // stack:     acc, ind11
+    SWAP1  POP
+    ISZERO
+%endmacro
+
+// Replaces the Fp12 element F = f + f'z at the given pointer by its conjugate
+// f - f'z, which is also its image under the Frobenius map x -> x^(p^6).
+%macro conj_fp12
+    // stack: ptr
+    %add_const(6)
+    %rep 6
+        // stack:       ind
+        DUP1  %mload_kernel_general
+        PUSH 0  SUBFP254
+        // stack:   -x, ind
+        DUP2  %mstore_kernel_general
+        %increment
+    %endrep
+    // stack:     ind12
+    POP
+%endmacro
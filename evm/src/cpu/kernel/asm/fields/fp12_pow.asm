@@ -0,0 +1,36 @@
+// Stores F^e at out, where F is the Fp12 element at in, by square-and-multiply
+// over the bits of e from the least significant one. Uses kernel memory
+// 130-141 for the successive squares of F.
+global pow_fp12:
+    // stack:                  in, e, out, retdest
+    DUP1  %load_fp6
+    %store_fp6(130)
+    %add_const(6)  %load_fp6
+    %store_fp6(136)
+    // stack:                      e, out, retdest
+    DUP2  %store_one_fp12
+pow_fp12_loop:
+    // stack:                      e, out, retdest
+    DUP1  ISZERO
+    %jumpi(pow_fp12_end)
+    DUP1  %and_const(1)  ISZERO
+    %jumpi(pow_fp12_square)
+    %stack (e, out) -> (out, 130, out, pow_fp12_square, e, out)
+    // stack: out, 130, out, pow_fp12_square, e, out, retdest
+    %jump(mul_Fp12)
+pow_fp12_square:
+    // stack:                      e, out, retdest
+    PUSH pow_fp12_next
+    PUSH 130
+    PUSH 130
+    PUSH 130
+    // stack: 130, 130, 130, pow_fp12_next, e, out, retdest
+    %jump(mul_Fp12)
+pow_fp12_next:
+    // stack:                      e, out, retdest
+    %shr_const(1)
+    %jump(pow_fp12_loop)
+pow_fp12_end:
+    // stack:                      0, out, retdest
+    %pop2
+    JUMP
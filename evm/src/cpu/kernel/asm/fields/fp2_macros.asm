@@ -0,0 +1,156 @@
+// Arithmetic in Fp2 = Fp[i] / (i^2 + 1), where Fp is the BN254 base field.
+// An element x + x_ i is kept on the stack as (x, x_), and in kernel memory as
+// two consecutive words x, x_.
+
+// cost: 2 loads + 2 dup/swaps + 1 add = 2*4 + 2 + 2 = 12
+%macro load_fp2
+    // stack:         ptr
+    DUP1  %increment
+    // stack:   ind1, ptr
+    %mload_kernel_general
+    // stack:     x_, ptr
+    SWAP1
+    // stack:    ptr, x_
+    %mload_kernel_general
+    // stack:      x, x_
+%endmacro
+
+// cost: 2 stores + 2 swaps/dups + 1 add = 2*4 + 2 + 2 = 12
+%macro store_fp2
+    // stack:      ptr, x, x_
+    SWAP1
+    // stack:      x, ptr, x_
+    DUP2
+    // stack: ptr, x, ptr, x_
+    %mstore_kernel_general
+    // stack:         ptr, x_
+    %increment
+    // stack:        ind1, x_
+    %mstore_kernel_general
+    // stack:
+%endmacro
+
+// cost: 4
+%macro add_fp2
+    // stack:         x, x_, y, y_
+    SWAP3
+    // stack:        y_, x_, y, x
+    ADDFP254
+    // stack:         x_ + y_, y, x
+    SWAP2
+    // stack:        x, y, x_ + y_
+    ADDFP254
+    // stack:    x + y, x_ + y_
+%endmacro
+
+// cost: 5
+%macro sub_fp2
+    // stack:         x, x_, y, y_
+    SWAP3
+    // stack:        y_, x_, y, x
+    SWAP1
+    // stack:        x_, y_, y, x
+    SUBFP254
+    // stack:         x_ - y_, y, x
+    SWAP2
+    // stack:        x, y, x_ - y_
+    SUBFP254
+    // stack:    x - y, x_ - y_
+%endmacro
+
+// cost: 13
+%macro mul_fp2
+    // stack:                  x, x_, y, y_
+    DUP4  DUP3  MULFP254
+    // stack:           x_ y_, x, x_, y, y_
+    DUP4  DUP3  MULFP254
+    // stack:     x y, x_ y_, x, x_, y, y_
+    SUBFP254
+    // stack:     x y - x_ y_, x, x_, y, y_
+    SWAP4
+    // stack:     y_, x, x_, y, x y - x_ y_
+    MULFP254
+    // stack:        x y_, x_, y, x y - x_ y_
+    SWAP2
+    // stack:        y, x_, x y_, x y - x_ y_
+    MULFP254
+    // stack:           x_ y, x y_, x y - x_ y_
+    ADDFP254
+    // stack:           x_ y + x y_, x y - x_ y_
+    SWAP1
+    // stack:           x y - x_ y_, x_ y + x y_
+%endmacro
+
+// Multiplies an Fp2 element by an element of Fp.
+// cost: 6
+%macro scale_fp2
+    // stack:         s, x, x_
+    DUP1
+    // stack:      s, s, x, x_
+    SWAP2
+    // stack:      x, s, s, x_
+    MULFP254
+    // stack:        s x, s, x_
+    SWAP2
+    // stack:        x_, s, s x
+    MULFP254
+    // stack:           s x_, s x
+    SWAP1
+    // stack:           s x, s x_
+%endmacro
+
+// cost: 6
+%macro neg_fp2
+    // stack:         x, x_
+    PUSH 0  SUBFP254
+    // stack:        -x, x_
+    SWAP1
+    PUSH 0  SUBFP254
+    // stack:       -x_, -x
+    SWAP1
+    // stack:        -x, -x_
+%endmacro
+
+// cost: 4
+%macro conj_fp2
+    // stack:         x, x_
+    SWAP1
+    PUSH 0  SUBFP254
+    // stack:       -x_, x
+    SWAP1
+    // stack:        x, -x_
+%endmacro
+
+// Non-deterministically provide the inverse of a nonzero element of Fp.
+%macro inv_fp254
+    // stack:         x
+    PROVER_INPUT(ff::bn254_base::inverse)
+    // stack:   x^-1, x
+    SWAP1  DUP2  MULFP254
+    // stack: x^-1 x, x^-1
+    %assert_eq_const(1)
+    // stack:   x^-1
+%endmacro
+
+// The inverse of x + x_ i is (x - x_ i) / (x^2 + x_^2).
+%macro inv_fp2
+    // stack:                x, x_
+    DUP2  DUP1  MULFP254
+    // stack:         x_^2, x, x_
+    DUP2  DUP1  MULFP254
+    // stack:    x^2, x_^2, x, x_
+    ADDFP254
+    // stack:            n, x, x_
+    %inv_fp254
+    // stack:         n^-1, x, x_
+    SWAP2
+    // stack:         x_, x, n^-1
+    PUSH 0  SUBFP254
+    // stack:        -x_, x, n^-1
+    DUP3  MULFP254
+    // stack:    -x_ n^-1, x, n^-1
+    SWAP2
+    // stack:    n^-1, x, -x_ n^-1
+    MULFP254
+    // stack: x n^-1, -x_ n^-1
+%endmacro
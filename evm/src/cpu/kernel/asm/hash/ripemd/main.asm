@@ -7,7 +7,7 @@
 ///     STATE, count, _buffer = ripemd_update(STATE, count, _buffer,                     8, bytes = size(len(_input)))
 ///     return process(STATE)
 ///
-/// ripemd is called on a stack with ADDR and length
+/// ripemd is called on a stack with ADDR and length, and copies the input from ADDR
 /// ripemd_stack is called on a stack with length, followed by the input bytes
 ///
 /// ripemd_update receives and return the stack in the form:
@@ -26,13 +26,18 @@ global ripemd_stack:
 
 global ripemd:
     // stack:  ADDR, length
-    %stack (ADDR: 3, length) -> (64, length, 0x80, 63, length, ADDR, length)
-    // stack:                    64, length, 0x80, 63, length, ADDR, length
+    // First copy _input to virt 136, where ripemd_stack would store it.
+    %stack (ADDR: 3, length) -> (0, @SEGMENT_KERNEL_GENERAL, 136, ADDR, length, ripemd_after_copy, length)
+    %jump(memcpy)
+ripemd_after_copy:
+    // stack:                               length
+    %stack (length) -> (64, length, 0x80, 63, 0, length)
+    // stack:           64, length, 0x80, 63, 0, length
     %jump(ripemd_storage) // stores the following into memory
                           // init  _buffer  at virt 0   [consumes           64]
                           // store _size    at virt 64  [consumes       length]
                           // store _padding at virt 72  [consumes 0x80,     63]
-                          // store _input   at virt 136 [consumes ADDR, length]
+                          // store no more  _input      [consumes            0]
 
 global ripemd_init:
     // stack: length
@@ -52,33 +52,6 @@ store_input_stack:
     POP
     %jump(ripemd_init)
 
-store_input:
-    // stack:               rem  , ADDR  , length
-    DUP4
-    DUP4
-    DUP4
-    MLOAD_GENERAL
-    // stack:         byte, rem  , ADDR  , length 
-    DUP2
-    DUP7
-    SUB
-    %add_const(136)
-    // stack: offset, byte, rem  , ADDR  , length 
-    %mstore_kernel_general
-    // stack:               rem  , ADDR  , length 
-    %decrement
-    // stack:               rem-1, ADDR  , length
-    SWAP3
-    %increment
-    SWAP3
-    // stack:               rem-1, ADDR+1, length
-    DUP1
-    %jumpi(store_input)
-    // stack:               0    , ADDR  , length
-    %pop4
-    // stack:                              length
-    %jump(ripemd_init)
-
 /// def buffer_update(get, set, times):
 ///     for i in range(times):
 ///         buffer[set+i] = bytestring[get+i]
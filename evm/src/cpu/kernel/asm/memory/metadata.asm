@@ -46,10 +46,66 @@
     %mload_context_metadata(@CTX_METADATA_CALL_VALUE)
 %endmacro
 
+%macro codesize
+    %mload_context_metadata(@CTX_METADATA_CODE_SIZE)
+%endmacro
+
+%macro calldatasize
+    %mload_context_metadata(@CTX_METADATA_CALLDATA_SIZE)
+%endmacro
+
+global sys_address:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %address
+    // stack: address, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_caller:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %sender
+    // stack: caller, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_callvalue:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %callvalue
+    // stack: call_value, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_codesize:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %codesize
+    // stack: code_size, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_calldatasize:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %calldatasize
+    // stack: calldata_size, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
 %macro msize
     %mload_context_metadata(@CTX_METADATA_MSIZE)
 %endmacro
 
+global sys_msize:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %msize
+    // stack: msize, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
 %macro update_msize
     // stack: offset
     %add_const(32)
@@ -65,3 +121,87 @@
     %mstore_context_metadata(@CTX_METADATA_MSIZE)
 %endmacro
 
+
+// The block information opcodes read the `BlockMetadata` fields, which are
+// written to global metadata before the kernel starts.
+
+global sys_coinbase:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BENEFICIARY)
+    // stack: coinbase, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_timestamp:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_TIMESTAMP)
+    // stack: timestamp, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_number:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_NUMBER)
+    // stack: number, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_prevrandao:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_DIFFICULTY)
+    // stack: prevrandao, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_gaslimit:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_GAS_LIMIT)
+    // stack: gas_limit, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_chainid:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_CHAIN_ID)
+    // stack: chain_id, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_basefee:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BASE_FEE)
+    // stack: base_fee, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+// Returns the hash of one of the 256 most recent complete blocks, or 0 for any
+// other block number.
+global sys_blockhash:
+    // stack: kexit_info, block_number
+    %charge_gas_const(@GAS_BLOCKHASH)
+    SWAP1
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_NUMBER)
+    SUB
+    // stack: cur_block_number - block_number, kexit_info
+    // The hash is only available if 1 <= cur_block_number - block_number <= 256.
+    // If block_number >= cur_block_number, the difference is 0 or wraps around,
+    // so it fails this check too. Near genesis, "negative" block numbers can
+    // pass it, but they read the zero entries for blocks before genesis.
+    DUP1 %decrement %ge_const(256) %jumpi(sys_blockhash_unavailable)
+    // The previous block hashes are stored oldest first, so this one is at
+    // index 256 - (cur_block_number - block_number).
+    PUSH 256 SUB
+    %mload_kernel(@SEGMENT_BLOCK_HASHES)
+    // stack: hash, kexit_info
+    SWAP1
+    EXIT_KERNEL
+sys_blockhash_unavailable:
+    %stack (diff, kexit_info) -> (kexit_info, 0)
+    EXIT_KERNEL
@@ -1,5 +1,7 @@
 global sys_mload:
     // stack: kexit_info, offset
+    %charge_gas_const(@GAS_VERYLOW)
+    PUSH 32 DUP3 %expand_memory
     PUSH 0 // acc = 0
     // stack: acc, kexit_info, offset
     DUP3 %add_const( 0) %mload_current(@SEGMENT_MAIN_MEMORY) %shl_const(0xf8) ADD
@@ -39,6 +41,8 @@ global sys_mload:
 
 global sys_mstore:
     // stack: kexit_info, offset, value
+    %charge_gas_const(@GAS_VERYLOW)
+    PUSH 32 DUP3 %expand_memory
     DUP3 PUSH  0 BYTE DUP3 %add_const( 0) %mstore_current(@SEGMENT_MAIN_MEMORY)
     DUP3 PUSH  1 BYTE DUP3 %add_const( 1) %mstore_current(@SEGMENT_MAIN_MEMORY)
     DUP3 PUSH  2 BYTE DUP3 %add_const( 2) %mstore_current(@SEGMENT_MAIN_MEMORY)
@@ -76,7 +80,91 @@ global sys_mstore:
 
 global sys_mstore8:
     // stack: kexit_info, offset, value
+    %charge_gas_const(@GAS_VERYLOW)
+    PUSH 1 DUP3 %expand_memory
     %stack (kexit_info, offset, value) -> (offset, value, kexit_info)
     %mstore_current(@SEGMENT_MAIN_MEMORY)
     // stack: kexit_info
     EXIT_KERNEL
+
+global sys_returndatasize:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    // stack: returndata_size, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_returndatacopy:
+    // stack: kexit_info, dest_offset, offset, size
+    // Reading past the end of the return data is an exceptional halt.
+    DUP3 DUP5 ADD
+    // stack: offset + size, kexit_info, dest_offset, offset, size
+    %mload_context_metadata(@CTX_METADATA_RETURNDATA_SIZE)
+    LT %jumpi(fault_exception)
+
+    // The cost is @GAS_VERYLOW, plus @GAS_COPY per word copied, plus memory
+    // expansion.
+    %charge_gas_const(@GAS_VERYLOW)
+    DUP4 DUP3 %charge_copy_gas
+
+    GET_CONTEXT
+    %stack (ctx, kexit_info, dest_offset, offset, size)
+        -> (ctx, @SEGMENT_MAIN_MEMORY, dest_offset,
+            ctx, @SEGMENT_RETURNDATA, offset,
+            size, sys_returndatacopy_finish, kexit_info)
+    %jump(memcpy)
+sys_returndatacopy_finish:
+    // stack: kexit_info
+    EXIT_KERNEL
+
+global sys_calldataload:
+    // stack: kexit_info, i
+    %charge_gas_const(@GAS_VERYLOW)
+    // Bytes past the end of the calldata read as zero, so capping i at
+    // calldata_size doesn't change the result.
+    SWAP1
+    %calldatasize
+    %min
+    // stack: offset, kexit_info
+    GET_CONTEXT
+    %stack (ctx, offset, kexit_info)
+        -> (ctx, @SEGMENT_CALLDATA, offset, 32, sys_calldataload_finish, kexit_info)
+    %jump(mload_packing)
+sys_calldataload_finish:
+    // stack: value, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+// Copies `size` bytes of the given segment of the current context, starting at
+// `offset`, to main memory at `dest_offset`. Bytes past the end of the source
+// data, whose length is given by the context metadata field `size_field`, are
+// copied as zeros.
+%macro wcopy(segment, size_field)
+    // stack: kexit_info, dest_offset, offset, size
+    // The cost is @GAS_VERYLOW, plus @GAS_COPY per word copied, plus memory
+    // expansion.
+    %charge_gas_const(@GAS_VERYLOW)
+    DUP4 DUP3 %charge_copy_gas
+
+    // The source segment is zero from offset `size_field` onwards, so capping
+    // the offset there doesn't change what gets copied.
+    %stack (kexit_info, dest_offset, offset, size) -> (offset, dest_offset, size, kexit_info)
+    %mload_context_metadata($size_field)
+    %min
+    GET_CONTEXT
+    %stack (ctx, offset, dest_offset, size, kexit_info)
+        -> (ctx, @SEGMENT_MAIN_MEMORY, dest_offset,
+            ctx, $segment, offset,
+            size, %%after, kexit_info)
+    %jump(memcpy)
+%%after:
+    // stack: kexit_info
+    EXIT_KERNEL
+%endmacro
+
+global sys_calldatacopy:
+    %wcopy(@SEGMENT_CALLDATA, @CTX_METADATA_CALLDATA_SIZE)
+
+global sys_codecopy:
+    %wcopy(@SEGMENT_CODE, @CTX_METADATA_CODE_SIZE)
@@ -15,3 +15,32 @@
     %mstore_kernel(@SEGMENT_NORMALIZED_TXN)
     // stack: (empty)
 %endmacro
+
+global sys_origin:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %mload_txn_field(@TXN_FIELD_ORIGIN)
+    // stack: origin, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+// The effective gas price, i.e. min(max_fee_per_gas, base_fee + max_priority_fee_per_gas).
+// Legacy transactions set both fee fields to their gas price, so this yields
+// the gas price for them.
+%macro effective_gas_price
+    %mload_txn_field(@TXN_FIELD_MAX_PRIORITY_FEE_PER_GAS)
+    %mload_global_metadata(@GLOBAL_METADATA_BLOCK_BASE_FEE)
+    ADD
+    // stack: base_fee + max_priority_fee
+    %mload_txn_field(@TXN_FIELD_MAX_FEE_PER_GAS)
+    %min
+    // stack: gas_price
+%endmacro
+
+global sys_gasprice:
+    // stack: kexit_info
+    %charge_gas_const(@GAS_BASE)
+    %effective_gas_price
+    // stack: gas_price, kexit_info
+    SWAP1
+    EXIT_KERNEL
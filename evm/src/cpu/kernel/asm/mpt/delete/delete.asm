@@ -1,6 +1,270 @@
-// Return a copy of the given node with the given key deleted.
+// Return a copy of the given node with the given key deleted. If the key is not
+// present, the node is returned unchanged.
 //
 // Pre stack: node_ptr, num_nibbles, key, retdest
 // Post stack: updated_node_ptr
 global mpt_delete:
-    PANIC // TODO
+    // stack: node_ptr, num_nibbles, key, retdest
+    DUP1 %mload_trie_data
+    // stack: node_type, node_ptr, num_nibbles, key, retdest
+
+    DUP1 %eq_const(@MPT_NODE_EMPTY)     %jumpi(mpt_delete_empty)
+    DUP1 %eq_const(@MPT_NODE_BRANCH)    %jumpi(mpt_delete_branch)
+    DUP1 %eq_const(@MPT_NODE_EXTENSION) %jumpi(mpt_delete_extension)
+    DUP1 %eq_const(@MPT_NODE_LEAF)      %jumpi(mpt_delete_leaf)
+
+    // There's still the MPT_NODE_HASH case, but if we hit a hash node,
+    // it means the prover failed to provide necessary Merkle data, so panic.
+    PANIC
+
+mpt_delete_empty:
+    // The key is not present, so there is nothing to delete.
+    %stack (node_type, node_ptr, num_nibbles, key, retdest) -> (retdest, node_ptr)
+    JUMP
+
+mpt_delete_leaf:
+    // stack: node_type, node_ptr, num_nibbles, key, retdest
+    POP
+    DUP1 %increment %mload_trie_data
+    // stack: node_len, node_ptr, num_nibbles, key, retdest
+    DUP3 EQ // node_len == num_nibbles
+    DUP2 %add_const(2) %mload_trie_data
+    // stack: node_key, node_len == num_nibbles, node_ptr, num_nibbles, key, retdest
+    DUP5 EQ // node_key == key
+    MUL // Cheaper than AND
+    // stack: keys_match, node_ptr, num_nibbles, key, retdest
+    %jumpi(mpt_delete_leaf_found)
+    // The key is not present, so there is nothing to delete.
+    %stack (node_ptr, num_nibbles, key, retdest) -> (retdest, node_ptr)
+    JUMP
+mpt_delete_leaf_found:
+    // The leaf is removed entirely. An empty node is represented by the null
+    // pointer, since TRIE_DATA[0] = 0 = MPT_NODE_EMPTY.
+    %stack (node_ptr, num_nibbles, key, retdest) -> (retdest, 0)
+    JUMP
+
+mpt_delete_extension:
+    // stack: node_type, node_ptr, num_nibbles, key, retdest
+    POP
+    DUP1 %increment %mload_trie_data
+    // stack: node_len, node_ptr, num_nibbles, key, retdest
+    DUP1 DUP4 LT // num_nibbles < node_len
+    %jumpi(mpt_delete_extension_not_found)
+    DUP1 DUP4 SUB
+    // stack: future_nibbles, node_len, node_ptr, num_nibbles, key, retdest
+    DUP5 DUP2 %mul_const(4) SHR // key_part = key >> (future_nibbles * 4)
+    // stack: key_part, future_nibbles, node_len, node_ptr, num_nibbles, key, retdest
+    DUP4 %add_const(2) %mload_trie_data
+    // stack: node_key, key_part, future_nibbles, node_len, node_ptr, num_nibbles, key, retdest
+    DUP2 EQ // does the first part of our key match the node's key?
+    %jumpi(mpt_delete_extension_found)
+    // stack: key_part, future_nibbles, node_len, node_ptr, num_nibbles, key, retdest
+    %pop2
+mpt_delete_extension_not_found:
+    // The key is not present, so there is nothing to delete.
+    %stack (node_len, node_ptr, num_nibbles, key, retdest) -> (retdest, node_ptr)
+    JUMP
+
+mpt_delete_extension_found:
+    // stack: key_part, future_nibbles, node_len, node_ptr, num_nibbles, key, retdest
+    DUP2 %mul_const(4) SHL // key_part_shifted = (key_part << (future_nibbles * 4))
+    DUP6 SUB // key -= key_part_shifted
+    // stack: key, future_nibbles, node_len, node_ptr, num_nibbles, old_key, retdest
+    %stack (key, future_nibbles, node_len, node_ptr, num_nibbles, old_key)
+        -> (node_ptr, future_nibbles, key, mpt_delete_extension_after_recursion, node_ptr)
+    %add_const(3) // child pointer is third field of extension node
+    %mload_trie_data
+    // stack: child_ptr, future_nibbles, key, mpt_delete_extension_after_recursion, node_ptr, retdest
+    %jump(mpt_delete)
+
+mpt_delete_extension_after_recursion:
+    // stack: updated_child_ptr, node_ptr, retdest
+    // The updated child may have become a leaf or extension node, in which case
+    // it gets merged with our extension node.
+    DUP2 %add_const(2) %mload_trie_data
+    DUP3 %increment %mload_trie_data
+    // stack: node_len, node_key, updated_child_ptr, node_ptr, retdest
+    %stack (node_len, node_key, updated_child_ptr, node_ptr)
+        -> (node_len, node_key, updated_child_ptr)
+    %jump(mpt_delete_prepend_key)
+
+mpt_delete_branch:
+    // stack: node_type, node_ptr, num_nibbles, key, retdest
+    POP
+    // Copy the original node's data to an updated node.
+    %get_trie_data_size
+    // stack: updated_branch_ptr, node_ptr, num_nibbles, key, retdest
+    PUSH @MPT_NODE_BRANCH %append_to_trie_data
+    SWAP1 %increment
+    // stack: node_payload_ptr, updated_branch_ptr, num_nibbles, key, retdest
+    %rep 17
+        // Copy the 16 children, followed by value_ptr.
+        DUP1 %mload_trie_data %append_to_trie_data
+        %increment
+    %endrep
+    POP
+    // stack: updated_branch_ptr, num_nibbles, key, retdest
+
+    // Branch based on whether the key terminates with this branch node.
+    DUP2 %jumpi(mpt_delete_branch_nonterminal)
+
+    // The key terminates here, so we delete the branch node's value.
+    // stack: updated_branch_ptr, num_nibbles, key, retdest
+    PUSH 0
+    DUP2 %add_const(17)
+    // stack: updated_branch_value_ptr_ptr, 0, updated_branch_ptr, num_nibbles, key, retdest
+    %mstore_trie_data
+    %stack (updated_branch_ptr, num_nibbles, key) -> (updated_branch_ptr)
+    %jump(mpt_delete_branch_normalize)
+
+mpt_delete_branch_nonterminal:
+    // The key continues, so we split off the first (most significant) nibble,
+    // and recursively delete from the child associated with that nibble.
+    // stack: updated_branch_ptr, num_nibbles, key, retdest
+    %stack (updated_branch_ptr, num_nibbles, key) -> (num_nibbles, key, updated_branch_ptr)
+    %split_first_nibble
+    // stack: first_nibble, num_nibbles, key, updated_branch_ptr, retdest
+    DUP4 %increment ADD
+    // stack: child_ptr_ptr, num_nibbles, key, updated_branch_ptr, retdest
+    %stack (child_ptr_ptr, num_nibbles, key, updated_branch_ptr)
+        -> (child_ptr_ptr, num_nibbles, key,
+            mpt_delete_branch_nonterminal_after_recursion,
+            child_ptr_ptr, updated_branch_ptr)
+    %mload_trie_data // Deref child_ptr_ptr, giving child_ptr
+    %jump(mpt_delete)
+
+mpt_delete_branch_nonterminal_after_recursion:
+    // stack: updated_child_ptr, child_ptr_ptr, updated_branch_ptr, retdest
+    SWAP1 %mstore_trie_data // Store the pointer to the updated child.
+    // stack: updated_branch_ptr, retdest
+
+mpt_delete_branch_normalize:
+    // A branch node needs at least two entries, counting both its children and
+    // its value. If only one entry is left, we replace the branch with an
+    // equivalent leaf or extension node.
+    // stack: updated_branch_ptr, retdest
+    PUSH 0 // last_child
+    PUSH 0 // num_children
+    PUSH 0 // i
+mpt_delete_branch_count_children:
+    // stack: i, num_children, last_child, updated_branch_ptr, retdest
+    DUP1 %eq_const(16) %jumpi(mpt_delete_branch_counted_children)
+    DUP1 DUP5 %increment ADD
+    // stack: child_ptr_ptr, i, num_children, last_child, updated_branch_ptr, retdest
+    %mload_trie_data %mload_trie_data
+    // stack: child_type, i, num_children, last_child, updated_branch_ptr, retdest
+    %jumpi(mpt_delete_branch_nonempty_child)
+    %increment
+    %jump(mpt_delete_branch_count_children)
+mpt_delete_branch_nonempty_child:
+    // stack: i, num_children, last_child, updated_branch_ptr, retdest
+    %stack (i, num_children, last_child) -> (i, num_children, i)
+    SWAP1 %increment SWAP1
+    %increment
+    %jump(mpt_delete_branch_count_children)
+
+mpt_delete_branch_counted_children:
+    // stack: 16, num_children, last_child, updated_branch_ptr, retdest
+    POP
+    DUP3 %add_const(17) %mload_trie_data
+    // stack: value_ptr, num_children, last_child, updated_branch_ptr, retdest
+    DUP1 ISZERO ISZERO DUP3 ADD
+    // stack: num_entries, value_ptr, num_children, last_child, updated_branch_ptr, retdest
+    %gt_const(1) %jumpi(mpt_delete_branch_keep)
+    // stack: value_ptr, num_children, last_child, updated_branch_ptr, retdest
+    SWAP1 %jumpi(mpt_delete_branch_collapse_to_child)
+
+    // Only the value is left, so we replace the branch with a leaf with an empty key.
+    // stack: value_ptr, last_child, updated_branch_ptr, retdest
+    %stack (value_ptr, last_child, updated_branch_ptr) -> (value_ptr)
+    %get_trie_data_size // pointer to the leaf node we're about to create
+    // stack: leaf_ptr, value_ptr, retdest
+    PUSH @MPT_NODE_LEAF %append_to_trie_data
+    PUSH 0 %append_to_trie_data // num_nibbles
+    PUSH 0 %append_to_trie_data // key
+    SWAP1 %append_to_trie_data
+    // stack: leaf_ptr, retdest
+    SWAP1
+    JUMP
+
+mpt_delete_branch_collapse_to_child:
+    // Only one child is left, so we replace the branch with an extension whose
+    // key is that child's nibble, merged with the child if possible.
+    // stack: value_ptr, last_child, updated_branch_ptr, retdest
+    POP
+    DUP1 DUP3 %increment ADD %mload_trie_data
+    // stack: child_ptr, last_child, updated_branch_ptr, retdest
+    %stack (child_ptr, last_child, updated_branch_ptr) -> (1, last_child, child_ptr)
+    %jump(mpt_delete_prepend_key)
+
+mpt_delete_branch_keep:
+    // stack: value_ptr, num_children, last_child, updated_branch_ptr, retdest
+    %pop3
+    // stack: updated_branch_ptr, retdest
+    SWAP1
+    JUMP
+
+// Return a node equivalent to an extension node with the given key, pointing to
+// the given child. If the child is a leaf or extension node, the key is merged
+// into its key instead, since extension nodes can't have such children.
+//
+// Pre stack: num_nibbles, key, child_ptr, retdest
+// Post stack: node_ptr
+mpt_delete_prepend_key:
+    // stack: num_nibbles, key, child_ptr, retdest
+    DUP3 %mload_trie_data
+    // stack: child_type, num_nibbles, key, child_ptr, retdest
+
+    DUP1 %eq_const(@MPT_NODE_EMPTY)     %jumpi(mpt_delete_prepend_key_empty)
+    DUP1 %eq_const(@MPT_NODE_BRANCH)    %jumpi(mpt_delete_prepend_key_branch)
+    DUP1 %eq_const(@MPT_NODE_EXTENSION) %jumpi(mpt_delete_prepend_key_merge)
+    DUP1 %eq_const(@MPT_NODE_LEAF)      %jumpi(mpt_delete_prepend_key_merge)
+
+    // If the child is a hash node, we can't tell whether it should be merged, so
+    // the prover failed to provide necessary Merkle data; panic.
+    PANIC
+
+mpt_delete_prepend_key_empty:
+    %stack (child_type, num_nibbles, key, child_ptr, retdest) -> (retdest, 0)
+    JUMP
+
+mpt_delete_prepend_key_branch:
+    // stack: child_type, num_nibbles, key, child_ptr, retdest
+    POP
+    %get_trie_data_size // pointer to the extension node we're about to create
+    // stack: extension_ptr, num_nibbles, key, child_ptr, retdest
+    PUSH @MPT_NODE_EXTENSION %append_to_trie_data
+    SWAP1 %append_to_trie_data // Append num_nibbles to our node
+    SWAP1 %append_to_trie_data // Append key to our node
+    SWAP1 %append_to_trie_data // Append child_ptr to our node
+    // stack: extension_ptr, retdest
+    SWAP1
+    JUMP
+
+mpt_delete_prepend_key_merge:
+    // The child has the same type as the node we're creating, and its last
+    // field (value_ptr for a leaf, child_ptr for an extension) carries over.
+    // stack: child_type, num_nibbles, key, child_ptr, retdest
+    DUP4 %increment %mload_trie_data
+    // stack: child_len, child_type, num_nibbles, key, child_ptr, retdest
+    DUP4
+    DUP2 %mul_const(4)
+    SHL // key << (child_len * 4)
+    DUP6 %add_const(2) %mload_trie_data
+    OR
+    // stack: merged_key, child_len, child_type, num_nibbles, key, child_ptr, retdest
+    SWAP1 DUP4 ADD
+    // stack: merged_len, merged_key, child_type, num_nibbles, key, child_ptr, retdest
+    DUP6 %add_const(3) %mload_trie_data
+    // stack: last_field, merged_len, merged_key, child_type, num_nibbles, key, child_ptr, retdest
+    %stack (last_field, merged_len, merged_key, child_type, num_nibbles, key, child_ptr)
+        -> (child_type, merged_len, merged_key, last_field)
+    %get_trie_data_size // pointer to the node we're about to create
+    // stack: new_node_ptr, child_type, merged_len, merged_key, last_field, retdest
+    SWAP1 %append_to_trie_data
+    SWAP1 %append_to_trie_data
+    SWAP1 %append_to_trie_data
+    SWAP1 %append_to_trie_data
+    // stack: new_node_ptr, retdest
+    SWAP1
+    JUMP
@@ -0,0 +1,16 @@
+// Deletion logic specific to a particular trie.
+
+// Mutate the state trie, deleting the given key.
+// Pre stack: key, retdest
+// Post stack: (empty)
+global mpt_delete_state_trie:
+    // stack: key, retdest
+    %stack (key) -> (key, mpt_delete_state_trie_save)
+    PUSH 64 // num_nibbles
+    %mload_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
+    // stack: state_root_ptr, num_nibbles, key, mpt_delete_state_trie_save, retdest
+    %jump(mpt_delete)
+mpt_delete_state_trie_save:
+    // stack: updated_node_ptr, retdest
+    %mstore_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
+    JUMP
@@ -41,45 +41,70 @@ global mpt_hash_receipt_trie:
 
 global encode_account:
     // stack: rlp_pos, value_ptr, retdest
-    // First, we compute the length of the RLP data we're about to write.
+    // First, we hash the account's storage trie. Hashing reuses @SEGMENT_RLP_RAW,
+    // where the enclosing leaf has already written its hex-prefixed key, which
+    // takes at most 34 bytes starting at position 9. We save those bytes on the
+    // stack and restore them afterward, before writing anything else.
+    PUSH encode_account_after_save_key
+    PUSH 2 PUSH 41 PUSH @SEGMENT_RLP_RAW PUSH 0
+    %jump(mload_packing)
+encode_account_after_save_key:
+    // stack: key_rlp_lo, rlp_pos, value_ptr, retdest
+    PUSH encode_account_after_save_key_hi
+    PUSH 32 PUSH 9 PUSH @SEGMENT_RLP_RAW PUSH 0
+    %jump(mload_packing)
+encode_account_after_save_key_hi:
+    // stack: key_rlp_hi, key_rlp_lo, rlp_pos, value_ptr, retdest
+    PUSH encode_account_after_hash_storage_trie
+    PUSH encode_storage_value
+    DUP6 %add_const(2) %mload_trie_data // storage_root_ptr = value[2]
+    // stack: storage_root_ptr, encode_storage_value, encode_account_after_hash_storage_trie, key_rlp_hi, key_rlp_lo, rlp_pos, value_ptr, retdest
+    %jump(mpt_hash)
+encode_account_after_hash_storage_trie:
+    // stack: storage_root_digest, key_rlp_hi, key_rlp_lo, rlp_pos, value_ptr, retdest
+    %stack (storage_root_digest, key_rlp_hi, key_rlp_lo)
+        -> (9, key_rlp_hi, 32, encode_account_after_restore_key_hi, key_rlp_lo, storage_root_digest)
+    %jump(mstore_unpacking_rlp)
+encode_account_after_restore_key_hi:
+    // stack: 41, key_rlp_lo, storage_root_digest, rlp_pos, value_ptr, retdest
+    %stack (pos, key_rlp_lo) -> (pos, key_rlp_lo, 2, encode_account_after_restore_key)
+    %jump(mstore_unpacking_rlp)
+encode_account_after_restore_key:
+    // stack: 43, storage_root_digest, rlp_pos, value_ptr, retdest
+    POP
+    // stack: storage_root_digest, rlp_pos, value_ptr, retdest
+    // Next, we compute the length of the RLP data we're about to write.
     // The nonce and balance fields are variable-length, so we need to load them
     // to determine their contribution, while the other two fields are fixed
     // 32-bytes integers.
-    DUP2 %mload_trie_data // nonce = value[0]
+    DUP3 %mload_trie_data // nonce = value[0]
     %rlp_scalar_len
-    // stack: nonce_rlp_len, rlp_pos, value_ptr, retdest
-    DUP3 %increment %mload_trie_data // balance = value[1]
+    // stack: nonce_rlp_len, storage_root_digest, rlp_pos, value_ptr, retdest
+    DUP4 %increment %mload_trie_data // balance = value[1]
     %rlp_scalar_len
-    // stack: balance_rlp_len, nonce_rlp_len, rlp_pos, value_ptr, retdest
+    // stack: balance_rlp_len, nonce_rlp_len, storage_root_digest, rlp_pos, value_ptr, retdest
     PUSH 66 // storage_root and code_hash fields each take 1 + 32 bytes
     ADD ADD
-    // stack: payload_len, rlp_pos, value_ptr, retdest
-    SWAP1
-    // stack: rlp_pos, payload_len, value_ptr, retdest
+    // stack: payload_len, storage_root_digest, rlp_pos, value_ptr, retdest
+    %stack (payload_len, storage_root_digest, rlp_pos) -> (rlp_pos, payload_len, storage_root_digest)
+    // stack: rlp_pos, payload_len, storage_root_digest, value_ptr, retdest
     DUP2 %rlp_list_len
-    // stack: list_len, rlp_pos, payload_len, value_ptr, retdest
+    // stack: list_len, rlp_pos, payload_len, storage_root_digest, value_ptr, retdest
     SWAP1
-    // stack: rlp_pos, list_len, payload_len, value_ptr, retdest
+    // stack: rlp_pos, list_len, payload_len, storage_root_digest, value_ptr, retdest
     %encode_rlp_multi_byte_string_prefix
-    // stack: rlp_pos_2, payload_len, value_ptr, retdest
+    // stack: rlp_pos_2, payload_len, storage_root_digest, value_ptr, retdest
     %encode_rlp_list_prefix
-    // stack: rlp_pos_3, value_ptr, retdest
-    DUP2 %mload_trie_data // nonce = value[0]
-    // stack: nonce, rlp_pos_3, value_ptr, retdest
+    // stack: rlp_pos_3, storage_root_digest, value_ptr, retdest
+    DUP3 %mload_trie_data // nonce = value[0]
+    // stack: nonce, rlp_pos_3, storage_root_digest, value_ptr, retdest
     SWAP1 %encode_rlp_scalar
-    // stack: rlp_pos_4, value_ptr, retdest
-    DUP2 %increment %mload_trie_data // balance = value[1]
-    // stack: balance, rlp_pos_4, value_ptr, retdest
+    // stack: rlp_pos_4, storage_root_digest, value_ptr, retdest
+    DUP3 %increment %mload_trie_data // balance = value[1]
+    // stack: balance, rlp_pos_4, storage_root_digest, value_ptr, retdest
     SWAP1 %encode_rlp_scalar
-    // stack: rlp_pos_5, value_ptr, retdest
-    PUSH encode_account_after_hash_storage_trie
-    PUSH encode_storage_value
-    DUP4 %add_const(2) %mload_trie_data // storage_root_ptr = value[2]
-    // stack: storage_root_ptr, encode_storage_value, encode_account_after_hash_storage_trie, rlp_pos_5, value_ptr, retdest
-    %jump(mpt_hash)
-encode_account_after_hash_storage_trie:
-    // stack: storage_root_digest, rlp_pos_5, value_ptr, retdest
-    SWAP1 %encode_rlp_256
+    // stack: rlp_pos_5, storage_root_digest, value_ptr, retdest
+    %encode_rlp_256
     // stack: rlp_pos_6, value_ptr, retdest
     SWAP1 %add_const(3) %mload_trie_data // code_hash = value[3]
     // stack: code_hash, rlp_pos_6, retdest
@@ -92,7 +117,32 @@ encode_txn:
     PANIC // TODO
 
 encode_receipt:
-    PANIC // TODO
+    // stack: rlp_pos, value_ptr, retdest
+    // The receipt is stored as its length, followed by its encoding.
+    SWAP1 %increment SWAP1
+    DUP2 %decrement %mload_trie_data
+    // stack: len, rlp_pos, receipt_ptr, retdest
+    %stack (len, rlp_pos, receipt_ptr) -> (rlp_pos, 0, @SEGMENT_TRIE_DATA, receipt_ptr, len)
+    %jump(encode_rlp_string)
 
 encode_storage_value:
-    PANIC // TODO: RLP encode as variable-len scalar?
+    // stack: rlp_pos, value_ptr, retdest
+    SWAP1 %mload_trie_data SWAP1
+    // stack: rlp_pos, value, retdest
+    // The leaf value is the RLP encoding of the scalar, which is itself wrapped
+    // in an RLP string. If the value is in [1, 0x7f], its encoding is a single
+    // byte below 0x80, which is its own string encoding.
+    DUP2 %gt_const(0x7f)
+    DUP3 ISZERO
+    OR
+    // stack: needs_prefix, rlp_pos, value, retdest
+    %jumpi(encode_storage_value_with_prefix)
+    // stack: rlp_pos, value, retdest
+    %jump(encode_rlp_scalar)
+encode_storage_value_with_prefix:
+    // stack: rlp_pos, value, retdest
+    DUP2 %rlp_scalar_len
+    // stack: value_rlp_len, rlp_pos, value, retdest
+    SWAP1 %encode_rlp_multi_byte_string_prefix
+    // stack: rlp_pos', value, retdest
+    %jump(encode_rlp_scalar)
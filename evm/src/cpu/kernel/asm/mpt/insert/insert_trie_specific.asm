@@ -15,3 +15,18 @@ mpt_insert_state_trie_save:
     // stack: updated_node_ptr, retdest
     %mstore_global_metadata(@GLOBAL_METADATA_STATE_TRIE_ROOT)
     JUMP
+
+// Mutate the receipt trie, inserting the given key-value pair.
+// Pre stack: num_nibbles, key, value_ptr, retdest
+// Post stack: (empty)
+global mpt_insert_receipt_trie:
+    // stack: num_nibbles, key, value_ptr, retdest
+    %stack (num_nibbles, key, value_ptr)
+        -> (num_nibbles, key, value_ptr, mpt_insert_receipt_trie_save)
+    %mload_global_metadata(@GLOBAL_METADATA_RECEIPT_TRIE_ROOT)
+    // stack: receipt_root_ptr, num_nibbles, key, value_ptr, mpt_insert_receipt_trie_save, retdest
+    %jump(mpt_insert)
+mpt_insert_receipt_trie_save:
+    // stack: updated_node_ptr, retdest
+    %mstore_global_metadata(@GLOBAL_METADATA_RECEIPT_TRIE_ROOT)
+    JUMP
@@ -33,8 +33,23 @@ global mpt_load_txn_trie_value:
 
 global mpt_load_receipt_trie_value:
     // stack: retdest
-    PANIC // TODO
+    // A receipt is stored as its length, followed by its encoding, one byte per word.
+    PROVER_INPUT(mpt)
+    DUP1 %append_to_trie_data
+mpt_load_receipt_trie_value_loop:
+    // stack: remaining_bytes, retdest
+    DUP1 ISZERO %jumpi(mpt_load_receipt_trie_value_end)
+    PROVER_INPUT(mpt) %append_to_trie_data
+    %decrement
+    %jump(mpt_load_receipt_trie_value_loop)
+mpt_load_receipt_trie_value_end:
+    // stack: 0, retdest
+    POP
+    JUMP
 
 global mpt_load_storage_trie_value:
     // stack: retdest
-    PANIC // TODO
+    PROVER_INPUT(mpt)
+    %append_to_trie_data
+    // stack: retdest
+    JUMP
@@ -101,7 +101,7 @@ mpt_read_extension:
     EQ // does the first part of our key match the node's key?
     %jumpi(mpt_read_extension_found)
     // Not found; return 0.
-    %stack (key_part, future_nibbles, node_payload_ptr, retdest) -> (retdest, 0)
+    %stack (key_part, future_nibbles, key, node_payload_ptr, retdest) -> (retdest, 0)
     JUMP
 mpt_read_extension_found:
     // stack: key_part, future_nibbles, key, node_payload_ptr, retdest
@@ -28,3 +28,62 @@ storage_key_exists:
     // stack: value, retdest
     SWAP1
     JUMP
+
+// Convenience macro to call storage_read and return where we left off.
+%macro storage_read
+    %stack (slot) -> (slot, %%after)
+    %jump(storage_read)
+%%after:
+%endmacro
+
+// Read a word from the current account's storage trie, as it was at the start
+// of the current transaction. EIP-2200 calls this the original value of a slot.
+//
+// Pre stack: slot, retdest
+// Post stack: value
+global storage_read_original:
+    // stack: slot, retdest
+    %address %addr_to_state_key
+    // stack: state_key, slot, retdest
+    PUSH 64 // state_key has 64 nibbles
+    %mload_global_metadata(@GLOBAL_METADATA_TXN_START_STATE_TRIE_ROOT)
+    // stack: state_root_ptr, 64, state_key, slot, retdest
+    %stack (state_root_ptr, num_nibbles, state_key)
+        -> (state_root_ptr, num_nibbles, state_key, storage_read_original_after_account)
+    %jump(mpt_read)
+
+storage_read_original_after_account:
+    // stack: account_ptr, slot, retdest
+    // If the account didn't exist at the start of the transaction, neither did
+    // any of its storage.
+    DUP1 ISZERO %jumpi(storage_read_original_no_account)
+    %add_const(2) %mload_trie_data
+    // stack: storage_root_ptr, slot, retdest
+    SWAP1 %slot_to_storage_key
+    // stack: storage_key, storage_root_ptr, retdest
+    %stack (storage_key, storage_root_ptr)
+        -> (storage_root_ptr, 64, storage_key, after_storage_read)
+    %jump(mpt_read)
+
+storage_read_original_no_account:
+    %stack (account_ptr, slot, retdest) -> (retdest, 0)
+    JUMP
+
+// Convenience macro to call storage_read_original and return where we left off.
+%macro storage_read_original
+    %stack (slot) -> (slot, %%after)
+    %jump(storage_read_original)
+%%after:
+%endmacro
+
+global sys_sload:
+    // stack: kexit_info, slot
+    DUP2 %address %insert_accessed_storage_keys
+    // stack: cold_access, kexit_info, slot
+    %access_gas(@GAS_COLDSLOAD)
+    %charge_gas
+    SWAP1
+    %storage_read
+    // stack: value, kexit_info
+    SWAP1
+    EXIT_KERNEL
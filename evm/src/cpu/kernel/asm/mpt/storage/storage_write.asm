@@ -4,8 +4,9 @@
 // Post stack: (empty)
 
 global storage_write:
-    // TODO: If value = 0, delete the key instead of inserting 0.
     // stack: slot, value, retdest
+    // Zero values are not stored, so writing 0 deletes the slot's key instead.
+    DUP2 ISZERO %jumpi(storage_delete)
 
     // First we write the value to MPT data, and get a pointer to it.
     %get_trie_data_size
@@ -16,15 +17,25 @@ global storage_write:
     // stack: slot, value_ptr, retdest
 
     // Next, call mpt_insert on the current account's storage root.
-    %stack (slot, value_ptr) -> (slot, value_ptr, after_storage_insert)
+    %stack (slot, value_ptr) -> (slot, value_ptr, after_storage_update)
     %slot_to_storage_key
-    // stack: storage_key, value_ptr, after_storage_write, retdest
+    // stack: storage_key, value_ptr, after_storage_update, retdest
     PUSH 64 // storage_key has 64 nibbles
     %current_storage_trie
-    // stack: storage_root_ptr, 64, storage_key, value_ptr, after_storage_insert, retdest
+    // stack: storage_root_ptr, 64, storage_key, value_ptr, after_storage_update, retdest
     %jump(mpt_insert)
 
-after_storage_insert:
+storage_delete:
+    // stack: slot, value, retdest
+    %stack (slot, value) -> (slot, after_storage_update)
+    %slot_to_storage_key
+    // stack: storage_key, after_storage_update, retdest
+    PUSH 64 // storage_key has 64 nibbles
+    %current_storage_trie
+    // stack: storage_root_ptr, 64, storage_key, after_storage_update, retdest
+    %jump(mpt_delete)
+
+after_storage_update:
     // stack: new_storage_root_ptr, retdest
     %current_account_data
     // stack: old_account_ptr, new_storage_root_ptr, retdest
@@ -42,3 +53,112 @@ after_storage_insert:
     %address %addr_to_state_key
     // stack: state_key, new_account_ptr, retdest
     %jump(mpt_insert_state_trie)
+
+// Convenience macro to call storage_write and return where we left off.
+%macro storage_write
+    %stack (slot, value) -> (slot, value, %%after)
+    %jump(storage_write)
+%%after:
+%endmacro
+
+global sys_sstore:
+    // stack: kexit_info, slot, value
+    %mload_context_metadata(@CTX_METADATA_STATIC) %jumpi(fault_exception)
+    // Per EIP-2200, SSTORE fails if the remaining gas is at most @GAS_CALLSTIPEND.
+    GAS %le_const(@GAS_CALLSTIPEND) %jumpi(fault_exception)
+    DUP2 %storage_read_original
+    DUP3 %storage_read
+    // stack: current, original, kexit_info, slot, value
+    %stack (current, original, kexit_info, slot, value)
+        -> (value, current, original, current, kexit_info, slot, value)
+    %sstore_gas
+    // stack: gas, current, kexit_info, slot, value
+    // sstore_gas assumes a warm slot, so a cold one costs an extra @GAS_COLDSLOAD.
+    DUP4 %address %insert_accessed_storage_keys
+    %mul_const(@GAS_COLDSLOAD) ADD
+    // stack: gas, current, kexit_info, slot, value
+    %charge_gas
+    // stack: current, kexit_info, slot, value
+    // If the value is unchanged, leave the trie untouched.
+    DUP4 EQ %jumpi(sstore_unchanged)
+    // stack: kexit_info, slot, value
+    %stack (kexit_info, slot, value) -> (slot, value, kexit_info)
+    %storage_write
+    // stack: kexit_info
+    EXIT_KERNEL
+
+sstore_unchanged:
+    // stack: kexit_info, slot, value
+    %stack (kexit_info, slot, value) -> (kexit_info)
+    EXIT_KERNEL
+
+// Computes the gas cost of an SSTORE, and updates the refund counter, following
+// EIP-2200 with the costs of EIP-2929 and the refunds of EIP-3529. The slot is
+// assumed to be warm.
+//
+// Pre stack: new, current, original, retdest
+// Post stack: gas
+global sstore_gas:
+    // stack: new, current, original, retdest
+    DUP2 DUP2 EQ %jumpi(sstore_gas_noop)
+    DUP3 DUP3 EQ %jumpi(sstore_gas_clean)
+    %jump(sstore_gas_dirty)
+
+sstore_gas_noop:
+    // The value is unchanged, so this costs as much as a warm SLOAD.
+    %stack (new, current, original, retdest) -> (retdest, @GAS_WARMACCESS)
+    JUMP
+
+sstore_gas_clean:
+    // The slot has not been modified by this transaction so far.
+    // stack: new, current, original, retdest
+    DUP3 ISZERO %jumpi(sstore_gas_clean_set)
+    // If the slot is being cleared, refund the clearing.
+    // stack: new, current, original, retdest
+    ISZERO %mul_const(@REFUND_SCLEAR) %add_refund
+    %stack (current, original, retdest) -> (retdest, @GAS_SRESET)
+    JUMP
+
+sstore_gas_clean_set:
+    %stack (new, current, original, retdest) -> (retdest, @GAS_SSET)
+    JUMP
+
+sstore_gas_dirty:
+    // The slot has already been modified by this transaction.
+    // stack: new, current, original, retdest
+    DUP3 ISZERO %jumpi(sstore_gas_dirty_after_clear_refund)
+    // If the slot was cleared earlier in this transaction, undo that refund.
+    // If it is being cleared now, refund the clearing.
+    DUP1 ISZERO %mul_const(@REFUND_SCLEAR) %add_refund
+    DUP2 ISZERO %mul_const(@REFUND_SCLEAR) %sub_refund
+sstore_gas_dirty_after_clear_refund:
+    // stack: new, current, original, retdest
+    DUP3 EQ
+    // stack: new == original, current, original, retdest
+    %jumpi(sstore_gas_dirty_restore)
+    %stack (current, original, retdest) -> (retdest, @GAS_WARMACCESS)
+    JUMP
+
+sstore_gas_dirty_restore:
+    // The slot is being restored to its original value, so refund the
+    // difference between the earlier SSTORE cost and a warm SLOAD.
+    // stack: current, original, retdest
+    POP
+    ISZERO %jumpi(sstore_gas_dirty_restore_set)
+    PUSH @GAS_WARMACCESS PUSH @GAS_SRESET SUB
+    %jump(sstore_gas_dirty_restore_refund)
+sstore_gas_dirty_restore_set:
+    PUSH @GAS_WARMACCESS PUSH @GAS_SSET SUB
+sstore_gas_dirty_restore_refund:
+    // stack: refund, retdest
+    %add_refund
+    PUSH @GAS_WARMACCESS
+    SWAP1
+    JUMP
+
+// Convenience macro to call sstore_gas and return where we left off.
+%macro sstore_gas
+    %stack (new, current, original) -> (new, current, original, %%after)
+    %jump(sstore_gas)
+%%after:
+%endmacro
@@ -0,0 +1,164 @@
+// Signed arithmetic and comparison operations, namely SDIV, SMOD, SIGNEXTEND,
+// SLT, SGT and SAR. Operands are 256-bit two's complement integers; each
+// operation is reduced to the unsigned operations which are implemented
+// natively.
+
+// Yields 1 if x is negative, i.e. if its most significant bit is set, and 0
+// otherwise.
+%macro sign_bit
+    // stack: x
+    %shr_const(255)
+    // stack: x >> 255
+%endmacro
+
+// Yields -x if s is 1, and x if s is 0. Assumes s is boolean.
+// This is computed without branching as (x ^ -s) + s.
+%macro negate_if
+    // stack: s, x
+    %stack (s, x) -> (s, x, s)
+    PUSH 0
+    SUB
+    // stack: -s, x, s
+    XOR
+    // stack: x ^ -s, s
+    ADD
+    // stack: (x ^ -s) + s
+%endmacro
+
+// Flips the most significant bit of x. This maps two's complement values onto
+// unsigned values while preserving their order.
+%macro flip_sign_bit
+    // stack: x
+    PUSH 0x8000000000000000000000000000000000000000000000000000000000000000
+    XOR
+    // stack: x ^ 2^255
+%endmacro
+
+global sys_sdiv:
+    // stack: kexit_info, a, b
+    %charge_gas_const(@GAS_LOW)
+    %stack (kexit_info, a, b) -> (a, b, kexit_info)
+    DUP1 %sign_bit
+    DUP3 %sign_bit
+    // stack: sign(b), sign(a), a, b, kexit_info
+    DUP2 DUP2 XOR
+    // stack: sign(a) ^ sign(b), sign(b), sign(a), a, b, kexit_info
+    %stack (s, sign_b, sign_a, a, b) -> (sign_b, b, sign_a, a, s)
+    %negate_if
+    // stack: |b|, sign(a), a, s, kexit_info
+    %stack (abs_b, sign_a, a) -> (sign_a, a, abs_b)
+    %negate_if
+    // stack: |a|, |b|, s, kexit_info
+    DIV
+    // stack: |a| / |b|, s, kexit_info
+    // Note that DIV yields 0 when |b| = 0, which is unaffected by the sign.
+    // The overflow case -2^255 / -1 yields 2^255 = -2^255 as required.
+    SWAP1
+    %negate_if
+    // stack: a / b, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_smod:
+    // stack: kexit_info, a, b
+    %charge_gas_const(@GAS_LOW)
+    %stack (kexit_info, a, b) -> (a, b, kexit_info)
+    DUP1 %sign_bit
+    DUP3 %sign_bit
+    // stack: sign(b), sign(a), a, b, kexit_info
+    // The sign of the result is that of the dividend.
+    %stack (sign_b, sign_a, a, b) -> (sign_b, b, sign_a, a, sign_a)
+    %negate_if
+    // stack: |b|, sign(a), a, sign(a), kexit_info
+    %stack (abs_b, sign_a, a) -> (sign_a, a, abs_b)
+    %negate_if
+    // stack: |a|, |b|, sign(a), kexit_info
+    MOD
+    // stack: |a| % |b|, sign(a), kexit_info
+    SWAP1
+    %negate_if
+    // stack: a % b, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_signextend:
+    // stack: kexit_info, b, x
+    %charge_gas_const(@GAS_LOW)
+    DUP2 %lt_const(31)
+    // stack: b < 31, kexit_info, b, x
+    %jumpi(signextend_nontrivial)
+    // If b >= 31, x already fills all 32 bytes so it is returned unchanged.
+    %stack (kexit_info, b, x) -> (kexit_info, x)
+    EXIT_KERNEL
+
+signextend_nontrivial:
+    // stack: kexit_info, b, x
+    SWAP1
+    %shl_const(3)
+    %add_const(7)
+    // stack: t, kexit_info, x, where t = 8 * b + 7 is the index of the sign bit.
+    DUP3 DUP2 SHR
+    %and_const(1)
+    // stack: s, t, kexit_info, x, where s is the sign bit of the low b + 1 bytes.
+    SWAP1
+    PUSH 2
+    SWAP1
+    SHL
+    %decrement
+    // stack: mask, s, kexit_info, x, where mask = 2^(t + 1) - 1.
+    SWAP1 DUP2 NOT MUL
+    // stack: s * ~mask, mask, kexit_info, x
+    SWAP1 DUP4 AND
+    // stack: x & mask, s * ~mask, kexit_info, x
+    OR
+    // stack: signextend(b, x), kexit_info, x
+    %stack (result, kexit_info, x) -> (kexit_info, result)
+    EXIT_KERNEL
+
+global sys_slt:
+    // stack: kexit_info, a, b
+    %charge_gas_const(@GAS_VERYLOW)
+    SWAP2
+    %flip_sign_bit
+    // stack: b ^ 2^255, a, kexit_info
+    SWAP1
+    %flip_sign_bit
+    // stack: a ^ 2^255, b ^ 2^255, kexit_info
+    LT
+    // stack: a < b, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_sgt:
+    // stack: kexit_info, a, b
+    %charge_gas_const(@GAS_VERYLOW)
+    SWAP2
+    %flip_sign_bit
+    // stack: b ^ 2^255, a, kexit_info
+    SWAP1
+    %flip_sign_bit
+    // stack: a ^ 2^255, b ^ 2^255, kexit_info
+    GT
+    // stack: a > b, kexit_info
+    SWAP1
+    EXIT_KERNEL
+
+global sys_sar:
+    // stack: kexit_info, shift, value
+    %charge_gas_const(@GAS_VERYLOW)
+    SWAP2
+    // stack: value, shift, kexit_info
+    DUP1 %sign_bit
+    PUSH 0
+    SUB
+    // stack: mask, value, shift, kexit_info, where mask = -sign(value).
+    // A negative value is shifted as ~(~value >> shift), so that it is
+    // filled with ones from the left.
+    SWAP1 DUP2 XOR
+    // stack: value ^ mask, mask, shift, kexit_info
+    DUP3 SHR
+    // stack: (value ^ mask) >> shift, mask, shift, kexit_info
+    XOR
+    // stack: value >> shift (arithmetic), shift, kexit_info
+    %stack (result, shift, kexit_info) -> (kexit_info, result)
+    EXIT_KERNEL
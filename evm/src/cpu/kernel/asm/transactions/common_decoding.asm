@@ -0,0 +1,223 @@
+// Macros and routines shared by the parsers of typed transactions, i.e. types 1
+// and 2. Each of the decode_and_store macros decodes a field at the given RLP
+// position, stores it, and leaves the position after the field.
+
+%macro decode_and_store_chain_id
+    // stack: pos
+    // Typed transactions always include a chain ID.
+    PUSH 1 %mstore_txn_field(@TXN_FIELD_CHAIN_ID_PRESENT)
+    %decode_rlp_scalar
+    %stack (pos, chain_id) -> (chain_id, pos)
+    %mstore_txn_field(@TXN_FIELD_CHAIN_ID)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_nonce
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, nonce) -> (nonce, pos)
+    %mstore_txn_field(@TXN_FIELD_NONCE)
+    // stack: pos
+%endmacro
+
+// As with legacy transactions, the gas price is stored as both
+// TXN_FIELD_MAX_PRIORITY_FEE_PER_GAS and TXN_FIELD_MAX_FEE_PER_GAS.
+%macro decode_and_store_gas_price
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, gas_price) -> (gas_price, gas_price, pos)
+    %mstore_txn_field(@TXN_FIELD_MAX_PRIORITY_FEE_PER_GAS)
+    %mstore_txn_field(@TXN_FIELD_MAX_FEE_PER_GAS)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_max_priority_fee
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, max_priority_fee) -> (max_priority_fee, pos)
+    %mstore_txn_field(@TXN_FIELD_MAX_PRIORITY_FEE_PER_GAS)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_max_fee
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, max_fee) -> (max_fee, pos)
+    %mstore_txn_field(@TXN_FIELD_MAX_FEE_PER_GAS)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_gas_limit
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, gas_limit) -> (gas_limit, pos)
+    %mstore_txn_field(@TXN_FIELD_GAS_LIMIT)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_to
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, to) -> (to, pos)
+    %mstore_txn_field(@TXN_FIELD_TO)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_value
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, value) -> (value, pos)
+    %mstore_txn_field(@TXN_FIELD_VALUE)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_data
+    // stack: pos
+    // Decode the data length, store it, and compute new_pos after any data.
+    %decode_rlp_string_len
+    %stack (pos, data_len) -> (data_len, pos, data_len, pos, data_len)
+    %mstore_txn_field(@TXN_FIELD_DATA_LEN)
+    // stack: pos, data_len, pos, data_len
+    ADD
+    // stack: new_pos, old_pos, data_len
+
+    // Memcpy the txn data from @SEGMENT_RLP_RAW to @SEGMENT_TXN_DATA.
+    %stack (new_pos, old_pos, data_len) -> (old_pos, data_len, %%after, new_pos)
+    PUSH @SEGMENT_RLP_RAW
+    GET_CONTEXT
+    PUSH 0
+    PUSH @SEGMENT_TXN_DATA
+    GET_CONTEXT
+    // stack: DST, SRC, data_len, %%after, new_pos
+    %jump(memcpy)
+%%after:
+    // stack: new_pos
+%endmacro
+
+%macro decode_and_store_access_list
+    // stack: pos
+    %stack (pos) -> (pos, %%after)
+    %jump(decode_and_store_access_list)
+%%after:
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_y_parity
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, y_parity) -> (y_parity, pos)
+    %mstore_txn_field(@TXN_FIELD_Y_PARITY)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_r
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, r) -> (r, pos)
+    %mstore_txn_field(@TXN_FIELD_R)
+    // stack: pos
+%endmacro
+
+%macro decode_and_store_s
+    // stack: pos
+    %decode_rlp_scalar
+    %stack (pos, s) -> (s, pos)
+    %mstore_txn_field(@TXN_FIELD_S)
+    // stack: pos
+%endmacro
+
+// An access list has the format
+//     [[address, [storage_key, ...]], ...]
+// Its addresses and storage keys are warmed, per EIP-2929, and counted, since
+// each one of them costs intrinsic gas, per EIP-2930. Duplicates are counted,
+// but only warmed once.
+//
+// Pre stack: pos, retdest
+// Post stack: pos'
+global decode_and_store_access_list:
+    // stack: pos, retdest
+    %decode_rlp_list_len
+    // stack: pos, len, retdest
+    SWAP1 DUP2 ADD
+    // stack: end_pos, pos, retdest
+    SWAP1
+decode_access_list_loop:
+    // stack: pos, end_pos, retdest
+    DUP2 DUP2 EQ %jumpi(decode_access_list_finish)
+    // We don't need the length of the [address, [storage_key, ...]] entry.
+    %decode_rlp_list_len
+    %stack (pos, entry_len) -> (pos)
+    %decode_rlp_scalar
+    // stack: pos, address, end_pos, retdest
+    DUP2 %insert_accessed_addresses_no_return
+    %mload_txn_field(@TXN_FIELD_ACCESS_LIST_ADDRESSES)
+    %increment
+    %mstore_txn_field(@TXN_FIELD_ACCESS_LIST_ADDRESSES)
+    // stack: pos, address, end_pos, retdest
+    %decode_rlp_list_len
+    // stack: pos, keys_len, address, end_pos, retdest
+    SWAP1 DUP2 ADD
+    // stack: keys_end_pos, pos, address, end_pos, retdest
+    SWAP1
+decode_storage_keys_loop:
+    // stack: pos, keys_end_pos, address, end_pos, retdest
+    DUP2 DUP2 EQ %jumpi(decode_storage_keys_finish)
+    %decode_rlp_scalar
+    // stack: pos, storage_key, keys_end_pos, address, end_pos, retdest
+    SWAP1 DUP4
+    // stack: address, storage_key, pos, keys_end_pos, address, end_pos, retdest
+    %insert_accessed_storage_keys_no_return
+    %mload_txn_field(@TXN_FIELD_ACCESS_LIST_STORAGE_KEYS)
+    %increment
+    %mstore_txn_field(@TXN_FIELD_ACCESS_LIST_STORAGE_KEYS)
+    // stack: pos, keys_end_pos, address, end_pos, retdest
+    %jump(decode_storage_keys_loop)
+decode_storage_keys_finish:
+    // stack: pos, keys_end_pos, address, end_pos, retdest
+    %stack (pos, keys_end_pos, address) -> (pos)
+    %jump(decode_access_list_loop)
+decode_access_list_finish:
+    // stack: pos, end_pos, retdest
+    %stack (pos, end_pos, retdest) -> (retdest, pos)
+    JUMP
+
+// The signed data of a typed transaction is
+//     keccak256(txn_type || rlp([fields up to the signature])).
+// The encodings of those fields are exactly the RLP payload up to the
+// signature, so we rewrite the type and a new list prefix right before them,
+// where the original prefix was, and hash the result in place. The new prefix
+// can't be longer than the original, so it fits.
+//
+// Pre stack: sig_pos, payload_start, retdest
+// Post stack: (empty, since we jump to process_normalized_txn)
+global typed_txn_compute_signed_data:
+    // stack: sig_pos, payload_start, retdest
+    DUP2 DUP2 SUB
+    // stack: payload_len, sig_pos, payload_start, retdest
+    DUP1 %rlp_list_len %increment
+    // stack: signed_len, payload_len, sig_pos, payload_start, retdest
+    DUP1 DUP4 SUB
+    // stack: start_pos, signed_len, payload_len, sig_pos, payload_start, retdest
+    PUSH 0 %mload_kernel(@SEGMENT_RLP_RAW)
+    // stack: txn_type, start_pos, signed_len, payload_len, sig_pos, payload_start, retdest
+    DUP2 %mstore_rlp
+    // stack: start_pos, signed_len, payload_len, sig_pos, payload_start, retdest
+    DUP3 DUP2 %increment
+    // stack: prefix_pos, payload_len, start_pos, signed_len, payload_len, sig_pos, payload_start, retdest
+    %encode_rlp_list_prefix
+    // stack: payload_start, start_pos, signed_len, payload_len, sig_pos, payload_start, retdest
+    %stack (payload_start, start_pos, signed_len, payload_len, sig_pos, payload_start_2)
+        -> (0, @SEGMENT_RLP_RAW, start_pos, signed_len)
+    // stack: ADDR: 3, signed_len, retdest
+    KECCAK_GENERAL
+    // stack: hash, retdest
+
+    %mload_txn_field(@TXN_FIELD_S)
+    %mload_txn_field(@TXN_FIELD_R)
+    %mload_txn_field(@TXN_FIELD_Y_PARITY) %add_const(27) // ecrecover interprets v as y_parity + 27
+
+    PUSH store_origin
+    // stack: store_origin, v, r, s, hash, retdest
+    SWAP4
+    // stack: hash, v, r, s, store_origin, retdest
+    %jump(ecrecover)
@@ -4,6 +4,12 @@
 
 global route_txn:
     // stack: retdest
+    // Each transaction starts with empty access lists. The parsers of type 1 and
+    // 2 transactions fill them with the entries of the transaction's access list.
+    %clear_access_lists
+    PUSH 0 %mstore_txn_field(@TXN_FIELD_ACCESS_LIST_ADDRESSES)
+    PUSH 0 %mstore_txn_field(@TXN_FIELD_ACCESS_LIST_STORAGE_KEYS)
+
     // First load transaction data into memory, where it will be parsed.
     PUSH read_txn_from_memory
     %jump(read_rlp_to_memory)
@@ -13,6 +13,7 @@
 
 global process_type_0_txn:
     // stack: retdest
+    PUSH 0 %mstore_txn_field(@TXN_FIELD_TYPE)
     PUSH 0 // initial pos
     // stack: pos, retdest
     %decode_rlp_list_len
@@ -149,7 +150,17 @@ type_0_compute_signed_data:
     // stack: rlp_pos, retdest
 
     %mload_txn_field(@TXN_FIELD_TO)
-    SWAP1 %encode_rlp_160
+    SWAP1
+    // stack: rlp_pos, to, retdest
+    %is_contract_creation
+    %jumpi(serialize_empty_to)
+    %encode_rlp_160
+    %jump(after_serializing_to)
+serialize_empty_to:
+    // A contract creation has an empty "to" field, which is also how RLP
+    // encodes the scalar 0.
+    %encode_rlp_scalar
+after_serializing_to:
     // stack: rlp_pos, retdest
 
     %mload_txn_field(@TXN_FIELD_VALUE)
@@ -205,7 +216,7 @@ finish_rlp_list:
     // stack: hash, v, r, s, store_origin, retdest
     %jump(ecrecover)
 
-store_origin:
+global store_origin:
     // stack: address, retdest
     // If ecrecover returned u256::MAX, that indicates failure.
     DUP1
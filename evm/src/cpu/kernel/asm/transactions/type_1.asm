@@ -8,4 +8,29 @@
 
 global process_type_1_txn:
     // stack: retdest
-    PANIC // TODO: Unfinished
+    PUSH 1 %mstore_txn_field(@TXN_FIELD_TYPE)
+    PUSH 1 // initial pos, skipping over the 0x01 byte
+    // stack: pos, retdest
+    %decode_rlp_list_len
+    // We don't actually need the length, but we need the start of the payload
+    // to compute the signed data.
+    %stack (pos, len) -> (pos, pos)
+    // stack: pos, payload_start, retdest
+
+    %decode_and_store_chain_id
+    %decode_and_store_nonce
+    %decode_and_store_gas_price
+    %decode_and_store_gas_limit
+    %decode_and_store_to
+    %decode_and_store_value
+    %decode_and_store_data
+    %decode_and_store_access_list
+    // stack: sig_pos, payload_start, retdest
+
+    DUP1
+    %decode_and_store_y_parity
+    %decode_and_store_r
+    %decode_and_store_s
+    POP
+    // stack: sig_pos, payload_start, retdest
+    %jump(typed_txn_compute_signed_data)
@@ -160,11 +160,6 @@
     // stack: input >= c, ...
 %endmacro
 
-%macro consume_gas_const(c)
-    PUSH $c
-    CONSUME_GAS
-%endmacro
-
 // If pred is zero, yields z; otherwise, yields nz
 %macro select
     // stack: pred, nz, z
@@ -219,8 +214,8 @@
     DUP2
     DUP2
     // stack: x, y, x, y
-    LT
-    // stack: x < y, x, y
+    GT
+    // stack: x > y, x, y
     %select_bool
     // stack: min
 %endmacro
@@ -230,8 +225,8 @@
     DUP2
     DUP2
     // stack: x, y, x, y
-    GT
-    // stack: x > y, x, y
+    LT
+    // stack: x < y, x, y
     %select_bool
     // stack: max
 %endmacro
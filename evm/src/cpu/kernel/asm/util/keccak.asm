@@ -1,5 +1,9 @@
 global sys_keccak256:
     // stack: kexit_info, offset, len
+    DUP3 %num_words %mul_const(@GAS_KECCAK256WORD)
+    %add_const(@GAS_KECCAK256)
+    %charge_gas
+    DUP3 DUP3 %expand_memory
     %stack (kexit_info, offset, len) -> (offset, len, kexit_info)
     PUSH @SEGMENT_MAIN_MEMORY
     GET_CONTEXT
@@ -25,10 +25,30 @@ pub(crate) enum ContextMetadata {
     StateTrieCheckpointPointer = 9,
     /// Size of the active main memory.
     MSize = 10,
+    /// The length of this context's stack. It is saved by `SET_CONTEXT` when switching away from
+    /// this context, and restored when switching back to it.
+    StackSize = 11,
+    /// The amount of gas made available to this context by its caller.
+    GasLimit = 12,
+    /// The size of the `Logs` segment at the creation of this context. Used to discard the logs
+    /// emitted by a reverted context.
+    LogsCheckpoint = 13,
+    /// The gas used so far by this context. Like `StackSize`, it is saved by `SET_CONTEXT` when
+    /// switching away from this context, and restored when switching back to it.
+    GasUsed = 14,
+    /// The sizes of the `AccessedAddresses` and `AccessedStorageKeys` segments at the creation of
+    /// this context. Used to forget the accesses of a reverted context.
+    AccessedAddressesCheckpoint = 15,
+    AccessedStorageKeysCheckpoint = 16,
+    /// The sizes of the `SelfDestructList` and `TouchedAddresses` segments, and the refund counter,
+    /// at the creation of this context. Used to discard them for a reverted context.
+    SelfDestructListCheckpoint = 17,
+    TouchedAddressesCheckpoint = 18,
+    RefundCounterCheckpoint = 19,
 }
 
 impl ContextMetadata {
-    pub(crate) const COUNT: usize = 11;
+    pub(crate) const COUNT: usize = 20;
 
     pub(crate) fn all() -> [Self; Self::COUNT] {
         [
@@ -43,6 +63,15 @@ impl ContextMetadata {
             Self::Static,
             Self::StateTrieCheckpointPointer,
             Self::MSize,
+            Self::StackSize,
+            Self::GasLimit,
+            Self::LogsCheckpoint,
+            Self::GasUsed,
+            Self::AccessedAddressesCheckpoint,
+            Self::AccessedStorageKeysCheckpoint,
+            Self::SelfDestructListCheckpoint,
+            Self::TouchedAddressesCheckpoint,
+            Self::RefundCounterCheckpoint,
         ]
     }
 
@@ -60,6 +89,23 @@ impl ContextMetadata {
             ContextMetadata::Static => "CTX_METADATA_STATIC",
             ContextMetadata::StateTrieCheckpointPointer => "CTX_METADATA_STATE_TRIE_CHECKPOINT_PTR",
             ContextMetadata::MSize => "CTX_METADATA_MSIZE",
+            ContextMetadata::StackSize => "CTX_METADATA_STACK_SIZE",
+            ContextMetadata::GasLimit => "CTX_METADATA_GAS_LIMIT",
+            ContextMetadata::LogsCheckpoint => "CTX_METADATA_LOGS_CHECKPOINT",
+            ContextMetadata::GasUsed => "CTX_METADATA_GAS_USED",
+            ContextMetadata::AccessedAddressesCheckpoint => {
+                "CTX_METADATA_ACCESSED_ADDRESSES_CHECKPOINT"
+            }
+            ContextMetadata::AccessedStorageKeysCheckpoint => {
+                "CTX_METADATA_ACCESSED_STORAGE_KEYS_CHECKPOINT"
+            }
+            ContextMetadata::SelfDestructListCheckpoint => {
+                "CTX_METADATA_SELFDESTRUCT_LIST_CHECKPOINT"
+            }
+            ContextMetadata::TouchedAddressesCheckpoint => {
+                "CTX_METADATA_TOUCHED_ADDRESSES_CHECKPOINT"
+            }
+            ContextMetadata::RefundCounterCheckpoint => "CTX_METADATA_REFUND_COUNTER_CHECKPOINT",
         }
     }
 }
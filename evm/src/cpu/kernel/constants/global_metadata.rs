@@ -32,10 +32,51 @@ pub(crate) enum GlobalMetadata {
     /// The sizes of the `TrieEncodedChild` and `TrieEncodedChildLen` buffers. In other words, the
     /// next available offset in these buffers.
     TrieEncodedChildSize = 14,
+
+    /// A pointer to the root of the state trie as it was at the start of the current transaction.
+    /// `SSTORE` uses it to look up the original value of a storage slot, as defined in EIP-2200.
+    TxnStartStateTrieRoot = 15,
+    /// The gas refund counter of the current transaction.
+    RefundCounter = 16,
+    /// The number of message-call contexts currently on the call stack. The EVM limits this to
+    /// 1024.
+    CallStackDepth = 17,
+    /// The size of the `Logs` segment, i.e. the next available offset in it.
+    LogsLen = 18,
+    /// The index of the current transaction within the block. Receipts are keyed by it in the
+    /// receipt trie.
+    TxnNumber = 19,
+    /// The total gas used by the transactions processed so far in this block.
+    BlockGasUsed = 20,
+
+    // The fields of the `BlockMetadata` of the block being proven. They are written before the
+    // kernel starts executing, and read by the block information opcodes.
+    BlockBeneficiary = 21,
+    BlockTimestamp = 22,
+    BlockNumber = 23,
+    /// The block difficulty. Since the Merge, this is the `prevrandao` value returned by opcode
+    /// 0x44.
+    BlockDifficulty = 24,
+    BlockGasLimit = 25,
+    BlockChainId = 26,
+    BlockBaseFee = 27,
+    /// The hash of the current block. The hashes of the preceding blocks are kept in the
+    /// `BlockHashes` segment.
+    BlockCurrentHash = 28,
+    /// The size of the `AccessedAddresses` segment, i.e. the number of addresses accessed by the
+    /// current transaction.
+    AccessedAddressesLen = 29,
+    /// The size of the `AccessedStorageKeys` segment. Since each key takes two words, this is twice
+    /// the number of storage keys accessed by the current transaction.
+    AccessedStorageKeysLen = 30,
+    /// The size of the `SelfDestructList` segment.
+    SelfDestructListLen = 31,
+    /// The size of the `TouchedAddresses` segment.
+    TouchedAddressesLen = 32,
 }
 
 impl GlobalMetadata {
-    pub(crate) const COUNT: usize = 14;
+    pub(crate) const COUNT: usize = 32;
 
     pub(crate) fn all() -> [Self; Self::COUNT] {
         [
@@ -53,6 +94,24 @@ impl GlobalMetadata {
             Self::TransactionTrieRootDigestAfter,
             Self::ReceiptTrieRootDigestAfter,
             Self::TrieEncodedChildSize,
+            Self::TxnStartStateTrieRoot,
+            Self::RefundCounter,
+            Self::CallStackDepth,
+            Self::LogsLen,
+            Self::TxnNumber,
+            Self::BlockGasUsed,
+            Self::BlockBeneficiary,
+            Self::BlockTimestamp,
+            Self::BlockNumber,
+            Self::BlockDifficulty,
+            Self::BlockGasLimit,
+            Self::BlockChainId,
+            Self::BlockBaseFee,
+            Self::BlockCurrentHash,
+            Self::AccessedAddressesLen,
+            Self::AccessedStorageKeysLen,
+            Self::SelfDestructListLen,
+            Self::TouchedAddressesLen,
         ]
     }
 
@@ -81,6 +140,24 @@ impl GlobalMetadata {
                 "GLOBAL_METADATA_RECEIPT_TRIE_DIGEST_AFTER"
             }
             GlobalMetadata::TrieEncodedChildSize => "TRIE_ENCODED_CHILD_SIZE",
+            GlobalMetadata::TxnStartStateTrieRoot => "GLOBAL_METADATA_TXN_START_STATE_TRIE_ROOT",
+            GlobalMetadata::RefundCounter => "GLOBAL_METADATA_REFUND_COUNTER",
+            GlobalMetadata::CallStackDepth => "GLOBAL_METADATA_CALL_STACK_DEPTH",
+            GlobalMetadata::LogsLen => "GLOBAL_METADATA_LOGS_LEN",
+            GlobalMetadata::TxnNumber => "GLOBAL_METADATA_TXN_NUMBER",
+            GlobalMetadata::BlockGasUsed => "GLOBAL_METADATA_BLOCK_GAS_USED",
+            GlobalMetadata::BlockBeneficiary => "GLOBAL_METADATA_BLOCK_BENEFICIARY",
+            GlobalMetadata::BlockTimestamp => "GLOBAL_METADATA_BLOCK_TIMESTAMP",
+            GlobalMetadata::BlockNumber => "GLOBAL_METADATA_BLOCK_NUMBER",
+            GlobalMetadata::BlockDifficulty => "GLOBAL_METADATA_BLOCK_DIFFICULTY",
+            GlobalMetadata::BlockGasLimit => "GLOBAL_METADATA_BLOCK_GAS_LIMIT",
+            GlobalMetadata::BlockChainId => "GLOBAL_METADATA_BLOCK_CHAIN_ID",
+            GlobalMetadata::BlockBaseFee => "GLOBAL_METADATA_BLOCK_BASE_FEE",
+            GlobalMetadata::BlockCurrentHash => "GLOBAL_METADATA_BLOCK_CURRENT_HASH",
+            GlobalMetadata::AccessedAddressesLen => "GLOBAL_METADATA_ACCESSED_ADDRESSES_LEN",
+            GlobalMetadata::AccessedStorageKeysLen => "GLOBAL_METADATA_ACCESSED_STORAGE_KEYS_LEN",
+            GlobalMetadata::SelfDestructListLen => "GLOBAL_METADATA_SELFDESTRUCT_LIST_LEN",
+            GlobalMetadata::TouchedAddressesLen => "GLOBAL_METADATA_TOUCHED_ADDRESSES_LEN",
         }
     }
 }
@@ -28,6 +28,14 @@ pub fn evm_constants() -> HashMap<String, U256> {
         c.insert(name.into(), U256::from(value));
     }
 
+    for (name, value) in PRECOMPILES {
+        c.insert(name.into(), U256::from(value));
+    }
+
+    for (name, value) in PRECOMPILES_GAS {
+        c.insert(name.into(), U256::from(value));
+    }
+
     for segment in Segment::all() {
         c.insert(segment.var_name().into(), (segment as u32).into());
     }
@@ -43,6 +51,7 @@ pub fn evm_constants() -> HashMap<String, U256> {
     for trie_type in PartialTrieType::all() {
         c.insert(trie_type.var_name().into(), (trie_type as u32).into());
     }
+    c.insert("MAX_CODE_SIZE".into(), MAX_CODE_SIZE.into());
     c.insert(
         "INVALID_OPCODES_USER".into(),
         U256::from_little_endian(&invalid_opcodes_user()),
@@ -50,6 +59,9 @@ pub fn evm_constants() -> HashMap<String, U256> {
     c
 }
 
+/// The maximum size of a contract's code, as set by EIP-170.
+const MAX_CODE_SIZE: u32 = 0x6000;
+
 const HASH_CONSTANTS: [(&str, [u8; 32]); 2] = [
     // Hash of an empty string: keccak(b'').hex()
     (
@@ -78,7 +90,7 @@ const EC_CONSTANTS: [(&str, [u8; 32]); 3] = [
     ),
 ];
 
-const GAS_CONSTANTS: [(&str, u16); 36] = [
+const GAS_CONSTANTS: [(&str, u16); 37] = [
     ("GAS_ZERO", 0),
     ("GAS_JUMPDEST", 1),
     ("GAS_BASE", 2),
@@ -93,8 +105,9 @@ const GAS_CONSTANTS: [(&str, u16); 36] = [
     ("GAS_COLDSLOAD", 2_100),
     ("GAS_SSET", 20_000),
     ("GAS_SRESET", 2_900),
-    ("REFUND_SCLEAR", 15_000),
+    ("REFUND_SCLEAR", 4_800),
     ("REFUND_SELFDESTRUCT", 24_000),
+    ("MAX_REFUND_QUOTIENT", 5),
     ("GAS_SELFDESTRUCT", 5_000),
     ("GAS_CREATE", 32_000),
     ("GAS_CODEDEPOSIT", 200),
@@ -116,3 +129,32 @@ const GAS_CONSTANTS: [(&str, u16); 36] = [
     ("GAS_COPY", 3),
     ("GAS_BLOCKHASH", 20),
 ];
+
+/// Addresses of the precompiled contracts.
+const PRECOMPILES: [(&str, u16); 9] = [
+    ("ECREC", 1),
+    ("SHA256", 2),
+    ("RIP160", 3),
+    ("ID", 4),
+    ("EXPMOD", 5),
+    ("BN_ADD", 6),
+    ("BN_MUL", 7),
+    ("SNARKV", 8),
+    ("BLAKE2_F", 9),
+];
+
+/// Gas costs of the precompiled contracts, as set by EIP-1108 and EIP-2565.
+const PRECOMPILES_GAS: [(&str, u16); 12] = [
+    ("ECREC_GAS", 3_000),
+    ("SHA256_STATIC_GAS", 60),
+    ("SHA256_DYNAMIC_GAS", 12),
+    ("RIP160_STATIC_GAS", 600),
+    ("RIP160_DYNAMIC_GAS", 120),
+    ("ID_STATIC_GAS", 15),
+    ("ID_DYNAMIC_GAS", 3),
+    ("EXPMOD_MIN_GAS", 200),
+    ("BN_ADD_GAS", 150),
+    ("BN_MUL_GAS", 6_000),
+    ("SNARKV_STATIC_GAS", 45_000),
+    ("SNARKV_DYNAMIC_GAS", 34_000),
+];
@@ -18,10 +18,18 @@ pub(crate) enum NormalizedTxnField {
     R = 10,
     S = 11,
     Origin = 12,
+    /// The number of addresses in the access list of a type 1 or 2 transaction, counting
+    /// duplicates. Each of them costs `GAS_ACCESSLISTADDRESS` intrinsic gas.
+    AccessListAddresses = 13,
+    /// The number of storage keys in the access list of a type 1 or 2 transaction, counting
+    /// duplicates. Each of them costs `GAS_ACCESSLISTSTORAGE` intrinsic gas.
+    AccessListStorageKeys = 14,
+    /// The EIP-2718 type of the transaction, which is 0 for legacy transactions.
+    Type = 15,
 }
 
 impl NormalizedTxnField {
-    pub(crate) const COUNT: usize = 13;
+    pub(crate) const COUNT: usize = 16;
 
     pub(crate) fn all() -> [Self; Self::COUNT] {
         [
@@ -38,6 +46,9 @@ impl NormalizedTxnField {
             Self::R,
             Self::S,
             Self::Origin,
+            Self::AccessListAddresses,
+            Self::AccessListStorageKeys,
+            Self::Type,
         ]
     }
 
@@ -57,6 +68,9 @@ impl NormalizedTxnField {
             NormalizedTxnField::R => "TXN_FIELD_R",
             NormalizedTxnField::S => "TXN_FIELD_S",
             NormalizedTxnField::Origin => "TXN_FIELD_ORIGIN",
+            NormalizedTxnField::AccessListAddresses => "TXN_FIELD_ACCESS_LIST_ADDRESSES",
+            NormalizedTxnField::AccessListStorageKeys => "TXN_FIELD_ACCESS_LIST_STORAGE_KEYS",
+            NormalizedTxnField::Type => "TXN_FIELD_TYPE",
         }
     }
 }
@@ -1,5 +1,6 @@
 //! An EVM interpreter for testing and debugging purposes.
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use anyhow::{anyhow, bail, ensure};
@@ -7,14 +8,18 @@ use ethereum_types::{U256, U512};
 use keccak_hash::keccak;
 use plonky2::field::goldilocks_field::GoldilocksField;
 
+use crate::bn254_arithmetic::BN_BASE;
 use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::assembler::BYTES_PER_OFFSET;
 use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
 use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
 use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::stack_bounds::MAX_USER_STACK_SIZE;
 use crate::generation::prover_input::ProverInputFn;
 use crate::generation::state::GenerationState;
 use crate::generation::GenerationInputs;
 use crate::memory::segments::Segment;
+use crate::witness::errors::ProgramError;
 use crate::witness::memory::{MemoryAddress, MemoryContextState, MemorySegmentState, MemoryState};
 use crate::witness::util::stack_peek;
 
@@ -35,7 +40,6 @@ impl MemoryState {
 
 pub struct Interpreter<'a> {
     kernel_mode: bool,
-    jumpdests: Vec<usize>,
     pub(crate) context: usize,
     pub(crate) generation_state: GenerationState<F>,
     prover_inputs_map: &'a HashMap<usize, ProverInputFn>,
@@ -86,7 +90,6 @@ impl<'a> Interpreter<'a> {
     ) -> Self {
         let mut result = Self {
             kernel_mode: true,
-            jumpdests: find_jumpdests(code),
             generation_state: GenerationState::new(GenerationInputs::default(), code),
             prover_inputs_map: prover_inputs,
             context: 0,
@@ -116,7 +119,9 @@ impl<'a> Interpreter<'a> {
     }
 
     fn code(&self) -> &MemorySegmentState {
-        &self.generation_state.memory.contexts[self.context].segments[Segment::Code as usize]
+        // The kernel's code lives in context 0.
+        let code_context = if self.kernel_mode { 0 } else { self.context };
+        &self.generation_state.memory.contexts[code_context].segments[Segment::Code as usize]
     }
 
     fn code_slice(&self, n: usize) -> Vec<u8> {
@@ -151,6 +156,20 @@ impl<'a> Interpreter<'a> {
             .set(field as usize, value)
     }
 
+    pub(crate) fn set_context_metadata_field(
+        &mut self,
+        context: usize,
+        field: ContextMetadata,
+        value: U256,
+    ) {
+        self.generation_state.memory.mstore_general(
+            context,
+            Segment::ContextMetadata,
+            field as usize,
+            value,
+        )
+    }
+
     pub(crate) fn get_trie_data(&self) -> &[U256] {
         &self.generation_state.memory.contexts[0].segments[Segment::TrieData as usize].content
     }
@@ -167,6 +186,16 @@ impl<'a> Interpreter<'a> {
             .collect()
     }
 
+    pub(crate) fn get_memory_segment(&self, segment: Segment) -> Vec<U256> {
+        self.generation_state.memory.contexts[0].segments[segment as usize]
+            .content
+            .clone()
+    }
+
+    pub(crate) fn set_memory_segment(&mut self, segment: Segment, memory: Vec<U256>) {
+        self.generation_state.memory.contexts[0].segments[segment as usize].content = memory;
+    }
+
     pub(crate) fn get_rlp_memory(&self) -> Vec<u8> {
         self.get_memory_segment_bytes(Segment::RlpRaw)
     }
@@ -238,26 +267,44 @@ impl<'a> Interpreter<'a> {
             .byte(0);
         self.opcode_count[opcode as usize] += 1;
         self.incr(1);
+
+        // In user mode, the CPU only executes some opcodes natively, charging their gas. The
+        // others are syscalls, for which the kernel charges gas itself. Faults trap to the
+        // kernel's exception handlers.
+        if !self.kernel_mode {
+            if let Some(err) = self.user_exception(opcode) {
+                self.run_exception(err.exc_code());
+                return Ok(());
+            }
+            match native_gas_cost(opcode) {
+                Some(cost) => self.generation_state.registers.gas_used += cost,
+                None => {
+                    self.run_syscall(opcode);
+                    return Ok(());
+                }
+            }
+        }
+
         match opcode {
-            0x00 => self.run_stop(),                                    // "STOP",
+            0x00 => self.run_stop(opcode),                              // "STOP",
             0x01 => self.run_add(),                                     // "ADD",
             0x02 => self.run_mul(),                                     // "MUL",
             0x03 => self.run_sub(),                                     // "SUB",
             0x04 => self.run_div(),                                     // "DIV",
-            0x05 => todo!(),                                            // "SDIV",
+            0x05 => self.run_sdiv(),                                    // "SDIV",
             0x06 => self.run_mod(),                                     // "MOD",
-            0x07 => todo!(),                                            // "SMOD",
+            0x07 => self.run_smod(),                                    // "SMOD",
             0x08 => self.run_addmod(),                                  // "ADDMOD",
             0x09 => self.run_mulmod(),                                  // "MULMOD",
             0x0a => self.run_exp(),                                     // "EXP",
-            0x0b => todo!(),                                            // "SIGNEXTEND",
+            0x0b => self.run_signextend(),                              // "SIGNEXTEND",
             0x0c => self.run_addfp254(),                                // "ADDFP254",
             0x0d => self.run_mulfp254(),                                // "MULFP254",
             0x0e => self.run_subfp254(),                                // "SUBFP254",
             0x10 => self.run_lt(),                                      // "LT",
             0x11 => self.run_gt(),                                      // "GT",
-            0x12 => todo!(),                                            // "SLT",
-            0x13 => todo!(),                                            // "SGT",
+            0x12 => self.run_slt(),                                     // "SLT",
+            0x13 => self.run_sgt(),                                     // "SGT",
             0x14 => self.run_eq(),                                      // "EQ",
             0x15 => self.run_iszero(),                                  // "ISZERO",
             0x16 => self.run_and(),                                     // "AND",
@@ -267,71 +314,72 @@ impl<'a> Interpreter<'a> {
             0x1a => self.run_byte(),                                    // "BYTE",
             0x1b => self.run_shl(),                                     // "SHL",
             0x1c => self.run_shr(),                                     // "SHR",
-            0x1d => todo!(),                                            // "SAR",
+            0x1d => self.run_sar(),                                     // "SAR",
             0x20 => self.run_keccak256(),                               // "KECCAK256",
             0x21 => self.run_keccak_general(),                          // "KECCAK_GENERAL",
-            0x30 => todo!(),                                            // "ADDRESS",
-            0x31 => todo!(),                                            // "BALANCE",
-            0x32 => todo!(),                                            // "ORIGIN",
-            0x33 => todo!(),                                            // "CALLER",
-            0x34 => self.run_callvalue(),                               // "CALLVALUE",
-            0x35 => self.run_calldataload(),                            // "CALLDATALOAD",
-            0x36 => self.run_calldatasize(),                            // "CALLDATASIZE",
-            0x37 => self.run_calldatacopy(),                            // "CALLDATACOPY",
-            0x38 => todo!(),                                            // "CODESIZE",
-            0x39 => todo!(),                                            // "CODECOPY",
-            0x3a => todo!(),                                            // "GASPRICE",
-            0x3b => todo!(),                                            // "EXTCODESIZE",
-            0x3c => todo!(),                                            // "EXTCODECOPY",
-            0x3d => todo!(),                                            // "RETURNDATASIZE",
-            0x3e => todo!(),                                            // "RETURNDATACOPY",
-            0x3f => todo!(),                                            // "EXTCODEHASH",
-            0x40 => todo!(),                                            // "BLOCKHASH",
-            0x41 => todo!(),                                            // "COINBASE",
-            0x42 => todo!(),                                            // "TIMESTAMP",
-            0x43 => todo!(),                                            // "NUMBER",
-            0x44 => todo!(),                                            // "DIFFICULTY",
-            0x45 => todo!(),                                            // "GASLIMIT",
-            0x46 => todo!(),                                            // "CHAINID",
-            0x48 => todo!(),                                            // "BASEFEE",
+            0x30 => self.run_syscall(opcode),                           // "ADDRESS",
+            0x31 => self.run_syscall(opcode),                           // "BALANCE",
+            0x32 => self.run_syscall(opcode),                           // "ORIGIN",
+            0x33 => self.run_syscall(opcode),                           // "CALLER",
+            0x34 => self.run_syscall(opcode),                           // "CALLVALUE",
+            0x35 => self.run_syscall(opcode),                           // "CALLDATALOAD",
+            0x36 => self.run_syscall(opcode),                           // "CALLDATASIZE",
+            0x37 => self.run_syscall(opcode),                           // "CALLDATACOPY",
+            0x38 => self.run_syscall(opcode),                           // "CODESIZE",
+            0x39 => self.run_syscall(opcode),                           // "CODECOPY",
+            0x3a => self.run_syscall(opcode),                           // "GASPRICE",
+            0x3b => self.run_syscall(opcode),                           // "EXTCODESIZE",
+            0x3c => self.run_syscall(opcode),                           // "EXTCODECOPY",
+            0x3d => self.run_syscall(opcode),                           // "RETURNDATASIZE",
+            0x3e => self.run_syscall(opcode),                           // "RETURNDATACOPY",
+            0x3f => self.run_syscall(opcode),                           // "EXTCODEHASH",
+            0x40 => self.run_syscall(opcode),                           // "BLOCKHASH",
+            0x41 => self.run_syscall(opcode),                           // "COINBASE",
+            0x42 => self.run_syscall(opcode),                           // "TIMESTAMP",
+            0x43 => self.run_syscall(opcode),                           // "NUMBER",
+            0x44 => self.run_syscall(opcode),                           // "DIFFICULTY",
+            0x45 => self.run_syscall(opcode),                           // "GASLIMIT",
+            0x46 => self.run_syscall(opcode),                           // "CHAINID",
+            0x47 => self.run_syscall(opcode),                           // "SELFBALANCE",
+            0x48 => self.run_syscall(opcode),                           // "BASEFEE",
             0x49 => self.run_prover_input()?,                           // "PROVER_INPUT",
             0x50 => self.run_pop(),                                     // "POP",
             0x51 => self.run_mload(),                                   // "MLOAD",
             0x52 => self.run_mstore(),                                  // "MSTORE",
             0x53 => self.run_mstore8(),                                 // "MSTORE8",
-            0x54 => todo!(),                                            // "SLOAD",
-            0x55 => todo!(),                                            // "SSTORE",
+            0x54 => self.run_syscall(opcode),                           // "SLOAD",
+            0x55 => self.run_syscall(opcode),                           // "SSTORE",
             0x56 => self.run_jump(),                                    // "JUMP",
             0x57 => self.run_jumpi(),                                   // "JUMPI",
             0x58 => self.run_pc(),                                      // "PC",
             0x59 => self.run_msize(),                                   // "MSIZE",
-            0x5a => todo!(),                                            // "GAS",
+            0x5a => self.run_gas(),                                     // "GAS",
             0x5b => self.run_jumpdest(),                                // "JUMPDEST",
             x if (0x60..0x80).contains(&x) => self.run_push(x - 0x5f),  // "PUSH"
             x if (0x80..0x90).contains(&x) => self.run_dup(x - 0x7f),   // "DUP"
             x if (0x90..0xa0).contains(&x) => self.run_swap(x - 0x8f)?, // "SWAP"
-            0xa0 => todo!(),                                            // "LOG0",
-            0xa1 => todo!(),                                            // "LOG1",
-            0xa2 => todo!(),                                            // "LOG2",
-            0xa3 => todo!(),                                            // "LOG3",
-            0xa4 => todo!(),                                            // "LOG4",
+            0xa0 => self.run_syscall(opcode),                           // "LOG0",
+            0xa1 => self.run_syscall(opcode),                           // "LOG1",
+            0xa2 => self.run_syscall(opcode),                           // "LOG2",
+            0xa3 => self.run_syscall(opcode),                           // "LOG3",
+            0xa4 => self.run_syscall(opcode),                           // "LOG4",
             0xa5 => bail!("Executed PANIC"),                            // "PANIC",
-            0xf0 => todo!(),                                            // "CREATE",
-            0xf1 => todo!(),                                            // "CALL",
-            0xf2 => todo!(),                                            // "CALLCODE",
-            0xf3 => todo!(),                                            // "RETURN",
-            0xf4 => todo!(),                                            // "DELEGATECALL",
-            0xf5 => todo!(),                                            // "CREATE2",
+            0xf0 => self.run_syscall(opcode),                           // "CREATE",
+            0xf1 => self.run_syscall(opcode),                           // "CALL",
+            0xf2 => self.run_syscall(opcode),                           // "CALLCODE",
+            0xf3 => self.run_syscall(opcode),                           // "RETURN",
+            0xf4 => self.run_syscall(opcode),                           // "DELEGATECALL",
+            0xf5 => self.run_syscall(opcode),                           // "CREATE2",
             0xf6 => self.run_get_context(),                             // "GET_CONTEXT",
             0xf7 => self.run_set_context(),                             // "SET_CONTEXT",
-            0xf8 => todo!(),                                            // "CONSUME_GAS",
-            0xf9 => todo!(),                                            // "EXIT_KERNEL",
-            0xfa => todo!(),                                            // "STATICCALL",
+            0xf8 => self.run_consume_gas(),                             // "CONSUME_GAS",
+            0xf9 => self.run_exit_kernel(),                             // "EXIT_KERNEL",
+            0xfa => self.run_syscall(opcode),                           // "STATICCALL",
             0xfb => self.run_mload_general(),                           // "MLOAD_GENERAL",
             0xfc => self.run_mstore_general(),                          // "MSTORE_GENERAL",
-            0xfd => todo!(),                                            // "REVERT",
+            0xfd => self.run_syscall(opcode),                           // "REVERT",
             0xfe => bail!("Executed INVALID"),                          // "INVALID",
-            0xff => todo!(),                                            // "SELFDESTRUCT",
+            0xff => self.run_syscall(opcode),                           // "SELFDESTRUCT",
             _ => bail!("Unrecognized opcode {}.", opcode),
         };
 
@@ -355,8 +403,13 @@ impl<'a> Interpreter<'a> {
         KERNEL.offset_label(self.generation_state.registers.program_counter)
     }
 
-    fn run_stop(&mut self) {
-        self.running = false;
+    fn run_stop(&mut self, opcode: u8) {
+        if self.kernel_mode {
+            self.running = false;
+        } else {
+            // In user mode, STOP returns control to the parent context.
+            self.run_syscall(opcode);
+        }
     }
 
     fn run_add(&mut self) {
@@ -377,25 +430,22 @@ impl<'a> Interpreter<'a> {
         self.push(x.overflowing_sub(y).0);
     }
 
-    // TODO: 107 is hardcoded as a dummy prime for testing
-    // should be changed to the proper implementation prime
-
     fn run_addfp254(&mut self) {
-        let x = self.pop();
-        let y = self.pop();
-        self.push((x + y) % 107);
+        let x = U512::from(self.pop());
+        let y = U512::from(self.pop());
+        self.push(U256::try_from((x + y) % BN_BASE).unwrap());
     }
 
     fn run_mulfp254(&mut self) {
         let x = self.pop();
         let y = self.pop();
-        self.push(U256::try_from(x.full_mul(y) % 107).unwrap());
+        self.push(U256::try_from(x.full_mul(y) % BN_BASE).unwrap());
     }
 
     fn run_subfp254(&mut self) {
-        let x = self.pop();
-        let y = self.pop();
-        self.push((U256::from(107) + x - y) % 107);
+        let x = U512::from(self.pop());
+        let y = U512::from(self.pop()) % BN_BASE;
+        self.push(U256::try_from((x + BN_BASE - y) % BN_BASE).unwrap());
     }
 
     fn run_div(&mut self) {
@@ -404,12 +454,39 @@ impl<'a> Interpreter<'a> {
         self.push(if y.is_zero() { U256::zero() } else { x / y });
     }
 
+    fn run_sdiv(&mut self) {
+        let (abs_x, x_neg) = signed_abs(self.pop());
+        let (abs_y, y_neg) = signed_abs(self.pop());
+        let quotient = if abs_y.is_zero() {
+            U256::zero()
+        } else {
+            abs_x / abs_y
+        };
+        self.push(if x_neg != y_neg {
+            negate(quotient)
+        } else {
+            quotient
+        });
+    }
+
     fn run_mod(&mut self) {
         let x = self.pop();
         let y = self.pop();
         self.push(if y.is_zero() { U256::zero() } else { x % y });
     }
 
+    fn run_smod(&mut self) {
+        let (abs_x, x_neg) = signed_abs(self.pop());
+        let (abs_y, _) = signed_abs(self.pop());
+        let remainder = if abs_y.is_zero() {
+            U256::zero()
+        } else {
+            abs_x % abs_y
+        };
+        // The result takes the sign of the dividend.
+        self.push(if x_neg { negate(remainder) } else { remainder });
+    }
+
     fn run_addmod(&mut self) {
         let x = U512::from(self.pop());
         let y = U512::from(self.pop());
@@ -438,6 +515,18 @@ impl<'a> Interpreter<'a> {
         self.push(x.overflowing_pow(y).0);
     }
 
+    fn run_signextend(&mut self) {
+        let b = self.pop();
+        let x = self.pop();
+        if b >= 31.into() {
+            self.push(x);
+            return;
+        }
+        let sign_bit = b.as_usize() * 8 + 7;
+        let mask = (U256::one() << (sign_bit + 1)) - 1;
+        self.push(if x.bit(sign_bit) { x | !mask } else { x & mask });
+    }
+
     fn run_lt(&mut self) {
         let x = self.pop();
         let y = self.pop();
@@ -450,6 +539,18 @@ impl<'a> Interpreter<'a> {
         self.push_bool(x > y);
     }
 
+    fn run_slt(&mut self) {
+        let x = self.pop();
+        let y = self.pop();
+        self.push_bool(signed_cmp(x, y) == Ordering::Less);
+    }
+
+    fn run_sgt(&mut self) {
+        let x = self.pop();
+        let y = self.pop();
+        self.push_bool(signed_cmp(x, y) == Ordering::Greater);
+    }
+
     fn run_eq(&mut self) {
         let x = self.pop();
         let y = self.pop();
@@ -498,13 +599,33 @@ impl<'a> Interpreter<'a> {
     fn run_shl(&mut self) {
         let shift = self.pop();
         let value = self.pop();
-        self.push(value << shift);
+        self.push(if shift < 256.into() {
+            value << shift
+        } else {
+            U256::zero()
+        });
     }
 
     fn run_shr(&mut self) {
         let shift = self.pop();
         let value = self.pop();
-        self.push(value >> shift);
+        self.push(if shift < 256.into() {
+            value >> shift
+        } else {
+            U256::zero()
+        });
+    }
+
+    fn run_sar(&mut self) {
+        let shift = self.pop();
+        let value = self.pop();
+        // Shifting by 255 or more leaves only copies of the sign bit.
+        let shift = shift.min(255.into()).as_usize();
+        self.push(if value.bit(255) {
+            !(!value >> shift)
+        } else {
+            value >> shift
+        });
     }
 
     fn run_keccak256(&mut self) {
@@ -525,8 +646,6 @@ impl<'a> Interpreter<'a> {
     fn run_keccak_general(&mut self) {
         let context = self.pop().as_usize();
         let segment = Segment::all()[self.pop().as_usize()];
-        // Not strictly needed but here to avoid surprises with MSIZE.
-        assert_ne!(segment, Segment::MainMemory, "Call KECCAK256 instead.");
         let offset = self.pop().as_usize();
         let size = self.pop().as_usize();
         let bytes = (offset..offset + size)
@@ -542,56 +661,6 @@ impl<'a> Interpreter<'a> {
         self.push(U256::from_big_endian(hash.as_bytes()));
     }
 
-    fn run_callvalue(&mut self) {
-        self.push(
-            self.generation_state.memory.contexts[self.context].segments
-                [Segment::ContextMetadata as usize]
-                .get(ContextMetadata::CallValue as usize),
-        )
-    }
-
-    fn run_calldataload(&mut self) {
-        let offset = self.pop().as_usize();
-        let value = U256::from_big_endian(
-            &(0..32)
-                .map(|i| {
-                    self.generation_state
-                        .memory
-                        .mload_general(self.context, Segment::Calldata, offset + i)
-                        .byte(0)
-                })
-                .collect::<Vec<_>>(),
-        );
-        self.push(value);
-    }
-
-    fn run_calldatasize(&mut self) {
-        self.push(
-            self.generation_state.memory.contexts[self.context].segments
-                [Segment::ContextMetadata as usize]
-                .get(ContextMetadata::CalldataSize as usize),
-        )
-    }
-
-    fn run_calldatacopy(&mut self) {
-        let dest_offset = self.pop().as_usize();
-        let offset = self.pop().as_usize();
-        let size = self.pop().as_usize();
-        for i in 0..size {
-            let calldata_byte = self.generation_state.memory.mload_general(
-                self.context,
-                Segment::Calldata,
-                offset + i,
-            );
-            self.generation_state.memory.mstore_general(
-                self.context,
-                Segment::MainMemory,
-                dest_offset + i,
-                calldata_byte,
-            );
-        }
-    }
-
     fn run_prover_input(&mut self) -> anyhow::Result<()> {
         let prover_input_fn = self
             .prover_inputs_map
@@ -672,13 +741,70 @@ impl<'a> Interpreter<'a> {
         )
     }
 
+    fn gas_limit(&self) -> U256 {
+        self.generation_state.memory.mload_general(
+            self.context,
+            Segment::ContextMetadata,
+            ContextMetadata::GasLimit as usize,
+        )
+    }
+
+    /// The exception, if any, which the CPU raises when executing the given opcode in user mode.
+    /// These are checked in the same order as in the CPU.
+    fn user_exception(&self, opcode: u8) -> Option<ProgramError> {
+        let cost = match native_gas_cost(opcode) {
+            Some(cost) => cost,
+            // Opcodes which are neither executed natively nor implemented by a syscall are invalid.
+            None if handler_addr("syscall_jumptable", opcode) == KERNEL.global_labels["panic"] => {
+                return Some(ProgramError::InvalidOpcode)
+            }
+            None => return None,
+        };
+        if U256::from(self.generation_state.registers.gas_used + cost) > self.gas_limit() {
+            return Some(ProgramError::OutOfGas);
+        }
+
+        let stack = self.stack();
+        if stack.len() < native_min_stack_len(opcode) {
+            return Some(ProgramError::StackUnderflow);
+        }
+        let top = |i: usize| stack[stack.len() - 1 - i];
+        match opcode {
+            0x56 if !self.is_jumpdest(top(0)) => Some(ProgramError::InvalidJumpDestination),
+            0x57 if !top(1).is_zero() && !self.is_jumpdest(top(0)) => {
+                Some(ProgramError::InvalidJumpiDestination)
+            }
+            0x58 | 0x5a | 0x60..=0x8f if stack.len() >= MAX_USER_STACK_SIZE => {
+                Some(ProgramError::StackOverflow)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_jumpdest(&self, offset: U256) -> bool {
+        let code = self
+            .code()
+            .content
+            .iter()
+            .map(|byte| byte.byte(0))
+            .collect::<Vec<_>>();
+        offset <= U256::from(u32::MAX)
+            && find_jumpdests(&code)
+                .binary_search(&offset.as_usize())
+                .is_ok()
+    }
+
+    fn run_gas(&mut self) {
+        self.push(self.gas_limit() - self.generation_state.registers.gas_used);
+    }
+
     fn run_jumpdest(&mut self) {
         assert!(!self.kernel_mode, "JUMPDEST is not needed in kernel code");
     }
 
     fn jump_to(&mut self, offset: usize) {
         // The JUMPDEST rule is not enforced in kernel mode.
-        if !self.kernel_mode && self.jumpdests.binary_search(&offset).is_err() {
+        if !self.kernel_mode && !self.is_jumpdest(offset.into()) {
             panic!("Destination is not a JUMPDEST.");
         }
 
@@ -711,8 +837,91 @@ impl<'a> Interpreter<'a> {
     }
 
     fn run_set_context(&mut self) {
-        let x = self.pop();
-        self.context = x.as_usize();
+        let new_context = self.pop().as_usize();
+        // Each context has its own stack, so we save the length of the old context's stack and
+        // restore that of the new context.
+        let stack_size_field = ContextMetadata::StackSize as usize;
+        self.generation_state.memory.mstore_general(
+            self.context,
+            Segment::ContextMetadata,
+            stack_size_field,
+            self.stack_len().into(),
+        );
+        let new_stack_size = self.generation_state.memory.mload_general(
+            new_context,
+            Segment::ContextMetadata,
+            stack_size_field,
+        );
+
+        // The same goes for the gas used by each context.
+        let gas_used_field = ContextMetadata::GasUsed as usize;
+        self.generation_state.memory.mstore_general(
+            self.context,
+            Segment::ContextMetadata,
+            gas_used_field,
+            self.generation_state.registers.gas_used.into(),
+        );
+        let new_gas_used = self.generation_state.memory.mload_general(
+            new_context,
+            Segment::ContextMetadata,
+            gas_used_field,
+        );
+
+        self.context = new_context;
+        self.generation_state.registers.context = new_context;
+        self.generation_state.registers.stack_len = new_stack_size.as_usize();
+        self.generation_state.registers.gas_used = new_gas_used.as_u64();
+    }
+
+    fn run_consume_gas(&mut self) {
+        let amount = self.pop().as_u64();
+        self.generation_state.registers.gas_used += amount;
+    }
+
+    /// Handle an opcode which is implemented in the kernel, by pushing `kexit_info` and jumping to
+    /// the appropriate handler, as the CPU does.
+    fn run_syscall(&mut self, opcode: u8) {
+        let handler_addr = handler_addr("syscall_jumptable", opcode);
+
+        let kexit_info = U256::from(self.generation_state.registers.program_counter)
+            + (U256::from(self.kernel_mode as u64) << 32);
+        self.push(kexit_info);
+
+        self.kernel_mode = true;
+        self.generation_state.registers.is_kernel = true;
+        self.generation_state.registers.program_counter = handler_addr;
+    }
+
+    /// Handle an exception raised in user mode, by pushing `trap_info` and jumping to the
+    /// appropriate handler, as the CPU does.
+    fn run_exception(&mut self, exc_code: u8) {
+        let handler_addr = handler_addr("exception_jumptable", exc_code);
+
+        // The program counter has already been incremented, but `trap_info` points at the
+        // faulting instruction.
+        let trap_info = U256::from(self.generation_state.registers.program_counter - 1)
+            + (U256::from(self.kernel_mode as u64) << 32)
+            + (U256::from(self.stack_len()) << 64);
+        self.push(trap_info);
+
+        self.kernel_mode = true;
+        self.generation_state.registers.is_kernel = true;
+        self.generation_state.registers.program_counter = handler_addr;
+    }
+
+    fn run_exit_kernel(&mut self) {
+        let kexit_info = self.pop();
+        let program_counter = kexit_info.low_u32() as usize;
+        let is_kernel_mode_val = (kexit_info >> 32).low_u32();
+        assert!(is_kernel_mode_val == 0 || is_kernel_mode_val == 1);
+        self.kernel_mode = is_kernel_mode_val != 0;
+
+        self.generation_state.registers.program_counter = program_counter;
+        self.generation_state.registers.is_kernel = self.kernel_mode;
+
+        if self.halt_offsets.contains(&program_counter) {
+            self.running = false;
+        }
     }
 
     fn run_mload_general(&mut self) {
@@ -742,6 +951,64 @@ impl<'a> Interpreter<'a> {
     }
 }
 
+/// Reads the handler address at the given index of one of the kernel's jump tables.
+fn handler_addr(jumptable: &str, index: u8) -> usize {
+    let handler_addr_addr =
+        KERNEL.global_labels[jumptable] + (index as usize) * BYTES_PER_OFFSET as usize;
+    KERNEL.code[handler_addr_addr..handler_addr_addr + BYTES_PER_OFFSET as usize]
+        .iter()
+        .fold(0, |acc, &byte| (acc << 8) + byte as usize)
+}
+
+/// The gas cost of an opcode which the CPU executes natively in user mode, or `None` if the opcode
+/// is handled by a syscall.
+fn native_gas_cost(opcode: u8) -> Option<u64> {
+    match opcode {
+        0x01 | 0x03 | 0x10 | 0x11 | 0x14..=0x1c => Some(3), // ADD, SUB, comparisons and bitwise ops
+        0x02 | 0x04 | 0x06 => Some(5),                      // MUL, DIV, MOD
+        0x08 | 0x09 => Some(8),                             // ADDMOD, MULMOD
+        0x50 | 0x58 | 0x5a => Some(2),                      // POP, PC, GAS
+        0x56 => Some(8),                                    // JUMP
+        0x57 => Some(10),                                   // JUMPI
+        0x5b => Some(1),                                    // JUMPDEST
+        0x60..=0x9f => Some(3),                             // PUSH, DUP, SWAP
+        _ => None,
+    }
+}
+
+/// The minimum stack length for an opcode which the CPU executes natively in user mode, below which
+/// it underflows.
+fn native_min_stack_len(opcode: u8) -> usize {
+    match opcode {
+        0x01..=0x04 | 0x06 | 0x10 | 0x11 | 0x14 | 0x16..=0x18 | 0x1a..=0x1c => 2, // binary ops
+        0x08 | 0x09 => 3,                                                         // ADDMOD, MULMOD
+        0x15 | 0x19 | 0x50 | 0x56 => 1, // ISZERO, NOT, POP, JUMP
+        0x57 => 2,                      // JUMPI
+        0x80..=0x8f => (opcode - 0x7f) as usize, // DUP
+        0x90..=0x9f => (opcode - 0x8e) as usize, // SWAP
+        _ => 0,
+    }
+}
+
+/// Return the two's complement negation of `x`, i.e. `2^256 - x`.
+fn negate(x: U256) -> U256 {
+    U256::zero().overflowing_sub(x).0
+}
+
+/// Interpret `x` as a two's complement integer, and return its absolute value along with whether
+/// it is negative. Note that the absolute value of `-2^255` is `2^255` as an unsigned integer.
+fn signed_abs(x: U256) -> (U256, bool) {
+    let is_neg = x.bit(255);
+    (if is_neg { negate(x) } else { x }, is_neg)
+}
+
+/// Compare `x` and `y` as two's complement integers.
+fn signed_cmp(x: U256, y: U256) -> Ordering {
+    // Flipping the sign bit maps two's complement integers onto unsigned ones, preserving order.
+    let sign_bit = U256::one() << 255;
+    (x ^ sign_bit).cmp(&(y ^ sign_bit))
+}
+
 /// Return the (ordered) JUMPDEST offsets in the code.
 fn find_jumpdests(code: &[u8]) -> Vec<usize> {
     let mut offset = 0;
@@ -814,6 +1081,7 @@ fn get_mnemonic(opcode: u8) -> &'static str {
         0x44 => "DIFFICULTY",
         0x45 => "GASLIMIT",
         0x46 => "CHAINID",
+        0x47 => "SELFBALANCE",
         0x48 => "BASEFEE",
         0x49 => "PROVER_INPUT",
         0x50 => "POP",
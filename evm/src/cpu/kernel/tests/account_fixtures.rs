@@ -0,0 +1,195 @@
+//! Fixtures for the tests which run kernel code against a state trie of test accounts.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use keccak_hash::keccak;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::generation::mpt::{all_mpt_prover_inputs_reversed, AccountRlp};
+use crate::generation::TrieInputs;
+use crate::memory::segments::Segment;
+
+/// Exit to 0xdeadbeef, staying in kernel mode so that we can keep running kernel code.
+pub(crate) const KEXIT_INFO: u64 = 0xdeadbeef | (1 << 32);
+
+#[derive(Clone)]
+pub(crate) struct TestAccount {
+    pub(crate) address: Address,
+    pub(crate) nonce: u64,
+    pub(crate) balance: u64,
+    pub(crate) code: Vec<u8>,
+    pub(crate) storage: Vec<(U256, U256)>,
+}
+
+impl TestAccount {
+    /// An account at a random address, with the given nonce, balance and code, and no storage.
+    pub(crate) fn new(nonce: u64, balance: u64, code: Vec<u8>) -> Self {
+        Self {
+            address: thread_rng().gen(),
+            nonce,
+            balance,
+            code,
+            storage: vec![],
+        }
+    }
+
+    /// An account with a zero nonce, a zero balance and no code.
+    pub(crate) fn empty() -> Self {
+        Self::new(0, 0, vec![])
+    }
+
+    pub(crate) fn storage_trie(&self) -> PartialTrie {
+        let mut trie = PartialTrie::Empty;
+        for &(slot, value) in &self.storage {
+            let mut bytes = [0; 32];
+            slot.to_big_endian(&mut bytes);
+            trie.insert(Nibbles::from(keccak(bytes)), rlp::encode(&value).to_vec());
+        }
+        trie
+    }
+}
+
+pub(crate) fn state_trie(accounts: &[TestAccount]) -> PartialTrie {
+    let mut trie = PartialTrie::Empty;
+    for account in accounts {
+        let account_rlp = AccountRlp {
+            nonce: account.nonce.into(),
+            balance: account.balance.into(),
+            storage_root: account.storage_trie().calc_hash(),
+            code_hash: keccak(&account.code),
+        };
+        trie.insert(
+            Nibbles::from(keccak(account.address)),
+            rlp::encode(&account_rlp).to_vec(),
+        );
+    }
+    trie
+}
+
+/// Load a state trie containing the given accounts, along with their code.
+pub(crate) fn load_accounts(accounts: &[TestAccount]) -> Result<Interpreter<'static>> {
+    let mut interpreter = load_tries(&TrieInputs {
+        state_trie: state_trie(accounts),
+        transactions_trie: Default::default(),
+        receipts_trie: Default::default(),
+        storage_tries: accounts
+            .iter()
+            .map(|account| (account.address, account.storage_trie()))
+            .collect(),
+    })?;
+    interpreter.generation_state.inputs.contract_code = accounts
+        .iter()
+        .map(|account| (keccak(&account.code), account.code.clone()))
+        .collect::<HashMap<_, _>>();
+    Ok(interpreter)
+}
+
+/// Load the given receipts trie, along with empty state and transaction tries.
+pub(crate) fn load_receipts_trie(receipts_trie: PartialTrie) -> Result<Interpreter<'static>> {
+    load_tries(&TrieInputs {
+        state_trie: Default::default(),
+        transactions_trie: Default::default(),
+        receipts_trie,
+        storage_tries: vec![],
+    })
+}
+
+fn load_tries(trie_inputs: &TrieInputs) -> Result<Interpreter<'static>> {
+    let load_all_mpts = KERNEL.global_labels["load_all_mpts"];
+    let mut interpreter = Interpreter::new_with_kernel(load_all_mpts, vec![0xDEADBEEFu32.into()]);
+    interpreter.generation_state.mpt_prover_inputs = all_mpt_prover_inputs_reversed(trie_inputs);
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+    Ok(interpreter)
+}
+
+/// Load a state trie containing the given accounts, and make the first one the current account of
+/// context 0, with the given amount of gas, so that kernel code running in context 0 can act on its
+/// behalf.
+pub(crate) fn prepare_interpreter(
+    accounts: &[TestAccount],
+    initial_gas: u64,
+) -> Result<Interpreter<'static>> {
+    let mut interpreter = load_accounts(accounts)?;
+    interpreter.set_context_metadata_field(
+        0,
+        ContextMetadata::Address,
+        address_to_u256(accounts[0].address),
+    );
+    interpreter.set_context_metadata_field(0, ContextMetadata::GasLimit, initial_gas.into());
+    let state_trie_root = interpreter.get_global_metadata_field(GlobalMetadata::StateTrieRoot);
+    interpreter.set_global_metadata_field(GlobalMetadata::TxnStartStateTrieRoot, state_trie_root);
+    Ok(interpreter)
+}
+
+/// Run the given syscall from context 0, and return its single result.
+pub(crate) fn run_syscall(
+    interpreter: &mut Interpreter,
+    syscall: &str,
+    args: &[U256],
+) -> Result<U256> {
+    execute_syscall(interpreter, syscall, args)?;
+    assert_eq!(interpreter.stack().len(), 1);
+    Ok(interpreter.pop())
+}
+
+/// Run the given syscall from context 0, for one which doesn't return anything.
+pub(crate) fn run_void_syscall(
+    interpreter: &mut Interpreter,
+    syscall: &str,
+    args: &[U256],
+) -> Result<()> {
+    execute_syscall(interpreter, syscall, args)?;
+    assert_eq!(interpreter.stack(), vec![]);
+    Ok(())
+}
+
+fn execute_syscall(interpreter: &mut Interpreter, syscall: &str, args: &[U256]) -> Result<()> {
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels[syscall];
+    for &arg in args.iter().rev() {
+        interpreter.push(arg);
+    }
+    interpreter.push(KEXIT_INFO.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.context, 0);
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::CallStackDepth),
+        0.into()
+    );
+    Ok(())
+}
+
+/// The gas used so far by context 0, taking refunds into account, given the gas it started with.
+pub(crate) fn gas_used(interpreter: &Interpreter, initial_gas: u64) -> U256 {
+    let gas_limit = interpreter.generation_state.memory.contexts[0].segments
+        [Segment::ContextMetadata as usize]
+        .get(ContextMetadata::GasLimit as usize);
+    let gas_left = gas_limit - interpreter.generation_state.registers.gas_used;
+    U256::from(initial_gas) - gas_left
+}
+
+pub(crate) fn hash_state_trie(interpreter: &mut Interpreter) -> Result<H256> {
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["mpt_hash_state_trie"];
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack().len(), 1);
+    Ok(H256::from_uint(&interpreter.pop()))
+}
+
+pub(crate) fn address_to_u256(address: Address) -> U256 {
+    U256::from_big_endian(address.as_bytes())
+}
+
+pub(crate) fn main_memory(interpreter: &Interpreter, range: std::ops::Range<usize>) -> Vec<u8> {
+    let memory =
+        &interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize];
+    range.map(|i| memory.get(i).byte(0)).collect()
+}
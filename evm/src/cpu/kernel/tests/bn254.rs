@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use ethereum_types::U256;
+use rand::{thread_rng, Rng};
+
+use crate::bn254_arithmetic::Fp12;
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::u256ify;
+use crate::memory::segments::Segment;
+
+/// The order of G1 and G2.
+const BN_SCALAR: &str = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+/// The generator of G1, and its double.
+const G1: [&str; 2] = ["0x1", "0x2"];
+const G1_DOUBLE: [&str; 2] = [
+    "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3",
+    "0x15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+];
+
+/// The generator of G2, and its double, as points (x, x_, y, y_) of the twisted curve.
+const G2: [&str; 4] = [
+    "0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+    "0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+    "0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+    "0x90689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+];
+const G2_DOUBLE: [&str; 4] = [
+    "0x27dc7234fd11d3e8c36c59277c3e6f149d5cd3cfa9a62aee49f8130962b4b3b9",
+    "0x203e205db4f19b37b60121b83a7333706db86431c6d835849957ed8c3928ad79",
+    "0x4bb53b8977e5f92a0bc372742c4830944a59b4fe6b1c0466e2a6dad122b5d2e",
+    "0x195e8aa5b7827463722b8c153931579d3505566b4edf48d498e185f0509de152",
+];
+
+/// A point of the twisted curve outside of G2.
+const NOT_G2: [&str; 4] = [
+    "0x5",
+    "0x7",
+    "0xb7acc216a621e5c67c9b30166500219eea2ccfe4316164ae4e33e83815be26a",
+    "0x11149b219bf0a6f98c9c7f2f68085bef34811df05e24592c10b170ce7fcc729b",
+];
+
+/// Runs the kernel function `fn_label` on the given stack, which should not include the return
+/// address, with the given contents of kernel memory. Returns the final stack and kernel memory.
+fn run_with_memory(
+    fn_label: &str,
+    stack: Vec<U256>,
+    memory: Vec<U256>,
+) -> Result<(Vec<U256>, Vec<U256>)> {
+    let mut initial_stack = stack;
+    initial_stack.push(0xdeadbeefu32.into());
+    initial_stack.reverse();
+    let mut interpreter =
+        Interpreter::new_with_kernel(KERNEL.global_labels[fn_label], initial_stack);
+    interpreter.set_memory_segment(Segment::KernelGeneral, memory);
+    interpreter.run()?;
+    let mut final_stack = interpreter.stack().to_vec();
+    final_stack.reverse();
+    Ok((
+        final_stack,
+        interpreter.get_memory_segment(Segment::KernelGeneral),
+    ))
+}
+
+/// Runs a function which maps the Fp12 element at `in` to one at `out`.
+fn run_fp12_map(fn_label: &str, f: Fp12) -> Result<Fp12> {
+    let (stack, memory) = run_with_memory(fn_label, vec![0.into(), 12.into()], f.to_words())?;
+    assert_eq!(stack, vec![]);
+    Ok(Fp12::from_words(&memory[12..24]))
+}
+
+#[test]
+fn test_frob_fp12() -> Result<()> {
+    let f: Fp12 = thread_rng().gen();
+    assert_eq!(run_fp12_map("frob_fp12_1", f)?, f.frob(1));
+    assert_eq!(run_fp12_map("frob_fp12_2", f)?, f.frob(2));
+    assert_eq!(run_fp12_map("frob_fp12_3", f)?, f.frob(3));
+    Ok(())
+}
+
+#[test]
+fn test_inv_fp12() -> Result<()> {
+    let f: Fp12 = thread_rng().gen();
+    let inverse = run_fp12_map("inv_fp12", f)?;
+    assert_eq!(inverse, f.inv());
+    assert_eq!(inverse * f, Fp12::UNIT);
+    Ok(())
+}
+
+#[test]
+fn test_pow_fp12() -> Result<()> {
+    let mut rng = thread_rng();
+    let f: Fp12 = rng.gen();
+    let e = U256::from(rng.gen::<u64>());
+    let (stack, memory) = run_with_memory("pow_fp12", vec![0.into(), e, 12.into()], f.to_words())?;
+    assert_eq!(stack, vec![]);
+    assert_eq!(Fp12::from_words(&memory[12..24]), f.pow(e));
+    Ok(())
+}
+
+fn twisted_in_subgroup(q: [&str; 4]) -> Result<U256> {
+    let (stack, _) = run_with_memory("bn254_twisted_in_subgroup", u256ify(q)?, vec![])?;
+    assert_eq!(stack.len(), 1);
+    Ok(stack[0])
+}
+
+#[test]
+fn test_twisted_in_subgroup() -> Result<()> {
+    assert_eq!(twisted_in_subgroup(G2)?, U256::one());
+    assert_eq!(twisted_in_subgroup(G2_DOUBLE)?, U256::one());
+    assert_eq!(twisted_in_subgroup(NOT_G2)?, U256::zero());
+    Ok(())
+}
+
+/// The reduced pairing of P in G1 and Q in G2, from the Miller loop and the final exponentiation.
+fn pairing(p: [&str; 2], q: [&str; 4]) -> Result<Fp12> {
+    let mut stack = u256ify(p)?;
+    stack.extend(u256ify(q)?);
+    stack.push(600.into());
+    let (stack, memory) = run_with_memory("bn254_miller", stack, vec![])?;
+    assert_eq!(stack, vec![]);
+
+    let (stack, memory) = run_with_memory("bn254_final_exponent", vec![600.into()], memory)?;
+    assert_eq!(stack, vec![]);
+    Ok(Fp12::from_words(&memory[600..612]))
+}
+
+#[test]
+fn test_pairing() -> Result<()> {
+    let e = pairing(G1_DOUBLE, G2)?;
+    assert_ne!(e, Fp12::UNIT);
+    assert_eq!(e.pow(U256::from_str(BN_SCALAR)?), Fp12::UNIT);
+    assert_eq!(pairing(G1, G2_DOUBLE)?, e);
+    assert_eq!(pairing(G1, G2)?.pow(2.into()), e);
+    Ok(())
+}
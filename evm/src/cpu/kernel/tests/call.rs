@@ -0,0 +1,328 @@
+use anyhow::Result;
+use ethereum_types::U256;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::tests::account_fixtures::{
+    address_to_u256, hash_state_trie, main_memory, prepare_interpreter, run_syscall, state_trie,
+    TestAccount, KEXIT_INFO,
+};
+use crate::memory::segments::Segment;
+
+const CALLER_BALANCE: u64 = 1000;
+
+const INITIAL_GAS: u64 = 1_000_000;
+
+/// An account with a nonce of 1, a balance of `CALLER_BALANCE` and the given code.
+fn account(code: Vec<u8>) -> TestAccount {
+    TestAccount::new(1, CALLER_BALANCE, code)
+}
+
+/// Code which stores 1 in slot 0, then reverts with the given word as its return data.
+fn sstore_then_revert_code(word: [u8; 32]) -> Vec<u8> {
+    let mut code = vec![
+        0x60, 0x01, 0x60, 0x00, 0x55, // SSTORE(0, 1)
+        0x7f, // PUSH32 word
+    ];
+    code.extend(word);
+    code.extend([
+        0x60, 0x00, 0x52, // MSTORE(0, word)
+        0x60, 0x20, 0x60, 0x00, 0xfd, // REVERT(0, 32)
+    ]);
+    code
+}
+
+#[test]
+fn test_call_return() -> Result<()> {
+    // Returns the first word of its calldata.
+    let echo_code = vec![
+        0x60, 0x00, 0x35, // CALLDATALOAD(0)
+        0x60, 0x00, 0x52, // MSTORE(0, calldata[0..32])
+        0x60, 0x20, 0x60, 0x00, 0xf3, // RETURN(0, 32)
+    ];
+    let caller = account(vec![]);
+    let callee = account(echo_code);
+    let mut interpreter = prepare_interpreter(&[caller, callee.clone()], INITIAL_GAS)?;
+
+    let word: [u8; 32] = thread_rng().gen();
+    interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize]
+        .content = word.into_iter().map(U256::from).collect();
+
+    // Only the first 16 bytes of the return data fit in the output region.
+    let args = [
+        100_000.into(),                  // gas
+        address_to_u256(callee.address), // address
+        0.into(),                        // value
+        0.into(),                        // args_offset
+        32.into(),                       // args_size
+        64.into(),                       // ret_offset
+        16.into(),                       // ret_size
+    ];
+    assert_eq!(run_syscall(&mut interpreter, "sys_call", &args)?, 1.into());
+    assert_eq!(main_memory(&interpreter, 64..80), word[..16]);
+    assert_eq!(main_memory(&interpreter, 80..96), [0; 16]);
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+        32.into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_call_revert() -> Result<()> {
+    let word: [u8; 32] = thread_rng().gen();
+    let caller = account(vec![]);
+    let callee = account(sstore_then_revert_code(word));
+    let accounts = [caller, callee.clone()];
+    let mut interpreter = prepare_interpreter(&accounts, INITIAL_GAS)?;
+
+    let args = [
+        100_000.into(),
+        address_to_u256(callee.address),
+        100.into(), // value, which should be refunded
+        0.into(),
+        0.into(),
+        0.into(),
+        32.into(),
+    ];
+    assert_eq!(run_syscall(&mut interpreter, "sys_call", &args)?, 0.into());
+    // The return data of a reverted call is still available to the caller.
+    assert_eq!(main_memory(&interpreter, 0..32), word);
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+        32.into()
+    );
+    // Neither the storage write nor the value transfer should have taken effect.
+    let expected = state_trie(&accounts).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_nested_call_revert() -> Result<()> {
+    let word: [u8; 32] = thread_rng().gen();
+    let inner = account(sstore_then_revert_code(word));
+
+    // Stores 1 in slot 0, then calls `inner` while sending it 7 wei. Afterwards, it records
+    // whether that call failed in slot 1, and the size of its return data in slot 2.
+    let mut outer_code = vec![
+        0x60, 0x01, 0x60, 0x00, 0x55, // SSTORE(0, 1)
+        0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60,
+        0x00, // ret_size, ret_offset, args_size, args_offset
+        0x60, 0x07, // value
+        0x73, // PUSH20 address
+    ];
+    outer_code.extend(inner.address.as_bytes());
+    outer_code.extend([
+        0x61, 0xff, 0xff, // gas
+        0xf1, // CALL
+        0x15, 0x60, 0x01, 0x55, // SSTORE(1, ISZERO(success))
+        0x3d, 0x60, 0x02, 0x55, // SSTORE(2, RETURNDATASIZE)
+        0x00, // STOP
+    ]);
+    let mut outer = account(outer_code);
+
+    let caller = account(vec![]);
+    let mut interpreter =
+        prepare_interpreter(&[caller.clone(), outer.clone(), inner.clone()], INITIAL_GAS)?;
+
+    let args = [
+        100_000.into(),
+        address_to_u256(outer.address),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+    ];
+    assert_eq!(run_syscall(&mut interpreter, "sys_call", &args)?, 1.into());
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+        0.into()
+    );
+
+    // Only the outer call's writes remain, and no ETH changed hands.
+    outer.storage = vec![
+        (0.into(), 1.into()),
+        (1.into(), 1.into()),
+        (2.into(), 32.into()),
+    ];
+    let expected = state_trie(&[caller, outer, inner]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_call_insufficient_balance() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![0x00]);
+    let accounts = [caller, callee.clone()];
+    let mut interpreter = prepare_interpreter(&accounts, INITIAL_GAS)?;
+    let largest_context = interpreter.get_global_metadata_field(GlobalMetadata::LargestContext);
+
+    let args = [
+        100_000.into(),
+        address_to_u256(callee.address),
+        (CALLER_BALANCE + 1).into(),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+    ];
+    assert_eq!(run_syscall(&mut interpreter, "sys_call", &args)?, 0.into());
+    // The call failed before creating a new context.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::LargestContext),
+        largest_context
+    );
+    let expected = state_trie(&accounts).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_call_depth_limit() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![0x00]);
+    let mut interpreter = prepare_interpreter(&[caller, callee.clone()], INITIAL_GAS)?;
+    let largest_context = interpreter.get_global_metadata_field(GlobalMetadata::LargestContext);
+
+    let args = [
+        100_000.into(),
+        address_to_u256(callee.address),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+    ];
+    interpreter.set_global_metadata_field(GlobalMetadata::CallStackDepth, 1023.into());
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels["sys_call"];
+    for &arg in args.iter().rev() {
+        interpreter.push(arg);
+    }
+    interpreter.push(KEXIT_INFO.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![1.into()]);
+    interpreter.pop();
+
+    interpreter.set_global_metadata_field(GlobalMetadata::CallStackDepth, 1024.into());
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels["sys_call"];
+    for &arg in args.iter().rev() {
+        interpreter.push(arg);
+    }
+    interpreter.push(KEXIT_INFO.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![0.into()]);
+    // Only the first call created a context.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::LargestContext),
+        largest_context + 1
+    );
+    Ok(())
+}
+
+#[test]
+fn test_staticcall_sstore() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x60, 0x01, 0x60, 0x00, 0x55, // SSTORE(0, 1)
+        0x00, // STOP
+    ]);
+    let accounts = [caller, callee.clone()];
+    let mut interpreter = prepare_interpreter(&accounts, INITIAL_GAS)?;
+
+    let args = [
+        100_000.into(),
+        address_to_u256(callee.address),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+    ];
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_staticcall", &args)?,
+        0.into()
+    );
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+        0.into()
+    );
+    let expected = state_trie(&accounts).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_delegatecall() -> Result<()> {
+    let mut caller = account(vec![]);
+    let callee = account(vec![
+        0x34, 0x60, 0x00, 0x55, // SSTORE(0, CALLVALUE)
+        0x00, // STOP
+    ]);
+    let mut interpreter = prepare_interpreter(&[caller.clone(), callee.clone()], INITIAL_GAS)?;
+    interpreter.set_context_metadata_field(0, ContextMetadata::CallValue, 5.into());
+
+    let args = [
+        100_000.into(),
+        address_to_u256(callee.address),
+        0.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+    ];
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_delegatecall", &args)?,
+        1.into()
+    );
+
+    // The callee's code ran with the caller's storage and call value.
+    caller.storage = vec![(0.into(), 5.into())];
+    let expected = state_trie(&[caller, callee]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_call_revert_discards_logs() -> Result<()> {
+    let caller = account(vec![]);
+    let logger = account(vec![
+        0x60, 0x00, 0x60, 0x00, 0xa0, // LOG0(0, 0)
+        0x00, // STOP
+    ]);
+    let reverting_logger = account(vec![
+        0x60, 0x00, 0x60, 0x00, 0xa0, // LOG0(0, 0)
+        0x60, 0x00, 0x60, 0x00, 0xfd, // REVERT(0, 0)
+    ]);
+    let mut interpreter = prepare_interpreter(
+        &[caller, logger.clone(), reverting_logger.clone()],
+        INITIAL_GAS,
+    )?;
+
+    for (callee, expected_success) in [(&logger, 1), (&reverting_logger, 0)] {
+        let args = [
+            100_000.into(),
+            address_to_u256(callee.address),
+            0.into(),
+            0.into(),
+            0.into(),
+            0.into(),
+            0.into(),
+        ];
+        assert_eq!(
+            run_syscall(&mut interpreter, "sys_call", &args)?,
+            expected_success.into()
+        );
+    }
+
+    // Only the first call's log remains, stored as its address, 0 topics and 0 data bytes.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::LogsLen),
+        3.into()
+    );
+    let logs = &interpreter.generation_state.memory.contexts[0].segments[Segment::Logs as usize];
+    assert_eq!(logs.get(0), address_to_u256(logger.address));
+    Ok(())
+}
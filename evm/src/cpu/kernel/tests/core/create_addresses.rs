@@ -1,41 +1,39 @@
+use std::str::FromStr;
+
 use anyhow::Result;
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use rand::{thread_rng, Rng};
+use rlp::RlpStream;
 
 use crate::cpu::kernel::aggregator::KERNEL;
 use crate::cpu::kernel::interpreter::Interpreter;
+use crate::memory::segments::Segment;
 
-#[test]
-fn test_get_create_address() -> Result<()> {
+fn run_get_create_address(sender: Address, nonce: u64) -> Result<Address> {
     let get_create_address = KERNEL.global_labels["get_create_address"];
 
-    // TODO: Replace with real data once we have a real implementation.
     let retaddr = 0xdeadbeefu32.into();
-    let nonce = 5.into();
-    let sender = 0.into();
-    let expected_addr = 123.into();
-
-    let initial_stack = vec![retaddr, nonce, sender];
+    let sender = U256::from_big_endian(sender.as_bytes());
+    let initial_stack = vec![retaddr, nonce.into(), sender];
     let mut interpreter = Interpreter::new_with_kernel(get_create_address, initial_stack);
     interpreter.run()?;
 
-    assert_eq!(interpreter.stack(), &[expected_addr]);
-
-    Ok(())
+    assert_eq!(interpreter.stack().len(), 1);
+    Ok(u256_to_address(interpreter.pop()))
 }
 
-#[test]
-fn test_get_create2_address() -> Result<()> {
+fn run_get_create2_address(sender: Address, salt: H256, code: &[u8]) -> Result<Address> {
     let get_create2_address = KERNEL.global_labels["get_create2_address"];
 
-    // TODO: Replace with real data once we have a real implementation.
     let retaddr = 0xdeadbeefu32.into();
-    let code_len = 0.into();
+    let code_len = code.len().into();
     let code_offset = 0.into();
-    let code_segment = 0.into();
+    let code_segment = (Segment::MainMemory as u32).into();
     let code_context = 0.into();
-    let salt = 5.into();
-    let sender = 0.into();
-    let expected_addr = 123.into();
-
+    let salt = U256::from_big_endian(salt.as_bytes());
+    let sender = U256::from_big_endian(sender.as_bytes());
     let initial_stack = vec![
         retaddr,
         code_len,
@@ -46,9 +44,104 @@ fn test_get_create2_address() -> Result<()> {
         sender,
     ];
     let mut interpreter = Interpreter::new_with_kernel(get_create2_address, initial_stack);
+    interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize]
+        .content = code.iter().map(|&byte| byte.into()).collect();
     interpreter.run()?;
 
-    assert_eq!(interpreter.stack(), &[expected_addr]);
+    assert_eq!(interpreter.stack().len(), 1);
+    Ok(u256_to_address(interpreter.pop()))
+}
+
+fn u256_to_address(x: U256) -> Address {
+    let mut bytes = [0; 32];
+    x.to_big_endian(&mut bytes);
+    Address::from_slice(&bytes[12..])
+}
+
+#[test]
+fn test_get_create_address() -> Result<()> {
+    let sender = Address::from(hex!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"));
+    let expected_addrs = [
+        hex!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+        hex!("343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+        hex!("f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+        hex!("fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+    ];
+    for (nonce, expected_addr) in expected_addrs.into_iter().enumerate() {
+        assert_eq!(
+            run_get_create_address(sender, nonce as u64)?,
+            Address::from(expected_addr)
+        );
+    }
+
+    // Also test some nonces which need multi-byte RLP encodings.
+    let mut rng = thread_rng();
+    for nonce in [0x7f, 0x80, 0x1234, rng.gen()] {
+        let sender: Address = rng.gen();
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&sender);
+        stream.append(&nonce);
+        let expected_addr = Address::from_slice(&keccak(stream.out())[12..]);
+        assert_eq!(run_get_create_address(sender, nonce)?, expected_addr);
+    }
+
+    Ok(())
+}
+
+/// The test cases from EIP-1014, given as `(sender, salt, init_code, address)`.
+const EIP_1014_CASES: [(&str, &str, &[u8], &str); 7] = [
+    (
+        "0000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        &hex!("00"),
+        "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38",
+    ),
+    (
+        "deadbeef00000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        &hex!("00"),
+        "b928f69bb1d91cd65274e3c79d8986362984fda3",
+    ),
+    (
+        "deadbeef00000000000000000000000000000000",
+        "000000000000000000000000feed000000000000000000000000000000000000",
+        &hex!("00"),
+        "d04116cdd17bebe565eb2422f2497e06cc1c9833",
+    ),
+    (
+        "0000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        &hex!("deadbeef"),
+        "70f2b2914a2a4b783faefb75f459a580616fcb5e",
+    ),
+    (
+        "00000000000000000000000000000000deadbeef",
+        "00000000000000000000000000000000000000000000000000000000cafebabe",
+        &hex!("deadbeef"),
+        "60f3f640a8508fc6a86d45df051962668e1e8ac7",
+    ),
+    (
+        "00000000000000000000000000000000deadbeef",
+        "00000000000000000000000000000000000000000000000000000000cafebabe",
+        &hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"),
+        "1d8bfdc5d46dc4f61d6b6115972536ebe6a8854c",
+    ),
+    (
+        "0000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        &[],
+        "e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0",
+    ),
+];
+
+#[test]
+fn test_get_create2_address() -> Result<()> {
+    for (sender, salt, code, expected_addr) in EIP_1014_CASES {
+        let sender = Address::from_str(sender)?;
+        let salt = H256::from_str(salt)?;
+        let expected_addr = Address::from_str(expected_addr)?;
+        assert_eq!(run_get_create2_address(sender, salt, code)?, expected_addr);
+    }
 
     Ok(())
 }
@@ -0,0 +1,277 @@
+use anyhow::Result;
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use keccak_hash::keccak;
+use rand::{thread_rng, Rng};
+use rlp::RlpStream;
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{
+    self, hash_state_trie, run_syscall, state_trie, TestAccount, KEXIT_INFO,
+};
+use crate::memory::segments::Segment;
+
+const CREATOR_BALANCE: u64 = 1000;
+const CREATOR_NONCE: u64 = 5;
+
+const INITIAL_GAS: u64 = 1_000_000;
+
+fn creator() -> TestAccount {
+    TestAccount::new(CREATOR_NONCE, CREATOR_BALANCE, vec![])
+}
+
+/// Load a state trie containing the given account, and make it the current account of context 0,
+/// with the given init code in its memory.
+fn prepare_interpreter(creator: &TestAccount, init_code: &[u8]) -> Result<Interpreter<'static>> {
+    let mut interpreter = account_fixtures::prepare_interpreter(&[creator.clone()], INITIAL_GAS)?;
+    interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize]
+        .content = init_code.iter().map(|&byte| byte.into()).collect();
+    Ok(interpreter)
+}
+
+/// Run the given create syscall from context 0, and return the address it yields.
+fn create(interpreter: &mut Interpreter, syscall: &str, args: &[U256]) -> Result<Address> {
+    let mut bytes = [0; 32];
+    run_syscall(interpreter, syscall, args)?.to_big_endian(&mut bytes);
+    Ok(Address::from_slice(&bytes[12..]))
+}
+
+fn create_address(sender: Address, nonce: u64) -> Address {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&sender);
+    stream.append(&nonce);
+    Address::from_slice(&keccak(stream.out())[12..])
+}
+
+fn create2_address(sender: Address, salt: U256, init_code: &[u8]) -> Address {
+    let mut bytes = vec![0xff];
+    bytes.extend(sender.as_bytes());
+    bytes.extend(H256::from_uint(&salt).as_bytes());
+    bytes.extend(keccak(init_code).as_bytes());
+    Address::from_slice(&keccak(bytes)[12..])
+}
+
+/// Init code which stores 1 in slot 0, then returns the given code, which must be at most 32
+/// bytes long.
+fn init_code_returning(code: &[u8]) -> Vec<u8> {
+    assert!(!code.is_empty() && code.len() <= 32);
+    let mut init_code = vec![
+        0x60,
+        0x01,
+        0x60,
+        0x00,
+        0x55,                    // SSTORE(0, 1)
+        0x5f + code.len() as u8, // PUSHn code
+    ];
+    init_code.extend(code);
+    init_code.extend([
+        0x60,
+        0x00,
+        0x52, // MSTORE(0, code)
+        0x60,
+        code.len() as u8,
+        0x60,
+        32 - code.len() as u8,
+        0xf3, // RETURN(32 - len, len)
+    ]);
+    init_code
+}
+
+/// Returns the accounts expected after `creator` created a contract with the given value, whose
+/// init code is `init_code_returning(code)`.
+fn expected_accounts(
+    creator: &TestAccount,
+    address: Address,
+    value: u64,
+    code: &[u8],
+) -> [TestAccount; 2] {
+    let creator_after = TestAccount {
+        nonce: creator.nonce + 1,
+        balance: creator.balance - value,
+        ..creator.clone()
+    };
+    let contract = TestAccount {
+        address,
+        nonce: 1,
+        balance: value,
+        code: code.to_vec(),
+        storage: vec![(0.into(), 1.into())],
+    };
+    [creator_after, contract]
+}
+
+#[test]
+fn test_create() -> Result<()> {
+    let creator = creator();
+    // SSTORE(0, 42), STOP
+    let code = [0x60, 0x2a, 0x60, 0x00, 0x55, 0x00];
+    let init_code = init_code_returning(&code);
+    let mut interpreter = prepare_interpreter(&creator, &init_code)?;
+
+    let args = [
+        100.into(),             // value
+        0.into(),               // code_offset
+        init_code.len().into(), // code_len
+    ];
+    let address = create(&mut interpreter, "sys_create", &args)?;
+    assert_eq!(address, create_address(creator.address, CREATOR_NONCE));
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+        0.into()
+    );
+
+    let expected = state_trie(&expected_accounts(&creator, address, 100, &code)).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_create2() -> Result<()> {
+    let creator = creator();
+    let code: [u8; 32] = thread_rng().gen();
+    let init_code = init_code_returning(&code);
+    let mut interpreter = prepare_interpreter(&creator, &init_code)?;
+
+    let salt = U256(thread_rng().gen());
+    let args = [
+        0.into(),               // value
+        0.into(),               // code_offset
+        init_code.len().into(), // code_len
+        salt,
+    ];
+    let address = create(&mut interpreter, "sys_create2", &args)?;
+    assert_eq!(address, create2_address(creator.address, salt, &init_code));
+
+    let expected = state_trie(&expected_accounts(&creator, address, 0, &code)).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+
+    // Creating a contract at the same address again fails, although the nonce is still bumped.
+    assert_eq!(
+        create(&mut interpreter, "sys_create2", &args)?,
+        Address::zero()
+    );
+    let [mut creator_after, contract] = expected_accounts(&creator, address, 0, &code);
+    creator_after.nonce += 1;
+    let expected = state_trie(&[creator_after, contract]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_create_revert() -> Result<()> {
+    let creator = creator();
+    let word: [u8; 32] = thread_rng().gen();
+    let mut init_code = vec![
+        0x60, 0x01, 0x60, 0x00, 0x55, // SSTORE(0, 1)
+        0x7f, // PUSH32 word
+    ];
+    init_code.extend(word);
+    init_code.extend([
+        0x60, 0x00, 0x52, // MSTORE(0, word)
+        0x60, 0x20, 0x60, 0x00, 0xfd, // REVERT(0, 32)
+    ]);
+    let mut interpreter = prepare_interpreter(&creator, &init_code)?;
+
+    let args = [100.into(), 0.into(), init_code.len().into()];
+    assert_eq!(
+        create(&mut interpreter, "sys_create", &args)?,
+        Address::zero()
+    );
+    // The revert data is available to the creator.
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+        32.into()
+    );
+
+    // Only the creator's nonce changed.
+    let creator_after = TestAccount {
+        nonce: CREATOR_NONCE + 1,
+        ..creator
+    };
+    let expected = state_trie(&[creator_after]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_create_code_rejected() -> Result<()> {
+    let creator = creator();
+    // Returns code which starts with 0xEF, which is disallowed by EIP-3541.
+    let ef_init_code = init_code_returning(&[0xef, 0x00]);
+    // Returns 0x6001 zero bytes, which exceeds the code size limit of EIP-170.
+    let large_init_code = vec![
+        0x61, 0x60, 0x01, 0x60, 0x00, 0xf3, // RETURN(0, 0x6001)
+    ];
+
+    for init_code in [ef_init_code, large_init_code] {
+        let mut interpreter = prepare_interpreter(&creator, &init_code)?;
+        let args = [100.into(), 0.into(), init_code.len().into()];
+        assert_eq!(
+            create(&mut interpreter, "sys_create", &args)?,
+            Address::zero()
+        );
+        assert_eq!(
+            run_syscall(&mut interpreter, "sys_returndatasize", &[])?,
+            0.into()
+        );
+
+        // The constructor's effects were reverted, but the creator's nonce was still bumped.
+        let creator_after = TestAccount {
+            nonce: CREATOR_NONCE + 1,
+            ..creator.clone()
+        };
+        let expected = state_trie(&[creator_after]).calc_hash();
+        assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_create_insufficient_balance() -> Result<()> {
+    let creator = creator();
+    let init_code = init_code_returning(&[0x00]);
+    let mut interpreter = prepare_interpreter(&creator, &init_code)?;
+    let largest_context = interpreter.get_global_metadata_field(GlobalMetadata::LargestContext);
+
+    let args = [
+        (CREATOR_BALANCE + 1).into(),
+        0.into(),
+        init_code.len().into(),
+    ];
+    assert_eq!(
+        create(&mut interpreter, "sys_create", &args)?,
+        Address::zero()
+    );
+    // The creation failed before creating a new context, or bumping the nonce.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::LargestContext),
+        largest_context
+    );
+    let expected = state_trie(&[creator]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_create_static() -> Result<()> {
+    let creator = creator();
+    let init_code = init_code_returning(&[0x00]);
+    let mut interpreter = prepare_interpreter(&creator, &init_code)?;
+    interpreter.set_context_metadata_field(0, ContextMetadata::Static, 1.into());
+
+    // CREATE is a state modification, so it faults in static mode.
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels["sys_create"];
+    interpreter.push(init_code.len().into());
+    interpreter.push(0.into());
+    interpreter.push(0.into());
+    interpreter.push(KEXIT_INFO.into());
+    interpreter.halt_offsets = vec![KERNEL.global_labels["fault_exception"]];
+    interpreter.run()?;
+    assert_eq!(
+        interpreter.generation_state.registers.program_counter,
+        KERNEL.global_labels["fault_exception"]
+    );
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use anyhow::Result;
+use ethereum_types::{Address, BigEndianHash, U256};
+use keccak_hash::keccak;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{
+    address_to_u256, main_memory, prepare_interpreter, run_syscall, TestAccount,
+};
+use crate::memory::segments::Segment;
+use crate::proof::NUM_PREV_BLOCK_HASHES;
+
+const BALANCE: u64 = 1000;
+
+const INITIAL_GAS: u64 = 1_000_000;
+
+fn word(x: U256) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    x.to_big_endian(&mut bytes);
+    bytes
+}
+
+#[test]
+fn test_block_information() -> Result<()> {
+    let account = TestAccount::new(1, BALANCE, vec![]);
+    let mut interpreter = prepare_interpreter(&[account.clone()], INITIAL_GAS)?;
+
+    let mut rng = thread_rng();
+    let fields = [
+        ("sys_coinbase", GlobalMetadata::BlockBeneficiary),
+        ("sys_timestamp", GlobalMetadata::BlockTimestamp),
+        ("sys_number", GlobalMetadata::BlockNumber),
+        ("sys_prevrandao", GlobalMetadata::BlockDifficulty),
+        ("sys_gaslimit", GlobalMetadata::BlockGasLimit),
+        ("sys_chainid", GlobalMetadata::BlockChainId),
+        ("sys_basefee", GlobalMetadata::BlockBaseFee),
+    ];
+    for (_, field) in fields {
+        interpreter.set_global_metadata_field(field, U256(rng.gen()));
+    }
+    for (name, field) in fields {
+        assert_eq!(
+            run_syscall(&mut interpreter, name, &[])?,
+            interpreter.get_global_metadata_field(field)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_blockhash() -> Result<()> {
+    let account = TestAccount::new(1, BALANCE, vec![]);
+    let mut interpreter = prepare_interpreter(&[account.clone()], INITIAL_GAS)?;
+
+    let mut rng = thread_rng();
+    let prev_hashes: Vec<U256> = (0..NUM_PREV_BLOCK_HASHES)
+        .map(|_| U256(rng.gen()))
+        .collect();
+    interpreter.generation_state.memory.contexts[0].segments[Segment::BlockHashes as usize]
+        .content = prev_hashes.clone();
+    let cur_block_number = 1000u64;
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockNumber, cur_block_number.into());
+
+    let blockhash = |interpreter: &mut Interpreter, block_number: U256| {
+        run_syscall(interpreter, "sys_blockhash", &[block_number])
+    };
+    assert_eq!(
+        blockhash(&mut interpreter, (cur_block_number - 1).into())?,
+        prev_hashes[255]
+    );
+    assert_eq!(
+        blockhash(&mut interpreter, (cur_block_number - 100).into())?,
+        prev_hashes[156]
+    );
+    assert_eq!(
+        blockhash(&mut interpreter, (cur_block_number - 256).into())?,
+        prev_hashes[0]
+    );
+    for block_number in [
+        U256::from(cur_block_number - 257),
+        U256::zero(),
+        cur_block_number.into(),
+        (cur_block_number + 1).into(),
+        U256::MAX,
+    ] {
+        assert_eq!(blockhash(&mut interpreter, block_number)?, 0.into());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_gasprice() -> Result<()> {
+    let account = TestAccount::new(1, BALANCE, vec![]);
+    let mut interpreter = prepare_interpreter(&[account.clone()], INITIAL_GAS)?;
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockBaseFee, 10.into());
+
+    // Legacy transactions store their gas price in both fee fields.
+    interpreter.set_txn_field(NormalizedTxnField::MaxPriorityFeePerGas, 25.into());
+    interpreter.set_txn_field(NormalizedTxnField::MaxFeePerGas, 25.into());
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_gasprice", &[])?,
+        25.into()
+    );
+
+    // The priority fee is capped by the max fee.
+    interpreter.set_txn_field(NormalizedTxnField::MaxPriorityFeePerGas, 20.into());
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_gasprice", &[])?,
+        25.into()
+    );
+
+    interpreter.set_txn_field(NormalizedTxnField::MaxPriorityFeePerGas, 2.into());
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_gasprice", &[])?,
+        12.into()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_account_information() -> Result<()> {
+    let code = vec![0x60, 0x2a, 0x00];
+    let account = TestAccount::new(1, BALANCE, vec![]);
+    let other = TestAccount::new(1, BALANCE, code.clone());
+    let mut interpreter = prepare_interpreter(&[account.clone(), other.clone()], INITIAL_GAS)?;
+
+    let missing = address_to_u256(thread_rng().gen());
+    // The high bits of the address are ignored.
+    let other_address = address_to_u256(other.address) | (U256::one() << 200);
+
+    let extcodehash = |interpreter: &mut Interpreter, address| {
+        run_syscall(interpreter, "sys_extcodehash", &[address])
+    };
+    assert_eq!(
+        extcodehash(&mut interpreter, other_address)?,
+        keccak(&code).into_uint()
+    );
+    assert_eq!(
+        extcodehash(&mut interpreter, address_to_u256(account.address))?,
+        keccak([]).into_uint()
+    );
+    assert_eq!(extcodehash(&mut interpreter, missing)?, 0.into());
+
+    let extcodesize = |interpreter: &mut Interpreter, address| {
+        run_syscall(interpreter, "sys_extcodesize", &[address])
+    };
+    assert_eq!(extcodesize(&mut interpreter, other_address)?, 3.into());
+    assert_eq!(extcodesize(&mut interpreter, missing)?, 0.into());
+
+    let balance = |interpreter: &mut Interpreter, address| {
+        run_syscall(interpreter, "sys_balance", &[address])
+    };
+    assert_eq!(balance(&mut interpreter, other_address)?, BALANCE.into());
+    assert_eq!(balance(&mut interpreter, missing)?, 0.into());
+    assert_eq!(
+        run_syscall(&mut interpreter, "sys_selfbalance", &[])?,
+        BALANCE.into()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_environment_in_call() -> Result<()> {
+    // Stores the results of various environment opcodes in consecutive words of memory, followed
+    // by some copied data, and returns all of it.
+    let mut code = vec![
+        0x30, 0x60, 0x00, 0x52, // MSTORE(0, ADDRESS)
+        0x33, 0x60, 0x20, 0x52, // MSTORE(32, CALLER)
+        0x34, 0x60, 0x40, 0x52, // MSTORE(64, CALLVALUE)
+        0x36, 0x60, 0x60, 0x52, // MSTORE(96, CALLDATASIZE)
+        0x60, 0x01, 0x35, 0x60, 0x80, 0x52, // MSTORE(128, CALLDATALOAD(1))
+        0x7f, 0x80, // PUSH32 2^255
+    ];
+    code.extend([0; 31]);
+    code.extend([
+        0x35, 0x60, 0xa0, 0x52, // MSTORE(160, CALLDATALOAD(2^255))
+        0x38, 0x60, 0xc0, 0x52, // MSTORE(192, CODESIZE)
+        0x47, 0x60, 0xe0, 0x52, // MSTORE(224, SELFBALANCE)
+        0x33, 0x31, 0x61, 0x01, 0x00, 0x52, // MSTORE(256, BALANCE(CALLER))
+        0x33, 0x3b, 0x61, 0x01, 0x20, 0x52, // MSTORE(288, EXTCODESIZE(CALLER))
+        0x33, 0x3f, 0x61, 0x01, 0x40, 0x52, // MSTORE(320, EXTCODEHASH(CALLER))
+        0x32, 0x61, 0x01, 0x60, 0x52, // MSTORE(352, ORIGIN)
+        0x43, 0x61, 0x01, 0x80, 0x52, // MSTORE(384, NUMBER)
+        0x60, 0x08, 0x38, 0x01, 0x60, 0x00, 0x61, 0x01, 0xa0,
+        0x39, // CODECOPY(416, 0, CODESIZE + 8)
+        0x60, 0x20, 0x60, 0x10, 0x61, 0x02, 0x28, 0x37, // CALLDATACOPY(552, 16, 32)
+        0x60, 0x07, 0x60, 0x00, 0x61, 0x02, 0x48, 0x33,
+        0x3c, // EXTCODECOPY(CALLER, 584, 0, 7)
+        0x61, 0x02, 0x4f, 0x60, 0x00, 0xf3, // RETURN(0, 591)
+    ]);
+    // The copied code ends at 416 + 128 + 8 = 552.
+    assert_eq!(code.len(), 128);
+    let caller_code = vec![0x60, 0x01, 0x00];
+    let caller = TestAccount::new(1, BALANCE, caller_code.clone());
+    let callee = TestAccount::new(1, BALANCE, code.clone());
+    let mut interpreter = prepare_interpreter(&[caller.clone(), callee.clone()], INITIAL_GAS)?;
+
+    let mut rng = thread_rng();
+    let origin: Address = rng.gen();
+    let number = U256(rng.gen());
+    interpreter.set_txn_field(NormalizedTxnField::Origin, address_to_u256(origin));
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockNumber, number);
+
+    let calldata: [u8; 32] = rng.gen();
+    interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize]
+        .content = calldata.into_iter().map(U256::from).collect();
+
+    let value = 7;
+    let args = [
+        100_000.into(),                  // gas
+        address_to_u256(callee.address), // address
+        value.into(),                    // value
+        0.into(),                        // args_offset
+        32.into(),                       // args_size
+        1024.into(),                     // ret_offset
+        591.into(),                      // ret_size
+    ];
+    assert_eq!(run_syscall(&mut interpreter, "sys_call", &args)?, 1.into());
+
+    let mut expected = vec![];
+    expected.extend(word(address_to_u256(callee.address)));
+    expected.extend(word(address_to_u256(caller.address)));
+    expected.extend(word(value.into()));
+    expected.extend(word(32.into()));
+    expected.extend(&calldata[1..]);
+    expected.push(0);
+    expected.extend([0; 32]);
+    expected.extend(word(code.len().into()));
+    expected.extend(word((BALANCE + value).into()));
+    expected.extend(word((BALANCE - value).into()));
+    expected.extend(word(caller_code.len().into()));
+    expected.extend(keccak(&caller_code).as_bytes());
+    expected.extend(word(address_to_u256(origin)));
+    expected.extend(word(number));
+    expected.extend(&code);
+    expected.extend([0; 8]);
+    expected.extend(&calldata[16..]);
+    expected.extend([0; 16]);
+    expected.extend(&caller_code);
+    expected.extend([0; 4]);
+    assert_eq!(expected.len(), 591);
+    assert_eq!(main_memory(&interpreter, 1024..1024 + 591), expected);
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use ethereum_types::U256;
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{
+    address_to_u256, gas_used, hash_state_trie, prepare_interpreter, run_syscall, state_trie,
+    TestAccount,
+};
+
+const INITIAL_GAS: u64 = 1_000_000;
+
+/// The gas sent along with each CALL.
+const CALL_GAS: u64 = 10_000;
+
+/// The cost of a CALL to a cold account, without any value or memory expansion.
+const CALL_COST: u64 = 2600;
+
+/// An account with a nonce of 1, a balance of 1000 and the given code.
+fn account(code: Vec<u8>) -> TestAccount {
+    TestAccount::new(1, 1000, code)
+}
+
+/// Deploy the given code, CALL it from context 0 with `CALL_GAS` gas, and return the success flag
+/// along with the gas used by context 0.
+fn call_code(code: Vec<u8>) -> Result<(U256, U256)> {
+    let caller = account(vec![]);
+    let callee = account(code);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    let args = [
+        CALL_GAS.into(),
+        address_to_u256(callee_address),
+        0.into(), // value
+        0.into(), // args_offset
+        0.into(), // args_size
+        0.into(), // ret_offset
+        0.into(), // ret_size
+    ];
+    let success = run_syscall(&mut interpreter, "sys_call", &args)?;
+    Ok((success, gas_used(&interpreter, INITIAL_GAS)))
+}
+
+/// Checks that the given code halts exceptionally, consuming all the gas it was sent.
+fn assert_faults(code: Vec<u8>) -> Result<()> {
+    let (success, gas_used) = call_code(code)?;
+    assert_eq!(success, 0.into());
+    assert_eq!(gas_used, (CALL_COST + CALL_GAS).into());
+    Ok(())
+}
+
+#[test]
+fn test_invalid_opcode() -> Result<()> {
+    assert_faults(vec![
+        0x60, 0x01, // PUSH1 1
+        0xfe, // INVALID
+    ])?;
+    // Kernel-only opcodes are invalid in user mode.
+    assert_faults(vec![
+        0xf6, // GET_CONTEXT
+    ])
+}
+
+#[test]
+fn test_stack_underflow() -> Result<()> {
+    assert_faults(vec![
+        0x60, 0x01, // PUSH1 1
+        0x01, // ADD
+    ])?;
+    assert_faults(vec![
+        0x60, 0x01, // PUSH1 1
+        0x91, // SWAP2
+    ])
+}
+
+#[test]
+fn test_stack_overflow() -> Result<()> {
+    // 1024 PCs fill the stack, so the last one overflows it.
+    assert_faults(vec![0x58; 1025])
+}
+
+#[test]
+fn test_out_of_gas() -> Result<()> {
+    // Each JUMPDEST costs 1 gas, so running CALL_GAS of them uses up exactly the gas sent...
+    let (success, gas_used) = call_code(vec![0x5b; CALL_GAS as usize])?;
+    assert_eq!(success, 1.into());
+    assert_eq!(gas_used, (CALL_COST + CALL_GAS).into());
+    // ...and one more goes over the limit.
+    assert_faults(vec![0x5b; CALL_GAS as usize + 1])
+}
+
+#[test]
+fn test_invalid_jump_destination() -> Result<()> {
+    assert_faults(vec![
+        0x60, 0x03, // PUSH1 3
+        0x56, // JUMP
+        0x00, // STOP, which isn't a JUMPDEST
+    ])?;
+    // The 0x5b here is PUSH data, not a JUMPDEST.
+    assert_faults(vec![
+        0x60, 0x01, // PUSH1 1
+        0x60, 0x06, // PUSH1 6
+        0x57, // JUMPI
+        0x60, 0x5b, // PUSH1 0x5b
+    ])
+}
+
+#[test]
+fn test_valid_jumps() -> Result<()> {
+    let (success, _) = call_code(vec![
+        0x60, 0x00, // PUSH1 0
+        0x60, 0x00, // PUSH1 0
+        0x57, // JUMPI, which isn't taken, so its destination doesn't matter
+        0x60, 0x09, // PUSH1 9
+        0x56, // JUMP
+        0xfe, // INVALID, which is skipped
+        0x5b, // JUMPDEST
+        0x00, // STOP
+    ])?;
+    assert_eq!(success, 1.into());
+    Ok(())
+}
+
+#[test]
+fn test_unwarranted_exception() -> Result<()> {
+    // Trap to the stack underflow handler on an ADD with two inputs, which doesn't underflow.
+    let mut interpreter =
+        Interpreter::new_with_kernel(KERNEL.global_labels["exc_stack_underflow"], vec![]);
+    interpreter.set_code(1, vec![0x01]);
+    interpreter.context = 1;
+    interpreter.push(1.into());
+    interpreter.push(2.into());
+    let trap_info = U256::from(2) << 64;
+    interpreter.push(trap_info);
+    let err = interpreter.run().unwrap_err();
+    assert_eq!(err.to_string(), "Executed PANIC");
+    Ok(())
+}
+
+#[test]
+fn test_failed_message_txn() -> Result<()> {
+    let accounts = [
+        account(vec![]),
+        account(vec![
+            0x60, 0x01, 0x60, 0x00, 0x55, // SSTORE(0, 1), which is rolled back
+            0xfe, // INVALID
+        ]),
+    ];
+    let [sender, contract] = &accounts;
+    let mut interpreter = prepare_interpreter(&accounts, INITIAL_GAS)?;
+
+    interpreter.set_txn_field(NormalizedTxnField::Origin, address_to_u256(sender.address));
+    interpreter.set_txn_field(NormalizedTxnField::Nonce, 1.into());
+    interpreter.set_txn_field(NormalizedTxnField::To, address_to_u256(contract.address));
+    interpreter.set_txn_field(NormalizedTxnField::GasLimit, 100_000.into());
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockGasLimit, 100_000.into());
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["process_normalized_txn"];
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    // The transaction consumed all of its gas.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::BlockGasUsed),
+        100_000.into()
+    );
+    // Its state changes were reverted, except for the sender's nonce.
+    let sender_after = TestAccount {
+        nonce: 2,
+        ..sender.clone()
+    };
+    let expected_state_trie = state_trie(&[sender_after, contract.clone()]);
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        expected_state_trie.calc_hash()
+    );
+    Ok(())
+}
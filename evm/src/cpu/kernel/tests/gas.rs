@@ -0,0 +1,218 @@
+use anyhow::Result;
+use ethereum_types::{Address, U256};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{
+    address_to_u256, gas_used, main_memory, prepare_interpreter, run_syscall, TestAccount,
+};
+
+const INITIAL_GAS: u64 = 1_000_000;
+
+/// The cost of a CALL to a cold account, without any value or memory expansion.
+const CALL_COST: u64 = 2600;
+
+/// An account with a nonce of 1, a balance of 1000 and the given code.
+fn account(code: Vec<u8>) -> TestAccount {
+    TestAccount::new(1, 1000, code)
+}
+
+/// CALL the given account from context 0 with the given gas, and an output region of one word at
+/// offset 0. Returns the success flag.
+fn call(interpreter: &mut Interpreter, address: Address, gas: u64, ret_size: u64) -> Result<U256> {
+    let args = [
+        gas.into(),
+        address_to_u256(address),
+        0.into(), // value
+        0.into(), // args_offset
+        0.into(), // args_size
+        0.into(), // ret_offset
+        ret_size.into(),
+    ];
+    run_syscall(interpreter, "sys_call", &args)
+}
+
+#[test]
+fn test_call_gas_used() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x60, 0x01, 0x60, 0x02, 0x01, // ADD(2, 1): 3 + 3 + 3
+        0x50, // POP: 2
+        0x00, // STOP: 0
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    assert_eq!(
+        call(&mut interpreter, callee_address, 100_000, 0)?,
+        1.into()
+    );
+    // The gas which the callee didn't use was refunded.
+    assert_eq!(gas_used(&interpreter, INITIAL_GAS), (CALL_COST + 11).into());
+    Ok(())
+}
+
+#[test]
+fn test_memory_expansion_gas() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        // Expands memory to 3 words, for 3 * 3 gas, on top of 3 + 3 + 3.
+        0x60, 0x01, 0x60, 0x40, 0x52, // MSTORE(64, 1)
+        // Memory needn't be expanded this time.
+        0x60, 0x02, 0x60, 0x20, 0x52, // MSTORE(32, 2)
+        0x00, // STOP
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    assert_eq!(
+        call(&mut interpreter, callee_address, 100_000, 0)?,
+        1.into()
+    );
+    assert_eq!(
+        gas_used(&interpreter, INITIAL_GAS),
+        (CALL_COST + 18 + 9).into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_gas_opcode() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x5a, // GAS
+        0x60, 0x00, 0x52, // MSTORE(0, gas)
+        0x60, 0x20, 0x60, 0x00, 0xf3, // RETURN(0, 32)
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    assert_eq!(call(&mut interpreter, callee_address, 1000, 32)?, 1.into());
+    // GAS itself costs 2.
+    assert_eq!(
+        U256::from_big_endian(&main_memory(&interpreter, 0..32)),
+        998.into()
+    );
+    // The callee used 2 + 3 + 3 + 3 (for its memory) + 3 + 3, and the caller paid 3 to expand its
+    // own memory for the output.
+    assert_eq!(
+        gas_used(&interpreter, INITIAL_GAS),
+        (CALL_COST + 3 + 17).into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_out_of_gas() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x60, 0x01, 0x60, 0x02, 0x01, // ADD(2, 1), which costs 9 in total
+        0x00, // STOP
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    assert_eq!(call(&mut interpreter, callee_address, 8, 0)?, 0.into());
+    // All of the gas sent to the callee is consumed.
+    assert_eq!(gas_used(&interpreter, INITIAL_GAS), (CALL_COST + 8).into());
+    Ok(())
+}
+
+#[test]
+fn test_message_txn_gas_used() -> Result<()> {
+    let sender = account(vec![]);
+    let contract = account(vec![
+        0x60, 0x01, 0x60, 0x02, 0x01, // ADD(2, 1)
+        0x60, 0x00, 0x55, // SSTORE(0, 3), a fresh slot
+        0x00, // STOP
+    ]);
+    let sender_address = sender.address;
+    let contract_address = contract.address;
+    let mut interpreter = prepare_interpreter(&[sender, contract], INITIAL_GAS)?;
+
+    interpreter.set_txn_field(NormalizedTxnField::Origin, address_to_u256(sender_address));
+    interpreter.set_txn_field(NormalizedTxnField::Nonce, 1.into());
+    interpreter.set_txn_field(NormalizedTxnField::To, address_to_u256(contract_address));
+    interpreter.set_txn_field(NormalizedTxnField::GasLimit, 100_000.into());
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockGasLimit, 100_000.into());
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["process_normalized_txn"];
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    // The intrinsic gas, plus 4 * 3 for the pushes and the ADD, plus 2100 + 20000 for the SSTORE
+    // to a cold slot.
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::BlockGasUsed),
+        (21000 + 12 + 2100 + 20000).into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_warm_call() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x00, // STOP
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    assert_eq!(call(&mut interpreter, callee_address, 1000, 0)?, 1.into());
+    assert_eq!(gas_used(&interpreter, INITIAL_GAS), CALL_COST.into());
+    // The first CALL warmed the callee, so the second one only pays for a warm access.
+    assert_eq!(call(&mut interpreter, callee_address, 1000, 0)?, 1.into());
+    assert_eq!(
+        gas_used(&interpreter, INITIAL_GAS),
+        (CALL_COST + 100).into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cold_and_warm_sload() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x60, 0x00, 0x54, // SLOAD(0), which is cold: 3 + 2100
+        0x60, 0x00, 0x54, // SLOAD(0), which is warm: 3 + 100
+        0x00, // STOP
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    assert_eq!(
+        call(&mut interpreter, callee_address, 100_000, 0)?,
+        1.into()
+    );
+    assert_eq!(
+        gas_used(&interpreter, INITIAL_GAS),
+        (CALL_COST + 2103 + 103).into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reverted_accesses_are_cold() -> Result<()> {
+    let caller = account(vec![]);
+    let callee = account(vec![
+        0x60, 0x00, 0x54, 0x50, // POP(SLOAD(0)): 3 + 2100 + 2 if cold, or 3 + 100 + 2 if warm
+        0x60, 0x00, 0x50, // POP(0): 3 + 2
+        0x00, // STOP
+    ]);
+    let callee_address = callee.address;
+    let mut interpreter = prepare_interpreter(&[caller, callee], INITIAL_GAS)?;
+
+    // The callee runs out of gas after its cold SLOAD, which reverts the access to the slot.
+    assert_eq!(call(&mut interpreter, callee_address, 2108, 0)?, 0.into());
+    // So the slot is still cold, and the callee runs out of gas again. Its address was accessed by
+    // the caller, so it stays warm.
+    assert_eq!(call(&mut interpreter, callee_address, 2108, 0)?, 0.into());
+    assert_eq!(
+        gas_used(&interpreter, INITIAL_GAS),
+        (CALL_COST + 2108 + 100 + 2108).into()
+    );
+    Ok(())
+}
@@ -1,16 +1,28 @@
 mod account_code;
+mod account_fixtures;
 mod balance;
+mod bn254;
+mod call;
 mod core;
+mod create;
 mod curve_ops;
 mod ecrecover;
+mod environment;
+mod exception;
 mod exp;
 mod fields;
+mod gas;
 mod hash;
 mod mpt;
 mod packing;
+mod precompiles;
+mod receipt;
 mod ripemd;
 mod rlp;
+mod signed;
+mod storage;
 mod transaction_parsing;
+mod txn_finalization;
 
 use std::str::FromStr;
 
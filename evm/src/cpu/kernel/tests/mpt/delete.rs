@@ -0,0 +1,116 @@
+use anyhow::Result;
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{BigEndianHash, H256};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::mpt::{nibbles_64, test_account_1_rlp};
+use crate::generation::mpt::all_mpt_prover_inputs_reversed;
+use crate::generation::TrieInputs;
+
+#[test]
+fn mpt_delete_empty() -> Result<()> {
+    test_state_trie(&[], nibbles_64(0xABC))
+}
+
+#[test]
+fn mpt_delete_leaf() -> Result<()> {
+    test_state_trie(&[0xABC], nibbles_64(0xABC))
+}
+
+#[test]
+fn mpt_delete_leaf_nonexistent_key() -> Result<()> {
+    test_state_trie(&[0xABC], nibbles_64(0x123))
+}
+
+#[test]
+fn mpt_delete_branch_nonexistent_key() -> Result<()> {
+    test_state_trie(&[0xABC, 0xABD], nibbles_64(0xABE))
+}
+
+#[test]
+fn mpt_delete_extension_nonexistent_key() -> Result<()> {
+    test_state_trie(&[0xABC, 0xABD], nibbles_64(0xB00))
+}
+
+#[test]
+fn mpt_delete_branch_remains() -> Result<()> {
+    test_state_trie(&[0xA, 0xB, 0xC], nibbles_64(0xB))
+}
+
+#[test]
+fn mpt_delete_branch_collapses_into_leaf() -> Result<()> {
+    // The branch's remaining leaf is merged with the extension above it.
+    test_state_trie(&[0xABC, 0xABD], nibbles_64(0xABD))
+}
+
+#[test]
+fn mpt_delete_branch_collapses_into_extension() -> Result<()> {
+    // The branch's remaining child is a branch, so it becomes an extension.
+    test_state_trie(&[0xA12, 0xB34, 0xB56], nibbles_64(0xA12))
+}
+
+#[test]
+fn mpt_delete_branch_merges_extensions() -> Result<()> {
+    // The branch's remaining child is an extension, which is merged with the
+    // new extension and with the extension above it.
+    test_state_trie(&[0xA123, 0xA124, 0xB000], nibbles_64(0xB000))
+}
+
+/// Builds a state trie containing an account at each of the given keys, deletes `k` from it in
+/// the kernel, and checks the resulting root against `PartialTrie::delete`.
+fn test_state_trie(keys: &[u64], k: Nibbles) -> Result<()> {
+    assert_eq!(k.count, 64);
+
+    let mut state_trie = PartialTrie::Empty;
+    for &key in keys {
+        state_trie.insert(nibbles_64(key), test_account_1_rlp());
+    }
+
+    let trie_inputs = TrieInputs {
+        state_trie: state_trie.clone(),
+        transactions_trie: Default::default(),
+        receipts_trie: Default::default(),
+        storage_tries: vec![],
+    };
+    let load_all_mpts = KERNEL.global_labels["load_all_mpts"];
+    let mpt_delete_state_trie = KERNEL.global_labels["mpt_delete_state_trie"];
+    let mpt_hash_state_trie = KERNEL.global_labels["mpt_hash_state_trie"];
+
+    let initial_stack = vec![0xDEADBEEFu32.into()];
+    let mut interpreter = Interpreter::new_with_kernel(load_all_mpts, initial_stack);
+    interpreter.generation_state.mpt_prover_inputs = all_mpt_prover_inputs_reversed(&trie_inputs);
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    // Next, execute mpt_delete_state_trie.
+    interpreter.generation_state.registers.program_counter = mpt_delete_state_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.push(k.packed); // key
+    interpreter.run()?;
+    assert_eq!(
+        interpreter.stack().len(),
+        0,
+        "Expected empty stack after delete, found {:?}",
+        interpreter.stack()
+    );
+
+    // Now, execute mpt_hash_state_trie.
+    interpreter.generation_state.registers.program_counter = mpt_hash_state_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+
+    assert_eq!(
+        interpreter.stack().len(),
+        1,
+        "Expected 1 item on stack after hashing, found {:?}",
+        interpreter.stack()
+    );
+    let hash = H256::from_uint(&interpreter.stack()[0]);
+
+    state_trie.delete(k);
+    let expected_state_trie_hash = state_trie.calc_hash();
+    assert_eq!(hash, expected_state_trie_hash);
+
+    Ok(())
+}
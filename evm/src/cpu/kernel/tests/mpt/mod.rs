@@ -3,6 +3,7 @@ use ethereum_types::{BigEndianHash, H256, U256};
 
 use crate::generation::mpt::AccountRlp;
 
+mod delete;
 mod hash;
 mod hex_prefix;
 mod insert;
@@ -8,8 +8,9 @@ use crate::cpu::kernel::tests::mpt::{extension_to_leaf, test_account_1, test_acc
 use crate::generation::mpt::all_mpt_prover_inputs_reversed;
 use crate::generation::TrieInputs;
 
-#[test]
-fn mpt_read() -> Result<()> {
+/// Load a state trie with an extension node leading to a leaf, and run `mpt_read` on it with the
+/// given 6-nibble key, leaving the value pointer it yields on the stack.
+fn read_extension_to_leaf(key: u64) -> Result<Interpreter<'static>> {
     let trie_inputs = TrieInputs {
         state_trie: extension_to_leaf(test_account_1_rlp()),
         transactions_trie: Default::default(),
@@ -29,12 +30,18 @@ fn mpt_read() -> Result<()> {
     // Now, execute mpt_read on the state trie.
     interpreter.generation_state.registers.program_counter = mpt_read;
     interpreter.push(0xdeadbeefu32.into());
-    interpreter.push(0xABCDEFu64.into());
+    interpreter.push(key.into());
     interpreter.push(6.into());
     interpreter.push(interpreter.get_global_metadata_field(GlobalMetadata::StateTrieRoot));
     interpreter.run()?;
-
     assert_eq!(interpreter.stack().len(), 1);
+    Ok(interpreter)
+}
+
+#[test]
+fn mpt_read() -> Result<()> {
+    let interpreter = read_extension_to_leaf(0xABCDEF)?;
+
     let result_ptr = interpreter.stack()[0].as_usize();
     let result = &interpreter.get_trie_data()[result_ptr..][..4];
     assert_eq!(result[0], test_account_1().nonce);
@@ -45,3 +52,11 @@ fn mpt_read() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn mpt_read_extension_mismatch() -> Result<()> {
+    // The key diverges from the extension node's nibbles, so it isn't found.
+    let interpreter = read_extension_to_leaf(0xABBDEF)?;
+    assert_eq!(interpreter.stack(), vec![0.into()]);
+    Ok(())
+}
@@ -0,0 +1,397 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use ethereum_types::{U256, U512};
+use hex_literal::hex;
+use num::{BigUint, Zero};
+use rand::{thread_rng, Rng};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{self, TestAccount, KEXIT_INFO};
+use crate::memory::segments::Segment;
+
+/// Where the caller's output region starts in its main memory, after any input we use.
+const RET_OFFSET: usize = 1000;
+
+/// Load a state trie containing a single account, and make it the current account of context 0,
+/// with the given input in its main memory.
+fn prepare_interpreter(input: &[u8]) -> Result<Interpreter<'static>> {
+    let mut interpreter =
+        account_fixtures::prepare_interpreter(&[TestAccount::new(1, 1000, vec![])], 1_000_000)?;
+    interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize]
+        .content = input.iter().map(|&byte| byte.into()).collect();
+    Ok(interpreter)
+}
+
+fn returndata_size(interpreter: &mut Interpreter) -> Result<usize> {
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["sys_returndatasize"];
+    interpreter.push(KEXIT_INFO.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack().len(), 1);
+    Ok(interpreter.pop().as_usize())
+}
+
+/// Run the given call syscall from context 0, targeting the given precompile with the given input
+/// and gas. Returns `None` if the call failed, or the precompile's output otherwise.
+fn call_precompile_with(
+    syscall: &str,
+    precompile: u8,
+    input: &[u8],
+    gas: u64,
+) -> Result<Option<Vec<u8>>> {
+    let mut interpreter = prepare_interpreter(input)?;
+    let mut args: Vec<U256> = vec![gas.into(), precompile.into()];
+    if syscall == "sys_call" {
+        args.push(0.into()); // value
+    }
+    args.extend([0, input.len(), RET_OFFSET, 1000].map(U256::from));
+
+    interpreter.generation_state.registers.program_counter = KERNEL.global_labels[syscall];
+    for &arg in args.iter().rev() {
+        interpreter.push(arg);
+    }
+    interpreter.push(KEXIT_INFO.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack().len(), 1);
+    assert_eq!(interpreter.context, 0);
+    let success = interpreter.pop();
+
+    let size = returndata_size(&mut interpreter)?;
+    if success.is_zero() {
+        assert_eq!(size, 0);
+        return Ok(None);
+    }
+    let memory =
+        &interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize];
+    Ok(Some(
+        (RET_OFFSET..RET_OFFSET + size)
+            .map(|i| memory.get(i).byte(0))
+            .collect(),
+    ))
+}
+
+fn call_precompile(precompile: u8, input: &[u8], gas: u64) -> Result<Option<Vec<u8>>> {
+    call_precompile_with("sys_call", precompile, input, gas)
+}
+
+fn words(words: &[&str]) -> Vec<u8> {
+    let mut bytes = vec![];
+    for &word in words {
+        let mut buf = [0; 32];
+        U256::from_str(word).unwrap().to_big_endian(&mut buf);
+        bytes.extend(buf);
+    }
+    bytes
+}
+
+#[test]
+fn test_ecrec() -> Result<()> {
+    let input = words(&[
+        "0x55f77e8909b1f1c9531c4a309bb2d40388e9ed4b87830c8f90363c6b36255fb9",
+        "0x1b",
+        "0xd667c5a20fa899b253924099e10ae92998626718585b8171eb98de468bbebc",
+        "0x58351f48ce34bf134ee611fb5bf255a5733f0029561d345a7d46bfa344b60ac0",
+    ]);
+    assert_eq!(
+        call_precompile(1, &input, 3_000)?,
+        Some(words(&["0x67f3c0Da351384838d7F7641AB0fCAcF853E1844"]))
+    );
+    assert_eq!(call_precompile(1, &input, 2_999)?, None);
+
+    // An invalid signature yields empty output, but the call still succeeds.
+    let invalid_input = words(&["0x1", "0x1d", "0x1", "0x1"]);
+    assert_eq!(call_precompile(1, &invalid_input, 3_000)?, Some(vec![]));
+    Ok(())
+}
+
+#[test]
+fn test_sha256() -> Result<()> {
+    let mut rng = thread_rng();
+    let input: Vec<u8> = (0..100).map(|_| rng.gen()).collect();
+    let expected = Sha256::digest(&input).to_vec();
+    // 60 + 12 * 4 words
+    assert_eq!(call_precompile(2, &input, 108)?, Some(expected));
+    assert_eq!(call_precompile(2, &input, 107)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_rip160() -> Result<()> {
+    let mut rng = thread_rng();
+    let input: Vec<u8> = (0..100).map(|_| rng.gen()).collect();
+    let mut expected = vec![0; 12];
+    expected.extend(Ripemd160::digest(&input));
+    // 600 + 120 * 4 words
+    assert_eq!(call_precompile(3, &input, 1_080)?, Some(expected));
+    assert_eq!(call_precompile(3, &input, 1_079)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_id() -> Result<()> {
+    let mut rng = thread_rng();
+    let input: Vec<u8> = (0..100).map(|_| rng.gen()).collect();
+    // 15 + 3 * 4 words
+    assert_eq!(call_precompile(4, &input, 27)?, Some(input.clone()));
+    assert_eq!(call_precompile(4, &input, 26)?, None);
+    assert_eq!(call_precompile(4, &[], 15)?, Some(vec![]));
+    // Precompiles can also be the target of a DELEGATECALL or STATICCALL.
+    assert_eq!(
+        call_precompile_with("sys_delegatecall", 4, &input, 27)?,
+        Some(input.clone())
+    );
+    assert_eq!(
+        call_precompile_with("sys_staticcall", 4, &input, 27)?,
+        Some(input)
+    );
+    Ok(())
+}
+
+fn expmod_input(base: &[u8], exp: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let mut input = vec![];
+    for len in [base.len(), exp.len(), modulus.len()] {
+        let mut buf = [0; 32];
+        U256::from(len).to_big_endian(&mut buf);
+        input.extend(buf);
+    }
+    input.extend(base);
+    input.extend(exp);
+    input.extend(modulus);
+    input
+}
+
+/// Reference implementation of modular exponentiation, for moduli of at most 256 bits.
+fn expmod(base: U256, exp: U256, modulus: U256) -> U256 {
+    if modulus.is_zero() {
+        return U256::zero();
+    }
+    let mulmod = |a: U256, b: U256| U256::try_from(a.full_mul(b) % U512::from(modulus)).unwrap();
+    let mut result = U256::one() % modulus;
+    for i in (0..exp.bits()).rev() {
+        result = mulmod(result, result);
+        if exp.bit(i) {
+            result = mulmod(result, base);
+        }
+    }
+    result
+}
+
+#[test]
+fn test_expmod() -> Result<()> {
+    // From EIP-198: 3^(p-1) mod p, for the secp256k1 base field order p.
+    let p = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+    let p_minus_1 = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e");
+    let input = expmod_input(&[3], &p_minus_1, &p);
+    // ceil(32 / 8)^2 * 255 / 3
+    assert_eq!(call_precompile(5, &input, 1_360)?, Some(words(&["0x1"])));
+    assert_eq!(call_precompile(5, &input, 1_359)?, None);
+
+    // An empty modulus yields empty output.
+    let input = expmod_input(&[], &[], &[]);
+    assert_eq!(call_precompile(5, &input, 200)?, Some(vec![]));
+
+    // A modulus of 0 yields 0.
+    let input = expmod_input(&[2], &[3], &[0, 0]);
+    assert_eq!(call_precompile(5, &input, 200)?, Some(vec![0, 0]));
+
+    let mut rng = thread_rng();
+    let base: Vec<u8> = (0..40).map(|_| rng.gen()).collect();
+    let exp: Vec<u8> = (0..3).map(|_| rng.gen()).collect();
+    let modulus: Vec<u8> = (0..20).map(|_| rng.gen()).collect();
+    let input = expmod_input(&base, &exp, &modulus);
+    let expected = expmod(
+        U256::try_from(U512::from_big_endian(&base) % U512::from_big_endian(&modulus)).unwrap(),
+        U256::from_big_endian(&exp),
+        U256::from_big_endian(&modulus),
+    );
+    let mut buf = [0; 32];
+    expected.to_big_endian(&mut buf);
+    assert_eq!(call_precompile(5, &input, 200)?, Some(buf[12..].to_vec()));
+    Ok(())
+}
+
+/// Runs the EXPMOD precompile on the given input, and checks its output against `BigUint::modpow`.
+fn check_expmod_large_modulus(base: &[u8], exp: &[u8], modulus: &[u8]) -> Result<()> {
+    let input = expmod_input(base, exp, modulus);
+    let m = BigUint::from_bytes_be(modulus);
+    let result = if m.is_zero() {
+        BigUint::zero()
+    } else {
+        BigUint::from_bytes_be(base).modpow(&BigUint::from_bytes_be(exp), &m)
+    };
+    let result = result.to_bytes_be();
+    let mut expected = vec![0; modulus.len() - result.len()];
+    expected.extend(result);
+    assert_eq!(call_precompile(5, &input, 10_000)?, Some(expected));
+    Ok(())
+}
+
+#[test]
+fn test_expmod_large_modulus() -> Result<()> {
+    let mut rng = thread_rng();
+    let base: Vec<u8> = (0..80).map(|_| rng.gen()).collect();
+    let exp: Vec<u8> = (0..2).map(|_| rng.gen()).collect();
+    let modulus: Vec<u8> = (0..64).map(|_| rng.gen()).collect();
+    check_expmod_large_modulus(&base, &exp, &modulus)?;
+
+    // A wide modulus can still have a small value.
+    let mut modulus = vec![0; 40];
+    modulus[39] = rng.gen();
+    check_expmod_large_modulus(&base, &exp, &modulus)?;
+
+    // A modulus of 0 yields 0.
+    check_expmod_large_modulus(&base, &exp, &[0; 33])?;
+    Ok(())
+}
+
+#[test]
+fn test_bn_add() -> Result<()> {
+    let input = words(&[
+        "0x1feee7ec986e198890cb83be8b8ba09ee953b3f149db6d9bfdaa5c308a33e58d",
+        "0x2051cc9a9edd46231604fd88f351e95ec72a285be93e289ac59cb48561efb2c6",
+        "0x15b64d0a5f329fb672029298be8050f444626e6de11903caffa74b388075be1b",
+        "0x2d9e07340bd5cd7b70687b98f2500ff930a89a30d7b6a3e04b1b4d345319d234",
+    ]);
+    let expected = words(&[
+        "0x18659c0e0a8fedcb8747cf463fc7cfa05f667d84e771d0a9521fc1a550688f0c",
+        "0x283ed10b42703e187e7a808aeb45c6b457bc4cc7d704e53b3348a1e3b0bfa55b",
+    ]);
+    assert_eq!(call_precompile(6, &input, 150)?, Some(expected));
+    assert_eq!(call_precompile(6, &input, 149)?, None);
+
+    // Missing input is treated as zeros, i.e. the point at infinity.
+    assert_eq!(call_precompile(6, &[], 150)?, Some(vec![0; 64]));
+
+    // (0, 3) is not on the curve.
+    let invalid_input = words(&["0x0", "0x3"]);
+    assert_eq!(call_precompile(6, &invalid_input, 150)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_bn_mul() -> Result<()> {
+    let input = words(&[
+        "0x1feee7ec986e198890cb83be8b8ba09ee953b3f149db6d9bfdaa5c308a33e58d",
+        "0x2051cc9a9edd46231604fd88f351e95ec72a285be93e289ac59cb48561efb2c6",
+        "0xabb2a34c0e7956cfe6cef9ddb7e810c45ea19a6ebadd79c21959af09f5ba480a",
+    ]);
+    let expected = words(&[
+        "0xe519344959cc17021fe98878f947f5c1b1675325533a620c1684cfa6367e6c0",
+        "0x7496a7575b0b6a821e19ce780ecc3e0b156e605327798693defeb9f265b7a6f",
+    ]);
+    assert_eq!(call_precompile(7, &input, 6_000)?, Some(expected));
+    assert_eq!(call_precompile(7, &input, 5_999)?, None);
+
+    let invalid_input = words(&["0x0", "0x3", "0x2"]);
+    assert_eq!(call_precompile(7, &invalid_input, 6_000)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_snarkv_empty() -> Result<()> {
+    assert_eq!(call_precompile(8, &[], 45_000)?, Some(words(&["0x1"])));
+    assert_eq!(call_precompile(8, &[], 44_999)?, None);
+    // The input must be a whole number of (G1, G2) pairs.
+    assert_eq!(call_precompile(8, &[0; 100], 1_000_000)?, None);
+    Ok(())
+}
+
+/// Encodes a pair of a G1 point (x, y) and a G2 point (x, x_, y, y_) as in EIP-197, with the
+/// imaginary part of each coordinate first.
+fn snarkv_pair(p: [&str; 2], q: [&str; 4]) -> Vec<u8> {
+    words(&[p[0], p[1], q[1], q[0], q[3], q[2]])
+}
+
+#[test]
+fn test_snarkv() -> Result<()> {
+    let g1 = ["0x1", "0x2"];
+    let g1_neg = [
+        "0x1",
+        "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45",
+    ];
+    let g1_double = [
+        "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3",
+        "0x15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+    ];
+    let g2 = [
+        "0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+        "0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+        "0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        "0x90689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+    ];
+    let g2_double = [
+        "0x27dc7234fd11d3e8c36c59277c3e6f149d5cd3cfa9a62aee49f8130962b4b3b9",
+        "0x203e205db4f19b37b60121b83a7333706db86431c6d835849957ed8c3928ad79",
+        "0x4bb53b8977e5f92a0bc372742c4830944a59b4fe6b1c0466e2a6dad122b5d2e",
+        "0x195e8aa5b7827463722b8c153931579d3505566b4edf48d498e185f0509de152",
+    ];
+    let zero = ["0x0"; 4];
+
+    // e(2 G1, G2) e(-G1, 2 G2) = 1.
+    let mut input = snarkv_pair(g1_double, g2);
+    input.extend(snarkv_pair(g1_neg, g2_double));
+    assert_eq!(call_precompile(8, &input, 113_000)?, Some(words(&["0x1"])));
+    assert_eq!(call_precompile(8, &input, 112_999)?, None);
+
+    // e(G1, G2)^2 != 1.
+    let mut input = snarkv_pair(g1, g2);
+    input.extend(snarkv_pair(g1, g2));
+    assert_eq!(call_precompile(8, &input, 113_000)?, Some(words(&["0x0"])));
+
+    // Pairs with the identity of G2 contribute nothing.
+    let input = snarkv_pair(g1, zero);
+    assert_eq!(call_precompile(8, &input, 79_000)?, Some(words(&["0x1"])));
+
+    // A point of the twisted curve outside of G2 is invalid.
+    let not_g2 = [
+        "0x5",
+        "0x7",
+        "0xb7acc216a621e5c67c9b30166500219eea2ccfe4316164ae4e33e83815be26a",
+        "0x11149b219bf0a6f98c9c7f2f68085bef34811df05e24592c10b170ce7fcc729b",
+    ];
+    let input = snarkv_pair(g1, not_g2);
+    assert_eq!(call_precompile(8, &input, 79_000)?, None);
+    Ok(())
+}
+
+/// Test vectors 4 to 6 from EIP-152, which share their h, m and t.
+fn blake2_f_input(rounds: u32, f: u8) -> Vec<u8> {
+    let mut input = rounds.to_be_bytes().to_vec();
+    input.extend(hex!("48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b"));
+    input.extend(hex!(
+        "6162630000000000000000000000000000000000000000000000000000000000"
+    ));
+    input.extend([0; 96]);
+    input.extend(hex!("03000000000000000000000000000000"));
+    input.push(f);
+    input
+}
+
+#[test]
+fn test_blake2_f() -> Result<()> {
+    assert_eq!(
+        call_precompile(9, &blake2_f_input(0, 1), 0)?,
+        Some(hex!("08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b").to_vec())
+    );
+    assert_eq!(
+        call_precompile(9, &blake2_f_input(12, 1), 12)?,
+        Some(hex!("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923").to_vec())
+    );
+    assert_eq!(
+        call_precompile(9, &blake2_f_input(12, 0), 12)?,
+        Some(hex!("75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735").to_vec())
+    );
+    assert_eq!(call_precompile(9, &blake2_f_input(12, 1), 11)?, None);
+
+    // Invalid final block flag.
+    assert_eq!(call_precompile(9, &blake2_f_input(12, 2), 12)?, None);
+    // Invalid input length.
+    let mut input = blake2_f_input(12, 1);
+    input.pop();
+    assert_eq!(call_precompile(9, &input, 12)?, None);
+    Ok(())
+}
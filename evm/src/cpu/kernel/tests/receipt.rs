@@ -0,0 +1,172 @@
+use anyhow::Result;
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{Address, BigEndianHash, Bloom, BloomInput, H256, U256};
+use rand::{thread_rng, Rng};
+use rlp::RlpStream;
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{load_receipts_trie, run_void_syscall};
+use crate::memory::segments::Segment;
+
+struct Log {
+    address: Address,
+    topics: Vec<H256>,
+    data: Vec<u8>,
+}
+
+fn encode_receipt(status: bool, cumulative_gas_used: U256, logs: &[Log]) -> Vec<u8> {
+    let mut bloom = Bloom::default();
+    for log in logs {
+        bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+        for topic in &log.topics {
+            bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+        }
+    }
+
+    let mut stream = RlpStream::new_list(4);
+    stream.append(&(status as u8));
+    stream.append(&cumulative_gas_used);
+    stream.append(&bloom.as_bytes().to_vec());
+    stream.begin_list(logs.len());
+    for log in logs {
+        stream.begin_list(3);
+        stream.append(&log.address);
+        stream.append_list(&log.topics);
+        stream.append(&log.data);
+    }
+    stream.out().to_vec()
+}
+
+fn receipt_key(txn_number: usize) -> Nibbles {
+    Nibbles::from_bytes_be(&rlp::encode(&txn_number)).unwrap()
+}
+
+fn prepare_interpreter(receipts_trie: PartialTrie) -> Result<Interpreter<'static>> {
+    let mut interpreter = load_receipts_trie(receipts_trie)?;
+    interpreter.set_context_metadata_field(0, ContextMetadata::GasLimit, 1_000_000.into());
+    Ok(interpreter)
+}
+
+fn hash_receipt_trie(interpreter: &mut Interpreter) -> Result<H256> {
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["mpt_hash_receipt_trie"];
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack().len(), 1);
+    Ok(H256::from_uint(&interpreter.pop()))
+}
+
+/// Emit a log from context 0, with data taken from its main memory.
+fn log(interpreter: &mut Interpreter, offset: usize, size: usize, topics: &[H256]) -> Result<()> {
+    let mut args = vec![offset.into(), size.into()];
+    args.extend(topics.iter().map(|topic| topic.into_uint()));
+    run_void_syscall(interpreter, &format!("sys_log{}", topics.len()), &args)
+}
+
+fn process_receipt(interpreter: &mut Interpreter, status: bool, gas_used: U256) -> Result<()> {
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["process_receipt"];
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.push(gas_used);
+    interpreter.push((status as u8).into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+    Ok(())
+}
+
+#[test]
+fn test_receipt_with_logs() -> Result<()> {
+    let mut rng = thread_rng();
+    let address: Address = rng.gen();
+    let mut interpreter = prepare_interpreter(PartialTrie::Empty)?;
+    interpreter.set_context_metadata_field(
+        0,
+        ContextMetadata::Address,
+        U256::from_big_endian(address.as_bytes()),
+    );
+    let mut memory: Vec<u8> = (0..100).map(|_| rng.gen()).collect();
+    memory[60] = 0x7f;
+    interpreter.generation_state.memory.contexts[0].segments[Segment::MainMemory as usize]
+        .content = memory.iter().copied().map(U256::from).collect();
+
+    // Cover data which is empty, a single byte below 0x80, short and long.
+    let logs = [
+        (0, 0, vec![]),
+        (60, 1, vec![rng.gen()]),
+        (10, 40, vec![rng.gen(), rng.gen()]),
+        (0, 100, vec![rng.gen(), rng.gen(), rng.gen(), rng.gen()]),
+    ];
+    for (offset, size, topics) in &logs {
+        log(&mut interpreter, *offset, *size, topics)?;
+    }
+    process_receipt(&mut interpreter, true, 50_000.into())?;
+
+    let logs = logs.map(|(offset, size, topics)| Log {
+        address,
+        topics,
+        data: memory[offset..offset + size].to_vec(),
+    });
+    let mut expected = PartialTrie::Empty;
+    expected.insert(receipt_key(0), encode_receipt(true, 50_000.into(), &logs));
+    assert_eq!(hash_receipt_trie(&mut interpreter)?, expected.calc_hash());
+    Ok(())
+}
+
+#[test]
+fn test_receipts_trie() -> Result<()> {
+    // Start with an existing receipt, to check that receipts are loaded and hashed correctly.
+    let mut expected = PartialTrie::Empty;
+    expected.insert(receipt_key(0), encode_receipt(true, 21_000.into(), &[]));
+    let mut interpreter = prepare_interpreter(expected.clone())?;
+    assert_eq!(hash_receipt_trie(&mut interpreter)?, expected.calc_hash());
+    interpreter.set_global_metadata_field(GlobalMetadata::TxnNumber, 1.into());
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockGasUsed, 21_000.into());
+
+    // Cover keys of each RLP length, and both statuses.
+    let mut cumulative_gas_used = U256::from(21_000);
+    for (i, txn_number) in [1, 0x7f, 0x80, 0x1234].into_iter().enumerate() {
+        let status = i % 2 == 0;
+        let gas_used = U256::from(30_000 + i);
+        interpreter.set_global_metadata_field(GlobalMetadata::TxnNumber, txn_number.into());
+        process_receipt(&mut interpreter, status, gas_used)?;
+
+        cumulative_gas_used += gas_used;
+        expected.insert(
+            receipt_key(txn_number),
+            encode_receipt(status, cumulative_gas_used, &[]),
+        );
+    }
+    assert_eq!(hash_receipt_trie(&mut interpreter)?, expected.calc_hash());
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::TxnNumber),
+        0x1235.into()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_typed_receipts() -> Result<()> {
+    let mut interpreter = prepare_interpreter(PartialTrie::Empty)?;
+    let mut expected = PartialTrie::Empty;
+    let mut cumulative_gas_used = U256::zero();
+    for (txn_number, txn_type) in [0u8, 1, 2].into_iter().enumerate() {
+        let gas_used = U256::from(21_000 + txn_number);
+        interpreter.set_txn_field(NormalizedTxnField::Type, txn_type.into());
+        process_receipt(&mut interpreter, true, gas_used)?;
+
+        // Legacy receipts have no prefix, while others are prefixed with their type.
+        cumulative_gas_used += gas_used;
+        let mut receipt = vec![];
+        if txn_type != 0 {
+            receipt.push(txn_type);
+        }
+        receipt.extend(encode_receipt(true, cumulative_gas_used, &[]));
+        expected.insert(receipt_key(txn_number), receipt);
+    }
+    assert_eq!(hash_receipt_trie(&mut interpreter)?, expected.calc_hash());
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use anyhow::Result;
+use ethereum_types::U256;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
+use crate::cpu::kernel::interpreter::{run, Interpreter};
+
+const SDIV: u8 = 0x05;
+const SMOD: u8 = 0x07;
+const SIGNEXTEND: u8 = 0x0b;
+const SLT: u8 = 0x12;
+const SGT: u8 = 0x13;
+const SAR: u8 = 0x1d;
+
+const OPS: [(u8, &str); 6] = [
+    (SDIV, "sys_sdiv"),
+    (SMOD, "sys_smod"),
+    (SIGNEXTEND, "sys_signextend"),
+    (SLT, "sys_slt"),
+    (SGT, "sys_sgt"),
+    (SAR, "sys_sar"),
+];
+
+/// The two's complement representation of `-x`.
+fn neg(x: u64) -> U256 {
+    U256::zero().overflowing_sub(x.into()).0
+}
+
+fn min_signed() -> U256 {
+    U256::one() << 255
+}
+
+fn max_signed() -> U256 {
+    min_signed() - 1
+}
+
+/// Values around the boundaries of the signed and unsigned ranges.
+fn edge_cases() -> Vec<U256> {
+    vec![
+        U256::zero(),
+        U256::one(),
+        2.into(),
+        7.into(),
+        31.into(),
+        32.into(),
+        255.into(),
+        256.into(),
+        0x7f.into(),
+        0x80.into(),
+        0xff.into(),
+        neg(1),
+        neg(2),
+        neg(7),
+        min_signed(),
+        min_signed() + 1,
+        max_signed(),
+    ]
+}
+
+/// Run the kernel's syscall handler for a signed operation, with `a` on top of the stack.
+fn run_syscall(label: &str, a: U256, b: U256) -> Result<U256> {
+    let kexit_info = 0xdeadbeefu32.into();
+    let initial_stack = vec![b, a, kexit_info];
+    let mut interpreter = Interpreter::new_with_kernel(KERNEL.global_labels[label], initial_stack);
+    interpreter.set_context_metadata_field(0, ContextMetadata::GasLimit, 1_000_000.into());
+    interpreter.run()?;
+    let stack = interpreter.stack();
+    assert_eq!(stack.len(), 1);
+    Ok(stack[0])
+}
+
+/// Run the interpreter's native implementation of a signed operation, with `a` on top of the stack.
+fn run_opcode(opcode: u8, a: U256, b: U256) -> Result<U256> {
+    let code = [opcode, 0x63, 0xde, 0xad, 0xbe, 0xef, 0x56]; // opcode, PUSH4 deadbeef, JUMP
+    let stack = run(&code, 0, vec![b, a], &KERNEL.prover_inputs)?
+        .stack()
+        .to_vec();
+    assert_eq!(stack.len(), 1);
+    Ok(stack[0])
+}
+
+fn check(opcode: u8, a: U256, b: U256, expected: U256) -> Result<()> {
+    let (_, label) = OPS.iter().find(|(op, _)| *op == opcode).unwrap();
+    assert_eq!(run_syscall(label, a, b)?, expected, "{label}({a:x}, {b:x})");
+    assert_eq!(run_opcode(opcode, a, b)?, expected, "{label}({a:x}, {b:x})");
+    Ok(())
+}
+
+#[test]
+fn test_sdiv() -> Result<()> {
+    check(SDIV, 7.into(), 2.into(), 3.into())?;
+    // Division rounds towards zero.
+    check(SDIV, neg(7), 2.into(), neg(3))?;
+    check(SDIV, 7.into(), neg(2), neg(3))?;
+    check(SDIV, neg(7), neg(2), 3.into())?;
+    check(SDIV, neg(1), neg(1), 1.into())?;
+    check(SDIV, neg(1), 2.into(), 0.into())?;
+    // Division by zero yields zero.
+    check(SDIV, neg(7), 0.into(), 0.into())?;
+    check(SDIV, 0.into(), neg(7), 0.into())?;
+    // The only overflowing case, -2^255 / -1, wraps around to -2^255.
+    check(SDIV, min_signed(), neg(1), min_signed())?;
+    check(SDIV, min_signed(), 1.into(), min_signed())?;
+    check(SDIV, min_signed(), min_signed(), 1.into())?;
+    check(SDIV, max_signed(), min_signed(), 0.into())?;
+    Ok(())
+}
+
+#[test]
+fn test_smod() -> Result<()> {
+    check(SMOD, 7.into(), 2.into(), 1.into())?;
+    // The result takes the sign of the dividend.
+    check(SMOD, neg(7), 2.into(), neg(1))?;
+    check(SMOD, 7.into(), neg(2), 1.into())?;
+    check(SMOD, neg(7), neg(2), neg(1))?;
+    check(SMOD, neg(8), neg(3), neg(2))?;
+    check(SMOD, neg(8), 4.into(), 0.into())?;
+    // Reduction modulo zero yields zero.
+    check(SMOD, neg(7), 0.into(), 0.into())?;
+    check(SMOD, min_signed(), neg(1), 0.into())?;
+    check(SMOD, min_signed(), max_signed(), neg(1))?;
+    check(SMOD, max_signed(), min_signed(), max_signed())?;
+    Ok(())
+}
+
+#[test]
+fn test_signextend() -> Result<()> {
+    check(SIGNEXTEND, 0.into(), 0x7f.into(), 0x7f.into())?;
+    check(SIGNEXTEND, 0.into(), 0xff.into(), neg(1))?;
+    check(SIGNEXTEND, 0.into(), 0x80.into(), neg(0x80))?;
+    // Bits above the extended byte are discarded.
+    check(SIGNEXTEND, 0.into(), 0x12345678.into(), 0x78.into())?;
+    check(SIGNEXTEND, 0.into(), 0x123456f8.into(), neg(8))?;
+    check(SIGNEXTEND, 1.into(), 0x12348000.into(), neg(0x8000))?;
+    check(SIGNEXTEND, 1.into(), 0x12347fff.into(), 0x7fff.into())?;
+    check(SIGNEXTEND, 30.into(), max_signed(), neg(1))?;
+    check(SIGNEXTEND, 30.into(), U256::MAX >> 9, U256::MAX >> 9)?;
+    check(
+        SIGNEXTEND,
+        30.into(),
+        (U256::MAX >> 8) - (U256::MAX >> 9),
+        !(U256::MAX >> 9),
+    )?;
+    // Extending from byte 31 or beyond leaves the value unchanged.
+    check(SIGNEXTEND, 31.into(), 0xff.into(), 0xff.into())?;
+    check(SIGNEXTEND, 32.into(), 0xff.into(), 0xff.into())?;
+    check(SIGNEXTEND, U256::MAX, 0xff.into(), 0xff.into())?;
+    check(SIGNEXTEND, min_signed() + 1, 0xff.into(), 0xff.into())?;
+    Ok(())
+}
+
+#[test]
+fn test_slt_sgt() -> Result<()> {
+    for (a, b) in [
+        (neg(1), 0.into()),
+        (neg(2), neg(1)),
+        (min_signed(), max_signed()),
+        (min_signed(), neg(1)),
+        (min_signed(), min_signed() + 1),
+        (0.into(), 1.into()),
+        (max_signed() - 1, max_signed()),
+    ] {
+        check(SLT, a, b, 1.into())?;
+        check(SLT, b, a, 0.into())?;
+        check(SGT, a, b, 0.into())?;
+        check(SGT, b, a, 1.into())?;
+    }
+    for a in [0.into(), neg(1), min_signed(), max_signed()] {
+        check(SLT, a, a, 0.into())?;
+        check(SGT, a, a, 0.into())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sar() -> Result<()> {
+    check(SAR, 1.into(), 16.into(), 8.into())?;
+    check(SAR, 1.into(), neg(16), neg(8))?;
+    // Negative values are rounded towards negative infinity.
+    check(SAR, 1.into(), neg(3), neg(2))?;
+    check(SAR, 4.into(), neg(1), neg(1))?;
+    check(SAR, 0.into(), neg(1), neg(1))?;
+    check(SAR, 255.into(), min_signed(), neg(1))?;
+    check(SAR, 254.into(), min_signed(), neg(2))?;
+    check(SAR, 254.into(), max_signed(), 1.into())?;
+    // Shifting by 256 or more leaves only copies of the sign bit.
+    check(SAR, 256.into(), neg(1), neg(1))?;
+    check(SAR, 256.into(), min_signed(), neg(1))?;
+    check(SAR, 256.into(), max_signed(), 0.into())?;
+    check(SAR, U256::MAX, neg(7), neg(1))?;
+    check(SAR, U256::MAX, 7.into(), 0.into())?;
+    Ok(())
+}
+
+/// Check that the kernel and the interpreter agree on all pairs of edge cases, as well as on some
+/// random inputs.
+#[test]
+fn test_signed_ops_consistency() -> Result<()> {
+    let mut rng = thread_rng();
+    let mut inputs = edge_cases();
+    inputs.extend((0..4).map(|_| U256([0; 4].map(|_| rng.gen()))));
+
+    for (opcode, label) in OPS {
+        for &a in &inputs {
+            for &b in &inputs {
+                assert_eq!(
+                    run_syscall(label, a, b)?,
+                    run_opcode(opcode, a, b)?,
+                    "{label}({a:x}, {b:x})"
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use anyhow::Result;
+use ethereum_types::U256;
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{
+    hash_state_trie, prepare_interpreter, run_syscall, run_void_syscall, state_trie, TestAccount,
+};
+
+const INITIAL_GAS: u64 = 1_000_000;
+
+/// An account with the given storage, which is the current account of context 0, as if a
+/// transaction had just started executing its code.
+fn prepare_account(storage: Vec<(U256, U256)>) -> Result<(TestAccount, Interpreter<'static>)> {
+    let account = TestAccount {
+        storage,
+        ..TestAccount::new(1, 0x1234, vec![0xab])
+    };
+    let interpreter = prepare_interpreter(&[account.clone()], INITIAL_GAS)?;
+    Ok((account, interpreter))
+}
+
+fn sload(interpreter: &mut Interpreter, slot: U256) -> Result<U256> {
+    run_syscall(interpreter, "sys_sload", &[slot])
+}
+
+fn sstore(interpreter: &mut Interpreter, slot: U256, value: U256) -> Result<()> {
+    run_void_syscall(interpreter, "sys_sstore", &[slot, value])
+}
+
+fn test_storage() -> Vec<(U256, U256)> {
+    vec![
+        (0.into(), 1.into()),
+        (1.into(), 0x7f.into()),
+        (2.into(), 0x80.into()),
+        (3.into(), 0x1234.into()),
+        (U256::MAX, U256::MAX),
+    ]
+}
+
+#[test]
+fn test_storage_hash() -> Result<()> {
+    let (account, mut interpreter) = prepare_account(test_storage())?;
+
+    let expected = state_trie(&[account]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_sload() -> Result<()> {
+    let (account, mut interpreter) = prepare_account(test_storage())?;
+
+    for &(slot, value) in &account.storage {
+        assert_eq!(sload(&mut interpreter, slot)?, value);
+    }
+    // Missing slots read as zero.
+    assert_eq!(sload(&mut interpreter, 4.into())?, 0.into());
+    assert_eq!(sload(&mut interpreter, U256::MAX - 1)?, 0.into());
+    Ok(())
+}
+
+#[test]
+fn test_sstore() -> Result<()> {
+    let mut rng = thread_rng();
+    let (mut account, mut interpreter) = prepare_account(test_storage())?;
+
+    let new_slot = U256(rng.gen());
+    let new_value = U256(rng.gen());
+    let updated_value = U256(rng.gen());
+    sstore(&mut interpreter, new_slot, new_value)?;
+    sstore(&mut interpreter, 3.into(), updated_value)?;
+    // Writing the current value again should leave the trie unchanged.
+    sstore(&mut interpreter, 1.into(), 0x7f.into())?;
+
+    assert_eq!(sload(&mut interpreter, new_slot)?, new_value);
+    assert_eq!(sload(&mut interpreter, 3.into())?, updated_value);
+    assert_eq!(sload(&mut interpreter, 1.into())?, 0x7f.into());
+
+    account.storage[3].1 = updated_value;
+    account.storage.push((new_slot, new_value));
+    let expected = state_trie(&[account]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_sstore_zero() -> Result<()> {
+    let (mut account, mut interpreter) = prepare_account(test_storage())?;
+
+    // Zeroing a slot deletes it from the storage trie.
+    sstore(&mut interpreter, 2.into(), 0.into())?;
+    sstore(&mut interpreter, U256::MAX, 0.into())?;
+    // Zeroing a missing slot should leave the trie unchanged.
+    sstore(&mut interpreter, 4.into(), 0.into())?;
+
+    assert_eq!(sload(&mut interpreter, 2.into())?, 0.into());
+    assert_eq!(sload(&mut interpreter, U256::MAX)?, 0.into());
+
+    account
+        .storage
+        .retain(|&(slot, _)| slot != 2.into() && slot != U256::MAX);
+    let expected = state_trie(&[account.clone()]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+
+    // Clearing every slot leaves an empty storage trie.
+    for &(slot, _) in &account.storage {
+        sstore(&mut interpreter, slot, 0.into())?;
+    }
+    account.storage.clear();
+    let expected = state_trie(&[account]).calc_hash();
+    assert_eq!(hash_state_trie(&mut interpreter)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_sstore_original_value() -> Result<()> {
+    let (_, mut interpreter) = prepare_account(test_storage())?;
+
+    // A clean slot costs a reset; subsequent writes to it are charged as warm accesses, and are
+    // refunded once the original value is restored.
+    sstore(&mut interpreter, 3.into(), 5.into())?;
+    sstore(&mut interpreter, 3.into(), 6.into())?;
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::RefundCounter),
+        0.into()
+    );
+    sstore(&mut interpreter, 3.into(), 0x1234.into())?;
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::RefundCounter),
+        2_800.into()
+    );
+    Ok(())
+}
+
+/// The SSTORE test cases from EIP-3529, given as `(original, values written, gas, refund)`. The
+/// gas figures exclude the `PUSH` instructions of the original test code.
+const EIP_3529_CASES: [(u64, &[u64], u64, u64); 17] = [
+    (0, &[0, 0], 200, 0),
+    (0, &[0, 1], 20_100, 0),
+    (0, &[1, 0], 20_100, 19_900),
+    (0, &[1, 2], 20_100, 0),
+    (0, &[1, 1], 20_100, 0),
+    (1, &[0, 0], 3_000, 4_800),
+    (1, &[0, 1], 3_000, 2_800),
+    (1, &[0, 2], 3_000, 0),
+    (1, &[2, 0], 3_000, 4_800),
+    (1, &[2, 3], 3_000, 0),
+    (1, &[2, 1], 3_000, 2_800),
+    (1, &[2, 2], 3_000, 0),
+    (1, &[1, 0], 3_000, 4_800),
+    (1, &[1, 2], 3_000, 0),
+    (1, &[1, 1], 200, 0),
+    (0, &[1, 0, 1], 40_100, 19_900),
+    (1, &[0, 1, 0], 5_900, 7_600),
+];
+
+#[test]
+fn test_sstore_gas() -> Result<()> {
+    let sstore_gas = KERNEL.global_labels["sstore_gas"];
+
+    for (original, values, expected_gas, expected_refund) in EIP_3529_CASES {
+        let mut interpreter = Interpreter::new_with_kernel(sstore_gas, vec![]);
+        let mut gas = 0;
+        let mut current = original;
+        for &new in values {
+            interpreter.generation_state.registers.program_counter = sstore_gas;
+            interpreter.push(0xDEADBEEFu32.into());
+            interpreter.push(original.into());
+            interpreter.push(current.into());
+            interpreter.push(new.into());
+            interpreter.run()?;
+            gas += interpreter.pop().as_u64();
+            assert_eq!(interpreter.stack(), vec![]);
+            current = new;
+        }
+
+        let refund = interpreter.get_global_metadata_field(GlobalMetadata::RefundCounter);
+        assert_eq!(gas, expected_gas, "original {original}, values {values:?}");
+        assert_eq!(
+            refund,
+            expected_refund.into(),
+            "original {original}, values {values:?}"
+        );
+    }
+    Ok(())
+}
@@ -1 +1,3 @@
 mod parse_type_0_txn;
+mod parse_type_1_txn;
+mod parse_type_2_txn;
@@ -38,6 +38,7 @@ fn process_type_0_txn() -> Result<()> {
 
     interpreter.run()?;
 
+    assert_eq!(interpreter.get_txn_field(Type), 0.into());
     assert_eq!(interpreter.get_txn_field(ChainIdPresent), 0.into());
     assert_eq!(interpreter.get_txn_field(ChainId), 0.into());
     assert_eq!(interpreter.get_txn_field(Nonce), 5.into());
@@ -64,3 +65,30 @@ fn process_type_0_txn() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn process_type_0_contract_creation_txn() -> Result<()> {
+    let process_type_0_txn = KERNEL.global_labels["process_type_0_txn"];
+    let process_normalized_txn = KERNEL.global_labels["process_normalized_txn"];
+
+    let retaddr = 0xDEADBEEFu32.into();
+    let mut interpreter = Interpreter::new_with_kernel(process_type_0_txn, vec![retaddr]);
+    interpreter.halt_offsets.push(process_normalized_txn);
+
+    // Signed with the same key as above. The "to" field is empty, and the data is init code which
+    // deploys 0x602a60005500.
+    interpreter.set_rlp_memory(hex!("f85b050a830186a080648f65602a600055006000526006601af31ba0bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020da012516fa2481c6bcf01da591ae25061d03371fdec7c2902f294daa7d8911ba070").to_vec());
+
+    interpreter.run()?;
+
+    assert_eq!(interpreter.get_txn_field(To), 0.into());
+    assert_eq!(interpreter.get_txn_field(Value), 100.into());
+    assert_eq!(interpreter.get_txn_field(DataLen), 15.into());
+    // The sender can only be recovered if the empty "to" field was part of the signed payload.
+    assert_eq!(
+        interpreter.get_txn_field(Origin),
+        U256::from_big_endian(&hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"))
+    );
+
+    Ok(())
+}
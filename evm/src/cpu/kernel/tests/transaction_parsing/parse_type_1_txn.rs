@@ -0,0 +1,88 @@
+use anyhow::Result;
+use ethereum_types::U256;
+use hex_literal::hex;
+use NormalizedTxnField::*;
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::memory::segments::Segment;
+
+#[test]
+fn process_type_1_txn() -> Result<()> {
+    let process_type_1_txn = KERNEL.global_labels["process_type_1_txn"];
+    let process_normalized_txn = KERNEL.global_labels["process_normalized_txn"];
+
+    let retaddr = 0xDEADBEEFu32.into();
+    let mut interpreter = Interpreter::new_with_kernel(process_type_1_txn, vec![retaddr]);
+
+    // When we reach process_normalized_txn, we're done with parsing and normalizing.
+    // Processing normalized transactions is outside the scope of this test.
+    interpreter.halt_offsets.push(process_normalized_txn);
+
+    // Signed with the same key as the type 0 transactions, with the fields
+    //     chain_id=1, nonce=5, gas_price=10, gas=22_000, to=ZERO_ADDRESS, value=100,
+    //     data=b'\x42\x42',
+    //     access_list=[
+    //         (0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae, [0, 7]),
+    //         (0xbb9bc244d798123fde783fcc1c72d3bb8c189413, []),
+    //     ]
+    interpreter.set_rlp_memory(hex!("01f8d601050a8255f094000000000000000000000000000000000000000064824242f872f85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000007d694bb9bc244d798123fde783fcc1c72d3bb8c189413c080a0a6a54c131efc0638f12c3fb01dfa702131e7ea9138bea3a896c51baaa6e7bb90a06b9770d58555c82587ad1f2ee188e6486050d943b46a2113dba2eaf81f0fb2ad").to_vec());
+
+    interpreter.run()?;
+
+    assert_eq!(interpreter.get_txn_field(Type), 1.into());
+    assert_eq!(interpreter.get_txn_field(ChainIdPresent), 1.into());
+    assert_eq!(interpreter.get_txn_field(ChainId), 1.into());
+    assert_eq!(interpreter.get_txn_field(Nonce), 5.into());
+    assert_eq!(interpreter.get_txn_field(MaxPriorityFeePerGas), 10.into());
+    assert_eq!(interpreter.get_txn_field(MaxFeePerGas), 10.into());
+    assert_eq!(interpreter.get_txn_field(GasLimit), 22_000.into());
+    assert_eq!(interpreter.get_txn_field(To), 0.into());
+    assert_eq!(interpreter.get_txn_field(Value), 100.into());
+    assert_eq!(interpreter.get_txn_field(DataLen), 2.into());
+    assert_eq!(interpreter.get_txn_data(), &[0x42.into(), 0x42.into()]);
+    assert_eq!(interpreter.get_txn_field(AccessListAddresses), 2.into());
+    assert_eq!(interpreter.get_txn_field(AccessListStorageKeys), 2.into());
+    assert_eq!(interpreter.get_txn_field(YParity), 0.into());
+    assert_eq!(
+        interpreter.get_txn_field(R),
+        U256::from_big_endian(&hex!(
+            "a6a54c131efc0638f12c3fb01dfa702131e7ea9138bea3a896c51baaa6e7bb90"
+        ))
+    );
+    assert_eq!(
+        interpreter.get_txn_field(S),
+        U256::from_big_endian(&hex!(
+            "6b9770d58555c82587ad1f2ee188e6486050d943b46a2113dba2eaf81f0fb2ad"
+        ))
+    );
+    // The sender can only be recovered if the signed data was reconstructed correctly.
+    assert_eq!(
+        interpreter.get_txn_field(Origin),
+        U256::from_big_endian(&hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"))
+    );
+
+    // The access list's addresses and storage keys are warm.
+    let address_1 = U256::from_big_endian(&hex!("de0b295669a9fd93d5f28d9ec85e40f4cb697bae"));
+    let address_2 = U256::from_big_endian(&hex!("bb9bc244d798123fde783fcc1c72d3bb8c189413"));
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::AccessedAddressesLen),
+        2.into()
+    );
+    assert_eq!(
+        interpreter.get_memory_segment(Segment::AccessedAddresses)[..2],
+        [address_1, address_2]
+    );
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::AccessedStorageKeysLen),
+        4.into()
+    );
+    assert_eq!(
+        interpreter.get_memory_segment(Segment::AccessedStorageKeys)[..4],
+        [address_1, 0.into(), address_1, 7.into()]
+    );
+
+    Ok(())
+}
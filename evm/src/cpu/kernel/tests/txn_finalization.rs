@@ -0,0 +1,340 @@
+use anyhow::Result;
+use ethereum_types::{Address, U256};
+use rand::{thread_rng, Rng};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::constants::txn_fields::NormalizedTxnField;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::account_fixtures::{
+    address_to_u256, hash_state_trie, load_accounts, state_trie, TestAccount,
+};
+
+const GAS_LIMIT: u64 = 100_000;
+
+/// Run a message transaction from `accounts[0]` to `accounts[1]`, with a gas limit of `GAS_LIMIT`,
+/// the given gas price, and the given block base fee and beneficiary.
+fn run_txn(
+    accounts: &[TestAccount],
+    gas_price: u64,
+    base_fee: u64,
+    beneficiary: Address,
+) -> Result<Interpreter<'static>> {
+    run_txn_with_value(accounts, 0, gas_price, base_fee, beneficiary)
+}
+
+/// Like `run_txn`, but the transaction also transfers the given value.
+fn run_txn_with_value(
+    accounts: &[TestAccount],
+    value: u64,
+    gas_price: u64,
+    base_fee: u64,
+    beneficiary: Address,
+) -> Result<Interpreter<'static>> {
+    let mut interpreter = prepare_txn(accounts, value, gas_price, base_fee, beneficiary)?;
+    run_prepared_txn(&mut interpreter)?;
+    Ok(interpreter)
+}
+
+/// Set up the transaction that `run_txn_with_value` runs, in a block with room for it, without
+/// running it yet.
+fn prepare_txn(
+    accounts: &[TestAccount],
+    value: u64,
+    gas_price: u64,
+    base_fee: u64,
+    beneficiary: Address,
+) -> Result<Interpreter<'static>> {
+    let mut interpreter = load_accounts(accounts)?;
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockGasLimit, GAS_LIMIT.into());
+    interpreter.set_global_metadata_field(GlobalMetadata::BlockBaseFee, base_fee.into());
+    interpreter.set_global_metadata_field(
+        GlobalMetadata::BlockBeneficiary,
+        address_to_u256(beneficiary),
+    );
+    interpreter.set_txn_field(
+        NormalizedTxnField::Origin,
+        address_to_u256(accounts[0].address),
+    );
+    interpreter.set_txn_field(NormalizedTxnField::Nonce, accounts[0].nonce.into());
+    interpreter.set_txn_field(NormalizedTxnField::To, address_to_u256(accounts[1].address));
+    interpreter.set_txn_field(NormalizedTxnField::Value, value.into());
+    interpreter.set_txn_field(NormalizedTxnField::GasLimit, GAS_LIMIT.into());
+    interpreter.set_txn_field(NormalizedTxnField::MaxFeePerGas, gas_price.into());
+    interpreter.set_txn_field(NormalizedTxnField::MaxPriorityFeePerGas, gas_price.into());
+    Ok(interpreter)
+}
+
+fn run_prepared_txn(interpreter: &mut Interpreter) -> Result<()> {
+    interpreter.generation_state.registers.program_counter =
+        KERNEL.global_labels["process_normalized_txn"];
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+    Ok(())
+}
+
+/// Checks that the transaction was skipped, leaving the given accounts as they were.
+fn assert_skipped(interpreter: &mut Interpreter, accounts: &[TestAccount]) -> Result<()> {
+    assert_eq!(block_gas_used(interpreter), 0.into());
+    assert_eq!(
+        interpreter.get_global_metadata_field(GlobalMetadata::TxnNumber),
+        0.into()
+    );
+    assert_eq!(
+        hash_state_trie(interpreter)?,
+        state_trie(accounts).calc_hash()
+    );
+    Ok(())
+}
+
+fn block_gas_used(interpreter: &Interpreter) -> U256 {
+    interpreter.get_global_metadata_field(GlobalMetadata::BlockGasUsed)
+}
+
+/// The code `PUSH20 address, SELFDESTRUCT`.
+fn selfdestruct_to(address: Address) -> Vec<u8> {
+    let mut code = vec![0x73];
+    code.extend_from_slice(address.as_bytes());
+    code.push(0xff);
+    code
+}
+
+/// The code `CALL(0xffff, address, 0, 0, 0, 0, 0)`, followed by the given final opcode.
+fn call_then(address: Address, last_opcode: u8) -> Vec<u8> {
+    let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00];
+    code.push(0x73);
+    code.extend_from_slice(address.as_bytes());
+    code.extend_from_slice(&[0x61, 0xff, 0xff, 0xf1, last_opcode]);
+    code
+}
+
+#[test]
+fn test_selfdestruct() -> Result<()> {
+    let recipient: Address = thread_rng().gen();
+    let sender = TestAccount::new(1, 1000, vec![]);
+    let contract = TestAccount::new(1, 500, selfdestruct_to(recipient));
+    let mut interpreter = run_txn(&[sender.clone(), contract], 0, 0, Address::zero())?;
+
+    // The PUSH20, SELFDESTRUCT itself, the access to the cold recipient, and the recipient's
+    // creation.
+    assert_eq!(
+        block_gas_used(&interpreter),
+        (21000 + 3 + 5000 + 2600 + 25000).into()
+    );
+    // The contract is deleted, and its balance sent to the recipient.
+    let sender_after = TestAccount { nonce: 2, ..sender };
+    let recipient_after = TestAccount {
+        address: recipient,
+        ..TestAccount::new(0, 500, vec![])
+    };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after, recipient_after]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_selfdestruct_to_self() -> Result<()> {
+    let sender = TestAccount::new(1, 1000, vec![]);
+    let contract = TestAccount::new(1, 500, vec![0x30, 0xff]); // SELFDESTRUCT(ADDRESS)
+    let mut interpreter = run_txn(&[sender.clone(), contract], 0, 0, Address::zero())?;
+
+    // The recipient is warm, being the transaction's recipient.
+    assert_eq!(block_gas_used(&interpreter), (21000 + 2 + 5000).into());
+    // The contract is deleted, and its balance burned.
+    let sender_after = TestAccount { nonce: 2, ..sender };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_empty_recipient_is_deleted() -> Result<()> {
+    let sender = TestAccount::new(1, 1000, vec![]);
+    let mut interpreter = run_txn(
+        &[sender.clone(), TestAccount::empty()],
+        0,
+        0,
+        Address::zero(),
+    )?;
+
+    assert_eq!(block_gas_used(&interpreter), 21000.into());
+    let sender_after = TestAccount { nonce: 2, ..sender };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_empty_callee_is_deleted() -> Result<()> {
+    let empty = TestAccount::empty();
+    let sender = TestAccount::new(1, 1000, vec![]);
+    let contract = TestAccount::new(1, 0, call_then(empty.address, 0x00)); // STOP
+    let mut interpreter = run_txn(
+        &[sender.clone(), contract.clone(), empty],
+        0,
+        0,
+        Address::zero(),
+    )?;
+
+    let sender_after = TestAccount { nonce: 2, ..sender };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after, contract]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reverted_touch_keeps_empty_account() -> Result<()> {
+    let empty = TestAccount::empty();
+    let sender = TestAccount::new(1, 1000, vec![]);
+    let contract = TestAccount::new(1, 0, call_then(empty.address, 0xfe)); // INVALID
+    let mut interpreter = run_txn(
+        &[sender.clone(), contract.clone(), empty.clone()],
+        0,
+        0,
+        Address::zero(),
+    )?;
+
+    // The call which touched the empty account was reverted, so it isn't deleted.
+    assert_eq!(block_gas_used(&interpreter), GAS_LIMIT.into());
+    let sender_after = TestAccount { nonce: 2, ..sender };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after, contract, empty]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_refund_cap() -> Result<()> {
+    let sender = TestAccount::new(1, 1000, vec![]);
+    let contract = TestAccount {
+        storage: vec![(0.into(), 1.into()), (1.into(), 1.into())],
+        ..TestAccount::new(
+            1,
+            0,
+            vec![
+                0x60, 0x00, 0x60, 0x00,
+                0x55, // SSTORE(0, 0), clearing a cold slot: 3 + 3 + 5000
+                0x60, 0x00, 0x60, 0x01,
+                0x55, // SSTORE(1, 0), clearing a cold slot: 3 + 3 + 5000
+                0x00, // STOP
+            ],
+        )
+    };
+    let mut interpreter = run_txn(&[sender.clone(), contract.clone()], 0, 0, Address::zero())?;
+
+    // Clearing the slots earns a refund of 2 * 4800, but it's capped at a fifth of the gas used.
+    let gas_used_before_refund = 21000 + 2 * 5006;
+    assert_eq!(
+        block_gas_used(&interpreter),
+        (gas_used_before_refund - gas_used_before_refund / 5).into()
+    );
+    let sender_after = TestAccount { nonce: 2, ..sender };
+    let contract_after = TestAccount {
+        storage: vec![],
+        ..contract
+    };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after, contract_after]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fees() -> Result<()> {
+    let beneficiary: Address = thread_rng().gen();
+    let sender = TestAccount::new(1, 1_000_000, vec![]);
+    let recipient = TestAccount::new(1, 0, vec![]);
+    let mut interpreter = run_txn(&[sender.clone(), recipient.clone()], 10, 7, beneficiary)?;
+
+    assert_eq!(block_gas_used(&interpreter), 21000.into());
+    // The sender pays the gas it used at the gas price. Out of that, the beneficiary gets the
+    // priority fee, and the base fee is burned.
+    let sender_after = TestAccount {
+        nonce: 2,
+        balance: 1_000_000 - 21000 * 10,
+        ..sender
+    };
+    let beneficiary_after = TestAccount {
+        address: beneficiary,
+        ..TestAccount::new(0, 21000 * 3, vec![])
+    };
+    assert_eq!(
+        hash_state_trie(&mut interpreter)?,
+        state_trie(&[sender_after, recipient, beneficiary_after]).calc_hash()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_txns_are_skipped() -> Result<()> {
+    // The sender can pay for exactly GAS_LIMIT gas at a price of 10.
+    let accounts = [
+        TestAccount::new(1, GAS_LIMIT * 10, vec![]),
+        TestAccount::new(1, 0, vec![]),
+    ];
+    // (value, gas_price, base_fee)
+    let invalid_txns = [
+        // The value doesn't fit in the sender's balance once the gas is paid for.
+        (1, 10, 7),
+        // Neither does the gas itself.
+        (0, 11, 7),
+        // The sender isn't willing to pay the base fee.
+        (0, 5, 7),
+    ];
+    for (value, gas_price, base_fee) in invalid_txns {
+        let mut interpreter =
+            run_txn_with_value(&accounts, value, gas_price, base_fee, Address::zero())?;
+        assert_skipped(&mut interpreter, &accounts)?;
+    }
+
+    // The nonce isn't the sender's.
+    let mut interpreter = prepare_txn(&accounts, 0, 10, 7, Address::zero())?;
+    interpreter.set_txn_field(NormalizedTxnField::Nonce, 2.into());
+    run_prepared_txn(&mut interpreter)?;
+    assert_skipped(&mut interpreter, &accounts)?;
+
+    // The priority fee is above the max fee.
+    let mut interpreter = prepare_txn(&accounts, 0, 10, 7, Address::zero())?;
+    interpreter.set_txn_field(NormalizedTxnField::MaxPriorityFeePerGas, 11.into());
+    run_prepared_txn(&mut interpreter)?;
+    assert_skipped(&mut interpreter, &accounts)?;
+
+    // The gas limit doesn't fit in the block's, either on its own or after earlier transactions.
+    for (block_gas_limit, earlier_gas_used) in [(GAS_LIMIT - 1, 0), (GAS_LIMIT, 1)] {
+        let mut interpreter = prepare_txn(&accounts, 0, 10, 7, Address::zero())?;
+        interpreter
+            .set_global_metadata_field(GlobalMetadata::BlockGasLimit, block_gas_limit.into());
+        interpreter
+            .set_global_metadata_field(GlobalMetadata::BlockGasUsed, earlier_gas_used.into());
+        run_prepared_txn(&mut interpreter)?;
+        assert_eq!(block_gas_used(&interpreter), earlier_gas_used.into());
+        assert_eq!(
+            hash_state_trie(&mut interpreter)?,
+            state_trie(&accounts).calc_hash()
+        );
+    }
+
+    // The sender has code, per EIP-3607.
+    let accounts_with_code = [
+        TestAccount::new(1, GAS_LIMIT * 10, vec![0x00]),
+        accounts[1].clone(),
+    ];
+    let mut interpreter = run_txn_with_value(&accounts_with_code, 0, 10, 7, Address::zero())?;
+    assert_skipped(&mut interpreter, &accounts_with_code)?;
+
+    // With just enough balance, the transaction goes through.
+    let interpreter = run_txn_with_value(&accounts, 0, 10, 7, Address::zero())?;
+    assert_eq!(block_gas_used(&interpreter), 21000.into());
+    Ok(())
+}
@@ -79,7 +79,11 @@ const STACK_BEHAVIORS: OpsColumnsView<Option<StackBehavior>> = OpsColumnsView {
         pushes: true,
         disable_other_channels: true,
     }),
-    gas: None, // TODO
+    gas: Some(StackBehavior {
+        num_pops: 0,
+        pushes: true,
+        disable_other_channels: false,
+    }),
     jumpdest: Some(StackBehavior {
         num_pops: 0,
         pushes: false,
@@ -94,15 +98,19 @@ const STACK_BEHAVIORS: OpsColumnsView<Option<StackBehavior>> = OpsColumnsView {
         disable_other_channels: true,
     }),
     set_context: Some(StackBehavior {
+        num_pops: 1,
+        pushes: false,
+        disable_other_channels: false,
+    }),
+    consume_gas: Some(StackBehavior {
         num_pops: 1,
         pushes: false,
         disable_other_channels: true,
     }),
-    consume_gas: None, // TODO
     exit_kernel: Some(StackBehavior {
         num_pops: 1,
         pushes: false,
-        disable_other_channels: true,
+        disable_other_channels: false,
     }),
     mload_general: Some(StackBehavior {
         num_pops: 3,
@@ -119,6 +127,11 @@ const STACK_BEHAVIORS: OpsColumnsView<Option<StackBehavior>> = OpsColumnsView {
         pushes: true,
         disable_other_channels: false,
     }),
+    exception: Some(StackBehavior {
+        num_pops: 0,
+        pushes: true,
+        disable_other_channels: false,
+    }),
 };
 
 fn eval_packed_one<P: PackedField>(
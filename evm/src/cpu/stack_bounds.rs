@@ -4,11 +4,10 @@
 //! occur. For example, if `is_add` is set but an addition would underflow, these constraints would
 //! make the proof unverifiable.
 //!
-//! Faults are handled under a separate operation flag, `is_exception` (this is still TODO), which
-//! traps to the kernel. The kernel then handles the exception. However, before it may do so, the
-//! kernel must verify in software that an exception did in fact occur (i.e. the trap was
-//! warranted) and `PANIC` otherwise; this prevents the prover from faking an exception on a valid
-//! operation.
+//! Faults are handled under a separate operation flag, `exception`, which traps to the kernel. The
+//! kernel then handles the exception. However, before it may do so, the kernel must verify in
+//! software that an exception did in fact occur (i.e. the trap was warranted) and `PANIC`
+//! otherwise; this prevents the prover from faking an exception on a valid operation.
 
 use plonky2::field::extension::Extendable;
 use plonky2::field::packed::PackedField;
@@ -1,6 +1,9 @@
-//! Handle instructions that are implemented in terms of system calls.
+//! Handle instructions that are implemented in terms of system calls, as well as exceptions.
 //!
-//! These are usually the ones that are too complicated to implement in one CPU table row.
+//! These are usually the ones that are too complicated to implement in one CPU table row. An
+//! exception traps to the kernel in the same way, except that its handler is selected by the
+//! exception code rather than by the opcode. The handler verifies that the exception really
+//! occurred, and `PANIC`s otherwise, so the prover can't fake one.
 
 use plonky2::field::extension::Extendable;
 use plonky2::field::packed::PackedField;
@@ -24,20 +27,38 @@ pub fn eval_packed<P: PackedField>(
     nv: &CpuColumnsView<P>,
     yield_constr: &mut ConstraintConsumer<P>,
 ) {
-    let filter = lv.is_cpu_cycle * lv.op.syscall;
+    let filter_syscall = lv.is_cpu_cycle * lv.op.syscall;
+    let filter_exception = lv.is_cpu_cycle * lv.op.exception;
+    let filter = filter_syscall + filter_exception;
+
+    // The exception code bits must be boolean. This isn't filtered by `is_cpu_cycle`, to keep the
+    // degree down, but the bits only matter in CPU cycles anyway.
+    let exc_code_bits = lv.general.exception().exc_code_bits;
+    for bit in exc_code_bits {
+        yield_constr.constraint(lv.op.exception * bit * (bit - P::ONES));
+    }
 
     // Look up the handler in memory
     let code_segment = P::Scalar::from_canonical_usize(Segment::Code as usize);
     let syscall_jumptable_start =
         P::Scalar::from_canonical_usize(KERNEL.global_labels["syscall_jumptable"]);
+    let exception_jumptable_start =
+        P::Scalar::from_canonical_usize(KERNEL.global_labels["exception_jumptable"]);
     let opcode: P = lv
         .opcode_bits
         .into_iter()
         .enumerate()
         .map(|(i, bit)| bit * P::Scalar::from_canonical_u64(1 << i))
         .sum();
+    let exc_code: P = exc_code_bits
+        .into_iter()
+        .enumerate()
+        .map(|(i, bit)| bit * P::Scalar::from_canonical_u64(1 << i))
+        .sum();
     let opcode_handler_addr_start =
         syscall_jumptable_start + opcode * P::Scalar::from_canonical_usize(BYTES_PER_OFFSET);
+    let exc_handler_addr_start =
+        exception_jumptable_start + exc_code * P::Scalar::from_canonical_usize(BYTES_PER_OFFSET);
     for (i, channel) in lv.mem_channels[0..BYTES_PER_OFFSET].iter().enumerate() {
         yield_constr.constraint(filter * (channel.used - P::ONES));
         yield_constr.constraint(filter * (channel.is_read - P::ONES));
@@ -48,7 +69,9 @@ pub fn eval_packed<P: PackedField>(
 
         // Set address, using a separate channel for each of the `BYTES_PER_OFFSET` limbs.
         let limb_address = opcode_handler_addr_start + P::Scalar::from_canonical_usize(i);
-        yield_constr.constraint(filter * (channel.addr_virtual - limb_address));
+        yield_constr.constraint(filter_syscall * (channel.addr_virtual - limb_address));
+        let limb_address = exc_handler_addr_start + P::Scalar::from_canonical_usize(i);
+        yield_constr.constraint(filter_exception * (channel.addr_virtual - limb_address));
     }
 
     // Disable unused channels (the last channel is used to push to the stack)
@@ -72,12 +95,17 @@ pub fn eval_packed<P: PackedField>(
 
     // This memory channel is constrained in `stack.rs`.
     let output = lv.mem_channels[NUM_GP_CHANNELS - 1].value;
-    // Push current PC + 1 to stack
-    yield_constr.constraint(filter * (output[0] - (lv.program_counter + P::ONES)));
+    // Push current PC + 1 to stack for a syscall, or the current PC for an exception, so that the
+    // handler can inspect the faulting instruction.
+    yield_constr.constraint(filter_syscall * (output[0] - (lv.program_counter + P::ONES)));
+    yield_constr.constraint(filter_exception * (output[0] - lv.program_counter));
     // Push current kernel flag to stack (share register with PC)
     yield_constr.constraint(filter * (output[1] - lv.is_kernel_mode));
+    // An exception also pushes the stack length, not counting this word, to the third limb.
+    yield_constr.constraint(filter_syscall * output[2]);
+    yield_constr.constraint(filter_exception * (output[2] - lv.stack_len));
     // Zero the rest of that register
-    for &limb in &output[2..] {
+    for &limb in &output[3..] {
         yield_constr.constraint(filter * limb);
     }
 }
@@ -88,13 +116,27 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
     nv: &CpuColumnsView<ExtensionTarget<D>>,
     yield_constr: &mut RecursiveConstraintConsumer<F, D>,
 ) {
-    let filter = builder.mul_extension(lv.is_cpu_cycle, lv.op.syscall);
+    let filter_syscall = builder.mul_extension(lv.is_cpu_cycle, lv.op.syscall);
+    let filter_exception = builder.mul_extension(lv.is_cpu_cycle, lv.op.exception);
+    let filter = builder.add_extension(filter_syscall, filter_exception);
+
+    // The exception code bits must be boolean. This isn't filtered by `is_cpu_cycle`, to keep the
+    // degree down, but the bits only matter in CPU cycles anyway.
+    let exc_code_bits = lv.general.exception().exc_code_bits;
+    for bit in exc_code_bits {
+        let constr = builder.mul_sub_extension(bit, bit, bit);
+        let constr = builder.mul_extension(lv.op.exception, constr);
+        yield_constr.constraint(builder, constr);
+    }
 
     // Look up the handler in memory
     let code_segment = F::from_canonical_usize(Segment::Code as usize);
     let syscall_jumptable_start = builder.constant_extension(
         F::from_canonical_usize(KERNEL.global_labels["syscall_jumptable"]).into(),
     );
+    let exception_jumptable_start = builder.constant_extension(
+        F::from_canonical_usize(KERNEL.global_labels["exception_jumptable"]).into(),
+    );
     let opcode = lv
         .opcode_bits
         .into_iter()
@@ -102,11 +144,22 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
         .fold(builder.zero_extension(), |cumul, bit| {
             builder.mul_const_add_extension(F::TWO, cumul, bit)
         });
+    let exc_code = exc_code_bits
+        .into_iter()
+        .rev()
+        .fold(builder.zero_extension(), |cumul, bit| {
+            builder.mul_const_add_extension(F::TWO, cumul, bit)
+        });
     let opcode_handler_addr_start = builder.mul_const_add_extension(
         F::from_canonical_usize(BYTES_PER_OFFSET),
         opcode,
         syscall_jumptable_start,
     );
+    let exc_handler_addr_start = builder.mul_const_add_extension(
+        F::from_canonical_usize(BYTES_PER_OFFSET),
+        exc_code,
+        exception_jumptable_start,
+    );
     for (i, channel) in lv.mem_channels[0..BYTES_PER_OFFSET].iter().enumerate() {
         {
             let constr = builder.mul_sub_extension(filter, channel.used, filter);
@@ -139,9 +192,20 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
             let constr = builder.arithmetic_extension(
                 F::ONE,
                 -F::from_canonical_usize(i),
-                filter,
+                filter_syscall,
                 diff,
-                filter,
+                filter_syscall,
+            );
+            yield_constr.constraint(builder, constr);
+        }
+        {
+            let diff = builder.sub_extension(channel.addr_virtual, exc_handler_addr_start);
+            let constr = builder.arithmetic_extension(
+                F::ONE,
+                -F::from_canonical_usize(i),
+                filter_exception,
+                diff,
+                filter_exception,
             );
             yield_constr.constraint(builder, constr);
         }
@@ -180,11 +244,17 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
 
     // This memory channel is constrained in `stack.rs`.
     let output = lv.mem_channels[NUM_GP_CHANNELS - 1].value;
-    // Push current PC + 1 to stack
+    // Push current PC + 1 to stack for a syscall, or the current PC for an exception, so that the
+    // handler can inspect the faulting instruction.
     {
         let pc_plus_1 = builder.add_const_extension(lv.program_counter, F::ONE);
         let diff = builder.sub_extension(output[0], pc_plus_1);
-        let constr = builder.mul_extension(filter, diff);
+        let constr = builder.mul_extension(filter_syscall, diff);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(output[0], lv.program_counter);
+        let constr = builder.mul_extension(filter_exception, diff);
         yield_constr.constraint(builder, constr);
     }
     // Push current kernel flag to stack (share register with PC)
@@ -193,8 +263,18 @@ pub fn eval_ext_circuit<F: RichField + Extendable<D>, const D: usize>(
         let constr = builder.mul_extension(filter, diff);
         yield_constr.constraint(builder, constr);
     }
+    // An exception also pushes the stack length, not counting this word, to the third limb.
+    {
+        let constr = builder.mul_extension(filter_syscall, output[2]);
+        yield_constr.constraint(builder, constr);
+    }
+    {
+        let diff = builder.sub_extension(output[2], lv.stack_len);
+        let constr = builder.mul_extension(filter_exception, diff);
+        yield_constr.constraint(builder, constr);
+    }
     // Zero the rest of that register
-    for &limb in &output[2..] {
+    for &limb in &output[3..] {
         let constr = builder.mul_extension(filter, limb);
         yield_constr.constraint(builder, constr);
     }
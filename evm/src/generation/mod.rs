@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use eth_trie_utils::partial_trie::PartialTrie;
-use ethereum_types::{Address, BigEndianHash, H256};
+use ethereum_types::{Address, BigEndianHash, H256, U256};
 use plonky2::field::extension::Extendable;
 use plonky2::field::polynomial::PolynomialValues;
+use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
 use plonky2::timed;
 use plonky2::util::timing::TimingTree;
@@ -20,7 +21,7 @@ use crate::cpu::kernel::aggregator::KERNEL;
 use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
 use crate::generation::state::GenerationState;
 use crate::memory::segments::Segment;
-use crate::proof::{BlockMetadata, PublicValues, TrieRoots};
+use crate::proof::{BlockHashes, BlockMetadata, PublicValues, TrieRoots, NUM_PREV_BLOCK_HASHES};
 use crate::witness::memory::MemoryAddress;
 use crate::witness::transition::transition;
 
@@ -37,10 +38,14 @@ pub struct GenerationInputs {
     pub tries: TrieInputs,
 
     /// Mapping between smart contract code hashes and the contract byte code.
-    /// All account smart contracts that are invoked will have an entry present.
+    /// All account smart contracts that are invoked will have an entry present, including those
+    /// deployed by these transactions.
     pub contract_code: HashMap<H256, Vec<u8>>,
 
     pub block_metadata: BlockMetadata,
+
+    /// The hashes of the recent blocks queried by `BLOCKHASH`, and of the current block.
+    pub block_hashes: BlockHashes,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -62,7 +67,9 @@ pub struct TrieInputs {
     pub storage_tries: Vec<(Address, PartialTrie)>,
 }
 
-pub(crate) fn generate_traces<F: RichField + Extendable<D>, const D: usize>(
+/// Runs the kernel on the given inputs, returning the resulting trace of each table along with the
+/// public values.
+pub fn generate_traces<F: RichField + Extendable<D>, const D: usize>(
     all_stark: &AllStark<F, D>,
     inputs: GenerationInputs,
     config: &StarkConfig,
@@ -70,6 +77,9 @@ pub(crate) fn generate_traces<F: RichField + Extendable<D>, const D: usize>(
 ) -> ([Vec<PolynomialValues<F>>; NUM_TABLES], PublicValues) {
     let mut state = GenerationState::<F>::new(inputs.clone(), &KERNEL.code);
 
+    write_block_metadata(&mut state);
+    write_block_hashes(&mut state);
+
     generate_bootstrap_kernel::<F>(&mut state);
 
     timed!(timing, "simulate CPU", simulate_cpu(&mut state));
@@ -102,6 +112,7 @@ pub(crate) fn generate_traces<F: RichField + Extendable<D>, const D: usize>(
         trie_roots_before,
         trie_roots_after,
         block_metadata: inputs.block_metadata,
+        block_hashes: inputs.block_hashes,
     };
 
     let tables = timed!(
@@ -112,6 +123,48 @@ pub(crate) fn generate_traces<F: RichField + Extendable<D>, const D: usize>(
     (tables, public_values)
 }
 
+/// Writes the block metadata to `GlobalMetadata`, where the block information opcodes read it.
+fn write_block_metadata<F: Field>(state: &mut GenerationState<F>) {
+    let metadata = &state.inputs.block_metadata;
+    let fields = [
+        (
+            GlobalMetadata::BlockBeneficiary,
+            U256::from_big_endian(metadata.block_beneficiary.as_bytes()),
+        ),
+        (GlobalMetadata::BlockTimestamp, metadata.block_timestamp),
+        (GlobalMetadata::BlockNumber, metadata.block_number),
+        (GlobalMetadata::BlockDifficulty, metadata.block_difficulty),
+        (GlobalMetadata::BlockGasLimit, metadata.block_gaslimit),
+        (GlobalMetadata::BlockChainId, metadata.block_chain_id),
+        (GlobalMetadata::BlockBaseFee, metadata.block_base_fee),
+    ];
+    for (field, value) in fields {
+        let address = MemoryAddress::new(0, Segment::GlobalMetadata, field as usize);
+        state.memory.set(address, value);
+    }
+}
+
+/// Writes the previous block hashes to the `BlockHashes` segment, where `BLOCKHASH` reads them, and
+/// the current block hash to `GlobalMetadata`.
+fn write_block_hashes<F: Field>(state: &mut GenerationState<F>) {
+    let block_hashes = &state.inputs.block_hashes;
+    assert_eq!(
+        block_hashes.prev_hashes.len(),
+        NUM_PREV_BLOCK_HASHES,
+        "Expected {NUM_PREV_BLOCK_HASHES} previous block hashes"
+    );
+    for (i, hash) in block_hashes.prev_hashes.iter().enumerate() {
+        let address = MemoryAddress::new(0, Segment::BlockHashes, i);
+        state.memory.set(address, hash.into_uint());
+    }
+    let address = MemoryAddress::new(
+        0,
+        Segment::GlobalMetadata,
+        GlobalMetadata::BlockCurrentHash as usize,
+    );
+    state.memory.set(address, block_hashes.cur_hash.into_uint());
+}
+
 fn simulate_cpu<F: RichField + Extendable<D>, const D: usize>(state: &mut GenerationState<F>) {
     let halt_pc0 = KERNEL.global_labels["halt_pc0"];
     let halt_pc1 = KERNEL.global_labels["halt_pc1"];
@@ -54,10 +54,11 @@ pub(crate) fn all_mpt_prover_inputs(trie_inputs: &TrieInputs) -> Vec<U256> {
         rlp::decode_list(rlp)
     });
 
-    mpt_prover_inputs(&trie_inputs.receipts_trie, &mut prover_inputs, &|_rlp| {
-        // TODO: Decode receipt RLP.
-        vec![]
-    });
+    mpt_prover_inputs(
+        &trie_inputs.receipts_trie,
+        &mut prover_inputs,
+        &parse_receipt,
+    );
 
     prover_inputs
 }
@@ -153,8 +154,9 @@ pub(crate) fn mpt_prover_inputs_state_trie(
             } = account;
 
             let storage_hash_only = PartialTrie::Hash(storage_root);
+            let merged_key = key.merge_nibbles(nibbles);
             let storage_trie: &PartialTrie = storage_tries_by_state_key
-                .get(&key)
+                .get(&merged_key)
                 .copied()
                 .unwrap_or(&storage_hash_only);
 
@@ -176,9 +178,135 @@ fn parse_storage_value(value_rlp: &[u8]) -> Vec<U256> {
     vec![value]
 }
 
+/// Receipts are stored in the kernel as their length, followed by their raw encoding, one byte per
+/// word.
+fn parse_receipt(receipt: &[u8]) -> Vec<U256> {
+    let mut parsed = vec![receipt.len().into()];
+    parsed.extend(receipt.iter().map(|&byte| U256::from(byte)));
+    parsed
+}
+
 fn empty_nibbles() -> Nibbles {
     Nibbles {
         count: 0,
         packed: U256::zero(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nibbles(count: usize, packed: u64) -> Nibbles {
+        Nibbles {
+            count,
+            packed: packed.into(),
+        }
+    }
+
+    /// Build a receipts trie from the given keys, each mapped to a one-byte value.
+    fn receipts_trie(keys: &[Nibbles]) -> PartialTrie {
+        let mut trie = PartialTrie::Empty;
+        for (i, &key) in keys.iter().enumerate() {
+            trie.insert(key, vec![i as u8]);
+        }
+        trie
+    }
+
+    fn receipts_trie_inputs(receipts_trie: PartialTrie) -> Vec<U256> {
+        all_mpt_prover_inputs_reversed(&TrieInputs {
+            state_trie: Default::default(),
+            transactions_trie: Default::default(),
+            receipts_trie,
+            storage_tries: vec![],
+        })
+    }
+
+    /// The reversed prover inputs for an empty state trie, an empty transactions trie and a
+    /// receipts trie with the given serialization.
+    fn expected_inputs(receipts_trie: Vec<U256>) -> Vec<U256> {
+        let empty = U256::from(PartialTrieType::Empty as u32);
+        let mut inputs = vec![empty, empty];
+        inputs.extend(receipts_trie);
+        inputs.reverse();
+        inputs
+    }
+
+    /// Delete the last of the given keys, and check that the resulting trie serializes like one
+    /// built without it, and like the given serialization of its receipts trie.
+    fn check_delete(keys: &[Nibbles], expected_receipts_trie: Vec<U256>) {
+        let (&deleted, remaining) = keys.split_last().unwrap();
+        let mut trie = receipts_trie(keys);
+        assert_eq!(trie.delete(deleted), Some(vec![remaining.len() as u8]));
+
+        let inputs = receipts_trie_inputs(trie);
+        assert_eq!(inputs, receipts_trie_inputs(receipts_trie(remaining)));
+        assert_eq!(inputs, expected_inputs(expected_receipts_trie));
+    }
+
+    #[test]
+    fn delete_collapses_branch_into_leaf() {
+        // The branch is left with a single leaf child, which absorbs its nibble.
+        check_delete(
+            &[nibbles(2, 0x12), nibbles(2, 0x34)],
+            serialized_leaf(nibbles(2, 0x12), 0),
+        );
+    }
+
+    #[test]
+    fn delete_collapses_branch_into_extension() {
+        // The root branch is left with a single child, which is itself an extension, so the two
+        // merge into one extension leading to the branch below.
+        let mut expected = vec![
+            (PartialTrieType::Extension as u32).into(),
+            2.into(),
+            0x12.into(),
+        ];
+        expected.extend(two_leaf_branch(0x3, 0x4));
+        check_delete(
+            &[nibbles(3, 0x123), nibbles(3, 0x124), nibbles(3, 0x456)],
+            expected,
+        );
+    }
+
+    #[test]
+    fn delete_merges_extensions() {
+        // The branch below the root extension collapses into an extension, which merges with the
+        // root one.
+        let mut expected = vec![
+            (PartialTrieType::Extension as u32).into(),
+            3.into(),
+            0x123.into(),
+        ];
+        expected.extend(two_leaf_branch(0x4, 0x5));
+        check_delete(
+            &[nibbles(4, 0x1234), nibbles(4, 0x1235), nibbles(4, 0x1267)],
+            expected,
+        );
+    }
+
+    /// The serialization of a receipts trie leaf with the given key and one-byte value.
+    fn serialized_leaf(key: Nibbles, value: u8) -> Vec<U256> {
+        vec![
+            (PartialTrieType::Leaf as u32).into(),
+            key.count.into(),
+            key.packed,
+            1.into(),
+            value.into(),
+        ]
+    }
+
+    /// The serialization of a branch with leaves holding the values 0 and 1, at the given
+    /// nibbles, and with empty keys.
+    fn two_leaf_branch(first: usize, second: usize) -> Vec<U256> {
+        let mut serialized = vec![(PartialTrieType::Branch as u32).into(), 0.into()];
+        for i in 0..16 {
+            if i == first || i == second {
+                serialized.extend(serialized_leaf(nibbles(0, 0), (i == second) as u8));
+            } else {
+                serialized.push((PartialTrieType::Empty as u32).into());
+            }
+        }
+        serialized
+    }
+}
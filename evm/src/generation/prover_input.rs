@@ -3,11 +3,14 @@ use std::str::FromStr;
 use ethereum_types::{BigEndianHash, H256, U256};
 use plonky2::field::types::Field;
 
+use crate::bn254_arithmetic::Fp12;
 use crate::generation::prover_input::EvmField::{
     Bn254Base, Bn254Scalar, Secp256k1Base, Secp256k1Scalar,
 };
 use crate::generation::prover_input::FieldOp::{Inverse, Sqrt};
 use crate::generation::state::GenerationState;
+use crate::memory::segments::Segment;
+use crate::witness::memory::MemoryAddress;
 use crate::witness::util::stack_peek;
 
 /// Prover input function represented as a scoped function name.
@@ -26,6 +29,7 @@ impl<F: Field> GenerationState<F> {
         match input_fn.0[0].as_str() {
             "end_of_txns" => self.run_end_of_txns(),
             "ff" => self.run_ff(input_fn),
+            "ffe" => self.run_ffe(input_fn),
             "mpt" => self.run_mpt(),
             "rlp" => self.run_rlp(),
             "account_code" => self.run_account_code(input_fn),
@@ -51,6 +55,31 @@ impl<F: Field> GenerationState<F> {
         field.op(op, x)
     }
 
+    /// Finite field extension operations.
+    fn run_ffe(&self, input_fn: &ProverInputFn) -> U256 {
+        let field = EvmField::from_str(input_fn.0[1].as_str()).unwrap();
+        assert!(
+            matches!(field, Bn254Base),
+            "Only BN254 extensions are supported."
+        );
+        match input_fn.0[2].as_str() {
+            "inverse_fp12" => {
+                // Return the `i`th word of the inverse of the Fp12 element at `ptr` in kernel memory.
+                // stack: i, ptr, ...
+                let i = stack_peek(self, 0).expect("Empty stack").as_usize();
+                let ptr = stack_peek(self, 1).expect("Empty stack").as_usize();
+                let words = (ptr..ptr + 12)
+                    .map(|virt| {
+                        self.memory
+                            .get(MemoryAddress::new(0, Segment::KernelGeneral, virt))
+                    })
+                    .collect::<Vec<_>>();
+                Fp12::from_words(&words).inv().to_words()[i]
+            }
+            _ => panic!("Unrecognized field extension operation."),
+        }
+    }
+
     /// MPT data.
     fn run_mpt(&mut self) -> U256 {
         self.mpt_prover_inputs
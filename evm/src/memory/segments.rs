@@ -39,10 +39,29 @@ pub(crate) enum Segment {
     /// instructions; initialised by `kernel/asm/shift.asm::init_shift_table()`.
     ShiftTable = 16,
     JumpdestBits = 17,
+    /// Contains the logs emitted by the current transaction. Each log is stored as its address,
+    /// number of topics, data length, topics, and finally its data bytes. It is owned by the kernel,
+    /// so it only lives on context 0.
+    Logs = 18,
+    /// The hashes of the `NUM_PREV_BLOCK_HASHES` blocks preceding the current one, oldest first.
+    /// They are read by `BLOCKHASH`.
+    BlockHashes = 19,
+    /// The addresses accessed by the current transaction, per EIP-2929. It is owned by the kernel,
+    /// so it only lives on context 0.
+    AccessedAddresses = 20,
+    /// The storage keys accessed by the current transaction, per EIP-2929, stored as pairs of an
+    /// address and a slot. It is owned by the kernel, so it only lives on context 0.
+    AccessedStorageKeys = 21,
+    /// The addresses of the accounts which self-destructed in the current transaction. They are
+    /// deleted from the state trie at the end of the transaction.
+    SelfDestructList = 22,
+    /// The addresses touched by the current transaction. Those which are empty at the end of the
+    /// transaction are deleted from the state trie, per EIP-161.
+    TouchedAddresses = 23,
 }
 
 impl Segment {
-    pub(crate) const COUNT: usize = 18;
+    pub(crate) const COUNT: usize = 24;
 
     pub(crate) fn all() -> [Self; Self::COUNT] {
         [
@@ -64,6 +83,12 @@ impl Segment {
             Self::TrieEncodedChildLen,
             Self::ShiftTable,
             Self::JumpdestBits,
+            Self::Logs,
+            Self::BlockHashes,
+            Self::AccessedAddresses,
+            Self::AccessedStorageKeys,
+            Self::SelfDestructList,
+            Self::TouchedAddresses,
         ]
     }
 
@@ -88,6 +113,12 @@ impl Segment {
             Segment::TrieEncodedChildLen => "SEGMENT_TRIE_ENCODED_CHILD_LEN",
             Segment::ShiftTable => "SEGMENT_SHIFT_TABLE",
             Segment::JumpdestBits => "SEGMENT_JUMPDEST_BITS",
+            Segment::Logs => "SEGMENT_LOGS",
+            Segment::BlockHashes => "SEGMENT_BLOCK_HASHES",
+            Segment::AccessedAddresses => "SEGMENT_ACCESSED_ADDRESSES",
+            Segment::AccessedStorageKeys => "SEGMENT_ACCESSED_STORAGE_KEYS",
+            Segment::SelfDestructList => "SEGMENT_SELFDESTRUCT_LIST",
+            Segment::TouchedAddresses => "SEGMENT_TOUCHED_ADDRESSES",
         }
     }
 
@@ -112,6 +143,12 @@ impl Segment {
             Segment::TrieEncodedChildLen => 6,
             Segment::ShiftTable => 256,
             Segment::JumpdestBits => 1,
+            Segment::Logs => 256,
+            Segment::BlockHashes => 256,
+            Segment::AccessedAddresses => 256,
+            Segment::AccessedStorageKeys => 256,
+            Segment::SelfDestructList => 256,
+            Segment::TouchedAddresses => 256,
         }
     }
 }
@@ -55,6 +55,7 @@ pub struct PublicValues {
     pub trie_roots_before: TrieRoots,
     pub trie_roots_after: TrieRoots,
     pub block_metadata: BlockMetadata,
+    pub block_hashes: BlockHashes,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -75,12 +76,36 @@ pub struct BlockMetadata {
     pub block_base_fee: U256,
 }
 
+/// The number of recent block hashes available to `BLOCKHASH`.
+pub const NUM_PREV_BLOCK_HASHES: usize = 256;
+
+/// The hashes of the blocks preceding the current one, which `BLOCKHASH` can query, along with the
+/// hash of the current block. Consecutive block proofs can be linked by checking that the current
+/// hash of one block is the latest previous hash of the next one.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BlockHashes {
+    /// The hashes of the `NUM_PREV_BLOCK_HASHES` most recent blocks before the current one, oldest
+    /// first. Entries for blocks before the genesis block should be zero.
+    pub prev_hashes: Vec<H256>,
+    pub cur_hash: H256,
+}
+
+impl Default for BlockHashes {
+    fn default() -> Self {
+        Self {
+            prev_hashes: vec![H256::zero(); NUM_PREV_BLOCK_HASHES],
+            cur_hash: H256::zero(),
+        }
+    }
+}
+
 /// Memory values which are public.
 /// Note: All the larger integers are encoded with 32-bit limbs in little-endian order.
 pub struct PublicValuesTarget {
     pub trie_roots_before: TrieRootsTarget,
     pub trie_roots_after: TrieRootsTarget,
     pub block_metadata: BlockMetadataTarget,
+    pub block_hashes: BlockHashesTarget,
 }
 
 pub struct TrieRootsTarget {
@@ -99,6 +124,12 @@ pub struct BlockMetadataTarget {
     pub block_base_fee: Target,
 }
 
+pub struct BlockHashesTarget {
+    /// The previous block hashes, oldest first, each given as 8 consecutive limbs.
+    pub prev_hashes: [Target; NUM_PREV_BLOCK_HASHES * 8],
+    pub cur_hash: [Target; 8],
+}
+
 #[derive(Debug, Clone)]
 pub struct StarkProof<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize> {
     /// Merkle cap of LDEs of trace values.
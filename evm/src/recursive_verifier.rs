@@ -19,6 +19,7 @@ use plonky2::util::reducing::ReducingFactorTarget;
 use plonky2::with_context;
 
 use crate::all_stark::{all_cross_table_lookups, AllStark, Table, NUM_TABLES};
+use crate::arithmetic::arithmetic_stark::ArithmeticStark;
 use crate::config::StarkConfig;
 use crate::constraint_consumer::RecursiveConstraintConsumer;
 use crate::cpu::cpu_stark::CpuStark;
@@ -35,9 +36,9 @@ use crate::permutation::{
     GrandProductChallengeSet, PermutationCheckDataTarget,
 };
 use crate::proof::{
-    AllProof, AllProofTarget, BlockMetadata, BlockMetadataTarget, PublicValues, PublicValuesTarget,
-    StarkOpeningSetTarget, StarkProof, StarkProofChallengesTarget, StarkProofTarget, TrieRoots,
-    TrieRootsTarget,
+    AllProof, AllProofTarget, BlockHashes, BlockHashesTarget, BlockMetadata, BlockMetadataTarget,
+    PublicValues, PublicValuesTarget, StarkOpeningSetTarget, StarkProof,
+    StarkProofChallengesTarget, StarkProofTarget, TrieRoots, TrieRootsTarget,
 };
 use crate::stark::Stark;
 use crate::util::{h160_limbs, h256_limbs};
@@ -329,6 +330,7 @@ pub fn all_verifier_data_recursive_stark_proof<
     circuit_config: &CircuitConfig,
 ) -> [VerifierCircuitData<F, C, D>; NUM_TABLES]
 where
+    [(); ArithmeticStark::<F, D>::COLUMNS]:,
     [(); CpuStark::<F, D>::COLUMNS]:,
     [(); KeccakStark::<F, D>::COLUMNS]:,
     [(); KeccakSpongeStark::<F, D>::COLUMNS]:,
@@ -338,6 +340,14 @@ where
     C::Hasher: AlgebraicHasher<F>,
 {
     [
+        verifier_data_recursive_stark_proof(
+            Table::Arithmetic,
+            all_stark.arithmetic_stark,
+            degree_bits[Table::Arithmetic as usize],
+            &all_stark.cross_table_lookups,
+            inner_config,
+            circuit_config,
+        ),
         verifier_data_recursive_stark_proof(
             Table::Cpu,
             all_stark.cpu_stark,
@@ -517,6 +527,13 @@ pub fn add_virtual_all_proof<F: RichField + Extendable<D>, const D: usize>(
     nums_ctl_zs: &[usize],
 ) -> AllProofTarget<D> {
     let stark_proofs = [
+        add_virtual_stark_proof(
+            builder,
+            &all_stark.arithmetic_stark,
+            config,
+            degree_bits[Table::Arithmetic as usize],
+            nums_ctl_zs[Table::Arithmetic as usize],
+        ),
         add_virtual_stark_proof(
             builder,
             &all_stark.cpu_stark,
@@ -595,10 +612,12 @@ pub fn add_virtual_public_values<F: RichField + Extendable<D>, const D: usize>(
     let trie_roots_before = add_virtual_trie_roots(builder);
     let trie_roots_after = add_virtual_trie_roots(builder);
     let block_metadata = add_virtual_block_metadata(builder);
+    let block_hashes = add_virtual_block_hashes(builder);
     PublicValuesTarget {
         trie_roots_before,
         trie_roots_after,
         block_metadata,
+        block_hashes,
     }
 }
 
@@ -636,6 +655,17 @@ pub fn add_virtual_block_metadata<F: RichField + Extendable<D>, const D: usize>(
     }
 }
 
+pub fn add_virtual_block_hashes<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+) -> BlockHashesTarget {
+    let prev_hashes = builder.add_virtual_target_arr();
+    let cur_hash = builder.add_virtual_target_arr();
+    BlockHashesTarget {
+        prev_hashes,
+        cur_hash,
+    }
+}
+
 pub fn add_virtual_stark_proof<F: RichField + Extendable<D>, S: Stark<F, D>, const D: usize>(
     builder: &mut CircuitBuilder<F, D>,
     stark: &S,
@@ -772,6 +802,11 @@ pub fn set_public_value_targets<F, W, const D: usize>(
         &public_values_target.block_metadata,
         &public_values.block_metadata,
     );
+    set_block_hashes_target(
+        witness,
+        &public_values_target.block_hashes,
+        &public_values.block_hashes,
+    );
 }
 
 pub fn set_trie_roots_target<F, W, const D: usize>(
@@ -834,6 +869,31 @@ pub fn set_block_metadata_target<F, W, const D: usize>(
     );
 }
 
+pub fn set_block_hashes_target<F, W, const D: usize>(
+    witness: &mut W,
+    block_hashes_target: &BlockHashesTarget,
+    block_hashes: &BlockHashes,
+) where
+    F: RichField + Extendable<D>,
+    W: Witness<F>,
+{
+    let prev_hashes_limbs = block_hashes
+        .prev_hashes
+        .iter()
+        .flat_map(|&hash| h256_limbs::<F>(hash));
+    for (&target, limb) in block_hashes_target
+        .prev_hashes
+        .iter()
+        .zip_eq(prev_hashes_limbs)
+    {
+        witness.set_target(target, limb);
+    }
+    witness.set_target_arr(
+        block_hashes_target.cur_hash,
+        h256_limbs(block_hashes.cur_hash),
+    );
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use anyhow::Result;
@@ -848,6 +908,7 @@ pub(crate) mod tests {
     use plonky2::plonk::proof::ProofWithPublicInputs;
 
     use crate::all_stark::{AllStark, Table};
+    use crate::arithmetic::arithmetic_stark::ArithmeticStark;
     use crate::config::StarkConfig;
     use crate::cpu::cpu_stark::CpuStark;
     use crate::cross_table_lookup::{CrossTableLookup, CtlCheckVarsTarget};
@@ -977,6 +1038,7 @@ pub(crate) mod tests {
         circuit_config: &CircuitConfig,
     ) -> Result<RecursiveAllProof<F, C, D>>
     where
+        [(); ArithmeticStark::<F, D>::COLUMNS]:,
         [(); CpuStark::<F, D>::COLUMNS]:,
         [(); KeccakStark::<F, D>::COLUMNS]:,
         [(); KeccakSpongeStark::<F, D>::COLUMNS]:,
@@ -991,13 +1053,24 @@ pub(crate) mod tests {
         } = all_proof.get_challenger_states(all_stark, inner_config);
         Ok(RecursiveAllProof {
             recursive_proofs: [
+                recursively_verify_stark_proof(
+                    Table::Arithmetic,
+                    all_stark.arithmetic_stark,
+                    &all_proof.stark_proofs[Table::Arithmetic as usize],
+                    &all_stark.cross_table_lookups,
+                    &ctl_challenges,
+                    states[0],
+                    inner_config,
+                    circuit_config,
+                )?
+                .0,
                 recursively_verify_stark_proof(
                     Table::Cpu,
                     all_stark.cpu_stark,
                     &all_proof.stark_proofs[Table::Cpu as usize],
                     &all_stark.cross_table_lookups,
                     &ctl_challenges,
-                    states[0],
+                    states[1],
                     inner_config,
                     circuit_config,
                 )?
@@ -1008,7 +1081,7 @@ pub(crate) mod tests {
                     &all_proof.stark_proofs[Table::Keccak as usize],
                     &all_stark.cross_table_lookups,
                     &ctl_challenges,
-                    states[1],
+                    states[2],
                     inner_config,
                     circuit_config,
                 )?
@@ -1019,7 +1092,7 @@ pub(crate) mod tests {
                     &all_proof.stark_proofs[Table::KeccakSponge as usize],
                     &all_stark.cross_table_lookups,
                     &ctl_challenges,
-                    states[2],
+                    states[3],
                     inner_config,
                     circuit_config,
                 )?
@@ -1030,7 +1103,7 @@ pub(crate) mod tests {
                     &all_proof.stark_proofs[Table::Logic as usize],
                     &all_stark.cross_table_lookups,
                     &ctl_challenges,
-                    states[3],
+                    states[4],
                     inner_config,
                     circuit_config,
                 )?
@@ -1041,7 +1114,7 @@ pub(crate) mod tests {
                     &all_proof.stark_proofs[Table::Memory as usize],
                     &all_stark.cross_table_lookups,
                     &ctl_challenges,
-                    states[4],
+                    states[5],
                     inner_config,
                     circuit_config,
                 )?
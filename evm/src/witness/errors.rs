@@ -1,4 +1,3 @@
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum ProgramError {
     OutOfGas,
@@ -8,3 +7,18 @@ pub enum ProgramError {
     InvalidJumpiDestination,
     StackOverflow,
 }
+
+impl ProgramError {
+    /// The code identifying this exception to the kernel, i.e. its index in
+    /// `exception_jumptable`.
+    pub(crate) fn exc_code(&self) -> u8 {
+        match self {
+            ProgramError::OutOfGas => 0,
+            ProgramError::InvalidOpcode => 1,
+            ProgramError::StackUnderflow => 2,
+            ProgramError::InvalidJumpDestination => 3,
+            ProgramError::InvalidJumpiDestination => 4,
+            ProgramError::StackOverflow => 5,
+        }
+    }
+}
@@ -163,6 +163,11 @@ impl MemoryState {
             segment,
             segment.bit_range()
         );
+        if address.context >= self.contexts.len() {
+            // A new context was created, e.g. by a call.
+            self.contexts
+                .resize(address.context + 1, MemoryContextState::default());
+        }
         self.contexts[address.context].segments[address.segment].set(address.virt, val);
     }
 }
@@ -1,4 +1,4 @@
-mod errors;
+pub(crate) mod errors;
 pub(crate) mod memory;
 mod operation;
 pub(crate) mod state;
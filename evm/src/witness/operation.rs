@@ -6,15 +6,18 @@ use plonky2::field::types::Field;
 use crate::cpu::columns::CpuColumnsView;
 use crate::cpu::kernel::aggregator::KERNEL;
 use crate::cpu::kernel::assembler::BYTES_PER_OFFSET;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
 use crate::cpu::membus::NUM_GP_CHANNELS;
 use crate::cpu::simple_logic::eq_iszero::generate_pinv_diff;
 use crate::generation::state::GenerationState;
 use crate::memory::segments::Segment;
+use crate::util::u256_limbs;
 use crate::witness::errors::ProgramError;
 use crate::witness::memory::MemoryAddress;
 use crate::witness::util::{
-    keccak_sponge_log, mem_read_code_with_log_and_fill, mem_read_gp_with_log_and_fill,
-    mem_write_gp_log_and_fill, stack_pop_with_log_and_fill, stack_push_log_and_fill,
+    fill_gas_left_bits, keccak_sponge_log, mem_read_code_with_log_and_fill,
+    mem_read_gp_with_log_and_fill, mem_write_gp_log_and_fill, stack_pop_with_log_and_fill,
+    stack_push_log_and_fill,
 };
 use crate::{arithmetic, logic};
 
@@ -91,6 +94,18 @@ pub(crate) fn generate_binary_arithmetic_op<F: Field>(
         }
     }
 
+    if matches!(
+        operator,
+        arithmetic::BinaryOperator::AddFp254
+            | arithmetic::BinaryOperator::MulFp254
+            | arithmetic::BinaryOperator::SubFp254
+    ) {
+        // The modulus is not read from the stack, but the CPU expects it in the channel where
+        // the generalized modular operations have it.
+        const MODULUS_CHANNEL: usize = 2;
+        row.mem_channels[MODULUS_CHANNEL].value = u256_limbs(arithmetic::bn_base_order());
+    }
+
     state.traces.push_arithmetic(operation);
     state.traces.push_memory(log_in0);
     state.traces.push_memory(log_in1);
@@ -309,7 +324,69 @@ pub(crate) fn generate_set_context<F: Field>(
     mut row: CpuColumnsView<F>,
 ) -> Result<(), ProgramError> {
     let [(ctx, log_in)] = stack_pop_with_log_and_fill::<1, _>(state, &mut row)?;
-    state.registers.context = ctx.as_usize();
+    let old_ctx = state.registers.context;
+    let new_ctx = ctx.as_usize();
+
+    // Each context has its own stack, so we save the length of the old context's stack and
+    // restore that of the new context.
+    let sp_field = ContextMetadata::StackSize as usize;
+    let old_sp_addr = MemoryAddress::new(old_ctx, Segment::ContextMetadata, sp_field);
+    let new_sp_addr = MemoryAddress::new(new_ctx, Segment::ContextMetadata, sp_field);
+    let old_sp = state.registers.stack_len.into();
+    let log_write_old_sp = mem_write_gp_log_and_fill(1, old_sp_addr, state, &mut row, old_sp);
+    // Apply the write right away, so that the read below sees it if `new_ctx == old_ctx`.
+    state.memory.apply_ops(&[log_write_old_sp]);
+    let (new_sp, log_read_new_sp) = mem_read_gp_with_log_and_fill(2, new_sp_addr, state, &mut row);
+
+    // The same goes for the gas used by each context.
+    let gas_field = ContextMetadata::GasUsed as usize;
+    let old_gas_addr = MemoryAddress::new(old_ctx, Segment::ContextMetadata, gas_field);
+    let new_gas_addr = MemoryAddress::new(new_ctx, Segment::ContextMetadata, gas_field);
+    let old_gas = state.registers.gas_used.into();
+    let log_write_old_gas = mem_write_gp_log_and_fill(3, old_gas_addr, state, &mut row, old_gas);
+    state.memory.apply_ops(&[log_write_old_gas]);
+    let (new_gas, log_read_new_gas) =
+        mem_read_gp_with_log_and_fill(4, new_gas_addr, state, &mut row);
+
+    state.registers.context = new_ctx;
+    state.registers.stack_len = new_sp.as_usize();
+    state.registers.gas_used = new_gas.as_u64();
+    state.traces.push_memory(log_in);
+    state.traces.push_memory(log_write_old_sp);
+    state.traces.push_memory(log_read_new_sp);
+    state.traces.push_memory(log_write_old_gas);
+    state.traces.push_memory(log_read_new_gas);
+    state.traces.push_cpu(row);
+    Ok(())
+}
+
+pub(crate) fn generate_gas<F: Field>(
+    state: &mut GenerationState<F>,
+    mut row: CpuColumnsView<F>,
+) -> Result<(), ProgramError> {
+    let address = MemoryAddress::new(
+        state.registers.context,
+        Segment::ContextMetadata,
+        ContextMetadata::GasLimit as usize,
+    );
+    let (gas_limit, log_read) = mem_read_gp_with_log_and_fill(0, address, state, &mut row);
+    let gas_left = gas_limit - state.registers.gas_used;
+    fill_gas_left_bits(&mut row, gas_left);
+    let write = stack_push_log_and_fill(state, &mut row, gas_left)?;
+
+    state.traces.push_memory(log_read);
+    state.traces.push_memory(write);
+    state.traces.push_cpu(row);
+    Ok(())
+}
+
+pub(crate) fn generate_consume_gas<F: Field>(
+    state: &mut GenerationState<F>,
+    mut row: CpuColumnsView<F>,
+) -> Result<(), ProgramError> {
+    let [(amount, log_in)] = stack_pop_with_log_and_fill::<1, _>(state, &mut row)?;
+    state.registers.gas_used += amount.as_u64();
+
     state.traces.push_memory(log_in);
     state.traces.push_cpu(row);
     Ok(())
@@ -353,11 +430,20 @@ pub(crate) fn generate_push<F: Field>(
     state.traces.push_memory(write);
     state.traces.push_cpu(row);
 
-    // In any subsequent cycles, we read up to 1 + NUM_GP_CHANNELS bytes.
+    // In any subsequent cycles, we read up to 1 + NUM_GP_CHANNELS bytes. These rows have no
+    // operation flag, and carry over the context, the mode and the gas accounting of the first one,
+    // whose gas used includes this instruction's cost.
+    let continuation_row = CpuColumnsView {
+        is_cpu_cycle: F::ONE,
+        context: row.context,
+        is_kernel_mode: row.is_kernel_mode,
+        gas: F::from_canonical_u64(state.registers.gas_used),
+        gas_limit: row.gas_limit,
+        gas_left_bits: row.gas_left_bits,
+        ..CpuColumnsView::default()
+    };
     for mut addrs_chunk in &addrs.chunks(1 + NUM_GP_CHANNELS) {
-        let mut row = CpuColumnsView::default();
-        row.is_cpu_cycle = F::ONE;
-        row.op.push = F::ONE;
+        let mut row = continuation_row;
 
         let first_addr = addrs_chunk.next().unwrap();
         let (_, first_read) = mem_read_code_with_log_and_fill(first_addr, state, &mut row);
@@ -384,11 +470,7 @@ pub(crate) fn generate_dup<F: Field>(
         .stack_len
         .checked_sub(1 + (n as usize))
         .ok_or(ProgramError::StackUnderflow)?;
-    let other_addr = MemoryAddress::new(
-        state.registers.effective_context(),
-        Segment::Stack,
-        other_addr_lo,
-    );
+    let other_addr = MemoryAddress::new(state.registers.context, Segment::Stack, other_addr_lo);
 
     let (val, log_in) = mem_read_gp_with_log_and_fill(0, other_addr, state, &mut row);
     let log_out = stack_push_log_and_fill(state, &mut row, val)?;
@@ -409,11 +491,7 @@ pub(crate) fn generate_swap<F: Field>(
         .stack_len
         .checked_sub(2 + (n as usize))
         .ok_or(ProgramError::StackUnderflow)?;
-    let other_addr = MemoryAddress::new(
-        state.registers.effective_context(),
-        Segment::Stack,
-        other_addr_lo,
-    );
+    let other_addr = MemoryAddress::new(state.registers.context, Segment::Stack, other_addr_lo);
 
     let [(in0, log_in0)] = stack_pop_with_log_and_fill::<1, _>(state, &mut row)?;
     let (in1, log_in1) = mem_read_gp_with_log_and_fill(1, other_addr, state, &mut row);
@@ -516,11 +594,70 @@ pub(crate) fn generate_syscall<F: Field>(
 
     let syscall_info = U256::from(state.registers.program_counter + 1)
         + (U256::from(u64::from(state.registers.is_kernel)) << 32);
+    // The push happens in kernel mode: a full user stack is for the handler to deal with.
+    state.registers.is_kernel = true;
     let log_out = stack_push_log_and_fill(state, &mut row, syscall_info)?;
 
     state.registers.program_counter = new_program_counter;
     log::debug!("Syscall to {}", KERNEL.offset_name(new_program_counter));
+
+    state.traces.push_memory(log_in0);
+    state.traces.push_memory(log_in1);
+    state.traces.push_memory(log_in2);
+    state.traces.push_memory(log_out);
+    state.traces.push_cpu(row);
+
+    Ok(())
+}
+
+pub(crate) fn generate_exception<F: Field>(
+    exc_code: u8,
+    state: &mut GenerationState<F>,
+    mut row: CpuColumnsView<F>,
+) -> Result<(), ProgramError> {
+    row.op.exception = F::ONE;
+    let exc_code_bits = &mut row.general.exception_mut().exc_code_bits;
+    for (i, bit) in exc_code_bits.iter_mut().enumerate() {
+        *bit = F::from_bool(exc_code & (1 << i) != 0);
+    }
+
+    let handler_jumptable_addr = KERNEL.global_labels["exception_jumptable"];
+    let handler_addr_addr =
+        handler_jumptable_addr + (exc_code as usize) * (BYTES_PER_OFFSET as usize);
+    assert_eq!(BYTES_PER_OFFSET, 3, "Code below assumes 3 bytes per offset");
+    let (handler_addr0, log_in0) = mem_read_gp_with_log_and_fill(
+        0,
+        MemoryAddress::new(0, Segment::Code, handler_addr_addr),
+        state,
+        &mut row,
+    );
+    let (handler_addr1, log_in1) = mem_read_gp_with_log_and_fill(
+        1,
+        MemoryAddress::new(0, Segment::Code, handler_addr_addr + 1),
+        state,
+        &mut row,
+    );
+    let (handler_addr2, log_in2) = mem_read_gp_with_log_and_fill(
+        2,
+        MemoryAddress::new(0, Segment::Code, handler_addr_addr + 2),
+        state,
+        &mut row,
+    );
+
+    let handler_addr = (handler_addr0 << 16) + (handler_addr1 << 8) + handler_addr2;
+    let new_program_counter = handler_addr.as_usize();
+
+    // Unlike a syscall, this points at the faulting instruction itself, and also records the
+    // stack length, so that the handler can check that the exception really occurred. As with a
+    // syscall, the push happens in kernel mode, since a full user stack may be why we faulted.
+    let trap_info = U256::from(state.registers.program_counter)
+        + (U256::from(u64::from(state.registers.is_kernel)) << 32)
+        + (U256::from(state.registers.stack_len) << 64);
     state.registers.is_kernel = true;
+    let log_out = stack_push_log_and_fill(state, &mut row, trap_info)?;
+
+    state.registers.program_counter = new_program_counter;
+    log::debug!("Exception to {}", KERNEL.offset_name(new_program_counter));
 
     state.traces.push_memory(log_in0);
     state.traces.push_memory(log_in1);
@@ -560,6 +697,14 @@ pub(crate) fn generate_exit_kernel<F: Field>(
     assert!(is_kernel_mode_val == 0 || is_kernel_mode_val == 1);
     let is_kernel_mode = is_kernel_mode_val != 0;
 
+    // The gas limit is read for the user mode instructions which follow, which mustn't exceed it.
+    let address = MemoryAddress::new(
+        state.registers.context,
+        Segment::ContextMetadata,
+        ContextMetadata::GasLimit as usize,
+    );
+    let (_, log_read) = mem_read_gp_with_log_and_fill(1, address, state, &mut row);
+
     state.registers.program_counter = program_counter;
     state.registers.is_kernel = is_kernel_mode;
     log::debug!(
@@ -569,6 +714,7 @@ pub(crate) fn generate_exit_kernel<F: Field>(
     );
 
     state.traces.push_memory(log_in);
+    state.traces.push_memory(log_read);
     state.traces.push_cpu(row);
 
     Ok(())
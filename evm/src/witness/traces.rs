@@ -124,12 +124,19 @@ impl<T: Copy> Traces<T> {
         let Traces {
             cpu,
             logic_ops,
-            arithmetic: _, // TODO
+            arithmetic,
             memory_ops,
             keccak_inputs,
             keccak_sponge_ops,
         } = self;
 
+        let arithmetic_trace = timed!(
+            timing,
+            "generate arithmetic trace",
+            all_stark
+                .arithmetic_stark
+                .generate_trace(arithmetic, cap_elements, timing)
+        );
         let cpu_rows = cpu.into_iter().map(|x| x.into()).collect();
         let cpu_trace = trace_rows_to_poly_values(cpu_rows);
         let keccak_trace = timed!(
@@ -160,6 +167,7 @@ impl<T: Copy> Traces<T> {
         );
 
         [
+            arithmetic_trace,
             cpu_trace,
             keccak_trace,
             keccak_sponge_trace,
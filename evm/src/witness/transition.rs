@@ -1,15 +1,20 @@
+use ethereum_types::U256;
 use itertools::Itertools;
 use plonky2::field::types::Field;
 
 use crate::cpu::columns::CpuColumnsView;
+use crate::cpu::gas::simple_opcode_gas;
 use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
 use crate::generation::state::GenerationState;
 use crate::memory::segments::Segment;
 use crate::witness::errors::ProgramError;
 use crate::witness::memory::MemoryAddress;
 use crate::witness::operation::*;
 use crate::witness::state::RegistersState;
-use crate::witness::util::{mem_read_code_with_log_and_fill, stack_peek};
+use crate::witness::util::{
+    fill_gas_left_bits, gas_limit_field, mem_read_code_with_log_and_fill, stack_peek,
+};
 use crate::{arithmetic, logic};
 
 fn read_code_memory<F: Field>(state: &mut GenerationState<F>, row: &mut CpuColumnsView<F>) -> u8 {
@@ -92,7 +97,7 @@ fn decode(registers: RegistersState, opcode: u8) -> Result<Operation, ProgramErr
         (0x46, _) => Ok(Operation::Syscall(opcode)),
         (0x47, _) => Ok(Operation::Syscall(opcode)),
         (0x48, _) => Ok(Operation::Syscall(opcode)),
-        (0x49, _) => Ok(Operation::ProverInput),
+        (0x49, true) => Ok(Operation::ProverInput),
         (0x50, _) => Ok(Operation::Pop),
         (0x51, _) => Ok(Operation::Syscall(opcode)),
         (0x52, _) => Ok(Operation::Syscall(opcode)),
@@ -113,7 +118,7 @@ fn decode(registers: RegistersState, opcode: u8) -> Result<Operation, ProgramErr
         (0xa2, _) => Ok(Operation::Syscall(opcode)),
         (0xa3, _) => Ok(Operation::Syscall(opcode)),
         (0xa4, _) => Ok(Operation::Syscall(opcode)),
-        (0xa5, _) => panic!(
+        (0xa5, true) => panic!(
             "Kernel panic at {}",
             KERNEL.offset_name(registers.program_counter)
         ),
@@ -209,11 +214,11 @@ fn perform_op<F: Field>(
         Operation::Jump => generate_jump(state, row)?,
         Operation::Jumpi => generate_jumpi(state, row)?,
         Operation::Pc => generate_pc(state, row)?,
-        Operation::Gas => todo!(),
+        Operation::Gas => generate_gas(state, row)?,
         Operation::Jumpdest => generate_jumpdest(state, row)?,
         Operation::GetContext => generate_get_context(state, row)?,
         Operation::SetContext => generate_set_context(state, row)?,
-        Operation::ConsumeGas => todo!(),
+        Operation::ConsumeGas => generate_consume_gas(state, row)?,
         Operation::ExitKernel => generate_exit_kernel(state, row)?,
         Operation::MloadGeneral => generate_mload_general(state, row)?,
         Operation::MstoreGeneral => generate_mstore_general(state, row)?,
@@ -237,6 +242,8 @@ fn try_perform_instruction<F: Field>(state: &mut GenerationState<F>) -> Result<(
     row.program_counter = F::from_canonical_usize(state.registers.program_counter);
     row.is_kernel_mode = F::from_bool(state.registers.is_kernel);
     row.stack_len = F::from_canonical_usize(state.registers.stack_len);
+    row.gas = F::from_canonical_u64(state.registers.gas_used);
+    row.gas_limit = gas_limit_field(state);
 
     let opcode = read_code_memory(state, &mut row);
     let op = decode(state.registers, opcode)?;
@@ -245,9 +252,35 @@ fn try_perform_instruction<F: Field>(state: &mut GenerationState<F>) -> Result<(
 
     fill_op_flag(op, &mut row);
 
+    if !state.registers.is_kernel {
+        charge_gas(state, &mut row)?;
+    }
+
     perform_op(state, op, row)
 }
 
+/// Charges the gas of an operation executed in user mode. Syscalls are free here, since the kernel
+/// charges them itself.
+fn charge_gas<F: Field>(
+    state: &mut GenerationState<F>,
+    row: &mut CpuColumnsView<F>,
+) -> Result<(), ProgramError> {
+    let cost = simple_opcode_gas(&row.op).expect("user mode operation has no fixed gas cost");
+    let address = MemoryAddress::new(
+        state.registers.context,
+        Segment::ContextMetadata,
+        ContextMetadata::GasLimit as usize,
+    );
+    let gas_limit = state.memory.get(address);
+    let gas_used = state.registers.gas_used + cost;
+    if U256::from(gas_used) > gas_limit {
+        return Err(ProgramError::OutOfGas);
+    }
+    fill_gas_left_bits(row, gas_limit - gas_used);
+    state.registers.gas_used = gas_used;
+    Ok(())
+}
+
 fn log_instruction<F: Field>(state: &mut GenerationState<F>, op: Operation) {
     let pc = state.registers.program_counter;
     let is_interesting_offset = KERNEL
@@ -275,8 +308,32 @@ fn log_instruction<F: Field>(state: &mut GenerationState<F>, op: Operation) {
     }
 }
 
-fn handle_error<F: Field>(_state: &mut GenerationState<F>) {
-    todo!("generation for exception handling is not implemented");
+/// Traps to the kernel's handler for the given exception, which reverts the current context.
+fn handle_error<F: Field>(state: &mut GenerationState<F>, err: ProgramError) {
+    let exc_code = err.exc_code();
+    log::debug!("Exception {:?} (code {}) in user mode", err, exc_code);
+
+    let mut row: CpuColumnsView<F> = CpuColumnsView::default();
+    row.is_cpu_cycle = F::ONE;
+    row.clock = F::from_canonical_usize(state.traces.clock());
+    row.context = F::from_canonical_usize(state.registers.context);
+    row.program_counter = F::from_canonical_usize(state.registers.program_counter);
+    row.is_kernel_mode = F::ZERO;
+    row.stack_len = F::from_canonical_usize(state.registers.stack_len);
+    row.gas = F::from_canonical_u64(state.registers.gas_used);
+    row.gas_limit = gas_limit_field(state);
+
+    // The exception itself costs nothing, so the gas left is unchanged.
+    let address = MemoryAddress::new(
+        state.registers.context,
+        Segment::ContextMetadata,
+        ContextMetadata::GasLimit as usize,
+    );
+    let gas_left = state.memory.get(address) - state.registers.gas_used;
+    fill_gas_left_bits(&mut row, gas_left);
+
+    read_code_memory(state, &mut row);
+    generate_exception(exc_code, state, row).expect("exception handling should not fail");
 }
 
 pub(crate) fn transition<F: Field>(state: &mut GenerationState<F>) {
@@ -294,8 +351,12 @@ pub(crate) fn transition<F: Field>(state: &mut GenerationState<F>) {
                 let offset_name = KERNEL.offset_name(state.registers.program_counter);
                 panic!("exception in kernel mode at {}: {:?}", offset_name, e);
             }
+            let traces_checkpoint = checkpoint.traces;
             state.rollback(checkpoint);
-            handle_error(state)
+            handle_error(state, e);
+            state
+                .memory
+                .apply_ops(state.traces.mem_ops_since(traces_checkpoint));
         }
     }
 }
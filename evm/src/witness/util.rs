@@ -2,6 +2,7 @@ use ethereum_types::U256;
 use plonky2::field::types::Field;
 
 use crate::cpu::columns::CpuColumnsView;
+use crate::cpu::kernel::constants::context_metadata::ContextMetadata;
 use crate::cpu::kernel::keccak_util::keccakf_u8s;
 use crate::cpu::membus::{NUM_CHANNELS, NUM_GP_CHANNELS};
 use crate::cpu::stack_bounds::MAX_USER_STACK_SIZE;
@@ -27,13 +28,37 @@ fn to_bits_le<F: Field>(n: u8) -> [F; 8] {
     res
 }
 
+/// The gas limit of the current context, as the CPU's `gas_limit` column holds it, i.e. the first
+/// two 32-bit limbs of its metadata field.
+pub(crate) fn gas_limit_field<F: Field>(state: &GenerationState<F>) -> F {
+    let gas_limit = state.memory.get(MemoryAddress::new(
+        state.registers.context,
+        Segment::ContextMetadata,
+        ContextMetadata::GasLimit as usize,
+    ));
+    let limb = gas_limit.0[0];
+    F::from_canonical_u32(limb as u32)
+        + F::from_canonical_u32((limb >> 32) as u32) * F::from_canonical_u64(1 << 32)
+}
+
+/// Fills in the bits of the gas left after the current instruction.
+pub(crate) fn fill_gas_left_bits<F: Field>(row: &mut CpuColumnsView<F>, gas_left: U256) {
+    assert!(
+        gas_left < U256::one() << row.gas_left_bits.len(),
+        "gas left does not fit in the gas_left_bits columns"
+    );
+    for (i, bit) in row.gas_left_bits.iter_mut().enumerate() {
+        *bit = F::from_bool(gas_left.bit(i));
+    }
+}
+
 /// Peak at the stack item `i`th from the top. If `i=0` this gives the tip.
 pub(crate) fn stack_peek<F: Field>(state: &GenerationState<F>, i: usize) -> Option<U256> {
     if i >= state.registers.stack_len {
         return None;
     }
     Some(state.memory.get(MemoryAddress::new(
-        state.registers.effective_context(),
+        state.registers.context,
         Segment::Stack,
         state.registers.stack_len - 1 - i,
     )))
@@ -142,7 +167,7 @@ pub(crate) fn stack_pop_with_log_and_fill<const N: usize, F: Field>(
 
     let result = std::array::from_fn(|i| {
         let address = MemoryAddress::new(
-            state.registers.effective_context(),
+            state.registers.context,
             Segment::Stack,
             state.registers.stack_len - 1 - i,
         );
@@ -164,7 +189,7 @@ pub(crate) fn stack_push_log_and_fill<F: Field>(
     }
 
     let address = MemoryAddress::new(
-        state.registers.effective_context(),
+        state.registers.context,
         Segment::Stack,
         state.registers.stack_len,
     );
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use env_logger::{try_init_from_env, Env, DEFAULT_FILTER_ENV};
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{BlockHashes, BlockMetadata};
+use plonky2_evm::prover::prove;
+use plonky2_evm::verifier::verify_proof;
+use rlp::RlpStream;
+
+type F = GoldilocksField;
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+
+/// Test a contract-creation transaction, whose init code deploys a small contract.
+#[test]
+fn test_create_contract() -> anyhow::Result<()> {
+    init_logger();
+
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+
+    let sender = hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+    let sender_nibbles = Nibbles::from(keccak(sender));
+    let value = U256::from(100u32);
+
+    // The deployed code stores 42 in slot 0.
+    let code = hex!("602a60005500");
+    let code_hash = keccak(code);
+    let contract = {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&Address::from(sender));
+        stream.append(&5u64);
+        Address::from_slice(&keccak(stream.out())[12..])
+    };
+    let contract_nibbles = Nibbles::from(keccak(contract));
+
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: PartialTrie::Empty.calc_hash(),
+        code_hash: keccak([]),
+    };
+
+    let state_trie_before = PartialTrie::Leaf {
+        nibbles: sender_nibbles,
+        value: rlp::encode(&sender_account_before).to_vec(),
+    };
+    let tries_before = TrieInputs {
+        state_trie: state_trie_before,
+        transactions_trie: PartialTrie::Empty,
+        receipts_trie: PartialTrie::Empty,
+        storage_tries: vec![],
+    };
+
+    // A type-0 transaction with nonce 5, gas price 10, gas limit 100,000, no "to" field, value 100,
+    // and init code which returns `code`, i.e.
+    //     PUSH6 code, PUSH1 0, MSTORE, PUSH1 6, PUSH1 26, RETURN
+    let txn = hex!("f85b050a830186a080648f65602a600055006000526006601af31ba0bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020da012516fa2481c6bcf01da591ae25061d03371fdec7c2902f294daa7d8911ba070");
+
+    let block_metadata = BlockMetadata {
+        block_gaslimit: 1_000_000.into(),
+        ..BlockMetadata::default()
+    };
+    let beneficiary_nibbles = Nibbles::from(keccak(block_metadata.block_beneficiary));
+
+    let inputs = GenerationInputs {
+        signed_txns: vec![txn.to_vec()],
+        tries: tries_before,
+        contract_code: HashMap::from([(code_hash, code.to_vec())]),
+        block_metadata,
+        block_hashes: BlockHashes::default(),
+    };
+
+    let mut timing = TimingTree::new("prove", log::Level::Debug);
+    let proof = prove::<F, C, D>(&all_stark, &config, inputs, &mut timing)?;
+    timing.filter(Duration::from_millis(100)).print();
+
+    // The gas used: 53000 for the creation transaction, 204 for its data, 18 for the init code,
+    // and 6 * 200 to deposit the code.
+    let gas_used = U256::from(54422);
+    let gas_price = U256::from(10);
+    let expected_state_trie_after = {
+        let sender_account_after = AccountRlp {
+            balance: sender_account_before.balance - value - gas_used * gas_price,
+            nonce: sender_account_before.nonce + 1,
+            ..sender_account_before
+        };
+        let contract_account = AccountRlp {
+            nonce: 1.into(),
+            balance: value,
+            storage_root: PartialTrie::Empty.calc_hash(),
+            code_hash,
+        };
+
+        let mut children = std::array::from_fn(|_| PartialTrie::Empty.into());
+        children[sender_nibbles.get_nibble(0) as usize] = PartialTrie::Leaf {
+            nibbles: sender_nibbles.truncate_n_nibbles_front(1),
+            value: rlp::encode(&sender_account_after).to_vec(),
+        }
+        .into();
+        children[contract_nibbles.get_nibble(0) as usize] = PartialTrie::Leaf {
+            nibbles: contract_nibbles.truncate_n_nibbles_front(1),
+            value: rlp::encode(&contract_account).to_vec(),
+        }
+        .into();
+        // With a zero base fee, the beneficiary gets the whole gas price as a priority fee.
+        children[beneficiary_nibbles.get_nibble(0) as usize] = PartialTrie::Leaf {
+            nibbles: beneficiary_nibbles.truncate_n_nibbles_front(1),
+            value: rlp::encode(&AccountRlp {
+                balance: gas_used * gas_price,
+                ..AccountRlp::default()
+            })
+            .to_vec(),
+        }
+        .into();
+        PartialTrie::Branch {
+            children,
+            value: vec![],
+        }
+    };
+
+    assert_eq!(
+        proof.public_values.trie_roots_after.state_root,
+        expected_state_trie_after.calc_hash()
+    );
+
+    verify_proof(all_stark, proof, &config)
+}
+
+fn eth_to_wei(eth: U256) -> U256 {
+    // 1 ether = 10^18 wei.
+    eth * U256::from(10).pow(18.into())
+}
+
+fn init_logger() {
+    let _ = try_init_from_env(Env::default().filter_or(DEFAULT_FILTER_ENV, "info"));
+}
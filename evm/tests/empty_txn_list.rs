@@ -9,7 +9,7 @@ use plonky2::util::timing::TimingTree;
 use plonky2_evm::all_stark::AllStark;
 use plonky2_evm::config::StarkConfig;
 use plonky2_evm::generation::{GenerationInputs, TrieInputs};
-use plonky2_evm::proof::BlockMetadata;
+use plonky2_evm::proof::{BlockHashes, BlockMetadata};
 use plonky2_evm::prover::prove;
 use plonky2_evm::verifier::verify_proof;
 
@@ -46,6 +46,7 @@ fn test_empty_txn_list() -> anyhow::Result<()> {
         },
         contract_code: HashMap::new(),
         block_metadata,
+        block_hashes: BlockHashes::default(),
     };
 
     let mut timing = TimingTree::new("prove", log::Level::Debug);
@@ -0,0 +1,380 @@
+//! Runs the `GeneralStateTests` fixtures of the Ethereum test suite, from
+//! <https://github.com/ethereum/tests>, and reports how many of them pass.
+//!
+//! The fixtures aren't vendored, so this test is ignored by default. To run it, point
+//! `GENERAL_STATE_TESTS_DIR` at a local copy of the `GeneralStateTests` directory:
+//!
+//! ```sh
+//! GENERAL_STATE_TESTS_DIR=path/to/tests/GeneralStateTests \
+//!     cargo test --release --test general_state_tests -- --ignored --nocapture
+//! ```
+//!
+//! Only the fixtures whose path contains `GENERAL_STATE_TESTS_FILTER` are run, if it is set. By
+//! default we only generate the traces, which is enough to compute the post-state root. Setting
+//! `GENERAL_STATE_TESTS_PROVE` also proves and verifies each transaction.
+
+use std::collections::HashMap;
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use env_logger::{try_init_from_env, Env, DEFAULT_FILTER_ENV};
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use keccak_hash::keccak;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::{generate_traces, GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{BlockHashes, BlockMetadata, NUM_PREV_BLOCK_HASHES};
+use plonky2_evm::prover::prove;
+use plonky2_evm::verifier::verify_proof;
+use serde::Deserialize;
+
+type F = GoldilocksField;
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+
+/// The names which the fixtures may use for the fork we implement. We run the expectations of the
+/// first one present.
+const FORKS: [&str; 2] = ["Merge", "Paris"];
+
+/// The chain ID used by all state tests.
+const CHAIN_ID: u64 = 1;
+
+/// A fixture file maps test names to their contents.
+type Fixture = HashMap<String, StateTest>;
+
+#[derive(Deserialize)]
+struct StateTest {
+    env: TestEnv,
+    pre: HashMap<String, PreAccount>,
+    post: HashMap<String, Vec<PostState>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestEnv {
+    current_coinbase: String,
+    current_difficulty: Option<String>,
+    /// The `prevrandao` value, which replaced the difficulty in the Merge.
+    current_random: Option<String>,
+    current_gas_limit: String,
+    current_number: String,
+    current_timestamp: String,
+    current_base_fee: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PreAccount {
+    balance: String,
+    code: String,
+    nonce: String,
+    storage: HashMap<String, String>,
+}
+
+/// The expected outcome of one variant of the test's transaction.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostState {
+    hash: String,
+    indexes: Indexes,
+    /// The signed transaction. Older fixtures only give its unsigned fields, along with the secret
+    /// key to sign it with.
+    txbytes: Option<String>,
+    /// Set if the transaction is invalid, and should be rejected.
+    expect_exception: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Indexes {
+    data: usize,
+    gas: usize,
+    value: usize,
+}
+
+enum Outcome {
+    Pass,
+    Fail(String),
+    Skip(String),
+}
+
+#[derive(Default)]
+struct Report {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+impl Report {
+    fn record(&mut self, name: &str, outcome: Outcome) {
+        match outcome {
+            Outcome::Pass => {
+                log::info!("PASS {name}");
+                self.passed += 1;
+            }
+            Outcome::Fail(reason) => {
+                log::info!("FAIL {name}: {reason}");
+                self.failed += 1;
+            }
+            Outcome::Skip(reason) => {
+                log::info!("SKIP {name}: {reason}");
+                self.skipped += 1;
+            }
+        }
+    }
+}
+
+#[test]
+#[ignore] // Requires a local copy of the fixtures, and takes a long time.
+fn test_general_state_tests() -> Result<()> {
+    init_logger();
+
+    let dir = std::env::var("GENERAL_STATE_TESTS_DIR")
+        .context("GENERAL_STATE_TESTS_DIR should point to the GeneralStateTests fixtures")?;
+    let filter = std::env::var("GENERAL_STATE_TESTS_FILTER").unwrap_or_default();
+    let should_prove = std::env::var("GENERAL_STATE_TESTS_PROVE").is_ok();
+
+    let mut paths = vec![];
+    find_fixtures(Path::new(&dir), &mut paths)?;
+    paths.retain(|path| path.to_string_lossy().contains(&filter));
+    paths.sort();
+
+    let mut report = Report::default();
+    for path in paths {
+        let fixture: Fixture = match fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(serde_json::from_str(&json)?))
+        {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                report.record(&path.display().to_string(), Outcome::Fail(e.to_string()));
+                continue;
+            }
+        };
+        let mut tests = fixture.into_iter().collect::<Vec<_>>();
+        tests.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (test_name, test) in tests {
+            let posts = match FORKS.iter().find_map(|fork| test.post.get(*fork)) {
+                Some(posts) => posts,
+                None => {
+                    report.record(
+                        &test_name,
+                        Outcome::Skip("no post-state for our fork".into()),
+                    );
+                    continue;
+                }
+            };
+            for post in posts {
+                let Indexes { data, gas, value } = post.indexes;
+                let name = format!("{test_name}[data={data}, gas={gas}, value={value}]");
+                report.record(&name, run_case(&test, post, should_prove));
+            }
+        }
+    }
+
+    log::info!(
+        "GeneralStateTests: {} passed, {} failed, {} skipped",
+        report.passed,
+        report.failed,
+        report.skipped
+    );
+    Ok(())
+}
+
+/// Collects the JSON files under the given directory, recursively.
+fn find_fixtures(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_fixtures(&path, paths)?;
+        } else if path.extension().map_or(false, |ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn run_case(test: &StateTest, post: &PostState, should_prove: bool) -> Outcome {
+    if let Some(exception) = &post.expect_exception {
+        // The kernel skips some invalid transactions, but doesn't detect every kind yet, e.g. a
+        // bad signature or chain ID, so these would be reported as spurious failures.
+        return Outcome::Skip(format!("expects the exception {exception}"));
+    }
+    let txbytes = match &post.txbytes {
+        Some(txbytes) => txbytes,
+        None => return Outcome::Skip("no txbytes".into()),
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| -> Result<Outcome> {
+        let inputs = generation_inputs(test, txbytes)?;
+        let expected_root = parse_h256(&post.hash)?;
+        let actual_root = if should_prove {
+            post_state_root_from_proof(inputs)?
+        } else {
+            post_state_root_from_traces(inputs)
+        };
+        if actual_root == expected_root {
+            Ok(Outcome::Pass)
+        } else {
+            Ok(Outcome::Fail(format!(
+                "expected post-state root {expected_root:?}, got {actual_root:?}"
+            )))
+        }
+    }));
+    match result {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(e)) => Outcome::Fail(e.to_string()),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            Outcome::Fail(format!("panicked: {message}"))
+        }
+    }
+}
+
+fn post_state_root_from_traces(inputs: GenerationInputs) -> H256 {
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let mut timing = TimingTree::new("generate traces", log::Level::Debug);
+    let (_traces, public_values) = generate_traces(&all_stark, inputs, &config, &mut timing);
+    public_values.trie_roots_after.state_root
+}
+
+fn post_state_root_from_proof(inputs: GenerationInputs) -> Result<H256> {
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let mut timing = TimingTree::new("prove", log::Level::Debug);
+    let proof = prove::<F, C, D>(&all_stark, &config, inputs, &mut timing)?;
+    let state_root = proof.public_values.trie_roots_after.state_root;
+    verify_proof(all_stark, proof, &config)?;
+    Ok(state_root)
+}
+
+fn generation_inputs(test: &StateTest, txbytes: &str) -> Result<GenerationInputs> {
+    let mut state_trie = PartialTrie::Empty;
+    let mut storage_tries = vec![];
+    let mut contract_code = HashMap::new();
+    for (address, account) in &test.pre {
+        let address = parse_address(address)?;
+
+        let mut storage_trie = PartialTrie::Empty;
+        for (slot, value) in &account.storage {
+            let value = parse_u256(value)?;
+            // Zero values aren't stored.
+            if value.is_zero() {
+                continue;
+            }
+            let mut slot_bytes = [0; 32];
+            parse_u256(slot)?.to_big_endian(&mut slot_bytes);
+            storage_trie.insert(
+                Nibbles::from(keccak(slot_bytes)),
+                rlp::encode(&value).to_vec(),
+            );
+        }
+
+        let code = parse_bytes(&account.code)?;
+        let code_hash = keccak(&code);
+        let account_rlp = AccountRlp {
+            nonce: parse_u256(&account.nonce)?,
+            balance: parse_u256(&account.balance)?,
+            storage_root: storage_trie.calc_hash(),
+            code_hash,
+        };
+        state_trie.insert(
+            Nibbles::from(keccak(address)),
+            rlp::encode(&account_rlp).to_vec(),
+        );
+        storage_tries.push((address, storage_trie));
+        contract_code.insert(code_hash, code);
+    }
+
+    let env = &test.env;
+    let difficulty = env
+        .current_random
+        .as_ref()
+        .or(env.current_difficulty.as_ref())
+        .ok_or_else(|| anyhow!("Neither currentRandom nor currentDifficulty is set"))?;
+    let block_number = parse_u256(&env.current_number)?;
+    let block_metadata = BlockMetadata {
+        block_beneficiary: parse_address(&env.current_coinbase)?,
+        block_timestamp: parse_u256(&env.current_timestamp)?,
+        block_number,
+        block_difficulty: parse_u256(difficulty)?,
+        block_gaslimit: parse_u256(&env.current_gas_limit)?,
+        block_chain_id: CHAIN_ID.into(),
+        block_base_fee: env
+            .current_base_fee
+            .as_deref()
+            .map_or(Ok(U256::zero()), parse_u256)?,
+    };
+
+    Ok(GenerationInputs {
+        signed_txns: vec![parse_bytes(txbytes)?],
+        tries: TrieInputs {
+            state_trie,
+            transactions_trie: PartialTrie::Empty,
+            receipts_trie: PartialTrie::Empty,
+            storage_tries,
+        },
+        contract_code,
+        block_metadata,
+        block_hashes: reference_block_hashes(block_number),
+    })
+}
+
+/// The hashes of the blocks before the given one, as the reference implementation of the state
+/// tests defines them: the hash of block `n` is the Keccak hash of `n` written in decimal.
+fn reference_block_hashes(block_number: U256) -> BlockHashes {
+    let prev_hashes = (1..=NUM_PREV_BLOCK_HASHES)
+        .rev()
+        .map(|age| match block_number.checked_sub(age.into()) {
+            Some(number) => keccak(number.to_string()),
+            // There are no blocks before the genesis block.
+            None => H256::zero(),
+        })
+        .collect();
+    BlockHashes {
+        prev_hashes,
+        cur_hash: H256::zero(),
+    }
+}
+
+fn parse_bytes(hex: &str) -> Result<Vec<u8>> {
+    Ok(hex::decode(hex.trim_start_matches("0x"))?)
+}
+
+fn parse_u256(hex: &str) -> Result<U256> {
+    let digits = hex.trim_start_matches("0x");
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+    U256::from_str_radix(digits, 16).map_err(|e| anyhow!("Invalid integer {hex}: {e:?}"))
+}
+
+fn parse_address(hex: &str) -> Result<Address> {
+    let bytes = parse_bytes(hex)?;
+    if bytes.len() != 20 {
+        bail!("Invalid address {hex}");
+    }
+    Ok(Address::from_slice(&bytes))
+}
+
+fn parse_h256(hex: &str) -> Result<H256> {
+    let bytes = parse_bytes(hex)?;
+    if bytes.len() != 32 {
+        bail!("Invalid hash {hex}");
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+fn init_logger() {
+    let _ = try_init_from_env(Env::default().filter_or(DEFAULT_FILTER_ENV, "info"));
+}
@@ -13,7 +13,7 @@ use plonky2_evm::all_stark::AllStark;
 use plonky2_evm::config::StarkConfig;
 use plonky2_evm::generation::mpt::AccountRlp;
 use plonky2_evm::generation::{GenerationInputs, TrieInputs};
-use plonky2_evm::proof::BlockMetadata;
+use plonky2_evm::proof::{BlockHashes, BlockMetadata};
 use plonky2_evm::prover::prove;
 use plonky2_evm::verifier::verify_proof;
 
@@ -58,23 +58,31 @@ fn test_simple_transfer() -> anyhow::Result<()> {
     // Generated using a little py-evm script.
     let txn = hex!("f861050a8255f094a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0648242421ba02c89eb757d9deeb1f5b3859a9d4d679951ef610ac47ad4608dc142beb1b7e313a05af7e9fbab825455d36c36c7f4cfcafbeafa9a77bdff936b52afb36d4fe4bcdd");
 
-    let block_metadata = BlockMetadata::default();
+    let block_metadata = BlockMetadata {
+        block_gaslimit: 1_000_000.into(),
+        ..BlockMetadata::default()
+    };
+    let beneficiary_nibbles = Nibbles::from(keccak(block_metadata.block_beneficiary));
 
     let inputs = GenerationInputs {
         signed_txns: vec![txn.to_vec()],
         tries: tries_before,
         contract_code: HashMap::new(),
         block_metadata,
+        block_hashes: BlockHashes::default(),
     };
 
     let mut timing = TimingTree::new("prove", log::Level::Debug);
     let proof = prove::<F, C, D>(&all_stark, &config, inputs, &mut timing)?;
     timing.filter(Duration::from_millis(100)).print();
 
+    // The gas used: 21000 for the transaction, plus 2 * 16 for its two nonzero data bytes.
+    let gas_used = U256::from(21032);
+    let gas_price = U256::from(10);
     let expected_state_trie_after = {
         let sender_account_after = AccountRlp {
-            balance: sender_account_before.balance - value, // TODO: Also subtract gas_used * price.
-            // nonce: sender_account_before.nonce + 1, // TODO
+            balance: sender_account_before.balance - value - gas_used * gas_price,
+            nonce: sender_account_before.nonce + 1,
             ..sender_account_before
         };
         let to_account_after = AccountRlp {
@@ -93,6 +101,16 @@ fn test_simple_transfer() -> anyhow::Result<()> {
             value: rlp::encode(&to_account_after).to_vec(),
         }
         .into();
+        // With a zero base fee, the beneficiary gets the whole gas price as a priority fee.
+        children[beneficiary_nibbles.get_nibble(0) as usize] = PartialTrie::Leaf {
+            nibbles: beneficiary_nibbles.truncate_n_nibbles_front(1),
+            value: rlp::encode(&AccountRlp {
+                balance: gas_used * gas_price,
+                ..AccountRlp::default()
+            })
+            .to_vec(),
+        }
+        .into();
         PartialTrie::Branch {
             children,
             value: vec![],
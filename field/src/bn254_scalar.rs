@@ -0,0 +1,375 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use itertools::Itertools;
+use num::bigint::BigUint;
+use num::One;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Field, PrimeField, Sample};
+
+/// The scalar field of the BN254 elliptic curve, i.e. the native field of SNARKs over BN254.
+///
+/// Its order is
+/// ```ignore
+/// P = 0x30644E72 E131A029 B85045B6 8181585D 2833E848 79B97091 43E1F593 F0000001
+///   = 21888242871839275222246405745257275088548364400416034343698204186575808495617
+/// ```
+///
+/// Elements are stored as their canonical value, in little-endian 64-bit limbs. Multiplication
+/// goes through Montgomery form, as it is used heavily by hashing.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct Bn254Scalar(pub [u64; 4]);
+
+const ORDER: [u64; 4] = [
+    0x43E1F593F0000001,
+    0x2833E84879B97091,
+    0xB85045B68181585D,
+    0x30644E72E131A029,
+];
+
+/// `2^512 mod P`, to convert a Montgomery product back to a canonical value.
+const R2: [u64; 4] = [
+    0x1BB8E645AE216DA7,
+    0x53FE3AB1E35C59E3,
+    0x8C49833D53BB8085,
+    0x0216D0B17F4E44A5,
+];
+
+/// `-P^-1 mod 2^64`.
+const INV: u64 = 0xC2E1F593EFFFFFFF;
+
+fn biguint_from_array(arr: [u64; 4]) -> BigUint {
+    BigUint::from_slice(&[
+        arr[0] as u32,
+        (arr[0] >> 32) as u32,
+        arr[1] as u32,
+        (arr[1] >> 32) as u32,
+        arr[2] as u32,
+        (arr[2] >> 32) as u32,
+        arr[3] as u32,
+        (arr[3] >> 32) as u32,
+    ])
+}
+
+/// Computes `a + b`, returning the carry.
+#[inline]
+fn add_limbs(a: [u64; 4], b: [u64; 4]) -> ([u64; 4], bool) {
+    let mut res = [0; 4];
+    let mut carry = false;
+    for i in 0..4 {
+        let (sum, c0) = a[i].overflowing_add(b[i]);
+        let (sum, c1) = sum.overflowing_add(carry as u64);
+        res[i] = sum;
+        carry = c0 || c1;
+    }
+    (res, carry)
+}
+
+/// Computes `a - b`, returning the borrow.
+#[inline]
+fn sub_limbs(a: [u64; 4], b: [u64; 4]) -> ([u64; 4], bool) {
+    let mut res = [0; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (diff, b0) = a[i].overflowing_sub(b[i]);
+        let (diff, b1) = diff.overflowing_sub(borrow as u64);
+        res[i] = diff;
+        borrow = b0 || b1;
+    }
+    (res, borrow)
+}
+
+/// Reduces a value below `2 P` to its canonical representative.
+#[inline]
+fn reduce_once(a: [u64; 4]) -> [u64; 4] {
+    let (res, borrow) = sub_limbs(a, ORDER);
+    if borrow {
+        a
+    } else {
+        res
+    }
+}
+
+/// Montgomery multiplication: computes `a b 2^-256 mod P` for `a, b < P`.
+#[inline]
+fn mont_mul(a: [u64; 4], b: [u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let acc = t[j] as u128 + a[j] as u128 * b[i] as u128 + carry;
+            t[j] = acc as u64;
+            carry = acc >> 64;
+        }
+        let acc = t[4] as u128 + carry;
+        t[4] = acc as u64;
+        t[5] = (acc >> 64) as u64;
+
+        let m = t[0].wrapping_mul(INV);
+        let mut carry = (t[0] as u128 + m as u128 * ORDER[0] as u128) >> 64;
+        for j in 1..4 {
+            let acc = t[j] as u128 + m as u128 * ORDER[j] as u128 + carry;
+            t[j - 1] = acc as u64;
+            carry = acc >> 64;
+        }
+        let acc = t[4] as u128 + carry;
+        t[3] = acc as u64;
+        t[4] = t[5] + (acc >> 64) as u64;
+    }
+    // Since P < 2^254, the result is below 2 P and fits in four limbs.
+    reduce_once([t[0], t[1], t[2], t[3]])
+}
+
+impl Default for Bn254Scalar {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl PartialEq for Bn254Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Bn254Scalar {}
+
+impl Hash for Bn254Scalar {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl Display for Bn254Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_canonical_biguint(), f)
+    }
+}
+
+impl Debug for Bn254Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.to_canonical_biguint(), f)
+    }
+}
+
+impl Sample for Bn254Scalar {
+    #[inline]
+    fn sample<R>(rng: &mut R) -> Self
+    where
+        R: rand::RngCore + ?Sized,
+    {
+        use num::bigint::RandBigInt;
+        Self::from_noncanonical_biguint(rng.gen_biguint_below(&Self::order()))
+    }
+}
+
+impl Field for Bn254Scalar {
+    const ZERO: Self = Self([0; 4]);
+    const ONE: Self = Self([1, 0, 0, 0]);
+    const TWO: Self = Self([2, 0, 0, 0]);
+    const NEG_ONE: Self = Self([
+        0x43E1F593F0000000,
+        0x2833E84879B97091,
+        0xB85045B68181585D,
+        0x30644E72E131A029,
+    ]);
+    const MONTGOMERY_INV: Self = todo!();
+
+    const TWO_ADICITY: usize = 28;
+    const CHARACTERISTIC_TWO_ADICITY: usize = Self::TWO_ADICITY;
+
+    const NONRESIDUE: Self = todo!();
+
+    // Sage: `g = GF(p).multiplicative_generator()`
+    const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self([5, 0, 0, 0]);
+
+    // Sage: `g_2 = power_mod(g, (p - 1) // 2^28), p)`
+    // 19103219067921713944291392827692070036145651957329286315305642004821462161904
+    const POWER_OF_TWO_GENERATOR: Self = Self([
+        0x9BD61B6E725B19F0,
+        0x402D111E41112ED4,
+        0x00E0A7EB8EF62ABC,
+        0x2A3C09F0A58A7E85,
+    ]);
+
+    const BITS: usize = 254;
+
+    fn order() -> BigUint {
+        biguint_from_array(ORDER)
+    }
+    fn characteristic() -> BigUint {
+        Self::order()
+    }
+
+    fn mul_by_nonresidue(&self) -> Self {
+        todo!()
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // Fermat's Little Theorem
+        Some(self.exp_biguint(&(Self::order() - BigUint::one() - BigUint::one())))
+    }
+
+    fn from_noncanonical_biguint(val: BigUint) -> Self {
+        let val = val % Self::order();
+        Self(
+            val.to_u64_digits()
+                .into_iter()
+                .pad_using(4, |_| 0)
+                .collect::<Vec<_>>()[..]
+                .try_into()
+                .expect("error converting to u64 array"),
+        )
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        Self([n, 0, 0, 0])
+    }
+
+    #[inline]
+    fn from_noncanonical_u128(n: u128) -> Self {
+        Self([n as u64, (n >> 64) as u64, 0, 0])
+    }
+
+    #[inline]
+    fn from_noncanonical_u96(n: (u64, u32)) -> Self {
+        Self([n.0, n.1 as u64, 0, 0])
+    }
+}
+
+impl PrimeField for Bn254Scalar {
+    fn to_canonical_biguint(&self) -> BigUint {
+        biguint_from_array(self.0)
+    }
+}
+
+impl Neg for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        if self.is_zero() {
+            Self::ZERO
+        } else {
+            Self(sub_limbs(ORDER, self.0).0)
+        }
+    }
+}
+
+impl Add for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        // Both operands are below P < 2^254, so the sum cannot overflow.
+        Self(reduce_once(add_limbs(self.0, rhs.0).0))
+    }
+}
+
+impl AddAssign for Bn254Scalar {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Bn254Scalar {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl Sub for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        let (diff, borrow) = sub_limbs(self.0, rhs.0);
+        if borrow {
+            Self(add_limbs(diff, ORDER).0)
+        } else {
+            Self(diff)
+        }
+    }
+}
+
+impl SubAssign for Bn254Scalar {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(mont_mul(mont_mul(self.0, rhs.0), R2))
+    }
+}
+
+impl MulAssign for Bn254Scalar {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for Bn254Scalar {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc * x).unwrap_or(Self::ONE)
+    }
+}
+
+impl Div for Bn254Scalar {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl DivAssign for Bn254Scalar {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::BigUint;
+    use num::Integer;
+
+    use crate::bn254_scalar::Bn254Scalar;
+    use crate::test_field_arithmetic;
+    use crate::types::{Field, PrimeField, Sample};
+
+    test_field_arithmetic!(crate::bn254_scalar::Bn254Scalar);
+
+    #[test]
+    fn test_mul_matches_biguint() {
+        for _ in 0..100 {
+            let a = Bn254Scalar::rand();
+            let b = Bn254Scalar::rand();
+            let expected: BigUint = (a.to_canonical_biguint() * b.to_canonical_biguint())
+                .mod_floor(&Bn254Scalar::order());
+            assert_eq!((a * b).to_canonical_biguint(), expected);
+        }
+        assert_eq!(
+            Bn254Scalar::NEG_ONE * Bn254Scalar::NEG_ONE,
+            Bn254Scalar::ONE
+        );
+    }
+}
@@ -17,6 +17,7 @@ mod inversion;
 pub(crate) mod arch;
 
 pub mod batch_util;
+pub mod bn254_scalar;
 pub mod cosets;
 pub mod extension;
 pub mod fft;
@@ -6,11 +6,13 @@ use crate::field::extension::Extendable;
 use crate::field::types::Field64;
 use crate::gates::arithmetic_base::ArithmeticGate;
 use crate::gates::exponentiation::ExponentiationGate;
+use crate::gates::xor3::Xor3Gate;
 use crate::hash::hash_types::RichField;
 use crate::iop::generator::{GeneratedValues, SimpleGenerator};
 use crate::iop::target::{BoolTarget, Target};
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
     /// Computes `-x`.
@@ -337,6 +339,50 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         BoolTarget::new_unsafe(self.mul(b1.target, b2.target))
     }
 
+    /// Computes `b1 ^ b2`.
+    pub fn xor(&mut self, b1: BoolTarget, b2: BoolTarget) -> BoolTarget {
+        let zero = self._false();
+        self.xor3(b1, b2, zero)
+    }
+
+    /// Computes `b1 ^ b2 ^ b3`. Constant inputs are folded, so that no gate is used for them.
+    pub fn xor3(&mut self, b1: BoolTarget, b2: BoolTarget, b3: BoolTarget) -> BoolTarget {
+        let mut negate = false;
+        let mut inputs = Vec::new();
+        for b in [b1, b2, b3] {
+            match self.target_as_constant(b.target) {
+                Some(c) => negate ^= c.is_one(),
+                None => inputs.push(b),
+            }
+        }
+
+        let res = match inputs[..] {
+            [] => return self.constant_bool(negate),
+            [b] => b,
+            _ => {
+                let gate = Xor3Gate::new_from_config(&self.config);
+                let (row, i) = self.find_slot(gate, &[], &[]);
+                let zero = self.zero();
+                let input_wires = [
+                    Xor3Gate::wire_ith_input_a(i),
+                    Xor3Gate::wire_ith_input_b(i),
+                    Xor3Gate::wire_ith_input_c(i),
+                ];
+                for (j, wire) in input_wires.into_iter().enumerate() {
+                    let input = inputs.get(j).map_or(zero, |b| b.target);
+                    self.connect(input, Target::wire(row, wire));
+                }
+                BoolTarget::new_unsafe(Target::wire(row, Xor3Gate::wire_ith_output(i)))
+            }
+        };
+
+        if negate {
+            self.not(res)
+        } else {
+            res
+        }
+    }
+
     pub fn _if(&mut self, b: BoolTarget, x: Target, y: Target) -> Target {
         let not_b = self.not(b);
         let maybe_x = self.mul(b.target, x);
@@ -365,7 +411,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
 }
 
 #[derive(Debug)]
-struct EqualityGenerator {
+pub struct EqualityGenerator {
     x: Target,
     y: Target,
     equal: BoolTarget,
@@ -386,6 +432,21 @@ impl<F: RichField> SimpleGenerator<F> for EqualityGenerator {
         out_buffer.set_bool_target(self.equal, x == y);
         out_buffer.set_target(self.inv, inv);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target(self.x)?;
+        dst.write_target(self.y)?;
+        dst.write_target_bool(self.equal)?;
+        dst.write_target(self.inv)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let x = src.read_target()?;
+        let y = src.read_target()?;
+        let equal = src.read_target_bool()?;
+        let inv = src.read_target()?;
+        Ok(Self { x, y, equal, inv })
+    }
 }
 
 /// Represents a base arithmetic operation in the circuit. Used to memoize results.
@@ -0,0 +1,217 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::gates::keccak_chi::KeccakChiGate;
+use crate::hash::hash_types::RichField;
+use crate::iop::target::{BoolTarget, Target};
+use crate::plonk::circuit_builder::CircuitBuilder;
+
+/// The number of bytes absorbed per permutation by Keccak-256.
+pub const KECCAK256_RATE_BYTES: usize = 136;
+
+/// The round constants of Keccak-f[1600].
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the rho step, indexed by `x + 5 y`.
+const RHO_OFFSETS: [usize; 25] = [
+    0, 1, 62, 28, 27, //
+    36, 44, 6, 55, 20, //
+    3, 10, 43, 25, 39, //
+    41, 45, 15, 21, 8, //
+    18, 2, 61, 56, 14,
+];
+
+/// A 64-bit lane of the Keccak state, as little-endian bits.
+pub type KeccakLaneTarget = [BoolTarget; 64];
+
+/// The Keccak-f[1600] state, as 25 lanes. Lane `(x, y)` is at index `x + 5 y`.
+pub type KeccakStateTarget = [KeccakLaneTarget; 25];
+
+/// Rotates a lane left by `n` bits.
+fn rotate_left(lane: KeccakLaneTarget, n: usize) -> KeccakLaneTarget {
+    core::array::from_fn(|i| lane[(i + 64 - n) % 64])
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
+    /// Computes the chi step of Keccak-f on single bits, i.e. `a ^ (!b & c)`.
+    fn keccak_chi(&mut self, a: BoolTarget, b: BoolTarget, c: BoolTarget) -> BoolTarget {
+        let gate = KeccakChiGate::new_from_config(&self.config);
+        let (row, i) = self.find_slot(gate, &[], &[]);
+        self.connect(
+            a.target,
+            Target::wire(row, KeccakChiGate::wire_ith_input_a(i)),
+        );
+        self.connect(
+            b.target,
+            Target::wire(row, KeccakChiGate::wire_ith_input_b(i)),
+        );
+        self.connect(
+            c.target,
+            Target::wire(row, KeccakChiGate::wire_ith_input_c(i)),
+        );
+        BoolTarget::new_unsafe(Target::wire(row, KeccakChiGate::wire_ith_output(i)))
+    }
+
+    /// Applies the Keccak-f[1600] permutation to `state`.
+    pub fn keccak_f(&mut self, mut state: KeccakStateTarget) -> KeccakStateTarget {
+        for round_constant in ROUND_CONSTANTS {
+            // Theta.
+            let columns: [KeccakLaneTarget; 5] = core::array::from_fn(|x| {
+                core::array::from_fn(|z| {
+                    let c = self.xor3(state[x][z], state[x + 5][z], state[x + 10][z]);
+                    self.xor3(c, state[x + 15][z], state[x + 20][z])
+                })
+            });
+            for x in 0..5 {
+                let rotated = rotate_left(columns[(x + 1) % 5], 1);
+                let d: KeccakLaneTarget =
+                    core::array::from_fn(|z| self.xor(columns[(x + 4) % 5][z], rotated[z]));
+                for y in 0..5 {
+                    for z in 0..64 {
+                        state[x + 5 * y][z] = self.xor(state[x + 5 * y][z], d[z]);
+                    }
+                }
+            }
+
+            // Rho and pi: lane `(x, y)` moves to `(y, 2 x + 3 y)`.
+            let mut permuted = state;
+            for x in 0..5 {
+                for y in 0..5 {
+                    permuted[y + 5 * ((2 * x + 3 * y) % 5)] =
+                        rotate_left(state[x + 5 * y], RHO_OFFSETS[x + 5 * y]);
+                }
+            }
+
+            // Chi.
+            for y in 0..5 {
+                for x in 0..5 {
+                    state[x + 5 * y] = core::array::from_fn(|z| {
+                        self.keccak_chi(
+                            permuted[x + 5 * y][z],
+                            permuted[(x + 1) % 5 + 5 * y][z],
+                            permuted[(x + 2) % 5 + 5 * y][z],
+                        )
+                    });
+                }
+            }
+
+            // Iota.
+            for z in 0..64 {
+                if (round_constant >> z) & 1 == 1 {
+                    state[0][z] = self.not(state[0][z]);
+                }
+            }
+        }
+
+        state
+    }
+
+    /// Computes the Keccak-256 hash of a sequence of bytes, as used by Ethereum. Each input is
+    /// range checked to be a byte, and each output is a byte of the digest.
+    pub fn keccak256(&mut self, input: &[Target]) -> [Target; 32] {
+        let mut bits: Vec<BoolTarget> = input
+            .iter()
+            .flat_map(|&byte| self.split_le(byte, 8))
+            .collect();
+
+        // Pad with `0x01 0x00 ... 0x00 0x80`, up to a multiple of the rate.
+        let num_bytes = input.len();
+        let padded_len = (num_bytes / KECCAK256_RATE_BYTES + 1) * KECCAK256_RATE_BYTES;
+        let mut padding = vec![0u8; padded_len - num_bytes];
+        padding[0] |= 0x01;
+        *padding.last_mut().unwrap() |= 0x80;
+        for byte in padding {
+            for i in 0..8 {
+                bits.push(self.constant_bool((byte >> i) & 1 == 1));
+            }
+        }
+
+        let zero = self._false();
+        let mut state = [[zero; 64]; 25];
+        for block in bits.chunks(8 * KECCAK256_RATE_BYTES) {
+            for (lane, block_lane) in state.iter_mut().zip(block.chunks(64)) {
+                for (bit, &block_bit) in lane.iter_mut().zip(block_lane) {
+                    *bit = self.xor(*bit, block_bit);
+                }
+            }
+            state = self.keccak_f(state);
+        }
+
+        core::array::from_fn(|i| {
+            let lane = &state[i / 8];
+            let byte_bits = &lane[8 * (i % 8)..8 * (i % 8 + 1)];
+            self.le_sum(byte_bits.iter())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use keccak_hash::keccak;
+
+    use super::*;
+    use crate::field::types::Field;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use crate::plonk::verifier::verify;
+
+    #[test]
+    fn test_keccak256() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        // Cover the empty input, a single padding byte, and an input spanning two blocks.
+        for len in [0, KECCAK256_RATE_BYTES - 1, KECCAK256_RATE_BYTES + 1] {
+            let message: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+            let expected = keccak(&message).0;
+
+            let input = builder.add_virtual_targets(len);
+            for (&t, &byte) in input.iter().zip(&message) {
+                pw.set_target(t, F::from_canonical_u8(byte));
+            }
+            let digest = builder.keccak256(&input);
+            for (&t, &byte) in digest.iter().zip(&expected) {
+                let expected_byte = builder.constant(F::from_canonical_u8(byte));
+                builder.connect(t, expected_byte);
+            }
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+
+        verify(proof, &data.verifier_only, &data.common)
+    }
+}
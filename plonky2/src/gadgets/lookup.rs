@@ -0,0 +1,165 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::gates::lookup::LookupGate;
+use crate::gates::lookup_table::{LookupTable, LookupTableGate, LookupTableGenerator};
+use crate::hash::hash_types::RichField;
+use crate::iop::target::Target;
+use crate::plonk::circuit_builder::CircuitBuilder;
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
+    /// Adds a lookup table of `(input, output)` pairs to the circuit, and returns its index. If an
+    /// input appears several times, lookups of that input return the output of its first entry.
+    pub fn add_lookup_table(&mut self, table: Vec<(u16, u16)>) -> usize {
+        assert!(!table.is_empty(), "Lookup tables cannot be empty.");
+        self.luts.push(Arc::new(table));
+        self.lookup_inputs.push(Vec::new());
+        self.luts.len() - 1
+    }
+
+    /// Returns the lookup table with the given index.
+    pub fn get_lut(&self, lut_index: usize) -> LookupTable {
+        self.luts[lut_index].clone()
+    }
+
+    /// Looks up `input` in the table with index `lut_index`, and returns the matching output.
+    /// Proofs can only be generated if `input` is one of the inputs of the table.
+    pub fn add_lookup_from_index(&mut self, input: Target, lut_index: usize) -> Target {
+        assert!(
+            lut_index < self.luts.len(),
+            "No lookup table with index {lut_index}."
+        );
+        let gate = LookupGate::new_from_config(&self.config, lut_index, self.get_lut(lut_index));
+        let (row, slot) = self.find_slot(gate, &[], &[]);
+
+        let input_wire = Target::wire(row, LookupGate::wire_ith_input(slot));
+        self.connect(input, input_wire);
+        self.lookup_inputs[lut_index].push(input_wire);
+
+        Target::wire(row, LookupGate::wire_ith_output(slot))
+    }
+
+    /// Fills the unused slots of the lookup gates, then adds the gates holding each lookup table,
+    /// along with a generator for the multiplicities of its entries.
+    pub(crate) fn add_lookup_table_gates(&mut self) {
+        if self.luts.is_empty() {
+            return;
+        }
+        assert!(
+            !self.config.zero_knowledge,
+            "Lookups are not supported in zero-knowledge circuits."
+        );
+
+        for lut_index in 0..self.luts.len() {
+            let lut = self.get_lut(lut_index);
+
+            // Unused slots would look up `(0, 0)`, which may not be in the table, so we fill them
+            // with lookups of the first entry instead.
+            let num_slots =
+                LookupGate::new_from_config(&self.config, lut_index, lut.clone()).num_slots;
+            let num_lookups = self.lookup_inputs[lut_index].len();
+            let num_padding = (num_slots - num_lookups % num_slots) % num_slots;
+            if num_padding > 0 {
+                let dummy_input = self.constant(F::from_canonical_u16(lut[0].0));
+                for _ in 0..num_padding {
+                    self.add_lookup_from_index(dummy_input, lut_index);
+                }
+            }
+
+            let table_gate = LookupTableGate::new_from_config(&self.config, lut_index);
+            let mut multiplicities = Vec::with_capacity(lut.len());
+            for entries in lut.chunks(table_gate.num_slots) {
+                let constants = entries
+                    .iter()
+                    .flat_map(|&(input, output)| {
+                        [F::from_canonical_u16(input), F::from_canonical_u16(output)]
+                    })
+                    .collect();
+                let row = self.add_gate(table_gate, constants);
+                multiplicities.extend(
+                    (0..entries.len())
+                        .map(|i| Target::wire(row, LookupTableGate::wire_ith_multiplicity(i))),
+                );
+            }
+
+            self.add_simple_generator(LookupTableGenerator {
+                lut,
+                inputs: self.lookup_inputs[lut_index].clone(),
+                multiplicities,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::field::types::Field;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use crate::plonk::verifier::verify;
+
+    #[test]
+    fn test_lookup() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let not_table = (0..16).map(|i| (i, 15 - i)).collect();
+        let square_table = (0..16).map(|i| (i, i * i)).collect();
+        let not_index = builder.add_lookup_table(not_table);
+        let square_index = builder.add_lookup_table(square_table);
+
+        // More lookups than fit in a single gate, to exercise several gates per table.
+        let inputs = builder.add_virtual_targets(50);
+        for (i, &input) in inputs.iter().enumerate() {
+            let i = i as u64 % 16;
+            let not = builder.add_lookup_from_index(input, not_index);
+            let expected_not = builder.constant(F::from_canonical_u64(15 - i));
+            builder.connect(not, expected_not);
+
+            let square = builder.add_lookup_from_index(input, square_index);
+            let expected_square = builder.constant(F::from_canonical_u64(i * i));
+            builder.connect(square, expected_square);
+        }
+
+        let mut pw = PartialWitness::new();
+        for (i, &input) in inputs.iter().enumerate() {
+            pw.set_target(input, F::from_canonical_usize(i % 16));
+        }
+
+        let data = builder.build::<C>();
+        assert!(data.common.has_lookups());
+        let proof = data.prove(pw)?;
+
+        verify(proof, &data.verifier_only, &data.common)
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the lookup table")]
+    fn test_lookup_missing_input() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let lut_index = builder.add_lookup_table((0..16).map(|i| (i, i)).collect());
+        let input = builder.add_virtual_target();
+        builder.add_lookup_from_index(input, lut_index);
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(input, F::from_canonical_u64(16));
+
+        let data = builder.build::<C>();
+        let _ = data.prove(pw);
+    }
+}
@@ -19,6 +19,7 @@ use crate::plonk::vars::{
     EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
     EvaluationVarsBasePacked,
 };
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// A gate which can perform a weighted multiply-add, i.e. `result = c0 x y + c1 z`. If the config
 /// supports enough routed wires, it can support several such operations in one gate.
@@ -60,6 +61,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for ArithmeticGate
         format!("{self:?}")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_ops = src.read_usize()?;
+        Ok(Self { num_ops })
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template Arithmetic$NUM_OPS() {{
@@ -218,7 +228,7 @@ impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D> for
 }
 
 #[derive(Clone, Debug)]
-struct ArithmeticBaseGenerator<F: RichField + Extendable<D>, const D: usize> {
+pub struct ArithmeticBaseGenerator<F: RichField + Extendable<D>, const D: usize> {
     row: usize,
     const_0: F,
     const_1: F,
@@ -253,6 +263,26 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
 
         out_buffer.set_target(output_target, computed_output)
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        dst.write_field(self.const_0)?;
+        dst.write_field(self.const_1)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let const_0 = src.read_field()?;
+        let const_1 = src.read_field()?;
+        let i = src.read_usize()?;
+        Ok(Self {
+            row,
+            const_0,
+            const_1,
+            i,
+        })
+    }
 }
 
 #[cfg(test)]
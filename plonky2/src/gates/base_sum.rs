@@ -23,6 +23,7 @@ use crate::plonk::vars::{
     EvaluationVarsBasePacked,
 };
 use crate::util::log_floor;
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// A gate which can decompose a number into base B little-endian limbs.
 #[derive(Copy, Clone, Debug)]
@@ -55,6 +56,15 @@ impl<F: RichField + Extendable<D>, const D: usize, const B: usize> Gate<F, D> fo
         format!("{self:?} + Base: {B}")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_limbs)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_limbs = src.read_usize()?;
+        Ok(Self { num_limbs })
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template BaseSum$NUM_LIMBS() {{
@@ -265,6 +275,17 @@ impl<F: RichField, const B: usize> SimpleGenerator<F> for BaseSplitGenerator<B>
             out_buffer.set_target(b, b_value);
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.num_limbs)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let num_limbs = src.read_usize()?;
+        Ok(Self { row, num_limbs })
+    }
 }
 
 #[cfg(test)]
@@ -16,6 +16,7 @@ use crate::plonk::vars::{
     EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
     EvaluationVarsBasePacked,
 };
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// A gate which takes a single constant parameter and outputs that value.
 #[derive(Copy, Clone, Debug)]
@@ -40,6 +41,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for ConstantGate {
         format!("{self:?}")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_consts)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_consts = src.read_usize()?;
+        Ok(Self { num_consts })
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template Constant$NUM_CONSTANTS() {{
@@ -3,6 +3,7 @@ use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::any::Any;
 use core::fmt::{Debug, Error, Formatter};
 use core::hash::{Hash, Hasher};
 use core::ops::Range;
@@ -12,6 +13,7 @@ use hashbrown::HashMap;
 use crate::field::batch_util::batch_multiply_inplace;
 use crate::field::extension::{Extendable, FieldExtension};
 use crate::field::types::Field;
+use crate::gates::lookup::LookupTerm;
 use crate::gates::selectors::UNUSED_SELECTOR;
 use crate::gates::util::StridedConstraintConsumer;
 use crate::hash::hash_types::RichField;
@@ -21,11 +23,19 @@ use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{
     EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
 };
+use crate::util::serialization::{Buffer, IoResult};
 
 /// A custom gate.
 pub trait Gate<F: RichField + Extendable<D>, const D: usize>: 'static + Send + Sync {
     fn id(&self) -> String;
 
+    /// Writes the parameters of this gate to `dst`, so that it can be rebuilt with `deserialize`.
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()>;
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self>
+    where
+        Self: Sized;
+
     fn export_circom_verification_code(&self) -> String;
     fn export_solidity_verification_code(&self) -> String;
 
@@ -192,11 +202,49 @@ pub trait Gate<F: RichField + Extendable<D>, const D: usize>: 'static + Send + S
     fn extra_constant_wires(&self) -> Vec<(usize, usize)> {
         vec![]
     }
+
+    /// The number of terms this gate contributes to the lookup argument.
+    fn num_lookups(&self) -> usize {
+        0
+    }
+
+    /// Evaluates the terms this gate contributes to the lookup argument, which checks that over the
+    /// whole circuit, the multiplicities of each `(table, input, output)` sum to zero. A looked up
+    /// pair has multiplicity one, and a table entry minus the number of times it is looked up.
+    ///
+    /// Gates with lookups must have a degree of at least `min(num_lookups, LOOKUP_CHUNK_SIZE) + 1`.
+    fn eval_lookups(&self, _vars: EvaluationVars<F, D>) -> Vec<LookupTerm<F::Extension>> {
+        Vec::new()
+    }
+
+    /// Like `eval_lookups`, but specialized for points in the base field.
+    fn eval_lookups_base(&self, _vars: EvaluationVarsBase<F>) -> Vec<LookupTerm<F>> {
+        Vec::new()
+    }
+
+    fn eval_lookups_circuit(
+        &self,
+        _builder: &mut CircuitBuilder<F, D>,
+        _vars: EvaluationTargets<D>,
+    ) -> Vec<LookupTerm<ExtensionTarget<D>>> {
+        Vec::new()
+    }
+}
+
+/// A wrapper trait over a `Gate`, to allow for gate serialization.
+pub trait AnyGate<F: RichField + Extendable<D>, const D: usize>: Gate<F, D> {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Gate<F, D>, F: RichField + Extendable<D>, const D: usize> AnyGate<F, D> for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 /// A wrapper around an `Rc<Gate>` which implements `PartialEq`, `Eq` and `Hash` based on gate IDs.
 #[derive(Clone)]
-pub struct GateRef<F: RichField + Extendable<D>, const D: usize>(pub Arc<dyn Gate<F, D>>);
+pub struct GateRef<F: RichField + Extendable<D>, const D: usize>(pub Arc<dyn AnyGate<F, D>>);
 
 impl<F: RichField + Extendable<D>, const D: usize> GateRef<F, D> {
     pub fn new<G: Gate<F, D>>(gate: G) -> GateRef<F, D> {
@@ -247,7 +295,12 @@ pub struct PrefixedGate<F: RichField + Extendable<D>, const D: usize> {
 }
 
 /// A gate's filter designed so that it is non-zero if `s = row`.
-fn compute_filter<K: Field>(row: usize, group_range: Range<usize>, s: K, many_selector: bool) -> K {
+pub(crate) fn compute_filter<K: Field>(
+    row: usize,
+    group_range: Range<usize>,
+    s: K,
+    many_selector: bool,
+) -> K {
     debug_assert!(group_range.contains(&row));
     group_range
         .filter(|&i| i != row)
@@ -256,7 +309,7 @@ fn compute_filter<K: Field>(row: usize, group_range: Range<usize>, s: K, many_se
         .product()
 }
 
-fn compute_filter_circuit<F: RichField + Extendable<D>, const D: usize>(
+pub(crate) fn compute_filter_circuit<F: RichField + Extendable<D>, const D: usize>(
     builder: &mut CircuitBuilder<F, D>,
     row: usize,
     group_range: Range<usize>,
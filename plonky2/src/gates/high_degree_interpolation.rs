@@ -21,6 +21,7 @@ use crate::iop::wire::Wire;
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// One of the instantiations of `InterpolationGate`: allows constraints of variable
 /// degree, up to `1<<subgroup_bits`.
@@ -93,6 +94,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D>
         format!("{self:?}<D={D}>")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.subgroup_bits)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let subgroup_bits = src.read_usize()?;
+        Ok(Self::new(subgroup_bits))
+    }
+
     fn export_circom_verification_code(&self) -> String {
         todo!()
     }
@@ -182,7 +192,7 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D>
     }
 
     fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
-        let gen = InterpolationGenerator::<F, D> {
+        let gen = HighDegreeInterpolationGenerator::<F, D> {
             row,
             gate: *self,
             _phantom: PhantomData,
@@ -212,14 +222,14 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D>
 }
 
 #[derive(Debug)]
-struct InterpolationGenerator<F: RichField + Extendable<D>, const D: usize> {
+pub struct HighDegreeInterpolationGenerator<F: RichField + Extendable<D>, const D: usize> {
     row: usize,
     gate: HighDegreeInterpolationGate<F, D>,
     _phantom: PhantomData<F>,
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
-    for InterpolationGenerator<F, D>
+    for HighDegreeInterpolationGenerator<F, D>
 {
     fn dependencies(&self) -> Vec<Target> {
         let local_target = |column| {
@@ -276,6 +286,21 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
         let evaluation_value_wires = self.gate.wires_evaluation_value().map(local_wire);
         out_buffer.set_ext_wires(evaluation_value_wires, evaluation_value);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        self.gate.serialize(dst)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let gate = HighDegreeInterpolationGate::deserialize(src)?;
+        Ok(Self {
+            row,
+            gate,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]
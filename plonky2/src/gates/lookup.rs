@@ -0,0 +1,284 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use crate::field::extension::Extendable;
+use crate::field::types::Field;
+use crate::gates::gate::Gate;
+use crate::gates::lookup_table::LookupTable;
+use crate::hash::hash_types::RichField;
+use crate::iop::ext_target::ExtensionTarget;
+use crate::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use crate::iop::target::Target;
+use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::plonk::circuit_data::CircuitConfig;
+use crate::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
+
+/// The number of lookup terms batched into each helper polynomial of the lookup argument. A gate
+/// with `n` lookup terms yields constraints of degree `min(n, LOOKUP_CHUNK_SIZE) + 1`.
+pub const LOOKUP_CHUNK_SIZE: usize = 4;
+
+/// A term of the lookup argument: the pair `(input, output)` of the table with index `table`,
+/// counted `multiplicity` times.
+#[derive(Copy, Clone, Debug)]
+pub struct LookupTerm<T> {
+    pub table: T,
+    pub input: T,
+    pub output: T,
+    pub multiplicity: T,
+}
+
+/// A gate which looks up pairs `(input, output)` in a lookup table. It has no constraints of its
+/// own: the lookup argument checks that each pair is one of the entries of the table, which are
+/// provided by `LookupTableGate`s.
+#[derive(Clone, Debug)]
+pub struct LookupGate {
+    /// Number of lookups per gate.
+    pub num_slots: usize,
+    /// Index of the looked up table in the circuit.
+    pub lut_index: usize,
+    /// The looked up table, used to generate the outputs.
+    pub lut: LookupTable,
+}
+
+impl LookupGate {
+    pub fn new_from_config(config: &CircuitConfig, lut_index: usize, lut: LookupTable) -> Self {
+        Self {
+            num_slots: config.num_routed_wires / 2,
+            lut_index,
+            lut,
+        }
+    }
+
+    pub const fn wire_ith_input(i: usize) -> usize {
+        2 * i
+    }
+
+    pub const fn wire_ith_output(i: usize) -> usize {
+        2 * i + 1
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for LookupGate {
+    fn id(&self) -> String {
+        // The table itself is not included, as it is determined by its index.
+        format!(
+            "LookupGate {{ num_slots: {}, lut_index: {} }}",
+            self.num_slots, self.lut_index
+        )
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_slots)?;
+        dst.write_usize(self.lut_index)?;
+        dst.write_lut(&self.lut)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_slots = src.read_usize()?;
+        let lut_index = src.read_usize()?;
+        let lut = src.read_lut()?;
+        Ok(Self {
+            num_slots,
+            lut_index,
+            lut,
+        })
+    }
+
+    fn export_circom_verification_code(&self) -> String {
+        "template LookupGateLib() {
+  signal input constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal input public_input_hash[4];
+  signal input constraints[NUM_GATE_CONSTRAINTS()][2];
+  signal output out[NUM_GATE_CONSTRAINTS()][2];
+
+  for (var i = 0; i < NUM_GATE_CONSTRAINTS(); i++) {
+    out[i] <== constraints[i];
+  }
+}"
+        .to_string()
+    }
+    fn export_solidity_verification_code(&self) -> String {
+        "library LookupGateLib {
+    function set_filter(GatesUtilsLib.EvaluationVars memory ev) internal pure {
+        $SET_FILTER;
+    }
+    function eval(GatesUtilsLib.EvaluationVars memory, uint64[2][$NUM_GATE_CONSTRAINTS] memory) internal pure {}
+}"
+        .to_string()
+    }
+
+    fn eval_unfiltered(&self, _vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        Vec::new()
+    }
+
+    fn eval_unfiltered_base_batch(&self, _vars: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        Vec::new()
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        _builder: &mut CircuitBuilder<F, D>,
+        _vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        Vec::new()
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        (0..self.num_slots)
+            .map(|slot| {
+                let g: Box<dyn WitnessGenerator<F>> = Box::new(
+                    LookupGenerator {
+                        row,
+                        slot,
+                        lut: self.lut.clone(),
+                    }
+                    .adapter(),
+                );
+                g
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        2 * self.num_slots
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    fn degree(&self) -> usize {
+        self.num_slots.min(LOOKUP_CHUNK_SIZE) + 1
+    }
+
+    fn num_constraints(&self) -> usize {
+        0
+    }
+
+    fn num_lookups(&self) -> usize {
+        self.num_slots
+    }
+
+    fn eval_lookups(&self, vars: EvaluationVars<F, D>) -> Vec<LookupTerm<F::Extension>> {
+        let table = F::Extension::from_canonical_usize(self.lut_index);
+        (0..self.num_slots)
+            .map(|i| LookupTerm {
+                table,
+                input: vars.local_wires[Self::wire_ith_input(i)],
+                output: vars.local_wires[Self::wire_ith_output(i)],
+                multiplicity: F::Extension::ONE,
+            })
+            .collect()
+    }
+
+    fn eval_lookups_base(&self, vars: EvaluationVarsBase<F>) -> Vec<LookupTerm<F>> {
+        let table = F::from_canonical_usize(self.lut_index);
+        (0..self.num_slots)
+            .map(|i| LookupTerm {
+                table,
+                input: vars.local_wires[Self::wire_ith_input(i)],
+                output: vars.local_wires[Self::wire_ith_output(i)],
+                multiplicity: F::ONE,
+            })
+            .collect()
+    }
+
+    fn eval_lookups_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<LookupTerm<ExtensionTarget<D>>> {
+        let table = builder.constant_extension(F::Extension::from_canonical_usize(self.lut_index));
+        let one = builder.one_extension();
+        (0..self.num_slots)
+            .map(|i| LookupTerm {
+                table,
+                input: vars.local_wires[Self::wire_ith_input(i)],
+                output: vars.local_wires[Self::wire_ith_output(i)],
+                multiplicity: one,
+            })
+            .collect()
+    }
+}
+
+/// Sets the output of a lookup to the first entry of the table with a matching input.
+#[derive(Clone, Debug)]
+pub struct LookupGenerator {
+    row: usize,
+    slot: usize,
+    lut: LookupTable,
+}
+
+impl<F: RichField> SimpleGenerator<F> for LookupGenerator {
+    fn dependencies(&self) -> Vec<Target> {
+        vec![Target::wire(
+            self.row,
+            LookupGate::wire_ith_input(self.slot),
+        )]
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let get_wire = |wire: usize| -> F { witness.get_target(Target::wire(self.row, wire)) };
+
+        let input = get_wire(LookupGate::wire_ith_input(self.slot)).to_canonical_u64();
+        let &(_, output) = self
+            .lut
+            .iter()
+            .find(|&&(inp, _)| inp as u64 == input)
+            .unwrap_or_else(|| panic!("Input {input} is not in the lookup table."));
+
+        out_buffer.set_target(
+            Target::wire(self.row, LookupGate::wire_ith_output(self.slot)),
+            F::from_canonical_u16(output),
+        );
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.slot)?;
+        dst.write_lut(&self.lut)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let slot = src.read_usize()?;
+        let lut = src.read_lut()?;
+        Ok(Self { row, slot, lut })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use crate::field::goldilocks_field::GoldilocksField;
+    use crate::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use crate::gates::lookup::LookupGate;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    fn gate() -> LookupGate {
+        let lut = Arc::new((0..16).map(|i| (i, 15 - i)).collect());
+        LookupGate::new_from_config(&CircuitConfig::standard_recursion_config(), 0, lut)
+    }
+
+    #[test]
+    fn low_degree() {
+        test_low_degree::<GoldilocksField, _, 4>(gate())
+    }
+
+    #[test]
+    fn eval_fns() -> anyhow::Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        test_eval_fns::<F, C, _, D>(gate())
+    }
+}
@@ -0,0 +1,271 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use crate::field::extension::Extendable;
+use crate::field::types::Field;
+use crate::gates::gate::Gate;
+use crate::gates::lookup::{LookupTerm, LOOKUP_CHUNK_SIZE};
+use crate::hash::hash_types::RichField;
+use crate::iop::ext_target::ExtensionTarget;
+use crate::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use crate::iop::target::Target;
+use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::plonk::circuit_data::CircuitConfig;
+use crate::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
+
+/// A lookup table, as a list of `(input, output)` pairs.
+pub type LookupTable = Arc<Vec<(u16, u16)>>;
+
+/// A gate which provides entries of a lookup table, along with the number of times each of them
+/// is looked up. The entries are stored in the gate's constants, so that they are fixed by the
+/// circuit without any work for the verifier; circuits with large tables can increase
+/// `CircuitConfig::num_constants` to fit more entries in each row.
+#[derive(Copy, Clone, Debug)]
+pub struct LookupTableGate {
+    /// Number of table entries per gate.
+    pub num_slots: usize,
+    /// Index of the table in the circuit.
+    pub lut_index: usize,
+}
+
+impl LookupTableGate {
+    pub fn new_from_config(config: &CircuitConfig, lut_index: usize) -> Self {
+        assert!(
+            config.num_constants >= 2,
+            "Lookup tables need at least two constants."
+        );
+        Self {
+            num_slots: config.num_constants / 2,
+            lut_index,
+        }
+    }
+
+    pub const fn const_ith_input(i: usize) -> usize {
+        2 * i
+    }
+
+    pub const fn const_ith_output(i: usize) -> usize {
+        2 * i + 1
+    }
+
+    pub const fn wire_ith_multiplicity(i: usize) -> usize {
+        i
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for LookupTableGate {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_slots)?;
+        dst.write_usize(self.lut_index)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_slots = src.read_usize()?;
+        let lut_index = src.read_usize()?;
+        Ok(Self {
+            num_slots,
+            lut_index,
+        })
+    }
+
+    fn export_circom_verification_code(&self) -> String {
+        "template LookupTableGateLib() {
+  signal input constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal input public_input_hash[4];
+  signal input constraints[NUM_GATE_CONSTRAINTS()][2];
+  signal output out[NUM_GATE_CONSTRAINTS()][2];
+
+  for (var i = 0; i < NUM_GATE_CONSTRAINTS(); i++) {
+    out[i] <== constraints[i];
+  }
+}"
+        .to_string()
+    }
+    fn export_solidity_verification_code(&self) -> String {
+        "library LookupTableGateLib {
+    function set_filter(GatesUtilsLib.EvaluationVars memory ev) internal pure {
+        $SET_FILTER;
+    }
+    function eval(GatesUtilsLib.EvaluationVars memory, uint64[2][$NUM_GATE_CONSTRAINTS] memory) internal pure {}
+}"
+        .to_string()
+    }
+
+    fn eval_unfiltered(&self, _vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        Vec::new()
+    }
+
+    fn eval_unfiltered_base_batch(&self, _vars: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        Vec::new()
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        _builder: &mut CircuitBuilder<F, D>,
+        _vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        Vec::new()
+    }
+
+    fn generators(&self, _row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        // The multiplicities depend on all the lookups in the table, so they are filled by a
+        // single `LookupTableGenerator` per table, added by the circuit builder.
+        Vec::new()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_slots
+    }
+
+    fn num_constants(&self) -> usize {
+        2 * self.num_slots
+    }
+
+    fn degree(&self) -> usize {
+        self.num_slots.min(LOOKUP_CHUNK_SIZE) + 1
+    }
+
+    fn num_constraints(&self) -> usize {
+        0
+    }
+
+    fn num_lookups(&self) -> usize {
+        self.num_slots
+    }
+
+    fn eval_lookups(&self, vars: EvaluationVars<F, D>) -> Vec<LookupTerm<F::Extension>> {
+        let table = F::Extension::from_canonical_usize(self.lut_index);
+        (0..self.num_slots)
+            .map(|i| LookupTerm {
+                table,
+                input: vars.local_constants[Self::const_ith_input(i)],
+                output: vars.local_constants[Self::const_ith_output(i)],
+                multiplicity: -vars.local_wires[Self::wire_ith_multiplicity(i)],
+            })
+            .collect()
+    }
+
+    fn eval_lookups_base(&self, vars: EvaluationVarsBase<F>) -> Vec<LookupTerm<F>> {
+        let table = F::from_canonical_usize(self.lut_index);
+        (0..self.num_slots)
+            .map(|i| LookupTerm {
+                table,
+                input: vars.local_constants[Self::const_ith_input(i)],
+                output: vars.local_constants[Self::const_ith_output(i)],
+                multiplicity: -vars.local_wires[Self::wire_ith_multiplicity(i)],
+            })
+            .collect()
+    }
+
+    fn eval_lookups_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<LookupTerm<ExtensionTarget<D>>> {
+        let table = builder.constant_extension(F::Extension::from_canonical_usize(self.lut_index));
+        (0..self.num_slots)
+            .map(|i| LookupTerm {
+                table,
+                input: vars.local_constants[Self::const_ith_input(i)],
+                output: vars.local_constants[Self::const_ith_output(i)],
+                multiplicity: builder.mul_const_extension(
+                    F::NEG_ONE,
+                    vars.local_wires[Self::wire_ith_multiplicity(i)],
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Counts the number of times each entry of a table is looked up. A lookup is attributed to the
+/// first entry with a matching input, as in `LookupGenerator`.
+#[derive(Clone, Debug)]
+pub struct LookupTableGenerator {
+    pub(crate) lut: LookupTable,
+    /// The input wires of all the lookups in the table.
+    pub(crate) inputs: Vec<Target>,
+    /// The multiplicity wires of the entries of the table, in order.
+    pub(crate) multiplicities: Vec<Target>,
+}
+
+impl<F: RichField> SimpleGenerator<F> for LookupTableGenerator {
+    fn dependencies(&self) -> Vec<Target> {
+        self.inputs.clone()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let mut first_entry = vec![None; 1 << 16];
+        for (i, &(input, _)) in self.lut.iter().enumerate().rev() {
+            first_entry[input as usize] = Some(i);
+        }
+
+        let mut counts = vec![0usize; self.lut.len()];
+        for &t in &self.inputs {
+            let input = witness.get_target(t).to_canonical_u64();
+            let entry = usize::try_from(input)
+                .ok()
+                .and_then(|i| first_entry.get(i).copied().flatten())
+                .unwrap_or_else(|| panic!("Input {input} is not in the lookup table."));
+            counts[entry] += 1;
+        }
+
+        for (&t, count) in self.multiplicities.iter().zip(counts) {
+            out_buffer.set_target(t, F::from_canonical_usize(count));
+        }
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_lut(&self.lut)?;
+        dst.write_target_vec(&self.inputs)?;
+        dst.write_target_vec(&self.multiplicities)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let lut = src.read_lut()?;
+        let inputs = src.read_target_vec()?;
+        let multiplicities = src.read_target_vec()?;
+        Ok(Self {
+            lut,
+            inputs,
+            multiplicities,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field::goldilocks_field::GoldilocksField;
+    use crate::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use crate::gates::lookup_table::LookupTableGate;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    fn gate() -> LookupTableGate {
+        LookupTableGate::new_from_config(&CircuitConfig::standard_recursion_config(), 0)
+    }
+
+    #[test]
+    fn low_degree() {
+        test_low_degree::<GoldilocksField, _, 4>(gate())
+    }
+
+    #[test]
+    fn eval_fns() -> anyhow::Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        test_eval_fns::<F, C, _, D>(gate())
+    }
+}
@@ -22,6 +22,7 @@ use crate::iop::wire::Wire;
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// One of the instantiations of `InterpolationGate`: all constraints are degree <= 2.
 /// The lower degree is a tradeoff for more gates (`eval_unfiltered_recursively` for
@@ -86,6 +87,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for LowDegreeInter
         format!("{self:?}<D={D}>")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.subgroup_bits)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let subgroup_bits = src.read_usize()?;
+        Ok(Self::new(subgroup_bits))
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template LowDegreeInterpolation$SUBGROUP_BITS() {{
@@ -482,7 +492,7 @@ function two_adic_subgroup(i) {{
     }
 
     fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
-        let gen = InterpolationGenerator::<F, D> {
+        let gen = LowDegreeInterpolationGenerator::<F, D> {
             row,
             gate: *self,
             _phantom: PhantomData,
@@ -511,14 +521,14 @@ function two_adic_subgroup(i) {{
 }
 
 #[derive(Debug)]
-struct InterpolationGenerator<F: RichField + Extendable<D>, const D: usize> {
+pub struct LowDegreeInterpolationGenerator<F: RichField + Extendable<D>, const D: usize> {
     row: usize,
     gate: LowDegreeInterpolationGate<F, D>,
     _phantom: PhantomData<F>,
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
-    for InterpolationGenerator<F, D>
+    for LowDegreeInterpolationGenerator<F, D>
 {
     fn dependencies(&self) -> Vec<Target> {
         let local_target = |column| {
@@ -597,6 +607,21 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
         let evaluation_value_wires = self.gate.wires_evaluation_value().map(local_wire);
         out_buffer.set_ext_wires(evaluation_value_wires, evaluation_value);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        self.gate.serialize(dst)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let gate = LowDegreeInterpolationGate::deserialize(src)?;
+        Ok(Self {
+            row,
+            gate,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -9,6 +9,9 @@ pub mod exponentiation;
 pub mod gate;
 pub mod high_degree_interpolation;
 pub mod interpolation;
+pub mod keccak_chi;
+pub mod lookup;
+pub mod lookup_table;
 pub mod low_degree_interpolation;
 pub mod multiplication_extension;
 pub mod noop;
@@ -21,6 +24,7 @@ pub mod reducing;
 pub mod reducing_extension;
 pub(crate) mod selectors;
 pub mod util;
+pub mod xor3;
 
 // Can't use #[cfg(test)] here because it needs to be visible to other crates.
 // See https://github.com/rust-lang/cargo/issues/8379
@@ -15,6 +15,7 @@ use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::circuit_data::CircuitConfig;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// A gate which can perform a weighted multiplication, i.e. `result = c0 x y`. If the config
 /// supports enough routed wires, it can support several such operations in one gate.
@@ -53,6 +54,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for MulExtensionGa
         format!("{self:?}")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_ops = src.read_usize()?;
+        Ok(Self { num_ops })
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template MultiplicationExtension$NUM_OPS() {{
@@ -194,7 +204,7 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for MulExtensionGa
 }
 
 #[derive(Clone, Debug)]
-struct MulExtensionGenerator<F: RichField + Extendable<D>, const D: usize> {
+pub struct MulExtensionGenerator<F: RichField + Extendable<D>, const D: usize> {
     row: usize,
     const_0: F,
     i: usize,
@@ -228,6 +238,19 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
 
         out_buffer.set_extension_target(output_target, computed_output)
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        dst.write_field(self.const_0)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let const_0 = src.read_field()?;
+        let i = src.read_usize()?;
+        Ok(Self { row, const_0, i })
+    }
 }
 
 #[cfg(test)]
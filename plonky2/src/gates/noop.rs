@@ -9,6 +9,7 @@ use crate::iop::ext_target::ExtensionTarget;
 use crate::iop::generator::WitnessGenerator;
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBaseBatch};
+use crate::util::serialization::{Buffer, IoResult};
 
 /// A gate which does nothing.
 pub struct NoopGate;
@@ -18,11 +19,36 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for NoopGate {
         "NoopGate".into()
     }
 
+    fn serialize(&self, _dst: &mut Vec<u8>) -> IoResult<()> {
+        Ok(())
+    }
+
+    fn deserialize(_src: &mut Buffer) -> IoResult<Self> {
+        Ok(Self)
+    }
+
     fn export_circom_verification_code(&self) -> String {
-        todo!()
+        "template NoopGate() {
+  signal input constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal input public_input_hash[4];
+  signal input constraints[NUM_GATE_CONSTRAINTS()][2];
+  signal output out[NUM_GATE_CONSTRAINTS()][2];
+
+  for (var i = 0; i < NUM_GATE_CONSTRAINTS(); i++) {
+    out[i] <== constraints[i];
+  }
+}"
+        .to_string()
     }
     fn export_solidity_verification_code(&self) -> String {
-        todo!()
+        "library NoopGateLib {
+    function set_filter(GatesUtilsLib.EvaluationVars memory ev) internal pure {
+        $SET_FILTER;
+    }
+    function eval(GatesUtilsLib.EvaluationVars memory, uint64[2][$NUM_GATE_CONSTRAINTS] memory) internal pure {}
+}"
+        .to_string()
     }
 
     fn eval_unfiltered(&self, _vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
@@ -20,6 +20,7 @@ use crate::iop::wire::Wire;
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// Evaluates a full Poseidon permutation with 12 state elements.
 ///
@@ -100,6 +101,14 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for PoseidonGate<F
         format!("{self:?}<WIDTH={SPONGE_WIDTH}>")
     }
 
+    fn serialize(&self, _dst: &mut Vec<u8>) -> IoResult<()> {
+        Ok(())
+    }
+
+    fn deserialize(_src: &mut Buffer) -> IoResult<Self> {
+        Ok(PoseidonGate::new())
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template Poseidon12() {{
@@ -250,7 +259,7 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for PoseidonGate<F
     index++;
   }}
 
-  for (var i = index + 1; i < NUM_GATE_CONSTRAINTS(); i++) {{
+  for (var i = index; i < NUM_GATE_CONSTRAINTS(); i++) {{
     out[i] <== constraints[i];
   }}
 }}
@@ -396,7 +405,235 @@ function MDS_MATRIX_DIAG(i) {{
         template_str
     }
     fn export_solidity_verification_code(&self) -> String {
-        todo!()
+        let mut template_str = "library Poseidon12Lib {
+    using GoldilocksExtLib for uint64[2];
+    function set_filter(GatesUtilsLib.EvaluationVars memory ev) internal pure {
+        $SET_FILTER;
+    }
+    function fast_partial_first_round_constant() internal pure returns (uint64[12] memory value) {
+        $SET_FAST_PARTIAL_FIRST_ROUND_CONSTANT;
+    }
+    function fast_partial_round_constants() internal pure returns (uint64[$N_PARTIAL_ROUNDS] memory value) {
+        $SET_FAST_PARTIAL_ROUND_CONSTANTS;
+    }
+    function fast_partial_round_initial_matrix() internal pure returns (uint64[11][11] memory value) {
+        $SET_FAST_PARTIAL_ROUND_INITIAL_MATRIX;
+    }
+    function fast_partial_round_w_hats() internal pure returns (uint64[11][$N_PARTIAL_ROUNDS] memory value) {
+        $SET_FAST_PARTIAL_ROUND_W_HATS;
+    }
+    function fast_partial_round_vs() internal pure returns (uint64[11][$N_PARTIAL_ROUNDS] memory value) {
+        $SET_FAST_PARTIAL_ROUND_VS;
+    }
+    function sbox(uint64[2] memory x) internal pure returns (uint64[2] memory) {
+        uint64[2] memory x2 = x.square();
+        return x.mul(x2).mul(x2.square());
+    }
+    function mds_layer(uint64[2][12] memory state) internal pure returns (uint64[2][12] memory res) {
+        uint64[12] memory circ = PoseidonLib.mds_matrix_circ();
+        uint64[12] memory diag = PoseidonLib.mds_matrix_diag();
+        for (uint32 r = 0; r < 12; r++) {
+            res[r] = state[r].scalar_mul(diag[r]);
+            for (uint32 i = 0; i < 12; i++) {
+                res[r] = res[r].add(state[(i + r) % 12].scalar_mul(circ[i]));
+            }
+        }
+    }
+    function mds_partial_layer_init(uint64[2][12] memory state) internal pure returns (uint64[2][12] memory res) {
+        uint64[11][11] memory m = fast_partial_round_initial_matrix();
+        res[0] = state[0];
+        for (uint32 r = 1; r < 12; r++) {
+            for (uint32 c = 1; c < 12; c++) {
+                res[c] = res[c].add(state[r].scalar_mul(m[r - 1][c - 1]));
+            }
+        }
+    }
+    function mds_partial_layer_fast(uint64[2][12] memory state, uint64[11] memory w_hats, uint64[11] memory vs) internal pure returns (uint64[2][12] memory res) {
+        // d = [M_00 | w^] dot [state]
+        res[0] = state[0].scalar_mul($MDS0TO0);
+        for (uint32 i = 1; i < 12; i++) {
+            res[0] = res[0].add(state[i].scalar_mul(w_hats[i - 1]));
+        }
+        // result = [d] concat [state[0] * v + state[shift up by 1]]
+        for (uint32 i = 1; i < 12; i++) {
+            res[i] = state[i].add(state[0].scalar_mul(vs[i - 1]));
+        }
+    }
+    function first_full_rounds(GatesUtilsLib.EvaluationVars memory ev, uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints, uint64[2][12] memory state, uint64[$NUM_ROUND_CONSTANTS] memory rc, uint32 index) internal pure returns (uint64[2][12] memory, uint32) {
+        for (uint32 r = 0; r < $HALF_N_FULL_ROUNDS; r++) {
+            for (uint32 i = 0; i < 12; i++) {
+                state[i] = state[i].add(GoldilocksExtLib.from_base(rc[12 * r + i]));
+                if (r != 0) {
+                    uint64[2] memory sbox_in = ev.wires[$START_FULL_0 + 12 * (r - 1) + i];
+                    GatesUtilsLib.push(constraints, ev.filter, index++, state[i].sub(sbox_in));
+                    state[i] = sbox_in;
+                }
+                state[i] = sbox(state[i]);
+            }
+            state = mds_layer(state);
+        }
+        return (state, index);
+    }
+    function partial_first_constant_layer(uint64[2][12] memory state) internal pure {
+        uint64[12] memory first_round_constant = fast_partial_first_round_constant();
+        for (uint32 i = 0; i < 12; i++) {
+            state[i] = state[i].add(GoldilocksExtLib.from_base(first_round_constant[i]));
+        }
+    }
+    function partial_rounds(GatesUtilsLib.EvaluationVars memory ev, uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints, uint64[2][12] memory state, uint32 index) internal pure returns (uint64[2][12] memory, uint32) {
+        partial_first_constant_layer(state);
+        state = mds_partial_layer_init(state);
+        uint64[$N_PARTIAL_ROUNDS] memory round_constants = fast_partial_round_constants();
+        uint64[11][$N_PARTIAL_ROUNDS] memory w_hats = fast_partial_round_w_hats();
+        uint64[11][$N_PARTIAL_ROUNDS] memory vs = fast_partial_round_vs();
+        for (uint32 r = 0; r < $N_PARTIAL_ROUNDS; r++) {
+            uint64[2] memory sbox_in = ev.wires[$START_PARTIAL + r];
+            GatesUtilsLib.push(constraints, ev.filter, index++, state[0].sub(sbox_in));
+            state[0] = sbox(sbox_in);
+            if (r < $N_PARTIAL_ROUNDS - 1) {
+                state[0] = state[0].add(GoldilocksExtLib.from_base(round_constants[r]));
+            }
+            state = mds_partial_layer_fast(state, w_hats[r], vs[r]);
+        }
+        return (state, index);
+    }
+    function second_full_rounds(GatesUtilsLib.EvaluationVars memory ev, uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints, uint64[2][12] memory state, uint64[$NUM_ROUND_CONSTANTS] memory rc, uint32 index) internal pure returns (uint64[2][12] memory, uint32) {
+        for (uint32 r = 0; r < $HALF_N_FULL_ROUNDS; r++) {
+            for (uint32 i = 0; i < 12; i++) {
+                state[i] = state[i].add(GoldilocksExtLib.from_base(rc[12 * ($HALF_N_FULL_ROUNDS + $N_PARTIAL_ROUNDS + r) + i]));
+                uint64[2] memory sbox_in = ev.wires[$START_FULL_1 + 12 * r + i];
+                GatesUtilsLib.push(constraints, ev.filter, index++, state[i].sub(sbox_in));
+                state[i] = sbox(sbox_in);
+            }
+            state = mds_layer(state);
+        }
+        return (state, index);
+    }
+    function eval(GatesUtilsLib.EvaluationVars memory ev, uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints) internal pure {
+        uint32 index = 0;
+        uint64[2] memory swap = ev.wires[$WIRE_SWAP];
+        GatesUtilsLib.push(constraints, ev.filter, index++, swap.mul(swap.sub(GoldilocksExtLib.one())));
+
+        // Compute the possibly-swapped input layer.
+        uint64[2][12] memory state;
+        for (uint32 i = 0; i < 4; i++) {
+            uint64[2] memory delta = ev.wires[$START_DELTA + i];
+            GatesUtilsLib.push(constraints, ev.filter, index++, swap.mul(ev.wires[i + 4].sub(ev.wires[i])).sub(delta));
+            state[i] = ev.wires[i].add(delta);
+            state[i + 4] = ev.wires[i + 4].sub(delta);
+        }
+        for (uint32 i = 8; i < 12; i++) {
+            state[i] = ev.wires[i];
+        }
+
+        uint64[$NUM_ROUND_CONSTANTS] memory rc = PoseidonLib.all_round_constants();
+        (state, index) = first_full_rounds(ev, constraints, state, rc, index);
+        (state, index) = partial_rounds(ev, constraints, state, index);
+        (state, index) = second_full_rounds(ev, constraints, state, rc, index);
+
+        for (uint32 i = 0; i < 12; i++) {
+            GatesUtilsLib.push(constraints, ev.filter, index++, state[i].sub(ev.wires[12 + i]));
+        }
+    }
+}"
+        .to_string();
+        template_str = template_str.replace("$WIRE_SWAP", &Self::WIRE_SWAP.to_string());
+        template_str = template_str.replace("$START_DELTA", &Self::START_DELTA.to_string());
+        template_str = template_str.replace("$START_FULL_0", &Self::START_FULL_0.to_string());
+        template_str = template_str.replace("$START_FULL_1", &Self::START_FULL_1.to_string());
+        template_str = template_str.replace("$START_PARTIAL", &Self::START_PARTIAL.to_string());
+        template_str = template_str.replace(
+            "$HALF_N_FULL_ROUNDS",
+            &poseidon::HALF_N_FULL_ROUNDS.to_string(),
+        );
+        template_str =
+            template_str.replace("$N_PARTIAL_ROUNDS", &poseidon::N_PARTIAL_ROUNDS.to_string());
+        template_str = template_str.replace(
+            "$NUM_ROUND_CONSTANTS",
+            &(SPONGE_WIDTH * poseidon::N_ROUNDS).to_string(),
+        );
+        template_str = template_str.replace(
+            "$MDS0TO0",
+            &(<F as Poseidon>::MDS_MATRIX_CIRC[0] + <F as Poseidon>::MDS_MATRIX_DIAG[0])
+                .to_string(),
+        );
+
+        let mut first_round_const_str = "".to_owned();
+        for i in 0..12 {
+            first_round_const_str += &("        value[".to_owned()
+                + &i.to_string()
+                + "] = "
+                + &<F as Poseidon>::FAST_PARTIAL_FIRST_ROUND_CONSTANT[i].to_string()
+                + ";\n");
+        }
+        template_str = template_str.replace(
+            "        $SET_FAST_PARTIAL_FIRST_ROUND_CONSTANT;\n",
+            &first_round_const_str,
+        );
+
+        let mut partial_const_str = "".to_owned();
+        for i in 0..poseidon::N_PARTIAL_ROUNDS {
+            partial_const_str += &("        value[".to_owned()
+                + &i.to_string()
+                + "] = "
+                + &<F as Poseidon>::FAST_PARTIAL_ROUND_CONSTANTS[i].to_string()
+                + ";\n");
+        }
+        template_str = template_str.replace(
+            "        $SET_FAST_PARTIAL_ROUND_CONSTANTS;\n",
+            &partial_const_str,
+        );
+
+        let mut init_m_str = "".to_owned();
+        for i in 0..11 {
+            for j in 0..11 {
+                init_m_str += &("        value[".to_owned()
+                    + &i.to_string()
+                    + "]["
+                    + &j.to_string()
+                    + "] = "
+                    + &<F as Poseidon>::FAST_PARTIAL_ROUND_INITIAL_MATRIX[i][j].to_string()
+                    + ";\n");
+            }
+        }
+        template_str = template_str.replace(
+            "        $SET_FAST_PARTIAL_ROUND_INITIAL_MATRIX;\n",
+            &init_m_str,
+        );
+
+        let mut partial_hats_str = "".to_owned();
+        for i in 0..poseidon::N_PARTIAL_ROUNDS {
+            for j in 0..11 {
+                partial_hats_str += &("        value[".to_owned()
+                    + &i.to_string()
+                    + "]["
+                    + &j.to_string()
+                    + "] = "
+                    + &<F as Poseidon>::FAST_PARTIAL_ROUND_W_HATS[i][j].to_string()
+                    + ";\n");
+            }
+        }
+        template_str = template_str.replace(
+            "        $SET_FAST_PARTIAL_ROUND_W_HATS;\n",
+            &partial_hats_str,
+        );
+
+        let mut partial_vs_str = "".to_owned();
+        for i in 0..poseidon::N_PARTIAL_ROUNDS {
+            for j in 0..11 {
+                partial_vs_str += &("        value[".to_owned()
+                    + &i.to_string()
+                    + "]["
+                    + &j.to_string()
+                    + "] = "
+                    + &<F as Poseidon>::FAST_PARTIAL_ROUND_VS[i][j].to_string()
+                    + ";\n");
+            }
+        }
+        template_str =
+            template_str.replace("        $SET_FAST_PARTIAL_ROUND_VS;\n", &partial_vs_str);
+
+        template_str
     }
 
     fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
@@ -702,7 +939,7 @@ function MDS_MATRIX_DIAG(i) {{
 }
 
 #[derive(Debug)]
-struct PoseidonGenerator<F: RichField + Extendable<D> + Poseidon, const D: usize> {
+pub struct PoseidonGenerator<F: RichField + Extendable<D> + Poseidon, const D: usize> {
     row: usize,
     _phantom: PhantomData<F>,
 }
@@ -799,6 +1036,18 @@ impl<F: RichField + Extendable<D> + Poseidon, const D: usize> SimpleGenerator<F>
             out_buffer.set_wire(local_wire(PoseidonGate::<F, D>::wire_output(i)), state[i]);
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        Ok(Self {
+            row,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]
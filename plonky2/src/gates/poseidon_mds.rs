@@ -19,6 +19,7 @@ use crate::iop::target::Target;
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// Poseidon MDS Gate
 #[derive(Debug, Default)]
@@ -119,6 +120,14 @@ impl<F: RichField + Extendable<D> + Poseidon, const D: usize> Gate<F, D> for Pos
         format!("{self:?}<WIDTH={SPONGE_WIDTH}>")
     }
 
+    fn serialize(&self, _dst: &mut Vec<u8>) -> IoResult<()> {
+        Ok(())
+    }
+
+    fn deserialize(_src: &mut Buffer) -> IoResult<Self> {
+        Ok(PoseidonMdsGate::new())
+    }
+
     fn export_circom_verification_code(&self) -> String {
         assert_eq!(D, 2);
         assert_eq!(SPONGE_WIDTH, 12);
@@ -162,7 +171,30 @@ impl<F: RichField + Extendable<D> + Poseidon, const D: usize> Gate<F, D> for Pos
         template_str
     }
     fn export_solidity_verification_code(&self) -> String {
-        todo!()
+        assert_eq!(D, 2);
+        assert_eq!(SPONGE_WIDTH, 12);
+        "library PoseidonMdsGate12Lib {
+    using GoldilocksExtLib for uint64[2];
+    function set_filter(GatesUtilsLib.EvaluationVars memory ev) internal pure {
+        $SET_FILTER;
+    }
+    function eval(GatesUtilsLib.EvaluationVars memory ev, uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints) internal pure {
+        uint64[12] memory circ = PoseidonLib.mds_matrix_circ();
+        uint64[12] memory diag = PoseidonLib.mds_matrix_diag();
+        for (uint32 r = 0; r < 12; r++) {
+            uint64[2] memory acc0 = ev.wires[r * 2].scalar_mul(diag[r]);
+            uint64[2] memory acc1 = ev.wires[r * 2 + 1].scalar_mul(diag[r]);
+            for (uint32 i = 0; i < 12; i++) {
+                uint32 j = (i + r) % 12;
+                acc0 = acc0.add(ev.wires[j * 2].scalar_mul(circ[i]));
+                acc1 = acc1.add(ev.wires[j * 2 + 1].scalar_mul(circ[i]));
+            }
+            GatesUtilsLib.push(constraints, ev.filter, r * 2, ev.wires[(12 + r) * 2].sub(acc0));
+            GatesUtilsLib.push(constraints, ev.filter, r * 2 + 1, ev.wires[(12 + r) * 2 + 1].sub(acc1));
+        }
+    }
+}"
+        .to_string()
     }
 
     fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
@@ -249,7 +281,7 @@ impl<F: RichField + Extendable<D> + Poseidon, const D: usize> Gate<F, D> for Pos
 }
 
 #[derive(Clone, Debug)]
-struct PoseidonMdsGenerator<const D: usize> {
+pub struct PoseidonMdsGenerator<const D: usize> {
     row: usize,
 }
 
@@ -284,6 +316,15 @@ impl<F: RichField + Extendable<D> + Poseidon, const D: usize> SimpleGenerator<F>
             );
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        Ok(Self { row })
+    }
 }
 
 #[cfg(test)]
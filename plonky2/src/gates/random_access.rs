@@ -24,6 +24,7 @@ use crate::plonk::vars::{
     EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
     EvaluationVarsBasePacked,
 };
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// A gate for checking that a particular element of a list matches a given value.
 #[derive(Copy, Clone, Debug)]
@@ -122,6 +123,19 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for RandomAccessGa
         format!("{self:?}<D={D}>")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.bits)?;
+        dst.write_usize(self.num_copies)?;
+        dst.write_usize(self.num_extra_constants)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let bits = src.read_usize()?;
+        let num_copies = src.read_usize()?;
+        let num_extra_constants = src.read_usize()?;
+        Ok(Self::new(num_copies, bits, num_extra_constants))
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template RandomAccessB$BITSC$NUM_COPIESE$NUM_EXTRA_CONSTANTS() {{
@@ -451,7 +465,7 @@ impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D>
 }
 
 #[derive(Debug)]
-struct RandomAccessGenerator<F: RichField + Extendable<D>, const D: usize> {
+pub struct RandomAccessGenerator<F: RichField + Extendable<D>, const D: usize> {
     row: usize,
     gate: RandomAccessGate<F, D>,
     copy: usize,
@@ -501,6 +515,19 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
             set_local_wire(self.gate.wire_bit(i, copy), bit);
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        self.gate.serialize(dst)?;
+        dst.write_usize(self.copy)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let gate = RandomAccessGate::deserialize(src)?;
+        let copy = src.read_usize()?;
+        Ok(Self { row, gate, copy })
+    }
 }
 
 #[cfg(test)]
@@ -14,6 +14,7 @@ use crate::iop::target::Target;
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// Computes `sum alpha^i c_i` for a vector `c_i` of `num_coeffs` elements of the base field.
 #[derive(Debug, Clone)]
@@ -60,6 +61,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for ReducingGate<D
         format!("{self:?}")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_coeffs)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_coeffs = src.read_usize()?;
+        Ok(Self::new(num_coeffs))
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template Reducing$NUM_COEFFS() {{
@@ -238,7 +248,7 @@ function r_wires_accs_start(i, num_coeffs) {{
 }
 
 #[derive(Debug)]
-struct ReducingGenerator<const D: usize> {
+pub struct ReducingGenerator<const D: usize> {
     row: usize,
     gate: ReducingGate<D>,
 }
@@ -280,6 +290,17 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for Reduci
         }
         out_buffer.set_extension_target(output, acc);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        <ReducingGate<D> as Gate<F, D>>::serialize(&self.gate, dst)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let gate = <ReducingGate<D> as Gate<F, D>>::deserialize(src)?;
+        Ok(Self { row, gate })
+    }
 }
 
 #[cfg(test)]
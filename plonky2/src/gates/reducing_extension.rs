@@ -14,6 +14,7 @@ use crate::iop::target::Target;
 use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// Computes `sum alpha^i c_i` for a vector `c_i` of `num_coeffs` elements of the extension field.
 #[derive(Debug, Clone)]
@@ -63,6 +64,15 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for ReducingExtens
         format!("{self:?}")
     }
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_coeffs)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_coeffs = src.read_usize()?;
+        Ok(Self::new(num_coeffs))
+    }
+
     fn export_circom_verification_code(&self) -> String {
         let mut template_str = format!(
             "template ReducingExtension$NUM_COEFFS() {{
@@ -210,7 +220,7 @@ function re_wires_accs_start(i, num_coeffs) {{
 
     fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
         vec![Box::new(
-            ReducingGenerator {
+            ReducingExtensionGenerator {
                 row,
                 gate: self.clone(),
             }
@@ -236,12 +246,14 @@ function re_wires_accs_start(i, num_coeffs) {{
 }
 
 #[derive(Debug)]
-struct ReducingGenerator<const D: usize> {
+pub struct ReducingExtensionGenerator<const D: usize> {
     row: usize,
     gate: ReducingExtensionGate<D>,
 }
 
-impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for ReducingGenerator<D> {
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
+    for ReducingExtensionGenerator<D>
+{
     fn dependencies(&self) -> Vec<Target> {
         ReducingExtensionGate::<D>::wires_alpha()
             .chain(ReducingExtensionGate::<D>::wires_old_acc())
@@ -272,6 +284,17 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for Reduci
             acc = computed_acc;
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        <ReducingExtensionGate<D> as Gate<F, D>>::serialize(&self.gate, dst)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let gate = <ReducingExtensionGate<D> as Gate<F, D>>::deserialize(src)?;
+        Ok(Self { row, gate })
+    }
 }
 
 #[cfg(test)]
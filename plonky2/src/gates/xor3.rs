@@ -0,0 +1,287 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::field::packed::PackedField;
+use crate::field::types::Field;
+use crate::gates::gate::Gate;
+use crate::gates::packed_util::PackedEvaluableBase;
+use crate::gates::util::StridedConstraintConsumer;
+use crate::hash::hash_types::RichField;
+use crate::iop::ext_target::ExtensionTarget;
+use crate::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use crate::iop::target::Target;
+use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::plonk::circuit_data::CircuitConfig;
+use crate::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+    EvaluationVarsBasePacked,
+};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
+
+/// A gate which computes the XOR of three bits, i.e. `output = a ^ b ^ c`. The inputs are assumed
+/// to be boolean; this gate does not check it. If the config supports enough routed wires, it can
+/// support several such operations in one gate.
+#[derive(Debug, Clone)]
+pub struct Xor3Gate {
+    /// Number of XOR operations performed by the gate.
+    pub num_ops: usize,
+}
+
+impl Xor3Gate {
+    pub fn new_from_config(config: &CircuitConfig) -> Self {
+        Self {
+            num_ops: Self::num_ops(config),
+        }
+    }
+
+    /// Determine the maximum number of operations that can fit in one gate for the given config.
+    pub(crate) fn num_ops(config: &CircuitConfig) -> usize {
+        let wires_per_op = 4;
+        config.num_routed_wires / wires_per_op
+    }
+
+    pub fn wire_ith_input_a(i: usize) -> usize {
+        4 * i
+    }
+    pub fn wire_ith_input_b(i: usize) -> usize {
+        4 * i + 1
+    }
+    pub fn wire_ith_input_c(i: usize) -> usize {
+        4 * i + 2
+    }
+    pub fn wire_ith_output(i: usize) -> usize {
+        4 * i + 3
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for Xor3Gate {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_ops = src.read_usize()?;
+        Ok(Self { num_ops })
+    }
+
+    fn export_circom_verification_code(&self) -> String {
+        "template Xor3$NUM_OPS() {
+  signal input constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal input public_input_hash[4];
+  signal input constraints[NUM_GATE_CONSTRAINTS()][2];
+  signal output out[NUM_GATE_CONSTRAINTS()][2];
+
+  signal filter[2];
+  $SET_FILTER;
+
+  signal ab[$NUM_OPS][2];
+  for (var i = 0; i < $NUM_OPS; i++) {
+    ab[i] <== GlExtSub()(GlExtAdd()(wires[4 * i], wires[4 * i + 1]), GlExtMul()(GlExt(2, 0)(), GlExtMul()(wires[4 * i], wires[4 * i + 1])));
+    out[i] <== ConstraintPush()(constraints[i], filter, GlExtSub()(wires[4 * i + 3], GlExtSub()(GlExtAdd()(ab[i], wires[4 * i + 2]), GlExtMul()(GlExt(2, 0)(), GlExtMul()(ab[i], wires[4 * i + 2])))));
+  }
+
+  for (var i = $NUM_OPS; i < NUM_GATE_CONSTRAINTS(); i++) {
+    out[i] <== constraints[i];
+  }
+}"
+        .replace("$NUM_OPS", &self.num_ops.to_string())
+    }
+    fn export_solidity_verification_code(&self) -> String {
+        "library Xor3$NUM_OPSLib {
+    using GoldilocksExtLib for uint64[2];
+    function set_filter(GatesUtilsLib.EvaluationVars memory ev) internal pure {
+        $SET_FILTER;
+    }
+    function eval(GatesUtilsLib.EvaluationVars memory ev, uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints) internal pure {
+        uint64[2] memory two = GatesUtilsLib.field_ext_from(2, 0);
+        for (uint32 i = 0; i < $NUM_OPS; i++) {
+            uint64[2] memory ab = ev.wires[4 * i].add(ev.wires[4 * i + 1]).sub(two.mul(ev.wires[4 * i].mul(ev.wires[4 * i + 1])));
+            uint64[2] memory computed = ab.add(ev.wires[4 * i + 2]).sub(two.mul(ab.mul(ev.wires[4 * i + 2])));
+            GatesUtilsLib.push(constraints, ev.filter, i, ev.wires[4 * i + 3].sub(computed));
+        }
+    }
+}"
+        .replace("$NUM_OPS", &self.num_ops.to_string())
+    }
+
+    fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        let two = F::Extension::TWO;
+
+        let mut constraints = Vec::new();
+        for i in 0..self.num_ops {
+            let a = vars.local_wires[Self::wire_ith_input_a(i)];
+            let b = vars.local_wires[Self::wire_ith_input_b(i)];
+            let c = vars.local_wires[Self::wire_ith_input_c(i)];
+            let output = vars.local_wires[Self::wire_ith_output(i)];
+            let ab = a + b - two * a * b;
+            let computed_output = ab + c - two * ab * c;
+
+            constraints.push(output - computed_output);
+        }
+
+        constraints
+    }
+
+    fn eval_unfiltered_base_one(
+        &self,
+        _vars: EvaluationVarsBase<F>,
+        _yield_constr: StridedConstraintConsumer<F>,
+    ) {
+        panic!("use eval_unfiltered_base_packed instead");
+    }
+
+    fn eval_unfiltered_base_batch(&self, vars_base: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        self.eval_unfiltered_base_batch_packed(vars_base)
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        let neg_two = -F::TWO;
+
+        let mut constraints = Vec::new();
+        for i in 0..self.num_ops {
+            let a = vars.local_wires[Self::wire_ith_input_a(i)];
+            let b = vars.local_wires[Self::wire_ith_input_b(i)];
+            let c = vars.local_wires[Self::wire_ith_input_c(i)];
+            let output = vars.local_wires[Self::wire_ith_output(i)];
+            let computed_output = {
+                let a_plus_b = builder.add_extension(a, b);
+                let ab = builder.arithmetic_extension(neg_two, F::ONE, a, b, a_plus_b);
+                let ab_plus_c = builder.add_extension(ab, c);
+                builder.arithmetic_extension(neg_two, F::ONE, ab, c, ab_plus_c)
+            };
+
+            constraints.push(builder.sub_extension(output, computed_output));
+        }
+
+        constraints
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        (0..self.num_ops)
+            .map(|i| {
+                let g: Box<dyn WitnessGenerator<F>> = Box::new(Xor3Generator { row, i }.adapter());
+                g
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_ops * 4
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    fn degree(&self) -> usize {
+        3
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_ops
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D> for Xor3Gate {
+    fn eval_unfiltered_base_packed<P: PackedField<Scalar = F>>(
+        &self,
+        vars: EvaluationVarsBasePacked<P>,
+        mut yield_constr: StridedConstraintConsumer<P>,
+    ) {
+        for i in 0..self.num_ops {
+            let a = vars.local_wires[Self::wire_ith_input_a(i)];
+            let b = vars.local_wires[Self::wire_ith_input_b(i)];
+            let c = vars.local_wires[Self::wire_ith_input_c(i)];
+            let output = vars.local_wires[Self::wire_ith_output(i)];
+            let ab = a + b - a * b * F::TWO;
+            let computed_output = ab + c - ab * c * F::TWO;
+
+            yield_constr.one(output - computed_output);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Xor3Generator {
+    row: usize,
+    i: usize,
+}
+
+impl<F: RichField> SimpleGenerator<F> for Xor3Generator {
+    fn dependencies(&self) -> Vec<Target> {
+        [
+            Xor3Gate::wire_ith_input_a(self.i),
+            Xor3Gate::wire_ith_input_b(self.i),
+            Xor3Gate::wire_ith_input_c(self.i),
+        ]
+        .iter()
+        .map(|&i| Target::wire(self.row, i))
+        .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let get_wire = |wire: usize| -> u64 {
+            witness
+                .get_target(Target::wire(self.row, wire))
+                .to_canonical_u64()
+        };
+
+        let a = get_wire(Xor3Gate::wire_ith_input_a(self.i));
+        let b = get_wire(Xor3Gate::wire_ith_input_b(self.i));
+        let c = get_wire(Xor3Gate::wire_ith_input_c(self.i));
+        debug_assert!(a <= 1 && b <= 1 && c <= 1);
+
+        let output_target = Target::wire(self.row, Xor3Gate::wire_ith_output(self.i));
+        out_buffer.set_target(output_target, F::from_canonical_u64(a ^ b ^ c))
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let i = src.read_usize()?;
+        Ok(Self { row, i })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::field::goldilocks_field::GoldilocksField;
+    use crate::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use crate::gates::xor3::Xor3Gate;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    #[test]
+    fn low_degree() {
+        let gate = Xor3Gate::new_from_config(&CircuitConfig::standard_recursion_config());
+        test_low_degree::<GoldilocksField, _, 4>(gate);
+    }
+
+    #[test]
+    fn eval_fns() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let gate = Xor3Gate::new_from_config(&CircuitConfig::standard_recursion_config());
+        test_eval_fns::<F, C, _, D>(gate)
+    }
+}
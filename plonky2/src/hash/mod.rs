@@ -6,4 +6,5 @@ pub mod merkle_proofs;
 pub mod merkle_tree;
 pub mod path_compression;
 pub mod poseidon;
+pub mod poseidon_bn254;
 pub mod poseidon_goldilocks;
@@ -1,5 +1,7 @@
+use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::any::type_name;
 use core::fmt::Debug;
 use core::marker::PhantomData;
 
@@ -12,6 +14,7 @@ use crate::iop::wire::Wire;
 use crate::iop::witness::{PartialWitness, PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_data::{CommonCircuitData, ProverOnlyCircuitData};
 use crate::plonk::config::GenericConfig;
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// Given a `PartitionWitness` that has only inputs set, populates the rest of the witness using the
 /// given set of generators.
@@ -98,6 +101,12 @@ pub(crate) fn generate_partial_witness<
 
 /// A generator participates in the generation of the witness.
 pub trait WitnessGenerator<F: Field>: 'static + Send + Sync + Debug {
+    /// An identifier for the concrete type of this generator, used to encode it when serializing
+    /// a circuit.
+    fn id(&self) -> String {
+        type_name::<Self>().to_string()
+    }
+
     /// Targets to be "watched" by this generator. Whenever a target in the watch list is populated,
     /// the generator will be queued to run.
     fn watch_list(&self) -> Vec<Target>;
@@ -106,6 +115,12 @@ pub trait WitnessGenerator<F: Field>: 'static + Send + Sync + Debug {
     /// flag is true, the generator will never be run again, otherwise it will be queued for another
     /// run next time a target in its watch list is populated.
     fn run(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) -> bool;
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()>;
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self>
+    where
+        Self: Sized;
 }
 
 /// Values generated by a generator invocation.
@@ -162,6 +177,12 @@ pub trait SimpleGenerator<F: Field>: 'static + Send + Sync + Debug {
 
     fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>);
 
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()>;
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self>
+    where
+        Self: Sized;
+
     fn adapter(self) -> SimpleGeneratorAdapter<F, Self>
     where
         Self: Sized,
@@ -192,11 +213,19 @@ impl<F: Field, SG: SimpleGenerator<F>> WitnessGenerator<F> for SimpleGeneratorAd
             false
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        self.inner.serialize(dst)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        Ok(SG::deserialize(src)?.adapter())
+    }
 }
 
 /// A generator which copies one wire to another.
 #[derive(Debug)]
-pub(crate) struct CopyGenerator {
+pub struct CopyGenerator {
     pub(crate) src: Target,
     pub(crate) dst: Target,
 }
@@ -210,11 +239,25 @@ impl<F: Field> SimpleGenerator<F> for CopyGenerator {
         let value = witness.get_target(self.src);
         out_buffer.set_target(self.dst, value);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target(self.src)?;
+        dst.write_target(self.dst)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let src_target = src.read_target()?;
+        let dst_target = src.read_target()?;
+        Ok(Self {
+            src: src_target,
+            dst: dst_target,
+        })
+    }
 }
 
 /// A generator for including a random value
 #[derive(Debug)]
-pub(crate) struct RandomValueGenerator {
+pub struct RandomValueGenerator {
     pub(crate) target: Target,
 }
 
@@ -227,11 +270,20 @@ impl<F: Field> SimpleGenerator<F> for RandomValueGenerator {
         let random_value = F::rand();
         out_buffer.set_target(self.target, random_value);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target(self.target)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let target = src.read_target()?;
+        Ok(Self { target })
+    }
 }
 
 /// A generator for testing if a value equals zero
 #[derive(Debug)]
-pub(crate) struct NonzeroTestGenerator {
+pub struct NonzeroTestGenerator {
     pub(crate) to_test: Target,
     pub(crate) dummy: Target,
 }
@@ -252,11 +304,22 @@ impl<F: Field> SimpleGenerator<F> for NonzeroTestGenerator {
 
         out_buffer.set_target(self.dummy, dummy_value);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target(self.to_test)?;
+        dst.write_target(self.dummy)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let to_test = src.read_target()?;
+        let dummy = src.read_target()?;
+        Ok(Self { to_test, dummy })
+    }
 }
 
 /// Generator used to fill an extra constant.
 #[derive(Debug, Clone)]
-pub(crate) struct ConstantGenerator<F: Field> {
+pub struct ConstantGenerator<F: Field> {
     pub row: usize,
     pub constant_index: usize,
     pub wire_index: usize,
@@ -269,7 +332,7 @@ impl<F: Field> ConstantGenerator<F> {
     }
 }
 
-impl<F: Field> SimpleGenerator<F> for ConstantGenerator<F> {
+impl<F: RichField> SimpleGenerator<F> for ConstantGenerator<F> {
     fn dependencies(&self) -> Vec<Target> {
         vec![]
     }
@@ -277,4 +340,24 @@ impl<F: Field> SimpleGenerator<F> for ConstantGenerator<F> {
     fn run_once(&self, _witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
         out_buffer.set_target(Target::wire(self.row, self.wire_index), self.constant);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.constant_index)?;
+        dst.write_usize(self.wire_index)?;
+        dst.write_field(self.constant)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let row = src.read_usize()?;
+        let constant_index = src.read_usize()?;
+        let wire_index = src.read_usize()?;
+        let constant = src.read_field()?;
+        Ok(Self {
+            row,
+            constant_index,
+            wire_index,
+            constant,
+        })
+    }
 }
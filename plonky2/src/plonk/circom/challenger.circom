@@ -0,0 +1,50 @@
+// A duplex sponge in overwrite mode, mirroring plonky2's `Challenger`. The shape of the transcript
+// is fixed by the circuit: it is a sequence of segments, each observing `TRANSCRIPT_SEGMENTS()[s][0]`
+// elements then drawing `TRANSCRIPT_SEGMENTS()[s][1]` challenges, so the sponge is unrolled at
+// compile time.
+template Challenger() {
+  signal input observed[NUM_OBSERVED()];
+  signal output challenges[NUM_DRAWN()];
+
+  var segments[NUM_TRANSCRIPT_SEGMENTS()][2] = TRANSCRIPT_SEGMENTS();
+  component perms[NUM_CHALLENGER_PERMUTATIONS()];
+  var input_buffer[8];
+  var input_len = 0;
+  var output_len = 0;
+  var num_perms = 0;
+  var num_observed = 0;
+  var num_drawn = 0;
+  for (var s = 0; s < NUM_TRANSCRIPT_SEGMENTS(); s++) {
+    for (var k = 0; k < segments[s][0] + segments[s][1]; k++) {
+      var observing = k < segments[s][0];
+      if (observing == 1) {
+        // Any buffered outputs are now invalid, since they wouldn't reflect this input.
+        output_len = 0;
+        input_buffer[input_len] = num_observed;
+        input_len++;
+        num_observed++;
+      }
+      if ((observing == 1 && input_len == 8) || (observing == 0 && (input_len > 0 || output_len == 0))) {
+        // Duplexing: the buffered inputs overwrite the rate part of the state.
+        perms[num_perms] = GlPoseidon();
+        for (var j = 0; j < 12; j++) {
+          if (j < input_len) {
+            perms[num_perms].in[j] <== observed[input_buffer[j]];
+          } else if (num_perms == 0) {
+            perms[num_perms].in[j] <== 0;
+          } else {
+            perms[num_perms].in[j] <== perms[num_perms - 1].out[j];
+          }
+        }
+        num_perms++;
+        input_len = 0;
+        output_len = 8;
+      }
+      if (observing == 0) {
+        output_len--;
+        challenges[num_drawn] <== perms[num_perms - 1].out[output_len];
+        num_drawn++;
+      }
+    }
+  }
+}
@@ -0,0 +1,197 @@
+// Checks one reduction step of a FRI query: the committed evaluations over the coset of `x` must
+// contain `old_eval`, and are interpolated at `beta` to get the evaluation at `x^arity`.
+template FriStep(ARITY_BITS, NUM_SIBLINGS, CAP_HEIGHT) {
+  var ARITY = 1 << ARITY_BITS;
+  signal input x;
+  signal input old_eval[2];
+  signal input beta[2];
+  signal input index_bits[ARITY_BITS + NUM_SIBLINGS + CAP_HEIGHT];
+  signal input evals[ARITY][2];
+  signal input siblings[NUM_SIBLINGS][4];
+  signal input cap[1 << CAP_HEIGHT][4];
+  signal output new_x;
+  signal output new_eval[2];
+
+  // Check consistency with our old evaluation from the previous round.
+  component old_eval_select = RandomAccess(ARITY_BITS, 2);
+  old_eval_select.in <== evals;
+  for (var i = 0; i < ARITY_BITS; i++) {
+    old_eval_select.bits[i] <== index_bits[i];
+  }
+  component old_eval_check = GlExtAssertEqual();
+  old_eval_check.a <== old_eval_select.out;
+  old_eval_check.b <== old_eval;
+
+  // The evaluations over the coset form the leaf at the coset index.
+  component merkle = MerkleProof(2 * ARITY, NUM_SIBLINGS, CAP_HEIGHT);
+  for (var i = 0; i < ARITY; i++) {
+    merkle.leaf[2 * i] <== evals[i][0];
+    merkle.leaf[2 * i + 1] <== evals[i][1];
+  }
+  for (var i = 0; i < NUM_SIBLINGS + CAP_HEIGHT; i++) {
+    merkle.index_bits[i] <== index_bits[ARITY_BITS + i];
+  }
+  merkle.siblings <== siblings;
+  merkle.cap <== cap;
+
+  // The coset starts at x * g^(arity - rev(x_index_within_coset)), where g is a primitive
+  // arity-th root of unity.
+  var g = gl_primitive_root_of_unity(ARITY_BITS);
+  var g_inv = gl_inv(g);
+  signal coset_start[ARITY_BITS + 1];
+  coset_start[0] <== x;
+  for (var i = 0; i < ARITY_BITS; i++) {
+    // Bit i of the index within the coset is bit ARITY_BITS - 1 - i of its reversal.
+    var c = gl_exp(g_inv, 1 << (ARITY_BITS - 1 - i));
+    coset_start[i + 1] <== GlReduce(128)(coset_start[i] * (1 + index_bits[i] * (c - 1)));
+  }
+
+  // Interpolate {(coset_start * g^i, evals[rev(i)])} and evaluate it at beta. The barycentric
+  // weight of the point x_i is x_i / (arity * coset_start^arity), and its denominator is
+  // beta - x_i; beta lies outside the coset except with negligible probability.
+  signal points[ARITY];
+  signal terms[ARITY][2];
+  signal sums[ARITY + 1][2];
+  sums[0] <== GlExt(0, 0)();
+  for (var i = 0; i < ARITY; i++) {
+    if (i == 0) {
+      points[i] <== coset_start[ARITY_BITS];
+    } else {
+      points[i] <== GlReduce(128)(points[i - 1] * g);
+    }
+    terms[i] <== GlExtMul()(
+      GlExtScalarMul()(evals[reverse_bits(i, ARITY_BITS)], points[i]),
+      GlExtInv()(GlExtSub()(beta, GlExtFromBase()(points[i])))
+    );
+    sums[i + 1] <== GlExtAdd()(sums[i], terms[i]);
+  }
+
+  // Scale the sum by l(beta) / (arity * coset_start^arity), where l(X) = X^arity - coset_start^arity
+  // vanishes on the coset. Note that coset_start^arity = x^arity.
+  new_x <== GlExpPowerOf2(ARITY_BITS)(coset_start[ARITY_BITS]);
+  signal l_beta[2];
+  l_beta <== GlExtSub()(GlExtExpPowerOf2(ARITY_BITS)(beta), GlExtFromBase()(new_x));
+  signal denominator_inv;
+  denominator_inv <== GlInv()(GlReduce(64 + ARITY_BITS)(ARITY * new_x));
+  new_eval <== GlExtScalarMul()(GlExtMul()(l_beta, sums[ARITY]), denominator_inv);
+}
+
+// Checks a FRI query round at the index drawn from `x_index`.
+template FriQueryRound() {
+  signal input x_index;
+  signal input alpha[2];
+  signal input alpha_pow_num_challenges[2];
+  signal input zeta[2];
+  signal input zeta_next[2];
+  signal input reduced_openings_zeta[2];
+  signal input reduced_openings_zeta_next[2];
+  signal input betas[NUM_FRI_STEPS()][2];
+  signal input initial_caps[4][NUM_CAP_ELEMENTS()][4];
+  signal input commit_phase_caps[NUM_FRI_STEPS()][NUM_CAP_ELEMENTS()][4];
+  signal input initial_evals[4][MAX_INITIAL_LEAF_LEN()];
+  signal input initial_siblings[4][NUM_INITIAL_SIBLINGS()][4];
+  signal input step_evals[NUM_FRI_STEPS()][MAX_FRI_ARITY()][2];
+  signal input step_siblings[NUM_FRI_STEPS()][MAX_FRI_STEP_SIBLINGS()][4];
+  signal input final_poly[NUM_FINAL_POLY_COEFFS()][2];
+
+  // The query index is the challenge modulo the size of the LDE, i.e. its low LDE_BITS() bits.
+  component index_bits = GlToBits();
+  index_bits.in <== x_index;
+
+  component initial_merkle[4];
+  for (var k = 0; k < 4; k++) {
+    initial_merkle[k] = MerkleProof(INITIAL_TREE_LEAF_LEN(k), NUM_INITIAL_SIBLINGS(), CAP_HEIGHT());
+    for (var i = 0; i < INITIAL_TREE_LEAF_LEN(k); i++) {
+      initial_merkle[k].leaf[i] <== initial_evals[k][i];
+    }
+    for (var i = 0; i < LDE_BITS(); i++) {
+      initial_merkle[k].index_bits[i] <== index_bits.bits[i];
+    }
+    initial_merkle[k].siblings <== initial_siblings[k];
+    initial_merkle[k].cap <== initial_caps[k];
+  }
+
+  // `x` is the point of the LDE domain at the query index, with the domain in bit-reversed order.
+  var omega = gl_primitive_root_of_unity(LDE_BITS());
+  signal xs[LDE_BITS() + 1];
+  xs[0] <== MULTIPLICATIVE_GROUP_GENERATOR();
+  for (var i = 0; i < LDE_BITS(); i++) {
+    // Bit i of the index is bit LDE_BITS() - 1 - i of its reversal.
+    var c = gl_exp(omega, 1 << (LDE_BITS() - 1 - i));
+    xs[i + 1] <== GlReduce(128)(xs[i] * (1 + index_bits.bits[i] * (c - 1)));
+  }
+  signal x[2];
+  x <== GlExtFromBase()(xs[LDE_BITS()]);
+
+  // All polynomials are opened at zeta, ignoring the salts of the leaves.
+  var num_polys = 0;
+  for (var k = 0; k < 4; k++) {
+    num_polys += INITIAL_TREE_NUM_POLYS(k);
+  }
+  signal evals[num_polys][2];
+  var n = 0;
+  for (var k = 0; k < 4; k++) {
+    for (var i = 0; i < INITIAL_TREE_NUM_POLYS(k); i++) {
+      evals[n] <== GlExtFromBase()(initial_evals[k][i]);
+      n++;
+    }
+  }
+  // The Z polynomials, which come first in their oracle, are also opened at g * zeta.
+  signal zs_evals[NUM_CHALLENGES()][2];
+  for (var i = 0; i < NUM_CHALLENGES(); i++) {
+    zs_evals[i] <== GlExtFromBase()(initial_evals[2][i]);
+  }
+  signal zero[2];
+  zero <== GlExt(0, 0)();
+  signal sum_zeta[2];
+  sum_zeta <== GlExtMul()(
+    GlExtSub()(Reduce(num_polys)(alpha, zero, evals), reduced_openings_zeta),
+    GlExtInv()(GlExtSub()(x, zeta))
+  );
+  signal sum_zeta_next[2];
+  sum_zeta_next <== GlExtMul()(
+    GlExtSub()(Reduce(NUM_CHALLENGES())(alpha, zero, zs_evals), reduced_openings_zeta_next),
+    GlExtInv()(GlExtSub()(x, zeta_next))
+  );
+
+  // `old_evals[s]` is the evaluation derived before step s, to be checked for consistency with its
+  // committed "parent" value. The combined openings are multiplied by `X`, so that the final
+  // polynomial has the maximum degree for which the LDT will pass.
+  signal old_evals[NUM_FRI_STEPS() + 1][2];
+  old_evals[0] <== GlExtScalarMul()(
+    GlExtAdd()(GlExtMul()(alpha_pow_num_challenges, sum_zeta), sum_zeta_next),
+    xs[LDE_BITS()]
+  );
+  signal step_xs[NUM_FRI_STEPS() + 1];
+  step_xs[0] <== xs[LDE_BITS()];
+
+  component steps[NUM_FRI_STEPS()];
+  var bit_offset = 0;
+  for (var s = 0; s < NUM_FRI_STEPS(); s++) {
+    var arity_bits = FRI_STEP_ARITY_BITS(s);
+    var num_siblings = FRI_STEP_NUM_SIBLINGS(s);
+    steps[s] = FriStep(arity_bits, num_siblings, CAP_HEIGHT());
+    steps[s].x <== step_xs[s];
+    steps[s].old_eval <== old_evals[s];
+    steps[s].beta <== betas[s];
+    for (var i = 0; i < arity_bits + num_siblings + CAP_HEIGHT(); i++) {
+      steps[s].index_bits[i] <== index_bits.bits[bit_offset + i];
+    }
+    for (var i = 0; i < (1 << arity_bits); i++) {
+      steps[s].evals[i] <== step_evals[s][i];
+    }
+    for (var i = 0; i < num_siblings; i++) {
+      steps[s].siblings[i] <== step_siblings[s][i];
+    }
+    steps[s].cap <== commit_phase_caps[s];
+    step_xs[s + 1] <== steps[s].new_x;
+    old_evals[s + 1] <== steps[s].new_eval;
+    bit_offset += arity_bits;
+  }
+
+  // Final check of FRI. After all the reductions, we check that the final polynomial is equal to
+  // the one sent by the prover.
+  component final_check = GlExtAssertEqual();
+  final_check.a <== Reduce(NUM_FINAL_POLY_COEFFS())(GlExtFromBase()(step_xs[NUM_FRI_STEPS()]), zero, final_poly);
+  final_check.b <== old_evals[NUM_FRI_STEPS()];
+}
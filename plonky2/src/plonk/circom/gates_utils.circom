@@ -0,0 +1,39 @@
+// Adds a filtered gate constraint to its running sum.
+template ConstraintPush() {
+  signal input constraint[2];
+  signal input filter[2];
+  signal input value[2];
+  signal output out[2];
+
+  out <== GlExtAdd()(constraint, GlExtMul()(filter, value));
+}
+
+// Multiplies the elements of the extension algebra held by the wires starting at `l` and `r`.
+template WiresAlgebraMul(l, r) {
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal output out[2][2];
+
+  signal prods[4][2];
+  prods[0] <== GlExtMul()(wires[l], wires[r]);
+  prods[1] <== GlExtMul()(wires[l + 1], wires[r + 1]);
+  prods[2] <== GlExtMul()(wires[l], wires[r + 1]);
+  prods[3] <== GlExtMul()(wires[l + 1], wires[r]);
+  out[0] <== GlExtAdd()(prods[0], GlExtScalarMul()(prods[1], GL_W()));
+  out[1] <== GlExtAdd()(prods[2], prods[3]);
+}
+
+// Continues the reduction `old_eval` with `in`, i.e. computes
+// `old_eval * alpha^N + sum_i in[i] * alpha^i`, as `ReducingFactor` does.
+template Reduce(N) {
+  signal input alpha[2];
+  signal input old_eval[2];
+  signal input in[N][2];
+  signal output out[2];
+
+  signal acc[N + 1][2];
+  acc[0] <== old_eval;
+  for (var i = 0; i < N; i++) {
+    acc[i + 1] <== GlExtAdd()(GlExtMul()(acc[i], alpha), in[N - 1 - i]);
+  }
+  out <== acc[N];
+}
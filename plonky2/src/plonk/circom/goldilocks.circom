@@ -0,0 +1,320 @@
+// Goldilocks arithmetic over the BN254 scalar field.
+//
+// An element is held by a signal whose value is an integer below 2^64, but not necessarily its
+// canonical representative. Each operation reduces its result modulo p, range checking the
+// quotient and the remainder so that nothing wraps around the BN254 modulus. Representatives are
+// only made canonical where the integer itself matters, i.e. for bit decompositions. Extension
+// elements are arrays of their coordinates in the basis (1, X), where X^2 = W.
+
+function GL_P() {
+  return $FIELD_ORDER;
+}
+
+function GL_W() {
+  return $EXTENSION_W;
+}
+
+function gl_mul(a, b) {
+  return ((a % GL_P()) * (b % GL_P())) % GL_P();
+}
+
+function gl_exp(x, e) {
+  var res = 1;
+  var base = x % GL_P();
+  var n = e;
+  while (n > 0) {
+    if ((n & 1) == 1) {
+      res = gl_mul(res, base);
+    }
+    base = gl_mul(base, base);
+    n = n >> 1;
+  }
+  return res;
+}
+
+function gl_inv(x) {
+  return gl_exp(x, GL_P() - 2);
+}
+
+// (a0 + a1 X)^-1 = (a0 - a1 X) / (a0^2 - W a1^2)
+function gl_ext_inv(a0, a1) {
+  var norm = (gl_mul(a0, a0) + GL_P() - gl_mul(GL_W(), gl_mul(a1, a1))) % GL_P();
+  var norm_inv = gl_inv(norm);
+  var res[2];
+  res[0] = gl_mul(a0, norm_inv);
+  res[1] = gl_mul(GL_P() - a1 % GL_P(), norm_inv);
+  return res;
+}
+
+function gl_primitive_root_of_unity(n_log) {
+  return gl_exp($POWER_OF_TWO_GENERATOR, 1 << ($TWO_ADICITY - n_log));
+}
+
+function reverse_bits(x, n_bits) {
+  var res = 0;
+  for (var i = 0; i < n_bits; i++) {
+    res = 2 * res + ((x >> i) & 1);
+  }
+  return res;
+}
+
+template LeBits(N) {
+  signal input in;
+  signal output bits[N];
+
+  var acc = 0;
+  var e = 1;
+  for (var i = 0; i < N; i++) {
+    bits[i] <-- (in >> i) & 1;
+    bits[i] * (bits[i] - 1) === 0;
+    acc += bits[i] * e;
+    e = e + e;
+  }
+  acc === in;
+}
+
+template RangeCheck(N) {
+  signal input in;
+
+  component bits = LeBits(N);
+  bits.in <== in;
+}
+
+// Reduces an integer below 2^N modulo p.
+template GlReduce(N) {
+  signal input in;
+  signal output out;
+
+  signal q;
+  q <-- in \ GL_P();
+  out <-- in % GL_P();
+  in === q * GL_P() + out;
+  component q_range = RangeCheck(N - 63);
+  q_range.in <== q;
+  component out_range = RangeCheck(64);
+  out_range.in <== out;
+}
+
+// The little-endian bits of the canonical representative of an element.
+template GlToBits() {
+  signal input in;
+  signal output bits[64];
+
+  // The input is below 2^64 < 2p, so it is either canonical or its canonical value plus p.
+  signal q;
+  q <-- in >= GL_P() ? 1 : 0;
+  q * (q - 1) === 0;
+  component canonical = LeBits(64);
+  canonical.in <== in - q * GL_P();
+
+  // The canonical value is below p: if its high 32 bits are all set, its low 32 bits are zero.
+  var lo = 0;
+  var e = 1;
+  for (var i = 0; i < 32; i++) {
+    lo += canonical.bits[i] * e;
+    e = e + e;
+  }
+  var hi_ones = 0;
+  for (var i = 32; i < 64; i++) {
+    hi_ones += canonical.bits[i];
+  }
+  signal hi_zeros;
+  hi_zeros <== 32 - hi_ones;
+  signal hi_zeros_inv;
+  hi_zeros_inv <-- hi_zeros != 0 ? 1 / hi_zeros : 0;
+  signal hi_is_max;
+  hi_is_max <== 1 - hi_zeros * hi_zeros_inv;
+  hi_zeros * hi_is_max === 0;
+  hi_is_max * lo === 0;
+
+  bits <== canonical.bits;
+}
+
+template GlAssertEqual() {
+  signal input a;
+  signal input b;
+
+  // Both values are below 2^64 < 2p, so they represent the same element iff they differ by 0 or p.
+  signal t;
+  t <== (a - b) * (a - b - GL_P());
+  t * (a - b + GL_P()) === 0;
+}
+
+template GlInv() {
+  signal input in;
+  signal output out;
+
+  out <-- gl_inv(in);
+  component out_range = RangeCheck(64);
+  out_range.in <== out;
+  component check = GlAssertEqual();
+  check.a <== GlReduce(128)(in * out);
+  check.b <== 1;
+}
+
+template GlExpPowerOf2(N) {
+  signal input in;
+  signal output out;
+
+  signal pows[N + 1];
+  pows[0] <== in;
+  for (var i = 0; i < N; i++) {
+    pows[i + 1] <== GlReduce(128)(pows[i] * pows[i]);
+  }
+  out <== pows[N];
+}
+
+template GlExt(a0, a1) {
+  signal output out[2];
+
+  out[0] <== a0;
+  out[1] <== a1;
+}
+
+template GlExtFromBase() {
+  signal input in;
+  signal output out[2];
+
+  out[0] <== in;
+  out[1] <== 0;
+}
+
+template GlExtAdd() {
+  signal input a[2];
+  signal input b[2];
+  signal output out[2];
+
+  out[0] <== GlReduce(65)(a[0] + b[0]);
+  out[1] <== GlReduce(65)(a[1] + b[1]);
+}
+
+template GlExtSub() {
+  signal input a[2];
+  signal input b[2];
+  signal output out[2];
+
+  out[0] <== GlReduce(66)(a[0] + 2 * GL_P() - b[0]);
+  out[1] <== GlReduce(66)(a[1] + 2 * GL_P() - b[1]);
+}
+
+template GlExtMul() {
+  signal input a[2];
+  signal input b[2];
+  signal output out[2];
+
+  signal a1b1;
+  a1b1 <== a[1] * b[1];
+  signal a0b1;
+  a0b1 <== a[0] * b[1];
+  out[0] <== GlReduce(131)(a[0] * b[0] + GL_W() * a1b1);
+  out[1] <== GlReduce(129)(a[1] * b[0] + a0b1);
+}
+
+template GlExtSquare() {
+  signal input in[2];
+  signal output out[2];
+
+  out <== GlExtMul()(in, in);
+}
+
+template GlExtScalarMul() {
+  signal input a[2];
+  signal input b;
+  signal output out[2];
+
+  out[0] <== GlReduce(128)(a[0] * b);
+  out[1] <== GlReduce(128)(a[1] * b);
+}
+
+// Computes x^n, for an exponent n below 2^N.
+template GlExtExpN(N) {
+  signal input x[2];
+  signal input n;
+  signal output out[2];
+
+  component n_bits = LeBits(N);
+  n_bits.in <== n;
+  signal pows[N][2];
+  signal prods[N][2];
+  signal acc[N + 1][2];
+  pows[0] <== x;
+  acc[0][0] <== 1;
+  acc[0][1] <== 0;
+  for (var i = 0; i < N; i++) {
+    if (i > 0) {
+      pows[i] <== GlExtSquare()(pows[i - 1]);
+    }
+    prods[i] <== GlExtMul()(acc[i], pows[i]);
+    for (var j = 0; j < 2; j++) {
+      acc[i + 1][j] <== acc[i][j] + n_bits.bits[i] * (prods[i][j] - acc[i][j]);
+    }
+  }
+  out <== acc[N];
+}
+
+template GlExtExpPowerOf2(N) {
+  signal input in[2];
+  signal output out[2];
+
+  signal pows[N + 1][2];
+  pows[0] <== in;
+  for (var i = 0; i < N; i++) {
+    pows[i + 1] <== GlExtSquare()(pows[i]);
+  }
+  out <== pows[N];
+}
+
+template GlExtInv() {
+  signal input in[2];
+  signal output out[2];
+
+  var inv[2] = gl_ext_inv(in[0], in[1]);
+  out[0] <-- inv[0];
+  out[1] <-- inv[1];
+  component out_range[2];
+  for (var i = 0; i < 2; i++) {
+    out_range[i] = RangeCheck(64);
+    out_range[i].in <== out[i];
+  }
+  component check = GlExtAssertEqual();
+  check.a <== GlExtMul()(in, out);
+  check.b <== GlExt(1, 0)();
+}
+
+template GlExtAssertEqual() {
+  signal input a[2];
+  signal input b[2];
+
+  component checks[2];
+  for (var i = 0; i < 2; i++) {
+    checks[i] = GlAssertEqual();
+    checks[i].a <== a[i];
+    checks[i].b <== b[i];
+  }
+}
+
+// Selects the element of `in` at the index whose little-endian bits are `bits`.
+template RandomAccess(N_BITS, WIDTH) {
+  var SIZE = 1 << N_BITS;
+  signal input in[SIZE][WIDTH];
+  signal input bits[N_BITS];
+  signal output out[WIDTH];
+
+  // The nodes of a binary tree, whose leaves are the inputs and whose root is the output.
+  signal nodes[2 * SIZE - 1][WIDTH];
+  for (var i = 0; i < SIZE; i++) {
+    nodes[i] <== in[i];
+  }
+  var start = 0;
+  var len = SIZE;
+  for (var l = 0; l < N_BITS; l++) {
+    for (var i = 0; i < len \ 2; i++) {
+      for (var j = 0; j < WIDTH; j++) {
+        nodes[start + len + i][j] <== nodes[start + 2 * i][j] + bits[l] * (nodes[start + 2 * i + 1][j] - nodes[start + 2 * i][j]);
+      }
+    }
+    start += len;
+    len = len \ 2;
+  }
+  out <== nodes[2 * SIZE - 2];
+}
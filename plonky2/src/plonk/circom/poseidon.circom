@@ -0,0 +1,170 @@
+function GL_ROUND_CONSTANTS() {
+  var rc[$NUM_ROUND_CONSTANTS];
+  $SET_ALL_ROUND_CONSTANTS
+  return rc;
+}
+
+function GL_CONST(i) {
+  var rc[$NUM_ROUND_CONSTANTS] = GL_ROUND_CONSTANTS();
+  return rc[i];
+}
+
+function GL_MDS_CIRC(i) {
+  var mds[12];
+  $SET_MDS_MATRIX_CIRC
+  return mds[i];
+}
+
+function GL_MDS_DIAG(i) {
+  var mds[12];
+  $SET_MDS_MATRIX_DIAG
+  return mds[i];
+}
+
+// x^7, for an input below 2^65.
+template GlSbox() {
+  signal input in;
+  signal output out;
+
+  signal x2;
+  x2 <== GlReduce(130)(in * in);
+  signal x3;
+  x3 <== GlReduce(129)(x2 * in);
+  signal x4;
+  x4 <== GlReduce(128)(x2 * x2);
+  out <== GlReduce(128)(x3 * x4);
+}
+
+template GlPoseidon() {
+  signal input in[12];
+  signal output out[12];
+
+  var rc[$NUM_ROUND_CONSTANTS] = GL_ROUND_CONSTANTS();
+  signal state[$N_ROUNDS + 1][12];
+  signal sbox[$N_ROUNDS][12];
+  state[0] <== in;
+  for (var r = 0; r < $N_ROUNDS; r++) {
+    var full_round = r < $HALF_N_FULL_ROUNDS || r >= $HALF_N_FULL_ROUNDS + $N_PARTIAL_ROUNDS;
+    for (var i = 0; i < 12; i++) {
+      if (full_round == 1 || i == 0) {
+        sbox[r][i] <== GlSbox()(state[r][i] + rc[12 * r + i]);
+      } else {
+        sbox[r][i] <== state[r][i] + rc[12 * r + i];
+      }
+    }
+    for (var i = 0; i < 12; i++) {
+      var acc = sbox[r][i] * GL_MDS_DIAG(i);
+      for (var j = 0; j < 12; j++) {
+        acc += sbox[r][(i + j) % 12] * GL_MDS_CIRC(j);
+      }
+      // The MDS coefficients are small, so the sum stays well below 2^80.
+      state[r + 1][i] <== GlReduce(80)(acc);
+    }
+  }
+  out <== state[$N_ROUNDS];
+}
+
+template GlHashNoPad(N) {
+  signal input in[N];
+  signal output out[4];
+
+  var num_perms = (N + 7) \ 8;
+  component perms[num_perms];
+  for (var k = 0; k < num_perms; k++) {
+    perms[k] = GlPoseidon();
+    // The inputs overwrite the rate part of the state.
+    for (var j = 0; j < 12; j++) {
+      if (j < 8 && 8 * k + j < N) {
+        perms[k].in[j] <== in[8 * k + j];
+      } else if (k == 0) {
+        perms[k].in[j] <== 0;
+      } else {
+        perms[k].in[j] <== perms[k - 1].out[j];
+      }
+    }
+  }
+  for (var i = 0; i < 4; i++) {
+    out[i] <== perms[num_perms - 1].out[i];
+  }
+}
+
+template GlHashOrNoop(N) {
+  signal input in[N];
+  signal output out[4];
+
+  if (N <= 4) {
+    for (var i = 0; i < 4; i++) {
+      if (i < N) {
+        out[i] <== in[i];
+      } else {
+        out[i] <== 0;
+      }
+    }
+  } else {
+    out <== GlHashNoPad(N)(in);
+  }
+}
+
+template GlTwoToOne() {
+  signal input left[4];
+  signal input right[4];
+  signal output out[4];
+
+  component perm = GlPoseidon();
+  for (var i = 0; i < 4; i++) {
+    perm.in[i] <== left[i];
+    perm.in[i + 4] <== right[i];
+    perm.in[i + 8] <== 0;
+  }
+  for (var i = 0; i < 4; i++) {
+    out[i] <== perm.out[i];
+  }
+}
+
+// Checks a Merkle proof of `leaf`. The low `NUM_SIBLINGS` bits of the leaf index select the path,
+// and the next `CAP_HEIGHT` bits the element of the cap it leads to. The leaf and the siblings,
+// which come from the proof, are range checked.
+template MerkleProof(LEAF_LEN, NUM_SIBLINGS, CAP_HEIGHT) {
+  signal input leaf[LEAF_LEN];
+  signal input index_bits[NUM_SIBLINGS + CAP_HEIGHT];
+  signal input siblings[NUM_SIBLINGS][4];
+  signal input cap[1 << CAP_HEIGHT][4];
+
+  component leaf_ranges[LEAF_LEN];
+  for (var i = 0; i < LEAF_LEN; i++) {
+    leaf_ranges[i] = RangeCheck(64);
+    leaf_ranges[i].in <== leaf[i];
+  }
+  component sibling_ranges[NUM_SIBLINGS][4];
+  for (var i = 0; i < NUM_SIBLINGS; i++) {
+    for (var j = 0; j < 4; j++) {
+      sibling_ranges[i][j] = RangeCheck(64);
+      sibling_ranges[i][j].in <== siblings[i][j];
+    }
+  }
+
+  signal digests[NUM_SIBLINGS + 1][4];
+  signal left[NUM_SIBLINGS][4];
+  signal right[NUM_SIBLINGS][4];
+  digests[0] <== GlHashOrNoop(LEAF_LEN)(leaf);
+  for (var i = 0; i < NUM_SIBLINGS; i++) {
+    // The current digest is the right child if the bit is set.
+    for (var j = 0; j < 4; j++) {
+      left[i][j] <== digests[i][j] + index_bits[i] * (siblings[i][j] - digests[i][j]);
+      right[i][j] <== digests[i][j] + siblings[i][j] - left[i][j];
+    }
+    digests[i + 1] <== GlTwoToOne()(left[i], right[i]);
+  }
+
+  component cap_select = RandomAccess(CAP_HEIGHT, 4);
+  cap_select.in <== cap;
+  for (var i = 0; i < CAP_HEIGHT; i++) {
+    cap_select.bits[i] <== index_bits[NUM_SIBLINGS + i];
+  }
+  component checks[4];
+  for (var i = 0; i < 4; i++) {
+    checks[i] = GlAssertEqual();
+    checks[i].a <== digests[NUM_SIBLINGS][i];
+    checks[i].b <== cap_select.out[i];
+  }
+}
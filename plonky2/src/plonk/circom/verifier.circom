@@ -0,0 +1,564 @@
+function MULTIPLICATIVE_GROUP_GENERATOR() {
+  return $MULTIPLICATIVE_GROUP_GENERATOR;
+}
+
+function NUM_PUBLIC_INPUTS() {
+  return $NUM_PUBLIC_INPUTS;
+}
+
+function NUM_CHALLENGES() {
+  return $NUM_CHALLENGES;
+}
+
+function NUM_ROUTED_WIRES() {
+  return $NUM_ROUTED_WIRES;
+}
+
+function NUM_OPENINGS_CONSTANTS() {
+  return $NUM_OPENINGS_CONSTANTS;
+}
+
+function NUM_OPENINGS_WIRES() {
+  return $NUM_OPENINGS_WIRES;
+}
+
+function NUM_PARTIAL_PRODUCTS() {
+  return $NUM_PARTIAL_PRODUCTS;
+}
+
+function QUOTIENT_DEGREE_FACTOR() {
+  return $QUOTIENT_DEGREE_FACTOR;
+}
+
+function NUM_QUOTIENT_POLYS() {
+  return $NUM_QUOTIENT_POLYS;
+}
+
+function NUM_GATE_CONSTRAINTS() {
+  return $NUM_GATE_CONSTRAINTS;
+}
+
+function DEGREE_BITS() {
+  return $DEGREE_BITS;
+}
+
+function ZETA_NEXT_GENERATOR() {
+  var g[2];
+  g[0] = $ZETA_NEXT_GENERATOR_0;
+  g[1] = $ZETA_NEXT_GENERATOR_1;
+  return g;
+}
+
+function K_IS(i) {
+  var k_is[$NUM_ROUTED_WIRES];
+  $SET_K_IS
+  return k_is[i];
+}
+
+function CIRCUIT_DIGEST() {
+  var digest[4];
+  $SET_CIRCUIT_DIGEST
+  return digest;
+}
+
+function CONSTANTS_SIGMAS_CAP() {
+  var cap[$NUM_CAP_ELEMENTS][4];
+  $SET_CONSTANTS_SIGMAS_CAP
+  return cap;
+}
+
+function CAP_HEIGHT() {
+  return $CAP_HEIGHT;
+}
+
+function NUM_CAP_ELEMENTS() {
+  return $NUM_CAP_ELEMENTS;
+}
+
+function LDE_BITS() {
+  return $LDE_BITS;
+}
+
+function NUM_INITIAL_SIBLINGS() {
+  return $NUM_INITIAL_SIBLINGS;
+}
+
+// The number of polynomials committed in each initial tree.
+function INITIAL_TREE_NUM_POLYS(i) {
+  var num_polys[4];
+  $SET_INITIAL_TREE_NUM_POLYS
+  return num_polys[i];
+}
+
+// The length of the leaves of each initial tree, including salts.
+function INITIAL_TREE_LEAF_LEN(i) {
+  var leaf_lens[4];
+  $SET_INITIAL_TREE_LEAF_LENS
+  return leaf_lens[i];
+}
+
+function MAX_INITIAL_LEAF_LEN() {
+  return $MAX_INITIAL_LEAF_LEN;
+}
+
+function NUM_FRI_QUERIES() {
+  return $NUM_FRI_QUERIES;
+}
+
+function NUM_FRI_STEPS() {
+  return $NUM_FRI_STEPS;
+}
+
+function FRI_STEP_ARITY_BITS(i) {
+  var arity_bits[$NUM_FRI_STEPS];
+  $SET_FRI_STEP_ARITY_BITS
+  return arity_bits[i];
+}
+
+function FRI_STEP_NUM_SIBLINGS(i) {
+  var num_siblings[$NUM_FRI_STEPS];
+  $SET_FRI_STEP_NUM_SIBLINGS
+  return num_siblings[i];
+}
+
+function MAX_FRI_ARITY() {
+  return $MAX_FRI_ARITY;
+}
+
+function MAX_FRI_STEP_SIBLINGS() {
+  return $MAX_FRI_STEP_SIBLINGS;
+}
+
+function NUM_FINAL_POLY_COEFFS() {
+  return $NUM_FINAL_POLY_COEFFS;
+}
+
+function POW_BITS() {
+  return $POW_BITS;
+}
+
+function NUM_OBSERVED() {
+  return $NUM_OBSERVED;
+}
+
+function NUM_DRAWN() {
+  return $NUM_DRAWN;
+}
+
+function NUM_CHALLENGER_PERMUTATIONS() {
+  return $NUM_CHALLENGER_PERMUTATIONS;
+}
+
+function NUM_TRANSCRIPT_SEGMENTS() {
+  return $NUM_TRANSCRIPT_SEGMENTS;
+}
+
+function TRANSCRIPT_SEGMENTS() {
+  var segments[$NUM_TRANSCRIPT_SEGMENTS][2];
+  $SET_TRANSCRIPT_SEGMENTS
+  return segments;
+}
+
+// Evaluates the gate constraints at zeta. Each gate adds its constraints, multiplied by its
+// filter, to the sums computed by the previous gates.
+template EvalGateConstraints() {
+  signal input constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal input public_input_hash[4];
+  signal output out[NUM_GATE_CONSTRAINTS()][2];
+
+  signal zero[NUM_GATE_CONSTRAINTS()][2];
+  for (var i = 0; i < NUM_GATE_CONSTRAINTS(); i++) {
+    zero[i] <== GlExt(0, 0)();
+  }
+
+  $EVALUATE_GATE_CONSTRAINTS
+}
+
+// Checks each polynomial identity, of the form `vanishing(x) = Z_H(x) quotient(x)`, at zeta.
+template VanishingCheck() {
+  signal input constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input plonk_sigmas[NUM_ROUTED_WIRES()][2];
+  signal input wires[NUM_OPENINGS_WIRES()][2];
+  signal input plonk_zs[NUM_CHALLENGES()][2];
+  signal input partial_products[NUM_CHALLENGES() * NUM_PARTIAL_PRODUCTS()][2];
+  signal input quotient_polys[NUM_QUOTIENT_POLYS()][2];
+  signal input plonk_zs_next[NUM_CHALLENGES()][2];
+  signal input public_inputs_hash[4];
+  signal input betas[NUM_CHALLENGES()];
+  signal input gammas[NUM_CHALLENGES()];
+  signal input alphas[NUM_CHALLENGES()];
+  signal input zeta[2];
+
+  var NUM_TERMS = NUM_CHALLENGES() * (NUM_PARTIAL_PRODUCTS() + 2) + NUM_GATE_CONSTRAINTS();
+  signal terms[NUM_TERMS][2];
+  signal one[2];
+  one <== GlExt(1, 0)();
+  signal zero[2];
+  zero <== GlExt(0, 0)();
+
+  // L_0(zeta) = (zeta^n - 1) / (n * (zeta - 1)); zeta is not 1 except with negligible probability.
+  signal zeta_pow_deg[2];
+  zeta_pow_deg <== GlExtExpPowerOf2(DEGREE_BITS())(zeta);
+  signal z_h_zeta[2];
+  z_h_zeta <== GlExtSub()(zeta_pow_deg, one);
+  signal l_0[2];
+  l_0 <== GlExtMul()(z_h_zeta, GlExtInv()(GlExtScalarMul()(GlExtSub()(zeta, one), 1 << DEGREE_BITS())));
+
+  // The numerators and denominators of the permutation argument. Their products are accumulated
+  // over chunks of QUOTIENT_DEGREE_FACTOR() routed wires, each checked against a partial product.
+  signal wires_gammas[NUM_CHALLENGES()][NUM_ROUTED_WIRES()][2];
+  signal numerators[NUM_CHALLENGES()][NUM_ROUTED_WIRES()][2];
+  signal denominators[NUM_CHALLENGES()][NUM_ROUTED_WIRES()][2];
+  signal numerator_accs[NUM_CHALLENGES()][NUM_ROUTED_WIRES()][2];
+  signal denominator_accs[NUM_CHALLENGES()][NUM_ROUTED_WIRES()][2];
+  for (var i = 0; i < NUM_CHALLENGES(); i++) {
+    terms[i] <== GlExtMul()(l_0, GlExtSub()(plonk_zs[i], one));
+
+    for (var j = 0; j < NUM_ROUTED_WIRES(); j++) {
+      wires_gammas[i][j] <== GlExtAdd()(wires[j], GlExtFromBase()(gammas[i]));
+      numerators[i][j] <== GlExtAdd()(wires_gammas[i][j], GlExtScalarMul()(zeta, GlReduce(128)(betas[i] * K_IS(j))));
+      denominators[i][j] <== GlExtAdd()(wires_gammas[i][j], GlExtScalarMul()(plonk_sigmas[j], betas[i]));
+      if (j % QUOTIENT_DEGREE_FACTOR() == 0) {
+        numerator_accs[i][j] <== numerators[i][j];
+        denominator_accs[i][j] <== denominators[i][j];
+      } else {
+        numerator_accs[i][j] <== GlExtMul()(numerator_accs[i][j - 1], numerators[i][j]);
+        denominator_accs[i][j] <== GlExtMul()(denominator_accs[i][j - 1], denominators[i][j]);
+      }
+    }
+
+    // Check the relationship between the accumulators before and after each chunk:
+    // `prev * numerator - next * denominator`.
+    for (var k = 0; k <= NUM_PARTIAL_PRODUCTS(); k++) {
+      var end = (k + 1) * QUOTIENT_DEGREE_FACTOR();
+      if (end > NUM_ROUTED_WIRES()) {
+        end = NUM_ROUTED_WIRES();
+      }
+      var index = NUM_CHALLENGES() + i * (NUM_PARTIAL_PRODUCTS() + 1) + k;
+      if (k == 0 && k == NUM_PARTIAL_PRODUCTS()) {
+        terms[index] <== GlExtSub()(
+          GlExtMul()(plonk_zs[i], numerator_accs[i][end - 1]),
+          GlExtMul()(plonk_zs_next[i], denominator_accs[i][end - 1])
+        );
+      } else if (k == 0) {
+        terms[index] <== GlExtSub()(
+          GlExtMul()(plonk_zs[i], numerator_accs[i][end - 1]),
+          GlExtMul()(partial_products[i * NUM_PARTIAL_PRODUCTS()], denominator_accs[i][end - 1])
+        );
+      } else if (k == NUM_PARTIAL_PRODUCTS()) {
+        terms[index] <== GlExtSub()(
+          GlExtMul()(partial_products[i * NUM_PARTIAL_PRODUCTS() + k - 1], numerator_accs[i][end - 1]),
+          GlExtMul()(plonk_zs_next[i], denominator_accs[i][end - 1])
+        );
+      } else {
+        terms[index] <== GlExtSub()(
+          GlExtMul()(partial_products[i * NUM_PARTIAL_PRODUCTS() + k - 1], numerator_accs[i][end - 1]),
+          GlExtMul()(partial_products[i * NUM_PARTIAL_PRODUCTS() + k], denominator_accs[i][end - 1])
+        );
+      }
+    }
+  }
+
+  signal constraints[NUM_GATE_CONSTRAINTS()][2];
+  constraints <== EvalGateConstraints()(constants, wires, public_inputs_hash);
+  for (var i = 0; i < NUM_GATE_CONSTRAINTS(); i++) {
+    terms[NUM_CHALLENGES() * (NUM_PARTIAL_PRODUCTS() + 2) + i] <== constraints[i];
+  }
+
+  // The quotient is split into chunks of degree n, which are combined with powers of zeta^n.
+  signal quotient_chunks[NUM_CHALLENGES()][QUOTIENT_DEGREE_FACTOR()][2];
+  component checks[NUM_CHALLENGES()];
+  for (var i = 0; i < NUM_CHALLENGES(); i++) {
+    for (var j = 0; j < QUOTIENT_DEGREE_FACTOR(); j++) {
+      quotient_chunks[i][j] <== quotient_polys[i * QUOTIENT_DEGREE_FACTOR() + j];
+    }
+    checks[i] = GlExtAssertEqual();
+    checks[i].a <== Reduce(NUM_TERMS)(GlExtFromBase()(alphas[i]), zero, terms);
+    checks[i].b <== GlExtMul()(z_h_zeta, Reduce(QUOTIENT_DEGREE_FACTOR())(zeta_pow_deg, zero, quotient_chunks[i]));
+  }
+}
+
+// Verifies a proof of the circuit. The public inputs of the proof are the public inputs of the
+// Circom circuit, and the rest of the proof is a private input, in the format produced by
+// `encode_circom_witness`.
+template Plonky2Verifier() {
+  signal input public_inputs[NUM_PUBLIC_INPUTS()];
+  signal input wires_cap[NUM_CAP_ELEMENTS()][4];
+  signal input plonk_zs_partial_products_cap[NUM_CAP_ELEMENTS()][4];
+  signal input quotient_polys_cap[NUM_CAP_ELEMENTS()][4];
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_ROUTED_WIRES()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_CHALLENGES()][2];
+  signal input openings_partial_products[NUM_CHALLENGES() * NUM_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_QUOTIENT_POLYS()][2];
+  signal input openings_plonk_zs_next[NUM_CHALLENGES()][2];
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_STEPS()][NUM_CAP_ELEMENTS()][4];
+  // The leaves and Merkle proofs of each query round, padded with zeros to a uniform shape.
+  signal input fri_query_initial_evals[NUM_FRI_QUERIES()][4][MAX_INITIAL_LEAF_LEN()];
+  signal input fri_query_initial_siblings[NUM_FRI_QUERIES()][4][NUM_INITIAL_SIBLINGS()][4];
+  signal input fri_query_step_evals[NUM_FRI_QUERIES()][NUM_FRI_STEPS()][MAX_FRI_ARITY()][2];
+  signal input fri_query_step_siblings[NUM_FRI_QUERIES()][NUM_FRI_STEPS()][MAX_FRI_STEP_SIBLINGS()][4];
+  signal input fri_final_poly[NUM_FINAL_POLY_COEFFS()][2];
+  signal input fri_pow_witness;
+
+  // Every element of the proof is range checked, which the Goldilocks templates rely on. The
+  // query rounds are checked along with their Merkle proofs, and their padding is unconstrained.
+  var num_elements = NUM_PUBLIC_INPUTS() + 4 * NUM_CAP_ELEMENTS() * (3 + NUM_FRI_STEPS())
+    + 2 * (NUM_OPENINGS_CONSTANTS() + NUM_ROUTED_WIRES() + NUM_OPENINGS_WIRES()
+      + NUM_CHALLENGES() * (NUM_PARTIAL_PRODUCTS() + 2) + NUM_QUOTIENT_POLYS() + NUM_FINAL_POLY_COEFFS()) + 1;
+  signal elements[num_elements];
+  var e = 0;
+  for (var i = 0; i < NUM_PUBLIC_INPUTS(); i++) {
+    elements[e] <== public_inputs[i];
+    e++;
+  }
+  for (var i = 0; i < NUM_CAP_ELEMENTS(); i++) {
+    for (var j = 0; j < 4; j++) {
+      elements[e] <== wires_cap[i][j];
+      elements[e + 1] <== plonk_zs_partial_products_cap[i][j];
+      elements[e + 2] <== quotient_polys_cap[i][j];
+      e += 3;
+      for (var s = 0; s < NUM_FRI_STEPS(); s++) {
+        elements[e] <== fri_commit_phase_merkle_caps[s][i][j];
+        e++;
+      }
+    }
+  }
+  for (var j = 0; j < 2; j++) {
+    for (var i = 0; i < NUM_OPENINGS_CONSTANTS(); i++) {
+      elements[e] <== openings_constants[i][j];
+      e++;
+    }
+    for (var i = 0; i < NUM_ROUTED_WIRES(); i++) {
+      elements[e] <== openings_plonk_sigmas[i][j];
+      e++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_WIRES(); i++) {
+      elements[e] <== openings_wires[i][j];
+      e++;
+    }
+    for (var i = 0; i < NUM_CHALLENGES(); i++) {
+      elements[e] <== openings_plonk_zs[i][j];
+      elements[e + 1] <== openings_plonk_zs_next[i][j];
+      e += 2;
+    }
+    for (var i = 0; i < NUM_CHALLENGES() * NUM_PARTIAL_PRODUCTS(); i++) {
+      elements[e] <== openings_partial_products[i][j];
+      e++;
+    }
+    for (var i = 0; i < NUM_QUOTIENT_POLYS(); i++) {
+      elements[e] <== openings_quotient_polys[i][j];
+      e++;
+    }
+    for (var i = 0; i < NUM_FINAL_POLY_COEFFS(); i++) {
+      elements[e] <== fri_final_poly[i][j];
+      e++;
+    }
+  }
+  elements[e] <== fri_pow_witness;
+  component element_ranges[num_elements];
+  for (var i = 0; i < num_elements; i++) {
+    element_ranges[i] = RangeCheck(64);
+    element_ranges[i].in <== elements[i];
+  }
+
+  signal public_inputs_hash[4];
+  public_inputs_hash <== GlHashNoPad(NUM_PUBLIC_INPUTS())(public_inputs);
+
+  // The transcript, in the order in which the prover observes it.
+  signal transcript[NUM_OBSERVED()];
+  var t = 0;
+  var circuit_digest[4] = CIRCUIT_DIGEST();
+  for (var i = 0; i < 4; i++) {
+    transcript[t] <== circuit_digest[i];
+    transcript[t + 4] <== public_inputs_hash[i];
+    t++;
+  }
+  t += 4;
+  for (var i = 0; i < NUM_CAP_ELEMENTS(); i++) {
+    for (var j = 0; j < 4; j++) {
+      transcript[t + 4 * i + j] <== wires_cap[i][j];
+      transcript[t + 4 * (NUM_CAP_ELEMENTS() + i) + j] <== plonk_zs_partial_products_cap[i][j];
+      transcript[t + 4 * (2 * NUM_CAP_ELEMENTS() + i) + j] <== quotient_polys_cap[i][j];
+    }
+  }
+  t += 12 * NUM_CAP_ELEMENTS();
+  // The openings at zeta are followed by the openings at g * zeta.
+  for (var j = 0; j < 2; j++) {
+    var pos = t + j;
+    for (var i = 0; i < NUM_OPENINGS_CONSTANTS(); i++) {
+      transcript[pos] <== openings_constants[i][j];
+      pos += 2;
+    }
+    for (var i = 0; i < NUM_ROUTED_WIRES(); i++) {
+      transcript[pos] <== openings_plonk_sigmas[i][j];
+      pos += 2;
+    }
+    for (var i = 0; i < NUM_OPENINGS_WIRES(); i++) {
+      transcript[pos] <== openings_wires[i][j];
+      pos += 2;
+    }
+    for (var i = 0; i < NUM_CHALLENGES(); i++) {
+      transcript[pos] <== openings_plonk_zs[i][j];
+      pos += 2;
+    }
+    for (var i = 0; i < NUM_CHALLENGES() * NUM_PARTIAL_PRODUCTS(); i++) {
+      transcript[pos] <== openings_partial_products[i][j];
+      pos += 2;
+    }
+    for (var i = 0; i < NUM_QUOTIENT_POLYS(); i++) {
+      transcript[pos] <== openings_quotient_polys[i][j];
+      pos += 2;
+    }
+    for (var i = 0; i < NUM_CHALLENGES(); i++) {
+      transcript[pos] <== openings_plonk_zs_next[i][j];
+      pos += 2;
+    }
+  }
+  t += 2 * (NUM_OPENINGS_CONSTANTS() + NUM_ROUTED_WIRES() + NUM_OPENINGS_WIRES()
+    + NUM_CHALLENGES() * (NUM_PARTIAL_PRODUCTS() + 2) + NUM_QUOTIENT_POLYS());
+  for (var s = 0; s < NUM_FRI_STEPS(); s++) {
+    for (var i = 0; i < NUM_CAP_ELEMENTS(); i++) {
+      for (var j = 0; j < 4; j++) {
+        transcript[t] <== fri_commit_phase_merkle_caps[s][i][j];
+        t++;
+      }
+    }
+  }
+  for (var i = 0; i < NUM_FINAL_POLY_COEFFS(); i++) {
+    transcript[t] <== fri_final_poly[i][0];
+    transcript[t + 1] <== fri_final_poly[i][1];
+    t += 2;
+  }
+  transcript[t] <== fri_pow_witness;
+
+  // The challenges, in the order in which they are drawn.
+  signal challenges[NUM_DRAWN()];
+  challenges <== Challenger()(transcript);
+  var c = 0;
+  signal plonk_betas[NUM_CHALLENGES()];
+  signal plonk_gammas[NUM_CHALLENGES()];
+  signal plonk_alphas[NUM_CHALLENGES()];
+  for (var i = 0; i < NUM_CHALLENGES(); i++) {
+    plonk_betas[i] <== challenges[i];
+    plonk_gammas[i] <== challenges[NUM_CHALLENGES() + i];
+    plonk_alphas[i] <== challenges[2 * NUM_CHALLENGES() + i];
+  }
+  c += 3 * NUM_CHALLENGES();
+  signal plonk_zeta[2];
+  plonk_zeta[0] <== challenges[c];
+  plonk_zeta[1] <== challenges[c + 1];
+  signal fri_alpha[2];
+  fri_alpha[0] <== challenges[c + 2];
+  fri_alpha[1] <== challenges[c + 3];
+  c += 4;
+  signal fri_betas[NUM_FRI_STEPS()][2];
+  for (var s = 0; s < NUM_FRI_STEPS(); s++) {
+    fri_betas[s][0] <== challenges[c];
+    fri_betas[s][1] <== challenges[c + 1];
+    c += 2;
+  }
+  signal fri_pow_response;
+  fri_pow_response <== challenges[c];
+  c++;
+
+  component vanishing = VanishingCheck();
+  vanishing.constants <== openings_constants;
+  vanishing.plonk_sigmas <== openings_plonk_sigmas;
+  vanishing.wires <== openings_wires;
+  vanishing.plonk_zs <== openings_plonk_zs;
+  vanishing.partial_products <== openings_partial_products;
+  vanishing.quotient_polys <== openings_quotient_polys;
+  vanishing.plonk_zs_next <== openings_plonk_zs_next;
+  vanishing.public_inputs_hash <== public_inputs_hash;
+  vanishing.betas <== plonk_betas;
+  vanishing.gammas <== plonk_gammas;
+  vanishing.alphas <== plonk_alphas;
+  vanishing.zeta <== plonk_zeta;
+
+  // Check the proof of work: the response must have POW_BITS() leading zeros.
+  component pow_bits = GlToBits();
+  pow_bits.in <== fri_pow_response;
+  for (var i = 0; i < POW_BITS(); i++) {
+    pow_bits.bits[63 - i] === 0;
+  }
+
+  // The openings are combined with powers of alpha, in the order of the FRI batches.
+  var num_openings_zeta = NUM_OPENINGS_CONSTANTS() + NUM_ROUTED_WIRES() + NUM_OPENINGS_WIRES()
+    + NUM_CHALLENGES() * (NUM_PARTIAL_PRODUCTS() + 1) + NUM_QUOTIENT_POLYS();
+  signal openings_zeta[num_openings_zeta][2];
+  var o = 0;
+  for (var i = 0; i < NUM_OPENINGS_CONSTANTS(); i++) {
+    openings_zeta[o] <== openings_constants[i];
+    o++;
+  }
+  for (var i = 0; i < NUM_ROUTED_WIRES(); i++) {
+    openings_zeta[o] <== openings_plonk_sigmas[i];
+    o++;
+  }
+  for (var i = 0; i < NUM_OPENINGS_WIRES(); i++) {
+    openings_zeta[o] <== openings_wires[i];
+    o++;
+  }
+  for (var i = 0; i < NUM_CHALLENGES(); i++) {
+    openings_zeta[o] <== openings_plonk_zs[i];
+    o++;
+  }
+  for (var i = 0; i < NUM_CHALLENGES() * NUM_PARTIAL_PRODUCTS(); i++) {
+    openings_zeta[o] <== openings_partial_products[i];
+    o++;
+  }
+  for (var i = 0; i < NUM_QUOTIENT_POLYS(); i++) {
+    openings_zeta[o] <== openings_quotient_polys[i];
+    o++;
+  }
+  signal zero[2];
+  zero <== GlExt(0, 0)();
+  signal reduced_openings_zeta[2];
+  reduced_openings_zeta <== Reduce(num_openings_zeta)(fri_alpha, zero, openings_zeta);
+  signal reduced_openings_zeta_next[2];
+  reduced_openings_zeta_next <== Reduce(NUM_CHALLENGES())(fri_alpha, zero, openings_plonk_zs_next);
+  signal fri_alpha_pows[NUM_CHALLENGES() + 1][2];
+  fri_alpha_pows[0] <== GlExt(1, 0)();
+  for (var i = 0; i < NUM_CHALLENGES(); i++) {
+    fri_alpha_pows[i + 1] <== GlExtMul()(fri_alpha_pows[i], fri_alpha);
+  }
+  var g[2] = ZETA_NEXT_GENERATOR();
+  signal plonk_zeta_next[2];
+  plonk_zeta_next <== GlExtMul()(plonk_zeta, GlExt(g[0], g[1])());
+
+  signal initial_caps[4][NUM_CAP_ELEMENTS()][4];
+  var constants_sigmas_cap[NUM_CAP_ELEMENTS()][4] = CONSTANTS_SIGMAS_CAP();
+  for (var i = 0; i < NUM_CAP_ELEMENTS(); i++) {
+    for (var j = 0; j < 4; j++) {
+      initial_caps[0][i][j] <== constants_sigmas_cap[i][j];
+    }
+  }
+  initial_caps[1] <== wires_cap;
+  initial_caps[2] <== plonk_zs_partial_products_cap;
+  initial_caps[3] <== quotient_polys_cap;
+
+  component rounds[NUM_FRI_QUERIES()];
+  for (var q = 0; q < NUM_FRI_QUERIES(); q++) {
+    rounds[q] = FriQueryRound();
+    rounds[q].x_index <== challenges[c + q];
+    rounds[q].alpha <== fri_alpha;
+    rounds[q].alpha_pow_num_challenges <== fri_alpha_pows[NUM_CHALLENGES()];
+    rounds[q].zeta <== plonk_zeta;
+    rounds[q].zeta_next <== plonk_zeta_next;
+    rounds[q].reduced_openings_zeta <== reduced_openings_zeta;
+    rounds[q].reduced_openings_zeta_next <== reduced_openings_zeta_next;
+    rounds[q].betas <== fri_betas;
+    rounds[q].initial_caps <== initial_caps;
+    rounds[q].commit_phase_caps <== fri_commit_phase_merkle_caps;
+    rounds[q].initial_evals <== fri_query_initial_evals[q];
+    rounds[q].initial_siblings <== fri_query_initial_siblings[q];
+    rounds[q].step_evals <== fri_query_step_evals[q];
+    rounds[q].step_siblings <== fri_query_step_siblings[q];
+    rounds[q].final_poly <== fri_final_poly;
+  }
+}
+
+component main {public [public_inputs]} = Plonky2Verifier();
@@ -0,0 +1,778 @@
+//! Export of a Circom circuit verifying the proofs of a given circuit, together with the encoding
+//! of proofs into the witness it expects. Proving this circuit with Groth16 wraps a plonky2 proof
+//! into one which is cheap to verify on-chain.
+//!
+//! Only the Goldilocks field with its quadratic extension and Poseidon Merkle trees are supported,
+//! as other hashes would be prohibitively expensive to evaluate over BN254.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::any::TypeId;
+
+use anyhow::{bail, ensure, Result};
+use hashbrown::HashMap;
+
+use crate::field::extension::{Extendable, FieldExtension};
+use crate::field::goldilocks_field::GoldilocksField;
+use crate::field::types::{Field, Field64};
+use crate::hash::hash_types::RichField;
+use crate::hash::hashing::{SPONGE_RATE, SPONGE_WIDTH};
+use crate::hash::merkle_tree::MerkleCap;
+use crate::hash::poseidon::{self, Poseidon, PoseidonHash, ALL_ROUND_CONSTANTS};
+use crate::plonk::circuit_data::{CommonCircuitData, VerifierOnlyCircuitData};
+use crate::plonk::config::{GenericConfig, GenericHashOut};
+use crate::plonk::plonk_common::{salt_size, PlonkOracle};
+use crate::plonk::proof::ProofWithPublicInputs;
+use crate::plonk::solidity_verifier::fill_template;
+use crate::plonk::validate_shape::validate_proof_with_pis_shape;
+
+const GOLDILOCKS_TEMPLATE: &str = include_str!("circom/goldilocks.circom");
+const POSEIDON_TEMPLATE: &str = include_str!("circom/poseidon.circom");
+const CHALLENGER_TEMPLATE: &str = include_str!("circom/challenger.circom");
+const FRI_TEMPLATE: &str = include_str!("circom/fri.circom");
+const GATES_UTILS_TEMPLATE: &str = include_str!("circom/gates_utils.circom");
+const VERIFIER_TEMPLATE: &str = include_str!("circom/verifier.circom");
+
+/// Indentation of the statements substituted into the body of a Circom function or template.
+const STATEMENT_INDENT: &str = "\n  ";
+
+/// The shape of a proof, which fixes the shape of the verifier circuit and of its witness.
+struct ProofShape {
+    num_cap_elements: usize,
+    /// The number of polynomials committed in each initial tree.
+    initial_tree_num_polys: [usize; 4],
+    /// The length of the leaves of each initial tree, including salts.
+    initial_tree_leaf_lens: [usize; 4],
+    /// The number of siblings in the Merkle proofs of the initial trees.
+    num_initial_siblings: usize,
+    /// The arity bits and number of Merkle proof siblings of each FRI reduction step.
+    steps: Vec<(usize, usize)>,
+    max_arity: usize,
+    max_step_siblings: usize,
+    num_final_poly_coeffs: usize,
+}
+
+impl ProofShape {
+    fn new<F: RichField + Extendable<D>, const D: usize>(
+        common_data: &CommonCircuitData<F, D>,
+    ) -> Self {
+        let fri_params = &common_data.fri_params;
+        let cap_height = fri_params.config.cap_height;
+
+        let initial_tree_num_polys = [
+            common_data.num_preprocessed_polys(),
+            common_data.config.num_wires,
+            common_data.num_zs_partial_products_polys(),
+            common_data.num_quotient_polys(),
+        ];
+        let oracles = [
+            PlonkOracle::CONSTANTS_SIGMAS,
+            PlonkOracle::WIRES,
+            PlonkOracle::ZS_PARTIAL_PRODUCTS,
+            PlonkOracle::QUOTIENT,
+        ];
+        let initial_tree_leaf_lens = core::array::from_fn(|i| {
+            initial_tree_num_polys[i] + salt_size(fri_params.hiding && oracles[i].blinding)
+        });
+
+        let mut codeword_bits = fri_params.lde_bits();
+        let steps = fri_params
+            .reduction_arity_bits
+            .iter()
+            .map(|&arity_bits| {
+                codeword_bits -= arity_bits;
+                (arity_bits, codeword_bits - cap_height)
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            num_cap_elements: fri_params.config.num_cap_elements(),
+            initial_tree_num_polys,
+            initial_tree_leaf_lens,
+            num_initial_siblings: fri_params.lde_bits() - cap_height,
+            max_arity: steps.iter().map(|&(a, _)| 1 << a).max().unwrap_or(0),
+            max_step_siblings: steps.iter().map(|&(_, s)| s).max().unwrap_or(0),
+            steps,
+            num_final_poly_coeffs: fri_params.final_poly_len(),
+        }
+    }
+
+    fn max_initial_leaf_len(&self) -> usize {
+        self.initial_tree_leaf_lens.into_iter().max().unwrap()
+    }
+}
+
+/// The transcript of the challenger, as segments of a number of observed elements followed by a
+/// number of drawn challenges, mirroring `get_challenges`.
+fn transcript_segments<F: RichField + Extendable<D>, const D: usize>(
+    common_data: &CommonCircuitData<F, D>,
+    shape: &ProofShape,
+) -> Vec<(usize, usize)> {
+    let config = &common_data.config;
+    let num_challenges = config.num_challenges;
+    let cap_len = 4 * shape.num_cap_elements;
+    let num_openings = common_data.num_constants
+        + config.num_routed_wires
+        + config.num_wires
+        + num_challenges * (common_data.num_partial_products + 2)
+        + common_data.num_quotient_polys();
+
+    let mut segments = vec![
+        // The circuit digest, the public inputs hash and the wires cap, then betas and gammas.
+        (8 + cap_len, 2 * num_challenges),
+        (cap_len, num_challenges),
+        (cap_len, D),
+        // The openings, then the FRI alpha.
+        (D * num_openings, D),
+    ];
+    segments.extend(shape.steps.iter().map(|_| (cap_len, D)));
+    // The final polynomial and the proof of work witness, then its response and the query indices.
+    segments.push((
+        D * shape.num_final_poly_coeffs + 1,
+        1 + common_data.fri_params.config.num_query_rounds,
+    ));
+    segments
+}
+
+/// The number of permutations performed by the challenger over the transcript, mirroring the
+/// duplexing of `Challenger`.
+fn num_challenger_permutations(segments: &[(usize, usize)]) -> usize {
+    let mut num_perms = 0;
+    let mut input_len = 0;
+    let mut output_len = 0;
+    for &(num_observed, num_drawn) in segments {
+        for _ in 0..num_observed {
+            output_len = 0;
+            input_len += 1;
+            if input_len == SPONGE_RATE {
+                num_perms += 1;
+                input_len = 0;
+                output_len = SPONGE_RATE;
+            }
+        }
+        for _ in 0..num_drawn {
+            if input_len > 0 || output_len == 0 {
+                num_perms += 1;
+                input_len = 0;
+                output_len = SPONGE_RATE;
+            }
+            output_len -= 1;
+        }
+    }
+    num_perms
+}
+
+fn check_supported_config<F, C, const D: usize>(common_data: &CommonCircuitData<F, D>) -> Result<()>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    ensure!(
+        D == 2,
+        "The Circom verifier only supports quadratic extensions."
+    );
+    ensure!(
+        F::ORDER == GoldilocksField::ORDER,
+        "The Circom verifier only supports the Goldilocks field."
+    );
+    ensure!(
+        !common_data.has_lookups(),
+        "The Circom verifier does not support lookups."
+    );
+    ensure!(
+        TypeId::of::<C::Hasher>() == TypeId::of::<PoseidonHash>(),
+        "The Circom verifier only supports Poseidon Merkle trees."
+    );
+    Ok(())
+}
+
+/// Joins statements to be substituted into the body of a Circom function or template.
+fn statements(statements: impl IntoIterator<Item = String>) -> String {
+    statements
+        .into_iter()
+        .map(|s| s + ";")
+        .collect::<Vec<_>>()
+        .join(STATEMENT_INDENT)
+}
+
+/// Computes the filter of a gate, mirroring `compute_filter`.
+fn filter_assignment<F: RichField + Extendable<D>, const D: usize>(
+    common_data: &CommonCircuitData<F, D>,
+    row: usize,
+) -> String {
+    let selectors_info = &common_data.selectors_info;
+    let selector_index = selectors_info.selector_indices[row];
+    let filter = selectors_info.groups[selector_index]
+        .clone()
+        .filter(|&i| i != row)
+        .chain((selectors_info.num_selectors() > 1).then_some(u32::MAX as usize))
+        .map(|i| format!("GlExtSub()(GlExt({i}, 0)(), constants[{selector_index}])"))
+        .reduce(|acc, factor| format!("GlExtMul()({acc}, {factor})"))
+        .unwrap_or_else(|| "GlExt(1, 0)()".to_string());
+    // The template provides the semicolon ending the statement.
+    format!("filter <== {filter}")
+}
+
+/// Renames the template declared by a gate's Circom code if its name is already taken.
+fn gate_template(code: String, names: &mut Vec<String>) -> Result<(String, String)> {
+    let start = match code.find("template ") {
+        Some(start) => start + "template ".len(),
+        None => bail!("Circom gate code does not declare a template."),
+    };
+    let len = code[start..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(code.len() - start);
+    let name = code[start..start + len].to_string();
+    let mut unique_name = name.clone();
+    let mut suffix = 1;
+    while names.contains(&unique_name) {
+        unique_name = format!("{name}_{suffix}");
+        suffix += 1;
+    }
+    names.push(unique_name.clone());
+    let code = code[..start].to_string() + &unique_name + &code[start + len..];
+    Ok((unique_name, code))
+}
+
+/// Generates a Circom circuit whose main component, `Plonky2Verifier`, checks proofs of the
+/// circuit described by `verifier_only` and `common_data`. The public inputs of the proof are the
+/// public signals of the Circom circuit, and proofs are passed in the format produced by
+/// `encode_circom_witness`.
+pub fn export_circom_verifier<F, C, const D: usize>(
+    verifier_only: &VerifierOnlyCircuitData<C, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<String>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    check_supported_config::<F, C, D>(common_data)?;
+    let shape = ProofShape::new(common_data);
+    let segments = transcript_segments(common_data, &shape);
+    let config = &common_data.config;
+    let fri_params = &common_data.fri_params;
+
+    let mut values = HashMap::<&str, String>::new();
+    let mut set = |name, value: String| values.insert(name, value);
+
+    // Field and hash constants.
+    set("FIELD_ORDER", F::ORDER.to_string());
+    set(
+        "POWER_OF_TWO_GENERATOR",
+        F::POWER_OF_TWO_GENERATOR.to_string(),
+    );
+    set("TWO_ADICITY", F::TWO_ADICITY.to_string());
+    set(
+        "MULTIPLICATIVE_GROUP_GENERATOR",
+        F::MULTIPLICATIVE_GROUP_GENERATOR.to_string(),
+    );
+    set("EXTENSION_W", <F as Extendable<D>>::W.to_string());
+    set(
+        "HALF_N_FULL_ROUNDS",
+        poseidon::HALF_N_FULL_ROUNDS.to_string(),
+    );
+    set("N_PARTIAL_ROUNDS", poseidon::N_PARTIAL_ROUNDS.to_string());
+    set("N_ROUNDS", poseidon::N_ROUNDS.to_string());
+    let num_round_constants = SPONGE_WIDTH * poseidon::N_ROUNDS;
+    set("NUM_ROUND_CONSTANTS", num_round_constants.to_string());
+    set(
+        "SET_ALL_ROUND_CONSTANTS",
+        statements(
+            ALL_ROUND_CONSTANTS[..num_round_constants]
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("rc[{i}] = {c}")),
+        ),
+    );
+    set(
+        "SET_MDS_MATRIX_CIRC",
+        statements(
+            <F as Poseidon>::MDS_MATRIX_CIRC
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("mds[{i}] = {c}")),
+        ),
+    );
+    set(
+        "SET_MDS_MATRIX_DIAG",
+        statements(
+            <F as Poseidon>::MDS_MATRIX_DIAG
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("mds[{i}] = {c}")),
+        ),
+    );
+
+    // Circuit constants.
+    set(
+        "SET_CIRCUIT_DIGEST",
+        statements(
+            verifier_only
+                .circuit_digest
+                .to_vec()
+                .iter()
+                .enumerate()
+                .map(|(i, x)| format!("digest[{i}] = {x}")),
+        ),
+    );
+    set(
+        "SET_CONSTANTS_SIGMAS_CAP",
+        statements(
+            verifier_only
+                .constants_sigmas_cap
+                .0
+                .iter()
+                .enumerate()
+                .flat_map(|(i, h)| {
+                    h.to_vec()
+                        .into_iter()
+                        .enumerate()
+                        .map(move |(j, x)| format!("cap[{i}][{j}] = {x}"))
+                }),
+        ),
+    );
+    set(
+        "SET_K_IS",
+        statements(
+            common_data.k_is[..config.num_routed_wires]
+                .iter()
+                .enumerate()
+                .map(|(i, k)| format!("k_is[{i}] = {k}")),
+        ),
+    );
+    set(
+        "NUM_PUBLIC_INPUTS",
+        common_data.num_public_inputs.to_string(),
+    );
+    set("NUM_CHALLENGES", config.num_challenges.to_string());
+    set("NUM_ROUTED_WIRES", config.num_routed_wires.to_string());
+    set(
+        "NUM_PARTIAL_PRODUCTS",
+        common_data.num_partial_products.to_string(),
+    );
+    set(
+        "QUOTIENT_DEGREE_FACTOR",
+        common_data.quotient_degree_factor.to_string(),
+    );
+    set(
+        "NUM_QUOTIENT_POLYS",
+        common_data.num_quotient_polys().to_string(),
+    );
+    set(
+        "NUM_GATE_CONSTRAINTS",
+        common_data.num_gate_constraints.to_string(),
+    );
+    set(
+        "NUM_SELECTORS",
+        common_data.selectors_info.num_selectors().to_string(),
+    );
+    set(
+        "NUM_OPENINGS_CONSTANTS",
+        common_data.num_constants.to_string(),
+    );
+    set("NUM_OPENINGS_WIRES", config.num_wires.to_string());
+    set("DEGREE_BITS", common_data.degree_bits().to_string());
+    let zeta_next_generator =
+        F::Extension::primitive_root_of_unity(common_data.degree_bits()).to_basefield_array();
+    set("ZETA_NEXT_GENERATOR_0", zeta_next_generator[0].to_string());
+    set("ZETA_NEXT_GENERATOR_1", zeta_next_generator[1].to_string());
+
+    // FRI parameters.
+    set("CAP_HEIGHT", fri_params.config.cap_height.to_string());
+    set("NUM_CAP_ELEMENTS", shape.num_cap_elements.to_string());
+    set("LDE_BITS", fri_params.lde_bits().to_string());
+    set(
+        "NUM_INITIAL_SIBLINGS",
+        shape.num_initial_siblings.to_string(),
+    );
+    set(
+        "SET_INITIAL_TREE_NUM_POLYS",
+        statements(
+            shape
+                .initial_tree_num_polys
+                .iter()
+                .enumerate()
+                .map(|(i, n)| format!("num_polys[{i}] = {n}")),
+        ),
+    );
+    set(
+        "SET_INITIAL_TREE_LEAF_LENS",
+        statements(
+            shape
+                .initial_tree_leaf_lens
+                .iter()
+                .enumerate()
+                .map(|(i, n)| format!("leaf_lens[{i}] = {n}")),
+        ),
+    );
+    set(
+        "MAX_INITIAL_LEAF_LEN",
+        shape.max_initial_leaf_len().to_string(),
+    );
+    set(
+        "NUM_FRI_QUERIES",
+        fri_params.config.num_query_rounds.to_string(),
+    );
+    set("NUM_FRI_STEPS", shape.steps.len().to_string());
+    set(
+        "SET_FRI_STEP_ARITY_BITS",
+        statements(
+            shape
+                .steps
+                .iter()
+                .enumerate()
+                .map(|(i, (arity_bits, _))| format!("arity_bits[{i}] = {arity_bits}")),
+        ),
+    );
+    set(
+        "SET_FRI_STEP_NUM_SIBLINGS",
+        statements(
+            shape
+                .steps
+                .iter()
+                .enumerate()
+                .map(|(i, (_, num_siblings))| format!("num_siblings[{i}] = {num_siblings}")),
+        ),
+    );
+    set("MAX_FRI_ARITY", shape.max_arity.to_string());
+    set("MAX_FRI_STEP_SIBLINGS", shape.max_step_siblings.to_string());
+    set(
+        "NUM_FINAL_POLY_COEFFS",
+        shape.num_final_poly_coeffs.to_string(),
+    );
+    set("POW_BITS", fri_params.config.proof_of_work_bits.to_string());
+
+    // Challenger.
+    set(
+        "NUM_OBSERVED",
+        segments.iter().map(|&(o, _)| o).sum::<usize>().to_string(),
+    );
+    set(
+        "NUM_DRAWN",
+        segments.iter().map(|&(_, d)| d).sum::<usize>().to_string(),
+    );
+    set(
+        "NUM_CHALLENGER_PERMUTATIONS",
+        num_challenger_permutations(&segments).to_string(),
+    );
+    set("NUM_TRANSCRIPT_SEGMENTS", segments.len().to_string());
+    set(
+        "SET_TRANSCRIPT_SEGMENTS",
+        statements(
+            segments
+                .iter()
+                .enumerate()
+                .flat_map(|(s, (num_observed, num_drawn))| {
+                    [
+                        format!("segments[{s}][0] = {num_observed}"),
+                        format!("segments[{s}][1] = {num_drawn}"),
+                    ]
+                }),
+        ),
+    );
+
+    // Gate constraints. Each gate adds its filtered constraints to those of the previous gates.
+    let mut gate_names = Vec::new();
+    let mut gate_templates = Vec::new();
+    let mut gate_wiring = Vec::new();
+    let mut previous_constraints = "zero".to_string();
+    for (row, gate) in common_data.gates.iter().enumerate() {
+        let mut gate_values = values.clone();
+        gate_values.insert("SET_FILTER", filter_assignment(common_data, row));
+        let code = fill_template(&gate.0.export_circom_verification_code(), &gate_values)?;
+        let (name, code) = gate_template(code, &mut gate_names)?;
+        gate_templates.push(code);
+        let component = format!("gate_{row}");
+        gate_wiring.push(format!("component {component} = {name}()"));
+        gate_wiring.push(format!("{component}.constants <== constants"));
+        gate_wiring.push(format!("{component}.wires <== wires"));
+        gate_wiring.push(format!(
+            "{component}.public_input_hash <== public_input_hash"
+        ));
+        gate_wiring.push(format!(
+            "{component}.constraints <== {previous_constraints}"
+        ));
+        previous_constraints = format!("{component}.out");
+    }
+    gate_wiring.push(format!("out <== {previous_constraints}"));
+    values.insert("EVALUATE_GATE_CONSTRAINTS", statements(gate_wiring));
+    // The Poseidon MDS gate relies on the matrix functions defined by the Poseidon gate.
+    if !gate_templates
+        .iter()
+        .any(|code| code.contains("function MDS_MATRIX_CIRC("))
+    {
+        gate_templates.push(
+            "function MDS_MATRIX_CIRC(i) {\n  return GL_MDS_CIRC(i);\n}\n\nfunction MDS_MATRIX_DIAG(i) {\n  return GL_MDS_DIAG(i);\n}"
+                .to_string(),
+        );
+    }
+
+    let templates = [
+        GOLDILOCKS_TEMPLATE,
+        POSEIDON_TEMPLATE,
+        CHALLENGER_TEMPLATE,
+        GATES_UTILS_TEMPLATE,
+        FRI_TEMPLATE,
+    ];
+    let mut circuit = "pragma circom 2.1.0;\n".to_string();
+    for template in templates {
+        circuit += "\n";
+        circuit += &fill_template(template, &values)?;
+    }
+    for template in gate_templates {
+        circuit += "\n";
+        circuit += &template;
+        circuit += "\n";
+    }
+    circuit += "\n";
+    circuit += &fill_template(VERIFIER_TEMPLATE, &values)?;
+    Ok(circuit)
+}
+
+/// A JSON array of the given JSON values.
+fn json_array(items: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(","))
+}
+
+/// A field element as a JSON string holding its canonical value in decimal.
+fn json_field<F: RichField>(x: &F) -> String {
+    format!("\"{}\"", x.to_canonical_u64())
+}
+
+fn json_field_ext<F: RichField + Extendable<D>, const D: usize>(x: &F::Extension) -> String {
+    json_array(x.to_basefield_array().iter().map(json_field))
+}
+
+fn json_hash<F: RichField, HO: GenericHashOut<F>>(hash: &HO) -> String {
+    json_array(hash.to_vec().iter().map(json_field))
+}
+
+/// `items` followed by `padding` up to a length of `len`.
+fn json_padded_array(items: impl IntoIterator<Item = String>, len: usize, padding: &str) -> String {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    items.resize(len, padding.to_string());
+    json_array(items)
+}
+
+/// Encodes a proof as the input JSON of the circuit generated by `export_circom_verifier`, to be
+/// passed to its witness generator. Field elements are encoded as decimal strings.
+pub fn encode_circom_witness<F, C, const D: usize>(
+    proof_with_pis: &ProofWithPublicInputs<F, C, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<String>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    check_supported_config::<F, C, D>(common_data)?;
+    validate_proof_with_pis_shape(proof_with_pis, common_data)?;
+    let shape = ProofShape::new(common_data);
+
+    let ProofWithPublicInputs {
+        proof,
+        public_inputs,
+    } = proof_with_pis;
+    let fri_proof = &proof.opening_proof;
+    ensure!(
+        fri_proof.commit_phase_merkle_caps.len() == shape.steps.len(),
+        "Number of FRI reduction steps doesn't match circuit data."
+    );
+    for round in &fri_proof.query_round_proofs {
+        ensure!(
+            round.steps.len() == shape.steps.len(),
+            "FRI proof shape doesn't match circuit data."
+        );
+    }
+
+    let json_ext_array =
+        |xs: &[F::Extension]| json_array(xs.iter().map(|x| json_field_ext::<F, D>(x)));
+    let json_cap = |cap: &MerkleCap<F, C::Hasher>| json_array(cap.0.iter().map(json_hash));
+    let zero_field = json_field(&F::ZERO);
+    let zero_hash = json_array(vec![zero_field.clone(); 4]);
+    let zero_field_ext = json_field_ext::<F, D>(&F::Extension::ZERO);
+
+    let openings = &proof.openings;
+    let entries = [
+        (
+            "public_inputs",
+            json_array(public_inputs.iter().map(json_field)),
+        ),
+        ("wires_cap", json_cap(&proof.wires_cap)),
+        (
+            "plonk_zs_partial_products_cap",
+            json_cap(&proof.plonk_zs_partial_products_cap),
+        ),
+        ("quotient_polys_cap", json_cap(&proof.quotient_polys_cap)),
+        ("openings_constants", json_ext_array(&openings.constants)),
+        (
+            "openings_plonk_sigmas",
+            json_ext_array(&openings.plonk_sigmas),
+        ),
+        ("openings_wires", json_ext_array(&openings.wires)),
+        ("openings_plonk_zs", json_ext_array(&openings.plonk_zs)),
+        (
+            "openings_partial_products",
+            json_ext_array(&openings.partial_products),
+        ),
+        (
+            "openings_quotient_polys",
+            json_ext_array(&openings.quotient_polys),
+        ),
+        (
+            "openings_plonk_zs_next",
+            json_ext_array(&openings.plonk_zs_next),
+        ),
+        (
+            "fri_commit_phase_merkle_caps",
+            json_array(fri_proof.commit_phase_merkle_caps.iter().map(json_cap)),
+        ),
+        (
+            "fri_query_initial_evals",
+            json_array(fri_proof.query_round_proofs.iter().map(|round| {
+                json_array(
+                    round
+                        .initial_trees_proof
+                        .evals_proofs
+                        .iter()
+                        .map(|(evals, _)| {
+                            json_padded_array(
+                                evals.iter().map(json_field),
+                                shape.max_initial_leaf_len(),
+                                &zero_field,
+                            )
+                        }),
+                )
+            })),
+        ),
+        (
+            "fri_query_initial_siblings",
+            json_array(fri_proof.query_round_proofs.iter().map(|round| {
+                json_array(round.initial_trees_proof.evals_proofs.iter().map(
+                    |(_, merkle_proof)| json_array(merkle_proof.siblings.iter().map(json_hash)),
+                ))
+            })),
+        ),
+        (
+            "fri_query_step_evals",
+            json_array(fri_proof.query_round_proofs.iter().map(|round| {
+                json_array(round.steps.iter().map(|step| {
+                    json_padded_array(
+                        step.evals.iter().map(|x| json_field_ext::<F, D>(x)),
+                        shape.max_arity,
+                        &zero_field_ext,
+                    )
+                }))
+            })),
+        ),
+        (
+            "fri_query_step_siblings",
+            json_array(fri_proof.query_round_proofs.iter().map(|round| {
+                json_array(round.steps.iter().map(|step| {
+                    json_padded_array(
+                        step.merkle_proof.siblings.iter().map(json_hash),
+                        shape.max_step_siblings,
+                        &zero_hash,
+                    )
+                }))
+            })),
+        ),
+        (
+            "fri_final_poly",
+            json_ext_array(&fri_proof.final_poly.coeffs),
+        ),
+        ("fri_pow_witness", json_field(&fri_proof.pow_witness)),
+    ];
+
+    let body = entries
+        .into_iter()
+        .map(|(name, value)| format!("  \"{name}\": {value}"))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    Ok(format!("{{\n{body}\n}}\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_builder::CircuitBuilder;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+
+    #[test]
+    fn test_circom_verifier_export() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let x = builder.add_virtual_target();
+        let y = builder.add_virtual_target();
+        let z = builder.mul(x, y);
+        let w = builder.add(z, x);
+        builder.register_public_input(x);
+        builder.register_public_input(w);
+        // Add a Poseidon gate.
+        let h = builder.hash_n_to_hash_no_pad::<PoseidonHash>(vec![x, y]);
+        builder.register_public_inputs(&h.elements);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(x, F::from_canonical_u64(3));
+        pw.set_target(y, F::from_canonical_u64(5));
+        let proof = data.prove(pw)?;
+        data.verify(proof.clone())?;
+
+        let verifier_data = data.verifier_data();
+        let circuit = verifier_data.export_circom_verifier()?;
+        assert!(circuit.starts_with("pragma circom"));
+        assert!(circuit.contains("template Poseidon12()"));
+        assert!(circuit.contains("component main {public [public_inputs]} = Plonky2Verifier();"));
+        assert!(!circuit.contains('$'));
+
+        let witness = encode_circom_witness(&proof, &verifier_data.common)?;
+        for name in [
+            "public_inputs",
+            "openings_plonk_zs_next",
+            "fri_query_step_siblings",
+            "fri_pow_witness",
+        ] {
+            assert!(witness.contains(&format!("\"{name}\": ")));
+        }
+        assert!(witness.contains(&format!(
+            "\"public_inputs\": {}",
+            json_array(proof.public_inputs.iter().map(json_field))
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn test_circom_verifier_unsupported_hasher() {
+        const D: usize = 2;
+        type C = KeccakGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let builder = CircuitBuilder::<F, D>::new(config);
+        let data = builder.build::<C>();
+        assert!(data.verifier_data().export_circom_verifier().is_err());
+    }
+
+    #[test]
+    fn test_num_challenger_permutations() {
+        // Observing a full rate of elements permutes immediately, and drawing permutes if there
+        // are pending inputs or no outputs left.
+        let segments = [(13, 4), (0, 9), (8, 1), (3, 0), (1, 2)];
+        assert_eq!(num_challenger_permutations(&segments), 5);
+        assert_eq!(num_challenger_permutations(&[(8, 0)]), 1);
+        assert_eq!(num_challenger_permutations(&[(0, 8)]), 1);
+        assert_eq!(num_challenger_permutations(&[(0, 9)]), 2);
+    }
+}
@@ -24,6 +24,8 @@ use crate::gates::arithmetic_base::ArithmeticGate;
 use crate::gates::arithmetic_extension::ArithmeticExtensionGate;
 use crate::gates::constant::ConstantGate;
 use crate::gates::gate::{CurrentSlot, Gate, GateInstance, GateRef};
+use crate::gates::lookup::LOOKUP_CHUNK_SIZE;
+use crate::gates::lookup_table::LookupTable;
 use crate::gates::noop::NoopGate;
 use crate::gates::public_input::PublicInputGate;
 use crate::gates::selectors::selector_polynomials;
@@ -48,7 +50,7 @@ use crate::timed;
 use crate::util::context_tree::ContextTree;
 use crate::util::partial_products::num_partial_products;
 use crate::util::timing::TimingTree;
-use crate::util::{log2_ceil, log2_strict, transpose, transpose_poly_values};
+use crate::util::{ceil_div_usize, log2_ceil, log2_strict, transpose, transpose_poly_values};
 
 pub struct CircuitBuilder<F: RichField + Extendable<D>, const D: usize> {
     pub config: CircuitConfig,
@@ -101,6 +103,12 @@ pub struct CircuitBuilder<F: RichField + Extendable<D>, const D: usize> {
     /// Optional verifier data that is registered as public inputs.
     /// This is used in cyclic recursion to hold the circuit's own verifier key.
     pub(crate) verifier_data_public_input: Option<VerifierCircuitTarget>,
+
+    /// The lookup tables of the circuit, indexed by their position.
+    pub(crate) luts: Vec<LookupTable>,
+
+    /// The input wires of the lookups into each table.
+    pub(crate) lookup_inputs: Vec<Vec<Target>>,
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
@@ -123,6 +131,8 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             constant_generators: Vec::new(),
             goal_common_data: None,
             verifier_data_public_input: None,
+            luts: Vec::new(),
+            lookup_inputs: Vec::new(),
         };
         builder.check_config();
         builder
@@ -740,6 +750,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         let rate_bits = self.config.fri_config.rate_bits;
         let cap_height = self.config.fri_config.cap_height;
 
+        // Add the lookup tables, which may use constants and thus must come first.
+        self.add_lookup_table_gates();
+
         // Hash the public inputs, and route them to a `PublicInputGate` which will enforce that
         // those hash wires match the claimed public inputs.
         let num_public_inputs = self.public_inputs.len();
@@ -879,6 +892,17 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         let num_partial_products =
             num_partial_products(self.config.num_routed_wires, quotient_degree_factor);
 
+        let max_num_lookups = gates
+            .iter()
+            .map(|gate| gate.0.num_lookups())
+            .max()
+            .unwrap_or(0);
+        let num_lookup_polys = if max_num_lookups > 0 {
+            1 + ceil_div_usize(max_num_lookups, LOOKUP_CHUNK_SIZE)
+        } else {
+            0
+        };
+
         let constants_sigmas_cap = constants_sigmas_commitment.merkle_tree.cap.clone();
         let domain_separator = self.domain_separator.unwrap_or_default();
         let domain_separator_digest = C::Hasher::hash_pad(&domain_separator);
@@ -904,6 +928,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             num_public_inputs,
             k_is,
             num_partial_products,
+            num_lookup_polys,
         };
         if let Some(goal_data) = self.goal_common_data {
             assert_eq!(goal_data, common, "The expected circuit data passed to cyclic recursion method did not match the actual circuit");
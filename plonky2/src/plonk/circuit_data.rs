@@ -1,8 +1,9 @@
 use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::ops::{Range, RangeFrom};
+use core::ops::Range;
 
 use anyhow::Result;
 
@@ -24,12 +25,17 @@ use crate::iop::ext_target::ExtensionTarget;
 use crate::iop::generator::WitnessGenerator;
 use crate::iop::target::Target;
 use crate::iop::witness::PartialWitness;
+use crate::plonk::circom_verifier::export_circom_verifier;
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::config::{GenericConfig, Hasher};
 use crate::plonk::plonk_common::PlonkOracle;
 use crate::plonk::proof::{CompressedProofWithPublicInputs, ProofWithPublicInputs};
 use crate::plonk::prover::prove;
+use crate::plonk::solidity_verifier::export_solidity_verifier;
 use crate::plonk::verifier::verify;
+use crate::util::serialization::{
+    Buffer, GateSerializer, IoResult, Read, WitnessGeneratorSerializer, Write,
+};
 use crate::util::timing::TimingTree;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -122,6 +128,34 @@ pub struct CircuitData<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>,
 impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
     CircuitData<F, C, D>
 {
+    pub fn to_bytes(
+        &self,
+        gate_serializer: &dyn GateSerializer<F, D>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<Vec<u8>> {
+        let mut buffer = Vec::new();
+        buffer.write_common_circuit_data(&self.common, gate_serializer)?;
+        buffer.write_prover_only_circuit_data(&self.prover_only, generator_serializer)?;
+        buffer.write_verifier_only_circuit_data(&self.verifier_only)?;
+        Ok(buffer)
+    }
+
+    pub fn from_bytes(
+        bytes: Vec<u8>,
+        gate_serializer: &dyn GateSerializer<F, D>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<Self> {
+        let mut buffer = Buffer::new(bytes);
+        let common = buffer.read_common_circuit_data(gate_serializer)?;
+        let prover_only = buffer.read_prover_only_circuit_data(generator_serializer)?;
+        let verifier_only = buffer.read_verifier_only_circuit_data()?;
+        Ok(Self {
+            prover_only,
+            verifier_only,
+            common,
+        })
+    }
+
     pub fn prove(&self, inputs: PartialWitness<F>) -> Result<ProofWithPublicInputs<F, C, D>> {
         prove(
             &self.prover_only,
@@ -200,6 +234,31 @@ pub struct ProverCircuitData<
 impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
     ProverCircuitData<F, C, D>
 {
+    pub fn to_bytes(
+        &self,
+        gate_serializer: &dyn GateSerializer<F, D>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<Vec<u8>> {
+        let mut buffer = Vec::new();
+        buffer.write_common_circuit_data(&self.common, gate_serializer)?;
+        buffer.write_prover_only_circuit_data(&self.prover_only, generator_serializer)?;
+        Ok(buffer)
+    }
+
+    pub fn from_bytes(
+        bytes: Vec<u8>,
+        gate_serializer: &dyn GateSerializer<F, D>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<Self> {
+        let mut buffer = Buffer::new(bytes);
+        let common = buffer.read_common_circuit_data(gate_serializer)?;
+        let prover_only = buffer.read_prover_only_circuit_data(generator_serializer)?;
+        Ok(Self {
+            prover_only,
+            common,
+        })
+    }
+
     pub fn prove(&self, inputs: PartialWitness<F>) -> Result<ProofWithPublicInputs<F, C, D>> {
         prove(
             &self.prover_only,
@@ -234,6 +293,23 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
     ) -> Result<()> {
         compressed_proof_with_pis.verify(&self.verifier_only, &self.common)
     }
+
+    /// Generates a Solidity contract verifying proofs of this circuit. See
+    /// `export_solidity_verifier`.
+    pub fn export_solidity_verifier(&self) -> Result<String>
+    where
+        C::Hasher: 'static,
+    {
+        export_solidity_verifier(&self.verifier_only, &self.common)
+    }
+
+    /// Generates a Circom circuit verifying proofs of this circuit. See `export_circom_verifier`.
+    pub fn export_circom_verifier(&self) -> Result<String>
+    where
+        C::Hasher: 'static,
+    {
+        export_circom_verifier(&self.verifier_only, &self.common)
+    }
 }
 
 /// Circuit data required by the prover, but not the verifier.
@@ -274,6 +350,19 @@ pub struct VerifierOnlyCircuitData<C: GenericConfig<D>, const D: usize> {
     pub circuit_digest: <<C as GenericConfig<D>>::Hasher as Hasher<C::F>>::Hash,
 }
 
+impl<C: GenericConfig<D>, const D: usize> VerifierOnlyCircuitData<C, D> {
+    pub fn to_bytes(&self) -> IoResult<Vec<u8>> {
+        let mut buffer = Vec::new();
+        buffer.write_verifier_only_circuit_data::<C::F, C, D>(self)?;
+        Ok(buffer)
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> IoResult<Self> {
+        let mut buffer = Buffer::new(bytes);
+        buffer.read_verifier_only_circuit_data::<C::F, C, D>()
+    }
+}
+
 /// Circuit data required by both the prover and the verifier.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct CommonCircuitData<F: RichField + Extendable<D>, const D: usize> {
@@ -303,9 +392,27 @@ pub struct CommonCircuitData<F: RichField + Extendable<D>, const D: usize> {
 
     /// The number of partial products needed to compute the `Z` polynomials.
     pub num_partial_products: usize,
+
+    /// The number of polynomials per challenge used by the lookup argument: a running sum followed
+    /// by its helper polynomials, or zero if the circuit has no lookups.
+    pub num_lookup_polys: usize,
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
+    pub fn to_bytes(&self, gate_serializer: &dyn GateSerializer<F, D>) -> IoResult<Vec<u8>> {
+        let mut buffer = Vec::new();
+        buffer.write_common_circuit_data(self, gate_serializer)?;
+        Ok(buffer)
+    }
+
+    pub fn from_bytes(
+        bytes: Vec<u8>,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<Self> {
+        let mut buffer = Buffer::new(bytes);
+        buffer.read_common_circuit_data(gate_serializer)
+    }
+
     pub const fn degree_bits(&self) -> usize {
         self.fri_params.degree_bits
     }
@@ -350,8 +457,39 @@ impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
     }
 
     /// Range of the partial products polynomials in the `zs_partial_products_commitment`.
-    pub fn partial_products_range(&self) -> RangeFrom<usize> {
-        self.config.num_challenges..
+    pub fn partial_products_range(&self) -> Range<usize> {
+        self.config.num_challenges..self.config.num_challenges * (1 + self.num_partial_products)
+    }
+
+    pub fn has_lookups(&self) -> bool {
+        self.num_lookup_polys > 0
+    }
+
+    /// The number of challenges drawn for each random value of the lookup argument. None are drawn
+    /// for circuits without lookups.
+    pub(crate) fn num_lookup_challenges(&self) -> usize {
+        if self.has_lookups() {
+            self.config.num_challenges
+        } else {
+            0
+        }
+    }
+
+    /// The number of helper polynomials of the lookup argument, per challenge.
+    pub fn num_lookup_helpers(&self) -> usize {
+        self.num_lookup_polys.saturating_sub(1)
+    }
+
+    /// Range of the lookup running sums in the `zs_partial_products_commitment`.
+    pub fn lookup_zs_range(&self) -> Range<usize> {
+        let start = self.partial_products_range().end;
+        start..start + self.num_lookup_challenges()
+    }
+
+    /// Range of the lookup helper polynomials in the `zs_partial_products_commitment`.
+    pub fn lookup_helpers_range(&self) -> Range<usize> {
+        let start = self.lookup_zs_range().end;
+        start..start + self.config.num_challenges * self.num_lookup_helpers()
     }
 
     pub(crate) fn get_fri_instance(&self, zeta: F::Extension) -> FriInstanceInfo<F, D> {
@@ -361,7 +499,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
             polynomials: self.fri_all_polys(),
         };
 
-        // The Z polynomials are also opened at g * zeta.
+        // The Z polynomials and the lookup running sums are also opened at g * zeta.
         let g = F::Extension::primitive_root_of_unity(self.degree_bits());
         let zeta_next = g * zeta;
         let zeta_next_batch = FriBatchInfo {
@@ -387,7 +525,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
             polynomials: self.fri_all_polys(),
         };
 
-        // The Z polynomials are also opened at g * zeta.
+        // The Z polynomials and the lookup running sums are also opened at g * zeta.
         let g = F::primitive_root_of_unity(self.degree_bits());
         let zeta_next = builder.mul_const_extension(g, zeta);
         let zeta_next_batch = FriBatchInfoTarget {
@@ -447,11 +585,18 @@ impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
     }
 
     pub(crate) fn num_zs_partial_products_polys(&self) -> usize {
-        self.config.num_challenges * (1 + self.num_partial_products)
+        self.config.num_challenges * (1 + self.num_partial_products + self.num_lookup_polys)
     }
 
     fn fri_zs_polys(&self) -> Vec<FriPolynomialInfo> {
-        FriPolynomialInfo::from_range(PlonkOracle::ZS_PARTIAL_PRODUCTS.index, self.zs_range())
+        [
+            FriPolynomialInfo::from_range(PlonkOracle::ZS_PARTIAL_PRODUCTS.index, self.zs_range()),
+            FriPolynomialInfo::from_range(
+                PlonkOracle::ZS_PARTIAL_PRODUCTS.index,
+                self.lookup_zs_range(),
+            ),
+        ]
+        .concat()
     }
 
     fn fri_quotient_polys(&self) -> Vec<FriPolynomialInfo> {
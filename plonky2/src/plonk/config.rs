@@ -12,6 +12,7 @@ use crate::hash::hash_types::{HashOut, HashOutTarget, RichField};
 use crate::hash::hashing::{PlonkyPermutation, SPONGE_WIDTH};
 use crate::hash::keccak::KeccakHash;
 use crate::hash::poseidon::PoseidonHash;
+use crate::hash::poseidon_bn254::PoseidonBn254Hash;
 use crate::iop::target::{BoolTarget, Target};
 use crate::plonk::circuit_builder::CircuitBuilder;
 
@@ -126,3 +127,15 @@ impl GenericConfig<2> for KeccakGoldilocksConfig {
     type Hasher = KeccakHash<25>;
     type InnerHasher = PoseidonHash;
 }
+
+/// Configuration using Poseidon over BN254 for Merkle trees and the challenger over the Goldilocks
+/// field. Its proofs are cheap to verify in a SNARK over BN254, so it is meant for the last layer
+/// of recursion, before wrapping.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PoseidonBn254GoldilocksConfig;
+impl GenericConfig<2> for PoseidonBn254GoldilocksConfig {
+    type F = GoldilocksField;
+    type FE = QuadraticExtension<Self::F>;
+    type Hasher = PoseidonBn254Hash;
+    type InnerHasher = PoseidonHash;
+}
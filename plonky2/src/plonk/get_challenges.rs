@@ -46,6 +46,9 @@ fn get_challenges<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, cons
     challenger.observe_cap(wires_cap);
     let plonk_betas = challenger.get_n_challenges(num_challenges);
     let plonk_gammas = challenger.get_n_challenges(num_challenges);
+    // The lookup challenges are only drawn for circuits with lookups.
+    let plonk_deltas = challenger.get_n_challenges(common_data.num_lookup_challenges());
+    let plonk_epsilons = challenger.get_n_challenges(common_data.num_lookup_challenges());
 
     challenger.observe_cap(plonk_zs_partial_products_cap);
     let plonk_alphas = challenger.get_n_challenges(num_challenges);
@@ -58,6 +61,8 @@ fn get_challenges<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, cons
     Ok(ProofChallenges {
         plonk_betas,
         plonk_gammas,
+        plonk_deltas,
+        plonk_epsilons,
         plonk_alphas,
         plonk_zeta,
         fri_challenges: challenger.fri_challenges::<C, D>(
@@ -263,6 +268,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         challenger.observe_cap(wires_cap);
         let plonk_betas = challenger.get_n_challenges(self, num_challenges);
         let plonk_gammas = challenger.get_n_challenges(self, num_challenges);
+        let num_lookup_challenges = inner_common_data.num_lookup_challenges();
+        let plonk_deltas = challenger.get_n_challenges(self, num_lookup_challenges);
+        let plonk_epsilons = challenger.get_n_challenges(self, num_lookup_challenges);
 
         challenger.observe_cap(plonk_zs_partial_products_cap);
         let plonk_alphas = challenger.get_n_challenges(self, num_challenges);
@@ -275,6 +283,8 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         ProofChallengesTarget {
             plonk_betas,
             plonk_gammas,
+            plonk_deltas,
+            plonk_epsilons,
             plonk_alphas,
             plonk_zeta,
             fri_challenges: challenger.fri_challenges::<C>(
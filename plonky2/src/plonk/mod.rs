@@ -1,3 +1,4 @@
+pub mod circom_verifier;
 pub mod circuit_builder;
 pub mod circuit_data;
 pub mod config;
@@ -7,6 +8,7 @@ pub(crate) mod permutation_argument;
 pub mod plonk_common;
 pub mod proof;
 pub mod prover;
+pub mod solidity_verifier;
 mod validate_shape;
 pub(crate) mod vanishing_poly;
 pub mod vars;
@@ -261,6 +261,12 @@ pub(crate) struct ProofChallenges<F: RichField + Extendable<D>, const D: usize>
     /// Random values used in Plonk's permutation argument.
     pub plonk_gammas: Vec<F>,
 
+    /// Points at which the logarithmic derivatives of the lookup argument are evaluated.
+    pub plonk_deltas: Vec<F>,
+
+    /// Random values used to compress lookup entries into a single field element.
+    pub plonk_epsilons: Vec<F>,
+
     /// Random values used to combine PLONK constraints.
     pub plonk_alphas: Vec<F>,
 
@@ -273,6 +279,8 @@ pub(crate) struct ProofChallenges<F: RichField + Extendable<D>, const D: usize>
 pub(crate) struct ProofChallengesTarget<const D: usize> {
     pub plonk_betas: Vec<Target>,
     pub plonk_gammas: Vec<Target>,
+    pub plonk_deltas: Vec<Target>,
+    pub plonk_epsilons: Vec<Target>,
     pub plonk_alphas: Vec<Target>,
     pub plonk_zeta: ExtensionTarget<D>,
     pub fri_challenges: FriChallengesTarget<D>,
@@ -298,6 +306,9 @@ pub struct OpeningSet<F: RichField + Extendable<D>, const D: usize> {
     pub plonk_zs: Vec<F::Extension>,
     pub plonk_zs_next: Vec<F::Extension>,
     pub partial_products: Vec<F::Extension>,
+    pub lookup_zs: Vec<F::Extension>,
+    pub lookup_zs_next: Vec<F::Extension>,
+    pub lookup_helpers: Vec<F::Extension>,
     pub quotient_polys: Vec<F::Extension>,
 }
 
@@ -319,16 +330,19 @@ impl<F: RichField + Extendable<D>, const D: usize> OpeningSet<F, D> {
         };
         let constants_sigmas_eval = eval_commitment(zeta, constants_sigmas_commitment);
         let zs_partial_products_eval = eval_commitment(zeta, zs_partial_products_commitment);
+        let zs_partial_products_next_eval =
+            eval_commitment(g * zeta, zs_partial_products_commitment);
         Self {
             constants: constants_sigmas_eval[common_data.constants_range()].to_vec(),
             plonk_sigmas: constants_sigmas_eval[common_data.sigmas_range()].to_vec(),
             wires: eval_commitment(zeta, wires_commitment),
             plonk_zs: zs_partial_products_eval[common_data.zs_range()].to_vec(),
-            plonk_zs_next: eval_commitment(g * zeta, zs_partial_products_commitment)
-                [common_data.zs_range()]
-            .to_vec(),
+            plonk_zs_next: zs_partial_products_next_eval[common_data.zs_range()].to_vec(),
             partial_products: zs_partial_products_eval[common_data.partial_products_range()]
                 .to_vec(),
+            lookup_zs: zs_partial_products_eval[common_data.lookup_zs_range()].to_vec(),
+            lookup_zs_next: zs_partial_products_next_eval[common_data.lookup_zs_range()].to_vec(),
+            lookup_helpers: zs_partial_products_eval[common_data.lookup_helpers_range()].to_vec(),
             quotient_polys: eval_commitment(zeta, quotient_polys_commitment),
         }
     }
@@ -341,12 +355,18 @@ impl<F: RichField + Extendable<D>, const D: usize> OpeningSet<F, D> {
                 self.wires.as_slice(),
                 self.plonk_zs.as_slice(),
                 self.partial_products.as_slice(),
+                self.lookup_zs.as_slice(),
+                self.lookup_helpers.as_slice(),
                 self.quotient_polys.as_slice(),
             ]
             .concat(),
         };
         let zeta_next_batch = FriOpeningBatch {
-            values: self.plonk_zs_next.clone(),
+            values: [
+                self.plonk_zs_next.as_slice(),
+                self.lookup_zs_next.as_slice(),
+            ]
+            .concat(),
         };
         FriOpenings {
             batches: vec![zeta_batch, zeta_next_batch],
@@ -363,6 +383,9 @@ pub struct OpeningSetTarget<const D: usize> {
     pub plonk_zs: Vec<ExtensionTarget<D>>,
     pub plonk_zs_next: Vec<ExtensionTarget<D>>,
     pub partial_products: Vec<ExtensionTarget<D>>,
+    pub lookup_zs: Vec<ExtensionTarget<D>>,
+    pub lookup_zs_next: Vec<ExtensionTarget<D>>,
+    pub lookup_helpers: Vec<ExtensionTarget<D>>,
     pub quotient_polys: Vec<ExtensionTarget<D>>,
 }
 
@@ -375,12 +398,18 @@ impl<const D: usize> OpeningSetTarget<D> {
                 self.wires.as_slice(),
                 self.plonk_zs.as_slice(),
                 self.partial_products.as_slice(),
+                self.lookup_zs.as_slice(),
+                self.lookup_helpers.as_slice(),
                 self.quotient_polys.as_slice(),
             ]
             .concat(),
         };
         let zeta_next_batch = FriOpeningBatchTarget {
-            values: self.plonk_zs_next.clone(),
+            values: [
+                self.plonk_zs_next.as_slice(),
+                self.lookup_zs_next.as_slice(),
+            ]
+            .concat(),
         };
         FriOpeningsTarget {
             batches: vec![zeta_batch, zeta_next_batch],
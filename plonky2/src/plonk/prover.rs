@@ -18,7 +18,7 @@ use crate::plonk::circuit_data::{CommonCircuitData, ProverOnlyCircuitData};
 use crate::plonk::config::{GenericConfig, Hasher};
 use crate::plonk::plonk_common::PlonkOracle;
 use crate::plonk::proof::{OpeningSet, Proof, ProofWithPublicInputs};
-use crate::plonk::vanishing_poly::eval_vanishing_poly_base_batch;
+use crate::plonk::vanishing_poly::{eval_vanishing_poly_base_batch, lookup_chunk_fraction};
 use crate::plonk::vars::EvaluationVarsBaseBatch;
 use crate::timed;
 use crate::util::partial_products::{partial_products_and_z_gx, quotient_chunk_products};
@@ -83,6 +83,9 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
     challenger.observe_cap(&wires_commitment.merkle_tree.cap);
     let betas = challenger.get_n_challenges(num_challenges);
     let gammas = challenger.get_n_challenges(num_challenges);
+    // The lookup challenges are only drawn for circuits with lookups.
+    let deltas = challenger.get_n_challenges(common_data.num_lookup_challenges());
+    let epsilons = challenger.get_n_challenges(common_data.num_lookup_challenges());
 
     assert!(
         common_data.quotient_degree_factor < common_data.config.num_routed_wires,
@@ -99,7 +102,22 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
         .iter_mut()
         .map(|partial_products_and_z| partial_products_and_z.pop().unwrap())
         .collect();
-    let zs_partial_products = [plonk_z_vecs, partial_products_and_zs.concat()].concat();
+
+    let lookup_polys = timed!(
+        timing,
+        "compute lookup polys",
+        compute_lookup_polys(
+            &witness,
+            &public_inputs_hash,
+            &deltas,
+            &epsilons,
+            prover_data,
+            common_data,
+        )
+    );
+
+    let zs_partial_products =
+        [plonk_z_vecs, partial_products_and_zs.concat(), lookup_polys].concat();
 
     let partial_products_and_zs_commitment = timed!(
         timing,
@@ -129,6 +147,8 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
             &partial_products_and_zs_commitment,
             &betas,
             &gammas,
+            &deltas,
+            &epsilons,
             &alphas,
         )
     );
@@ -306,6 +326,104 @@ fn wires_permutation_partial_products_and_zs<
         .collect()
 }
 
+/// Compute the polynomials of the lookup argument: the running sum `Z` for each challenge, followed
+/// by the helper polynomials of each challenge. See `check_lookups` for the constraints they satisfy.
+fn compute_lookup_polys<
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    const D: usize,
+>(
+    witness: &MatrixWitness<F>,
+    public_inputs_hash: &<<C as GenericConfig<D>>::InnerHasher as Hasher<F>>::Hash,
+    deltas: &[F],
+    epsilons: &[F],
+    prover_data: &ProverOnlyCircuitData<F, C, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Vec<PolynomialValues<F>> {
+    if !common_data.has_lookups() {
+        return Vec::new();
+    }
+
+    let degree = common_data.degree();
+    let num_helpers = common_data.num_lookup_helpers();
+    let num_selectors = common_data.selectors_info.num_selectors();
+    let constants = prover_data.constants_sigmas_commitment.polynomials
+        [common_data.constants_range()]
+    .par_iter()
+    .map(|poly| poly.clone().fft())
+    .collect::<Vec<_>>();
+
+    // The lookup terms of each row, which are only nonzero for rows of gates with lookups.
+    let lookup_terms = (0..degree)
+        .into_par_iter()
+        .map(|row| {
+            let local_constants = constants
+                .iter()
+                .map(|poly| poly.values[row])
+                .collect::<Vec<_>>();
+            let local_wires = (0..common_data.config.num_wires)
+                .map(|j| witness.get_wire(row, j))
+                .collect::<Vec<_>>();
+            let mut vars_batch =
+                EvaluationVarsBaseBatch::new(1, &local_constants, &local_wires, public_inputs_hash);
+            vars_batch.remove_prefix(num_selectors);
+
+            // A row belongs to the gate whose index is the value of its selector.
+            common_data
+                .gates
+                .iter()
+                .enumerate()
+                .find(|&(i, gate)| {
+                    let selector_index = common_data.selectors_info.selector_indices[i];
+                    gate.0.num_lookups() > 0
+                        && local_constants[selector_index] == F::from_canonical_usize(i)
+                })
+                .map(|(_, gate)| gate.0.eval_lookups_base(vars_batch.view(0)))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut lookup_zs = Vec::with_capacity(deltas.len());
+    let mut all_helpers = Vec::with_capacity(deltas.len() * num_helpers);
+    for (&delta, &epsilon) in deltas.iter().zip(epsilons) {
+        let (numerators, denominators): (Vec<F>, Vec<F>) = lookup_terms
+            .par_iter()
+            .flat_map_iter(|terms| {
+                (0..num_helpers).map(move |c| lookup_chunk_fraction(terms, c, delta, epsilon))
+            })
+            .unzip();
+        let denominator_invs = F::batch_multiplicative_inverse(&denominators);
+        // Row-major values of the helper polynomials.
+        let helpers = numerators
+            .into_iter()
+            .zip(denominator_invs)
+            .map(|(num, den_inv)| num * den_inv)
+            .collect::<Vec<_>>();
+
+        let mut z = Vec::with_capacity(degree);
+        let mut acc = F::ZERO;
+        for row_helpers in helpers.chunks(num_helpers) {
+            z.push(acc);
+            acc += row_helpers.iter().copied().sum();
+        }
+        debug_assert_eq!(acc, F::ZERO, "The lookup argument does not balance.");
+        lookup_zs.push(PolynomialValues::new(z));
+
+        all_helpers.extend(
+            transpose(
+                &helpers
+                    .chunks(num_helpers)
+                    .map(<[F]>::to_vec)
+                    .collect::<Vec<_>>(),
+            )
+            .into_iter()
+            .map(PolynomialValues::new),
+        );
+    }
+
+    [lookup_zs, all_helpers].concat()
+}
+
 const BATCH_SIZE: usize = 32;
 
 fn compute_quotient_polys<
@@ -321,6 +439,8 @@ fn compute_quotient_polys<
     zs_partial_products_commitment: &'a PolynomialBatch<F, C, D>,
     betas: &[F],
     gammas: &[F],
+    deltas: &[F],
+    epsilons: &[F],
     alphas: &[F],
 ) -> Vec<PolynomialCoeffs<F>> {
     let num_challenges = common_data.config.num_challenges;
@@ -361,6 +481,9 @@ fn compute_quotient_polys<
             let mut local_zs_batch = Vec::with_capacity(xs_batch.len());
             let mut next_zs_batch = Vec::with_capacity(xs_batch.len());
             let mut partial_products_batch = Vec::with_capacity(xs_batch.len());
+            let mut local_lookup_zs_batch = Vec::with_capacity(xs_batch.len());
+            let mut next_lookup_zs_batch = Vec::with_capacity(xs_batch.len());
+            let mut lookup_helpers_batch = Vec::with_capacity(xs_batch.len());
             let mut s_sigmas_batch = Vec::with_capacity(xs_batch.len());
 
             let mut local_constants_batch_refs = Vec::with_capacity(xs_batch.len());
@@ -377,11 +500,15 @@ fn compute_quotient_polys<
                 let local_wires = wires_commitment.get_lde_values(i, step);
                 let local_zs_partial_products =
                     zs_partial_products_commitment.get_lde_values(i, step);
+                let next_zs_partial_products =
+                    zs_partial_products_commitment.get_lde_values(i_next, step);
                 let local_zs = &local_zs_partial_products[common_data.zs_range()];
-                let next_zs = &zs_partial_products_commitment.get_lde_values(i_next, step)
-                    [common_data.zs_range()];
+                let next_zs = &next_zs_partial_products[common_data.zs_range()];
                 let partial_products =
                     &local_zs_partial_products[common_data.partial_products_range()];
+                let local_lookup_zs = &local_zs_partial_products[common_data.lookup_zs_range()];
+                let next_lookup_zs = &next_zs_partial_products[common_data.lookup_zs_range()];
+                let lookup_helpers = &local_zs_partial_products[common_data.lookup_helpers_range()];
 
                 debug_assert_eq!(local_wires.len(), common_data.config.num_wires);
                 debug_assert_eq!(local_zs.len(), num_challenges);
@@ -393,6 +520,9 @@ fn compute_quotient_polys<
                 local_zs_batch.push(local_zs);
                 next_zs_batch.push(next_zs);
                 partial_products_batch.push(partial_products);
+                local_lookup_zs_batch.push(local_lookup_zs);
+                next_lookup_zs_batch.push(next_lookup_zs);
+                lookup_helpers_batch.push(lookup_helpers);
                 s_sigmas_batch.push(s_sigmas);
             }
 
@@ -428,9 +558,14 @@ fn compute_quotient_polys<
                 &local_zs_batch,
                 &next_zs_batch,
                 &partial_products_batch,
+                &local_lookup_zs_batch,
+                &next_lookup_zs_batch,
+                &lookup_helpers_batch,
                 &s_sigmas_batch,
                 betas,
                 gammas,
+                deltas,
+                epsilons,
                 alphas,
                 &z_h_on_coset,
             );
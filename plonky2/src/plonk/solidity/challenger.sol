@@ -0,0 +1,62 @@
+/// A duplex sponge in overwrite mode, mirroring plonky2's `Challenger`.
+library ChallengerLib {
+    uint256 constant SPONGE_RATE = 8;
+
+    struct Challenger {
+        uint64[12] state;
+        uint64[8] input_buffer;
+        uint256 input_len;
+        uint64[8] output_buffer;
+        uint256 output_len;
+    }
+
+    function duplexing(Challenger memory c) internal pure {
+        for (uint256 i = 0; i < c.input_len; i++) {
+            c.state[i] = c.input_buffer[i];
+        }
+        c.input_len = 0;
+        c.state = HasherLib.permute(c.state);
+        for (uint256 i = 0; i < SPONGE_RATE; i++) {
+            c.output_buffer[i] = c.state[i];
+        }
+        c.output_len = SPONGE_RATE;
+    }
+
+    function observe_element(Challenger memory c, uint64 element) internal pure {
+        // Any buffered outputs are now invalid, since they wouldn't reflect this input.
+        c.output_len = 0;
+        c.input_buffer[c.input_len] = element;
+        c.input_len++;
+        if (c.input_len == SPONGE_RATE) {
+            duplexing(c);
+        }
+    }
+
+    function observe_extension_element(Challenger memory c, uint64[2] memory element) internal pure {
+        observe_element(c, element[0]);
+        observe_element(c, element[1]);
+    }
+
+    function observe_elements(Challenger memory c, uint64[4] memory elements) internal pure {
+        for (uint256 i = 0; i < 4; i++) {
+            observe_element(c, elements[i]);
+        }
+    }
+
+    function observe_hash(Challenger memory c, bytes32 h) internal pure {
+        observe_elements(c, HasherLib.to_elements(h));
+    }
+
+    function get_challenge(Challenger memory c) internal pure returns (uint64) {
+        if (c.input_len > 0 || c.output_len == 0) {
+            duplexing(c);
+        }
+        c.output_len--;
+        return c.output_buffer[c.output_len];
+    }
+
+    function get_extension_challenge(Challenger memory c) internal pure returns (uint64[2] memory res) {
+        res[0] = get_challenge(c);
+        res[1] = get_challenge(c);
+    }
+}
@@ -0,0 +1,42 @@
+library GatesUtilsLib {
+    using GoldilocksExtLib for uint64[2];
+
+    struct EvaluationVars {
+        uint64[2][$NUM_OPENINGS_CONSTANTS] constants;
+        uint64[2][$NUM_OPENINGS_WIRES] wires;
+        uint64[2][4] public_input_hash;
+        uint64[2] filter;
+    }
+
+    function field_ext_from(uint64 a, uint64 b) internal pure returns (uint64[2] memory res) {
+        res[0] = a;
+        res[1] = b;
+    }
+
+    function push(
+        uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints,
+        uint64[2] memory filter,
+        uint32 index,
+        uint64[2] memory value
+    ) internal pure {
+        constraints[index] = constraints[index].add(filter.mul(value));
+    }
+
+    /// Multiplies the extension algebra elements stored in wires `l..l + D` and `r..r + D`.
+    function wires_algebra_mul(uint64[2][$NUM_OPENINGS_WIRES] memory wires, uint32 l, uint32 r)
+        internal
+        pure
+        returns (uint64[2][$D] memory res)
+    {
+        for (uint32 i = 0; i < $D; i++) {
+            for (uint32 j = 0; j < $D; j++) {
+                uint64[2] memory t = wires[l + i].mul(wires[r + j]);
+                if (i + j < $D) {
+                    res[i + j] = res[i + j].add(t);
+                } else {
+                    res[i + j - $D] = res[i + j - $D].add(t.scalar_mul($EXTENSION_W));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+library GoldilocksFieldLib {
+    uint64 constant P = $FIELD_ORDER;
+    uint64 constant POWER_OF_TWO_GENERATOR = $POWER_OF_TWO_GENERATOR;
+    uint256 constant TWO_ADICITY = $TWO_ADICITY;
+
+    function add(uint64 a, uint64 b) internal pure returns (uint64 res) {
+        res = uint64(addmod(a, b, P));
+    }
+
+    function sub(uint64 a, uint64 b) internal pure returns (uint64 res) {
+        res = uint64(addmod(a, P - b, P));
+    }
+
+    function mul(uint64 a, uint64 b) internal pure returns (uint64 res) {
+        res = uint64(mulmod(a, b, P));
+    }
+
+    function square(uint64 a) internal pure returns (uint64 res) {
+        res = uint64(mulmod(a, a, P));
+    }
+
+    function exp(uint64 x, uint256 e) internal pure returns (uint64 res) {
+        uint256 base = x;
+        uint256 acc = 1;
+        while (e > 0) {
+            if ((e & 1) == 1) {
+                acc = mulmod(acc, base, P);
+            }
+            base = mulmod(base, base, P);
+            e >>= 1;
+        }
+        res = uint64(acc);
+    }
+
+    function exp_power_of_2(uint64 x, uint256 power_log) internal pure returns (uint64 res) {
+        res = x;
+        for (uint256 i = 0; i < power_log; i++) {
+            res = square(res);
+        }
+    }
+
+    function inverse(uint64 a) internal pure returns (uint64 res) {
+        require(a != 0, "inverse of zero");
+        res = exp(a, P - 2);
+    }
+
+    function primitive_root_of_unity(uint256 n_log) internal pure returns (uint64 res) {
+        require(n_log <= TWO_ADICITY, "no root of unity of this order");
+        res = exp_power_of_2(POWER_OF_TWO_GENERATOR, TWO_ADICITY - n_log);
+    }
+
+    function reverse_bits(uint256 x, uint256 bits) internal pure returns (uint256 res) {
+        for (uint256 i = 0; i < bits; i++) {
+            res = (res << 1) | (x & 1);
+            x >>= 1;
+        }
+    }
+}
+
+library GoldilocksExtLib {
+    uint64 constant P = $FIELD_ORDER;
+    uint64 constant W = $EXTENSION_W;
+
+    function zero() internal pure returns (uint64[2] memory res) {}
+
+    function one() internal pure returns (uint64[2] memory res) {
+        res[0] = 1;
+    }
+
+    function from_base(uint64 a) internal pure returns (uint64[2] memory res) {
+        res[0] = a;
+    }
+
+    function is_zero(uint64[2] memory a) internal pure returns (bool) {
+        return a[0] == 0 && a[1] == 0;
+    }
+
+    function equal(uint64[2] memory a, uint64[2] memory b) internal pure returns (bool) {
+        return a[0] == b[0] && a[1] == b[1];
+    }
+
+    function add(uint64[2] memory a, uint64[2] memory b) internal pure returns (uint64[2] memory res) {
+        res[0] = uint64(addmod(a[0], b[0], P));
+        res[1] = uint64(addmod(a[1], b[1], P));
+    }
+
+    function sub(uint64[2] memory a, uint64[2] memory b) internal pure returns (uint64[2] memory res) {
+        res[0] = uint64(addmod(a[0], P - b[0], P));
+        res[1] = uint64(addmod(a[1], P - b[1], P));
+    }
+
+    function neg(uint64[2] memory a) internal pure returns (uint64[2] memory res) {
+        res[0] = uint64(addmod(0, P - a[0], P));
+        res[1] = uint64(addmod(0, P - a[1], P));
+    }
+
+    function mul(uint64[2] memory a, uint64[2] memory b) internal pure returns (uint64[2] memory res) {
+        res[0] = uint64(addmod(mulmod(a[0], b[0], P), mulmod(W, mulmod(a[1], b[1], P), P), P));
+        res[1] = uint64(addmod(mulmod(a[0], b[1], P), mulmod(a[1], b[0], P), P));
+    }
+
+    function scalar_mul(uint64[2] memory a, uint64 s) internal pure returns (uint64[2] memory res) {
+        res[0] = uint64(mulmod(a[0], s, P));
+        res[1] = uint64(mulmod(a[1], s, P));
+    }
+
+    function square(uint64[2] memory a) internal pure returns (uint64[2] memory res) {
+        res = mul(a, a);
+    }
+
+    function exp(uint64[2] memory x, uint256 e) internal pure returns (uint64[2] memory res) {
+        res = one();
+        uint64[2] memory base = x;
+        while (e > 0) {
+            if ((e & 1) == 1) {
+                res = mul(res, base);
+            }
+            base = square(base);
+            e >>= 1;
+        }
+    }
+
+    function exp_power_of_2(uint64[2] memory x, uint256 power_log) internal pure returns (uint64[2] memory res) {
+        res = x;
+        for (uint256 i = 0; i < power_log; i++) {
+            res = square(res);
+        }
+    }
+
+    /// (a0 + a1 X)^-1 = (a0 - a1 X) / (a0^2 - W a1^2), since X^2 = W.
+    function inverse(uint64[2] memory a) internal pure returns (uint64[2] memory res) {
+        uint256 norm = addmod(mulmod(a[0], a[0], P), P - mulmod(W, mulmod(a[1], a[1], P), P), P);
+        uint64 norm_inv = GoldilocksFieldLib.inverse(uint64(norm));
+        res[0] = uint64(mulmod(a[0], norm_inv, P));
+        res[1] = uint64(mulmod(P - a[1], norm_inv, P));
+    }
+
+    function div(uint64[2] memory a, uint64[2] memory b) internal pure returns (uint64[2] memory res) {
+        res = mul(a, inverse(b));
+    }
+}
@@ -0,0 +1,78 @@
+/// Merkle tree and challenger hashing with Keccak-256 truncated to 25 bytes. A digest is stored
+/// left-aligned in a `bytes32`, and takes `HASH_SIZE` bytes in the proof.
+library HasherLib {
+    uint64 constant P = $FIELD_ORDER;
+    uint256 constant HASH_SIZE = 25;
+
+    function reverse_bytes(uint64 x) internal pure returns (uint64) {
+        x = ((x & 0xFF00FF00FF00FF00) >> 8) | ((x & 0x00FF00FF00FF00FF) << 8);
+        x = ((x & 0xFFFF0000FFFF0000) >> 16) | ((x & 0x0000FFFF0000FFFF) << 16);
+        return (x >> 32) | (x << 32);
+    }
+
+    /// Encodes the elements as little-endian `uint64`s, as the Rust prover serializes them.
+    function to_le_bytes(uint64[] memory input) internal pure returns (bytes memory buf) {
+        buf = new bytes(input.length * 8);
+        for (uint256 i = 0; i < input.length; i++) {
+            uint64 le = reverse_bytes(input[i]);
+            assembly {
+                mstore(add(add(buf, 32), mul(i, 8)), shl(192, le))
+            }
+        }
+    }
+
+    function to_elements(bytes32 h) internal pure returns (uint64[4] memory res) {
+        // The 25 hash bytes are split into little-endian chunks of 7 bytes.
+        for (uint256 i = 0; i < HASH_SIZE; i++) {
+            res[i / 7] |= uint64(uint8(h[i])) << uint64(8 * (i % 7));
+        }
+    }
+
+    function read_hash(bytes calldata proof, uint256 pos) internal pure returns (bytes32) {
+        return bytes32(bytes25(proof[pos:pos + HASH_SIZE]));
+    }
+
+    function hash_no_pad(uint64[] memory input) internal pure returns (bytes32) {
+        return bytes32(bytes25(keccak256(to_le_bytes(input))));
+    }
+
+    function hash_or_noop(uint64[] memory input) internal pure returns (bytes32) {
+        if (input.length <= 4) {
+            require(input.length * 8 <= HASH_SIZE, "leaf does not fit in a hash");
+            uint256 res;
+            for (uint256 i = 0; i < input.length; i++) {
+                res |= uint256(reverse_bytes(input[i])) << (192 - 64 * i);
+            }
+            return bytes32(res);
+        }
+        return hash_no_pad(input);
+    }
+
+    function two_to_one(bytes32 left, bytes32 right) internal pure returns (bytes32) {
+        return bytes32(bytes25(keccak256(abi.encodePacked(bytes25(left), bytes25(right)))));
+    }
+
+    /// The Keccak pseudo-permutation: the state is hashed repeatedly, and the little-endian words
+    /// of the outputs which are canonical field elements form the new state.
+    function permute(uint64[12] memory input) internal pure returns (uint64[12] memory res) {
+        uint64[] memory words = new uint64[](12);
+        for (uint256 i = 0; i < 12; i++) {
+            words[i] = input[i];
+        }
+        bytes32 h = keccak256(to_le_bytes(words));
+        uint256 n = 0;
+        while (true) {
+            for (uint256 k = 0; k < 4 && n < 12; k++) {
+                uint64 word = reverse_bytes(uint64(uint256(h) >> (192 - 64 * k)));
+                if (word < P) {
+                    res[n] = word;
+                    n++;
+                }
+            }
+            if (n == 12) {
+                break;
+            }
+            h = keccak256(abi.encodePacked(h));
+        }
+    }
+}
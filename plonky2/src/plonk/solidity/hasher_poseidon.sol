@@ -0,0 +1,47 @@
+/// Merkle tree and challenger hashing with Poseidon. A digest is stored in a `bytes32` holding its
+/// four elements as big-endian `uint64`s, which is also how it is laid out in the proof.
+library HasherLib {
+    uint64 constant P = $FIELD_ORDER;
+    uint256 constant HASH_SIZE = 32;
+
+    function from_elements(uint64[4] memory elements) internal pure returns (bytes32) {
+        return bytes32(
+            (uint256(elements[0]) << 192) | (uint256(elements[1]) << 128) | (uint256(elements[2]) << 64)
+                | uint256(elements[3])
+        );
+    }
+
+    function to_elements(bytes32 h) internal pure returns (uint64[4] memory res) {
+        for (uint256 i = 0; i < 4; i++) {
+            res[i] = uint64(uint256(h) >> (192 - 64 * i));
+        }
+    }
+
+    function read_hash(bytes calldata proof, uint256 pos) internal pure returns (bytes32 h) {
+        h = bytes32(proof[pos:pos + HASH_SIZE]);
+        uint64[4] memory elements = to_elements(h);
+        for (uint256 i = 0; i < 4; i++) {
+            require(elements[i] < P, "non-canonical hash element");
+        }
+    }
+
+    function hash_or_noop(uint64[] memory input) internal pure returns (bytes32) {
+        uint64[4] memory elements;
+        if (input.length <= 4) {
+            for (uint256 i = 0; i < input.length; i++) {
+                elements[i] = input[i];
+            }
+        } else {
+            elements = PoseidonLib.hash_no_pad(input);
+        }
+        return from_elements(elements);
+    }
+
+    function two_to_one(bytes32 left, bytes32 right) internal pure returns (bytes32) {
+        return from_elements(PoseidonLib.compress(to_elements(left), to_elements(right)));
+    }
+
+    function permute(uint64[12] memory state) internal pure returns (uint64[12] memory) {
+        return PoseidonLib.permute(state);
+    }
+}
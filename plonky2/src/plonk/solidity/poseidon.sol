@@ -0,0 +1,87 @@
+library PoseidonLib {
+    uint64 constant P = $FIELD_ORDER;
+    uint256 constant SPONGE_RATE = 8;
+    uint256 constant HALF_N_FULL_ROUNDS = $HALF_N_FULL_ROUNDS;
+    uint256 constant N_PARTIAL_ROUNDS = $N_PARTIAL_ROUNDS;
+    uint256 constant N_ROUNDS = $N_ROUNDS;
+
+    function all_round_constants() internal pure returns (uint64[$NUM_ROUND_CONSTANTS] memory rc) {
+        $SET_ALL_ROUND_CONSTANTS
+    }
+
+    function mds_matrix_circ() internal pure returns (uint64[12] memory mds) {
+        $SET_MDS_MATRIX_CIRC
+    }
+
+    function mds_matrix_diag() internal pure returns (uint64[12] memory mds) {
+        $SET_MDS_MATRIX_DIAG
+    }
+
+    function sbox_monomial(uint64 x) internal pure returns (uint64) {
+        uint256 x2 = mulmod(x, x, P);
+        uint256 x4 = mulmod(x2, x2, P);
+        uint256 x3 = mulmod(x2, x, P);
+        return uint64(mulmod(x3, x4, P));
+    }
+
+    function mds_layer(uint64[12] memory state, uint64[12] memory circ, uint64[12] memory diag)
+        internal
+        pure
+        returns (uint64[12] memory res)
+    {
+        for (uint256 r = 0; r < 12; r++) {
+            uint256 acc = mulmod(state[r], diag[r], P);
+            for (uint256 i = 0; i < 12; i++) {
+                acc = addmod(acc, mulmod(state[(i + r) % 12], circ[i], P), P);
+            }
+            res[r] = uint64(acc);
+        }
+    }
+
+    function permute(uint64[12] memory input) internal pure returns (uint64[12] memory state) {
+        uint64[$NUM_ROUND_CONSTANTS] memory rc = all_round_constants();
+        uint64[12] memory circ = mds_matrix_circ();
+        uint64[12] memory diag = mds_matrix_diag();
+        for (uint256 i = 0; i < 12; i++) {
+            state[i] = input[i];
+        }
+        for (uint256 r = 0; r < N_ROUNDS; r++) {
+            for (uint256 i = 0; i < 12; i++) {
+                state[i] = uint64(addmod(state[i], rc[12 * r + i], P));
+            }
+            if (r < HALF_N_FULL_ROUNDS || r >= HALF_N_FULL_ROUNDS + N_PARTIAL_ROUNDS) {
+                for (uint256 i = 0; i < 12; i++) {
+                    state[i] = sbox_monomial(state[i]);
+                }
+            } else {
+                state[0] = sbox_monomial(state[0]);
+            }
+            state = mds_layer(state, circ, diag);
+        }
+    }
+
+    function hash_no_pad(uint64[] memory input) internal pure returns (uint64[4] memory res) {
+        uint64[12] memory state;
+        for (uint256 i = 0; i < input.length; i += SPONGE_RATE) {
+            for (uint256 j = 0; j < SPONGE_RATE && i + j < input.length; j++) {
+                state[j] = input[i + j];
+            }
+            state = permute(state);
+        }
+        for (uint256 i = 0; i < 4; i++) {
+            res[i] = state[i];
+        }
+    }
+
+    function compress(uint64[4] memory left, uint64[4] memory right) internal pure returns (uint64[4] memory res) {
+        uint64[12] memory state;
+        for (uint256 i = 0; i < 4; i++) {
+            state[i] = left[i];
+            state[i + 4] = right[i];
+        }
+        state = permute(state);
+        for (uint256 i = 0; i < 4; i++) {
+            res[i] = state[i];
+        }
+    }
+}
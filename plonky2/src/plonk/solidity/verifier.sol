@@ -0,0 +1,601 @@
+contract Plonky2Verifier {
+    using GoldilocksFieldLib for uint64;
+    using GoldilocksExtLib for uint64[2];
+    using ChallengerLib for ChallengerLib.Challenger;
+
+    uint64 constant P = $FIELD_ORDER;
+    uint64 constant MULTIPLICATIVE_GROUP_GENERATOR = $MULTIPLICATIVE_GROUP_GENERATOR;
+    uint64 constant LDE_ROOT = $LDE_ROOT;
+    uint64 constant ZETA_NEXT_GENERATOR_0 = $ZETA_NEXT_GENERATOR_0;
+    uint64 constant ZETA_NEXT_GENERATOR_1 = $ZETA_NEXT_GENERATOR_1;
+    bytes32 constant CIRCUIT_DIGEST = $CIRCUIT_DIGEST;
+    uint256 constant HASH_SIZE = $HASH_SIZE;
+
+    uint256 constant NUM_PUBLIC_INPUTS = $NUM_PUBLIC_INPUTS;
+    uint256 constant NUM_CHALLENGES = $NUM_CHALLENGES;
+    uint256 constant NUM_ROUTED_WIRES = $NUM_ROUTED_WIRES;
+    uint256 constant NUM_PARTIAL_PRODUCTS = $NUM_PARTIAL_PRODUCTS;
+    uint256 constant QUOTIENT_DEGREE_FACTOR = $QUOTIENT_DEGREE_FACTOR;
+    uint256 constant NUM_GATE_CONSTRAINTS = $NUM_GATE_CONSTRAINTS;
+    uint256 constant NUM_VANISHING_TERMS = NUM_CHALLENGES * (NUM_PARTIAL_PRODUCTS + 2) + NUM_GATE_CONSTRAINTS;
+    uint256 constant DEGREE_BITS = $DEGREE_BITS;
+    uint256 constant DEGREE = uint256(1) << DEGREE_BITS;
+
+    uint256 constant NUM_PREPROCESSED_POLYS = $NUM_PREPROCESSED_POLYS;
+    uint256 constant NUM_WIRE_POLYS = $NUM_WIRE_POLYS;
+    uint256 constant NUM_ZS_PARTIAL_PRODUCTS_POLYS = $NUM_ZS_PARTIAL_PRODUCTS_POLYS;
+    uint256 constant NUM_QUOTIENT_POLYS = $NUM_QUOTIENT_POLYS;
+    uint256 constant SALT_SIZE = $SALT_SIZE;
+
+    uint256 constant LDE_BITS = $LDE_BITS;
+    uint256 constant LDE_SIZE = uint256(1) << LDE_BITS;
+    uint256 constant NUM_CAP_ELEMENTS = $NUM_CAP_ELEMENTS;
+    uint256 constant NUM_INITIAL_SIBLINGS = $NUM_INITIAL_SIBLINGS;
+    uint256 constant NUM_FRI_QUERIES = $NUM_FRI_QUERIES;
+    uint256 constant NUM_FRI_STEPS = $NUM_FRI_STEPS;
+    uint256 constant NUM_FINAL_POLY_COEFFS = $NUM_FINAL_POLY_COEFFS;
+    uint256 constant POW_BITS = $POW_BITS;
+
+    // Byte offsets of each part of the proof. Field elements take 8 bytes and extension field
+    // elements 16 bytes, both big-endian; hashes take `HASH_SIZE` bytes.
+    uint256 constant PROOF_SIZE = $PROOF_SIZE;
+    uint256 constant WIRES_CAP_OFFSET = $WIRES_CAP_OFFSET;
+    uint256 constant ZS_PARTIAL_PRODUCTS_CAP_OFFSET = $ZS_PARTIAL_PRODUCTS_CAP_OFFSET;
+    uint256 constant QUOTIENT_POLYS_CAP_OFFSET = $QUOTIENT_POLYS_CAP_OFFSET;
+    uint256 constant NUM_OPENINGS = $NUM_OPENINGS;
+    uint256 constant CONSTANTS_OFFSET = $CONSTANTS_OFFSET;
+    uint256 constant SIGMAS_OFFSET = $SIGMAS_OFFSET;
+    uint256 constant WIRES_OFFSET = $WIRES_OFFSET;
+    uint256 constant ZS_OFFSET = $ZS_OFFSET;
+    uint256 constant PARTIAL_PRODUCTS_OFFSET = $PARTIAL_PRODUCTS_OFFSET;
+    uint256 constant QUOTIENT_POLYS_OFFSET = $QUOTIENT_POLYS_OFFSET;
+    uint256 constant ZS_NEXT_OFFSET = $ZS_NEXT_OFFSET;
+    uint256 constant COMMIT_PHASE_CAPS_OFFSET = $COMMIT_PHASE_CAPS_OFFSET;
+    uint256 constant QUERY_ROUNDS_OFFSET = $QUERY_ROUNDS_OFFSET;
+    uint256 constant QUERY_ROUND_SIZE = $QUERY_ROUND_SIZE;
+    uint256 constant INITIAL_TREES_SIZE = $INITIAL_TREES_SIZE;
+    uint256 constant FINAL_POLY_OFFSET = $FINAL_POLY_OFFSET;
+    uint256 constant POW_WITNESS_OFFSET = $POW_WITNESS_OFFSET;
+
+    struct ProofChallenges {
+        uint64[] plonk_betas;
+        uint64[] plonk_gammas;
+        uint64[] plonk_alphas;
+        uint64[2] plonk_zeta;
+        uint64[2] fri_alpha;
+        uint64[2][] fri_betas;
+        uint64 fri_pow_response;
+        uint256[] fri_query_indices;
+    }
+
+    struct FriContext {
+        uint64[2] zeta;
+        uint64[2] zeta_next;
+        uint64[2] reduced_openings_zeta;
+        uint64[2] reduced_openings_zeta_next;
+    }
+
+    struct QueryState {
+        uint256 x_index;
+        uint64 x;
+        uint64[2] old_eval;
+        uint256 pos;
+    }
+
+    function constants_sigmas_cap() internal pure returns (bytes32[$NUM_CAP_ELEMENTS] memory cap) {
+        $SET_CONSTANTS_SIGMAS_CAP
+    }
+
+    function get_k_is() internal pure returns (uint64[$NUM_ROUTED_WIRES] memory k_is) {
+        $SET_K_IS
+    }
+
+    function fri_step(uint256 i) internal pure returns (uint256 arity_bits, uint256 num_siblings) {
+        $SET_FRI_STEPS
+        revert("invalid FRI step");
+    }
+
+    function read_field(bytes calldata proof, uint256 pos) internal pure returns (uint64 res) {
+        res = uint64(bytes8(proof[pos:pos + 8]));
+        require(res < P, "non-canonical field element");
+    }
+
+    function read_ext(bytes calldata proof, uint256 pos) internal pure returns (uint64[2] memory res) {
+        res[0] = read_field(proof, pos);
+        res[1] = read_field(proof, pos + 8);
+    }
+
+    function read_leaf(bytes calldata proof, uint256 pos, uint256 len) internal pure returns (uint64[] memory leaf) {
+        leaf = new uint64[](len);
+        for (uint256 i = 0; i < len; i++) {
+            leaf[i] = read_field(proof, pos + 8 * i);
+        }
+    }
+
+    /// Verifies a proof, given as the bytes produced by `encode_solidity_proof`. Reverts if the
+    /// proof is invalid.
+    function verify(bytes calldata proof) external pure returns (bool) {
+        require(proof.length == PROOF_SIZE, "invalid proof size");
+        uint64[4] memory public_inputs_hash = hash_public_inputs(proof);
+        ProofChallenges memory challenges = get_challenges(proof, public_inputs_hash);
+        verify_vanishing_poly(proof, challenges, public_inputs_hash);
+        verify_fri_proof(proof, challenges);
+        return true;
+    }
+
+    function hash_public_inputs(bytes calldata proof) internal pure returns (uint64[4] memory) {
+        uint64[] memory public_inputs = read_leaf(proof, 0, NUM_PUBLIC_INPUTS);
+        return PoseidonLib.hash_no_pad(public_inputs);
+    }
+
+    function observe_cap(ChallengerLib.Challenger memory challenger, bytes calldata proof, uint256 pos)
+        internal
+        pure
+    {
+        for (uint256 i = 0; i < NUM_CAP_ELEMENTS; i++) {
+            challenger.observe_hash(HasherLib.read_hash(proof, pos + i * HASH_SIZE));
+        }
+    }
+
+    function get_challenges(bytes calldata proof, uint64[4] memory public_inputs_hash)
+        internal
+        pure
+        returns (ProofChallenges memory challenges)
+    {
+        ChallengerLib.Challenger memory challenger;
+
+        // Observe the instance.
+        challenger.observe_hash(CIRCUIT_DIGEST);
+        challenger.observe_elements(public_inputs_hash);
+
+        challenges.plonk_betas = new uint64[](NUM_CHALLENGES);
+        challenges.plonk_gammas = new uint64[](NUM_CHALLENGES);
+        challenges.plonk_alphas = new uint64[](NUM_CHALLENGES);
+
+        observe_cap(challenger, proof, WIRES_CAP_OFFSET);
+        for (uint256 i = 0; i < NUM_CHALLENGES; i++) {
+            challenges.plonk_betas[i] = challenger.get_challenge();
+        }
+        for (uint256 i = 0; i < NUM_CHALLENGES; i++) {
+            challenges.plonk_gammas[i] = challenger.get_challenge();
+        }
+
+        observe_cap(challenger, proof, ZS_PARTIAL_PRODUCTS_CAP_OFFSET);
+        for (uint256 i = 0; i < NUM_CHALLENGES; i++) {
+            challenges.plonk_alphas[i] = challenger.get_challenge();
+        }
+
+        observe_cap(challenger, proof, QUOTIENT_POLYS_CAP_OFFSET);
+        challenges.plonk_zeta = challenger.get_extension_challenge();
+
+        // The openings at zeta are followed by the openings at g * zeta in the proof.
+        for (uint256 i = 0; i < NUM_OPENINGS; i++) {
+            challenger.observe_extension_element(read_ext(proof, CONSTANTS_OFFSET + 16 * i));
+        }
+
+        get_fri_challenges(challenger, proof, challenges);
+    }
+
+    function get_fri_challenges(
+        ChallengerLib.Challenger memory challenger,
+        bytes calldata proof,
+        ProofChallenges memory challenges
+    ) internal pure {
+        // Scaling factor to combine polynomials.
+        challenges.fri_alpha = challenger.get_extension_challenge();
+
+        // Recover the random betas used in the FRI reductions.
+        challenges.fri_betas = new uint64[2][](NUM_FRI_STEPS);
+        for (uint256 i = 0; i < NUM_FRI_STEPS; i++) {
+            observe_cap(challenger, proof, COMMIT_PHASE_CAPS_OFFSET + i * NUM_CAP_ELEMENTS * HASH_SIZE);
+            challenges.fri_betas[i] = challenger.get_extension_challenge();
+        }
+
+        for (uint256 i = 0; i < NUM_FINAL_POLY_COEFFS; i++) {
+            challenger.observe_extension_element(read_ext(proof, FINAL_POLY_OFFSET + 16 * i));
+        }
+
+        challenger.observe_element(read_field(proof, POW_WITNESS_OFFSET));
+        challenges.fri_pow_response = challenger.get_challenge();
+
+        challenges.fri_query_indices = new uint256[](NUM_FRI_QUERIES);
+        for (uint256 i = 0; i < NUM_FRI_QUERIES; i++) {
+            challenges.fri_query_indices[i] = uint256(challenger.get_challenge()) % LDE_SIZE;
+        }
+    }
+
+    function eval_l_0(uint64[2] memory x) internal pure returns (uint64[2] memory) {
+        if (x.equal(GoldilocksExtLib.one())) {
+            // The formula below would divide by zero.
+            return GoldilocksExtLib.one();
+        }
+        // L_0(x) = (x^n - 1) / (n * (x - 1))
+        return x.exp_power_of_2(DEGREE_BITS).sub(GoldilocksExtLib.one()).div(
+            x.sub(GoldilocksExtLib.one()).scalar_mul(uint64(DEGREE))
+        );
+    }
+
+    function evaluate_gate_constraints(bytes calldata proof, uint64[4] memory public_inputs_hash)
+        internal
+        pure
+        returns (uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints)
+    {
+        GatesUtilsLib.EvaluationVars memory ev;
+        for (uint256 i = 0; i < $NUM_OPENINGS_CONSTANTS; i++) {
+            ev.constants[i] = read_ext(proof, CONSTANTS_OFFSET + 16 * i);
+        }
+        for (uint256 i = 0; i < $NUM_OPENINGS_WIRES; i++) {
+            ev.wires[i] = read_ext(proof, WIRES_OFFSET + 16 * i);
+        }
+        for (uint256 i = 0; i < 4; i++) {
+            ev.public_input_hash[i] = GoldilocksExtLib.from_base(public_inputs_hash[i]);
+        }
+
+        $EVALUATE_GATE_CONSTRAINTS
+    }
+
+    /// The products of the numerators and denominators of the permutation argument over the `k`-th
+    /// chunk of routed wires.
+    function partial_product_chunk(bytes calldata proof, ProofChallenges memory challenges, uint256 i, uint256 k)
+        internal
+        pure
+        returns (uint64[2] memory numerator, uint64[2] memory denominator)
+    {
+        uint64[$NUM_ROUTED_WIRES] memory k_is = get_k_is();
+        numerator = GoldilocksExtLib.one();
+        denominator = GoldilocksExtLib.one();
+        uint256 end = (k + 1) * QUOTIENT_DEGREE_FACTOR;
+        if (end > NUM_ROUTED_WIRES) {
+            end = NUM_ROUTED_WIRES;
+        }
+        for (uint256 j = k * QUOTIENT_DEGREE_FACTOR; j < end; j++) {
+            uint64[2] memory wire = read_ext(proof, WIRES_OFFSET + 16 * j).add(
+                GoldilocksExtLib.from_base(challenges.plonk_gammas[i])
+            );
+            numerator = numerator.mul(
+                wire.add(challenges.plonk_zeta.scalar_mul(k_is[j]).scalar_mul(challenges.plonk_betas[i]))
+            );
+            denominator = denominator.mul(
+                wire.add(read_ext(proof, SIGMAS_OFFSET + 16 * j).scalar_mul(challenges.plonk_betas[i]))
+            );
+        }
+    }
+
+    /// Checks the relationship between the accumulators before and after the `k`-th chunk of
+    /// routed wires: `prev * numerator - next * denominator`.
+    function partial_product_check(bytes calldata proof, ProofChallenges memory challenges, uint256 i, uint256 k)
+        internal
+        pure
+        returns (uint64[2] memory)
+    {
+        uint64[2] memory prev = k == 0
+            ? read_ext(proof, ZS_OFFSET + 16 * i)
+            : read_ext(proof, PARTIAL_PRODUCTS_OFFSET + 16 * (i * NUM_PARTIAL_PRODUCTS + k - 1));
+        uint64[2] memory next = k == NUM_PARTIAL_PRODUCTS
+            ? read_ext(proof, ZS_NEXT_OFFSET + 16 * i)
+            : read_ext(proof, PARTIAL_PRODUCTS_OFFSET + 16 * (i * NUM_PARTIAL_PRODUCTS + k));
+        (uint64[2] memory numerator, uint64[2] memory denominator) = partial_product_chunk(proof, challenges, i, k);
+        return prev.mul(numerator).sub(next.mul(denominator));
+    }
+
+    function vanishing_terms(bytes calldata proof, ProofChallenges memory challenges, uint64[4] memory public_inputs_hash)
+        internal
+        pure
+        returns (uint64[2][] memory terms)
+    {
+        terms = new uint64[2][](NUM_VANISHING_TERMS);
+
+        // The L_0(x) (Z(x) - 1) vanishing terms, followed by the terms checking the partial products.
+        uint64[2] memory l_0 = eval_l_0(challenges.plonk_zeta);
+        for (uint256 i = 0; i < NUM_CHALLENGES; i++) {
+            terms[i] = l_0.mul(read_ext(proof, ZS_OFFSET + 16 * i).sub(GoldilocksExtLib.one()));
+            for (uint256 k = 0; k <= NUM_PARTIAL_PRODUCTS; k++) {
+                terms[NUM_CHALLENGES + i * (NUM_PARTIAL_PRODUCTS + 1) + k] =
+                    partial_product_check(proof, challenges, i, k);
+            }
+        }
+
+        uint64[2][$NUM_GATE_CONSTRAINTS] memory constraints = evaluate_gate_constraints(proof, public_inputs_hash);
+        for (uint256 i = 0; i < NUM_GATE_CONSTRAINTS; i++) {
+            terms[NUM_CHALLENGES * (NUM_PARTIAL_PRODUCTS + 2) + i] = constraints[i];
+        }
+    }
+
+    function verify_vanishing_poly(bytes calldata proof, ProofChallenges memory challenges, uint64[4] memory public_inputs_hash)
+        internal
+        pure
+    {
+        uint64[2][] memory terms = vanishing_terms(proof, challenges, public_inputs_hash);
+        uint64[2] memory zeta_pow_deg = challenges.plonk_zeta.exp_power_of_2(DEGREE_BITS);
+        uint64[2] memory z_h_zeta = zeta_pow_deg.sub(GoldilocksExtLib.one());
+
+        // Check each polynomial identity, of the form `vanishing(x) = Z_H(x) quotient(x)`, at zeta.
+        for (uint256 i = 0; i < NUM_CHALLENGES; i++) {
+            uint64[2] memory vanishing;
+            for (uint256 j = terms.length; j > 0; j--) {
+                vanishing = vanishing.scalar_mul(challenges.plonk_alphas[i]).add(terms[j - 1]);
+            }
+            // The quotient is split into chunks of degree n, which are combined with powers of zeta^n.
+            uint64[2] memory quotient;
+            for (uint256 j = QUOTIENT_DEGREE_FACTOR; j > 0; j--) {
+                quotient = quotient.mul(zeta_pow_deg).add(
+                    read_ext(proof, QUOTIENT_POLYS_OFFSET + 16 * (i * QUOTIENT_DEGREE_FACTOR + j - 1))
+                );
+            }
+            require(vanishing.equal(z_h_zeta.mul(quotient)), "vanishing polynomial check failed");
+        }
+    }
+
+    function merkle_root(
+        bytes calldata proof,
+        uint64[] memory leaf,
+        uint256 index,
+        uint256 siblings_pos,
+        uint256 num_siblings
+    ) internal pure returns (bytes32 digest) {
+        digest = HasherLib.hash_or_noop(leaf);
+        for (uint256 i = 0; i < num_siblings; i++) {
+            bytes32 sibling = HasherLib.read_hash(proof, siblings_pos + i * HASH_SIZE);
+            if ((index & 1) == 1) {
+                digest = HasherLib.two_to_one(sibling, digest);
+            } else {
+                digest = HasherLib.two_to_one(digest, sibling);
+            }
+            index >>= 1;
+        }
+    }
+
+    function initial_tree_num_polys(uint256 oracle) internal pure returns (uint256) {
+        if (oracle == 0) return NUM_PREPROCESSED_POLYS;
+        if (oracle == 1) return NUM_WIRE_POLYS;
+        if (oracle == 2) return NUM_ZS_PARTIAL_PRODUCTS_POLYS;
+        return NUM_QUOTIENT_POLYS;
+    }
+
+    /// The constants and sigmas are not blinded, so only the other oracles are salted.
+    function initial_tree_leaf_len(uint256 oracle) internal pure returns (uint256) {
+        return initial_tree_num_polys(oracle) + (oracle == 0 ? 0 : SALT_SIZE);
+    }
+
+    /// The position of the leaf of `oracle` within a query round.
+    function initial_tree_pos(uint256 oracle) internal pure returns (uint256 pos) {
+        for (uint256 k = 0; k < oracle; k++) {
+            pos += 8 * initial_tree_leaf_len(k) + NUM_INITIAL_SIBLINGS * HASH_SIZE;
+        }
+    }
+
+    function initial_cap(bytes calldata proof, uint256 oracle, uint256 i) internal pure returns (bytes32) {
+        if (oracle == 0) {
+            return constants_sigmas_cap()[i];
+        }
+        return HasherLib.read_hash(proof, WIRES_CAP_OFFSET + ((oracle - 1) * NUM_CAP_ELEMENTS + i) * HASH_SIZE);
+    }
+
+    function verify_initial_trees(bytes calldata proof, uint256 pos, uint256 x_index) internal pure {
+        for (uint256 oracle = 0; oracle < 4; oracle++) {
+            uint256 leaf_len = initial_tree_leaf_len(oracle);
+            bytes32 root = merkle_root(
+                proof, read_leaf(proof, pos, leaf_len), x_index, pos + 8 * leaf_len, NUM_INITIAL_SIBLINGS
+            );
+            require(
+                root == initial_cap(proof, oracle, x_index >> NUM_INITIAL_SIBLINGS), "invalid initial Merkle proof"
+            );
+            pos += 8 * leaf_len + NUM_INITIAL_SIBLINGS * HASH_SIZE;
+        }
+    }
+
+    /// Continues the Horner evaluation `acc` with the first `len` elements of a leaf, in reverse order.
+    function reduce_leaf(bytes calldata proof, uint256 pos, uint256 len, uint64[2] memory alpha, uint64[2] memory acc)
+        internal
+        pure
+        returns (uint64[2] memory res)
+    {
+        res = acc;
+        for (uint256 i = len; i > 0; i--) {
+            res = res.mul(alpha).add(GoldilocksExtLib.from_base(read_field(proof, pos + 8 * (i - 1))));
+        }
+    }
+
+    function reduce_openings(bytes calldata proof, uint256 pos, uint256 len, uint64[2] memory alpha)
+        internal
+        pure
+        returns (uint64[2] memory res)
+    {
+        for (uint256 i = len; i > 0; i--) {
+            res = res.mul(alpha).add(read_ext(proof, pos + 16 * (i - 1)));
+        }
+    }
+
+    function fri_context(bytes calldata proof, ProofChallenges memory challenges)
+        internal
+        pure
+        returns (FriContext memory ctx)
+    {
+        ctx.zeta = challenges.plonk_zeta;
+        ctx.zeta_next = challenges.plonk_zeta.mul(
+            GatesUtilsLib.field_ext_from(ZETA_NEXT_GENERATOR_0, ZETA_NEXT_GENERATOR_1)
+        );
+        ctx.reduced_openings_zeta =
+            reduce_openings(proof, CONSTANTS_OFFSET, NUM_OPENINGS - NUM_CHALLENGES, challenges.fri_alpha);
+        ctx.reduced_openings_zeta_next = reduce_openings(proof, ZS_NEXT_OFFSET, NUM_CHALLENGES, challenges.fri_alpha);
+    }
+
+    function fri_combine_initial(
+        bytes calldata proof,
+        ProofChallenges memory challenges,
+        FriContext memory ctx,
+        uint256 pos,
+        uint64 x
+    ) internal pure returns (uint64[2] memory sum) {
+        uint64[2] memory alpha = challenges.fri_alpha;
+        uint64[2] memory x_ext = GoldilocksExtLib.from_base(x);
+
+        // All polynomials are opened at zeta.
+        uint64[2] memory reduced;
+        for (uint256 oracle = 4; oracle > 0; oracle--) {
+            reduced = reduce_leaf(
+                proof, pos + initial_tree_pos(oracle - 1), initial_tree_num_polys(oracle - 1), alpha, reduced
+            );
+        }
+        sum = reduced.sub(ctx.reduced_openings_zeta).div(x_ext.sub(ctx.zeta));
+
+        // The Z polynomials are also opened at g * zeta.
+        reduced = reduce_leaf(proof, pos + initial_tree_pos(2), NUM_CHALLENGES, alpha, GoldilocksExtLib.zero());
+        sum = alpha.exp(NUM_CHALLENGES).mul(sum).add(
+            reduced.sub(ctx.reduced_openings_zeta_next).div(x_ext.sub(ctx.zeta_next))
+        );
+
+        // Multiply the final polynomial by `X`, so that `final_poly` has the maximum degree for
+        // which the LDT will pass.
+        sum = sum.scalar_mul(x);
+    }
+
+    /// Computes `l(beta) * sum`, where `l(X) = X^arity - coset_start^arity` vanishes on the coset,
+    /// and `sum` already includes the numerators of the barycentric weights.
+    function barycentric_scale(uint64[2] memory sum, uint64[2] memory beta, uint64 coset_start, uint256 arity_bits)
+        internal
+        pure
+        returns (uint64[2] memory res)
+    {
+        uint64 start_pow_arity = coset_start.exp(uint256(1) << arity_bits);
+        uint64 denominator = uint64(uint256(1) << arity_bits).mul(start_pow_arity);
+        res = beta.exp(uint256(1) << arity_bits).sub(GoldilocksExtLib.from_base(start_pow_arity)).mul(sum).scalar_mul(
+            denominator.inverse()
+        );
+    }
+
+    /// Interpolates `{(coset_start * g^i, values[i])}` and evaluates it at `beta`. The barycentric
+    /// weight of the point `x_i` is `x_i / (arity * coset_start^arity)`.
+    function interpolate_coset(
+        uint64[2][] memory values,
+        uint64 coset_start,
+        uint64 g,
+        uint256 arity_bits,
+        uint64[2] memory beta
+    ) internal pure returns (uint64[2] memory res) {
+        uint64[2] memory sum;
+        uint64 point = coset_start;
+        for (uint256 i = 0; i < values.length; i++) {
+            uint64[2] memory diff = beta.sub(GoldilocksExtLib.from_base(point));
+            if (diff.is_zero()) {
+                return values[i];
+            }
+            sum = sum.add(values[i].scalar_mul(point).div(diff));
+            point = point.mul(g);
+        }
+        res = barycentric_scale(sum, beta, coset_start, arity_bits);
+    }
+
+    /// Computes P'(x^arity) from {P(x*g^i)}_(i=0..arity), where g is a `arity`-th root of unity
+    /// and P' is the FRI reduced polynomial.
+    function compute_evaluation(
+        bytes calldata proof,
+        uint256 pos,
+        uint64 x,
+        uint256 x_index_within_coset,
+        uint256 arity_bits,
+        uint64[2] memory beta
+    ) internal pure returns (uint64[2] memory res) {
+        // The evaluations are committed in bit-reversed order.
+        uint64[2][] memory values = new uint64[2][](uint256(1) << arity_bits);
+        for (uint256 i = 0; i < values.length; i++) {
+            values[i] = read_ext(proof, pos + 16 * GoldilocksFieldLib.reverse_bits(i, arity_bits));
+        }
+        uint64 g = GoldilocksFieldLib.primitive_root_of_unity(arity_bits);
+        uint256 rev_x_index_within_coset = GoldilocksFieldLib.reverse_bits(x_index_within_coset, arity_bits);
+        uint64 coset_start = x.mul(g.exp((uint256(1) << arity_bits) - rev_x_index_within_coset));
+        res = interpolate_coset(values, coset_start, g, arity_bits, beta);
+    }
+
+    function verify_fri_step_merkle_proof(
+        bytes calldata proof,
+        QueryState memory qs,
+        uint256 i,
+        uint256 arity_bits,
+        uint256 num_siblings
+    ) internal pure {
+        uint256 arity = uint256(1) << arity_bits;
+        uint256 coset_index = qs.x_index >> arity_bits;
+        uint64[] memory leaf = new uint64[](2 * arity);
+        for (uint256 j = 0; j < arity; j++) {
+            uint64[2] memory value = read_ext(proof, qs.pos + 16 * j);
+            leaf[2 * j] = value[0];
+            leaf[2 * j + 1] = value[1];
+        }
+        bytes32 root = merkle_root(proof, leaf, coset_index, qs.pos + 16 * arity, num_siblings);
+        require(
+            root
+                == HasherLib.read_hash(
+                    proof, COMMIT_PHASE_CAPS_OFFSET + (i * NUM_CAP_ELEMENTS + (coset_index >> num_siblings)) * HASH_SIZE
+                ),
+            "invalid FRI Merkle proof"
+        );
+    }
+
+    function verify_fri_query_step(bytes calldata proof, ProofChallenges memory challenges, QueryState memory qs, uint256 i)
+        internal
+        pure
+    {
+        (uint256 arity_bits, uint256 num_siblings) = fri_step(i);
+        uint256 x_index_within_coset = qs.x_index & ((uint256(1) << arity_bits) - 1);
+
+        // Check consistency with our old evaluation from the previous round.
+        require(
+            read_ext(proof, qs.pos + 16 * x_index_within_coset).equal(qs.old_eval), "inconsistent FRI evaluation"
+        );
+
+        // Infer P(y) from {P(x)}_{x^arity=y}.
+        qs.old_eval = compute_evaluation(proof, qs.pos, qs.x, x_index_within_coset, arity_bits, challenges.fri_betas[i]);
+        verify_fri_step_merkle_proof(proof, qs, i, arity_bits, num_siblings);
+
+        // Update the point x to x^arity.
+        qs.x = qs.x.exp_power_of_2(arity_bits);
+        qs.x_index >>= arity_bits;
+        qs.pos += 16 * (uint256(1) << arity_bits) + num_siblings * HASH_SIZE;
+    }
+
+    function eval_final_poly(bytes calldata proof, uint64 x) internal pure returns (uint64[2] memory res) {
+        for (uint256 i = NUM_FINAL_POLY_COEFFS; i > 0; i--) {
+            res = res.scalar_mul(x).add(read_ext(proof, FINAL_POLY_OFFSET + 16 * (i - 1)));
+        }
+    }
+
+    function verify_fri_query_round(
+        bytes calldata proof,
+        ProofChallenges memory challenges,
+        FriContext memory ctx,
+        uint256 round
+    ) internal pure {
+        QueryState memory qs;
+        qs.pos = QUERY_ROUNDS_OFFSET + round * QUERY_ROUND_SIZE;
+        qs.x_index = challenges.fri_query_indices[round];
+        verify_initial_trees(proof, qs.pos, qs.x_index);
+
+        // `x` is the point of the LDE domain at `x_index`, with the domain in bit-reversed order.
+        qs.x = GoldilocksFieldLib.mul(
+            MULTIPLICATIVE_GROUP_GENERATOR,
+            GoldilocksFieldLib.exp(LDE_ROOT, GoldilocksFieldLib.reverse_bits(qs.x_index, LDE_BITS))
+        );
+
+        // `old_eval` is the last derived evaluation; it will be checked for consistency with its
+        // committed "parent" value in the next iteration.
+        qs.old_eval = fri_combine_initial(proof, challenges, ctx, qs.pos, qs.x);
+        qs.pos += INITIAL_TREES_SIZE;
+
+        for (uint256 i = 0; i < NUM_FRI_STEPS; i++) {
+            verify_fri_query_step(proof, challenges, qs, i);
+        }
+
+        // Final check of FRI. After all the reductions, we check that the final polynomial is equal
+        // to the one sent by the prover.
+        require(eval_final_poly(proof, qs.x).equal(qs.old_eval), "final polynomial evaluation is invalid");
+    }
+
+    function verify_fri_proof(bytes calldata proof, ProofChallenges memory challenges) internal pure {
+        require((challenges.fri_pow_response >> (64 - POW_BITS)) == 0, "invalid proof of work witness");
+        FriContext memory ctx = fri_context(proof, challenges);
+        for (uint256 round = 0; round < NUM_FRI_QUERIES; round++) {
+            verify_fri_query_round(proof, challenges, ctx, round);
+        }
+    }
+}
@@ -0,0 +1,770 @@
+//! Export of a Solidity contract verifying the proofs of a given circuit, together with the
+//! encoding of proofs into the calldata layout it expects.
+//!
+//! Only the configurations used on-chain are supported: the Goldilocks field with its quadratic
+//! extension, and either Poseidon or Keccak-256 truncated to 25 bytes for Merkle trees.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+use anyhow::{bail, ensure, Result};
+use hashbrown::HashMap;
+use keccak_hash::keccak;
+
+use crate::field::extension::{Extendable, FieldExtension};
+use crate::field::goldilocks_field::GoldilocksField;
+use crate::field::types::{Field, Field64};
+use crate::hash::hash_types::RichField;
+use crate::hash::hashing::SPONGE_WIDTH;
+use crate::hash::keccak::KeccakHash;
+use crate::hash::poseidon::{self, Poseidon, PoseidonHash, ALL_ROUND_CONSTANTS};
+use crate::plonk::circuit_data::{CommonCircuitData, VerifierOnlyCircuitData};
+use crate::plonk::config::{GenericConfig, GenericHashOut, Hasher};
+use crate::plonk::plonk_common::{salt_size, PlonkOracle};
+use crate::plonk::proof::ProofWithPublicInputs;
+use crate::plonk::validate_shape::validate_proof_with_pis_shape;
+
+const GOLDILOCKS_TEMPLATE: &str = include_str!("solidity/goldilocks.sol");
+const POSEIDON_TEMPLATE: &str = include_str!("solidity/poseidon.sol");
+const HASHER_POSEIDON_TEMPLATE: &str = include_str!("solidity/hasher_poseidon.sol");
+const HASHER_KECCAK_TEMPLATE: &str = include_str!("solidity/hasher_keccak.sol");
+const CHALLENGER_TEMPLATE: &str = include_str!("solidity/challenger.sol");
+const GATES_UTILS_TEMPLATE: &str = include_str!("solidity/gates_utils.sol");
+const VERIFIER_TEMPLATE: &str = include_str!("solidity/verifier.sol");
+
+/// Indentation of the statements substituted into the body of a Solidity function.
+const STATEMENT_INDENT: &str = "\n        ";
+
+/// The Merkle tree hashers supported by the Solidity verifier. The challenger and the public
+/// inputs hash always use Poseidon, which is the only `AlgebraicHasher`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum SolidityHasher {
+    Poseidon,
+    Keccak,
+}
+
+impl SolidityHasher {
+    fn of<F: RichField, H: Hasher<F> + 'static>() -> Result<Self> {
+        if TypeId::of::<H>() == TypeId::of::<PoseidonHash>() {
+            Ok(Self::Poseidon)
+        } else if TypeId::of::<H>() == TypeId::of::<KeccakHash<25>>() {
+            Ok(Self::Keccak)
+        } else {
+            bail!("The Solidity verifier only supports Poseidon and 25-byte Keccak Merkle trees.")
+        }
+    }
+
+    /// The number of bytes taken by a hash in the proof.
+    fn hash_size(self) -> usize {
+        match self {
+            Self::Poseidon => 32,
+            Self::Keccak => 25,
+        }
+    }
+
+    fn template(self) -> &'static str {
+        match self {
+            Self::Poseidon => HASHER_POSEIDON_TEMPLATE,
+            Self::Keccak => HASHER_KECCAK_TEMPLATE,
+        }
+    }
+
+    /// Encodes a hash as laid out in the proof: Poseidon digests as four big-endian `u64`s, and
+    /// Keccak digests as their raw bytes.
+    fn encode_hash<F: RichField, HO: GenericHashOut<F>>(self, hash: &HO) -> Vec<u8> {
+        match self {
+            Self::Poseidon => hash
+                .to_vec()
+                .into_iter()
+                .flat_map(|x| x.to_canonical_u64().to_be_bytes())
+                .collect(),
+            Self::Keccak => hash.to_bytes(),
+        }
+    }
+
+    /// A `bytes32` literal holding the hash, left-aligned.
+    fn hash_literal<F: RichField, HO: GenericHashOut<F>>(self, hash: &HO) -> String {
+        let mut bytes = self.encode_hash(hash);
+        bytes.resize(32, 0);
+        bytes
+            .iter()
+            .fold("0x".to_string(), |acc, b| acc + &format!("{b:02x}"))
+    }
+}
+
+/// Byte offsets of each part of a proof, as encoded by `encode_solidity_proof`.
+struct ProofLayout {
+    wires_cap: usize,
+    zs_partial_products_cap: usize,
+    quotient_polys_cap: usize,
+    constants: usize,
+    sigmas: usize,
+    wires: usize,
+    zs: usize,
+    partial_products: usize,
+    quotient_polys: usize,
+    zs_next: usize,
+    num_openings: usize,
+    commit_phase_caps: usize,
+    query_rounds: usize,
+    query_round_size: usize,
+    initial_trees_size: usize,
+    final_poly: usize,
+    pow_witness: usize,
+    size: usize,
+    /// The number of siblings in the Merkle proofs of the initial trees.
+    num_initial_siblings: usize,
+    /// The arity bits and number of Merkle proof siblings of each FRI reduction step.
+    steps: Vec<(usize, usize)>,
+}
+
+impl ProofLayout {
+    fn new<F: RichField + Extendable<D>, const D: usize>(
+        common_data: &CommonCircuitData<F, D>,
+        hash_size: usize,
+    ) -> Self {
+        let config = &common_data.config;
+        let fri_params = &common_data.fri_params;
+        let cap_height = fri_params.config.cap_height;
+        let cap_size = fri_params.config.num_cap_elements() * hash_size;
+        let ext_size = 8 * D;
+
+        let mut offset = 8 * common_data.num_public_inputs;
+        let mut next = |len: usize| {
+            let start = offset;
+            offset += len;
+            start
+        };
+
+        let wires_cap = next(cap_size);
+        let zs_partial_products_cap = next(cap_size);
+        let quotient_polys_cap = next(cap_size);
+
+        let constants = next(ext_size * common_data.num_constants);
+        let sigmas = next(ext_size * config.num_routed_wires);
+        let wires = next(ext_size * config.num_wires);
+        let zs = next(ext_size * config.num_challenges);
+        let partial_products =
+            next(ext_size * config.num_challenges * common_data.num_partial_products);
+        let quotient_polys = next(ext_size * common_data.num_quotient_polys());
+        let zs_next = next(ext_size * config.num_challenges);
+        let num_openings = (next(0) - constants) / ext_size;
+
+        let commit_phase_caps = next(cap_size * fri_params.reduction_arity_bits.len());
+
+        let num_initial_siblings = fri_params.lde_bits() - cap_height;
+        let initial_trees_size = Self::initial_tree_leaf_lens(common_data)
+            .into_iter()
+            .map(|leaf_len| 8 * leaf_len + num_initial_siblings * hash_size)
+            .sum::<usize>();
+        let mut codeword_bits = fri_params.lde_bits();
+        let steps = fri_params
+            .reduction_arity_bits
+            .iter()
+            .map(|&arity_bits| {
+                codeword_bits -= arity_bits;
+                (arity_bits, codeword_bits - cap_height)
+            })
+            .collect::<Vec<_>>();
+        let query_round_size = initial_trees_size
+            + steps
+                .iter()
+                .map(|&(arity_bits, num_siblings)| {
+                    ext_size * (1 << arity_bits) + num_siblings * hash_size
+                })
+                .sum::<usize>();
+        let query_rounds = next(query_round_size * fri_params.config.num_query_rounds);
+
+        let final_poly = next(ext_size * fri_params.final_poly_len());
+        let pow_witness = next(8);
+        let size = next(0);
+
+        Self {
+            wires_cap,
+            zs_partial_products_cap,
+            quotient_polys_cap,
+            constants,
+            sigmas,
+            wires,
+            zs,
+            partial_products,
+            quotient_polys,
+            zs_next,
+            num_openings,
+            commit_phase_caps,
+            query_rounds,
+            query_round_size,
+            initial_trees_size,
+            final_poly,
+            pow_witness,
+            size,
+            num_initial_siblings,
+            steps,
+        }
+    }
+
+    /// The length of the leaves of each initial tree, including salts.
+    fn initial_tree_leaf_lens<F: RichField + Extendable<D>, const D: usize>(
+        common_data: &CommonCircuitData<F, D>,
+    ) -> [usize; 4] {
+        let hiding = common_data.fri_params.hiding;
+        [
+            common_data.num_preprocessed_polys()
+                + salt_size(hiding && PlonkOracle::CONSTANTS_SIGMAS.blinding),
+            common_data.config.num_wires + salt_size(hiding && PlonkOracle::WIRES.blinding),
+            common_data.num_zs_partial_products_polys()
+                + salt_size(hiding && PlonkOracle::ZS_PARTIAL_PRODUCTS.blinding),
+            common_data.num_quotient_polys() + salt_size(hiding && PlonkOracle::QUOTIENT.blinding),
+        ]
+    }
+}
+
+fn check_supported_config<F, C, const D: usize>(
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<SolidityHasher>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    ensure!(
+        D == 2,
+        "The Solidity verifier only supports quadratic extensions."
+    );
+    ensure!(
+        F::ORDER == GoldilocksField::ORDER,
+        "The Solidity verifier only supports the Goldilocks field."
+    );
+    ensure!(
+        !common_data.has_lookups(),
+        "The Solidity verifier does not support lookups."
+    );
+    SolidityHasher::of::<F, C::Hasher>()
+}
+
+/// Replaces each `$NAME` placeholder in `template` by its value in `values`.
+pub(crate) fn fill_template(template: &str, values: &HashMap<&str, String>) -> Result<String> {
+    let mut res = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('$') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        match values.get(name) {
+            Some(value) => res.push_str(value),
+            None => bail!("Unknown placeholder ${} in template.", name),
+        }
+        rest = &rest[end..];
+    }
+    res.push_str(rest);
+    Ok(res)
+}
+
+/// Joins statements to be substituted into the body of a Solidity function.
+fn statements(statements: impl IntoIterator<Item = String>) -> String {
+    statements
+        .into_iter()
+        .map(|s| s + ";")
+        .collect::<Vec<_>>()
+        .join(STATEMENT_INDENT)
+}
+
+/// Computes the filter of a gate, mirroring `compute_filter`.
+fn filter_statements<F: RichField + Extendable<D>, const D: usize>(
+    common_data: &CommonCircuitData<F, D>,
+    row: usize,
+) -> String {
+    let selectors_info = &common_data.selectors_info;
+    let selector_index = selectors_info.selector_indices[row];
+    let factors = selectors_info.groups[selector_index]
+        .clone()
+        .filter(|&i| i != row)
+        .chain((selectors_info.num_selectors() > 1).then_some(u32::MAX as usize));
+    // The template provides the semicolon ending the last statement.
+    core::iter::once("ev.filter = GoldilocksExtLib.one()".to_string())
+        .chain(factors.map(|i| {
+            format!(
+                "ev.filter = GoldilocksExtLib.mul(ev.filter, GoldilocksExtLib.sub(GatesUtilsLib.field_ext_from({i}, 0), ev.constants[{selector_index}]))"
+            )
+        }))
+        .collect::<Vec<_>>()
+        .join(&(";".to_string() + STATEMENT_INDENT))
+}
+
+/// Renames the library declared by a gate's Solidity code if its name is already taken.
+fn gate_library(code: String, names: &mut Vec<String>) -> Result<(String, String)> {
+    let start = match code.find("library ") {
+        Some(start) => start + "library ".len(),
+        None => bail!("Solidity gate code does not declare a library."),
+    };
+    let len = code[start..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(code.len() - start);
+    let name = code[start..start + len].to_string();
+    let mut unique_name = name.clone();
+    let mut suffix = 1;
+    while names.contains(&unique_name) {
+        unique_name = format!("{name}_{suffix}");
+        suffix += 1;
+    }
+    names.push(unique_name.clone());
+    let code = code[..start].to_string() + &unique_name + &code[start + len..];
+    Ok((unique_name, code))
+}
+
+/// Generates a Solidity contract, `Plonky2Verifier`, whose `verify(bytes)` method checks proofs
+/// of the circuit described by `verifier_only` and `common_data`. Proofs are passed in the format
+/// produced by `encode_solidity_proof`.
+pub fn export_solidity_verifier<F, C, const D: usize>(
+    verifier_only: &VerifierOnlyCircuitData<C, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<String>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    let hasher = check_supported_config::<F, C, D>(common_data)?;
+    let hash_size = hasher.hash_size();
+    let layout = ProofLayout::new(common_data, hash_size);
+    let config = &common_data.config;
+    let fri_params = &common_data.fri_params;
+
+    let mut values = HashMap::<&str, String>::new();
+    let mut set = |name, value: String| values.insert(name, value);
+
+    // Field and hash constants.
+    set("FIELD_ORDER", F::ORDER.to_string());
+    set(
+        "POWER_OF_TWO_GENERATOR",
+        F::POWER_OF_TWO_GENERATOR.to_string(),
+    );
+    set("TWO_ADICITY", F::TWO_ADICITY.to_string());
+    set(
+        "MULTIPLICATIVE_GROUP_GENERATOR",
+        F::MULTIPLICATIVE_GROUP_GENERATOR.to_string(),
+    );
+    set("EXTENSION_W", <F as Extendable<D>>::W.to_string());
+    set("D", D.to_string());
+    set("HASH_SIZE", hash_size.to_string());
+    set(
+        "HALF_N_FULL_ROUNDS",
+        poseidon::HALF_N_FULL_ROUNDS.to_string(),
+    );
+    set("N_PARTIAL_ROUNDS", poseidon::N_PARTIAL_ROUNDS.to_string());
+    set("N_ROUNDS", poseidon::N_ROUNDS.to_string());
+    let num_round_constants = SPONGE_WIDTH * poseidon::N_ROUNDS;
+    set("NUM_ROUND_CONSTANTS", num_round_constants.to_string());
+    set(
+        "SET_ALL_ROUND_CONSTANTS",
+        statements(
+            ALL_ROUND_CONSTANTS[..num_round_constants]
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("rc[{i}] = {c}")),
+        ),
+    );
+    set(
+        "SET_MDS_MATRIX_CIRC",
+        statements(
+            <F as Poseidon>::MDS_MATRIX_CIRC
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("mds[{i}] = {c}")),
+        ),
+    );
+    set(
+        "SET_MDS_MATRIX_DIAG",
+        statements(
+            <F as Poseidon>::MDS_MATRIX_DIAG
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("mds[{i}] = {c}")),
+        ),
+    );
+
+    // Circuit constants.
+    set(
+        "CIRCUIT_DIGEST",
+        hasher.hash_literal(&verifier_only.circuit_digest),
+    );
+    set(
+        "SET_CONSTANTS_SIGMAS_CAP",
+        statements(
+            verifier_only
+                .constants_sigmas_cap
+                .0
+                .iter()
+                .enumerate()
+                .map(|(i, h)| format!("cap[{i}] = {}", hasher.hash_literal(h))),
+        ),
+    );
+    set(
+        "SET_K_IS",
+        statements(
+            common_data.k_is[..config.num_routed_wires]
+                .iter()
+                .enumerate()
+                .map(|(i, k)| format!("k_is[{i}] = {k}")),
+        ),
+    );
+    set(
+        "NUM_PUBLIC_INPUTS",
+        common_data.num_public_inputs.to_string(),
+    );
+    set("NUM_CHALLENGES", config.num_challenges.to_string());
+    set("NUM_ROUTED_WIRES", config.num_routed_wires.to_string());
+    set(
+        "NUM_PARTIAL_PRODUCTS",
+        common_data.num_partial_products.to_string(),
+    );
+    set(
+        "QUOTIENT_DEGREE_FACTOR",
+        common_data.quotient_degree_factor.to_string(),
+    );
+    set(
+        "NUM_GATE_CONSTRAINTS",
+        common_data.num_gate_constraints.to_string(),
+    );
+    set(
+        "NUM_SELECTORS",
+        common_data.selectors_info.num_selectors().to_string(),
+    );
+    set(
+        "NUM_OPENINGS_CONSTANTS",
+        common_data.num_constants.to_string(),
+    );
+    set("NUM_OPENINGS_WIRES", config.num_wires.to_string());
+    set("DEGREE_BITS", common_data.degree_bits().to_string());
+    let zeta_next_generator =
+        F::Extension::primitive_root_of_unity(common_data.degree_bits()).to_basefield_array();
+    set("ZETA_NEXT_GENERATOR_0", zeta_next_generator[0].to_string());
+    set("ZETA_NEXT_GENERATOR_1", zeta_next_generator[1].to_string());
+
+    // FRI parameters.
+    set("LDE_BITS", fri_params.lde_bits().to_string());
+    set(
+        "LDE_ROOT",
+        F::primitive_root_of_unity(fri_params.lde_bits()).to_string(),
+    );
+    set(
+        "NUM_CAP_ELEMENTS",
+        fri_params.config.num_cap_elements().to_string(),
+    );
+    set(
+        "NUM_FRI_QUERIES",
+        fri_params.config.num_query_rounds.to_string(),
+    );
+    set("NUM_FRI_STEPS", layout.steps.len().to_string());
+    set(
+        "NUM_FINAL_POLY_COEFFS",
+        fri_params.final_poly_len().to_string(),
+    );
+    set("POW_BITS", fri_params.config.proof_of_work_bits.to_string());
+    set(
+        "SET_FRI_STEPS",
+        layout
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, (arity_bits, num_siblings))| {
+                format!("if (i == {i}) return ({arity_bits}, {num_siblings});")
+            })
+            .collect::<Vec<_>>()
+            .join(STATEMENT_INDENT),
+    );
+    set(
+        "NUM_PREPROCESSED_POLYS",
+        common_data.num_preprocessed_polys().to_string(),
+    );
+    set("NUM_WIRE_POLYS", config.num_wires.to_string());
+    set(
+        "NUM_ZS_PARTIAL_PRODUCTS_POLYS",
+        common_data.num_zs_partial_products_polys().to_string(),
+    );
+    set(
+        "NUM_QUOTIENT_POLYS",
+        common_data.num_quotient_polys().to_string(),
+    );
+    // Only the oracles other than the constants and sigmas are salted.
+    set("SALT_SIZE", salt_size(fri_params.hiding).to_string());
+    set(
+        "NUM_INITIAL_SIBLINGS",
+        layout.num_initial_siblings.to_string(),
+    );
+
+    // Proof layout.
+    set("PROOF_SIZE", layout.size.to_string());
+    set("WIRES_CAP_OFFSET", layout.wires_cap.to_string());
+    set(
+        "ZS_PARTIAL_PRODUCTS_CAP_OFFSET",
+        layout.zs_partial_products_cap.to_string(),
+    );
+    set(
+        "QUOTIENT_POLYS_CAP_OFFSET",
+        layout.quotient_polys_cap.to_string(),
+    );
+    set("NUM_OPENINGS", layout.num_openings.to_string());
+    set("CONSTANTS_OFFSET", layout.constants.to_string());
+    set("SIGMAS_OFFSET", layout.sigmas.to_string());
+    set("WIRES_OFFSET", layout.wires.to_string());
+    set("ZS_OFFSET", layout.zs.to_string());
+    set(
+        "PARTIAL_PRODUCTS_OFFSET",
+        layout.partial_products.to_string(),
+    );
+    set("QUOTIENT_POLYS_OFFSET", layout.quotient_polys.to_string());
+    set("ZS_NEXT_OFFSET", layout.zs_next.to_string());
+    set(
+        "COMMIT_PHASE_CAPS_OFFSET",
+        layout.commit_phase_caps.to_string(),
+    );
+    set("QUERY_ROUNDS_OFFSET", layout.query_rounds.to_string());
+    set("QUERY_ROUND_SIZE", layout.query_round_size.to_string());
+    set("INITIAL_TREES_SIZE", layout.initial_trees_size.to_string());
+    set("FINAL_POLY_OFFSET", layout.final_poly.to_string());
+    set("POW_WITNESS_OFFSET", layout.pow_witness.to_string());
+
+    // Gate constraints. Each gate's filter is computed before its constraints are evaluated.
+    let mut gate_names = Vec::new();
+    let mut gate_libraries = Vec::new();
+    let mut gate_calls = Vec::new();
+    for (row, gate) in common_data.gates.iter().enumerate() {
+        let mut gate_values = values.clone();
+        gate_values.insert("SET_FILTER", filter_statements(common_data, row));
+        let code = fill_template(&gate.0.export_solidity_verification_code(), &gate_values)?;
+        let (name, code) = gate_library(code, &mut gate_names)?;
+        gate_libraries.push(code);
+        gate_calls.push(format!("{name}.set_filter(ev)"));
+        gate_calls.push(format!("{name}.eval(ev, constraints)"));
+    }
+    values.insert("EVALUATE_GATE_CONSTRAINTS", statements(gate_calls));
+
+    let templates = [
+        GOLDILOCKS_TEMPLATE,
+        POSEIDON_TEMPLATE,
+        hasher.template(),
+        CHALLENGER_TEMPLATE,
+        GATES_UTILS_TEMPLATE,
+    ];
+    let mut contract =
+        "// SPDX-License-Identifier: MIT OR Apache-2.0\npragma solidity ^0.8.9;\n".to_string();
+    for template in templates {
+        contract += "\n";
+        contract += &fill_template(template, &values)?;
+    }
+    for library in gate_libraries {
+        contract += "\n";
+        contract += &library;
+        contract += "\n";
+    }
+    contract += "\n";
+    contract += &fill_template(VERIFIER_TEMPLATE, &values)?;
+    Ok(contract)
+}
+
+/// Encodes a proof in the layout expected by the `verify` method of the contract generated by
+/// `export_solidity_verifier`. Field elements are encoded as 8-byte big-endian integers.
+pub fn encode_solidity_proof<F, C, const D: usize>(
+    proof_with_pis: &ProofWithPublicInputs<F, C, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<Vec<u8>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    let hasher = check_supported_config::<F, C, D>(common_data)?;
+    validate_proof_with_pis_shape(proof_with_pis, common_data)?;
+    let layout = ProofLayout::new(common_data, hasher.hash_size());
+
+    let mut buffer = Vec::with_capacity(layout.size);
+    let write_field = |buffer: &mut Vec<u8>, x: &F| {
+        buffer.extend_from_slice(&x.to_canonical_u64().to_be_bytes());
+    };
+    let write_field_ext = |buffer: &mut Vec<u8>, x: &F::Extension| {
+        for c in x.to_basefield_array() {
+            write_field(buffer, &c);
+        }
+    };
+
+    let ProofWithPublicInputs {
+        proof,
+        public_inputs,
+    } = proof_with_pis;
+    for x in public_inputs {
+        write_field(&mut buffer, x);
+    }
+
+    for cap in [
+        &proof.wires_cap,
+        &proof.plonk_zs_partial_products_cap,
+        &proof.quotient_polys_cap,
+    ] {
+        for h in &cap.0 {
+            buffer.extend(hasher.encode_hash(h));
+        }
+    }
+
+    let openings = &proof.openings;
+    for x in [
+        &openings.constants,
+        &openings.plonk_sigmas,
+        &openings.wires,
+        &openings.plonk_zs,
+        &openings.partial_products,
+        &openings.quotient_polys,
+        &openings.plonk_zs_next,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        write_field_ext(&mut buffer, x);
+    }
+
+    let fri_proof = &proof.opening_proof;
+    ensure!(
+        fri_proof.commit_phase_merkle_caps.len() == layout.steps.len(),
+        "Number of FRI reduction steps doesn't match circuit data."
+    );
+    for cap in &fri_proof.commit_phase_merkle_caps {
+        for h in &cap.0 {
+            buffer.extend(hasher.encode_hash(h));
+        }
+    }
+
+    for round in &fri_proof.query_round_proofs {
+        for (evals, merkle_proof) in &round.initial_trees_proof.evals_proofs {
+            for x in evals {
+                write_field(&mut buffer, x);
+            }
+            for h in &merkle_proof.siblings {
+                buffer.extend(hasher.encode_hash(h));
+            }
+        }
+        for step in &round.steps {
+            for x in &step.evals {
+                write_field_ext(&mut buffer, x);
+            }
+            for h in &step.merkle_proof.siblings {
+                buffer.extend(hasher.encode_hash(h));
+            }
+        }
+    }
+
+    for x in &fri_proof.final_poly.coeffs {
+        write_field_ext(&mut buffer, x);
+    }
+    write_field(&mut buffer, &fri_proof.pow_witness);
+
+    ensure!(
+        buffer.len() == layout.size,
+        "FRI proof shape doesn't match circuit data."
+    );
+    Ok(buffer)
+}
+
+/// Encodes a call to the `verify(bytes)` method of the contract generated by
+/// `export_solidity_verifier`, following the Solidity ABI.
+pub fn encode_solidity_calldata<F, C, const D: usize>(
+    proof_with_pis: &ProofWithPublicInputs<F, C, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<Vec<u8>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    C::Hasher: 'static,
+{
+    let proof = encode_solidity_proof(proof_with_pis, common_data)?;
+
+    let mut calldata = keccak(b"verify(bytes)").0[..4].to_vec();
+    // The offset of the `bytes` argument, then its length and its contents padded to 32 bytes.
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&32u64.to_be_bytes());
+    calldata.extend_from_slice(&word);
+    word[24..].copy_from_slice(&(proof.len() as u64).to_be_bytes());
+    calldata.extend_from_slice(&word);
+    calldata.extend_from_slice(&proof);
+    calldata.resize(calldata.len() + (32 - proof.len() % 32) % 32, 0);
+    Ok(calldata)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_builder::CircuitBuilder;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+
+    fn test_solidity_verifier_export<C: GenericConfig<2, F = GoldilocksField>>() -> Result<()>
+    where
+        C::Hasher: 'static,
+    {
+        const D: usize = 2;
+        type F = GoldilocksField;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let x = builder.add_virtual_target();
+        let y = builder.add_virtual_target();
+        let z = builder.mul(x, y);
+        let w = builder.add(z, x);
+        builder.register_public_input(x);
+        builder.register_public_input(w);
+        // Add a Poseidon gate.
+        let h = builder.hash_n_to_hash_no_pad::<PoseidonHash>(vec![x, y]);
+        builder.register_public_inputs(&h.elements);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(x, F::from_canonical_u64(3));
+        pw.set_target(y, F::from_canonical_u64(5));
+        let proof = data.prove(pw)?;
+        data.verify(proof.clone())?;
+
+        let verifier_data = data.verifier_data();
+        let contract = verifier_data.export_solidity_verifier()?;
+        assert!(contract.contains("contract Plonky2Verifier"));
+        assert!(contract.contains("library Poseidon12Lib"));
+        assert!(!contract.contains('$'));
+
+        let hash_size = SolidityHasher::of::<F, C::Hasher>()?.hash_size();
+        let layout = ProofLayout::new(&verifier_data.common, hash_size);
+        let encoded = encode_solidity_proof(&proof, &verifier_data.common)?;
+        assert_eq!(encoded.len(), layout.size);
+        assert!(contract.contains(&format!("PROOF_SIZE = {};", encoded.len())));
+
+        let calldata = encode_solidity_calldata(&proof, &verifier_data.common)?;
+        assert_eq!(calldata.len() % 32, 4);
+        assert_eq!(&calldata[4 + 64..4 + 64 + encoded.len()], &encoded[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solidity_verifier_poseidon() -> Result<()> {
+        test_solidity_verifier_export::<PoseidonGoldilocksConfig>()
+    }
+
+    #[test]
+    fn test_solidity_verifier_keccak() -> Result<()> {
+        test_solidity_verifier_export::<KeccakGoldilocksConfig>()
+    }
+
+    #[test]
+    fn test_fill_template() -> Result<()> {
+        let mut values = HashMap::new();
+        values.insert("A", "1".to_string());
+        values.insert("AB", "2".to_string());
+        assert_eq!(fill_template("$A + $AB;", &values)?, "1 + 2;");
+        assert!(fill_template("$ABC", &values).is_err());
+        Ok(())
+    }
+}
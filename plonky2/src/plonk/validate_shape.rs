@@ -51,6 +51,9 @@ where
         plonk_zs,
         plonk_zs_next,
         partial_products,
+        lookup_zs,
+        lookup_zs_next,
+        lookup_helpers,
         quotient_polys,
     } = openings;
     let cap_height = common_data.fri_params.config.cap_height;
@@ -63,6 +66,9 @@ where
     ensure!(plonk_zs.len() == config.num_challenges);
     ensure!(plonk_zs_next.len() == config.num_challenges);
     ensure!(partial_products.len() == config.num_challenges * common_data.num_partial_products);
+    ensure!(lookup_zs.len() == common_data.lookup_zs_range().len());
+    ensure!(lookup_zs_next.len() == common_data.lookup_zs_range().len());
+    ensure!(lookup_helpers.len() == common_data.lookup_helpers_range().len());
     ensure!(quotient_polys.len() == common_data.num_quotient_polys());
     Ok(())
 }
@@ -1,10 +1,13 @@
 use alloc::vec::Vec;
 use alloc::{format, vec};
+use core::ops::Range;
 
 use crate::field::batch_util::batch_add_inplace;
 use crate::field::extension::{Extendable, FieldExtension};
 use crate::field::types::Field;
 use crate::field::zero_poly_coset::ZeroPolyOnCoset;
+use crate::gates::gate::{compute_filter, compute_filter_circuit};
+use crate::gates::lookup::{LookupTerm, LOOKUP_CHUNK_SIZE};
 use crate::hash::hash_types::RichField;
 use crate::iop::ext_target::ExtensionTarget;
 use crate::iop::target::Target;
@@ -13,7 +16,9 @@ use crate::plonk::circuit_data::CommonCircuitData;
 use crate::plonk::config::GenericConfig;
 use crate::plonk::plonk_common;
 use crate::plonk::plonk_common::eval_l_0_circuit;
-use crate::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBaseBatch};
+use crate::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+};
 use crate::util::partial_products::{check_partial_products, check_partial_products_circuit};
 use crate::util::reducing::ReducingFactorTarget;
 use crate::util::strided_view::PackedStridedView;
@@ -33,20 +38,29 @@ pub(crate) fn eval_vanishing_poly<
     local_zs: &[F::Extension],
     next_zs: &[F::Extension],
     partial_products: &[F::Extension],
+    local_lookup_zs: &[F::Extension],
+    next_lookup_zs: &[F::Extension],
+    lookup_helpers: &[F::Extension],
     s_sigmas: &[F::Extension],
     betas: &[F],
     gammas: &[F],
+    deltas: &[F],
+    epsilons: &[F],
     alphas: &[F],
 ) -> Vec<F::Extension> {
     let max_degree = common_data.quotient_degree_factor;
     let num_prods = common_data.num_partial_products;
+    let num_helpers = common_data.num_lookup_helpers();
 
     let constraint_terms = evaluate_gate_constraints::<F, C, D>(common_data, vars);
+    let gate_lookups = evaluate_gate_lookups(common_data, vars);
 
     // The L_0(x) (Z(x) - 1) vanishing terms.
     let mut vanishing_z_1_terms = Vec::new();
     // The terms checking the partial products.
     let mut vanishing_partial_products_terms = Vec::new();
+    // The terms checking the lookup argument.
+    let mut vanishing_lookup_terms = Vec::new();
 
     let l_0_x = plonk_common::eval_l_0(common_data.degree(), x);
 
@@ -83,11 +97,23 @@ pub(crate) fn eval_vanishing_poly<
             max_degree,
         );
         vanishing_partial_products_terms.extend(partial_product_checks);
+
+        if common_data.has_lookups() {
+            vanishing_lookup_terms.extend(check_lookups(
+                &gate_lookups,
+                local_lookup_zs[i],
+                next_lookup_zs[i],
+                &lookup_helpers[i * num_helpers..(i + 1) * num_helpers],
+                deltas[i].into(),
+                epsilons[i].into(),
+            ));
+        }
     }
 
     let vanishing_terms = [
         vanishing_z_1_terms,
         vanishing_partial_products_terms,
+        vanishing_lookup_terms,
         constraint_terms,
     ]
     .concat();
@@ -109,9 +135,14 @@ pub(crate) fn eval_vanishing_poly_base_batch<
     local_zs_batch: &[&[F]],
     next_zs_batch: &[&[F]],
     partial_products_batch: &[&[F]],
+    local_lookup_zs_batch: &[&[F]],
+    next_lookup_zs_batch: &[&[F]],
+    lookup_helpers_batch: &[&[F]],
     s_sigmas_batch: &[&[F]],
     betas: &[F],
     gammas: &[F],
+    deltas: &[F],
+    epsilons: &[F],
     alphas: &[F],
     z_h_on_coset: &ZeroPolyOnCoset<F>,
 ) -> Vec<Vec<F>> {
@@ -121,10 +152,14 @@ pub(crate) fn eval_vanishing_poly_base_batch<
     assert_eq!(local_zs_batch.len(), n);
     assert_eq!(next_zs_batch.len(), n);
     assert_eq!(partial_products_batch.len(), n);
+    assert_eq!(local_lookup_zs_batch.len(), n);
+    assert_eq!(next_lookup_zs_batch.len(), n);
+    assert_eq!(lookup_helpers_batch.len(), n);
     assert_eq!(s_sigmas_batch.len(), n);
 
     let max_degree = common_data.quotient_degree_factor;
     let num_prods = common_data.num_partial_products;
+    let num_helpers = common_data.num_lookup_helpers();
 
     let num_gate_constraints = common_data.num_gate_constraints;
 
@@ -142,6 +177,12 @@ pub(crate) fn eval_vanishing_poly_base_batch<
     let mut vanishing_z_1_terms = Vec::with_capacity(num_challenges);
     // The terms checking the partial products.
     let mut vanishing_partial_products_terms = Vec::new();
+    // The terms checking the lookup argument.
+    let mut vanishing_lookup_terms = Vec::new();
+
+    // The gates' lookup terms do not depend on the selectors, which we strip once for the batch.
+    let mut lookup_vars_batch = vars_batch;
+    lookup_vars_batch.remove_prefix(common_data.selectors_info.num_selectors());
 
     let mut res_batch: Vec<Vec<F>> = Vec::with_capacity(n);
     for k in 0..n {
@@ -151,9 +192,13 @@ pub(crate) fn eval_vanishing_poly_base_batch<
         let local_zs = local_zs_batch[k];
         let next_zs = next_zs_batch[k];
         let partial_products = partial_products_batch[k];
+        let local_lookup_zs = local_lookup_zs_batch[k];
+        let next_lookup_zs = next_lookup_zs_batch[k];
+        let lookup_helpers = lookup_helpers_batch[k];
         let s_sigmas = s_sigmas_batch[k];
 
         let constraint_terms = PackedStridedView::new(&constraint_terms_batch, n, k);
+        let gate_lookups = evaluate_gate_lookups_base(common_data, vars, lookup_vars_batch.view(k));
 
         let l_0_x = z_h_on_coset.eval_l_0(index, x);
         for i in 0..num_challenges {
@@ -186,6 +231,17 @@ pub(crate) fn eval_vanishing_poly_base_batch<
             );
             vanishing_partial_products_terms.extend(partial_product_checks);
 
+            if common_data.has_lookups() {
+                vanishing_lookup_terms.extend(check_lookups(
+                    &gate_lookups,
+                    local_lookup_zs[i],
+                    next_lookup_zs[i],
+                    &lookup_helpers[i * num_helpers..(i + 1) * num_helpers],
+                    deltas[i],
+                    epsilons[i],
+                ));
+            }
+
             numerator_values.clear();
             denominator_values.clear();
         }
@@ -193,12 +249,14 @@ pub(crate) fn eval_vanishing_poly_base_batch<
         let vanishing_terms = vanishing_z_1_terms
             .iter()
             .chain(vanishing_partial_products_terms.iter())
+            .chain(vanishing_lookup_terms.iter())
             .chain(constraint_terms);
         let res = plonk_common::reduce_with_powers_multi(vanishing_terms, alphas);
         res_batch.push(res);
 
         vanishing_z_1_terms.clear();
         vanishing_partial_products_terms.clear();
+        vanishing_lookup_terms.clear();
     }
     res_batch
 }
@@ -321,24 +379,37 @@ pub(crate) fn eval_vanishing_poly_circuit<
     local_zs: &[ExtensionTarget<D>],
     next_zs: &[ExtensionTarget<D>],
     partial_products: &[ExtensionTarget<D>],
+    local_lookup_zs: &[ExtensionTarget<D>],
+    next_lookup_zs: &[ExtensionTarget<D>],
+    lookup_helpers: &[ExtensionTarget<D>],
     s_sigmas: &[ExtensionTarget<D>],
     betas: &[Target],
     gammas: &[Target],
+    deltas: &[Target],
+    epsilons: &[Target],
     alphas: &[Target],
 ) -> Vec<ExtensionTarget<D>> {
     let max_degree = common_data.quotient_degree_factor;
     let num_prods = common_data.num_partial_products;
+    let num_helpers = common_data.num_lookup_helpers();
 
     let constraint_terms = with_context!(
         builder,
         "evaluate gate constraints",
         evaluate_gate_constraints_circuit::<F, C, D>(builder, common_data, vars,)
     );
+    let gate_lookups = with_context!(
+        builder,
+        "evaluate gate lookups",
+        evaluate_gate_lookups_circuit(builder, common_data, vars)
+    );
 
     // The L_0(x) (Z(x) - 1) vanishing terms.
     let mut vanishing_z_1_terms = Vec::new();
     // The terms checking the partial products.
     let mut vanishing_partial_products_terms = Vec::new();
+    // The terms checking the lookup argument.
+    let mut vanishing_lookup_terms = Vec::new();
 
     let l_0_x = eval_l_0_circuit(builder, common_data.degree(), x, x_pow_deg);
 
@@ -387,11 +458,24 @@ pub(crate) fn eval_vanishing_poly_circuit<
             max_degree,
         );
         vanishing_partial_products_terms.extend(partial_product_checks);
+
+        if common_data.has_lookups() {
+            vanishing_lookup_terms.extend(check_lookups_circuit(
+                builder,
+                &gate_lookups,
+                local_lookup_zs[i],
+                next_lookup_zs[i],
+                &lookup_helpers[i * num_helpers..(i + 1) * num_helpers],
+                deltas[i],
+                epsilons[i],
+            ));
+        }
     }
 
     let vanishing_terms = [
         vanishing_z_1_terms,
         vanishing_partial_products_terms,
+        vanishing_lookup_terms,
         constraint_terms,
     ]
     .concat();
@@ -405,3 +489,228 @@ pub(crate) fn eval_vanishing_poly_circuit<
         })
         .collect()
 }
+
+/// The lookup terms of a gate at some point, along with the gate's filter and its indicator, i.e.
+/// its filter scaled to be one on the gate's rows.
+struct GateLookups<T> {
+    filter: T,
+    indicator: T,
+    terms: Vec<LookupTerm<T>>,
+}
+
+/// Returns the inverse of the value taken by the filter of the gate with index `row` on the gate's
+/// own rows.
+fn filter_normalizer_inv<F: Field>(
+    row: usize,
+    group_range: Range<usize>,
+    many_selectors: bool,
+) -> F {
+    compute_filter(
+        row,
+        group_range,
+        F::from_canonical_usize(row),
+        many_selectors,
+    )
+    .inverse()
+}
+
+fn evaluate_gate_lookups<F: RichField + Extendable<D>, const D: usize>(
+    common_data: &CommonCircuitData<F, D>,
+    vars: EvaluationVars<F, D>,
+) -> Vec<GateLookups<F::Extension>> {
+    let num_selectors = common_data.selectors_info.num_selectors();
+    let mut lookup_vars = vars;
+    lookup_vars.remove_prefix(num_selectors);
+    common_data
+        .gates
+        .iter()
+        .enumerate()
+        .filter(|(_, gate)| gate.0.num_lookups() > 0)
+        .map(|(i, gate)| {
+            let selector_index = common_data.selectors_info.selector_indices[i];
+            let group_range = common_data.selectors_info.groups[selector_index].clone();
+            let filter = compute_filter(
+                i,
+                group_range.clone(),
+                vars.local_constants[selector_index],
+                num_selectors > 1,
+            );
+            let normalizer_inv = filter_normalizer_inv::<F>(i, group_range, num_selectors > 1);
+            GateLookups {
+                filter,
+                indicator: filter.scalar_mul(normalizer_inv),
+                terms: gate.0.eval_lookups(lookup_vars),
+            }
+        })
+        .collect()
+}
+
+/// Like `evaluate_gate_lookups`, but for a point in the base field. `lookup_vars` are the same
+/// variables as `vars`, without the selectors.
+fn evaluate_gate_lookups_base<F: RichField + Extendable<D>, const D: usize>(
+    common_data: &CommonCircuitData<F, D>,
+    vars: EvaluationVarsBase<F>,
+    lookup_vars: EvaluationVarsBase<F>,
+) -> Vec<GateLookups<F>> {
+    let num_selectors = common_data.selectors_info.num_selectors();
+    common_data
+        .gates
+        .iter()
+        .enumerate()
+        .filter(|(_, gate)| gate.0.num_lookups() > 0)
+        .map(|(i, gate)| {
+            let selector_index = common_data.selectors_info.selector_indices[i];
+            let group_range = common_data.selectors_info.groups[selector_index].clone();
+            let filter = compute_filter(
+                i,
+                group_range.clone(),
+                vars.local_constants[selector_index],
+                num_selectors > 1,
+            );
+            let normalizer_inv = filter_normalizer_inv::<F>(i, group_range, num_selectors > 1);
+            GateLookups {
+                filter,
+                indicator: filter * normalizer_inv,
+                terms: gate.0.eval_lookups_base(lookup_vars),
+            }
+        })
+        .collect()
+}
+
+fn evaluate_gate_lookups_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    common_data: &CommonCircuitData<F, D>,
+    vars: EvaluationTargets<D>,
+) -> Vec<GateLookups<ExtensionTarget<D>>> {
+    let num_selectors = common_data.selectors_info.num_selectors();
+    let mut lookup_vars = vars;
+    lookup_vars.remove_prefix(num_selectors);
+    common_data
+        .gates
+        .iter()
+        .enumerate()
+        .filter(|(_, gate)| gate.0.num_lookups() > 0)
+        .map(|(i, gate)| {
+            let selector_index = common_data.selectors_info.selector_indices[i];
+            let group_range = common_data.selectors_info.groups[selector_index].clone();
+            let filter = compute_filter_circuit(
+                builder,
+                i,
+                group_range.clone(),
+                vars.local_constants[selector_index],
+                num_selectors > 1,
+            );
+            let normalizer_inv = filter_normalizer_inv::<F>(i, group_range, num_selectors > 1);
+            GateLookups {
+                filter,
+                indicator: builder.mul_const_extension(normalizer_inv, filter),
+                terms: gate.0.eval_lookups_circuit(builder, lookup_vars),
+            }
+        })
+        .collect()
+}
+
+/// Returns the numerator and denominator of `sum_j m_j / (delta - v_j)`, where `v_j` is the
+/// compression of the `j`th term in the given chunk of `terms`, and `m_j` its multiplicity.
+pub(crate) fn lookup_chunk_fraction<K: Field>(
+    terms: &[LookupTerm<K>],
+    chunk: usize,
+    delta: K,
+    epsilon: K,
+) -> (K, K) {
+    terms
+        .iter()
+        .skip(chunk * LOOKUP_CHUNK_SIZE)
+        .take(LOOKUP_CHUNK_SIZE)
+        .fold((K::ZERO, K::ONE), |(numerator, denominator), term| {
+            let compressed = term.input + epsilon * (term.output + epsilon * term.table);
+            let d = delta - compressed;
+            (
+                numerator * d + term.multiplicity * denominator,
+                denominator * d,
+            )
+        })
+}
+
+fn lookup_chunk_fraction_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    terms: &[LookupTerm<ExtensionTarget<D>>],
+    chunk: usize,
+    delta: ExtensionTarget<D>,
+    epsilon: Target,
+) -> (ExtensionTarget<D>, ExtensionTarget<D>) {
+    let mut numerator = builder.zero_extension();
+    let mut denominator = builder.one_extension();
+    for term in terms
+        .iter()
+        .skip(chunk * LOOKUP_CHUNK_SIZE)
+        .take(LOOKUP_CHUNK_SIZE)
+    {
+        let compressed = builder.scalar_mul_add_extension(epsilon, term.table, term.output);
+        let compressed = builder.scalar_mul_add_extension(epsilon, compressed, term.input);
+        let d = builder.sub_extension(delta, compressed);
+        let m_times_denominator = builder.mul_extension(term.multiplicity, denominator);
+        numerator = builder.mul_add_extension(numerator, d, m_times_denominator);
+        denominator = builder.mul_extension(denominator, d);
+    }
+    (numerator, denominator)
+}
+
+/// Checks the lookup argument for a single challenge. The running sum `Z` accumulates the helper
+/// polynomials on the rows of gates with lookups, and each helper `h_c` must equal the fraction
+/// `sum_j m_j / (delta - v_j)` of the terms in chunk `c` of the gate's lookups. As `Z` is cyclic,
+/// this forces the sum of all fractions over the circuit to be zero.
+fn check_lookups<K: Field>(
+    gate_lookups: &[GateLookups<K>],
+    z_x: K,
+    z_gx: K,
+    helpers: &[K],
+    delta: K,
+    epsilon: K,
+) -> Vec<K> {
+    let indicator: K = gate_lookups.iter().map(|g| g.indicator).sum();
+    let helpers_sum: K = helpers.iter().copied().sum();
+    let mut checks = Vec::with_capacity(1 + helpers.len());
+    checks.push(z_gx - z_x - indicator * helpers_sum);
+    for (c, &h) in helpers.iter().enumerate() {
+        checks.push(
+            gate_lookups
+                .iter()
+                .map(|g| {
+                    let (numerator, denominator) =
+                        lookup_chunk_fraction(&g.terms, c, delta, epsilon);
+                    g.filter * (h * denominator - numerator)
+                })
+                .sum(),
+        );
+    }
+    checks
+}
+
+fn check_lookups_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    gate_lookups: &[GateLookups<ExtensionTarget<D>>],
+    z_x: ExtensionTarget<D>,
+    z_gx: ExtensionTarget<D>,
+    helpers: &[ExtensionTarget<D>],
+    delta: Target,
+    epsilon: Target,
+) -> Vec<ExtensionTarget<D>> {
+    let delta = builder.convert_to_ext(delta);
+    let indicator = builder.add_many_extension(gate_lookups.iter().map(|g| g.indicator));
+    let helpers_sum = builder.add_many_extension(helpers);
+    let z_diff = builder.sub_extension(z_gx, z_x);
+    let mut checks = Vec::with_capacity(1 + helpers.len());
+    checks.push(builder.arithmetic_extension(F::NEG_ONE, F::ONE, indicator, helpers_sum, z_diff));
+    for (c, &h) in helpers.iter().enumerate() {
+        let mut check = builder.zero_extension();
+        for g in gate_lookups {
+            let (numerator, denominator) =
+                lookup_chunk_fraction_circuit(builder, &g.terms, c, delta, epsilon);
+            let diff = builder.mul_sub_extension(h, denominator, numerator);
+            check = builder.mul_add_extension(g.filter, diff, check);
+        }
+        checks.push(check);
+    }
+    checks
+}
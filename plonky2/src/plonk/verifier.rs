@@ -58,6 +58,9 @@ pub(crate) fn verify_with_challenges<
     let next_zs = &proof.openings.plonk_zs_next;
     let s_sigmas = &proof.openings.plonk_sigmas;
     let partial_products = &proof.openings.partial_products;
+    let local_lookup_zs = &proof.openings.lookup_zs;
+    let next_lookup_zs = &proof.openings.lookup_zs_next;
+    let lookup_helpers = &proof.openings.lookup_helpers;
 
     // Evaluate the vanishing polynomial at our challenge point, zeta.
     let vanishing_polys_zeta = eval_vanishing_poly::<F, C, D>(
@@ -67,9 +70,14 @@ pub(crate) fn verify_with_challenges<
         local_zs,
         next_zs,
         partial_products,
+        local_lookup_zs,
+        next_lookup_zs,
+        lookup_helpers,
         s_sigmas,
         &challenges.plonk_betas,
         &challenges.plonk_gammas,
+        &challenges.plonk_deltas,
+        &challenges.plonk_epsilons,
         &challenges.plonk_alphas,
     );
 
@@ -192,6 +192,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             plonk_zs: self.select_vec_ext(b, &os0.plonk_zs, &os1.plonk_zs),
             plonk_zs_next: self.select_vec_ext(b, &os0.plonk_zs_next, &os1.plonk_zs_next),
             partial_products: self.select_vec_ext(b, &os0.partial_products, &os1.partial_products),
+            lookup_zs: self.select_vec_ext(b, &os0.lookup_zs, &os1.lookup_zs),
+            lookup_zs_next: self.select_vec_ext(b, &os0.lookup_zs_next, &os1.lookup_zs_next),
+            lookup_helpers: self.select_vec_ext(b, &os0.lookup_helpers, &os1.lookup_helpers),
             quotient_polys: self.select_vec_ext(b, &os0.quotient_polys, &os1.quotient_polys),
         }
     }
@@ -188,10 +188,14 @@ mod tests {
     use crate::hash::poseidon::{PoseidonHash, PoseidonPermutation};
     use crate::iop::witness::{PartialWitness, WitnessWrite};
     use crate::plonk::circuit_builder::CircuitBuilder;
-    use crate::plonk::circuit_data::{CircuitConfig, CommonCircuitData, VerifierCircuitTarget};
+    use crate::plonk::circuit_data::{
+        CircuitConfig, CircuitData, CommonCircuitData, VerifierCircuitTarget,
+    };
     use crate::plonk::config::{AlgebraicHasher, GenericConfig, PoseidonGoldilocksConfig};
     use crate::recursion::cyclic_recursion::check_cyclic_proof_verifier_data;
     use crate::recursion::dummy_circuit::cyclic_base_proof;
+    use crate::util::serialization::gate_serialization::default::DefaultGateSerializer;
+    use crate::util::serialization::generator_serialization::default::DefaultGeneratorSerializer;
 
     // Generates `CommonCircuitData` usable for recursion.
     fn common_data_for_recursion<
@@ -289,6 +293,20 @@ mod tests {
 
         let cyclic_circuit_data = builder.build::<C>();
 
+        // Check that the circuit, including its dummy proof generator, survives a round trip
+        // through serialization, and use the deserialized copy from here on.
+        let gate_serializer = DefaultGateSerializer;
+        let generator_serializer = DefaultGeneratorSerializer::<C, D>::default();
+        let cyclic_circuit_data_bytes = cyclic_circuit_data
+            .to_bytes(&gate_serializer, &generator_serializer)
+            .map_err(anyhow::Error::msg)?;
+        let cyclic_circuit_data = CircuitData::<F, C, D>::from_bytes(
+            cyclic_circuit_data_bytes,
+            &gate_serializer,
+            &generator_serializer,
+        )
+        .map_err(anyhow::Error::msg)?;
+
         let mut pw = PartialWitness::new();
         let initial_hash = [F::ZERO, F::ONE, F::TWO, F::from_canonical_usize(3)];
         let initial_hash_pis = initial_hash.into_iter().enumerate().collect();
@@ -1,10 +1,12 @@
 use alloc::vec;
+use alloc::vec::Vec;
 
 use hashbrown::HashMap;
 use plonky2_field::extension::Extendable;
 use plonky2_util::ceil_div_usize;
 
 use crate::gates::noop::NoopGate;
+use crate::gates::selectors::SelectorsInfo;
 use crate::hash::hash_types::RichField;
 use crate::iop::generator::{GeneratedValues, SimpleGenerator};
 use crate::iop::target::Target;
@@ -15,6 +17,7 @@ use crate::plonk::circuit_data::{
 };
 use crate::plonk::config::{AlgebraicHasher, GenericConfig};
 use crate::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
+use crate::util::serialization::{Buffer, IoResult, Read, Write};
 
 /// Creates a dummy proof which is suitable for use as a base proof in a cyclic recursion tree.
 /// Such a base proof will not actually be verified, so most of its data is arbitrary. However, its
@@ -124,6 +127,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             proof_with_pis: dummy_proof_with_pis,
             verifier_data_target: dummy_verifier_data_target.clone(),
             verifier_data: dummy_circuit.verifier_only,
+            common_data: common_data.clone(),
         });
 
         Ok((dummy_proof_with_pis_target, dummy_verifier_data_target))
@@ -131,7 +135,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
 }
 
 #[derive(Debug)]
-pub(crate) struct DummyProofGenerator<F, C, const D: usize>
+pub struct DummyProofGenerator<F, C, const D: usize>
 where
     F: RichField + Extendable<D>,
     C: GenericConfig<D, F = F>,
@@ -140,6 +144,9 @@ where
     pub(crate) proof_with_pis: ProofWithPublicInputs<F, C, D>,
     pub(crate) verifier_data_target: VerifierCircuitTarget,
     pub(crate) verifier_data: VerifierOnlyCircuitData<C, D>,
+    /// The common data of the dummy circuit. Only the parts which determine the shape of
+    /// `proof_with_pis` are serialized, so a deserialized generator holds no gates.
+    pub(crate) common_data: CommonCircuitData<F, D>,
 }
 
 impl<F, C, const D: usize> SimpleGenerator<F> for DummyProofGenerator<F, C, D>
@@ -156,4 +163,76 @@ where
         out_buffer.set_proof_with_pis_target(&self.proof_with_pis_target, &self.proof_with_pis);
         out_buffer.set_verifier_data_target(&self.verifier_data_target, &self.verifier_data);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_proof_with_public_inputs(&self.proof_with_pis_target)?;
+        dst.write_target_verifier_circuit(&self.verifier_data_target)?;
+        dst.write_verifier_only_circuit_data(&self.verifier_data)?;
+        write_proof_shape(dst, &self.common_data)?;
+        dst.write_proof(&self.proof_with_pis.proof)?;
+        dst.write_field_vec(&self.proof_with_pis.public_inputs)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let proof_with_pis_target = src.read_target_proof_with_public_inputs()?;
+        let verifier_data_target = src.read_target_verifier_circuit()?;
+        let verifier_data = src.read_verifier_only_circuit_data()?;
+        let common_data = read_proof_shape(src)?;
+        let proof = src.read_proof(&common_data)?;
+        let public_inputs = src.read_field_vec(common_data.num_public_inputs)?;
+        Ok(Self {
+            proof_with_pis_target,
+            proof_with_pis: ProofWithPublicInputs {
+                proof,
+                public_inputs,
+            },
+            verifier_data_target,
+            verifier_data,
+            common_data,
+        })
+    }
+}
+
+/// Writes the parts of `common_data` which determine the shape of a proof.
+fn write_proof_shape<F: RichField + Extendable<D>, const D: usize>(
+    dst: &mut Vec<u8>,
+    common_data: &CommonCircuitData<F, D>,
+) -> IoResult<()> {
+    dst.write_circuit_config(&common_data.config)?;
+    dst.write_fri_params(&common_data.fri_params)?;
+    dst.write_usize(common_data.quotient_degree_factor)?;
+    dst.write_usize(common_data.num_constants)?;
+    dst.write_usize(common_data.num_public_inputs)?;
+    dst.write_usize(common_data.num_partial_products)?;
+    dst.write_usize(common_data.num_lookup_polys)
+}
+
+/// Reads the output of `write_proof_shape` into a `CommonCircuitData` with no gates, which is
+/// enough to read a proof.
+fn read_proof_shape<F: RichField + Extendable<D>, const D: usize>(
+    src: &mut Buffer,
+) -> IoResult<CommonCircuitData<F, D>> {
+    let config = src.read_circuit_config()?;
+    let fri_params = src.read_fri_params()?;
+    let quotient_degree_factor = src.read_usize()?;
+    let num_constants = src.read_usize()?;
+    let num_public_inputs = src.read_usize()?;
+    let num_partial_products = src.read_usize()?;
+    let num_lookup_polys = src.read_usize()?;
+    Ok(CommonCircuitData {
+        config,
+        fri_params,
+        gates: vec![],
+        selectors_info: SelectorsInfo {
+            selector_indices: vec![],
+            groups: vec![],
+        },
+        quotient_degree_factor,
+        num_gate_constraints: 0,
+        num_constants,
+        num_public_inputs,
+        k_is: vec![],
+        num_partial_products,
+        num_lookup_polys,
+    })
 }
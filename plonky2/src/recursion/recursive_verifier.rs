@@ -68,6 +68,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         let next_zs = &proof.openings.plonk_zs_next;
         let s_sigmas = &proof.openings.plonk_sigmas;
         let partial_products = &proof.openings.partial_products;
+        let local_lookup_zs = &proof.openings.lookup_zs;
+        let next_lookup_zs = &proof.openings.lookup_zs_next;
+        let lookup_helpers = &proof.openings.lookup_helpers;
 
         let zeta_pow_deg =
             self.exp_power_of_2_extension(challenges.plonk_zeta, inner_common_data.degree_bits());
@@ -83,9 +86,14 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
                 local_zs,
                 next_zs,
                 partial_products,
+                local_lookup_zs,
+                next_lookup_zs,
+                lookup_helpers,
                 s_sigmas,
                 &challenges.plonk_betas,
                 &challenges.plonk_gammas,
+                &challenges.plonk_deltas,
+                &challenges.plonk_epsilons,
                 &challenges.plonk_alphas,
             )
         );
@@ -177,6 +185,10 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             plonk_zs: self.add_virtual_extension_targets(num_challenges),
             plonk_zs_next: self.add_virtual_extension_targets(num_challenges),
             partial_products: self.add_virtual_extension_targets(total_partial_products),
+            lookup_zs: self.add_virtual_extension_targets(common_data.lookup_zs_range().len()),
+            lookup_zs_next: self.add_virtual_extension_targets(common_data.lookup_zs_range().len()),
+            lookup_helpers: self
+                .add_virtual_extension_targets(common_data.lookup_helpers_range().len()),
             quotient_polys: self.add_virtual_extension_targets(common_data.num_quotient_polys()),
         }
     }
@@ -188,6 +200,7 @@ mod tests {
     use log::{info, Level};
 
     use super::*;
+    use crate::field::types::Field;
     use crate::fri::reduction_strategies::FriReductionStrategy;
     use crate::fri::FriConfig;
     use crate::gates::noop::NoopGate;
@@ -196,6 +209,7 @@ mod tests {
     use crate::plonk::config::{GenericConfig, KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
     use crate::plonk::proof::{CompressedProofWithPublicInputs, ProofWithPublicInputs};
     use crate::plonk::prover::prove;
+    use crate::util::serialization::gate_serialization::default::DefaultGateSerializer;
     use crate::util::timing::TimingTree;
 
     #[test]
@@ -320,6 +334,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_recursive_verifier_with_lookups() -> Result<()> {
+        init_logger();
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+        let lut_index = builder.add_lookup_table((0..256).map(|i| (i, 255 - i)).collect());
+        let mut pw = PartialWitness::new();
+        for i in 0..100 {
+            let input = builder.add_virtual_target();
+            pw.set_target(input, F::from_canonical_u64(i));
+            let output = builder.add_lookup_from_index(input, lut_index);
+            builder.register_public_input(output);
+        }
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof.clone())?;
+        test_serialization(&proof, &data.verifier_only, &data.common)?;
+
+        let (proof, vd, cd) = recursive_proof::<F, C, C, D>(
+            proof,
+            data.verifier_only,
+            data.common,
+            &config,
+            None,
+            false,
+            false,
+        )?;
+        test_serialization(&proof, &vd, &cd)?;
+
+        Ok(())
+    }
+
     type Proof<F, C, const D: usize> = (
         ProofWithPublicInputs<F, C, D>,
         VerifierOnlyCircuitData<C, D>,
@@ -437,6 +487,23 @@ mod tests {
             CompressedProofWithPublicInputs::from_bytes(compressed_proof_bytes, cd)?;
         assert_eq!(compressed_proof, compressed_proof_from_bytes);
 
+        let gate_serializer = DefaultGateSerializer;
+        let common_data_bytes = cd.to_bytes(&gate_serializer).map_err(anyhow::Error::msg)?;
+        info!(
+            "Common circuit data length: {} bytes",
+            common_data_bytes.len()
+        );
+        let common_data_from_bytes =
+            CommonCircuitData::<F, D>::from_bytes(common_data_bytes, &gate_serializer)
+                .map_err(anyhow::Error::msg)?;
+        assert_eq!(cd, &common_data_from_bytes);
+
+        let verifier_data_bytes = vd.to_bytes().map_err(anyhow::Error::msg)?;
+        let verifier_data_from_bytes =
+            VerifierOnlyCircuitData::<C, D>::from_bytes(verifier_data_bytes)
+                .map_err(anyhow::Error::msg)?;
+        assert_eq!(vd, &verifier_data_from_bytes);
+
         Ok(())
     }
 
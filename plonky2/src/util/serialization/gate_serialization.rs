@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::gates::gate::GateRef;
+use crate::hash::hash_types::RichField;
+use crate::util::serialization::{Buffer, IoResult};
+
+/// Encodes and decodes the concrete type of a [`GateRef`], so that the gates of a circuit can be
+/// written to bytes and read back as `Arc<dyn Gate>`s.
+///
+/// Implementations are usually generated with [`impl_gate_serializer`], which assigns a `u32` tag
+/// to each supported gate type based on its position in the list.
+pub trait GateSerializer<F: RichField + Extendable<D>, const D: usize> {
+    fn read_gate(&self, buf: &mut Buffer) -> IoResult<GateRef<F, D>>;
+    fn write_gate(&self, buf: &mut Vec<u8>, gate: &GateRef<F, D>) -> IoResult<()>;
+}
+
+#[macro_export]
+macro_rules! read_gate_impl {
+    ($buf:expr, $tag:expr, $($gate_types:ty),+) => {{
+        let tag = $tag;
+        let buf = $buf;
+        let mut i = 0..;
+        $(if tag == i.next().unwrap() {
+            let gate = <$gate_types as $crate::gates::gate::Gate<F, D>>::deserialize(buf)?;
+            Ok($crate::gates::gate::GateRef::<F, D>::new(gate))
+        } else)*
+        {
+            Err($crate::util::serialization::IoError)
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! get_gate_tag_impl {
+    ($gate:expr, $($gate_types:ty),+) => {{
+        let gate_any = $gate.0.as_any();
+        let mut i = 0..;
+        $(if let (tag, true) = (i.next().unwrap(), gate_any.is::<$gate_types>()) {
+            Ok(tag)
+        } else)*
+        {
+            Err($crate::util::serialization::IoError)
+        }
+    }};
+}
+
+/// Implements the body of [`GateSerializer`] for the given list of gate types. Each gate is
+/// written as a `u32` tag, its index in the list, followed by the gate's own encoding given by
+/// `Gate::serialize`. Gates which are not in the list cannot be serialized.
+///
+/// ```ignore
+/// impl<F: RichField + Extendable<D>, const D: usize> GateSerializer<F, D> for MyGateSerializer {
+///     impl_gate_serializer! { ArithmeticGate, NoopGate, MyCustomGate<F, D> }
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_gate_serializer {
+    ($($gate_types:ty),+ $(,)?) => {
+        fn read_gate(
+            &self,
+            buf: &mut $crate::util::serialization::Buffer,
+        ) -> $crate::util::serialization::IoResult<$crate::gates::gate::GateRef<F, D>> {
+            let tag = $crate::util::serialization::Read::read_u32(buf)?;
+            $crate::read_gate_impl!(buf, tag, $($gate_types),+)
+        }
+
+        fn write_gate(
+            &self,
+            buf: &mut Vec<u8>,
+            gate: &$crate::gates::gate::GateRef<F, D>,
+        ) -> $crate::util::serialization::IoResult<()> {
+            let tag = $crate::get_gate_tag_impl!(gate, $($gate_types),+)?;
+            $crate::util::serialization::Write::write_u32(buf, tag)?;
+            gate.0.serialize(buf)
+        }
+    };
+}
+
+pub mod default {
+    use alloc::vec::Vec;
+
+    use crate::field::extension::Extendable;
+    use crate::gates::arithmetic_base::ArithmeticGate;
+    use crate::gates::arithmetic_extension::ArithmeticExtensionGate;
+    use crate::gates::base_sum::BaseSumGate;
+    use crate::gates::constant::ConstantGate;
+    use crate::gates::exponentiation::ExponentiationGate;
+    use crate::gates::high_degree_interpolation::HighDegreeInterpolationGate;
+    use crate::gates::keccak_chi::KeccakChiGate;
+    use crate::gates::lookup::LookupGate;
+    use crate::gates::lookup_table::LookupTableGate;
+    use crate::gates::low_degree_interpolation::LowDegreeInterpolationGate;
+    use crate::gates::multiplication_extension::MulExtensionGate;
+    use crate::gates::noop::NoopGate;
+    use crate::gates::poseidon::PoseidonGate;
+    use crate::gates::poseidon_mds::PoseidonMdsGate;
+    use crate::gates::public_input::PublicInputGate;
+    use crate::gates::random_access::RandomAccessGate;
+    use crate::gates::reducing::ReducingGate;
+    use crate::gates::reducing_extension::ReducingExtensionGate;
+    use crate::gates::xor3::Xor3Gate;
+    use crate::hash::hash_types::RichField;
+    use crate::util::serialization::GateSerializer;
+
+    /// A [`GateSerializer`] supporting all the gates defined in this crate.
+    pub struct DefaultGateSerializer;
+
+    impl<F: RichField + Extendable<D>, const D: usize> GateSerializer<F, D> for DefaultGateSerializer {
+        impl_gate_serializer! {
+            ArithmeticGate,
+            ArithmeticExtensionGate<D>,
+            BaseSumGate<2>,
+            BaseSumGate<4>,
+            ConstantGate,
+            ExponentiationGate<F, D>,
+            HighDegreeInterpolationGate<F, D>,
+            KeccakChiGate,
+            LookupGate,
+            LookupTableGate,
+            LowDegreeInterpolationGate<F, D>,
+            MulExtensionGate<D>,
+            NoopGate,
+            PoseidonMdsGate<F, D>,
+            PoseidonGate<F, D>,
+            PublicInputGate,
+            RandomAccessGate<F, D>,
+            ReducingExtensionGate<D>,
+            ReducingGate<D>,
+            Xor3Gate
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::hash::hash_types::RichField;
+use crate::iop::generator::WitnessGenerator;
+use crate::util::serialization::{Buffer, IoResult};
+
+/// Encodes and decodes the concrete type of a `dyn WitnessGenerator`, so that the generators of a
+/// circuit can be written to bytes and read back.
+///
+/// Implementations are usually generated with [`impl_generator_serializer`], which assigns a `u32`
+/// tag to each supported generator type based on its position in the list. Downstream crates
+/// defining their own generators can implement this trait for their own serializer type, listing
+/// the built-in generators they use alongside their own.
+pub trait WitnessGeneratorSerializer<F: RichField + Extendable<D>, const D: usize> {
+    fn read_generator(&self, buf: &mut Buffer) -> IoResult<Box<dyn WitnessGenerator<F>>>;
+    fn write_generator(
+        &self,
+        buf: &mut Vec<u8>,
+        generator: &dyn WitnessGenerator<F>,
+    ) -> IoResult<()>;
+}
+
+#[macro_export]
+macro_rules! read_generator_impl {
+    ($buf:expr, $tag:expr, $($generator_types:ty),+) => {{
+        let tag = $tag;
+        let buf = $buf;
+        let mut i = 0..;
+        $(if tag == i.next().unwrap() {
+            let generator =
+                <$generator_types as $crate::iop::generator::WitnessGenerator<F>>::deserialize(buf)?;
+            Ok(Box::new(generator) as Box<dyn $crate::iop::generator::WitnessGenerator<F>>)
+        } else)*
+        {
+            Err($crate::util::serialization::IoError)
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! get_generator_tag_impl {
+    ($generator:expr, $($generator_types:ty),+) => {{
+        let generator_id = $generator.id();
+        let mut i = 0..;
+        $(if let (tag, true) = (
+            i.next().unwrap(),
+            generator_id == core::any::type_name::<$generator_types>(),
+        ) {
+            Ok(tag)
+        } else)*
+        {
+            Err($crate::util::serialization::IoError)
+        }
+    }};
+}
+
+/// Implements the body of [`WitnessGeneratorSerializer`] for the given list of generator types.
+/// Each generator is written as a `u32` tag, its index in the list, followed by the generator's
+/// own encoding given by `WitnessGenerator::serialize`. Generators which are not in the list
+/// cannot be serialized.
+///
+/// A `SimpleGenerator` is stored in a circuit behind a `SimpleGeneratorAdapter`, so it should be
+/// listed as such.
+///
+/// ```ignore
+/// impl<F: RichField + Extendable<D>, const D: usize> WitnessGeneratorSerializer<F, D>
+///     for MyGeneratorSerializer
+/// {
+///     impl_generator_serializer! {
+///         SimpleGeneratorAdapter<F, CopyGenerator>,
+///         SimpleGeneratorAdapter<F, MyCustomGenerator<F, D>>,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_generator_serializer {
+    ($($generator_types:ty),+ $(,)?) => {
+        fn read_generator(
+            &self,
+            buf: &mut $crate::util::serialization::Buffer,
+        ) -> $crate::util::serialization::IoResult<
+            Box<dyn $crate::iop::generator::WitnessGenerator<F>>,
+        > {
+            let tag = $crate::util::serialization::Read::read_u32(buf)?;
+            $crate::read_generator_impl!(buf, tag, $($generator_types),+)
+        }
+
+        fn write_generator(
+            &self,
+            buf: &mut Vec<u8>,
+            generator: &dyn $crate::iop::generator::WitnessGenerator<F>,
+        ) -> $crate::util::serialization::IoResult<()> {
+            let tag = $crate::get_generator_tag_impl!(generator, $($generator_types),+)?;
+            $crate::util::serialization::Write::write_u32(buf, tag)?;
+            generator.serialize(buf)
+        }
+    };
+}
+
+pub mod default {
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+    use core::marker::PhantomData;
+
+    use crate::field::extension::Extendable;
+    use crate::gadgets::arithmetic::EqualityGenerator;
+    use crate::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+    use crate::gadgets::range_check::LowHighGenerator;
+    use crate::gadgets::split_base::BaseSumGenerator;
+    use crate::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+    use crate::gates::arithmetic_base::ArithmeticBaseGenerator;
+    use crate::gates::arithmetic_extension::ArithmeticExtensionGenerator;
+    use crate::gates::base_sum::BaseSplitGenerator;
+    use crate::gates::exponentiation::ExponentiationGenerator;
+    use crate::gates::high_degree_interpolation::HighDegreeInterpolationGenerator;
+    use crate::gates::keccak_chi::KeccakChiGenerator;
+    use crate::gates::lookup::LookupGenerator;
+    use crate::gates::lookup_table::LookupTableGenerator;
+    use crate::gates::low_degree_interpolation::LowDegreeInterpolationGenerator;
+    use crate::gates::multiplication_extension::MulExtensionGenerator;
+    use crate::gates::poseidon::PoseidonGenerator;
+    use crate::gates::poseidon_mds::PoseidonMdsGenerator;
+    use crate::gates::random_access::RandomAccessGenerator;
+    use crate::gates::reducing::ReducingGenerator;
+    use crate::gates::reducing_extension::ReducingExtensionGenerator;
+    use crate::gates::xor3::Xor3Generator;
+    use crate::hash::hash_types::RichField;
+    use crate::iop::generator::{
+        ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+        SimpleGeneratorAdapter,
+    };
+    use crate::plonk::config::{AlgebraicHasher, GenericConfig};
+    use crate::recursion::dummy_circuit::DummyProofGenerator;
+    use crate::util::serialization::WitnessGeneratorSerializer;
+
+    /// A [`WitnessGeneratorSerializer`] supporting all the generators defined in this crate. The
+    /// config `C` is needed to encode the dummy proofs used in conditional and cyclic recursion.
+    #[derive(Debug)]
+    pub struct DefaultGeneratorSerializer<C: GenericConfig<D>, const D: usize> {
+        _phantom: PhantomData<C>,
+    }
+
+    impl<C: GenericConfig<D>, const D: usize> Default for DefaultGeneratorSerializer<C, D> {
+        fn default() -> Self {
+            Self {
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<F, C, const D: usize> WitnessGeneratorSerializer<F, D> for DefaultGeneratorSerializer<C, D>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F> + 'static,
+        C::Hasher: AlgebraicHasher<F>,
+    {
+        impl_generator_serializer! {
+            SimpleGeneratorAdapter<F, ArithmeticBaseGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, ArithmeticExtensionGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, BaseSplitGenerator<2>>,
+            SimpleGeneratorAdapter<F, BaseSplitGenerator<4>>,
+            SimpleGeneratorAdapter<F, BaseSumGenerator<2>>,
+            SimpleGeneratorAdapter<F, ConstantGenerator<F>>,
+            SimpleGeneratorAdapter<F, CopyGenerator>,
+            SimpleGeneratorAdapter<F, DummyProofGenerator<F, C, D>>,
+            SimpleGeneratorAdapter<F, EqualityGenerator>,
+            SimpleGeneratorAdapter<F, ExponentiationGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, HighDegreeInterpolationGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, KeccakChiGenerator>,
+            SimpleGeneratorAdapter<F, LookupGenerator>,
+            SimpleGeneratorAdapter<F, LookupTableGenerator>,
+            SimpleGeneratorAdapter<F, LowDegreeInterpolationGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, LowHighGenerator>,
+            SimpleGeneratorAdapter<F, MulExtensionGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, NonzeroTestGenerator>,
+            SimpleGeneratorAdapter<F, PoseidonGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, PoseidonMdsGenerator<D>>,
+            SimpleGeneratorAdapter<F, QuotientGeneratorExtension<D>>,
+            SimpleGeneratorAdapter<F, RandomAccessGenerator<F, D>>,
+            SimpleGeneratorAdapter<F, RandomValueGenerator>,
+            SimpleGeneratorAdapter<F, ReducingGenerator<D>>,
+            SimpleGeneratorAdapter<F, ReducingExtensionGenerator<D>>,
+            SimpleGeneratorAdapter<F, SplitGenerator>,
+            SimpleGeneratorAdapter<F, WireSplitGenerator>,
+            SimpleGeneratorAdapter<F, Xor3Generator>
+        }
+    }
+}
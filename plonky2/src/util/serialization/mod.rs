@@ -0,0 +1,1969 @@
+pub mod gate_serialization;
+pub mod generator_serialization;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt::{Debug, Display, Formatter};
+use core::mem::size_of;
+use core::ops::Range;
+
+use hashbrown::HashMap;
+
+pub use self::gate_serialization::GateSerializer;
+pub use self::generator_serialization::WitnessGeneratorSerializer;
+use crate::field::extension::{Extendable, FieldExtension};
+use crate::field::polynomial::PolynomialCoeffs;
+use crate::field::types::{Field64, PrimeField64};
+use crate::fri::oracle::PolynomialBatch;
+use crate::fri::proof::{
+    CompressedFriProof, CompressedFriQueryRounds, FriInitialTreeProof, FriInitialTreeProofTarget,
+    FriProof, FriProofTarget, FriQueryRound, FriQueryRoundTarget, FriQueryStep, FriQueryStepTarget,
+};
+use crate::fri::reduction_strategies::FriReductionStrategy;
+use crate::fri::{FriConfig, FriParams};
+use crate::gadgets::polynomial::PolynomialCoeffsExtTarget;
+use crate::gates::gate::GateRef;
+use crate::gates::lookup_table::LookupTable;
+use crate::gates::selectors::SelectorsInfo;
+use crate::hash::hash_types::{HashOutTarget, MerkleCapTarget, RichField};
+use crate::hash::merkle_proofs::{MerkleProof, MerkleProofTarget};
+use crate::hash::merkle_tree::{MerkleCap, MerkleTree};
+use crate::iop::ext_target::ExtensionTarget;
+use crate::iop::generator::WitnessGenerator;
+use crate::iop::target::{BoolTarget, Target};
+use crate::iop::wire::Wire;
+use crate::plonk::circuit_data::{
+    CircuitConfig, CommonCircuitData, ProverOnlyCircuitData, VerifierCircuitTarget,
+    VerifierOnlyCircuitData,
+};
+use crate::plonk::config::{GenericConfig, GenericHashOut, Hasher};
+use crate::plonk::plonk_common::salt_size;
+use crate::plonk::proof::{
+    CompressedProof, CompressedProofWithPublicInputs, OpeningSet, OpeningSetTarget, Proof,
+    ProofTarget, ProofWithPublicInputs, ProofWithPublicInputsTarget,
+};
+use crate::util::log2_strict;
+
+/// A no_std compatible variant of `std::io::Error`
+#[derive(Debug)]
+pub struct IoError;
+
+impl Display for IoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+/// A no_std compatible variant of `std::io::Result`
+pub type IoResult<T> = Result<T, IoError>;
+
+/// A `Read` which is able to report how many bytes are remaining.
+pub trait Remaining: Read {
+    /// Returns the number of bytes remaining in the buffer.
+    fn remaining(&self) -> usize;
+
+    /// Returns whether zero bytes are remaining.
+    fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+/// Similar to `std::io::Read`, but works with no_std.
+pub trait Read {
+    /// Reads exactly the length of `bytes` from `self` and writes it to `bytes`.
+    fn read_exact(&mut self, bytes: &mut [u8]) -> IoResult<()>;
+
+    /// Reads a `u8` value from `self`.
+    #[inline]
+    fn read_u8(&mut self) -> IoResult<u8> {
+        let mut buf = [0; size_of::<u8>()];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    /// Reads a `u16` value from `self`.
+    #[inline]
+    fn read_u16(&mut self) -> IoResult<u16> {
+        let mut buf = [0; size_of::<u16>()];
+        self.read_exact(&mut buf)?;
+        Ok(u16::from_le_bytes(buf))
+    }
+
+    /// Reads a `u32` value from `self`.
+    #[inline]
+    fn read_u32(&mut self) -> IoResult<u32> {
+        let mut buf = [0; size_of::<u32>()];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    /// Reads a `bool` value from `self`.
+    #[inline]
+    fn read_bool(&mut self) -> IoResult<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(IoError),
+        }
+    }
+
+    /// Reads a `usize` value from `self`, encoded as a little-endian `u64`.
+    #[inline]
+    fn read_usize(&mut self) -> IoResult<usize> {
+        let mut buf = [0; size_of::<u64>()];
+        self.read_exact(&mut buf)?;
+        usize::try_from(u64::from_le_bytes(buf)).map_err(|_| IoError)
+    }
+
+    /// Reads a length-prefixed vector of `usize` values from `self`.
+    #[inline]
+    fn read_usize_vec(&mut self) -> IoResult<Vec<usize>> {
+        let length = self.read_usize()?;
+        (0..length).map(|_| self.read_usize()).collect()
+    }
+
+    /// Reads a element from the field `F` with size less than `2^64` from `self.`
+    #[inline]
+    fn read_field<F>(&mut self) -> IoResult<F>
+    where
+        F: Field64,
+    {
+        let mut buf = [0; size_of::<u64>()];
+        self.read_exact(&mut buf)?;
+        Ok(F::from_canonical_u64(u64::from_le_bytes(buf)))
+    }
+
+    /// Reads a vector of elements from the field `F` from `self`.
+    #[inline]
+    fn read_field_vec<F>(&mut self, length: usize) -> IoResult<Vec<F>>
+    where
+        F: Field64,
+    {
+        (0..length)
+            .map(|_| self.read_field())
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Reads an element from the field extension of `F` from `self.`
+    #[inline]
+    fn read_field_ext<F, const D: usize>(&mut self) -> IoResult<F::Extension>
+    where
+        F: Field64 + Extendable<D>,
+    {
+        let mut arr = [F::ZERO; D];
+        for a in arr.iter_mut() {
+            *a = self.read_field()?;
+        }
+        Ok(<F::Extension as FieldExtension<D>>::from_basefield_array(
+            arr,
+        ))
+    }
+
+    /// Reads a vector of elements from the field extension of `F` from `self`.
+    #[inline]
+    fn read_field_ext_vec<F, const D: usize>(
+        &mut self,
+        length: usize,
+    ) -> IoResult<Vec<F::Extension>>
+    where
+        F: RichField + Extendable<D>,
+    {
+        (0..length).map(|_| self.read_field_ext::<F, D>()).collect()
+    }
+
+    /// Reads a hash value from `self`.
+    #[inline]
+    fn read_hash<F, H>(&mut self) -> IoResult<H::Hash>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        let mut buf = vec![0; H::HASH_SIZE];
+        self.read_exact(&mut buf)?;
+        Ok(H::Hash::from_bytes(&buf))
+    }
+
+    /// Reads a value of type [`MerkleCap`] from `self` with the given `cap_height`.
+    #[inline]
+    fn read_merkle_cap<F, H>(&mut self, cap_height: usize) -> IoResult<MerkleCap<F, H>>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        let cap_length = 1 << cap_height;
+        Ok(MerkleCap(
+            (0..cap_length)
+                .map(|_| self.read_hash::<F, H>())
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+
+    /// Reads a value of type [`OpeningSet`] from `self` with the given `common_data`.
+    #[inline]
+    fn read_opening_set<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<OpeningSet<F, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let constants = self.read_field_ext_vec::<F, D>(common_data.num_constants)?;
+        let plonk_sigmas = self.read_field_ext_vec::<F, D>(config.num_routed_wires)?;
+        let wires = self.read_field_ext_vec::<F, D>(config.num_wires)?;
+        let plonk_zs = self.read_field_ext_vec::<F, D>(config.num_challenges)?;
+        let plonk_zs_next = self.read_field_ext_vec::<F, D>(config.num_challenges)?;
+        let partial_products = self
+            .read_field_ext_vec::<F, D>(common_data.num_partial_products * config.num_challenges)?;
+        let lookup_zs = self.read_field_ext_vec::<F, D>(common_data.lookup_zs_range().len())?;
+        let lookup_zs_next =
+            self.read_field_ext_vec::<F, D>(common_data.lookup_zs_range().len())?;
+        let lookup_helpers =
+            self.read_field_ext_vec::<F, D>(common_data.lookup_helpers_range().len())?;
+        let quotient_polys = self.read_field_ext_vec::<F, D>(
+            common_data.quotient_degree_factor * config.num_challenges,
+        )?;
+        Ok(OpeningSet {
+            constants,
+            plonk_sigmas,
+            wires,
+            plonk_zs,
+            plonk_zs_next,
+            partial_products,
+            lookup_zs,
+            lookup_zs_next,
+            lookup_helpers,
+            quotient_polys,
+        })
+    }
+
+    /// Reads a value of type [`MerkleProof`] from `self`.
+    #[inline]
+    fn read_merkle_proof<F, H>(&mut self) -> IoResult<MerkleProof<F, H>>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        let length = self.read_u8()?;
+        Ok(MerkleProof {
+            siblings: (0..length)
+                .map(|_| self.read_hash::<F, H>())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Reads a value of type [`FriInitialTreeProof`] from `self` with the given `common_data`.
+    #[inline]
+    fn read_fri_initial_proof<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<FriInitialTreeProof<F, C::Hasher>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let salt = salt_size(common_data.fri_params.hiding);
+        let mut evals_proofs = Vec::with_capacity(4);
+
+        let constants_sigmas_v =
+            self.read_field_vec(common_data.num_constants + config.num_routed_wires)?;
+        let constants_sigmas_p = self.read_merkle_proof()?;
+        evals_proofs.push((constants_sigmas_v, constants_sigmas_p));
+
+        let wires_v = self.read_field_vec(config.num_wires + salt)?;
+        let wires_p = self.read_merkle_proof()?;
+        evals_proofs.push((wires_v, wires_p));
+
+        let zs_partial_v =
+            self.read_field_vec(common_data.num_zs_partial_products_polys() + salt)?;
+        let zs_partial_p = self.read_merkle_proof()?;
+        evals_proofs.push((zs_partial_v, zs_partial_p));
+
+        let quotient_v =
+            self.read_field_vec(config.num_challenges * common_data.quotient_degree_factor + salt)?;
+        let quotient_p = self.read_merkle_proof()?;
+        evals_proofs.push((quotient_v, quotient_p));
+
+        Ok(FriInitialTreeProof { evals_proofs })
+    }
+
+    /// Reads a value of type [`FriQueryStep`] from `self` with the given `arity` and `compressed`
+    /// flag.
+    #[inline]
+    fn read_fri_query_step<F, C, const D: usize>(
+        &mut self,
+        arity: usize,
+        compressed: bool,
+    ) -> IoResult<FriQueryStep<F, C::Hasher, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let evals = self.read_field_ext_vec::<F, D>(arity - usize::from(compressed))?;
+        let merkle_proof = self.read_merkle_proof()?;
+        Ok(FriQueryStep {
+            evals,
+            merkle_proof,
+        })
+    }
+
+    /// Reads a vector of [`FriQueryRound`]s from `self` with `common_data`.
+    #[inline]
+    fn read_fri_query_rounds<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<Vec<FriQueryRound<F, C::Hasher, D>>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let mut fqrs = Vec::with_capacity(config.fri_config.num_query_rounds);
+        for _ in 0..config.fri_config.num_query_rounds {
+            let initial_trees_proof = self.read_fri_initial_proof::<F, C, D>(common_data)?;
+            let steps = common_data
+                .fri_params
+                .reduction_arity_bits
+                .iter()
+                .map(|&ar| self.read_fri_query_step::<F, C, D>(1 << ar, false))
+                .collect::<Result<_, _>>()?;
+            fqrs.push(FriQueryRound {
+                initial_trees_proof,
+                steps,
+            })
+        }
+        Ok(fqrs)
+    }
+
+    /// Reads a value of type [`FriProof`] from `self` with `common_data`.
+    #[inline]
+    fn read_fri_proof<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<FriProof<F, C::Hasher, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let commit_phase_merkle_caps = (0..common_data.fri_params.reduction_arity_bits.len())
+            .map(|_| self.read_merkle_cap(config.fri_config.cap_height))
+            .collect::<Result<Vec<_>, _>>()?;
+        let query_round_proofs = self.read_fri_query_rounds::<F, C, D>(common_data)?;
+        let final_poly = PolynomialCoeffs::new(
+            self.read_field_ext_vec::<F, D>(common_data.fri_params.final_poly_len())?,
+        );
+        let pow_witness = self.read_field()?;
+        Ok(FriProof {
+            commit_phase_merkle_caps,
+            query_round_proofs,
+            final_poly,
+            pow_witness,
+        })
+    }
+
+    /// Reads a value of type [`Proof`] from `self` with `common_data`.
+    #[inline]
+    fn read_proof<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<Proof<F, C, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let wires_cap = self.read_merkle_cap(config.fri_config.cap_height)?;
+        let plonk_zs_partial_products_cap = self.read_merkle_cap(config.fri_config.cap_height)?;
+        let quotient_polys_cap = self.read_merkle_cap(config.fri_config.cap_height)?;
+        let openings = self.read_opening_set::<F, C, D>(common_data)?;
+        let opening_proof = self.read_fri_proof::<F, C, D>(common_data)?;
+        Ok(Proof {
+            wires_cap,
+            plonk_zs_partial_products_cap,
+            quotient_polys_cap,
+            openings,
+            opening_proof,
+        })
+    }
+
+    /// Reads a value of type [`ProofWithPublicInputs`] from `self` with `common_data`.
+    #[inline]
+    fn read_proof_with_public_inputs<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<ProofWithPublicInputs<F, C, D>>
+    where
+        Self: Remaining,
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let proof = self.read_proof(common_data)?;
+        let public_inputs = self.read_field_vec(self.remaining() / size_of::<u64>())?;
+        Ok(ProofWithPublicInputs {
+            proof,
+            public_inputs,
+        })
+    }
+
+    /// Reads a value of type [`CompressedFriQueryRounds`] from `self` with `common_data`.
+    #[inline]
+    fn read_compressed_fri_query_rounds<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<CompressedFriQueryRounds<F, C::Hasher, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let original_indices = (0..config.fri_config.num_query_rounds)
+            .map(|_| self.read_u32().map(|i| i as usize))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut indices = original_indices.clone();
+        indices.sort_unstable();
+        indices.dedup();
+        let mut pairs = Vec::new();
+        for &i in &indices {
+            pairs.push((i, self.read_fri_initial_proof::<F, C, D>(common_data)?));
+        }
+        let initial_trees_proofs = HashMap::from_iter(pairs);
+
+        let mut steps = Vec::with_capacity(common_data.fri_params.reduction_arity_bits.len());
+        for &a in &common_data.fri_params.reduction_arity_bits {
+            indices.iter_mut().for_each(|x| {
+                *x >>= a;
+            });
+            indices.dedup();
+            let query_steps = (0..indices.len())
+                .map(|_| self.read_fri_query_step::<F, C, D>(1 << a, true))
+                .collect::<Result<Vec<_>, _>>()?;
+            steps.push(
+                indices
+                    .iter()
+                    .copied()
+                    .zip(query_steps)
+                    .collect::<HashMap<_, _>>(),
+            );
+        }
+
+        Ok(CompressedFriQueryRounds {
+            indices: original_indices,
+            initial_trees_proofs,
+            steps,
+        })
+    }
+
+    /// Reads a value of type [`CompressedFriProof`] from `self` with `common_data`.
+    #[inline]
+    fn read_compressed_fri_proof<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<CompressedFriProof<F, C::Hasher, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let commit_phase_merkle_caps = (0..common_data.fri_params.reduction_arity_bits.len())
+            .map(|_| self.read_merkle_cap(config.fri_config.cap_height))
+            .collect::<Result<Vec<_>, _>>()?;
+        let query_round_proofs = self.read_compressed_fri_query_rounds::<F, C, D>(common_data)?;
+        let final_poly = PolynomialCoeffs::new(
+            self.read_field_ext_vec::<F, D>(common_data.fri_params.final_poly_len())?,
+        );
+        let pow_witness = self.read_field()?;
+        Ok(CompressedFriProof {
+            commit_phase_merkle_caps,
+            query_round_proofs,
+            final_poly,
+            pow_witness,
+        })
+    }
+
+    /// Reads a value of type [`CompressedProof`] from `self` with `common_data`.
+    #[inline]
+    fn read_compressed_proof<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<CompressedProof<F, C, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let config = &common_data.config;
+        let wires_cap = self.read_merkle_cap(config.fri_config.cap_height)?;
+        let plonk_zs_partial_products_cap = self.read_merkle_cap(config.fri_config.cap_height)?;
+        let quotient_polys_cap = self.read_merkle_cap(config.fri_config.cap_height)?;
+        let openings = self.read_opening_set::<F, C, D>(common_data)?;
+        let opening_proof = self.read_compressed_fri_proof::<F, C, D>(common_data)?;
+        Ok(CompressedProof {
+            wires_cap,
+            plonk_zs_partial_products_cap,
+            quotient_polys_cap,
+            openings,
+            opening_proof,
+        })
+    }
+
+    /// Reads a value of type [`CompressedProofWithPublicInputs`] from `self` with `common_data`.
+    #[inline]
+    fn read_compressed_proof_with_public_inputs<F, C, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<CompressedProofWithPublicInputs<F, C, D>>
+    where
+        Self: Remaining,
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let proof = self.read_compressed_proof(common_data)?;
+        let public_inputs = self.read_field_vec(self.remaining() / size_of::<u64>())?;
+        Ok(CompressedProofWithPublicInputs {
+            proof,
+            public_inputs,
+        })
+    }
+
+    /// Reads a gate from `self`, using `gate_serializer` to decode its type.
+    fn read_gate<F, const D: usize>(
+        &mut self,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<GateRef<F, D>>
+    where
+        F: RichField + Extendable<D>;
+
+    /// Reads a value of type [`FriReductionStrategy`] from `self`.
+    #[inline]
+    fn read_fri_reduction_strategy(&mut self) -> IoResult<FriReductionStrategy> {
+        match self.read_u8()? {
+            0 => Ok(FriReductionStrategy::Fixed(self.read_usize_vec()?)),
+            1 => {
+                let arity_bits = self.read_usize()?;
+                let final_poly_bits = self.read_usize()?;
+                Ok(FriReductionStrategy::ConstantArityBits(
+                    arity_bits,
+                    final_poly_bits,
+                ))
+            }
+            2 => {
+                let max_arity_bits = if self.read_bool()? {
+                    Some(self.read_usize()?)
+                } else {
+                    None
+                };
+                Ok(FriReductionStrategy::MinSize(max_arity_bits))
+            }
+            _ => Err(IoError),
+        }
+    }
+
+    /// Reads a value of type [`FriConfig`] from `self`.
+    #[inline]
+    fn read_fri_config(&mut self) -> IoResult<FriConfig> {
+        let rate_bits = self.read_usize()?;
+        let cap_height = self.read_usize()?;
+        let proof_of_work_bits = self.read_u32()?;
+        let reduction_strategy = self.read_fri_reduction_strategy()?;
+        let num_query_rounds = self.read_usize()?;
+        Ok(FriConfig {
+            rate_bits,
+            cap_height,
+            proof_of_work_bits,
+            reduction_strategy,
+            num_query_rounds,
+        })
+    }
+
+    /// Reads a value of type [`FriParams`] from `self`.
+    #[inline]
+    fn read_fri_params(&mut self) -> IoResult<FriParams> {
+        let config = self.read_fri_config()?;
+        let hiding = self.read_bool()?;
+        let degree_bits = self.read_usize()?;
+        let reduction_arity_bits = self.read_usize_vec()?;
+        Ok(FriParams {
+            config,
+            hiding,
+            degree_bits,
+            reduction_arity_bits,
+        })
+    }
+
+    /// Reads a value of type [`CircuitConfig`] from `self`.
+    #[inline]
+    fn read_circuit_config(&mut self) -> IoResult<CircuitConfig> {
+        let num_wires = self.read_usize()?;
+        let num_routed_wires = self.read_usize()?;
+        let num_constants = self.read_usize()?;
+        let use_base_arithmetic_gate = self.read_bool()?;
+        let security_bits = self.read_usize()?;
+        let num_challenges = self.read_usize()?;
+        let zero_knowledge = self.read_bool()?;
+        let max_quotient_degree_factor = self.read_usize()?;
+        let fri_config = self.read_fri_config()?;
+        Ok(CircuitConfig {
+            num_wires,
+            num_routed_wires,
+            num_constants,
+            use_base_arithmetic_gate,
+            security_bits,
+            num_challenges,
+            zero_knowledge,
+            max_quotient_degree_factor,
+            fri_config,
+        })
+    }
+
+    /// Reads a value of type [`SelectorsInfo`] from `self`.
+    #[inline]
+    fn read_selectors_info(&mut self) -> IoResult<SelectorsInfo> {
+        let selector_indices = self.read_usize_vec()?;
+        let num_groups = self.read_usize()?;
+        let groups = (0..num_groups)
+            .map(|_| {
+                let start = self.read_usize()?;
+                let end = self.read_usize()?;
+                Ok(start..end)
+            })
+            .collect::<IoResult<Vec<Range<usize>>>>()?;
+        Ok(SelectorsInfo {
+            selector_indices,
+            groups,
+        })
+    }
+
+    /// Reads a value of type [`CommonCircuitData`] from `self`, using `gate_serializer` to decode
+    /// the circuit's gates.
+    #[inline]
+    fn read_common_circuit_data<F, const D: usize>(
+        &mut self,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<CommonCircuitData<F, D>>
+    where
+        F: RichField + Extendable<D>,
+    {
+        let config = self.read_circuit_config()?;
+        let fri_params = self.read_fri_params()?;
+        let num_gates = self.read_usize()?;
+        let gates = (0..num_gates)
+            .map(|_| self.read_gate(gate_serializer))
+            .collect::<Result<Vec<_>, _>>()?;
+        let selectors_info = self.read_selectors_info()?;
+        let quotient_degree_factor = self.read_usize()?;
+        let num_gate_constraints = self.read_usize()?;
+        let num_constants = self.read_usize()?;
+        let num_public_inputs = self.read_usize()?;
+        let num_k_is = self.read_usize()?;
+        let k_is = self.read_field_vec(num_k_is)?;
+        let num_partial_products = self.read_usize()?;
+        let num_lookup_polys = self.read_usize()?;
+        Ok(CommonCircuitData {
+            config,
+            fri_params,
+            gates,
+            selectors_info,
+            quotient_degree_factor,
+            num_gate_constraints,
+            num_constants,
+            num_public_inputs,
+            k_is,
+            num_partial_products,
+            num_lookup_polys,
+        })
+    }
+
+    /// Reads a value of type [`VerifierOnlyCircuitData`] from `self`.
+    #[inline]
+    fn read_verifier_only_circuit_data<F, C, const D: usize>(
+        &mut self,
+    ) -> IoResult<VerifierOnlyCircuitData<C, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let cap_height = self.read_usize()?;
+        let constants_sigmas_cap = self.read_merkle_cap(cap_height)?;
+        let circuit_digest = self.read_hash::<F, C::Hasher>()?;
+        Ok(VerifierOnlyCircuitData {
+            constants_sigmas_cap,
+            circuit_digest,
+        })
+    }
+
+    /// Reads a generator from `self`, using `generator_serializer` to decode its type.
+    fn read_generator<F, const D: usize>(
+        &mut self,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<Box<dyn WitnessGenerator<F>>>
+    where
+        F: RichField + Extendable<D>;
+
+    /// Reads a value of type [`Target`] from `self`.
+    #[inline]
+    fn read_target(&mut self) -> IoResult<Target> {
+        match self.read_u8()? {
+            0 => {
+                let row = self.read_usize()?;
+                let column = self.read_usize()?;
+                Ok(Target::wire(row, column))
+            }
+            1 => {
+                let index = self.read_usize()?;
+                Ok(Target::VirtualTarget { index })
+            }
+            _ => Err(IoError),
+        }
+    }
+
+    /// Reads a vector of [`Target`]s from `self`, prefixed by its length.
+    #[inline]
+    fn read_target_vec(&mut self) -> IoResult<Vec<Target>> {
+        let length = self.read_usize()?;
+        (0..length).map(|_| self.read_target()).collect()
+    }
+
+    /// Reads a [`LookupTable`] from `self`, prefixed by its length.
+    #[inline]
+    fn read_lut(&mut self) -> IoResult<LookupTable> {
+        let length = self.read_usize()?;
+        let lut = (0..length)
+            .map(|_| Ok((self.read_u16()?, self.read_u16()?)))
+            .collect::<IoResult<Vec<_>>>()?;
+        Ok(Arc::new(lut))
+    }
+
+    /// Reads a value of type [`ExtensionTarget`] from `self`.
+    #[inline]
+    fn read_target_ext<const D: usize>(&mut self) -> IoResult<ExtensionTarget<D>> {
+        let mut arr = [Target::wire(0, 0); D];
+        for a in arr.iter_mut() {
+            *a = self.read_target()?;
+        }
+        Ok(ExtensionTarget(arr))
+    }
+
+    /// Reads a vector of [`ExtensionTarget`]s from `self`, prefixed by its length.
+    #[inline]
+    fn read_target_ext_vec<const D: usize>(&mut self) -> IoResult<Vec<ExtensionTarget<D>>> {
+        let length = self.read_usize()?;
+        (0..length).map(|_| self.read_target_ext::<D>()).collect()
+    }
+
+    /// Reads a value of type [`BoolTarget`] from `self`.
+    #[inline]
+    fn read_target_bool(&mut self) -> IoResult<BoolTarget> {
+        Ok(BoolTarget::new_unsafe(self.read_target()?))
+    }
+
+    /// Reads a vector of [`BoolTarget`]s from `self`, prefixed by its length.
+    #[inline]
+    fn read_target_bool_vec(&mut self) -> IoResult<Vec<BoolTarget>> {
+        let length = self.read_usize()?;
+        (0..length).map(|_| self.read_target_bool()).collect()
+    }
+
+    /// Reads a value of type [`HashOutTarget`] from `self`.
+    #[inline]
+    fn read_target_hash(&mut self) -> IoResult<HashOutTarget> {
+        let mut elements = [Target::wire(0, 0); 4];
+        for a in elements.iter_mut() {
+            *a = self.read_target()?;
+        }
+        Ok(HashOutTarget { elements })
+    }
+
+    /// Reads a value of type [`MerkleCapTarget`] from `self`.
+    #[inline]
+    fn read_target_merkle_cap(&mut self) -> IoResult<MerkleCapTarget> {
+        let length = self.read_usize()?;
+        Ok(MerkleCapTarget(
+            (0..length)
+                .map(|_| self.read_target_hash())
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+
+    /// Reads a value of type [`MerkleProofTarget`] from `self`.
+    #[inline]
+    fn read_target_merkle_proof(&mut self) -> IoResult<MerkleProofTarget> {
+        let length = self.read_usize()?;
+        Ok(MerkleProofTarget {
+            siblings: (0..length)
+                .map(|_| self.read_target_hash())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Reads a value of type [`OpeningSetTarget`] from `self`.
+    #[inline]
+    fn read_target_opening_set<const D: usize>(&mut self) -> IoResult<OpeningSetTarget<D>> {
+        let constants = self.read_target_ext_vec::<D>()?;
+        let plonk_sigmas = self.read_target_ext_vec::<D>()?;
+        let wires = self.read_target_ext_vec::<D>()?;
+        let plonk_zs = self.read_target_ext_vec::<D>()?;
+        let plonk_zs_next = self.read_target_ext_vec::<D>()?;
+        let partial_products = self.read_target_ext_vec::<D>()?;
+        let lookup_zs = self.read_target_ext_vec::<D>()?;
+        let lookup_zs_next = self.read_target_ext_vec::<D>()?;
+        let lookup_helpers = self.read_target_ext_vec::<D>()?;
+        let quotient_polys = self.read_target_ext_vec::<D>()?;
+        Ok(OpeningSetTarget {
+            constants,
+            plonk_sigmas,
+            wires,
+            plonk_zs,
+            plonk_zs_next,
+            partial_products,
+            lookup_zs,
+            lookup_zs_next,
+            lookup_helpers,
+            quotient_polys,
+        })
+    }
+
+    /// Reads a value of type [`FriInitialTreeProofTarget`] from `self`.
+    #[inline]
+    fn read_target_fri_initial_proof(&mut self) -> IoResult<FriInitialTreeProofTarget> {
+        let length = self.read_usize()?;
+        let evals_proofs = (0..length)
+            .map(|_| {
+                let v = self.read_target_vec()?;
+                let p = self.read_target_merkle_proof()?;
+                Ok((v, p))
+            })
+            .collect::<IoResult<Vec<_>>>()?;
+        Ok(FriInitialTreeProofTarget { evals_proofs })
+    }
+
+    /// Reads a value of type [`FriQueryStepTarget`] from `self`.
+    #[inline]
+    fn read_target_fri_query_step<const D: usize>(&mut self) -> IoResult<FriQueryStepTarget<D>> {
+        let evals = self.read_target_ext_vec::<D>()?;
+        let merkle_proof = self.read_target_merkle_proof()?;
+        Ok(FriQueryStepTarget {
+            evals,
+            merkle_proof,
+        })
+    }
+
+    /// Reads a vector of [`FriQueryRoundTarget`]s from `self`, prefixed by its length.
+    #[inline]
+    fn read_target_fri_query_rounds<const D: usize>(
+        &mut self,
+    ) -> IoResult<Vec<FriQueryRoundTarget<D>>> {
+        let num_query_rounds = self.read_usize()?;
+        (0..num_query_rounds)
+            .map(|_| {
+                let initial_trees_proof = self.read_target_fri_initial_proof()?;
+                let num_steps = self.read_usize()?;
+                let steps = (0..num_steps)
+                    .map(|_| self.read_target_fri_query_step::<D>())
+                    .collect::<Result<_, _>>()?;
+                Ok(FriQueryRoundTarget {
+                    initial_trees_proof,
+                    steps,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads a value of type [`FriProofTarget`] from `self`.
+    #[inline]
+    fn read_target_fri_proof<const D: usize>(&mut self) -> IoResult<FriProofTarget<D>> {
+        let num_caps = self.read_usize()?;
+        let commit_phase_merkle_caps = (0..num_caps)
+            .map(|_| self.read_target_merkle_cap())
+            .collect::<Result<Vec<_>, _>>()?;
+        let query_round_proofs = self.read_target_fri_query_rounds::<D>()?;
+        let final_poly = PolynomialCoeffsExtTarget(self.read_target_ext_vec::<D>()?);
+        let pow_witness = self.read_target()?;
+        Ok(FriProofTarget {
+            commit_phase_merkle_caps,
+            query_round_proofs,
+            final_poly,
+            pow_witness,
+        })
+    }
+
+    /// Reads a value of type [`ProofTarget`] from `self`.
+    #[inline]
+    fn read_target_proof<const D: usize>(&mut self) -> IoResult<ProofTarget<D>> {
+        let wires_cap = self.read_target_merkle_cap()?;
+        let plonk_zs_partial_products_cap = self.read_target_merkle_cap()?;
+        let quotient_polys_cap = self.read_target_merkle_cap()?;
+        let openings = self.read_target_opening_set::<D>()?;
+        let opening_proof = self.read_target_fri_proof::<D>()?;
+        Ok(ProofTarget {
+            wires_cap,
+            plonk_zs_partial_products_cap,
+            quotient_polys_cap,
+            openings,
+            opening_proof,
+        })
+    }
+
+    /// Reads a value of type [`ProofWithPublicInputsTarget`] from `self`.
+    #[inline]
+    fn read_target_proof_with_public_inputs<const D: usize>(
+        &mut self,
+    ) -> IoResult<ProofWithPublicInputsTarget<D>> {
+        let proof = self.read_target_proof::<D>()?;
+        let public_inputs = self.read_target_vec()?;
+        Ok(ProofWithPublicInputsTarget {
+            proof,
+            public_inputs,
+        })
+    }
+
+    /// Reads a value of type [`VerifierCircuitTarget`] from `self`.
+    #[inline]
+    fn read_target_verifier_circuit(&mut self) -> IoResult<VerifierCircuitTarget> {
+        let constants_sigmas_cap = self.read_target_merkle_cap()?;
+        let circuit_digest = self.read_target_hash()?;
+        Ok(VerifierCircuitTarget {
+            constants_sigmas_cap,
+            circuit_digest,
+        })
+    }
+
+    /// Reads a vector of vectors of elements from the field `F` from `self`, each prefixed by its
+    /// length.
+    #[inline]
+    fn read_field_vec_vec<F>(&mut self) -> IoResult<Vec<Vec<F>>>
+    where
+        F: Field64,
+    {
+        let length = self.read_usize()?;
+        (0..length)
+            .map(|_| {
+                let inner_length = self.read_usize()?;
+                self.read_field_vec(inner_length)
+            })
+            .collect()
+    }
+
+    /// Reads a value of type [`MerkleTree`] from `self`.
+    #[inline]
+    fn read_merkle_tree<F, H>(&mut self) -> IoResult<MerkleTree<F, H>>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        let leaves = self.read_field_vec_vec()?;
+        let num_digests = self.read_usize()?;
+        let digests = (0..num_digests)
+            .map(|_| self.read_hash::<F, H>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let cap_height = self.read_usize()?;
+        let cap = self.read_merkle_cap(cap_height)?;
+        Ok(MerkleTree {
+            leaves,
+            digests,
+            cap,
+        })
+    }
+
+    /// Reads a value of type [`PolynomialBatch`] from `self`.
+    #[inline]
+    fn read_polynomial_batch<F, C, const D: usize>(&mut self) -> IoResult<PolynomialBatch<F, C, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let polynomials = self
+            .read_field_vec_vec()?
+            .into_iter()
+            .map(PolynomialCoeffs::new)
+            .collect();
+        let merkle_tree = self.read_merkle_tree()?;
+        let degree_log = self.read_usize()?;
+        let rate_bits = self.read_usize()?;
+        let blinding = self.read_bool()?;
+        Ok(PolynomialBatch {
+            polynomials,
+            merkle_tree,
+            degree_log,
+            rate_bits,
+            blinding,
+        })
+    }
+
+    /// Reads a value of type [`ProverOnlyCircuitData`] from `self`, using `generator_serializer`
+    /// to decode the circuit's witness generators.
+    #[inline]
+    fn read_prover_only_circuit_data<F, C, const D: usize>(
+        &mut self,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<ProverOnlyCircuitData<F, C, D>>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let num_generators = self.read_usize()?;
+        let generators = (0..num_generators)
+            .map(|_| self.read_generator(generator_serializer))
+            .collect::<Result<Vec<_>, _>>()?;
+        let num_watches = self.read_usize()?;
+        let generator_indices_by_watches = (0..num_watches)
+            .map(|_| {
+                let k = self.read_usize()?;
+                Ok((k, self.read_usize_vec()?))
+            })
+            .collect::<IoResult<BTreeMap<_, _>>>()?;
+        let constants_sigmas_commitment = self.read_polynomial_batch()?;
+        let sigmas = self.read_field_vec_vec()?;
+        let subgroup_len = self.read_usize()?;
+        let subgroup = self.read_field_vec(subgroup_len)?;
+        let public_inputs = self.read_target_vec()?;
+        let representative_map = self.read_usize_vec()?;
+        let fft_root_table = if self.read_bool()? {
+            Some(self.read_field_vec_vec()?)
+        } else {
+            None
+        };
+        let circuit_digest = self.read_hash::<F, C::Hasher>()?;
+        Ok(ProverOnlyCircuitData {
+            generators,
+            generator_indices_by_watches,
+            constants_sigmas_commitment,
+            sigmas,
+            subgroup,
+            public_inputs,
+            representative_map,
+            fft_root_table,
+            circuit_digest,
+        })
+    }
+}
+
+/// Writing
+pub trait Write {
+    /// Error Type
+    type Error;
+
+    /// Writes all `bytes` to `self`.
+    fn write_all(&mut self, bytes: &[u8]) -> IoResult<()>;
+
+    /// Writes a byte `x` to `self`.
+    #[inline]
+    fn write_u8(&mut self, x: u8) -> IoResult<()> {
+        self.write_all(&[x])
+    }
+
+    /// Writes a `u16` `x` to `self`.
+    #[inline]
+    fn write_u16(&mut self, x: u16) -> IoResult<()> {
+        self.write_all(&x.to_le_bytes())
+    }
+
+    /// Writes a word `x` to `self.`
+    #[inline]
+    fn write_u32(&mut self, x: u32) -> IoResult<()> {
+        self.write_all(&x.to_le_bytes())
+    }
+
+    /// Writes a `bool` `x` to `self`.
+    #[inline]
+    fn write_bool(&mut self, x: bool) -> IoResult<()> {
+        self.write_u8(u8::from(x))
+    }
+
+    /// Writes a `usize` `x` to `self`, encoded as a little-endian `u64`.
+    #[inline]
+    fn write_usize(&mut self, x: usize) -> IoResult<()> {
+        self.write_all(&(x as u64).to_le_bytes())
+    }
+
+    /// Writes a vector `v` of `usize` values to `self`, prefixed by its length.
+    #[inline]
+    fn write_usize_vec(&mut self, v: &[usize]) -> IoResult<()> {
+        self.write_usize(v.len())?;
+        for &a in v {
+            self.write_usize(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an element `x` from the field `F` to `self`.
+    #[inline]
+    fn write_field<F>(&mut self, x: F) -> IoResult<()>
+    where
+        F: PrimeField64,
+    {
+        self.write_all(&x.to_canonical_u64().to_le_bytes())
+    }
+
+    /// Writes a vector `v` of elements from the field `F` to `self`.
+    #[inline]
+    fn write_field_vec<F>(&mut self, v: &[F]) -> IoResult<()>
+    where
+        F: PrimeField64,
+    {
+        for &a in v {
+            self.write_field(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an element `x` from the field extension of `F` to `self`.
+    #[inline]
+    fn write_field_ext<F, const D: usize>(&mut self, x: F::Extension) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+    {
+        for &a in &x.to_basefield_array() {
+            self.write_field(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a vector `v` of elements from the field extension of `F` to `self`.
+    #[inline]
+    fn write_field_ext_vec<F, const D: usize>(&mut self, v: &[F::Extension]) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+    {
+        for &a in v {
+            self.write_field_ext::<F, D>(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a hash `h` to `self`.
+    #[inline]
+    fn write_hash<F, H>(&mut self, h: H::Hash) -> IoResult<()>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        self.write_all(&h.to_bytes())
+    }
+
+    /// Writes `cap`, a value of type [`MerkleCap`], to `self`.
+    #[inline]
+    fn write_merkle_cap<F, H>(&mut self, cap: &MerkleCap<F, H>) -> IoResult<()>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        for &a in &cap.0 {
+            self.write_hash::<F, H>(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `os` of type [`OpeningSet`] to `self.`
+    #[inline]
+    fn write_opening_set<F, const D: usize>(&mut self, os: &OpeningSet<F, D>) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+    {
+        self.write_field_ext_vec::<F, D>(&os.constants)?;
+        self.write_field_ext_vec::<F, D>(&os.plonk_sigmas)?;
+        self.write_field_ext_vec::<F, D>(&os.wires)?;
+        self.write_field_ext_vec::<F, D>(&os.plonk_zs)?;
+        self.write_field_ext_vec::<F, D>(&os.plonk_zs_next)?;
+        self.write_field_ext_vec::<F, D>(&os.partial_products)?;
+        self.write_field_ext_vec::<F, D>(&os.lookup_zs)?;
+        self.write_field_ext_vec::<F, D>(&os.lookup_zs_next)?;
+        self.write_field_ext_vec::<F, D>(&os.lookup_helpers)?;
+        self.write_field_ext_vec::<F, D>(&os.quotient_polys)
+    }
+
+    /// Writes a value `p` of type [`MerkleProof`] to `self.`
+    #[inline]
+    fn write_merkle_proof<F, H>(&mut self, p: &MerkleProof<F, H>) -> IoResult<()>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        let length = p.siblings.len();
+        self.write_u8(
+            length
+                .try_into()
+                .expect("Merkle proof length must fit in u8."),
+        )?;
+        for &h in &p.siblings {
+            self.write_hash::<F, H>(h)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `fitp` of type [`FriInitialTreeProof`] to `self.`
+    #[inline]
+    fn write_fri_initial_proof<F, C, const D: usize>(
+        &mut self,
+        fitp: &FriInitialTreeProof<F, C::Hasher>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        for (v, p) in &fitp.evals_proofs {
+            self.write_field_vec(v)?;
+            self.write_merkle_proof(p)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `fqs` of type [`FriQueryStep`] to `self.`
+    #[inline]
+    fn write_fri_query_step<F, C, const D: usize>(
+        &mut self,
+        fqs: &FriQueryStep<F, C::Hasher, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        self.write_field_ext_vec::<F, D>(&fqs.evals)?;
+        self.write_merkle_proof(&fqs.merkle_proof)
+    }
+
+    /// Writes a value `fqrs` of type [`FriQueryRound`] to `self.`
+    #[inline]
+    fn write_fri_query_rounds<F, C, const D: usize>(
+        &mut self,
+        fqrs: &[FriQueryRound<F, C::Hasher, D>],
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        for fqr in fqrs {
+            self.write_fri_initial_proof::<F, C, D>(&fqr.initial_trees_proof)?;
+            for fqs in &fqr.steps {
+                self.write_fri_query_step::<F, C, D>(fqs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a value `fq` of type [`FriProof`] to `self.`
+    #[inline]
+    fn write_fri_proof<F, C, const D: usize>(
+        &mut self,
+        fp: &FriProof<F, C::Hasher, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        for cap in &fp.commit_phase_merkle_caps {
+            self.write_merkle_cap(cap)?;
+        }
+        self.write_fri_query_rounds::<F, C, D>(&fp.query_round_proofs)?;
+        self.write_field_ext_vec::<F, D>(&fp.final_poly.coeffs)?;
+        self.write_field(fp.pow_witness)
+    }
+
+    /// Writes a value `proof` of type [`Proof`] to `self.`
+    #[inline]
+    fn write_proof<F, C, const D: usize>(&mut self, proof: &Proof<F, C, D>) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        self.write_merkle_cap(&proof.wires_cap)?;
+        self.write_merkle_cap(&proof.plonk_zs_partial_products_cap)?;
+        self.write_merkle_cap(&proof.quotient_polys_cap)?;
+        self.write_opening_set(&proof.openings)?;
+        self.write_fri_proof::<F, C, D>(&proof.opening_proof)
+    }
+
+    /// Writes a value `proof_with_pis` of type [`ProofWithPublicInputs`] to `self.`
+    #[inline]
+    fn write_proof_with_public_inputs<F, C, const D: usize>(
+        &mut self,
+        proof_with_pis: &ProofWithPublicInputs<F, C, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let ProofWithPublicInputs {
+            proof,
+            public_inputs,
+        } = proof_with_pis;
+        self.write_proof(proof)?;
+        self.write_field_vec(public_inputs)
+    }
+
+    /// Writes a value `cfqrs` of type [`CompressedFriQueryRounds`] to `self.`
+    #[inline]
+    fn write_compressed_fri_query_rounds<F, C, const D: usize>(
+        &mut self,
+        cfqrs: &CompressedFriQueryRounds<F, C::Hasher, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        for &i in &cfqrs.indices {
+            self.write_u32(i as u32)?;
+        }
+        let mut initial_trees_proofs = cfqrs.initial_trees_proofs.iter().collect::<Vec<_>>();
+        initial_trees_proofs.sort_by_key(|&x| x.0);
+        for (_, itp) in initial_trees_proofs {
+            self.write_fri_initial_proof::<F, C, D>(itp)?;
+        }
+        for h in &cfqrs.steps {
+            let mut fri_query_steps = h.iter().collect::<Vec<_>>();
+            fri_query_steps.sort_by_key(|&x| x.0);
+            for (_, fqs) in fri_query_steps {
+                self.write_fri_query_step::<F, C, D>(fqs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a value `fq` of type [`CompressedFriProof`] to `self.`
+    #[inline]
+    fn write_compressed_fri_proof<F, C, const D: usize>(
+        &mut self,
+        fp: &CompressedFriProof<F, C::Hasher, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        for cap in &fp.commit_phase_merkle_caps {
+            self.write_merkle_cap(cap)?;
+        }
+        self.write_compressed_fri_query_rounds::<F, C, D>(&fp.query_round_proofs)?;
+        self.write_field_ext_vec::<F, D>(&fp.final_poly.coeffs)?;
+        self.write_field(fp.pow_witness)
+    }
+
+    /// Writes a value `proof` of type [`CompressedProof`] to `self.`
+    #[inline]
+    fn write_compressed_proof<F, C, const D: usize>(
+        &mut self,
+        proof: &CompressedProof<F, C, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        self.write_merkle_cap(&proof.wires_cap)?;
+        self.write_merkle_cap(&proof.plonk_zs_partial_products_cap)?;
+        self.write_merkle_cap(&proof.quotient_polys_cap)?;
+        self.write_opening_set(&proof.openings)?;
+        self.write_compressed_fri_proof::<F, C, D>(&proof.opening_proof)
+    }
+
+    /// Writes a value `proof_with_pis` of type [`CompressedProofWithPublicInputs`] to `self.`
+    #[inline]
+    fn write_compressed_proof_with_public_inputs<F, C, const D: usize>(
+        &mut self,
+        proof_with_pis: &CompressedProofWithPublicInputs<F, C, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let CompressedProofWithPublicInputs {
+            proof,
+            public_inputs,
+        } = proof_with_pis;
+        self.write_compressed_proof(proof)?;
+        self.write_field_vec(public_inputs)
+    }
+
+    /// Writes a gate `gate` to `self`, using `gate_serializer` to encode its type.
+    fn write_gate<F, const D: usize>(
+        &mut self,
+        gate: &GateRef<F, D>,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>;
+
+    /// Writes a value `strategy` of type [`FriReductionStrategy`] to `self.`
+    #[inline]
+    fn write_fri_reduction_strategy(&mut self, strategy: &FriReductionStrategy) -> IoResult<()> {
+        match strategy {
+            FriReductionStrategy::Fixed(reduction_arity_bits) => {
+                self.write_u8(0)?;
+                self.write_usize_vec(reduction_arity_bits)
+            }
+            &FriReductionStrategy::ConstantArityBits(arity_bits, final_poly_bits) => {
+                self.write_u8(1)?;
+                self.write_usize(arity_bits)?;
+                self.write_usize(final_poly_bits)
+            }
+            &FriReductionStrategy::MinSize(max_arity_bits) => {
+                self.write_u8(2)?;
+                self.write_bool(max_arity_bits.is_some())?;
+                match max_arity_bits {
+                    Some(max_arity_bits) => self.write_usize(max_arity_bits),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Writes a value `config` of type [`FriConfig`] to `self.`
+    #[inline]
+    fn write_fri_config(&mut self, config: &FriConfig) -> IoResult<()> {
+        let FriConfig {
+            rate_bits,
+            cap_height,
+            proof_of_work_bits,
+            reduction_strategy,
+            num_query_rounds,
+        } = config;
+        self.write_usize(*rate_bits)?;
+        self.write_usize(*cap_height)?;
+        self.write_u32(*proof_of_work_bits)?;
+        self.write_fri_reduction_strategy(reduction_strategy)?;
+        self.write_usize(*num_query_rounds)
+    }
+
+    /// Writes a value `params` of type [`FriParams`] to `self.`
+    #[inline]
+    fn write_fri_params(&mut self, params: &FriParams) -> IoResult<()> {
+        let FriParams {
+            config,
+            hiding,
+            degree_bits,
+            reduction_arity_bits,
+        } = params;
+        self.write_fri_config(config)?;
+        self.write_bool(*hiding)?;
+        self.write_usize(*degree_bits)?;
+        self.write_usize_vec(reduction_arity_bits)
+    }
+
+    /// Writes a value `config` of type [`CircuitConfig`] to `self.`
+    #[inline]
+    fn write_circuit_config(&mut self, config: &CircuitConfig) -> IoResult<()> {
+        let CircuitConfig {
+            num_wires,
+            num_routed_wires,
+            num_constants,
+            use_base_arithmetic_gate,
+            security_bits,
+            num_challenges,
+            zero_knowledge,
+            max_quotient_degree_factor,
+            fri_config,
+        } = config;
+        self.write_usize(*num_wires)?;
+        self.write_usize(*num_routed_wires)?;
+        self.write_usize(*num_constants)?;
+        self.write_bool(*use_base_arithmetic_gate)?;
+        self.write_usize(*security_bits)?;
+        self.write_usize(*num_challenges)?;
+        self.write_bool(*zero_knowledge)?;
+        self.write_usize(*max_quotient_degree_factor)?;
+        self.write_fri_config(fri_config)
+    }
+
+    /// Writes a value `selectors_info` of type [`SelectorsInfo`] to `self.`
+    #[inline]
+    fn write_selectors_info(&mut self, selectors_info: &SelectorsInfo) -> IoResult<()> {
+        let SelectorsInfo {
+            selector_indices,
+            groups,
+        } = selectors_info;
+        self.write_usize_vec(selector_indices)?;
+        self.write_usize(groups.len())?;
+        for group in groups {
+            self.write_usize(group.start)?;
+            self.write_usize(group.end)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `common_data` of type [`CommonCircuitData`] to `self`, using
+    /// `gate_serializer` to encode the circuit's gates.
+    #[inline]
+    fn write_common_circuit_data<F, const D: usize>(
+        &mut self,
+        common_data: &CommonCircuitData<F, D>,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+    {
+        let CommonCircuitData {
+            config,
+            fri_params,
+            gates,
+            selectors_info,
+            quotient_degree_factor,
+            num_gate_constraints,
+            num_constants,
+            num_public_inputs,
+            k_is,
+            num_partial_products,
+            num_lookup_polys,
+        } = common_data;
+        self.write_circuit_config(config)?;
+        self.write_fri_params(fri_params)?;
+        self.write_usize(gates.len())?;
+        for gate in gates {
+            self.write_gate(gate, gate_serializer)?;
+        }
+        self.write_selectors_info(selectors_info)?;
+        self.write_usize(*quotient_degree_factor)?;
+        self.write_usize(*num_gate_constraints)?;
+        self.write_usize(*num_constants)?;
+        self.write_usize(*num_public_inputs)?;
+        self.write_usize(k_is.len())?;
+        self.write_field_vec(k_is)?;
+        self.write_usize(*num_partial_products)?;
+        self.write_usize(*num_lookup_polys)
+    }
+
+    /// Writes a value `verifier_data` of type [`VerifierOnlyCircuitData`] to `self.`
+    #[inline]
+    fn write_verifier_only_circuit_data<F, C, const D: usize>(
+        &mut self,
+        verifier_data: &VerifierOnlyCircuitData<C, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let VerifierOnlyCircuitData {
+            constants_sigmas_cap,
+            circuit_digest,
+        } = verifier_data;
+        self.write_usize(log2_strict(constants_sigmas_cap.0.len()))?;
+        self.write_merkle_cap(constants_sigmas_cap)?;
+        self.write_hash::<F, C::Hasher>(*circuit_digest)
+    }
+
+    /// Writes a generator `generator` to `self`, using `generator_serializer` to encode its type.
+    fn write_generator<F, const D: usize>(
+        &mut self,
+        generator: &dyn WitnessGenerator<F>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>;
+
+    /// Writes a target `x` to `self`.
+    #[inline]
+    fn write_target(&mut self, x: Target) -> IoResult<()> {
+        match x {
+            Target::Wire(Wire { row, column }) => {
+                self.write_u8(0)?;
+                self.write_usize(row)?;
+                self.write_usize(column)
+            }
+            Target::VirtualTarget { index } => {
+                self.write_u8(1)?;
+                self.write_usize(index)
+            }
+        }
+    }
+
+    /// Writes a vector `v` of targets to `self`, prefixed by its length.
+    #[inline]
+    fn write_target_vec(&mut self, v: &[Target]) -> IoResult<()> {
+        self.write_usize(v.len())?;
+        for &a in v {
+            self.write_target(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a [`LookupTable`] `lut` to `self`, prefixed by its length.
+    #[inline]
+    fn write_lut(&mut self, lut: &[(u16, u16)]) -> IoResult<()> {
+        self.write_usize(lut.len())?;
+        for &(input, output) in lut {
+            self.write_u16(input)?;
+            self.write_u16(output)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an extension target `x` to `self`.
+    #[inline]
+    fn write_target_ext<const D: usize>(&mut self, x: ExtensionTarget<D>) -> IoResult<()> {
+        for a in x.0 {
+            self.write_target(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a vector `v` of extension targets to `self`, prefixed by its length.
+    #[inline]
+    fn write_target_ext_vec<const D: usize>(&mut self, v: &[ExtensionTarget<D>]) -> IoResult<()> {
+        self.write_usize(v.len())?;
+        for &a in v {
+            self.write_target_ext(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a boolean target `x` to `self`.
+    #[inline]
+    fn write_target_bool(&mut self, x: BoolTarget) -> IoResult<()> {
+        self.write_target(x.target)
+    }
+
+    /// Writes a vector `v` of boolean targets to `self`, prefixed by its length.
+    #[inline]
+    fn write_target_bool_vec(&mut self, v: &[BoolTarget]) -> IoResult<()> {
+        self.write_usize(v.len())?;
+        for &a in v {
+            self.write_target_bool(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a hash target `h` to `self`.
+    #[inline]
+    fn write_target_hash(&mut self, h: &HashOutTarget) -> IoResult<()> {
+        for &a in &h.elements {
+            self.write_target(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `cap`, a value of type [`MerkleCapTarget`], to `self`.
+    #[inline]
+    fn write_target_merkle_cap(&mut self, cap: &MerkleCapTarget) -> IoResult<()> {
+        self.write_usize(cap.0.len())?;
+        for a in &cap.0 {
+            self.write_target_hash(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `p` of type [`MerkleProofTarget`] to `self.`
+    #[inline]
+    fn write_target_merkle_proof(&mut self, p: &MerkleProofTarget) -> IoResult<()> {
+        self.write_usize(p.siblings.len())?;
+        for h in &p.siblings {
+            self.write_target_hash(h)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `os` of type [`OpeningSetTarget`] to `self.`
+    #[inline]
+    fn write_target_opening_set<const D: usize>(
+        &mut self,
+        os: &OpeningSetTarget<D>,
+    ) -> IoResult<()> {
+        self.write_target_ext_vec::<D>(&os.constants)?;
+        self.write_target_ext_vec::<D>(&os.plonk_sigmas)?;
+        self.write_target_ext_vec::<D>(&os.wires)?;
+        self.write_target_ext_vec::<D>(&os.plonk_zs)?;
+        self.write_target_ext_vec::<D>(&os.plonk_zs_next)?;
+        self.write_target_ext_vec::<D>(&os.partial_products)?;
+        self.write_target_ext_vec::<D>(&os.lookup_zs)?;
+        self.write_target_ext_vec::<D>(&os.lookup_zs_next)?;
+        self.write_target_ext_vec::<D>(&os.lookup_helpers)?;
+        self.write_target_ext_vec::<D>(&os.quotient_polys)
+    }
+
+    /// Writes a value `fitp` of type [`FriInitialTreeProofTarget`] to `self.`
+    #[inline]
+    fn write_target_fri_initial_proof(&mut self, fitp: &FriInitialTreeProofTarget) -> IoResult<()> {
+        self.write_usize(fitp.evals_proofs.len())?;
+        for (v, p) in &fitp.evals_proofs {
+            self.write_target_vec(v)?;
+            self.write_target_merkle_proof(p)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `fqs` of type [`FriQueryStepTarget`] to `self.`
+    #[inline]
+    fn write_target_fri_query_step<const D: usize>(
+        &mut self,
+        fqs: &FriQueryStepTarget<D>,
+    ) -> IoResult<()> {
+        self.write_target_ext_vec(&fqs.evals)?;
+        self.write_target_merkle_proof(&fqs.merkle_proof)
+    }
+
+    /// Writes a vector `fqrs` of [`FriQueryRoundTarget`]s to `self`, prefixed by its length.
+    #[inline]
+    fn write_target_fri_query_rounds<const D: usize>(
+        &mut self,
+        fqrs: &[FriQueryRoundTarget<D>],
+    ) -> IoResult<()> {
+        self.write_usize(fqrs.len())?;
+        for fqr in fqrs {
+            self.write_target_fri_initial_proof(&fqr.initial_trees_proof)?;
+            self.write_usize(fqr.steps.len())?;
+            for fqs in &fqr.steps {
+                self.write_target_fri_query_step(fqs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a value `fp` of type [`FriProofTarget`] to `self.`
+    #[inline]
+    fn write_target_fri_proof<const D: usize>(&mut self, fp: &FriProofTarget<D>) -> IoResult<()> {
+        self.write_usize(fp.commit_phase_merkle_caps.len())?;
+        for cap in &fp.commit_phase_merkle_caps {
+            self.write_target_merkle_cap(cap)?;
+        }
+        self.write_target_fri_query_rounds(&fp.query_round_proofs)?;
+        self.write_target_ext_vec(&fp.final_poly.0)?;
+        self.write_target(fp.pow_witness)
+    }
+
+    /// Writes a value `proof` of type [`ProofTarget`] to `self.`
+    #[inline]
+    fn write_target_proof<const D: usize>(&mut self, proof: &ProofTarget<D>) -> IoResult<()> {
+        self.write_target_merkle_cap(&proof.wires_cap)?;
+        self.write_target_merkle_cap(&proof.plonk_zs_partial_products_cap)?;
+        self.write_target_merkle_cap(&proof.quotient_polys_cap)?;
+        self.write_target_opening_set(&proof.openings)?;
+        self.write_target_fri_proof(&proof.opening_proof)
+    }
+
+    /// Writes a value `proof_with_pis` of type [`ProofWithPublicInputsTarget`] to `self.`
+    #[inline]
+    fn write_target_proof_with_public_inputs<const D: usize>(
+        &mut self,
+        proof_with_pis: &ProofWithPublicInputsTarget<D>,
+    ) -> IoResult<()> {
+        let ProofWithPublicInputsTarget {
+            proof,
+            public_inputs,
+        } = proof_with_pis;
+        self.write_target_proof(proof)?;
+        self.write_target_vec(public_inputs)
+    }
+
+    /// Writes a value `verifier_circuit` of type [`VerifierCircuitTarget`] to `self.`
+    #[inline]
+    fn write_target_verifier_circuit(
+        &mut self,
+        verifier_circuit: &VerifierCircuitTarget,
+    ) -> IoResult<()> {
+        let VerifierCircuitTarget {
+            constants_sigmas_cap,
+            circuit_digest,
+        } = verifier_circuit;
+        self.write_target_merkle_cap(constants_sigmas_cap)?;
+        self.write_target_hash(circuit_digest)
+    }
+
+    /// Writes a vector `v` of vectors of elements from the field `F` to `self`, each prefixed by
+    /// its length.
+    #[inline]
+    fn write_field_vec_vec<F>(&mut self, v: &[Vec<F>]) -> IoResult<()>
+    where
+        F: PrimeField64,
+    {
+        self.write_usize(v.len())?;
+        for a in v {
+            self.write_usize(a.len())?;
+            self.write_field_vec(a)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value `tree` of type [`MerkleTree`] to `self.`
+    #[inline]
+    fn write_merkle_tree<F, H>(&mut self, tree: &MerkleTree<F, H>) -> IoResult<()>
+    where
+        F: RichField,
+        H: Hasher<F>,
+    {
+        let MerkleTree {
+            leaves,
+            digests,
+            cap,
+        } = tree;
+        self.write_field_vec_vec(leaves)?;
+        self.write_usize(digests.len())?;
+        for &h in digests {
+            self.write_hash::<F, H>(h)?;
+        }
+        self.write_usize(log2_strict(cap.0.len()))?;
+        self.write_merkle_cap(cap)
+    }
+
+    /// Writes a value `poly_batch` of type [`PolynomialBatch`] to `self.`
+    #[inline]
+    fn write_polynomial_batch<F, C, const D: usize>(
+        &mut self,
+        poly_batch: &PolynomialBatch<F, C, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let PolynomialBatch {
+            polynomials,
+            merkle_tree,
+            degree_log,
+            rate_bits,
+            blinding,
+        } = poly_batch;
+        self.write_usize(polynomials.len())?;
+        for poly in polynomials {
+            self.write_usize(poly.coeffs.len())?;
+            self.write_field_vec(&poly.coeffs)?;
+        }
+        self.write_merkle_tree(merkle_tree)?;
+        self.write_usize(*degree_log)?;
+        self.write_usize(*rate_bits)?;
+        self.write_bool(*blinding)
+    }
+
+    /// Writes a value `prover_only_circuit_data` of type [`ProverOnlyCircuitData`] to `self`,
+    /// using `generator_serializer` to encode the circuit's witness generators.
+    #[inline]
+    fn write_prover_only_circuit_data<F, C, const D: usize>(
+        &mut self,
+        prover_only_circuit_data: &ProverOnlyCircuitData<F, C, D>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+    {
+        let ProverOnlyCircuitData {
+            generators,
+            generator_indices_by_watches,
+            constants_sigmas_commitment,
+            sigmas,
+            subgroup,
+            public_inputs,
+            representative_map,
+            fft_root_table,
+            circuit_digest,
+        } = prover_only_circuit_data;
+        self.write_usize(generators.len())?;
+        for generator in generators {
+            self.write_generator(generator.as_ref(), generator_serializer)?;
+        }
+        self.write_usize(generator_indices_by_watches.len())?;
+        for (k, v) in generator_indices_by_watches {
+            self.write_usize(*k)?;
+            self.write_usize_vec(v)?;
+        }
+        self.write_polynomial_batch(constants_sigmas_commitment)?;
+        self.write_field_vec_vec(sigmas)?;
+        self.write_usize(subgroup.len())?;
+        self.write_field_vec(subgroup)?;
+        self.write_target_vec(public_inputs)?;
+        self.write_usize_vec(representative_map)?;
+        self.write_bool(fft_root_table.is_some())?;
+        if let Some(fft_root_table) = fft_root_table {
+            self.write_field_vec_vec(fft_root_table)?;
+        }
+        self.write_hash::<F, C::Hasher>(*circuit_digest)
+    }
+}
+
+impl Write for Vec<u8> {
+    type Error = Infallible;
+
+    #[inline]
+    fn write_all(&mut self, bytes: &[u8]) -> IoResult<()> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_gate<F, const D: usize>(
+        &mut self,
+        gate: &GateRef<F, D>,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+    {
+        gate_serializer.write_gate(self, gate)
+    }
+
+    #[inline]
+    fn write_generator<F, const D: usize>(
+        &mut self,
+        generator: &dyn WitnessGenerator<F>,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<()>
+    where
+        F: RichField + Extendable<D>,
+    {
+        generator_serializer.write_generator(self, generator)
+    }
+}
+
+/// Buffer
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Buffer {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+#[cfg(feature = "std")]
+impl Buffer {
+    /// Builds a new [`Buffer`] over `buffer`.
+    #[inline]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Returns the inner buffer.
+    #[inline]
+    pub fn bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Remaining for Buffer {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+}
+
+impl Read for Buffer {
+    #[inline]
+    fn read_exact(&mut self, bytes: &mut [u8]) -> IoResult<()> {
+        let n = bytes.len();
+        if self.remaining() < n {
+            Err(IoError)
+        } else {
+            bytes.copy_from_slice(&self.bytes[self.pos..][..n]);
+            self.pos += n;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn read_gate<F, const D: usize>(
+        &mut self,
+        gate_serializer: &dyn GateSerializer<F, D>,
+    ) -> IoResult<GateRef<F, D>>
+    where
+        F: RichField + Extendable<D>,
+    {
+        gate_serializer.read_gate(self)
+    }
+
+    #[inline]
+    fn read_generator<F, const D: usize>(
+        &mut self,
+        generator_serializer: &dyn WitnessGeneratorSerializer<F, D>,
+    ) -> IoResult<Box<dyn WitnessGenerator<F>>>
+    where
+        F: RichField + Extendable<D>,
+    {
+        generator_serializer.read_generator(self)
+    }
+}
@@ -8,10 +8,13 @@ use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::{PartitionWitness, Witness};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 
 use crate::gates::add_many_u32::U32AddManyGate;
 use crate::gates::arithmetic_u32::U32ArithmeticGate;
+use crate::gates::bitwise_u32::{U32BitwiseGate, U32BitwiseOp};
 use crate::gates::subtraction_u32::U32SubtractionGate;
+use crate::serialization::{ReadU32, WriteU32};
 use crate::witness::GeneratedValuesU32;
 
 #[derive(Clone, Copy, Debug)]
@@ -60,6 +63,30 @@ pub trait CircuitBuilderU32<F: RichField + Extendable<D>, const D: usize> {
 
     // Returns x - y - borrow, as a pair (result, borrow), where borrow is 0 or 1 depending on whether borrowing from the next digit is required (iff y + borrow > x).
     fn sub_u32(&mut self, x: U32Target, y: U32Target, borrow: U32Target) -> (U32Target, U32Target);
+
+    /// Applies a bitwise operation to `x`, and to `y` if the operation has two inputs. This uses a
+    /// `U32BitwiseGate`, unless all the inputs are constant.
+    fn bitwise_u32(&mut self, op: U32BitwiseOp, x: U32Target, y: U32Target) -> U32Target;
+
+    fn and_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn or_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn xor_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn not_u32(&mut self, x: U32Target) -> U32Target;
+
+    /// Rotates `x` left by `n` bits.
+    fn rotl_u32(&mut self, x: U32Target, n: u32) -> U32Target;
+
+    /// Rotates `x` right by `n` bits.
+    fn rotr_u32(&mut self, x: U32Target, n: u32) -> U32Target;
+
+    /// Shifts `x` left by `n < 32` bits, discarding the bits shifted out.
+    fn shl_u32(&mut self, x: U32Target, n: u32) -> U32Target;
+
+    /// Shifts `x` right by `n < 32` bits.
+    fn shr_u32(&mut self, x: U32Target, n: u32) -> U32Target;
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderU32<F, D>
@@ -228,10 +255,81 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderU32<F, D>
 
         (output_result, output_borrow)
     }
+
+    fn bitwise_u32(&mut self, op: U32BitwiseOp, x: U32Target, y: U32Target) -> U32Target {
+        let inputs = [x, y];
+        let inputs = &inputs[..op.num_inputs()];
+
+        let constants: Option<Vec<u32>> = inputs
+            .iter()
+            .map(|input| {
+                self.target_as_constant(input.0)
+                    .map(|c| c.to_canonical_u64() as u32)
+            })
+            .collect();
+        if let Some(constants) = constants {
+            let y = constants.get(1).copied().unwrap_or(0);
+            return self.constant_u32(op.apply(constants[0], y));
+        }
+
+        let gate = U32BitwiseGate::<F, D>::new_from_config(&self.config, op);
+        let (row, copy) = self.find_slot(gate, &[], &[]);
+
+        for (k, input) in inputs.iter().enumerate() {
+            self.connect(Target::wire(row, gate.wire_ith_input_k(copy, k)), input.0);
+        }
+
+        U32Target(Target::wire(row, gate.wire_ith_output(copy)))
+    }
+
+    fn and_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        self.bitwise_u32(U32BitwiseOp::And, x, y)
+    }
+
+    fn or_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        self.bitwise_u32(U32BitwiseOp::Or, x, y)
+    }
+
+    fn xor_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        self.bitwise_u32(U32BitwiseOp::Xor, x, y)
+    }
+
+    fn not_u32(&mut self, x: U32Target) -> U32Target {
+        // For a 32-bit `x`, `!x = (2^32 - 1) - x`.
+        let max = self.constant(F::from_canonical_u32(u32::MAX));
+        U32Target(self.sub(max, x.0))
+    }
+
+    fn rotl_u32(&mut self, x: U32Target, n: u32) -> U32Target {
+        match n % 32 {
+            0 => x,
+            n => self.bitwise_u32(U32BitwiseOp::Rotl(n as u8), x, x),
+        }
+    }
+
+    fn rotr_u32(&mut self, x: U32Target, n: u32) -> U32Target {
+        self.rotl_u32(x, 32 - n % 32)
+    }
+
+    fn shl_u32(&mut self, x: U32Target, n: u32) -> U32Target {
+        assert!(n < 32, "Shift amount {n} is too large.");
+        if n == 0 {
+            return x;
+        }
+        self.bitwise_u32(U32BitwiseOp::Shl(n as u8), x, x)
+    }
+
+    fn shr_u32(&mut self, x: U32Target, n: u32) -> U32Target {
+        assert!(n < 32, "Shift amount {n} is too large.");
+        if n == 0 {
+            return x;
+        }
+        self.bitwise_u32(U32BitwiseOp::Shr(n as u8), x, x)
+    }
 }
 
 #[derive(Debug)]
-struct SplitToU32Generator<F: RichField + Extendable<D>, const D: usize> {
+pub struct SplitToU32Generator<F: RichField + Extendable<D>, const D: usize> {
     x: Target,
     low: U32Target,
     high: U32Target,
@@ -254,6 +352,24 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
         out_buffer.set_u32_target(self.low, low);
         out_buffer.set_u32_target(self.high, high);
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target(self.x)?;
+        dst.write_target_u32(self.low)?;
+        dst.write_target_u32(self.high)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let x = src.read_target()?;
+        let low = src.read_target_u32()?;
+        let high = src.read_target_u32()?;
+        Ok(Self {
+            x,
+            low,
+            high,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -266,6 +382,7 @@ mod tests {
     use rand::Rng;
 
     use super::*;
+    use crate::witness::WitnessU32;
 
     #[test]
     pub fn test_add_many_u32s() -> Result<()> {
@@ -300,4 +417,44 @@ mod tests {
         let proof = data.prove(pw).unwrap();
         data.verify(proof)
     }
+
+    #[test]
+    pub fn test_bitwise_u32s() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let mut rng = OsRng;
+        let x_value: u32 = rng.gen();
+        let y_value: u32 = rng.gen();
+        let x = builder.add_virtual_u32_target();
+        let y = builder.add_virtual_u32_target();
+        pw.set_u32_target(x, x_value);
+        pw.set_u32_target(y, y_value);
+
+        let results = [
+            (builder.and_u32(x, y), x_value & y_value),
+            (builder.or_u32(x, y), x_value | y_value),
+            (builder.xor_u32(x, y), x_value ^ y_value),
+            (builder.not_u32(x), !x_value),
+            (builder.rotl_u32(x, 5), x_value.rotate_left(5)),
+            (builder.rotr_u32(x, 5), x_value.rotate_right(5)),
+            (builder.rotr_u32(x, 0), x_value),
+            (builder.shl_u32(x, 9), x_value << 9),
+            (builder.shr_u32(x, 31), x_value >> 31),
+        ];
+        for (result, expected) in results {
+            let expected = builder.constant_u32(expected);
+            builder.connect_u32(result, expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
 }
@@ -1,3 +1,4 @@
 pub mod arithmetic_u32;
 pub mod multiple_comparison;
 pub mod range_check;
+pub mod sha256;
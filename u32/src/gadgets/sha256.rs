@@ -0,0 +1,237 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::ceil_div_usize;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gates::sha256_ch_maj::{Sha256ChMajGate, Sha256ChMajOp};
+use crate::gates::sha256_sigma::{Sha256Sigma, Sha256SigmaGate};
+
+/// The initial hash value of SHA-256.
+pub const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The round constants of SHA-256.
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 gadgets over `U32Target` words. Words are big-endian, as in the SHA-256 specification,
+/// so that e.g. the first byte of a message is the most significant byte of its first word.
+pub trait CircuitBuilderSha256<F: RichField + Extendable<D>, const D: usize> {
+    /// Computes one of the sigma functions of SHA-256.
+    fn sha256_sigma(&mut self, sigma: Sha256Sigma, x: U32Target) -> U32Target;
+
+    /// Computes `Ch(x, y, z) = (x & y) ^ (!x & z)`.
+    fn sha256_ch(&mut self, x: U32Target, y: U32Target, z: U32Target) -> U32Target;
+
+    /// Computes `Maj(x, y, z) = (x & y) ^ (x & z) ^ (y & z)`.
+    fn sha256_maj(&mut self, x: U32Target, y: U32Target, z: U32Target) -> U32Target;
+
+    /// Applies the SHA-256 compression function to `state`, with a 16-word message block.
+    fn sha256_compress(&mut self, state: [U32Target; 8], block: [U32Target; 16]) -> [U32Target; 8];
+
+    /// Computes the SHA-256 digest of a message which is already padded, i.e. whose length is a
+    /// multiple of 16 words.
+    fn sha256_padded(&mut self, padded_message: &[U32Target]) -> [U32Target; 8];
+
+    /// Computes the SHA-256 digest of a message made of whole 32-bit words, such as an 80-byte
+    /// Bitcoin block header, or the concatenation of two digests. The padding is added as
+    /// constants.
+    fn sha256(&mut self, message: &[U32Target]) -> [U32Target; 8];
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderSha256<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn sha256_sigma(&mut self, sigma: Sha256Sigma, x: U32Target) -> U32Target {
+        let gate = Sha256SigmaGate::<F, D>::new_from_config(&self.config, sigma);
+        let (row, copy) = self.find_slot(gate, &[], &[]);
+
+        self.connect(Target::wire(row, gate.wire_ith_input(copy)), x.0);
+
+        U32Target(Target::wire(row, gate.wire_ith_output(copy)))
+    }
+
+    fn sha256_ch(&mut self, x: U32Target, y: U32Target, z: U32Target) -> U32Target {
+        add_ch_maj(self, Sha256ChMajOp::Ch, [x, y, z])
+    }
+
+    fn sha256_maj(&mut self, x: U32Target, y: U32Target, z: U32Target) -> U32Target {
+        add_ch_maj(self, Sha256ChMajOp::Maj, [x, y, z])
+    }
+
+    fn sha256_compress(&mut self, state: [U32Target; 8], block: [U32Target; 16]) -> [U32Target; 8] {
+        let mut schedule = block.to_vec();
+        for t in 16..64 {
+            let s0 = self.sha256_sigma(Sha256Sigma::SmallSigma0, schedule[t - 15]);
+            let s1 = self.sha256_sigma(Sha256Sigma::SmallSigma1, schedule[t - 2]);
+            let (w, _) = self.add_many_u32(&[s1, schedule[t - 7], s0, schedule[t - 16]]);
+            schedule.push(w);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (t, &w) in schedule.iter().enumerate() {
+            let big_s1 = self.sha256_sigma(Sha256Sigma::BigSigma1, e);
+            let ch = self.sha256_ch(e, f, g);
+            let k = self.constant_u32(ROUND_CONSTANTS[t]);
+            let big_s0 = self.sha256_sigma(Sha256Sigma::BigSigma0, a);
+            let maj = self.sha256_maj(a, b, c);
+
+            // `e' = d + T1` and `a' = T1 + T2`, where `T1 = h + Σ1(e) + Ch(e, f, g) + K + W` and
+            // `T2 = Σ0(a) + Maj(a, b, c)`.
+            let t1 = [h, big_s1, ch, k, w];
+            let (new_e, _) = self.add_many_u32(&[&t1[..], &[d]].concat());
+            let (new_a, _) = self.add_many_u32(&[&t1[..], &[big_s0, maj]].concat());
+
+            h = g;
+            g = f;
+            f = e;
+            e = new_e;
+            d = c;
+            c = b;
+            b = a;
+            a = new_a;
+        }
+
+        let working = [a, b, c, d, e, f, g, h];
+        core::array::from_fn(|i| self.add_u32(state[i], working[i]).0)
+    }
+
+    fn sha256_padded(&mut self, padded_message: &[U32Target]) -> [U32Target; 8] {
+        assert_eq!(
+            padded_message.len() % 16,
+            0,
+            "The padded message must be a multiple of 16 words."
+        );
+
+        let mut state = SHA256_IV.map(|x| self.constant_u32(x));
+        for block in padded_message.chunks(16) {
+            state = self.sha256_compress(state, block.try_into().unwrap());
+        }
+        state
+    }
+
+    fn sha256(&mut self, message: &[U32Target]) -> [U32Target; 8] {
+        // Append a one bit, zeros, and the 64-bit length in bits, up to a multiple of 16 words.
+        let num_words = message.len();
+        let padded_len = ceil_div_usize(num_words + 3, 16) * 16;
+        let bit_len = 32 * num_words as u64;
+
+        let mut padded_message: Vec<U32Target> = message.to_vec();
+        padded_message.push(self.constant_u32(0x80000000));
+        while padded_message.len() < padded_len - 2 {
+            padded_message.push(self.zero_u32());
+        }
+        padded_message.push(self.constant_u32((bit_len >> 32) as u32));
+        padded_message.push(self.constant_u32(bit_len as u32));
+
+        self.sha256_padded(&padded_message)
+    }
+}
+
+fn add_ch_maj<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    op: Sha256ChMajOp,
+    inputs: [U32Target; 3],
+) -> U32Target {
+    let gate = Sha256ChMajGate::<F, D>::new_from_config(&builder.config, op);
+    let (row, copy) = builder.find_slot(gate, &[], &[]);
+
+    for (k, input) in inputs.into_iter().enumerate() {
+        builder.connect(Target::wire(row, gate.wire_ith_input_k(copy, k)), input.0);
+    }
+
+    U32Target(Target::wire(row, gate.wire_ith_output(copy)))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+    use crate::witness::WitnessU32;
+
+    /// Converts a message made of whole words to big-endian words.
+    fn to_words(message: &[u8]) -> Vec<u32> {
+        message
+            .chunks(4)
+            .map(|chunk| u32::from_be_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sha256() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        // Test vectors from FIPS 180-2, Appendix B. The second message spans two blocks.
+        let vectors: [(&[u8], [u32; 8]); 2] = [
+            (
+                b"",
+                [
+                    0xe3b0c442, 0x98fc1c14, 0x9afbf4c8, 0x996fb924, 0x27ae41e4, 0x649b934c,
+                    0xa495991b, 0x7852b855,
+                ],
+            ),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                [
+                    0x248d6a61, 0xd20638b8, 0xe5c02693, 0x0c3e6039, 0xa33ce459, 0x64ff2167,
+                    0xf6ecedd4, 0x19db06c1,
+                ],
+            ),
+        ];
+        for (message, expected) in vectors {
+            let words = to_words(message);
+            let message_targets = builder.add_virtual_u32_targets(words.len());
+            for (&t, &word) in message_targets.iter().zip(&words) {
+                pw.set_u32_target(t, word);
+            }
+
+            let digest = builder.sha256(&message_targets);
+            for (t, word) in digest.into_iter().zip(expected) {
+                let expected_word = builder.constant_u32(word);
+                builder.connect_u32(t, expected_word);
+            }
+        }
+
+        // "abc", padded by hand.
+        let mut padded_abc = [0; 16];
+        padded_abc[0] = 0x61626380;
+        padded_abc[15] = 0x18;
+        let padded_targets = padded_abc.map(|x| builder.constant_u32(x));
+        let digest = builder.sha256_padded(&padded_targets);
+        let expected = [
+            0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+            0xf20015ad,
+        ];
+        for (t, word) in digest.into_iter().zip(expected) {
+            let expected_word = builder.constant_u32(word);
+            builder.connect_u32(t, expected_word);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+}
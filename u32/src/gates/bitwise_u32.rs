@@ -0,0 +1,544 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Sub};
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
+use plonky2::gates::gate::Gate;
+use plonky2::gates::packed_util::PackedEvaluableBase;
+use plonky2::gates::util::StridedConstraintConsumer;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+    EvaluationVarsBasePacked,
+};
+use plonky2::util::serialization::{Buffer, IoError, IoResult, Read, Write};
+
+/// A bitwise operation on 32-bit words. The amounts of rotations and shifts are below 32.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum U32BitwiseOp {
+    And,
+    Or,
+    Xor,
+    /// Rotates the input left by the given amount.
+    Rotl(u8),
+    /// Shifts the input left by the given amount, discarding the high bits.
+    Shl(u8),
+    /// Shifts the input right by the given amount.
+    Shr(u8),
+}
+
+impl U32BitwiseOp {
+    /// The number of input words of the operation.
+    pub fn num_inputs(self) -> usize {
+        match self {
+            Self::And | Self::Or | Self::Xor => 2,
+            Self::Rotl(_) | Self::Shl(_) | Self::Shr(_) => 1,
+        }
+    }
+
+    /// Applies the operation. `y` is ignored by operations with a single input.
+    pub fn apply(self, x: u32, y: u32) -> u32 {
+        match self {
+            Self::And => x & y,
+            Self::Or => x | y,
+            Self::Xor => x ^ y,
+            Self::Rotl(n) => x.rotate_left(n as u32),
+            Self::Shl(n) => x << n,
+            Self::Shr(n) => x >> n,
+        }
+    }
+
+    /// For operations with a single input, the position in the output of bit `j` of the input, if
+    /// it is kept.
+    fn output_position(self, j: usize) -> Option<usize> {
+        match self {
+            Self::And | Self::Or | Self::Xor => unreachable!(),
+            Self::Rotl(n) => Some((j + n as usize) % 32),
+            Self::Shl(n) => Some(j + n as usize).filter(|&i| i < 32),
+            Self::Shr(n) => j.checked_sub(n as usize),
+        }
+    }
+
+    fn to_usizes(self) -> (usize, usize) {
+        match self {
+            Self::And => (0, 0),
+            Self::Or => (1, 0),
+            Self::Xor => (2, 0),
+            Self::Rotl(n) => (3, n as usize),
+            Self::Shl(n) => (4, n as usize),
+            Self::Shr(n) => (5, n as usize),
+        }
+    }
+
+    fn from_usizes(kind: usize, amount: usize) -> Option<Self> {
+        let amount = u8::try_from(amount).ok().filter(|&n| n < 32)?;
+        match kind {
+            0 => Some(Self::And),
+            1 => Some(Self::Or),
+            2 => Some(Self::Xor),
+            3 => Some(Self::Rotl(amount)),
+            4 => Some(Self::Shl(amount)),
+            5 => Some(Self::Shr(amount)),
+            _ => None,
+        }
+    }
+}
+
+/// A gate which performs a bitwise operation on 32-bit words, by decomposing the inputs into bits
+/// and recombining the bits of the output. This also range-checks the inputs to be at most 32
+/// bits. If the config supports enough wires, it can support several such operations in one gate.
+#[derive(Copy, Clone, Debug)]
+pub struct U32BitwiseGate<F: RichField + Extendable<D>, const D: usize> {
+    pub op: U32BitwiseOp,
+    pub num_ops: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> U32BitwiseGate<F, D> {
+    pub fn new_from_config(config: &CircuitConfig, op: U32BitwiseOp) -> Self {
+        Self {
+            op,
+            num_ops: Self::num_ops(config, op),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn num_ops(config: &CircuitConfig, op: U32BitwiseOp) -> usize {
+        let routed_wires_per_op = op.num_inputs() + 1;
+        let wires_per_op = routed_wires_per_op + 32 * op.num_inputs();
+        (config.num_wires / wires_per_op).min(config.num_routed_wires / routed_wires_per_op)
+    }
+
+    fn routed_wires_per_op(&self) -> usize {
+        self.op.num_inputs() + 1
+    }
+
+    /// The `k`th input of the `i`th operation, for `k` below the number of inputs of the operation.
+    pub fn wire_ith_input_k(&self, i: usize, k: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(k < self.op.num_inputs());
+        self.routed_wires_per_op() * i + k
+    }
+    pub fn wire_ith_output(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        self.routed_wires_per_op() * i + self.op.num_inputs()
+    }
+
+    pub fn wire_ith_input_k_jth_bit(&self, i: usize, k: usize, j: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(k < self.op.num_inputs());
+        debug_assert!(j < 32);
+        self.routed_wires_per_op() * self.num_ops + 32 * (self.op.num_inputs() * i + k) + j
+    }
+}
+
+/// Returns the constraints of a single operation, given its wires. `constant` maps an integer to
+/// the corresponding field element.
+fn eval_bitwise_op<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>>(
+    op: U32BitwiseOp,
+    inputs: &[T],
+    output: T,
+    bits: &[Vec<T>],
+    constant: impl Fn(u64) -> T,
+) -> Vec<T> {
+    let mut constraints = Vec::with_capacity(33 * inputs.len() + 1);
+
+    let one = constant(1);
+    let zero = constant(0);
+    for (&input, input_bits) in inputs.iter().zip(bits) {
+        let mut combined_bits = zero;
+        for (j, &bit) in input_bits.iter().enumerate() {
+            constraints.push(bit * (bit - one));
+            combined_bits = combined_bits + bit * constant(1 << j);
+        }
+        constraints.push(combined_bits - input);
+    }
+
+    let mut computed_output = zero;
+    for j in 0..32 {
+        let (output_bit, position) = match op {
+            U32BitwiseOp::And => (bits[0][j] * bits[1][j], j),
+            U32BitwiseOp::Or => (bits[0][j] + bits[1][j] - bits[0][j] * bits[1][j], j),
+            U32BitwiseOp::Xor => (
+                bits[0][j] + bits[1][j] - constant(2) * bits[0][j] * bits[1][j],
+                j,
+            ),
+            _ => match op.output_position(j) {
+                Some(position) => (bits[0][j], position),
+                None => continue,
+            },
+        };
+        computed_output = computed_output + output_bit * constant(1 << position);
+    }
+    constraints.push(computed_output - output);
+
+    constraints
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for U32BitwiseGate<F, D> {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        let (kind, amount) = self.op.to_usizes();
+        dst.write_usize(kind)?;
+        dst.write_usize(amount)?;
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let kind = src.read_usize()?;
+        let amount = src.read_usize()?;
+        let op = U32BitwiseOp::from_usizes(kind, amount).ok_or(IoError)?;
+        let num_ops = src.read_usize()?;
+        Ok(Self {
+            op,
+            num_ops,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn export_circom_verification_code(&self) -> String {
+        todo!()
+    }
+    fn export_solidity_verification_code(&self) -> String {
+        todo!()
+    }
+
+    fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let num_inputs = self.op.num_inputs();
+            let inputs: Vec<_> = (0..num_inputs)
+                .map(|k| vars.local_wires[self.wire_ith_input_k(i, k)])
+                .collect();
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits: Vec<Vec<_>> = (0..num_inputs)
+                .map(|k| {
+                    (0..32)
+                        .map(|j| vars.local_wires[self.wire_ith_input_k_jth_bit(i, k, j)])
+                        .collect()
+                })
+                .collect();
+
+            constraints.extend(eval_bitwise_op(
+                self.op,
+                &inputs,
+                output,
+                &bits,
+                F::Extension::from_canonical_u64,
+            ));
+        }
+
+        constraints
+    }
+
+    fn eval_unfiltered_base_one(
+        &self,
+        _vars: EvaluationVarsBase<F>,
+        _yield_constr: StridedConstraintConsumer<F>,
+    ) {
+        panic!("use eval_unfiltered_base_packed instead");
+    }
+
+    fn eval_unfiltered_base_batch(&self, vars_base: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        self.eval_unfiltered_base_batch_packed(vars_base)
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let num_inputs = self.op.num_inputs();
+            let bit = |k, j| vars.local_wires[self.wire_ith_input_k_jth_bit(i, k, j)];
+
+            for k in 0..num_inputs {
+                let mut combined_bits = builder.zero_extension();
+                for j in 0..32 {
+                    constraints.push(builder.mul_sub_extension(bit(k, j), bit(k, j), bit(k, j)));
+                    combined_bits = builder.mul_const_add_extension(
+                        F::from_canonical_u64(1 << j),
+                        bit(k, j),
+                        combined_bits,
+                    );
+                }
+                let input = vars.local_wires[self.wire_ith_input_k(i, k)];
+                constraints.push(builder.sub_extension(combined_bits, input));
+            }
+
+            let mut computed_output = builder.zero_extension();
+            for j in 0..32 {
+                let (output_bit, position) = match self.op {
+                    U32BitwiseOp::And => (builder.mul_extension(bit(0, j), bit(1, j)), j),
+                    U32BitwiseOp::Or => {
+                        let sum = builder.add_extension(bit(0, j), bit(1, j));
+                        let or = builder.arithmetic_extension(
+                            F::NEG_ONE,
+                            F::ONE,
+                            bit(0, j),
+                            bit(1, j),
+                            sum,
+                        );
+                        (or, j)
+                    }
+                    U32BitwiseOp::Xor => {
+                        let sum = builder.add_extension(bit(0, j), bit(1, j));
+                        let xor = builder.arithmetic_extension(
+                            -F::TWO,
+                            F::ONE,
+                            bit(0, j),
+                            bit(1, j),
+                            sum,
+                        );
+                        (xor, j)
+                    }
+                    _ => match self.op.output_position(j) {
+                        Some(position) => (bit(0, j), position),
+                        None => continue,
+                    },
+                };
+                computed_output = builder.mul_const_add_extension(
+                    F::from_canonical_u64(1 << position),
+                    output_bit,
+                    computed_output,
+                );
+            }
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            constraints.push(builder.sub_extension(computed_output, output));
+        }
+
+        constraints
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        (0..self.num_ops)
+            .map(|i| {
+                let g: Box<dyn WitnessGenerator<F>> = Box::new(
+                    U32BitwiseGenerator {
+                        gate: *self,
+                        row,
+                        i,
+                    }
+                    .adapter(),
+                );
+                g
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_ops * (self.routed_wires_per_op() + 32 * self.op.num_inputs())
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    fn degree(&self) -> usize {
+        2
+    }
+
+    // A boolean check for each bit, plus the recombinations of the inputs and of the output.
+    fn num_constraints(&self) -> usize {
+        self.num_ops * (33 * self.op.num_inputs() + 1)
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D>
+    for U32BitwiseGate<F, D>
+{
+    fn eval_unfiltered_base_packed<P: PackedField<Scalar = F>>(
+        &self,
+        vars: EvaluationVarsBasePacked<P>,
+        mut yield_constr: StridedConstraintConsumer<P>,
+    ) {
+        for i in 0..self.num_ops {
+            let num_inputs = self.op.num_inputs();
+            let inputs: Vec<_> = (0..num_inputs)
+                .map(|k| vars.local_wires[self.wire_ith_input_k(i, k)])
+                .collect();
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits: Vec<Vec<_>> = (0..num_inputs)
+                .map(|k| {
+                    (0..32)
+                        .map(|j| vars.local_wires[self.wire_ith_input_k_jth_bit(i, k, j)])
+                        .collect()
+                })
+                .collect();
+
+            yield_constr.many(eval_bitwise_op(self.op, &inputs, output, &bits, |x| {
+                P::from(F::from_canonical_u64(x))
+            }));
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct U32BitwiseGenerator<F: RichField + Extendable<D>, const D: usize> {
+    gate: U32BitwiseGate<F, D>,
+    row: usize,
+    i: usize,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
+    for U32BitwiseGenerator<F, D>
+{
+    fn dependencies(&self) -> Vec<Target> {
+        (0..self.gate.op.num_inputs())
+            .map(|k| Target::wire(self.row, self.gate.wire_ith_input_k(self.i, k)))
+            .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let inputs: Vec<u32> = (0..self.gate.op.num_inputs())
+            .map(|k| {
+                witness
+                    .get_target(Target::wire(
+                        self.row,
+                        self.gate.wire_ith_input_k(self.i, k),
+                    ))
+                    .to_canonical_u64() as u32
+            })
+            .collect();
+
+        for (k, &input) in inputs.iter().enumerate() {
+            for j in 0..32 {
+                out_buffer.set_target(
+                    Target::wire(self.row, self.gate.wire_ith_input_k_jth_bit(self.i, k, j)),
+                    F::from_bool((input >> j) & 1 == 1),
+                );
+            }
+        }
+
+        let output = self
+            .gate
+            .op
+            .apply(inputs[0], inputs.get(1).copied().unwrap_or(0));
+        out_buffer.set_target(
+            Target::wire(self.row, self.gate.wire_ith_output(self.i)),
+            F::from_canonical_u32(output),
+        );
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        self.gate.serialize(dst)?;
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let gate = U32BitwiseGate::deserialize(src)?;
+        let row = src.read_usize()?;
+        let i = src.read_usize()?;
+        Ok(Self { gate, row, i })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::extension::quartic::QuarticExtension;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+    use plonky2::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    const OPS: [U32BitwiseOp; 6] = [
+        U32BitwiseOp::And,
+        U32BitwiseOp::Or,
+        U32BitwiseOp::Xor,
+        U32BitwiseOp::Rotl(7),
+        U32BitwiseOp::Shl(12),
+        U32BitwiseOp::Shr(31),
+    ];
+
+    #[test]
+    fn low_degree() {
+        for op in OPS {
+            test_low_degree::<GoldilocksField, _, 4>(U32BitwiseGate::<GoldilocksField, 4> {
+                op,
+                num_ops: 2,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    #[test]
+    fn eval_fns() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        for op in OPS {
+            test_eval_fns::<F, C, _, D>(U32BitwiseGate::<GoldilocksField, D> {
+                op,
+                num_ops: 2,
+                _phantom: PhantomData,
+            })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_gate_constraint() {
+        type F = GoldilocksField;
+        type FF = QuarticExtension<GoldilocksField>;
+        const D: usize = 4;
+        const NUM_OPS: usize = 2;
+
+        fn get_wires(op: U32BitwiseOp, inputs: &[(u32, u32)]) -> Vec<FF> {
+            let mut v0 = Vec::new();
+            let mut v1 = Vec::new();
+            for &(x, y) in inputs {
+                let words = [x, y];
+                let words = &words[..op.num_inputs()];
+                for &word in words {
+                    v0.push(F::from_canonical_u32(word));
+                    v1.extend((0..32).map(|j| F::from_bool((word >> j) & 1 == 1)));
+                }
+                v0.push(F::from_canonical_u32(op.apply(x, y)));
+            }
+            v0.iter().chain(v1.iter()).map(|&x| x.into()).collect()
+        }
+
+        let mut rng = OsRng;
+        for op in OPS {
+            let inputs: Vec<(u32, u32)> = (0..NUM_OPS).map(|_| (rng.gen(), rng.gen())).collect();
+            let gate = U32BitwiseGate::<F, D> {
+                op,
+                num_ops: NUM_OPS,
+                _phantom: PhantomData,
+            };
+
+            let vars = EvaluationVars {
+                local_constants: &[],
+                local_wires: &get_wires(op, &inputs),
+                public_inputs_hash: &HashOut::rand(),
+            };
+
+            assert!(
+                gate.eval_unfiltered(vars).iter().all(|x| x.is_zero()),
+                "Gate constraints are not satisfied."
+            );
+        }
+    }
+}
@@ -1,5 +1,8 @@
 pub mod add_many_u32;
 pub mod arithmetic_u32;
+pub mod bitwise_u32;
 pub mod comparison;
 pub mod range_check_u32;
+pub mod sha256_ch_maj;
+pub mod sha256_sigma;
 pub mod subtraction_u32;
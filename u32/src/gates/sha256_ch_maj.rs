@@ -0,0 +1,439 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Sub};
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
+use plonky2::gates::gate::Gate;
+use plonky2::gates::packed_util::PackedEvaluableBase;
+use plonky2::gates::util::StridedConstraintConsumer;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+    EvaluationVarsBasePacked,
+};
+use plonky2::util::serialization::{Buffer, IoError, IoResult, Read, Write};
+
+/// One of the two bitwise functions of three words used in the rounds of SHA-256.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Sha256ChMajOp {
+    /// `Ch(x, y, z) = (x & y) ^ (!x & z)`, which chooses bits of `y` or `z` depending on `x`.
+    Ch,
+    /// `Maj(x, y, z) = (x & y) ^ (x & z) ^ (y & z)`, the bitwise majority.
+    Maj,
+}
+
+impl Sha256ChMajOp {
+    const ALL: [Self; 2] = [Self::Ch, Self::Maj];
+
+    /// Applies the function to `x`, `y` and `z`.
+    pub fn apply(self, x: u32, y: u32, z: u32) -> u32 {
+        match self {
+            Self::Ch => (x & y) ^ (!x & z),
+            Self::Maj => (x & y) ^ (x & z) ^ (y & z),
+        }
+    }
+
+    /// Applies the function to bits `x`, `y` and `z`, given as field elements.
+    fn apply_bits<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>>(
+        self,
+        x: T,
+        y: T,
+        z: T,
+        two: T,
+    ) -> T {
+        match self {
+            Self::Ch => z + x * (y - z),
+            Self::Maj => x * y + x * z + y * z - two * x * y * z,
+        }
+    }
+}
+
+/// A gate which computes `Ch` or `Maj` on three 32-bit words, by decomposing them into bits. This
+/// also range-checks the inputs to be at most 32 bits.
+#[derive(Copy, Clone, Debug)]
+pub struct Sha256ChMajGate<F: RichField + Extendable<D>, const D: usize> {
+    pub op: Sha256ChMajOp,
+    pub num_ops: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Sha256ChMajGate<F, D> {
+    pub fn new_from_config(config: &CircuitConfig, op: Sha256ChMajOp) -> Self {
+        Self {
+            op,
+            num_ops: Self::num_ops(config),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn num_ops(config: &CircuitConfig) -> usize {
+        let wires_per_op = 4 + 3 * 32;
+        let routed_wires_per_op = 4;
+        (config.num_wires / wires_per_op).min(config.num_routed_wires / routed_wires_per_op)
+    }
+
+    /// The `k`th input of the `i`th operation, for `k` in `0..3`.
+    pub fn wire_ith_input_k(&self, i: usize, k: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(k < 3);
+        4 * i + k
+    }
+    pub fn wire_ith_output(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        4 * i + 3
+    }
+
+    pub fn wire_ith_input_k_jth_bit(&self, i: usize, k: usize, j: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(k < 3);
+        debug_assert!(j < 32);
+        4 * self.num_ops + 96 * i + 32 * k + j
+    }
+}
+
+/// Returns the constraints of a single operation, given its wires.
+fn eval_ch_maj_op<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>>(
+    op: Sha256ChMajOp,
+    inputs: [T; 3],
+    output: T,
+    bits: [&[T]; 3],
+    one: T,
+    two: T,
+) -> Vec<T> {
+    let mut constraints = Vec::with_capacity(100);
+
+    let mut combined_output = None;
+    for j in (0..32).rev() {
+        let output_bit = op.apply_bits(bits[0][j], bits[1][j], bits[2][j], two);
+        combined_output = Some(combined_output.map_or(output_bit, |acc| acc * two + output_bit));
+    }
+    for k in 0..3 {
+        let mut combined_bits = None;
+        for j in (0..32).rev() {
+            let bit = bits[k][j];
+            constraints.push(bit * (bit - one));
+            combined_bits = Some(combined_bits.map_or(bit, |acc| acc * two + bit));
+        }
+        constraints.push(combined_bits.unwrap() - inputs[k]);
+    }
+    constraints.push(combined_output.unwrap() - output);
+
+    constraints
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for Sha256ChMajGate<F, D> {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.op as usize)?;
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let op = *Sha256ChMajOp::ALL.get(src.read_usize()?).ok_or(IoError)?;
+        let num_ops = src.read_usize()?;
+        Ok(Self {
+            op,
+            num_ops,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn export_circom_verification_code(&self) -> String {
+        todo!()
+    }
+    fn export_solidity_verification_code(&self) -> String {
+        todo!()
+    }
+
+    fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let inputs = [0, 1, 2].map(|k| vars.local_wires[self.wire_ith_input_k(i, k)]);
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits = [0, 1, 2].map(|k| {
+                (0..32)
+                    .map(|j| vars.local_wires[self.wire_ith_input_k_jth_bit(i, k, j)])
+                    .collect::<Vec<_>>()
+            });
+
+            constraints.extend(eval_ch_maj_op(
+                self.op,
+                inputs,
+                output,
+                [&bits[0], &bits[1], &bits[2]],
+                F::Extension::ONE,
+                F::Extension::TWO,
+            ));
+        }
+
+        constraints
+    }
+
+    fn eval_unfiltered_base_one(
+        &self,
+        _vars: EvaluationVarsBase<F>,
+        _yield_constr: StridedConstraintConsumer<F>,
+    ) {
+        panic!("use eval_unfiltered_base_packed instead");
+    }
+
+    fn eval_unfiltered_base_batch(&self, vars_base: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        self.eval_unfiltered_base_batch_packed(vars_base)
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bit = |k, j| vars.local_wires[self.wire_ith_input_k_jth_bit(i, k, j)];
+
+            let mut combined_output = builder.zero_extension();
+            for j in (0..32).rev() {
+                let (x, y, z) = (bit(0, j), bit(1, j), bit(2, j));
+                let output_bit = match self.op {
+                    // z + x (y - z)
+                    Sha256ChMajOp::Ch => {
+                        let y_minus_z = builder.sub_extension(y, z);
+                        builder.mul_add_extension(x, y_minus_z, z)
+                    }
+                    // x y + z (x + y - 2 x y)
+                    Sha256ChMajOp::Maj => {
+                        let x_plus_y = builder.add_extension(x, y);
+                        let x_xor_y = builder.arithmetic_extension(-F::TWO, F::ONE, x, y, x_plus_y);
+                        let z_and_x_xor_y = builder.mul_extension(z, x_xor_y);
+                        builder.mul_add_extension(x, y, z_and_x_xor_y)
+                    }
+                };
+                combined_output =
+                    builder.mul_const_add_extension(F::TWO, combined_output, output_bit);
+            }
+            for k in 0..3 {
+                let mut combined_bits = builder.zero_extension();
+                for j in (0..32).rev() {
+                    constraints.push(builder.mul_sub_extension(bit(k, j), bit(k, j), bit(k, j)));
+                    combined_bits =
+                        builder.mul_const_add_extension(F::TWO, combined_bits, bit(k, j));
+                }
+                let input = vars.local_wires[self.wire_ith_input_k(i, k)];
+                constraints.push(builder.sub_extension(combined_bits, input));
+            }
+            constraints.push(builder.sub_extension(combined_output, output));
+        }
+
+        constraints
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        (0..self.num_ops)
+            .map(|i| {
+                let g: Box<dyn WitnessGenerator<F>> = Box::new(
+                    Sha256ChMajGenerator {
+                        gate: *self,
+                        row,
+                        i,
+                    }
+                    .adapter(),
+                );
+                g
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_ops * (4 + 3 * 32)
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    // Bounded by the `x y z` term of `Maj`.
+    fn degree(&self) -> usize {
+        3
+    }
+
+    // A boolean check for each bit, plus the recombinations of the inputs and of the output.
+    fn num_constraints(&self) -> usize {
+        self.num_ops * (3 * 32 + 4)
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D>
+    for Sha256ChMajGate<F, D>
+{
+    fn eval_unfiltered_base_packed<P: PackedField<Scalar = F>>(
+        &self,
+        vars: EvaluationVarsBasePacked<P>,
+        mut yield_constr: StridedConstraintConsumer<P>,
+    ) {
+        for i in 0..self.num_ops {
+            let inputs = [0, 1, 2].map(|k| vars.local_wires[self.wire_ith_input_k(i, k)]);
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits = [0, 1, 2].map(|k| {
+                (0..32)
+                    .map(|j| vars.local_wires[self.wire_ith_input_k_jth_bit(i, k, j)])
+                    .collect::<Vec<_>>()
+            });
+
+            yield_constr.many(eval_ch_maj_op(
+                self.op,
+                inputs,
+                output,
+                [&bits[0], &bits[1], &bits[2]],
+                P::ONES,
+                P::from(F::TWO),
+            ));
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sha256ChMajGenerator<F: RichField + Extendable<D>, const D: usize> {
+    gate: Sha256ChMajGate<F, D>,
+    row: usize,
+    i: usize,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
+    for Sha256ChMajGenerator<F, D>
+{
+    fn dependencies(&self) -> Vec<Target> {
+        (0..3)
+            .map(|k| Target::wire(self.row, self.gate.wire_ith_input_k(self.i, k)))
+            .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let inputs = [0, 1, 2].map(|k| {
+            witness
+                .get_target(Target::wire(
+                    self.row,
+                    self.gate.wire_ith_input_k(self.i, k),
+                ))
+                .to_canonical_u64() as u32
+        });
+
+        for (k, input) in inputs.into_iter().enumerate() {
+            for j in 0..32 {
+                out_buffer.set_target(
+                    Target::wire(self.row, self.gate.wire_ith_input_k_jth_bit(self.i, k, j)),
+                    F::from_bool((input >> j) & 1 == 1),
+                );
+            }
+        }
+        out_buffer.set_target(
+            Target::wire(self.row, self.gate.wire_ith_output(self.i)),
+            F::from_canonical_u32(self.gate.op.apply(inputs[0], inputs[1], inputs[2])),
+        );
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        self.gate.serialize(dst)?;
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let gate = Sha256ChMajGate::deserialize(src)?;
+        let row = src.read_usize()?;
+        let i = src.read_usize()?;
+        Ok(Self { gate, row, i })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::extension::quartic::QuarticExtension;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+    use plonky2::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn low_degree() {
+        for op in Sha256ChMajOp::ALL {
+            test_low_degree::<GoldilocksField, _, 4>(Sha256ChMajGate::<GoldilocksField, 4> {
+                op,
+                num_ops: 1,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    #[test]
+    fn eval_fns() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        for op in Sha256ChMajOp::ALL {
+            test_eval_fns::<F, C, _, D>(Sha256ChMajGate::<GoldilocksField, D> {
+                op,
+                num_ops: 1,
+                _phantom: PhantomData,
+            })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_gate_constraint() {
+        type F = GoldilocksField;
+        type FF = QuarticExtension<GoldilocksField>;
+        const D: usize = 4;
+
+        fn get_wires(op: Sha256ChMajOp, inputs: [u32; 3]) -> Vec<FF> {
+            let mut v: Vec<_> = inputs.iter().map(|&x| F::from_canonical_u32(x)).collect();
+            v.push(F::from_canonical_u32(
+                op.apply(inputs[0], inputs[1], inputs[2]),
+            ));
+            for input in inputs {
+                v.extend((0..32).map(|j| F::from_bool((input >> j) & 1 == 1)));
+            }
+            v.iter().map(|&x| x.into()).collect()
+        }
+
+        let mut rng = OsRng;
+        for op in Sha256ChMajOp::ALL {
+            let gate = Sha256ChMajGate::<F, D> {
+                op,
+                num_ops: 1,
+                _phantom: PhantomData,
+            };
+
+            let vars = EvaluationVars {
+                local_constants: &[],
+                local_wires: &get_wires(op, [rng.gen(), rng.gen(), rng.gen()]),
+                public_inputs_hash: &HashOut::rand(),
+            };
+
+            assert!(
+                gate.eval_unfiltered(vars).iter().all(|x| x.is_zero()),
+                "Gate constraints are not satisfied."
+            );
+        }
+    }
+}
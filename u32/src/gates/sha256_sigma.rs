@@ -0,0 +1,460 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Sub};
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
+use plonky2::gates::gate::Gate;
+use plonky2::gates::packed_util::PackedEvaluableBase;
+use plonky2::gates::util::StridedConstraintConsumer;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+    EvaluationVarsBasePacked,
+};
+use plonky2::util::serialization::{Buffer, IoError, IoResult, Read, Write};
+
+/// One of the four sigma functions of SHA-256.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Sha256Sigma {
+    /// `Σ0(x) = (x >>> 2) ^ (x >>> 13) ^ (x >>> 22)`, used on `a` in each round.
+    BigSigma0,
+    /// `Σ1(x) = (x >>> 6) ^ (x >>> 11) ^ (x >>> 25)`, used on `e` in each round.
+    BigSigma1,
+    /// `σ0(x) = (x >>> 7) ^ (x >>> 18) ^ (x >> 3)`, used in the message schedule.
+    SmallSigma0,
+    /// `σ1(x) = (x >>> 17) ^ (x >>> 19) ^ (x >> 10)`, used in the message schedule.
+    SmallSigma1,
+}
+
+impl Sha256Sigma {
+    const ALL: [Self; 4] = [
+        Self::BigSigma0,
+        Self::BigSigma1,
+        Self::SmallSigma0,
+        Self::SmallSigma1,
+    ];
+
+    /// The two rotation amounts, and the amount of the third term, which is a rotation for the
+    /// big sigmas and a shift for the small ones.
+    const fn amounts(self) -> (u32, u32, u32) {
+        match self {
+            Self::BigSigma0 => (2, 13, 22),
+            Self::BigSigma1 => (6, 11, 25),
+            Self::SmallSigma0 => (7, 18, 3),
+            Self::SmallSigma1 => (17, 19, 10),
+        }
+    }
+
+    const fn is_shift(self) -> bool {
+        matches!(self, Self::SmallSigma0 | Self::SmallSigma1)
+    }
+
+    /// Applies the function to `x`.
+    pub fn apply(self, x: u32) -> u32 {
+        let (r0, r1, r2) = self.amounts();
+        let third = if self.is_shift() {
+            x >> r2
+        } else {
+            x.rotate_right(r2)
+        };
+        x.rotate_right(r0) ^ x.rotate_right(r1) ^ third
+    }
+
+    /// The indices of the input bits which are XORed together into bit `i` of the output.
+    fn input_bits(self, i: usize) -> Vec<usize> {
+        let (r0, r1, r2) = self.amounts();
+        let mut bits = Vec::with_capacity(3);
+        bits.push((i + r0 as usize) % 32);
+        bits.push((i + r1 as usize) % 32);
+        if !self.is_shift() {
+            bits.push((i + r2 as usize) % 32);
+        } else if i + (r2 as usize) < 32 {
+            bits.push(i + r2 as usize);
+        }
+        bits
+    }
+}
+
+/// A gate which computes one of the sigma functions of SHA-256 on a 32-bit word, by decomposing
+/// it into bits. This also range-checks the input to be at most 32 bits.
+#[derive(Copy, Clone, Debug)]
+pub struct Sha256SigmaGate<F: RichField + Extendable<D>, const D: usize> {
+    pub sigma: Sha256Sigma,
+    pub num_ops: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Sha256SigmaGate<F, D> {
+    pub fn new_from_config(config: &CircuitConfig, sigma: Sha256Sigma) -> Self {
+        Self {
+            sigma,
+            num_ops: Self::num_ops(config),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn num_ops(config: &CircuitConfig) -> usize {
+        let wires_per_op = 2 + 32;
+        let routed_wires_per_op = 2;
+        (config.num_wires / wires_per_op).min(config.num_routed_wires / routed_wires_per_op)
+    }
+
+    pub fn wire_ith_input(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        2 * i
+    }
+    pub fn wire_ith_output(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        2 * i + 1
+    }
+
+    pub fn wire_ith_input_jth_bit(&self, i: usize, j: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(j < 32);
+        2 * self.num_ops + 32 * i + j
+    }
+}
+
+/// Computes `a ^ b` for bits `a` and `b`.
+fn xor<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>>(a: T, b: T, two: T) -> T {
+    a + b - two * a * b
+}
+
+/// Returns the constraints of a single sigma operation, given its wires.
+fn eval_sigma_op<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>>(
+    sigma: Sha256Sigma,
+    input: T,
+    output: T,
+    bits: &[T],
+    one: T,
+    two: T,
+) -> Vec<T> {
+    let mut constraints = Vec::with_capacity(34);
+
+    let mut combined_bits = None;
+    let mut combined_output = None;
+    for i in (0..32).rev() {
+        constraints.push(bits[i] * (bits[i] - one));
+
+        let output_bit = sigma
+            .input_bits(i)
+            .into_iter()
+            .map(|j| bits[j])
+            .reduce(|a, b| xor(a, b, two))
+            .unwrap();
+        combined_bits = Some(combined_bits.map_or(bits[i], |acc| acc * two + bits[i]));
+        combined_output = Some(combined_output.map_or(output_bit, |acc| acc * two + output_bit));
+    }
+    constraints.push(combined_bits.unwrap() - input);
+    constraints.push(combined_output.unwrap() - output);
+
+    constraints
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for Sha256SigmaGate<F, D> {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.sigma as usize)?;
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let sigma = *Sha256Sigma::ALL.get(src.read_usize()?).ok_or(IoError)?;
+        let num_ops = src.read_usize()?;
+        Ok(Self {
+            sigma,
+            num_ops,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn export_circom_verification_code(&self) -> String {
+        todo!()
+    }
+    fn export_solidity_verification_code(&self) -> String {
+        todo!()
+    }
+
+    fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let input = vars.local_wires[self.wire_ith_input(i)];
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits: Vec<_> = (0..32)
+                .map(|j| vars.local_wires[self.wire_ith_input_jth_bit(i, j)])
+                .collect();
+
+            constraints.extend(eval_sigma_op(
+                self.sigma,
+                input,
+                output,
+                &bits,
+                F::Extension::ONE,
+                F::Extension::TWO,
+            ));
+        }
+
+        constraints
+    }
+
+    fn eval_unfiltered_base_one(
+        &self,
+        _vars: EvaluationVarsBase<F>,
+        _yield_constr: StridedConstraintConsumer<F>,
+    ) {
+        panic!("use eval_unfiltered_base_packed instead");
+    }
+
+    fn eval_unfiltered_base_batch(&self, vars_base: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        self.eval_unfiltered_base_batch_packed(vars_base)
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let input = vars.local_wires[self.wire_ith_input(i)];
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits: Vec<_> = (0..32)
+                .map(|j| vars.local_wires[self.wire_ith_input_jth_bit(i, j)])
+                .collect();
+
+            let mut combined_bits = builder.zero_extension();
+            let mut combined_output = builder.zero_extension();
+            for j in (0..32).rev() {
+                constraints.push(builder.mul_sub_extension(bits[j], bits[j], bits[j]));
+
+                let mut output_bit = None;
+                for k in self.sigma.input_bits(j) {
+                    output_bit = Some(match output_bit {
+                        None => bits[k],
+                        Some(acc) => {
+                            let sum = builder.add_extension(acc, bits[k]);
+                            builder.arithmetic_extension(-F::TWO, F::ONE, acc, bits[k], sum)
+                        }
+                    });
+                }
+                combined_bits = builder.mul_const_add_extension(F::TWO, combined_bits, bits[j]);
+                combined_output =
+                    builder.mul_const_add_extension(F::TWO, combined_output, output_bit.unwrap());
+            }
+            constraints.push(builder.sub_extension(combined_bits, input));
+            constraints.push(builder.sub_extension(combined_output, output));
+        }
+
+        constraints
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        (0..self.num_ops)
+            .map(|i| {
+                let g: Box<dyn WitnessGenerator<F>> = Box::new(
+                    Sha256SigmaGenerator {
+                        gate: *self,
+                        row,
+                        i,
+                    }
+                    .adapter(),
+                );
+                g
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_ops * (2 + 32)
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    // Bounded by the XOR of three bits.
+    fn degree(&self) -> usize {
+        3
+    }
+
+    // A boolean check for each bit, plus the recombinations of the input and of the output.
+    fn num_constraints(&self) -> usize {
+        self.num_ops * (32 + 2)
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D>
+    for Sha256SigmaGate<F, D>
+{
+    fn eval_unfiltered_base_packed<P: PackedField<Scalar = F>>(
+        &self,
+        vars: EvaluationVarsBasePacked<P>,
+        mut yield_constr: StridedConstraintConsumer<P>,
+    ) {
+        for i in 0..self.num_ops {
+            let input = vars.local_wires[self.wire_ith_input(i)];
+            let output = vars.local_wires[self.wire_ith_output(i)];
+            let bits: Vec<_> = (0..32)
+                .map(|j| vars.local_wires[self.wire_ith_input_jth_bit(i, j)])
+                .collect();
+
+            yield_constr.many(eval_sigma_op(
+                self.sigma,
+                input,
+                output,
+                &bits,
+                P::ONES,
+                P::from(F::TWO),
+            ));
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sha256SigmaGenerator<F: RichField + Extendable<D>, const D: usize> {
+    gate: Sha256SigmaGate<F, D>,
+    row: usize,
+    i: usize,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
+    for Sha256SigmaGenerator<F, D>
+{
+    fn dependencies(&self) -> Vec<Target> {
+        vec![Target::wire(self.row, self.gate.wire_ith_input(self.i))]
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let input = witness
+            .get_target(Target::wire(self.row, self.gate.wire_ith_input(self.i)))
+            .to_canonical_u64() as u32;
+
+        for j in 0..32 {
+            out_buffer.set_target(
+                Target::wire(self.row, self.gate.wire_ith_input_jth_bit(self.i, j)),
+                F::from_bool((input >> j) & 1 == 1),
+            );
+        }
+        out_buffer.set_target(
+            Target::wire(self.row, self.gate.wire_ith_output(self.i)),
+            F::from_canonical_u32(self.gate.sigma.apply(input)),
+        );
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        self.gate.serialize(dst)?;
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let gate = Sha256SigmaGate::deserialize(src)?;
+        let row = src.read_usize()?;
+        let i = src.read_usize()?;
+        Ok(Self { gate, row, i })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::extension::quartic::QuarticExtension;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+    use plonky2::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn low_degree() {
+        for sigma in Sha256Sigma::ALL {
+            test_low_degree::<GoldilocksField, _, 4>(Sha256SigmaGate::<GoldilocksField, 4> {
+                sigma,
+                num_ops: 3,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    #[test]
+    fn eval_fns() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        for sigma in Sha256Sigma::ALL {
+            test_eval_fns::<F, C, _, D>(Sha256SigmaGate::<GoldilocksField, D> {
+                sigma,
+                num_ops: 3,
+                _phantom: PhantomData,
+            })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_gate_constraint() {
+        type F = GoldilocksField;
+        type FF = QuarticExtension<GoldilocksField>;
+        const D: usize = 4;
+        const NUM_OPS: usize = 3;
+
+        fn get_wires(sigma: Sha256Sigma, inputs: &[u32]) -> Vec<FF> {
+            let mut v0 = Vec::new();
+            let mut v1 = Vec::new();
+            for &input in inputs {
+                v0.push(F::from_canonical_u32(input));
+                v0.push(F::from_canonical_u32(sigma.apply(input)));
+                v1.extend((0..32).map(|j| F::from_bool((input >> j) & 1 == 1)));
+            }
+            v0.iter().chain(v1.iter()).map(|&x| x.into()).collect()
+        }
+
+        let mut rng = OsRng;
+        for sigma in Sha256Sigma::ALL {
+            let inputs: Vec<u32> = (0..NUM_OPS).map(|_| rng.gen()).collect();
+            let gate = Sha256SigmaGate::<F, D> {
+                sigma,
+                num_ops: NUM_OPS,
+                _phantom: PhantomData,
+            };
+
+            let vars = EvaluationVars {
+                local_constants: &[],
+                local_wires: &get_wires(sigma, &inputs),
+                public_inputs_hash: &HashOut::rand(),
+            };
+
+            assert!(
+                gate.eval_unfiltered(vars).iter().all(|x| x.is_zero()),
+                "Gate constraints are not satisfied."
+            );
+        }
+    }
+
+    #[test]
+    fn test_sigma_values() {
+        // The big sigmas are applied to the initial values of `a` and `e`.
+        assert_eq!(Sha256Sigma::BigSigma0.apply(0x6a09e667), 0xce20b47e);
+        assert_eq!(Sha256Sigma::BigSigma1.apply(0x510e527f), 0x3587272b);
+        assert_eq!(Sha256Sigma::SmallSigma0.apply(0x00000001), 0x02004000);
+        assert_eq!(Sha256Sigma::SmallSigma1.apply(0x00000018), 0x000f0000);
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::gadgets::arithmetic::EqualityGenerator;
+use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+use plonky2::gadgets::range_check::LowHighGenerator;
+use plonky2::gadgets::split_base::BaseSumGenerator;
+use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+use plonky2::gates::arithmetic_base::{ArithmeticBaseGenerator, ArithmeticGate};
+use plonky2::gates::arithmetic_extension::{ArithmeticExtensionGate, ArithmeticExtensionGenerator};
+use plonky2::gates::base_sum::{BaseSplitGenerator, BaseSumGate};
+use plonky2::gates::constant::ConstantGate;
+use plonky2::gates::exponentiation::{ExponentiationGate, ExponentiationGenerator};
+use plonky2::gates::high_degree_interpolation::{
+    HighDegreeInterpolationGate, HighDegreeInterpolationGenerator,
+};
+use plonky2::gates::low_degree_interpolation::{
+    LowDegreeInterpolationGate, LowDegreeInterpolationGenerator,
+};
+use plonky2::gates::multiplication_extension::{MulExtensionGate, MulExtensionGenerator};
+use plonky2::gates::noop::NoopGate;
+use plonky2::gates::poseidon::{PoseidonGate, PoseidonGenerator};
+use plonky2::gates::poseidon_mds::{PoseidonMdsGate, PoseidonMdsGenerator};
+use plonky2::gates::public_input::PublicInputGate;
+use plonky2::gates::random_access::{RandomAccessGate, RandomAccessGenerator};
+use plonky2::gates::reducing::{ReducingGate, ReducingGenerator};
+use plonky2::gates::reducing_extension::{ReducingExtensionGate, ReducingExtensionGenerator};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::generator::{
+    ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+    SimpleGeneratorAdapter,
+};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+use plonky2::util::serialization::{
+    Buffer, GateSerializer, IoResult, Read, WitnessGeneratorSerializer, Write,
+};
+use plonky2::{impl_gate_serializer, impl_generator_serializer};
+
+use crate::gadgets::arithmetic_u32::{SplitToU32Generator, U32Target};
+use crate::gates::add_many_u32::{U32AddManyGate, U32AddManyGenerator};
+use crate::gates::arithmetic_u32::{U32ArithmeticGate, U32ArithmeticGenerator};
+use crate::gates::bitwise_u32::{U32BitwiseGate, U32BitwiseGenerator};
+use crate::gates::comparison::{ComparisonGate, ComparisonGenerator};
+use crate::gates::range_check_u32::{U32RangeCheckGate, U32RangeCheckGenerator};
+use crate::gates::sha256_ch_maj::{Sha256ChMajGate, Sha256ChMajGenerator};
+use crate::gates::sha256_sigma::{Sha256SigmaGate, Sha256SigmaGenerator};
+use crate::gates::subtraction_u32::{U32SubtractionGate, U32SubtractionGenerator};
+
+pub trait WriteU32 {
+    fn write_target_u32(&mut self, x: U32Target) -> IoResult<()>;
+}
+
+impl WriteU32 for Vec<u8> {
+    #[inline]
+    fn write_target_u32(&mut self, x: U32Target) -> IoResult<()> {
+        self.write_target(x.0)
+    }
+}
+
+pub trait ReadU32 {
+    fn read_target_u32(&mut self) -> IoResult<U32Target>;
+}
+
+impl ReadU32 for Buffer {
+    #[inline]
+    fn read_target_u32(&mut self) -> IoResult<U32Target> {
+        Ok(U32Target(self.read_target()?))
+    }
+}
+
+/// A [`GateSerializer`] supporting the gates of `plonky2` and of this crate.
+pub struct U32GateSerializer;
+
+impl<F: RichField + Extendable<D>, const D: usize> GateSerializer<F, D> for U32GateSerializer {
+    impl_gate_serializer! {
+        ArithmeticGate,
+        ArithmeticExtensionGate<D>,
+        BaseSumGate<2>,
+        BaseSumGate<4>,
+        ComparisonGate<F, D>,
+        ConstantGate,
+        ExponentiationGate<F, D>,
+        HighDegreeInterpolationGate<F, D>,
+        LowDegreeInterpolationGate<F, D>,
+        MulExtensionGate<D>,
+        NoopGate,
+        PoseidonMdsGate<F, D>,
+        PoseidonGate<F, D>,
+        PublicInputGate,
+        RandomAccessGate<F, D>,
+        ReducingExtensionGate<D>,
+        ReducingGate<D>,
+        Sha256ChMajGate<F, D>,
+        Sha256SigmaGate<F, D>,
+        U32AddManyGate<F, D>,
+        U32ArithmeticGate<F, D>,
+        U32BitwiseGate<F, D>,
+        U32RangeCheckGate<F, D>,
+        U32SubtractionGate<F, D>
+    }
+}
+
+/// A [`WitnessGeneratorSerializer`] supporting the generators of `plonky2` and of this crate.
+#[derive(Debug)]
+pub struct U32GeneratorSerializer<C: GenericConfig<D>, const D: usize> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C: GenericConfig<D>, const D: usize> Default for U32GeneratorSerializer<C, D> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, C, const D: usize> WitnessGeneratorSerializer<F, D> for U32GeneratorSerializer<C, D>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F> + 'static,
+    C::Hasher: AlgebraicHasher<F>,
+{
+    impl_generator_serializer! {
+        SimpleGeneratorAdapter<F, ArithmeticBaseGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, ArithmeticExtensionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, BaseSplitGenerator<2>>,
+        SimpleGeneratorAdapter<F, BaseSplitGenerator<4>>,
+        SimpleGeneratorAdapter<F, BaseSumGenerator<2>>,
+        SimpleGeneratorAdapter<F, ComparisonGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, ConstantGenerator<F>>,
+        SimpleGeneratorAdapter<F, CopyGenerator>,
+        SimpleGeneratorAdapter<F, DummyProofGenerator<F, C, D>>,
+        SimpleGeneratorAdapter<F, EqualityGenerator>,
+        SimpleGeneratorAdapter<F, ExponentiationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, HighDegreeInterpolationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, LowDegreeInterpolationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, LowHighGenerator>,
+        SimpleGeneratorAdapter<F, MulExtensionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, NonzeroTestGenerator>,
+        SimpleGeneratorAdapter<F, PoseidonGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, PoseidonMdsGenerator<D>>,
+        SimpleGeneratorAdapter<F, QuotientGeneratorExtension<D>>,
+        SimpleGeneratorAdapter<F, RandomAccessGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, RandomValueGenerator>,
+        SimpleGeneratorAdapter<F, ReducingGenerator<D>>,
+        SimpleGeneratorAdapter<F, ReducingExtensionGenerator<D>>,
+        SimpleGeneratorAdapter<F, Sha256ChMajGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, Sha256SigmaGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, SplitGenerator>,
+        SimpleGeneratorAdapter<F, SplitToU32Generator<F, D>>,
+        SimpleGeneratorAdapter<F, U32AddManyGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32ArithmeticGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32BitwiseGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32RangeCheckGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, U32SubtractionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, WireSplitGenerator>
+    }
+}
@@ -8,6 +8,7 @@ use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 
 use crate::bimap::bimap_from_lists;
 use crate::gates::switch::SwitchGate;
@@ -333,7 +334,7 @@ fn route<F: Field>(
 }
 
 #[derive(Debug)]
-struct PermutationGenerator<F: Field> {
+pub struct PermutationGenerator<F: Field> {
     a: Vec<Vec<Target>>,
     b: Vec<Vec<Target>>,
     a_switches: Vec<Target>,
@@ -366,6 +367,39 @@ impl<F: Field> SimpleGenerator<F> for PermutationGenerator<F> {
             out_buffer,
         );
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.a.len())?;
+        for a in &self.a {
+            dst.write_target_vec(a)?;
+        }
+        dst.write_usize(self.b.len())?;
+        for b in &self.b {
+            dst.write_target_vec(b)?;
+        }
+        dst.write_target_vec(&self.a_switches)?;
+        dst.write_target_vec(&self.b_switches)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let a_len = src.read_usize()?;
+        let a = (0..a_len)
+            .map(|_| src.read_target_vec())
+            .collect::<IoResult<Vec<_>>>()?;
+        let b_len = src.read_usize()?;
+        let b = (0..b_len)
+            .map(|_| src.read_target_vec())
+            .collect::<IoResult<Vec<_>>>()?;
+        let a_switches = src.read_target_vec()?;
+        let b_switches = src.read_target_vec()?;
+        Ok(Self {
+            a,
+            b,
+            a_switches,
+            b_switches,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,119 @@
+use std::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::gadgets::arithmetic::EqualityGenerator;
+use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+use plonky2::gadgets::range_check::LowHighGenerator;
+use plonky2::gadgets::split_base::BaseSumGenerator;
+use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+use plonky2::gates::arithmetic_base::{ArithmeticBaseGenerator, ArithmeticGate};
+use plonky2::gates::arithmetic_extension::{ArithmeticExtensionGate, ArithmeticExtensionGenerator};
+use plonky2::gates::base_sum::{BaseSplitGenerator, BaseSumGate};
+use plonky2::gates::constant::ConstantGate;
+use plonky2::gates::exponentiation::{ExponentiationGate, ExponentiationGenerator};
+use plonky2::gates::high_degree_interpolation::{
+    HighDegreeInterpolationGate, HighDegreeInterpolationGenerator,
+};
+use plonky2::gates::low_degree_interpolation::{
+    LowDegreeInterpolationGate, LowDegreeInterpolationGenerator,
+};
+use plonky2::gates::multiplication_extension::{MulExtensionGate, MulExtensionGenerator};
+use plonky2::gates::noop::NoopGate;
+use plonky2::gates::poseidon::{PoseidonGate, PoseidonGenerator};
+use plonky2::gates::poseidon_mds::{PoseidonMdsGate, PoseidonMdsGenerator};
+use plonky2::gates::public_input::PublicInputGate;
+use plonky2::gates::random_access::{RandomAccessGate, RandomAccessGenerator};
+use plonky2::gates::reducing::{ReducingGate, ReducingGenerator};
+use plonky2::gates::reducing_extension::{ReducingExtensionGate, ReducingExtensionGenerator};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::generator::{
+    ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+    SimpleGeneratorAdapter,
+};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+use plonky2::util::serialization::{GateSerializer, WitnessGeneratorSerializer};
+use plonky2::{impl_gate_serializer, impl_generator_serializer};
+
+use crate::gates::assert_le::{AssertLessThanGate, AssertLessThanGenerator};
+use crate::gates::switch::{SwitchGate, SwitchGenerator};
+use crate::permutation::PermutationGenerator;
+use crate::sorting::MemoryOpSortGenerator;
+
+/// A [`GateSerializer`] supporting the gates of `plonky2` and of this crate.
+pub struct WaksmanGateSerializer;
+
+impl<F: RichField + Extendable<D>, const D: usize> GateSerializer<F, D> for WaksmanGateSerializer {
+    impl_gate_serializer! {
+        ArithmeticGate,
+        ArithmeticExtensionGate<D>,
+        AssertLessThanGate<F, D>,
+        BaseSumGate<2>,
+        BaseSumGate<4>,
+        ConstantGate,
+        ExponentiationGate<F, D>,
+        HighDegreeInterpolationGate<F, D>,
+        LowDegreeInterpolationGate<F, D>,
+        MulExtensionGate<D>,
+        NoopGate,
+        PoseidonMdsGate<F, D>,
+        PoseidonGate<F, D>,
+        PublicInputGate,
+        RandomAccessGate<F, D>,
+        ReducingExtensionGate<D>,
+        ReducingGate<D>,
+        SwitchGate<F, D>
+    }
+}
+
+/// A [`WitnessGeneratorSerializer`] supporting the generators of `plonky2` and of this crate.
+#[derive(Debug)]
+pub struct WaksmanGeneratorSerializer<C: GenericConfig<D>, const D: usize> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C: GenericConfig<D>, const D: usize> Default for WaksmanGeneratorSerializer<C, D> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, C, const D: usize> WitnessGeneratorSerializer<F, D> for WaksmanGeneratorSerializer<C, D>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F> + 'static,
+    C::Hasher: AlgebraicHasher<F>,
+{
+    impl_generator_serializer! {
+        SimpleGeneratorAdapter<F, ArithmeticBaseGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, ArithmeticExtensionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, AssertLessThanGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, BaseSplitGenerator<2>>,
+        SimpleGeneratorAdapter<F, BaseSplitGenerator<4>>,
+        SimpleGeneratorAdapter<F, BaseSumGenerator<2>>,
+        SimpleGeneratorAdapter<F, ConstantGenerator<F>>,
+        SimpleGeneratorAdapter<F, CopyGenerator>,
+        SimpleGeneratorAdapter<F, DummyProofGenerator<F, C, D>>,
+        SimpleGeneratorAdapter<F, EqualityGenerator>,
+        SimpleGeneratorAdapter<F, ExponentiationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, HighDegreeInterpolationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, LowDegreeInterpolationGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, LowHighGenerator>,
+        SimpleGeneratorAdapter<F, MemoryOpSortGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, MulExtensionGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, NonzeroTestGenerator>,
+        SimpleGeneratorAdapter<F, PermutationGenerator<F>>,
+        SimpleGeneratorAdapter<F, PoseidonGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, PoseidonMdsGenerator<D>>,
+        SimpleGeneratorAdapter<F, QuotientGeneratorExtension<D>>,
+        SimpleGeneratorAdapter<F, RandomAccessGenerator<F, D>>,
+        SimpleGeneratorAdapter<F, RandomValueGenerator>,
+        SimpleGeneratorAdapter<F, ReducingGenerator<D>>,
+        SimpleGeneratorAdapter<F, ReducingExtensionGenerator<D>>,
+        SimpleGeneratorAdapter<F, SplitGenerator>,
+        SwitchGenerator<F, D>,
+        SimpleGeneratorAdapter<F, WireSplitGenerator>
+    }
+}
@@ -8,6 +8,7 @@ use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use plonky2_util::ceil_div_usize;
 
 use crate::gates::assert_le::AssertLessThanGate;
@@ -28,6 +29,28 @@ pub struct MemoryOpTarget {
     value: Target,
 }
 
+impl MemoryOpTarget {
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_bool(self.is_write)?;
+        dst.write_target(self.address)?;
+        dst.write_target(self.timestamp)?;
+        dst.write_target(self.value)
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let is_write = src.read_target_bool()?;
+        let address = src.read_target()?;
+        let timestamp = src.read_target()?;
+        let value = src.read_target()?;
+        Ok(Self {
+            is_write,
+            address,
+            timestamp,
+            value,
+        })
+    }
+}
+
 pub fn assert_permutation_memory_ops_circuit<F: RichField + Extendable<D>, const D: usize>(
     builder: &mut CircuitBuilder<F, D>,
     a: &[MemoryOpTarget],
@@ -127,7 +150,7 @@ pub fn sort_memory_ops_circuit<F: RichField + Extendable<D>, const D: usize>(
 }
 
 #[derive(Debug)]
-struct MemoryOpSortGenerator<F: RichField + Extendable<D>, const D: usize> {
+pub struct MemoryOpSortGenerator<F: RichField + Extendable<D>, const D: usize> {
     input_ops: Vec<MemoryOpTarget>,
     output_ops: Vec<MemoryOpTarget>,
     _phantom: PhantomData<F>,
@@ -178,6 +201,29 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
             out_buffer.set_target(out_op.value, op.value);
         }
     }
+
+    fn serialize(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_usize(self.input_ops.len())?;
+        for op in self.input_ops.iter().chain(&self.output_ops) {
+            op.serialize(dst)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(src: &mut Buffer) -> IoResult<Self> {
+        let num_ops = src.read_usize()?;
+        let input_ops = (0..num_ops)
+            .map(|_| MemoryOpTarget::deserialize(src))
+            .collect::<IoResult<Vec<_>>>()?;
+        let output_ops = (0..num_ops)
+            .map(|_| MemoryOpTarget::deserialize(src))
+            .collect::<IoResult<Vec<_>>>()?;
+        Ok(Self {
+            input_ops,
+            output_ops,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 #[cfg(test)]